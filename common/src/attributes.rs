@@ -1,24 +1,57 @@
 use std::collections::HashMap;
 use syn::{Lit, Meta, MetaNameValue, NestedMeta};
 
+use crate::rv_trace::IoCodec;
+
 use crate::constants::{
-    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+    DEFAULT_MAX_BYTECODE_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_MEMORY_ADDRESS,
+    DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
 };
 
 pub struct Attributes {
     pub wasm: bool,
+    /// Pass all arguments as private inputs, and only their digest as public input.
+    pub commit_inputs: bool,
+    /// Allow misaligned word loads and stores, at a cost of extra cycles per access.
+    pub misaligned_accesses: bool,
+    /// Build the guest with the C extension (compressed instructions).
+    pub compressed_instructions: bool,
+    /// Hash every message of advice the guest reads into the nondeterminism digest.
+    pub nondeterminism_digest: bool,
     pub memory_size: u64,
     pub stack_size: u64,
+    /// If unset, the heap takes up the rest of the guest's memory.
+    pub heap_size: Option<u64>,
     pub max_input_size: u64,
     pub max_output_size: u64,
+    /// Size of the advice tape; the guest has none if it is zero.
+    pub max_advice_size: u64,
+    pub max_private_input_size: u64,
+    pub max_bytecode_size: u64,
+    pub max_memory_address: u64,
+    pub max_trace_length: u64,
+    /// Serialization format of the inputs and outputs.
+    pub io_codec: IoCodec,
 }
 
 pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let mut attributes = HashMap::<_, u64>::new();
     let mut wasm = false;
+    let mut commit_inputs = false;
+    let mut misaligned_accesses = false;
+    let mut compressed_instructions = false;
+    let mut nondeterminism_digest = false;
+    let mut io_codec = IoCodec::default();
 
     for attr in attr {
         match attr {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.is_ident("codec") => {
+                io_codec = lit.value().parse().unwrap_or_else(|e| panic!("{}", e));
+            }
             NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
                 let value: u64 = match lit {
                     Lit::Int(lit) => lit.base10_parse().unwrap(),
@@ -28,14 +61,32 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
                 match ident.to_string().as_str() {
                     "memory_size" => attributes.insert("memory_size", value),
                     "stack_size" => attributes.insert("stack_size", value),
+                    "heap_size" => attributes.insert("heap_size", value),
                     "max_input_size" => attributes.insert("max_input_size", value),
                     "max_output_size" => attributes.insert("max_output_size", value),
+                    "max_advice_size" => attributes.insert("max_advice_size", value),
+                    "max_private_input_size" => attributes.insert("max_private_input_size", value),
+                    "max_bytecode_size" => attributes.insert("max_bytecode_size", value),
+                    "max_memory_address" => attributes.insert("max_memory_address", value),
+                    "max_trace_length" => attributes.insert("max_trace_length", value),
                     _ => panic!("invalid attribute"),
                 };
             }
             NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wasm") => {
                 wasm = true;
             }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("commit_inputs") => {
+                commit_inputs = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("misaligned_accesses") => {
+                misaligned_accesses = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("compressed_instructions") => {
+                compressed_instructions = true;
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("nondeterminism_digest") => {
+                nondeterminism_digest = true;
+            }
             _ => panic!("expected integer literal"),
         }
     }
@@ -44,18 +95,43 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
         .get("memory_size")
         .unwrap_or(&DEFAULT_MEMORY_SIZE);
     let stack_size = *attributes.get("stack_size").unwrap_or(&DEFAULT_STACK_SIZE);
+    let heap_size = attributes.get("heap_size").copied();
     let max_input_size = *attributes
         .get("max_input_size")
         .unwrap_or(&DEFAULT_MAX_INPUT_SIZE);
     let max_output_size = *attributes
         .get("max_output_size")
         .unwrap_or(&DEFAULT_MAX_OUTPUT_SIZE);
+    let max_advice_size = *attributes.get("max_advice_size").unwrap_or(&0);
+    let max_private_input_size = *attributes
+        .get("max_private_input_size")
+        .unwrap_or(&DEFAULT_MAX_INPUT_SIZE);
+    let max_bytecode_size = *attributes
+        .get("max_bytecode_size")
+        .unwrap_or(&DEFAULT_MAX_BYTECODE_SIZE);
+    let max_memory_address = *attributes
+        .get("max_memory_address")
+        .unwrap_or(&DEFAULT_MAX_MEMORY_ADDRESS);
+    let max_trace_length = *attributes
+        .get("max_trace_length")
+        .unwrap_or(&DEFAULT_MAX_TRACE_LENGTH);
 
     Attributes {
         wasm,
+        commit_inputs,
+        misaligned_accesses,
+        compressed_instructions,
+        nondeterminism_digest,
         memory_size,
         stack_size,
+        heap_size,
         max_input_size,
         max_output_size,
+        max_advice_size,
+        max_private_input_size,
+        max_bytecode_size,
+        max_memory_address,
+        max_trace_length,
+        io_codec,
     }
 }
@@ -3,22 +3,104 @@ const RISCV_REGISTER_COUNT: u64 = 32;
 const VIRTUAL_REGISTER_COUNT: u64 = 32; //  see Section 6.1 of Jolt paper
 pub const REGISTER_COUNT: u64 = RISCV_REGISTER_COUNT + VIRTUAL_REGISTER_COUNT;
 pub const BYTES_PER_INSTRUCTION: usize = 4;
+/// Instructions are 2-byte aligned, as compressed (RVC) instructions are 2 bytes long.
+pub const INSTRUCTION_ALIGNMENT: usize = 2;
 /// 3 registers (rd, rs1, rs2) + 1 RAM
 pub const MEMORY_OPS_PER_INSTRUCTION: usize = 4;
 
 pub const RAM_START_ADDRESS: u64 = 0x80000000;
 pub const DEFAULT_MEMORY_SIZE: u64 = 10 * 1024 * 1024;
 pub const DEFAULT_STACK_SIZE: u64 = 4096;
+/// Size in bytes of the guard regions the linker script places below the guest's stack
+/// and above its heap. The guest never touches them, so the tracer reports an access to
+/// one as a stack overflow or heap exhaustion rather than letting the stack run into
+/// the program's static data.
+pub const GUARD_REGION_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_INPUT_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_OUTPUT_SIZE: u64 = 4096;
+/// Default bounds used by `#[jolt::provable]` to size the commitment scheme's setup.
+pub const DEFAULT_MAX_BYTECODE_SIZE: u64 = 1 << 20;
+pub const DEFAULT_MAX_MEMORY_ADDRESS: u64 = 1 << 20;
+pub const DEFAULT_MAX_TRACE_LENGTH: u64 = 1 << 24;
+/// Size in bytes of the (Keccak-256) inputs digest exposed to the guest.
+pub const INPUTS_DIGEST_SIZE: u64 = 32;
+/// Size in bytes of the log buffer the guest writes `jolt::println!` output to.
+pub const MAX_LOG_SIZE: u64 = 1024;
+/// Size in bytes of the (Keccak-256) digest of the input stream, which the guest
+/// writes when it terminates.
+pub const STREAM_DIGEST_SIZE: u64 = 32;
+/// Size in bytes of the (Keccak-256) hash chain over the journal entries, which the
+/// guest writes when it terminates.
+pub const JOURNAL_DIGEST_SIZE: u64 = 32;
+/// Size in bytes of the (Keccak-256) hash chain over the advice read by a guest with
+/// `#[jolt::provable(nondeterminism_digest)]`, which the guest writes when it terminates.
+pub const NONDETERMINISM_DIGEST_SIZE: u64 = 32;
+
+/// `add x0, a0, a1`, executed by `jolt::start_cycle_tracker!`: marks the start of a
+/// profiling region labeled by the `a1`-byte string at address `a0`. The instruction
+/// writes x0, so it has no effect on the guest; the tracer records it.
+pub const CYCLE_TRACKER_START: u32 = 0x00b5_0033;
+/// `sub x0, a0, a1`, executed by `jolt::end_cycle_tracker!`: marks the end of a
+/// profiling region. See `CYCLE_TRACKER_START`.
+pub const CYCLE_TRACKER_END: u32 = 0x40b5_0033;
+
+/// `.insn i CUSTOM_0, 0, rd, x0, 0`: reads the next word of advice (the guest's
+/// private inputs, then the responses to its hint requests) into `rd`. The value is
+/// supplied by the prover, so it is not part of the public inputs (and is not
+/// constrained in any way).
+pub const ADVICE_INSTRUCTION: u32 = 0x0000_000b;
+/// `.insn r CUSTOM_0, 1, 0, rd, rs1, x0`, executed by `jolt::relu`: writes
+/// `max(rs1, 0)` (as a signed word) to `rd`, with a single lookup.
+pub const RELU_INSTRUCTION: u32 = 0x0000_100b;
+/// `.insn r CUSTOM_0, 2, 0, rd, rs1, rs2`, executed by `jolt::argmax`: writes the index
+/// (0 or 1) of the larger of the signed words `rs1` and `rs2` to `rd`, with a single
+/// lookup. Ties select `rs1`.
+pub const ARGMAX_INSTRUCTION: u32 = 0x0000_200b;
+/// `.insn r CUSTOM_0, 3, 0, rd, rs1, rs2`, executed by `jolt::DecisionTree::predict`:
+/// takes one step down a decision tree. `rs1` is the address of the current node, two
+/// words: its split (a signed halfword threshold in the upper half, and the index of
+/// the feature it tests in the lower half) and the offset in bytes from the node to its
+/// left child. `rs2` is the address of the features, as signed words. Writes the address
+/// of the left child to `rd` if the feature is at most the threshold, and that of the
+/// right child (the next node after the left child) otherwise.
+pub const TREE_STEP_INSTRUCTION: u32 = 0x0000_300b;
+/// `.insn r CUSTOM_0, 4, 0, rd, rs1, rs2`, executed by `jolt::fixed_mul`: writes the
+/// product of the signed Q16.16 values `rs1` and `rs2`, rounded to the nearest Q16.16
+/// value (halves round up), to `rd`. The result wraps around on overflow, like MUL.
+pub const FIXED_MUL_INSTRUCTION: u32 = 0x0000_400b;
+/// `.insn r CUSTOM_0, 5, 0, rd, rs1, rs2`, executed by `jolt::saturating_add`: writes
+/// the sum of the signed words `rs1` and `rs2`, clamped to `[i32::MIN, i32::MAX]`, to
+/// `rd`.
+pub const SATURATING_ADD_INSTRUCTION: u32 = 0x0000_500b;
+/// `.insn r CUSTOM_0, 6, 0, rd, rs1, x0`, executed by `jolt::exp`: writes `e^x` to `rd`,
+/// where `x` is the lower half of `rs1` as a signed Q8.8 value, as an unsigned Q16.16
+/// value (see `fixed_point::exp`), with a single lookup.
+pub const EXP_INSTRUCTION: u32 = 0x0000_600b;
+/// `.insn r CUSTOM_0, 7, 0, rd, rs1, x0`, executed by `jolt::sigmoid`: writes the
+/// logistic function `1 / (1 + e^-x)` to `rd`, where `x` is the lower half of `rs1` as a
+/// signed Q8.8 value, as a Q16.16 value (see `fixed_point::sigmoid`), with a single
+/// lookup.
+pub const SIGMOID_INSTRUCTION: u32 = 0x0000_700b;
+/// `and x0, a0, a1`, executed by `jolt::hint_with`: requests a hint for the
+/// `a1`-byte request at address `a0`. Like `CYCLE_TRACKER_START`, it has no effect
+/// on the guest; the tracer passes the request to the host's hint provider and
+/// queues the response as advice.
+pub const HINT_REQUEST: u32 = 0x00b5_7033;
+/// `or x0, a0, x0`, executed by `jolt::read_chunk`: requests the next (up to) `a0`
+/// bytes of the input stream. The tracer queues them as advice, and records them
+/// in the program I/O.
+pub const INPUT_STREAM_REQUEST: u32 = 0x0005_6033;
+/// `or x0, a0, a1`, executed by `jolt::journal`: appends the `a1`-byte entry at
+/// address `a0` to the journal. The tracer records the entry in the program I/O.
+pub const JOURNAL_APPEND: u32 = 0x00b5_6033;
 
 pub const fn virtual_register_index(index: u64) -> u64 {
     index + VIRTUAL_REGISTER_COUNT
 }
 
 // Layout of the witness (where || denotes concatenation):
-//     registers || virtual registers || inputs || outputs || panic || termination || padding || RAM
+//     registers || virtual registers || inputs || inputs digest || outputs || logs || stream digest || journal digest || panic || termination || padding || RAM
 // Layout of VM memory:
-//     peripheral devices || inputs || outputs || panic || termination || padding || RAM
+//     peripheral devices || inputs || inputs digest || outputs || logs || stream digest || journal digest || panic || termination || padding || RAM
 // Notably, we want to be able to map the VM memory address space to witness indices
 // using a constant shift, namely (RAM_WITNESS_OFFSET + RAM_START_ADDRESS)
@@ -0,0 +1,146 @@
+//! Fixed-point activation functions (`exp` and `sigmoid`) shared by the tracer, which executes the
+//! corresponding custom instructions, and the prover and verifier, which materialize
+//! them as subtables. Both sides must agree on every bit of every entry, so these use
+//! integer arithmetic only (a platform's `f64::exp` is not guaranteed to be correctly
+//! rounded).
+
+/// `e^(2^k / 256)` for `k` in `0..12`, as Q64.64 values
+const EXP_POW2: [u128; 12] = [
+    0x1_0100_802a_b557_77d3,
+    0x1_0202_0156_0044_5b0c,
+    0x1_0408_0ab5_5de3_917b,
+    0x1_0820_5601_127e_c98e,
+    0x1_1082_b577_d34e_d7d6,
+    0x1_2216_045b_6f5c_cf9d,
+    0x1_48b5_e3c3_e818_6676,
+    0x1_a612_98e1_e069_bc97,
+    0x2_b7e1_5162_8aed_2a6b,
+    0x7_6399_2e35_376b_730d,
+    0x36_9920_5c4e_74b0_cf1b,
+    0xba4_f53e_a386_36f8_5f00,
+];
+
+/// `e^(-2^k / 256)` for `k` in `0..12`, as Q64.64 values
+const EXP_NEG_POW2: [u128; 12] = [
+    0xff00_7fd5_5ffd_de39,
+    0xfe01_feab_5511_27cc,
+    0xfc07_f55f_f77d_2494,
+    0xf81f_ab54_45ae_bc8a,
+    0xf07d_5fde_3815_1e73,
+    0xe1eb_5127_6c11_0c3c,
+    0xc75f_7cf5_6410_5743,
+    0x9b45_97e3_7cb0_4ff4,
+    0x5e2d_58d8_b3bc_df1b,
+    0x22a5_5547_7f03_9740,
+    0x04b0_556e_084f_3d1e,
+    0x0015_fc21_0410_27ad,
+];
+
+/// Inputs at or beyond ±16.0 saturate
+const EXP_INPUT_LIMIT: i32 = 16 << 8;
+
+/// Multiplies two Q64.64 values whose integer parts fit in 32 bits, truncating the
+/// product to Q64.64.
+fn mul_q64(x: u128, y: u128) -> u128 {
+    let (x_hi, x_lo) = (x >> 64, x as u64 as u128);
+    let (y_hi, y_lo) = (y >> 64, y as u64 as u128);
+    ((x_hi * y_hi) << 64) + x_hi * y_lo + x_lo * y_hi + ((x_lo * y_lo) >> 64)
+}
+
+/// `e^(x / 256)` as a Q64.64 value, for `|x| < EXP_INPUT_LIMIT`: the product of the
+/// powers `e^(±2^k / 256)` for the bits `k` set in `|x|`. The truncation error is a
+/// few units of 2^-64, far below the precision of the Q16.16 results built on it.
+fn exp_q64(x: i32) -> u128 {
+    debug_assert!(x.abs() < EXP_INPUT_LIMIT);
+    let powers = if x < 0 { &EXP_NEG_POW2 } else { &EXP_POW2 };
+    let magnitude = x.unsigned_abs();
+    powers
+        .iter()
+        .enumerate()
+        .filter(|(k, _)| (magnitude >> k) & 1 == 1)
+        .fold(1 << 64, |result, (_, power)| mul_q64(result, *power))
+}
+
+/// `e^x` for a Q8.8 value `x`, as an unsigned Q16.16 value rounded to nearest
+/// (within 2^-17 of the exact value, give or take 2^-60). Saturates to `u32::MAX` for
+/// `x` above about 11.09, and rounds to 0 below about -11.78.
+pub fn exp(x: i16) -> u32 {
+    let x = x as i32;
+    if x >= EXP_INPUT_LIMIT {
+        u32::MAX
+    } else if x <= -EXP_INPUT_LIMIT {
+        0
+    } else {
+        let rounded = (exp_q64(x) + (1 << 47)) >> 48;
+        rounded.min(u32::MAX as u128) as u32
+    }
+}
+
+/// The logistic function `1 / (1 + e^-x)` for a Q8.8 value `x`, as a Q16.16 value in
+/// `[0, 1]` rounded to nearest (within 2^-17 of the exact value, give or take 2^-60).
+/// Inputs at or beyond ±16.0 give exactly 1 or 0.
+pub fn sigmoid(x: i16) -> u32 {
+    let x = x as i32;
+    if x >= EXP_INPUT_LIMIT {
+        1 << 16
+    } else if x <= -EXP_INPUT_LIMIT {
+        0
+    } else {
+        // 2^16 / (1 + e^-x), with 1 + e^-x in Q64.64
+        let denominator = (1 << 64) + exp_q64(-x);
+        (((1 << 80) + denominator / 2) / denominator) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The distance from `actual` to `expected`, in units of 2^-16
+    fn error(actual: u32, expected: f64) -> f64 {
+        (actual as f64 - expected * 65536.0).abs()
+    }
+
+    #[test]
+    fn exp_tables() {
+        for k in 0..12 {
+            let x = (1 << k) as f64 / 256.0;
+            let q64 = |v: u128| v as f64 / 2f64.powi(64);
+            assert!((q64(EXP_POW2[k]) / x.exp() - 1.0).abs() < 1e-15);
+            assert!((q64(EXP_NEG_POW2[k]) / (-x).exp() - 1.0).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn exp_accuracy() {
+        assert_eq!(exp(0), 1 << 16);
+        assert_eq!(exp(i16::MAX), u32::MAX);
+        assert_eq!(exp(i16::MIN), 0);
+        for x in i16::MIN..=i16::MAX {
+            let expected = (x as f64 / 256.0).exp();
+            if expected * 65536.0 >= u32::MAX as f64 {
+                assert_eq!(exp(x), u32::MAX, "exp({x})");
+            } else {
+                // Relative to the magnitude of f64's own rounding error
+                let tolerance = 0.5 + expected * 65536.0 * 1e-13;
+                assert!(error(exp(x), expected) <= tolerance, "exp({x})");
+            }
+        }
+    }
+
+    #[test]
+    fn sigmoid_accuracy() {
+        assert_eq!(sigmoid(0), 1 << 15);
+        assert_eq!(sigmoid(i16::MAX), 1 << 16);
+        assert_eq!(sigmoid(i16::MIN), 0);
+        for x in i16::MIN..=i16::MAX {
+            let expected = 1.0 / (1.0 + (-(x as f64) / 256.0).exp());
+            assert!(error(sigmoid(x), expected) <= 0.5 + 1e-9, "sigmoid({x})");
+            // Symmetric about 0, up to rounding
+            if x > i16::MIN {
+                let sum = sigmoid(x) + sigmoid(-x);
+                assert!(sum.abs_diff(1 << 16) <= 1, "sigmoid(±{x})");
+            }
+        }
+    }
+}
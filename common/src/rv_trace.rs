@@ -1,17 +1,45 @@
 use std::str::FromStr;
 
-use crate::constants::{MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use crate::constants::{
+    INPUTS_DIGEST_SIZE, JOURNAL_DIGEST_SIZE, MAX_LOG_SIZE, MEMORY_OPS_PER_INSTRUCTION,
+    NONDETERMINISM_DIGEST_SIZE, RAM_START_ADDRESS, REGISTER_COUNT, STREAM_DIGEST_SIZE,
+};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 use strum::EnumCount;
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter, FromRepr};
 
+/// A cycle-tracker marker executed by the guest (see `CYCLE_TRACKER_START`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CycleMarker {
+    pub label: String,
+    /// Whether the marker starts (rather than ends) the region.
+    pub start: bool,
+    /// Index of the marker instruction in the trace.
+    pub cycle: usize,
+}
+
+/// A function in the guest ELF's symbol table.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FunctionSymbol {
+    /// Demangled name of the function.
+    pub name: String,
+    pub address: u64,
+    /// Size in bytes of the function's code; 0 if unknown.
+    pub size: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RVTraceRow {
     pub instruction: ELFInstruction,
     pub register_state: RegisterState,
     pub memory_state: Option<MemoryState>,
     pub advice_value: Option<u64>,
+    /// The words read by an instruction that reads more memory than `memory_state`
+    /// records, in order (e.g. the split, child offset and feature read by `TREE_STEP`).
     pub precompile_input: Option<[u32; 16]>,
     pub precompile_output_address: Option<u64>,
 }
@@ -104,9 +132,10 @@ impl From<&RVTraceRow> for [MemoryOp; MEMORY_OPS_PER_INSTRUCTION] {
             | RV32IM::DIV
             | RV32IM::DIVU
             | RV32IM::REM
-            | RV32IM::REMU => [rs1_read(), rs2_read(), rd_write(), MemoryOp::noop_read()],
+            | RV32IM::REMU
+            | RV32IM::ARGMAX => [rs1_read(), rs2_read(), rd_write(), MemoryOp::noop_read()],
 
-            RV32IM::LUI | RV32IM::AUIPC | RV32IM::VIRTUAL_ADVICE => [
+            RV32IM::LUI | RV32IM::AUIPC | RV32IM::VIRTUAL_ADVICE | RV32IM::RDCYCLE => [
                 MemoryOp::noop_read(),
                 MemoryOp::noop_read(),
                 rd_write(),
@@ -131,7 +160,10 @@ impl From<&RVTraceRow> for [MemoryOp; MEMORY_OPS_PER_INSTRUCTION] {
             | RV32IM::SLTIU
             | RV32IM::JALR
             | RV32IM::VIRTUAL_MOVE
-            | RV32IM::VIRTUAL_MOVSIGN => [
+            | RV32IM::VIRTUAL_MOVSIGN
+            | RV32IM::RELU
+            | RV32IM::EXP
+            | RV32IM::SIGMOID => [
                 rs1_read(),
                 MemoryOp::noop_read(),
                 rd_write(),
@@ -209,6 +241,10 @@ pub struct ELFInstruction {
     /// `virtual_sequence_remaining` will be Some(0); if this is the penultimate instruction
     /// in the sequence, `virtual_sequence_remaining` will be Some(1); etc.
     pub virtual_sequence_remaining: Option<usize>,
+    /// Whether this instruction was expanded from a 16-bit compressed (RVC) instruction,
+    /// in which case the next instruction is 2 bytes after it rather than 4. The
+    /// instructions of a virtual sequence inherit this from the instruction they replace.
+    pub compressed: bool,
 }
 
 /// Boolean flags used in Jolt's R1CS constraints (`opflags` in the Jolt paper).
@@ -241,6 +277,12 @@ pub enum CircuitFlags {
     Assert,
     /// Used in virtual sequences; the program counter should be the same for the full sequence.
     DoNotUpdatePC,
+    /// 1 if the instruction is 2 bytes long (i.e. a compressed instruction), and updates
+    /// the program counter; the PC advances by 2 rather than 4, and jumps link `PC + 2`.
+    Compressed,
+    /// 1 if the instruction reads the cycle counter (i.e. `rdcycle`), whose value is the
+    /// index of the step in the trace.
+    CycleCounter,
 }
 pub const NUM_CIRCUIT_FLAGS: usize = CircuitFlags::COUNT;
 
@@ -333,6 +375,7 @@ impl ELFInstruction {
             | RV32IM::SLTU
             | RV32IM::SLTI
             | RV32IM::SLTIU
+            | RV32IM::ARGMAX
             | RV32IM::BEQ
             | RV32IM::BNE
             | RV32IM::BLT
@@ -365,6 +408,15 @@ impl ELFInstruction {
             None => false
         };
 
+        // Only the instruction that updates the PC advances it by the instruction's length
+        flags[CircuitFlags::Compressed as usize] =
+            self.compressed && !flags[CircuitFlags::DoNotUpdatePC as usize];
+
+        flags[CircuitFlags::CycleCounter as usize] = matches!(
+            self.opcode,
+            RV32IM::RDCYCLE,
+        );
+
         flags
     }
 }
@@ -454,6 +506,34 @@ pub enum RV32IM {
     REMU,
     FENCE,
     UNIMPL,
+    // RV32A instructions (LR.W is traced as LW)
+    SC_W,
+    AMOSWAP_W,
+    AMOADD_W,
+    AMOXOR_W,
+    AMOAND_W,
+    AMOOR_W,
+    AMOMIN_W,
+    AMOMAX_W,
+    AMOMINU_W,
+    AMOMAXU_W,
+    // LW and SW of guests that opt into misaligned accesses (see `RV32IM::misaligned`)
+    LW_MISALIGNED,
+    SW_MISALIGNED,
+    // Zicsr reads of the `cycle` and `instret` counters (i.e. `rdcycle` and `rdinstret`),
+    // which both count the steps of the trace before the instruction
+    RDCYCLE,
+    // Custom instructions for ML guests (see `RELU_INSTRUCTION`, `ARGMAX_INSTRUCTION`,
+    // `TREE_STEP_INSTRUCTION`, `EXP_INSTRUCTION` and `SIGMOID_INSTRUCTION`)
+    RELU,
+    ARGMAX,
+    TREE_STEP,
+    EXP,
+    SIGMOID,
+    // Custom Q16.16 fixed-point instructions (see `FIXED_MUL_INSTRUCTION` and
+    // `SATURATING_ADD_INSTRUCTION`)
+    FIXED_MUL,
+    SATURATING_ADD,
     // Virtual instructions
     VIRTUAL_MOVSIGN,
     VIRTUAL_MOVE,
@@ -466,6 +546,49 @@ pub enum RV32IM {
     VIRTUAL_ASSERT_HALFWORD_ALIGNMENT,
 }
 
+impl RV32IM {
+    /// The opcode this instruction is traced as if the guest allows misaligned word
+    /// accesses: LW and SW become `LW_MISALIGNED` and `SW_MISALIGNED`, which Jolt
+    /// proves with longer virtual sequences that also handle misaligned addresses.
+    pub fn misaligned(self) -> Self {
+        match self {
+            Self::LW => Self::LW_MISALIGNED,
+            Self::SW => Self::SW_MISALIGNED,
+            opcode => opcode,
+        }
+    }
+
+    /// The number of steps of the trace an instruction with this opcode is proven in,
+    /// i.e. the length of its virtual sequence if it has one. The counters read by
+    /// `RDCYCLE` are sums of these.
+    pub const fn virtual_sequence_length(self) -> usize {
+        match self {
+            Self::MULH => 7,
+            Self::MULHSU => 4,
+            Self::DIV => 8,
+            Self::DIVU => 9,
+            Self::REM => 7,
+            Self::REMU => 8,
+            Self::SH => 12,
+            Self::SB => 11,
+            Self::LBU => 7,
+            Self::LHU => 8,
+            Self::LB => 7,
+            Self::LH => 8,
+            Self::SC_W => 2,
+            Self::AMOSWAP_W => 3,
+            Self::AMOADD_W | Self::AMOXOR_W | Self::AMOAND_W | Self::AMOOR_W => 4,
+            Self::AMOMIN_W | Self::AMOMAX_W | Self::AMOMINU_W | Self::AMOMAXU_W => 8,
+            Self::LW_MISALIGNED => 11,
+            Self::SW_MISALIGNED => 21,
+            Self::TREE_STEP => 11,
+            Self::FIXED_MUL => 14,
+            Self::SATURATING_ADD => 10,
+            _ => 1,
+        }
+    }
+}
+
 impl FromStr for RV32IM {
     type Err = String;
 
@@ -521,6 +644,24 @@ impl FromStr for RV32IM {
             "REMU" => Ok(Self::REMU),
             "FENCE" => Ok(Self::FENCE),
             "UNIMPL" => Ok(Self::UNIMPL),
+            "SC.W" => Ok(Self::SC_W),
+            "AMOSWAP.W" => Ok(Self::AMOSWAP_W),
+            "AMOADD.W" => Ok(Self::AMOADD_W),
+            "AMOXOR.W" => Ok(Self::AMOXOR_W),
+            "AMOAND.W" => Ok(Self::AMOAND_W),
+            "AMOOR.W" => Ok(Self::AMOOR_W),
+            "AMOMIN.W" => Ok(Self::AMOMIN_W),
+            "AMOMAX.W" => Ok(Self::AMOMAX_W),
+            "AMOMINU.W" => Ok(Self::AMOMINU_W),
+            "AMOMAXU.W" => Ok(Self::AMOMAXU_W),
+            "RDCYCLE" => Ok(Self::RDCYCLE),
+            "RELU" => Ok(Self::RELU),
+            "ARGMAX" => Ok(Self::ARGMAX),
+            "TREE_STEP" => Ok(Self::TREE_STEP),
+            "EXP" => Ok(Self::EXP),
+            "SIGMOID" => Ok(Self::SIGMOID),
+            "FIXED_MUL" => Ok(Self::FIXED_MUL),
+            "SATURATING_ADD" => Ok(Self::SATURATING_ADD),
             _ => Err("Could not match instruction to RV32IM set.".to_string()),
         }
     }
@@ -537,6 +678,31 @@ impl FromStr for RV32IM {
 pub struct JoltDevice {
     pub inputs: Vec<u8>,
     pub outputs: Vec<u8>,
+    /// Bytes written by the guest to the log region (e.g. via `jolt::println!`).
+    /// Like the outputs, these are part of the public inputs to the proof.
+    pub logs: Vec<u8>,
+    /// Bytes of the input stream read by the guest (via `jolt::read_chunk`). The
+    /// guest reads them as advice, and commits to them by writing their digest to
+    /// the stream digest region, so these are part of the public inputs too.
+    pub input_stream: Vec<u8>,
+    /// Initial contents of the advice tape (see `MemoryLayout::advice_start`), zero
+    /// padded. Unlike the other fields, these are not part of the public inputs: the
+    /// prover commits to them, and drops them from the proof (see `redact_advice_tape`).
+    pub advice_tape: Vec<u8>,
+    /// Entries appended by the guest to its journal (via `jolt::journal`), or `None`
+    /// if they were redacted (see `redact_journal`). The journal is bound to the proof
+    /// by `journal_digest`, so it is not bounded by `max_output_size`.
+    pub journal: Option<Vec<Vec<u8>>>,
+    /// Hash chain over the journal entries (see `chain_journal_entry`), which the
+    /// guest writes to the journal digest region when it terminates. Part of the
+    /// public inputs even if the entries themselves are redacted.
+    pub journal_digest: [u8; JOURNAL_DIGEST_SIZE as usize],
+    /// Hash chain over every message of advice (private inputs, hints and input stream
+    /// chunks) read by a guest with `#[jolt::provable(nondeterminism_digest)]`,
+    /// which the guest writes to the nondeterminism digest region when it terminates;
+    /// zero for other guests. Part of the public inputs, so a verifier can check that
+    /// the guest used only the nondeterminism it declares.
+    pub nondeterminism_digest: [u8; NONDETERMINISM_DIGEST_SIZE as usize],
     pub panic: bool,
     pub memory_layout: MemoryLayout,
 }
@@ -546,6 +712,12 @@ impl JoltDevice {
         Self {
             inputs: Vec::new(),
             outputs: Vec::new(),
+            logs: Vec::new(),
+            input_stream: Vec::new(),
+            advice_tape: Vec::new(),
+            journal: Some(Vec::new()),
+            journal_digest: [0; JOURNAL_DIGEST_SIZE as usize],
+            nondeterminism_digest: [0; NONDETERMINISM_DIGEST_SIZE as usize],
             panic: false,
             memory_layout: MemoryLayout::new(max_input_size, max_output_size),
         }
@@ -556,6 +728,8 @@ impl JoltDevice {
             self.panic as u8
         } else if self.is_termination(address) {
             0 // Termination bit should never be loaded after it is set
+        } else if self.is_stream_digest(address) || self.is_journal_digest(address) {
+            0 // Likewise, the stream and journal digests are written once, by whole words
         } else if self.is_input(address) {
             let internal_address = self.convert_read_address(address);
             if self.inputs.len() <= internal_address {
@@ -563,6 +737,12 @@ impl JoltDevice {
             } else {
                 self.inputs[internal_address]
             }
+        } else if self.is_inputs_digest(address) {
+            let internal_address = (address - self.memory_layout.inputs_digest) as usize;
+            self.inputs_digest()[internal_address]
+        } else if self.is_nondeterminism_digest(address) {
+            let internal_address = (address - self.memory_layout.nondeterminism_digest) as usize;
+            self.nondeterminism_digest[internal_address]
         } else if self.is_output(address) {
             let internal_address = self.convert_write_address(address);
             if self.outputs.len() <= internal_address {
@@ -570,6 +750,19 @@ impl JoltDevice {
             } else {
                 self.outputs[internal_address]
             }
+        } else if self.is_log(address) {
+            let internal_address = (address - self.memory_layout.log_start) as usize;
+            if self.logs.len() <= internal_address {
+                0
+            } else {
+                self.logs[internal_address]
+            }
+        } else if self.is_advice(address) {
+            let internal_address = (address - self.memory_layout.advice_start) as usize;
+            self.advice_tape
+                .get(internal_address)
+                .copied()
+                .unwrap_or_default()
         } else {
             0 // zero-padding
         }
@@ -582,7 +775,25 @@ impl JoltDevice {
             return;
         }
 
-        if address == self.memory_layout.termination {
+        if address == self.memory_layout.termination
+            || self.is_stream_digest(address)
+            || self.is_journal_digest(address)
+        {
+            return;
+        }
+
+        if self.is_log(address) {
+            let internal_address = (address - self.memory_layout.log_start) as usize;
+            if self.logs.len() <= internal_address {
+                self.logs.resize(internal_address + 1, 0);
+            }
+            self.logs[internal_address] = value;
+            return;
+        }
+
+        if self.is_nondeterminism_digest(address) {
+            let internal_address = (address - self.memory_layout.nondeterminism_digest) as usize;
+            self.nondeterminism_digest[internal_address] = value;
             return;
         }
 
@@ -602,8 +813,105 @@ impl JoltDevice {
         address >= self.memory_layout.input_start && address < self.memory_layout.input_end
     }
 
+    pub fn is_inputs_digest(&self, address: u64) -> bool {
+        address >= self.memory_layout.inputs_digest && address < self.memory_layout.output_start
+    }
+
+    /// Keccak-256 digest of the program inputs. The digest is exposed to the guest via a
+    /// reserved, read-only memory region, and is recomputed by the verifier from the
+    /// public inputs, so it is bound to the proof by memory checking.
+    pub fn inputs_digest(&self) -> [u8; INPUTS_DIGEST_SIZE as usize] {
+        Keccak256::digest(&self.inputs).into()
+    }
+
     pub fn is_output(&self, address: u64) -> bool {
-        address >= self.memory_layout.output_start && address < self.memory_layout.termination
+        address >= self.memory_layout.output_start && address < self.memory_layout.output_end
+    }
+
+    pub fn is_log(&self, address: u64) -> bool {
+        address >= self.memory_layout.log_start && address < self.memory_layout.log_end
+    }
+
+    /// The guest's log output, split into lines.
+    pub fn log_lines(&self) -> Vec<String> {
+        String::from_utf8_lossy(&self.logs)
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    /// If the guest panicked, its panic message: the last `panicked at ...` entry in
+    /// the logs, written by the guest's panic handler. Empty if the handler could not
+    /// log it (e.g. the log region was full).
+    pub fn panic_message(&self) -> Option<String> {
+        if !self.panic {
+            return None;
+        }
+        let logs = String::from_utf8_lossy(&self.logs);
+        let message = logs
+            .rfind("panicked at ")
+            .map_or("", |start| logs[start..].trim_end());
+        Some(message.to_string())
+    }
+
+    pub fn is_stream_digest(&self, address: u64) -> bool {
+        address >= self.memory_layout.stream_digest && address < self.memory_layout.journal_digest
+    }
+
+    /// Keccak-256 digest of the input stream read by the guest, which the guest writes
+    /// to the stream digest region when it terminates. `None` if it read nothing, in
+    /// which case the guest leaves the region zeroed.
+    pub fn stream_digest(&self) -> Option<[u8; STREAM_DIGEST_SIZE as usize]> {
+        if self.input_stream.is_empty() {
+            return None;
+        }
+        Some(Keccak256::digest(&self.input_stream).into())
+    }
+
+    pub fn is_journal_digest(&self, address: u64) -> bool {
+        address >= self.memory_layout.journal_digest
+            && address < self.memory_layout.nondeterminism_digest
+    }
+
+    /// Unlike the stream and journal digests, the nondeterminism digest is not known to
+    /// the host in advance: like the outputs, it is whatever the guest writes.
+    pub fn is_nondeterminism_digest(&self, address: u64) -> bool {
+        address >= self.memory_layout.nondeterminism_digest && address < self.memory_layout.panic
+    }
+
+    /// Appends `entry` to the journal, and extends the hash chain over it.
+    pub fn append_journal(&mut self, entry: &[u8]) {
+        self.journal_digest = chain_journal_entry(&self.journal_digest, entry);
+        if let Some(journal) = &mut self.journal {
+            journal.push(entry.to_vec());
+        }
+    }
+
+    /// Drops the journal entries, keeping only their digest: a proof of the
+    /// resulting program I/O attests to the digest alone.
+    pub fn redact_journal(&mut self) {
+        self.journal = None;
+    }
+
+    /// Whether the journal entries (if not redacted) hash to `journal_digest`.
+    pub fn journal_matches_digest(&self) -> bool {
+        self.journal.as_ref().map_or(true, |journal| {
+            let digest = journal
+                .iter()
+                .fold([0; JOURNAL_DIGEST_SIZE as usize], |digest, entry| {
+                    chain_journal_entry(&digest, entry)
+                });
+            digest == self.journal_digest
+        })
+    }
+
+    pub fn is_advice(&self, address: u64) -> bool {
+        address >= self.memory_layout.advice_start && address < self.memory_layout.advice_end
+    }
+
+    /// Drops the advice tape, which the verifier neither needs nor should see.
+    pub fn redact_advice_tape(&mut self) {
+        self.advice_tape = Vec::new();
     }
 
     pub fn is_panic(&self, address: u64) -> bool {
@@ -623,6 +931,19 @@ impl JoltDevice {
     }
 }
 
+/// One link of the journal's hash chain: Keccak-256 of the previous digest (zero for
+/// the empty journal) followed by the entry. The guest computes the same chain as it
+/// appends entries.
+pub fn chain_journal_entry(
+    digest: &[u8; JOURNAL_DIGEST_SIZE as usize],
+    entry: &[u8],
+) -> [u8; JOURNAL_DIGEST_SIZE as usize] {
+    let mut hasher = Keccak256::new();
+    hasher.update(digest);
+    hasher.update(entry);
+    hasher.finalize().into()
+}
+
 #[derive(
     Debug, Clone, PartialEq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
 )]
@@ -631,42 +952,177 @@ pub struct MemoryLayout {
     pub max_output_size: u64,
     pub input_start: u64,
     pub input_end: u64,
+    pub inputs_digest: u64,
     pub output_start: u64,
     pub output_end: u64,
+    pub log_start: u64,
+    pub log_end: u64,
+    pub stream_digest: u64,
+    pub journal_digest: u64,
+    pub nondeterminism_digest: u64,
     pub panic: u64,
     pub termination: u64,
+    /// Size in bytes of the advice tape, zero if the program has none. A power of two
+    /// number of words (see `with_max_advice_size`).
+    pub max_advice_size: u64,
+    /// The advice tape: memory whose initial contents are supplied by the prover
+    /// (`JoltDevice::advice_tape`) and committed to with the rest of the witness,
+    /// rather than fixed by the preprocessing or the public inputs. It ends at
+    /// `RAM_START_ADDRESS`.
+    pub advice_start: u64,
+    pub advice_end: u64,
+    /// How the guest's inputs and outputs are serialized. Part of the memory layout so
+    /// that it is bound to the preprocessing and to every proof.
+    pub io_codec: IoCodec,
 }
 
 impl MemoryLayout {
-    pub fn new(mut max_input_size: u64, mut max_output_size: u64) -> Self {
+    pub fn new(max_input_size: u64, max_output_size: u64) -> Self {
+        Self::layout(max_input_size, max_output_size, 0)
+    }
+
+    /// Reserves an advice tape of at least `max_advice_size` bytes (none if it is
+    /// zero), keeping the other bounds and the codec.
+    pub fn with_max_advice_size(self, max_advice_size: u64) -> Self {
+        Self::layout(self.max_input_size, self.max_output_size, max_advice_size)
+            .with_io_codec(self.io_codec)
+    }
+
+    fn layout(mut max_input_size: u64, mut max_output_size: u64, mut max_advice_size: u64) -> Self {
         // Must be word-aligned
         max_input_size = max_input_size.next_multiple_of(4);
         max_output_size = max_output_size.next_multiple_of(4);
 
         // Adds 8 to account for panic bit and termination bit
         // (they each occupy one full 4-byte word)
-        let io_region_num_bytes = max_input_size + max_output_size + 8;
+        let io_region_num_bytes = max_input_size
+            + INPUTS_DIGEST_SIZE
+            + max_output_size
+            + MAX_LOG_SIZE
+            + STREAM_DIGEST_SIZE
+            + JOURNAL_DIGEST_SIZE
+            + NONDETERMINISM_DIGEST_SIZE
+            + 8;
+
+        // The advice tape spans a power of two number of words (at least two), so that
+        // it can be committed to as a polynomial of its own
+        if max_advice_size > 0 {
+            max_advice_size = max_advice_size.div_ceil(4).next_power_of_two().max(2) * 4;
+        }
 
         // Padded so that the witness index corresponding to `RAM_START_ADDRESS`
         // is a power of 2
-        let io_region_num_words =
-            (REGISTER_COUNT + io_region_num_bytes / 4).next_power_of_two() - REGISTER_COUNT;
+        let io_region_num_words = (REGISTER_COUNT + (io_region_num_bytes + max_advice_size) / 4)
+            .next_power_of_two()
+            - REGISTER_COUNT;
         let input_start = RAM_START_ADDRESS - io_region_num_words * 4;
         let input_end = input_start + max_input_size;
-        let output_start = input_end;
+        let inputs_digest = input_end;
+        let output_start = inputs_digest + INPUTS_DIGEST_SIZE;
         let output_end = output_start + max_output_size;
-        let panic = output_end;
+        let log_start = output_end;
+        let log_end = log_start + MAX_LOG_SIZE;
+        let stream_digest = log_end;
+        let journal_digest = stream_digest + STREAM_DIGEST_SIZE;
+        let nondeterminism_digest = journal_digest + JOURNAL_DIGEST_SIZE;
+        let panic = nondeterminism_digest + NONDETERMINISM_DIGEST_SIZE;
         let termination = panic + 4;
+        // The witness index of `RAM_START_ADDRESS` is a power of two no smaller than the
+        // tape, so the tape's witness indices are aligned to its size
+        let advice_end = RAM_START_ADDRESS;
+        let advice_start = advice_end - max_advice_size;
 
         Self {
             max_input_size,
             max_output_size,
             input_start,
             input_end,
+            inputs_digest,
             output_start,
             output_end,
+            log_start,
+            log_end,
+            stream_digest,
+            journal_digest,
+            nondeterminism_digest,
             panic,
             termination,
+            max_advice_size,
+            advice_start,
+            advice_end,
+            io_codec: IoCodec::default(),
+        }
+    }
+
+    pub fn with_io_codec(mut self, io_codec: IoCodec) -> Self {
+        self.io_codec = io_codec;
+        self
+    }
+}
+
+/// A serialization format for guest inputs and outputs (see the `codec` attribute of
+/// `jolt::provable`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IoCodec {
+    #[default]
+    Postcard,
+    Bincode,
+}
+
+impl IoCodec {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IoCodec::Postcard => "postcard",
+            IoCodec::Bincode => "bincode",
+        }
+    }
+}
+
+impl FromStr for IoCodec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "postcard" => Ok(IoCodec::Postcard),
+            "bincode" => Ok(IoCodec::Bincode),
+            _ => Err(format!(
+                "unsupported codec \"{}\" (expected \"postcard\" or \"bincode\")",
+                s
+            )),
+        }
+    }
+}
+
+impl CanonicalSerialize for IoCodec {
+    fn serialize_with_mode<W: std::io::Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        (*self as u8).serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        (*self as u8).serialized_size(compress)
+    }
+}
+
+impl Valid for IoCodec {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for IoCodec {
+    fn deserialize_with_mode<R: std::io::Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_with_mode(reader, compress, validate)? {
+            0 => Ok(IoCodec::Postcard),
+            1 => Ok(IoCodec::Bincode),
+            _ => Err(SerializationError::InvalidData),
         }
     }
 }
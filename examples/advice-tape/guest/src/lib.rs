@@ -0,0 +1,14 @@
+#![cfg_attr(feature = "guest", no_std)]
+
+/// Sums the first `n` words of the advice tape, then overwrites the first word with
+/// the sum, so that the guest both reads and writes the tape.
+#[jolt::provable(max_advice_size = 64)]
+fn advice_sum(n: u32) -> (u32, u32) {
+    let tape = unsafe { jolt::io::advice_tape() };
+    let word =
+        |tape: &[u8], i: usize| u32::from_le_bytes(tape[4 * i..4 * (i + 1)].try_into().unwrap());
+
+    let sum = (0..n as usize).fold(0u32, |sum, i| sum.wrapping_add(word(tape, i)));
+    tape[..4].copy_from_slice(&sum.to_le_bytes());
+    (sum, word(tape, 0))
+}
@@ -0,0 +1,23 @@
+use std::time::Instant;
+
+use jolt_sdk::{Jolt, RV32IJoltVM};
+
+pub fn main() {
+    let (mut program, preprocessing) = guest::preprocess_advice_sum();
+    let tape: Vec<u8> = (1..=16u32).flat_map(u32::to_le_bytes).collect();
+    program.set_advice_tape(&tape);
+
+    let now = Instant::now();
+    let (output, proof) = guest::prove_advice_sum(program, &preprocessing, 16).unwrap();
+    println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
+    let is_valid = RV32IJoltVM::verify(
+        &preprocessing.verifier_key(),
+        proof.proof,
+        proof.commitments,
+        None,
+    )
+    .is_ok();
+
+    println!("output: {:?}", output);
+    println!("valid: {}", is_valid);
+}
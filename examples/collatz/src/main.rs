@@ -5,7 +5,7 @@ pub fn main() {
     let (prove_collatz_single, verify_collatz_single) = guest::build_collatz_convergence();
 
     let now = Instant::now();
-    let (output, proof) = prove_collatz_single(19);
+    let (output, proof) = prove_collatz_single(19).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_collatz_single(proof);
 
@@ -19,7 +19,7 @@ pub fn main() {
     // https://www.reddit.com/r/compsci/comments/gk9x6g/collatz_conjecture_news_recently_i_managed_to/
     let start: u128 = 1 << 68;
     let now = Instant::now();
-    let (output, proof) = prove_collatz_convergence(start, start + 100);
+    let (output, proof) = prove_collatz_convergence(start, start + 100).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_collatz_convergence(proof);
 
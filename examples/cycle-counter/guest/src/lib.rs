@@ -0,0 +1,13 @@
+#![cfg_attr(feature = "guest", no_std)]
+
+/// Sums `1..=n`, returning the sum and the number of cycles it took.
+#[jolt::provable]
+fn metered_sum(n: u32) -> (u32, u32) {
+    let start = jolt::cycle_tracker::cycle();
+    let mut sum = 0u32;
+    for i in 1..=n {
+        sum = sum.wrapping_add(i);
+    }
+    let end = jolt::cycle_tracker::cycle();
+    (sum, end - start)
+}
@@ -0,0 +1,13 @@
+use std::time::Instant;
+
+pub fn main() {
+    let (prove, verify) = guest::build_metered_sum();
+
+    let now = Instant::now();
+    let ((sum, cycles), proof) = prove(100).unwrap();
+    println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
+    let is_valid = verify(proof);
+
+    println!("sum: {} ({} cycles)", sum, cycles);
+    println!("valid: {}", is_valid);
+}
@@ -8,7 +8,7 @@ pub fn main() {
         .expect("should write");
 
     let now = Instant::now();
-    let (output, proof) = prove_fib(50);
+    let (output, proof) = prove_fib(50).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_fib(proof);
 
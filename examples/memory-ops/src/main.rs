@@ -4,7 +4,7 @@ pub fn main() {
     let (prove, verify) = guest::build_memory_ops();
 
     let now = Instant::now();
-    let (output, proof) = prove();
+    let (output, proof) = prove().unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify(proof);
 
@@ -0,0 +1,48 @@
+#![cfg_attr(feature = "guest", no_std)]
+#![allow(unused_assignments, asm_sub_register)]
+
+#[repr(align(4))]
+struct Words([u8; 12]);
+
+#[jolt::provable(misaligned_accesses)]
+fn misaligned_ops() -> (u32, u32, u32) {
+    use core::arch::asm;
+
+    let mut data = Words([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+    unsafe {
+        let ptr = data.0.as_mut_ptr();
+
+        // Load Word (LW instruction) one byte past a word boundary
+        let mut val_lw: u32 = 0;
+        asm!(
+            "lw {val}, 1({ptr})",
+            ptr = in(reg) ptr,
+            val = out(reg) val_lw,
+        );
+
+        // Store Word (SW instruction) straddling the first two words
+        asm!(
+            "sw {value}, 3({ptr})",
+            ptr = in(reg) ptr,
+            value = in(reg) 0x12345678,
+        );
+
+        // Load the stored word back, and a word overlapping its last byte
+        let mut val_stored: u32 = 0;
+        asm!(
+            "lw {val}, 3({ptr})",
+            ptr = in(reg) ptr,
+            val = out(reg) val_stored,
+        );
+        let mut val_overlap: u32 = 0;
+        asm!(
+            "lw {val}, 6({ptr})",
+            ptr = in(reg) ptr,
+            val = out(reg) val_overlap,
+        );
+
+        // Return these values so that the load instructions
+        // don't get optimized away
+        (val_lw, val_stored, val_overlap)
+    }
+}
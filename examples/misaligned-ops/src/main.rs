@@ -0,0 +1,13 @@
+use std::time::Instant;
+
+pub fn main() {
+    let (prove, verify) = guest::build_misaligned_ops();
+
+    let now = Instant::now();
+    let (output, proof) = prove().unwrap();
+    println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
+    let is_valid = verify(proof);
+
+    println!("outputs: {:#x} {:#x} {:#x}", output.0, output.1, output.2);
+    println!("valid: {}", is_valid);
+}
@@ -0,0 +1,91 @@
+#![cfg_attr(feature = "guest", no_std)]
+
+use jolt::DecisionTree;
+
+/// Applies ReLU to `logits`, returning the activations and the index of the largest
+/// (the first, if there are several).
+#[jolt::provable]
+fn classify(logits: [i32; 8]) -> ([i32; 8], u32) {
+    let activations = logits.map(jolt::relu);
+    let mut best = 0;
+    for (i, &activation) in activations.iter().enumerate().skip(1) {
+        if jolt::argmax(activations[best], activation) == 1 {
+            best = i;
+        }
+    }
+    (activations, best as u32)
+}
+
+/// A depth-3 regression tree over 4 features.
+static TREE: [[u32; 2]; 15] = [
+    DecisionTree::split(0, 0, 10),
+    DecisionTree::split(1, 1, -5),
+    DecisionTree::split(2, 2, 100),
+    DecisionTree::split(3, 3, 0),
+    DecisionTree::split(4, 0, 3),
+    DecisionTree::split(5, 1, 20),
+    DecisionTree::split(6, 3, -1),
+    DecisionTree::leaf(-40),
+    DecisionTree::leaf(-25),
+    DecisionTree::leaf(-10),
+    DecisionTree::leaf(5),
+    DecisionTree::leaf(15),
+    DecisionTree::leaf(30),
+    DecisionTree::leaf(45),
+    DecisionTree::leaf(60),
+];
+
+/// Evaluates `TREE` on `features`.
+#[jolt::provable]
+fn predict(features: [i32; 4]) -> i32 {
+    DecisionTree::new(&TREE, 3, 4).predict(&features)
+}
+
+/// The dot product of the Q16.16 vectors `a` and `b`, saturating rather than
+/// overflowing.
+#[jolt::provable]
+fn dot_q16(a: [i32; 4], b: [i32; 4]) -> i32 {
+    a.iter().zip(b).fold(0, |sum, (&a, b)| {
+        jolt::saturating_add(sum, jolt::fixed_mul(a, b))
+    })
+}
+
+/// The softmax of the Q8.8 values `logits`, as Q16.16 probabilities.
+#[jolt::provable]
+fn softmax(logits: [i16; 4]) -> [u32; 4] {
+    jolt::softmax(logits)
+}
+
+/// A gradient-boosted ensemble of two depth-2 trees over 4 features, whose leaves are
+/// Q8.8 scores.
+static ENSEMBLE: [[[u32; 2]; 7]; 2] = [
+    [
+        DecisionTree::split(0, 0, 10),
+        DecisionTree::split(1, 1, 0),
+        DecisionTree::split(2, 2, 50),
+        DecisionTree::leaf(-384), // -1.5
+        DecisionTree::leaf(-128), // -0.5
+        DecisionTree::leaf(192),  // 0.75
+        DecisionTree::leaf(512),  // 2.0
+    ],
+    [
+        DecisionTree::split(0, 3, 0),
+        DecisionTree::split(1, 2, 100),
+        DecisionTree::split(2, 1, -3),
+        DecisionTree::leaf(-64),  // -0.25
+        DecisionTree::leaf(128),  // 0.5
+        DecisionTree::leaf(-256), // -1.0
+        DecisionTree::leaf(320),  // 1.25
+    ],
+];
+
+/// The probability, in Q16.16, that `ENSEMBLE` assigns to the positive class of
+/// `features`: the sigmoid of the sum of the trees' scores.
+#[jolt::provable]
+fn boosted_probability(features: [i32; 4]) -> u32 {
+    let score: i32 = ENSEMBLE
+        .iter()
+        .map(|nodes| DecisionTree::new(nodes, 2, 4).predict(&features))
+        .sum();
+    jolt::sigmoid(score.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+}
@@ -0,0 +1,52 @@
+use std::time::Instant;
+
+pub fn main() {
+    let (prove_classify, verify_classify) = guest::build_classify();
+    let (prove_predict, verify_predict) = guest::build_predict();
+    let (prove_dot_q16, verify_dot_q16) = guest::build_dot_q16();
+    let (prove_softmax, verify_softmax) = guest::build_softmax();
+    let (prove_boosted_probability, verify_boosted_probability) =
+        guest::build_boosted_probability();
+
+    let now = Instant::now();
+    let ((activations, class), proof) = prove_classify([-3, 7, 0, 12, -40, 12, 5, 1]).unwrap();
+    println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
+    let is_valid = verify_classify(proof);
+
+    println!("activations: {:?}, class: {}", activations, class);
+    println!("valid: {}", is_valid);
+
+    let (prediction, proof) = prove_predict([12, 0, 150, -7]).unwrap();
+    let is_valid = verify_predict(proof);
+
+    println!("prediction: {}", prediction);
+    println!("valid: {}", is_valid);
+
+    // [1.5, -2.25, 0.5, 4] . [2, 1, -0.25, 0.125]
+    let q16 = |x: f64| (x * 65536.0) as i32;
+    let (dot, proof) = prove_dot_q16(
+        [1.5, -2.25, 0.5, 4.0].map(q16),
+        [2.0, 1.0, -0.25, 0.125].map(q16),
+    )
+    .unwrap();
+    let is_valid = verify_dot_q16(proof);
+
+    println!("dot product: {}", dot as f64 / 65536.0);
+    println!("valid: {}", is_valid);
+
+    // [1, 2, -0.5, 0.25] in Q8.8
+    let (probabilities, proof) = prove_softmax([256, 512, -128, 64]).unwrap();
+    let is_valid = verify_softmax(proof);
+
+    println!(
+        "probabilities: {:?}",
+        probabilities.map(|p| p as f64 / 65536.0)
+    );
+    println!("valid: {}", is_valid);
+
+    let (probability, proof) = prove_boosted_probability([12, 0, 150, -7]).unwrap();
+    let is_valid = verify_boosted_probability(proof);
+
+    println!("probability: {}", probability as f64 / 65536.0);
+    println!("valid: {}", is_valid);
+}
@@ -4,7 +4,7 @@ pub fn main() {
     let (prove, verify) = guest::build_muldiv();
 
     let now = Instant::now();
-    let (output, proof) = prove(12031293, 17, 92);
+    let (output, proof) = prove(12031293, 17, 92).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify(proof);
 
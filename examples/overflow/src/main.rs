@@ -1,24 +1,15 @@
-use std::any::Any;
-use std::panic;
 use std::time::Instant;
 
 pub fn main() {
     let (prove_overflow_stack, _) = guest::build_overflow_stack();
 
-    let res = panic::catch_unwind(|| {
-        // trying to allocate 1024 elems array and sum it up
-        // with stack_size=1024, should panic
-        let (_, _) = prove_overflow_stack();
-    });
-    handle_result(res);
+    // trying to allocate 1024 elems array and sum it up
+    // with stack_size=1024, should fail
+    handle_result(prove_overflow_stack());
 
-    // now lets try to overflow the heap, should also panic
+    // now lets try to overflow the heap, should also fail
     let (prove_overflow_heap, _) = guest::build_overflow_heap();
-
-    let res = panic::catch_unwind(|| {
-        let (_, _) = prove_overflow_heap();
-    });
-    handle_result(res);
+    handle_result(prove_overflow_heap());
 
     // valid case for stack allocation, calls overflow_stack() under the hood
     // but with stack_size=8192
@@ -26,7 +17,7 @@ pub fn main() {
         guest::build_allocate_stack_with_increased_size();
 
     let now = Instant::now();
-    let (output, proof) = prove_allocate_stack_with_increased_size();
+    let (output, proof) = prove_allocate_stack_with_increased_size().unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_allocate_stack_with_increased_size(proof);
 
@@ -34,10 +25,8 @@ pub fn main() {
     println!("valid: {}", is_valid);
 }
 
-fn handle_result(res: Result<(), Box<dyn Any + Send>>) {
+fn handle_result<T>(res: Result<T, jolt_sdk::JoltError>) {
     if let Err(e) = &res {
-        if let Some(msg) = e.downcast_ref::<String>() {
-            println!("--> Panic occurred with message: {}\n", msg);
-        }
+        println!("--> Proving failed with: {}\n", e);
     }
 }
@@ -7,7 +7,7 @@ pub fn main() {
     let iters = 100;
     let native_output = guest::sha2_chain(input, iters);
     let now = Instant::now();
-    let (output, proof) = prove_sha2_chain(input, iters);
+    let (output, proof) = prove_sha2_chain(input, iters).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_sha2_chain(proof);
 
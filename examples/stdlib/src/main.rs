@@ -3,7 +3,7 @@ use std::time::Instant;
 pub fn main() {
     let (prove, verify) = guest::build_int_to_string();
 
-    let (output, proof) = prove(81);
+    let (output, proof) = prove(81).unwrap();
     let is_valid = verify(proof);
 
     println!("int to string output: {:?}", output);
@@ -12,7 +12,7 @@ pub fn main() {
     let (prove, verify) = guest::build_string_concat();
 
     let now = Instant::now();
-    let (output, proof) = prove(20);
+    let (output, proof) = prove(20).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify(proof);
 
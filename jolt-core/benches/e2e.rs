@@ -0,0 +1,162 @@
+use ark_bn254::{Bn254, Fr, G1Projective};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use criterion::{BatchSize, Criterion};
+use jolt_core::host;
+use jolt_core::jolt::vm::config::JoltConfig;
+use jolt_core::jolt::vm::rv32i_vm::{RV32IJoltProof, RV32IJoltVM, C, M};
+use jolt_core::jolt::vm::{Jolt, JoltPreprocessing, JoltStuff};
+use jolt_core::poly::commitment::commitment_scheme::CommitmentScheme;
+use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+use jolt_core::poly::commitment::hyrax::HyraxScheme;
+use jolt_core::poly::commitment::mock::MockCommitScheme;
+use jolt_core::poly::commitment::zeromorph::Zeromorph;
+use jolt_core::utils::transcript::KeccakTranscript;
+use serde_json::json;
+
+/// Where the proof sizes are written, unless overridden by `JOLT_E2E_RESULTS`.
+const DEFAULT_RESULTS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/jolt-e2e.json");
+
+struct Guest {
+    name: &'static str,
+    package: &'static str,
+    set_input: fn(&mut host::Program),
+}
+
+const GUESTS: [Guest; 3] = [
+    Guest {
+        name: "fibonacci",
+        package: "fibonacci-guest",
+        set_input: |program| program.set_input(&100u32),
+    },
+    Guest {
+        name: "sha3",
+        package: "sha3-guest",
+        set_input: |program| program.set_input(&vec![5u8; 2048]),
+    },
+    Guest {
+        name: "memory-ops",
+        package: "memory-ops-guest",
+        set_input: |_| {},
+    },
+];
+
+// Benchmarks proving and verifying `guest` with `PCS`, and records the size of its proof
+fn benchmark_e2e<PCS>(
+    c: &mut Criterion,
+    pcs_name: &str,
+    guest: &Guest,
+    results: &mut Vec<serde_json::Value>,
+) where
+    PCS: CommitmentScheme<KeccakTranscript, Field = Fr>,
+{
+    let mut program = host::Program::new(guest.package);
+    (guest.set_input)(&mut program);
+    let (bytecode, memory_init) = program.decode();
+    let (io_device, trace) = program.trace();
+    let trace_length = trace.len();
+
+    let config = JoltConfig::<C, M, Fr, PCS, KeccakTranscript>::builder()
+        .max_input_size(io_device.memory_layout.max_input_size)
+        .max_output_size(io_device.memory_layout.max_output_size)
+        .max_bytecode_size(1 << 20)
+        .max_memory_address(1 << 20)
+        .max_trace_length(trace_length.next_power_of_two())
+        .build()
+        .unwrap();
+    let preprocessing: JoltPreprocessing<C, Fr, PCS, KeccakTranscript> =
+        RV32IJoltVM::preprocess_with_config(bytecode, memory_init, &config);
+    let prove = |io_device, trace, preprocessing| {
+        <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+            io_device,
+            trace,
+            preprocessing,
+        )
+    };
+
+    let mut group = c.benchmark_group(format!("{}/{pcs_name}", guest.name));
+    group.bench_function("prove", |b| {
+        b.iter_batched(
+            || (io_device.clone(), trace.clone()),
+            |(io_device, trace)| prove(io_device, trace, &preprocessing),
+            BatchSize::LargeInput,
+        )
+    });
+
+    let (proof, commitments, _) = prove(io_device, trace, &preprocessing);
+    let mut proof_bytes = vec![];
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+    let mut commitment_bytes = vec![];
+    commitments
+        .serialize_compressed(&mut commitment_bytes)
+        .unwrap();
+
+    let verifier_key = preprocessing.verifier_key();
+    group.bench_function("verify", |b| {
+        b.iter_batched(
+            || {
+                (
+                    RV32IJoltProof::<Fr, PCS, KeccakTranscript>::deserialize_compressed(
+                        proof_bytes.as_slice(),
+                    )
+                    .unwrap(),
+                    JoltStuff::<PCS::Commitment>::deserialize_compressed(
+                        commitment_bytes.as_slice(),
+                    )
+                    .unwrap(),
+                )
+            },
+            |(proof, commitments)| {
+                assert!(RV32IJoltVM::verify(&verifier_key, proof, commitments, None).is_ok())
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    results.push(json!({
+        "guest": guest.name,
+        "pcs": pcs_name,
+        "trace_length": trace_length,
+        "proof_bytes": proof_bytes.len(),
+        "commitment_bytes": commitment_bytes.len(),
+    }));
+}
+
+fn main() {
+    let mut c = Criterion::default().configure_from_args().sample_size(10);
+
+    let mut results = vec![];
+    for guest in &GUESTS {
+        benchmark_e2e::<HyperKZG<Bn254, KeccakTranscript>>(&mut c, "HyperKZG", guest, &mut results);
+        benchmark_e2e::<Zeromorph<Bn254, KeccakTranscript>>(
+            &mut c,
+            "Zeromorph",
+            guest,
+            &mut results,
+        );
+        benchmark_e2e::<HyraxScheme<G1Projective, KeccakTranscript>>(
+            &mut c,
+            "Hyrax",
+            guest,
+            &mut results,
+        );
+        benchmark_e2e::<MockCommitScheme<Fr, KeccakTranscript>>(
+            &mut c,
+            "Mock",
+            guest,
+            &mut results,
+        );
+    }
+
+    // Timings are in criterion's own reports (`target/criterion/<guest>/<pcs>/...`)
+    let results_path =
+        std::env::var("JOLT_E2E_RESULTS").unwrap_or_else(|_| DEFAULT_RESULTS_PATH.to_string());
+    std::fs::write(
+        &results_path,
+        serde_json::to_string_pretty(&results).unwrap(),
+    )
+    .unwrap();
+    println!("Wrote proof sizes to {results_path}");
+
+    c.final_summary();
+}
@@ -5,7 +5,8 @@ use jolt_core::poly::commitment::commitment_scheme::CommitmentScheme;
 use jolt_core::poly::commitment::hyperkzg::HyperKZG;
 use jolt_core::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use jolt_core::subprotocols::grand_product::{
-    BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductProof,
+    BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductProof, GrandProductStrategy,
+    SelectableGrandProduct,
 };
 use jolt_core::subprotocols::grand_product_quarks::{QuarkGrandProduct, QuarkGrandProductConfig};
 use jolt_core::subprotocols::QuarkHybridLayerDepth;
@@ -264,5 +265,32 @@ fn main() {
         >>::Config::default(),
     );
 
+    // The memory-checking strategies, for short and long traces
+    for (trace, layer_size) in [("Short", 1 << 10), ("Long", 1 << 16)] {
+        for (strategy_name, strategy) in [
+            ("GKR", GrandProductStrategy::Gkr),
+            (
+                "Quark",
+                GrandProductStrategy::Quark(QuarkHybridLayerDepth::Default),
+            ),
+        ] {
+            benchmark_prove_and_verify::<
+                HyperKZG<Bn254, KeccakTranscript>,
+                Fr,
+                SelectableGrandProduct<Fr, KeccakTranscript>,
+                KeccakTranscript,
+            >(
+                &mut c,
+                BenchConfig {
+                    name: format!("HyperKZG {strategy_name} Strategy, {trace} Trace").leak(),
+                    num_layers: 8,
+                    layer_size,
+                    percentage_ones: 0,
+                },
+                strategy,
+            );
+        }
+    }
+
     c.final_summary();
 }
@@ -0,0 +1,198 @@
+/* C bindings for jolt-core, built with its `ffi` feature. */
+
+/* Generated from src/jolt/ffi.rs by cbindgen; do not edit. */
+
+#ifndef JOLT_H
+#define JOLT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define JOLT_OK 0
+
+/**
+ * A pointer argument was null, or the ELF path was not valid UTF-8.
+ */
+#define JOLT_INVALID_ARGUMENT 1
+
+/**
+ * The prover failed, e.g. because the trace is longer than the preprocessing supports.
+ */
+#define JOLT_PROVE_FAILED 2
+
+/**
+ * The proof or verifier key could not be deserialized, e.g. because it was
+ * produced by an incompatible version of Jolt.
+ */
+#define JOLT_DESERIALIZATION_FAILED 3
+
+/**
+ * The proof did not verify.
+ */
+#define JOLT_INVALID_PROOF 4
+
+/**
+ * Jolt panicked.
+ */
+#define JOLT_PANIC 5
+
+/**
+ * The ELF could not be read or decoded.
+ */
+#define JOLT_DECODE_FAILED 6
+
+/**
+ * The guest panicked or faulted.
+ */
+#define JOLT_TRACE_FAILED 7
+
+/**
+ * An ELF decoded and preprocessed by `jolt_prover_new`. It is only read by
+ * `jolt_prover_prove`, so it may prove on several threads at once.
+ */
+typedef struct JoltProver JoltProver;
+
+/**
+ * A verifier key deserialized by `jolt_verifier_new`.
+ */
+typedef struct JoltVerifier JoltVerifier;
+
+/**
+ * A buffer of bytes owned by Jolt; release it with `jolt_free_bytes`.
+ */
+typedef struct JoltBytes {
+  uint8_t *data;
+  size_t len;
+} JoltBytes;
+
+/**
+ * The bounds an ELF is preprocessed for. Proving cost does not depend on them, but
+ * preprocessing cost and memory grow with `max_trace_length`, so it should not be
+ * much larger than the longest execution that will be proven.
+ */
+typedef struct JoltProverOptions {
+  /**
+   * The sizes of the program I/O regions the ELF was built for, as in
+   * `MemoryLayout::new(max_input_size, max_output_size)`.
+   */
+  uint64_t max_input_size;
+  uint64_t max_output_size;
+  size_t max_bytecode_size;
+  size_t max_memory_address;
+  size_t max_trace_length;
+} JoltProverOptions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The default bounds of `jolt::provable`.
+ */
+JoltProverOptions jolt_prover_options_default(void);
+
+/**
+ * Decodes the RV32IM ELF at `elf_path` and preprocesses it for `options`, writing
+ * the prover to `out`. The ELF is read again by every proof, so it must stay at
+ * `elf_path` while the prover is in use.
+ *
+ * # Safety
+ * `elf_path` must be a NUL-terminated string, `options` must be readable, `out` must
+ * be writable, and `error` must be null or writable.
+ */
+int32_t jolt_prover_new(const char *elf_path,
+                        const JoltProverOptions *options,
+                        JoltProver **out,
+                        char **error);
+
+/**
+ * Writes the serialized verifier key of `prover`'s proofs to `out`.
+ *
+ * # Safety
+ * `prover` must have been returned by `jolt_prover_new` and not freed since, `out`
+ * must be writable, and `error` must be null or writable.
+ */
+int32_t jolt_prover_verifier_key(const JoltProver *prover, JoltBytes *out, char **error);
+
+/**
+ * Proves an execution of `prover`'s ELF on `input` (serialized with the guest's
+ * codec), writing the serialized proof to `out`.
+ *
+ * # Safety
+ * `prover` must have been returned by `jolt_prover_new` and not freed since, `input`
+ * must point to `input_len` readable bytes (or be null if `input_len` is 0), `out`
+ * must be writable, and `error` must be null or writable.
+ */
+int32_t jolt_prover_prove(const JoltProver *prover,
+                          const uint8_t *input,
+                          size_t input_len,
+                          JoltBytes *out,
+                          char **error);
+
+/**
+ * Releases a prover returned by `jolt_prover_new`.
+ *
+ * # Safety
+ * `prover` must be null or have been returned by `jolt_prover_new`, and not freed since.
+ */
+void jolt_prover_free(JoltProver *prover);
+
+/**
+ * Deserializes a verifier key written by `jolt_prover_verifier_key`, writing the
+ * verifier to `out`.
+ *
+ * # Safety
+ * `verifier_key` must point to `verifier_key_len` readable bytes, `out` must be
+ * writable, and `error` must be null or writable.
+ */
+int32_t jolt_verifier_new(const uint8_t *verifier_key,
+                          size_t verifier_key_len,
+                          JoltVerifier **out,
+                          char **error);
+
+/**
+ * Verifies a serialized `JoltHyperKZGProof`, returning `JOLT_OK` if it is valid.
+ *
+ * # Safety
+ * `verifier` must have been returned by `jolt_verifier_new` and not freed since,
+ * `proof` must point to `proof_len` readable bytes, and `error` must be null or
+ * writable.
+ */
+int32_t jolt_verifier_verify(const JoltVerifier *verifier,
+                             const uint8_t *proof,
+                             size_t proof_len,
+                             char **error);
+
+/**
+ * Releases a verifier returned by `jolt_verifier_new`.
+ *
+ * # Safety
+ * `verifier` must be null or have been returned by `jolt_verifier_new`, and not
+ * freed since.
+ */
+void jolt_verifier_free(JoltVerifier *verifier);
+
+/**
+ * Releases a buffer written by `jolt_prover_verifier_key` or `jolt_prover_prove`,
+ * and nulls it out.
+ *
+ * # Safety
+ * `bytes` must be null or have been written by one of those functions, and not
+ * freed since.
+ */
+void jolt_free_bytes(JoltBytes *bytes);
+
+/**
+ * Releases an error message written by any of the functions above.
+ *
+ * # Safety
+ * `error` must be null or have been written by one of those functions, and not
+ * freed since.
+ */
+void jolt_free_error(char *error);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* JOLT_H */
@@ -247,7 +247,7 @@ where
             <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::prove(
                 io_device,
                 trace,
-                preprocessing.clone(),
+                &preprocessing,
             );
 
         println!("Proof sizing:");
@@ -264,8 +264,12 @@ where
             &jolt_proof.instruction_lookups,
         );
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, None);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            jolt_proof,
+            jolt_commitments,
+            None,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -310,10 +314,14 @@ where
             <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::prove(
                 io_device,
                 trace,
-                preprocessing.clone(),
+                &preprocessing,
             );
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, None);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            jolt_proof,
+            jolt_commitments,
+            None,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
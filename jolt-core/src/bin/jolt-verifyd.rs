@@ -0,0 +1,157 @@
+//! `jolt-verifyd`: an HTTP server verifying `JoltHyperKZGProof`s.
+//!
+//! Routes:
+//! - `GET /programs` lists the programs proofs can be verified for.
+//! - `POST /verify/<program>` verifies the `JoltHyperKZGProof` in the request
+//!   body, as written by `Serializable::serialize_to_bytes`, against `<program>`'s
+//!   preprocessing.
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use ark_serialize::Validate;
+use clap::Parser;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use jolt_core::jolt::compat::ProofHeader;
+use jolt_core::jolt::verifier_service::{BatchConfig, ProgramRegistry, VerifierService};
+use jolt_core::jolt::vm::rv32i_vm::{JoltHyperKZGProof, Serializable};
+use jolt_core::utils::errors::VerifierServiceError;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Directory of `<program>.preprocessing` files, as written by `Jolt::save_preprocessing`
+    #[clap(long)]
+    preprocessing_dir: PathBuf,
+
+    #[clap(long, default_value = "127.0.0.1:3030")]
+    addr: String,
+
+    /// Number of threads handling requests
+    #[clap(long, default_value_t = 4)]
+    threads: usize,
+
+    /// Maximum number of proofs whose pairing checks are verified together
+    #[clap(long, default_value_t = 64)]
+    max_batch_size: usize,
+
+    /// Maximum time in milliseconds a proof waits for others to be batched with
+    #[clap(long, default_value_t = 5)]
+    max_wait_ms: u64,
+
+    /// Maximum size in bytes of an uploaded proof
+    #[clap(long, default_value_t = 1 << 24)]
+    max_proof_size: u64,
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+    let args = Args::parse();
+
+    let registry = ProgramRegistry::load_dir(&args.preprocessing_dir)
+        .unwrap_or_else(|err| panic!("failed to load preprocessing: {err}"));
+    if registry.is_empty() {
+        tracing::warn!(
+            "no preprocessing found in {}",
+            args.preprocessing_dir.display()
+        );
+    }
+    tracing::info!("loaded programs: {:?}", registry.names());
+
+    let service = Arc::new(VerifierService::new(
+        registry,
+        BatchConfig {
+            max_batch_size: args.max_batch_size,
+            max_wait: Duration::from_millis(args.max_wait_ms),
+        },
+    ));
+    let server = Arc::new(
+        Server::http(&args.addr)
+            .unwrap_or_else(|err| panic!("failed to bind {}: {err}", args.addr)),
+    );
+    tracing::info!("listening on {}", args.addr);
+
+    let workers: Vec<_> = (0..args.threads)
+        .map(|_| {
+            let server = server.clone();
+            let service = service.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&service, request, args.max_proof_size);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+}
+
+fn handle(service: &VerifierService, mut request: Request, max_proof_size: u64) {
+    let url = request.url().to_string();
+    let program = url.strip_prefix("/verify/");
+    let (status, body) = match (request.method(), url.as_str(), program) {
+        (Method::Get, "/programs", _) => (200, json!({ "programs": service.registry().names() })),
+        (Method::Post, _, Some(program)) => {
+            match verify(service, program, &mut request, max_proof_size) {
+                Ok(()) => (200, json!({ "valid": true })),
+                Err(err @ VerifierServiceError::UnknownProgram(_)) => {
+                    (404, json!({ "error": err.to_string() }))
+                }
+                Err(err @ VerifierServiceError::ProofTooLarge(_)) => {
+                    (413, json!({ "error": err.to_string() }))
+                }
+                Err(
+                    err @ (VerifierServiceError::Io(_)
+                    | VerifierServiceError::Deserialization(_)
+                    | VerifierServiceError::Format(_)),
+                ) => (400, json!({ "error": err.to_string() })),
+                Err(
+                    err @ (VerifierServiceError::MemoryLayoutMismatch(_)
+                    | VerifierServiceError::Verify(_)
+                    | VerifierServiceError::PairingCheckFailed),
+                ) => (200, json!({ "valid": false, "error": err.to_string() })),
+                Err(err) => (500, json!({ "error": err.to_string() })),
+            }
+        }
+        _ => (404, json!({ "error": "not found" })),
+    };
+
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(err) = request.respond(response) {
+        tracing::warn!("failed to respond to {url}: {err}");
+    }
+}
+
+fn verify(
+    service: &VerifierService,
+    program: &str,
+    request: &mut Request,
+    max_proof_size: u64,
+) -> Result<(), VerifierServiceError> {
+    if request
+        .body_length()
+        .is_some_and(|length| length as u64 > max_proof_size)
+    {
+        return Err(VerifierServiceError::ProofTooLarge(max_proof_size));
+    }
+    // Without a Content-Length (e.g. chunked bodies), read one byte past the limit
+    let mut bytes = vec![];
+    request
+        .as_reader()
+        .take(max_proof_size.saturating_add(1))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_proof_size {
+        return Err(VerifierServiceError::ProofTooLarge(max_proof_size));
+    }
+    let mut reader = bytes.as_slice();
+    let header = ProofHeader::deserialize_checked(&mut reader)?;
+    JoltHyperKZGProof::check_header(&header)?;
+    let proof = JoltHyperKZGProof::deserialize_body(header, reader, Validate::Yes)?;
+    service.verify(program, proof)
+}
@@ -204,6 +204,13 @@ mod tests {
     use ark_std::test_rng;
     use rand_chacha::rand_core::RngCore;
 
+    #[test]
+    fn byte_order() {
+        let mut bytes = [0u8; 32];
+        bytes[..2].copy_from_slice(&[2, 1]);
+        assert_eq!(<Fr as JoltField>::from_bytes(&bytes), Fr::from(0x0102u64));
+    }
+
     #[test]
     fn implicit_montgomery_conversion() {
         let mut rng = test_rng();
@@ -157,11 +157,21 @@ impl<F: BiniusSpecific> JoltField for BiniusField<F> {
     fn from_bytes(bytes: &[u8]) -> Self {
         assert_eq!(bytes.len(), Self::NUM_BYTES);
 
-        let field_element = bytemuck::try_from_bytes::<F>(bytes).unwrap();
+        let mut bytes: [u8; 16] = bytes.try_into().unwrap();
+        to_native_byte_order(&mut bytes);
+        let field_element = bytemuck::try_from_bytes::<F>(&bytes).unwrap();
         Self(field_element.to_owned())
     }
 }
 
+/// Binius field elements are stored as a single `u128`, so `bytemuck` exposes them in
+/// the host's byte order. Serialized bytes are always little-endian, so that proofs
+/// produced on big-endian hosts can be verified elsewhere.
+fn to_native_byte_order(_bytes: &mut [u8; 16]) {
+    #[cfg(target_endian = "big")]
+    _bytes.reverse();
+}
+
 impl<F: BiniusSpecific> Zero for BiniusField<F> {
     fn zero() -> Self {
         Self(F::ZERO)
@@ -271,8 +281,10 @@ impl<F: BiniusSpecific> CanonicalSerialize for BiniusField<F> {
         mut writer: W,
         _compress: ark_serialize::Compress,
     ) -> Result<(), ark_serialize::SerializationError> {
-        let bytes = bytemuck::bytes_of(&self.0);
-        writer.write_all(bytes)?;
+        let mut bytes: [u8; 16] = bytemuck::bytes_of(&self.0).try_into().unwrap();
+        // The conversion is its own inverse
+        to_native_byte_order(&mut bytes);
+        writer.write_all(&bytes)?;
         Ok(())
     }
 
@@ -303,3 +315,17 @@ impl<F: BiniusSpecific> std::fmt::Display for BiniusField<F> {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_order() {
+        let x = BiniusField::<BinaryField128b>::from_u64(0x0102);
+        let mut bytes = vec![];
+        x.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes, 0x0102u128.to_le_bytes());
+        assert_eq!(BiniusField::<BinaryField128b>::from_bytes(&bytes), x);
+    }
+}
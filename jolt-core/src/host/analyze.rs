@@ -1,11 +1,23 @@
-use std::{collections::HashMap, fs::File, io, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+};
 
 use serde::{Deserialize, Serialize};
-use tracer::{ELFInstruction, JoltDevice, RVTraceRow, RV32IM};
+use tracer::{CycleMarker, ELFInstruction, FunctionSymbol, JoltDevice, RVTraceRow, RV32IM};
 
 use crate::{
     field::JoltField,
-    jolt::vm::{rv32i_vm::RV32I, JoltTraceStep},
+    host::virtual_trace,
+    jolt::{
+        instruction::JoltInstruction,
+        vm::{
+            rv32i_vm::{RV32ISubtables, C, M, RV32I},
+            JoltTraceStep,
+        },
+    },
 };
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -17,8 +29,82 @@ pub struct ProgramSummary {
 
     pub io_device: JoltDevice,
     pub processed_trace: Vec<JoltTraceStep<RV32I>>,
+
+    /// Markers executed by `jolt::start_cycle_tracker!`/`jolt::end_cycle_tracker!`.
+    pub cycle_markers: Vec<CycleMarker>,
+
+    /// Functions in the guest ELF's symbol table, sorted by address.
+    pub symbols: Vec<FunctionSymbol>,
 }
 
+/// Cycles spent in a region delimited by `jolt::start_cycle_tracker!` and
+/// `jolt::end_cycle_tracker!`, summed over every time the region was entered.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleRegion {
+    pub label: String,
+    /// Number of times the region was entered and exited.
+    pub count: usize,
+    /// Number of RV32IM instructions executed in the region.
+    pub rv32im_cycles: usize,
+    /// Number of Jolt trace steps (i.e. after expanding virtual sequences) in the region.
+    pub trace_length: usize,
+}
+
+/// Cycles spent in a function of the guest, summed over every call to it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionProfile {
+    pub name: String,
+    /// Number of times the function was called.
+    pub calls: usize,
+    /// Number of RV32IM instructions executed in the function itself.
+    pub self_cycles: usize,
+    /// Number of Jolt trace steps in the function itself.
+    pub self_trace_length: usize,
+    /// Number of Jolt trace steps in the function and the functions it called.
+    pub total_trace_length: usize,
+}
+
+/// Lookups performed by one instruction (opcode) of the guest, after expanding
+/// virtual sequences.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstructionUsage {
+    pub opcode: RV32IM,
+    /// Number of trace steps executing the instruction.
+    pub count: usize,
+    /// Number of those steps that perform an instruction lookup; the others (e.g.
+    /// loads and stores) are only checked by the R1CS constraints.
+    pub lookups: usize,
+    /// Number of subtable lookups the instruction lookups decompose into.
+    pub subtable_lookups: usize,
+}
+
+impl InstructionUsage {
+    /// Estimated number of field elements the prover commits to for these lookups:
+    /// per instruction lookup, its `C` chunks and its instruction flag, and per
+    /// subtable lookup, its read count and its lookup output.
+    pub fn estimated_commitment_cost(&self) -> usize {
+        self.lookups * (C + 1) + 2 * self.subtable_lookups
+    }
+}
+
+/// Lookups into one Lasso subtable, summed over all instructions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtableUsage {
+    pub name: String,
+    pub lookups: usize,
+}
+
+impl SubtableUsage {
+    /// Estimated number of field elements the prover commits to for these lookups:
+    /// per lookup, its read count and its lookup output.
+    pub fn estimated_commitment_cost(&self) -> usize {
+        2 * self.lookups
+    }
+}
+
+/// Name of the code not covered by any symbol.
+const UNKNOWN_FUNCTION: &str = "[unknown]";
+
 impl ProgramSummary {
     pub fn trace_len(&self) -> usize {
         self.processed_trace.len()
@@ -42,10 +128,461 @@ impl ProgramSummary {
         counts
     }
 
+    /// Lookups per instruction and per subtable, sorted by decreasing estimated
+    /// commitment cost. Useful to compare the cost of alternative implementations of
+    /// the same computation (e.g. a custom instruction vs. its software equivalent).
+    pub fn analyze_lookups<F: JoltField>(&self) -> (Vec<InstructionUsage>, Vec<SubtableUsage>) {
+        lookup_usage::<F>(&self.raw_trace)
+    }
+
+    /// Per-region cycle counts, in the order in which the regions were first entered.
+    pub fn analyze_cycle_regions(&self) -> Vec<CycleRegion> {
+        cycle_regions(&self.raw_trace, &self.cycle_markers)
+    }
+
+    /// Per-function cycle counts, sorted by decreasing `total_trace_length`.
+    /// Requires a guest ELF with symbols (see `symbols`).
+    pub fn analyze_functions(&self) -> Vec<FunctionProfile> {
+        function_profiles(&self.raw_trace, &self.symbols)
+    }
+
+    /// Trace length per call stack, in the "folded stacks" format consumed by flame
+    /// graph tools (e.g. `inferno-flamegraph`): one `caller;callee count` line per
+    /// call stack.
+    pub fn folded_stacks(&self) -> Vec<(String, usize)> {
+        folded_stacks(&self.raw_trace, &self.symbols)
+    }
+
+    pub fn write_folded_stacks(&self, path: PathBuf) -> io::Result<()> {
+        let mut file = io::BufWriter::new(File::create(path)?);
+        for (stack, count) in self.folded_stacks() {
+            writeln!(file, "{stack} {count}")?;
+        }
+        file.flush()
+    }
+
     pub fn write_to_file(self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         let mut file = File::create(path)?;
-        let data = bincode::serialize(&self)?;
+        let data = bincode::serde::encode_to_vec(&self, bincode::config::legacy())?;
         io::Write::write_all(&mut file, &data)?;
         Ok(())
     }
 }
+
+/// A region is counted from its start marker up to (but not including) its end
+/// marker, so the marker instructions themselves are attributed to the enclosing
+/// region. Regions with the same label may nest; unmatched markers are ignored.
+fn cycle_regions(raw_trace: &[RVTraceRow], markers: &[CycleMarker]) -> Vec<CycleRegion> {
+    // trace_offsets[i] is the number of trace steps before the i-th RV32IM instruction
+    let mut trace_offsets = Vec::with_capacity(raw_trace.len() + 1);
+    trace_offsets.push(0);
+    for row in raw_trace {
+        let offset = trace_offsets.last().unwrap() + trace_length(row);
+        trace_offsets.push(offset);
+    }
+
+    let mut regions: Vec<CycleRegion> = vec![];
+    let mut open: HashMap<&str, Vec<usize>> = HashMap::new();
+    for marker in markers {
+        if marker.start {
+            open.entry(marker.label.as_str())
+                .or_default()
+                .push(marker.cycle);
+            if !regions.iter().any(|region| region.label == marker.label) {
+                regions.push(CycleRegion {
+                    label: marker.label.clone(),
+                    count: 0,
+                    rv32im_cycles: 0,
+                    trace_length: 0,
+                });
+            }
+        } else if let Some(start) = open
+            .get_mut(marker.label.as_str())
+            .and_then(|starts| starts.pop())
+        {
+            let region = regions
+                .iter_mut()
+                .find(|region| region.label == marker.label)
+                .unwrap();
+            region.count += 1;
+            region.rv32im_cycles += marker.cycle - start;
+            region.trace_length += trace_offsets[marker.cycle] - trace_offsets[start];
+        }
+    }
+
+    regions
+}
+
+fn lookup_usage<F: JoltField>(
+    raw_trace: &[RVTraceRow],
+) -> (Vec<InstructionUsage>, Vec<SubtableUsage>) {
+    let mut instructions: HashMap<RV32IM, InstructionUsage> = HashMap::new();
+    let mut subtables: HashMap<&'static str, usize> = HashMap::new();
+    // The subtables (and number of lookups into each) only depend on the opcode
+    let mut decompositions: HashMap<RV32IM, Vec<(&'static str, usize)>> = HashMap::new();
+    for row in raw_trace.iter().flat_map(|row| virtual_trace(row.clone())) {
+        let opcode = row.instruction.opcode;
+        let usage = instructions.entry(opcode).or_insert(InstructionUsage {
+            opcode,
+            count: 0,
+            lookups: 0,
+            subtable_lookups: 0,
+        });
+        usage.count += 1;
+
+        let Ok(instruction) = RV32I::try_from(&row) else {
+            continue;
+        };
+        let decomposition = decompositions.entry(opcode).or_insert_with(|| {
+            instruction
+                .subtables::<F>(C, M)
+                .into_iter()
+                .map(|(subtable, indices)| {
+                    let name: &'static str = RV32ISubtables::<F>::try_from(subtable.subtable_id())
+                        .expect("RV32I subtable")
+                        .into();
+                    (name, indices.len())
+                })
+                .collect()
+        });
+        usage.lookups += 1;
+        for &(name, num_lookups) in decomposition.iter() {
+            usage.subtable_lookups += num_lookups;
+            *subtables.entry(name).or_default() += num_lookups;
+        }
+    }
+
+    let mut instructions: Vec<_> = instructions.into_values().collect();
+    instructions.sort_by(|a, b| {
+        b.estimated_commitment_cost()
+            .cmp(&a.estimated_commitment_cost())
+            .then_with(|| b.count.cmp(&a.count))
+            .then_with(|| format!("{:?}", a.opcode).cmp(&format!("{:?}", b.opcode)))
+    });
+    let mut subtables: Vec<_> = subtables
+        .into_iter()
+        .map(|(name, lookups)| SubtableUsage {
+            name: name.to_string(),
+            lookups,
+        })
+        .collect();
+    subtables.sort_by(|a, b| b.lookups.cmp(&a.lookups).then_with(|| a.name.cmp(&b.name)));
+    (instructions, subtables)
+}
+
+/// Number of Jolt trace steps for `row`, i.e. the length of its virtual sequence.
+fn trace_length(row: &RVTraceRow) -> usize {
+    virtual_trace(row.clone()).len()
+}
+
+/// Reconstructs the call stack (outermost function first) at each RV32IM instruction,
+/// passing it to `on_step` along with the instruction's trace length, and passing each
+/// function called to `on_call`. `jal`/`jalr` linking into `ra` is a call, and
+/// `jalr x0, 0(ra)` is a return; jumps that leave the current function any other way
+/// (e.g. tail calls) replace the innermost frame.
+fn walk_call_stacks<'a>(
+    raw_trace: &[RVTraceRow],
+    symbols: &'a [FunctionSymbol],
+    mut on_call: impl FnMut(&'a str),
+    mut on_step: impl FnMut(&[&'a str], usize),
+) {
+    let mut stack: Vec<&str> = vec![];
+    let mut call_pending = false;
+    for row in raw_trace {
+        let function = resolve_function(symbols, row.instruction.address);
+        if call_pending {
+            stack.push(function);
+            on_call(function);
+        } else if stack.last() != Some(&function) {
+            stack.pop();
+            stack.push(function);
+        }
+        on_step(&stack, trace_length(row));
+
+        let instruction = &row.instruction;
+        let is_jump = matches!(instruction.opcode, RV32IM::JAL | RV32IM::JALR);
+        call_pending = is_jump && instruction.rd == Some(1);
+        let is_return = instruction.opcode == RV32IM::JALR
+            && instruction.rs1 == Some(1)
+            && instruction.rd == Some(0);
+        if is_return && stack.len() > 1 {
+            stack.pop();
+        }
+    }
+}
+
+fn function_profiles(raw_trace: &[RVTraceRow], symbols: &[FunctionSymbol]) -> Vec<FunctionProfile> {
+    let mut profiles: HashMap<&str, FunctionProfile> = HashMap::new();
+    let mut calls: HashMap<&str, usize> = HashMap::new();
+    let mut frames: Vec<&str> = vec![];
+    walk_call_stacks(
+        raw_trace,
+        symbols,
+        |function| *calls.entry(function).or_default() += 1,
+        |stack, trace_length| {
+            let profile = |function: &str| FunctionProfile {
+                name: function.to_string(),
+                calls: 0,
+                self_cycles: 0,
+                self_trace_length: 0,
+                total_trace_length: 0,
+            };
+            let innermost = *stack.last().unwrap();
+            let entry = profiles
+                .entry(innermost)
+                .or_insert_with(|| profile(innermost));
+            entry.self_cycles += 1;
+            entry.self_trace_length += trace_length;
+
+            // Recursive functions count once towards their total
+            frames.clear();
+            frames.extend_from_slice(stack);
+            frames.sort_unstable();
+            frames.dedup();
+            for &function in frames.iter() {
+                profiles
+                    .entry(function)
+                    .or_insert_with(|| profile(function))
+                    .total_trace_length += trace_length;
+            }
+        },
+    );
+
+    let mut profiles: Vec<_> = profiles
+        .into_iter()
+        .map(|(function, mut profile)| {
+            profile.calls = calls.get(function).copied().unwrap_or(0);
+            profile
+        })
+        .collect();
+    profiles.sort_by(|a, b| {
+        b.total_trace_length
+            .cmp(&a.total_trace_length)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    profiles
+}
+
+/// Trace length per call stack, sorted by call stack.
+fn folded_stacks(raw_trace: &[RVTraceRow], symbols: &[FunctionSymbol]) -> Vec<(String, usize)> {
+    let mut folded: HashMap<Vec<&str>, usize> = HashMap::new();
+    walk_call_stacks(
+        raw_trace,
+        symbols,
+        |_| {},
+        |stack, trace_length| match folded.get_mut(stack) {
+            Some(count) => *count += trace_length,
+            None => {
+                folded.insert(stack.to_vec(), trace_length);
+            }
+        },
+    );
+    let mut folded: Vec<_> = folded
+        .into_iter()
+        .map(|(stack, count)| (stack.join(";"), count))
+        .collect();
+    folded.sort();
+    folded
+}
+
+/// The name of the function containing `address`.
+fn resolve_function(symbols: &[FunctionSymbol], address: u64) -> &str {
+    let index = symbols.partition_point(|symbol| symbol.address <= address);
+    if index == 0 {
+        return UNKNOWN_FUNCTION;
+    }
+    let symbol = &symbols[index - 1];
+    // Symbols of unknown size extend to the next symbol
+    if symbol.size != 0 && address >= symbol.address + symbol.size {
+        return UNKNOWN_FUNCTION;
+    }
+    &symbol.name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use tracer::RegisterState;
+
+    fn row(opcode: RV32IM, rs1_val: u64, rs2_val: u64, rd_post_val: u64) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address: 0,
+                opcode,
+                rs1: Some(1),
+                rs2: Some(2),
+                rd: Some(3),
+                imm: None,
+                virtual_sequence_remaining: None,
+                compressed: false,
+            },
+            register_state: RegisterState {
+                rs1_val: Some(rs1_val),
+                rs2_val: Some(rs2_val),
+                rd_post_val: Some(rd_post_val),
+            },
+            memory_state: None,
+            advice_value: None,
+            precompile_input: None,
+            precompile_output_address: None,
+        }
+    }
+
+    fn marker(label: &str, start: bool, cycle: usize) -> CycleMarker {
+        CycleMarker {
+            label: label.to_string(),
+            start,
+            cycle,
+        }
+    }
+
+    #[test]
+    fn cycle_regions_counts_trace_length() {
+        let divu = row(RV32IM::DIVU, 7, 2, 3);
+        let divu_length = virtual_trace(divu.clone()).len();
+        let raw_trace = vec![
+            row(RV32IM::ADD, 1, 2, 3),
+            row(RV32IM::ADD, 1, 2, 3),
+            divu,
+            row(RV32IM::ADD, 1, 2, 3),
+            row(RV32IM::ADD, 1, 2, 3),
+            row(RV32IM::ADD, 1, 2, 3),
+        ];
+        let markers = vec![
+            marker("outer", true, 0),
+            marker("inner", true, 1),
+            marker("inner", false, 3),
+            marker("inner", true, 4),
+            marker("inner", false, 5),
+            marker("outer", false, 6),
+            marker("unmatched", false, 6),
+        ];
+
+        let regions = cycle_regions(&raw_trace, &markers);
+        assert_eq!(
+            regions,
+            vec![
+                CycleRegion {
+                    label: "outer".to_string(),
+                    count: 1,
+                    rv32im_cycles: 6,
+                    trace_length: 5 + divu_length,
+                },
+                CycleRegion {
+                    label: "inner".to_string(),
+                    count: 2,
+                    rv32im_cycles: 3,
+                    trace_length: 2 + divu_length,
+                },
+            ]
+        );
+    }
+
+    fn jump(address: u64, opcode: RV32IM, rs1: u64, rd: u64) -> RVTraceRow {
+        let mut row = row(opcode, 0, 0, 0);
+        row.instruction.address = address;
+        row.instruction.rs1 = Some(rs1);
+        row.instruction.rs2 = None;
+        row.instruction.rd = Some(rd);
+        row
+    }
+
+    fn add(address: u64) -> RVTraceRow {
+        let mut row = row(RV32IM::ADD, 1, 2, 3);
+        row.instruction.address = address;
+        row
+    }
+
+    #[test]
+    fn function_profiles_follow_calls() {
+        let symbols = vec![
+            FunctionSymbol {
+                name: "main".to_string(),
+                address: 0x100,
+                size: 0x20,
+            },
+            FunctionSymbol {
+                name: "f".to_string(),
+                address: 0x200,
+                size: 0x10,
+            },
+        ];
+        let raw_trace = vec![
+            add(0x100),
+            jump(0x104, RV32IM::JAL, 0, 1), // call f
+            add(0x200),
+            jump(0x204, RV32IM::JALR, 1, 0), // ret
+            add(0x108),
+            add(0x300), // outside of any symbol
+        ];
+
+        assert_eq!(
+            function_profiles(&raw_trace, &symbols),
+            vec![
+                FunctionProfile {
+                    name: "main".to_string(),
+                    calls: 0,
+                    self_cycles: 3,
+                    self_trace_length: 3,
+                    total_trace_length: 5,
+                },
+                FunctionProfile {
+                    name: "f".to_string(),
+                    calls: 1,
+                    self_cycles: 2,
+                    self_trace_length: 2,
+                    total_trace_length: 2,
+                },
+                FunctionProfile {
+                    name: "[unknown]".to_string(),
+                    calls: 0,
+                    self_cycles: 1,
+                    self_trace_length: 1,
+                    total_trace_length: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            folded_stacks(&raw_trace, &symbols),
+            vec![
+                ("[unknown]".to_string(), 1),
+                ("main".to_string(), 3),
+                ("main;f".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_usage_counts_subtable_lookups() {
+        let add = row(RV32IM::ADD, 1, 2, 3);
+        let sw = row(RV32IM::SW, 1, 2, 0);
+        let raw_trace = vec![add.clone(), add.clone(), sw];
+        let add_subtable_lookups: usize = RV32I::try_from(&add)
+            .unwrap()
+            .subtables::<Fr>(C, M)
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .sum();
+
+        let (instructions, subtables) = lookup_usage::<Fr>(&raw_trace);
+        let add_usage = instructions
+            .iter()
+            .find(|usage| usage.opcode == RV32IM::ADD)
+            .unwrap();
+        assert_eq!(add_usage.count, 2);
+        assert_eq!(add_usage.lookups, 2);
+        assert_eq!(add_usage.subtable_lookups, 2 * add_subtable_lookups);
+        // Stores don't perform lookups
+        let sw_usage = instructions
+            .iter()
+            .find(|usage| usage.opcode == RV32IM::SW)
+            .unwrap();
+        assert_eq!(sw_usage.lookups, 0);
+        assert_eq!(sw_usage.estimated_commitment_cost(), 0);
+        assert_eq!(
+            subtables.iter().map(|usage| usage.lookups).sum::<usize>(),
+            2 * add_subtable_lookups
+        );
+    }
+}
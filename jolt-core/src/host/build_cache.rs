@@ -0,0 +1,121 @@
+//! Content-addressed cache of guest ELFs, shared by all `Program`s, so that unchanged
+//! guests are not rebuilt.
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha3::{Digest, Keccak256};
+
+use crate::jolt::vm::archive::to_hex;
+
+/// Environment variable overriding the directory guest ELFs are cached in.
+pub const GUEST_CACHE_DIR_ENV_VAR: &str = "JOLT_GUEST_CACHE_DIR";
+const DEFAULT_GUEST_CACHE_DIR: &str = "/tmp/jolt-guest-cache";
+
+/// The directory guest ELFs are cached in.
+pub fn cache_dir() -> PathBuf {
+    env::var_os(GUEST_CACHE_DIR_ENV_VAR)
+        .map_or_else(|| PathBuf::from(DEFAULT_GUEST_CACHE_DIR), PathBuf::from)
+}
+
+/// The key a guest is cached under: a hash of the sources of the workspace it is built
+/// in (every `.rs`, `.toml` and `.lock` file under `workspace_root`, outside of target
+/// and hidden directories) and `build_config`, which holds everything else the build
+/// depends on (e.g. the toolchain, compiler flags and linker script).
+pub(crate) fn cache_key(workspace_root: &Path, build_config: &[&[u8]]) -> io::Result<String> {
+    let mut hasher = Keccak256::new();
+    for part in build_config {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hash_sources(workspace_root, workspace_root, &mut hasher)?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// The root of the workspace `cargo` builds in from `dir`: the closest ancestor of
+/// `dir` holding a `Cargo.lock`.
+pub(crate) fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| ancestor.join("Cargo.lock").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// The cached ELF of `guest` for `key`, if any.
+pub(crate) fn lookup(guest: &str, key: &str) -> Option<PathBuf> {
+    let path = elf_path(guest, key);
+    path.is_file().then_some(path)
+}
+
+/// Copies the ELF at `elf` into the cache, returning the path of the cached copy.
+pub(crate) fn store(guest: &str, key: &str, elf: &Path) -> io::Result<PathBuf> {
+    let path = elf_path(guest, key);
+    fs::create_dir_all(path.parent().unwrap())?;
+    // Copy then rename, so that concurrent builds never observe a partial ELF
+    let tmp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+    fs::copy(elf, &tmp_path)?;
+    fs::rename(&tmp_path, &path)?;
+    Ok(path)
+}
+
+fn elf_path(guest: &str, key: &str) -> PathBuf {
+    cache_dir().join(format!("{}-{}", guest, key))
+}
+
+fn hash_sources(root: &Path, dir: &Path, hasher: &mut Keccak256) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                hash_sources(root, &path, hasher)?;
+            }
+        } else if file_type.is_file()
+            && matches!(
+                path.extension().and_then(|extension| extension.to_str()),
+                Some("rs" | "toml" | "lock")
+            )
+        {
+            let relative_path = path.strip_prefix(root).unwrap().to_string_lossy();
+            let contents = fs::read(&path)?;
+            hasher.update((relative_path.len() as u64).to_le_bytes());
+            hasher.update(relative_path.as_bytes());
+            hasher.update((contents.len() as u64).to_le_bytes());
+            hasher.update(&contents);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_key_tracks_sources() {
+        let root = env::temp_dir().join(format!("jolt-build-cache-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("Cargo.lock"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "fn main() {}").unwrap();
+        assert_eq!(workspace_root(&root.join("src")), Some(root.clone()));
+
+        let key = cache_key(&root, &[b"opt-level=z"]).unwrap();
+        assert_eq!(key, cache_key(&root, &[b"opt-level=z"]).unwrap());
+        assert_ne!(key, cache_key(&root, &[b"opt-level=3"]).unwrap());
+
+        // Build outputs and non-source files do not affect the key
+        fs::write(root.join("target/guest"), "elf").unwrap();
+        fs::write(root.join("README.md"), "guest").unwrap();
+        assert_eq!(key, cache_key(&root, &[b"opt-level=z"]).unwrap());
+
+        fs::write(root.join("src/lib.rs"), "fn main() { loop {} }").unwrap();
+        assert_ne!(key, cache_key(&root, &[b"opt-level=z"]).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
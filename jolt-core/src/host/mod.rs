@@ -4,71 +4,149 @@ use core::str::FromStr;
 use std::{
     fs::{self, File},
     io::{self, Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
+    sync::Arc,
 };
 
 use postcard;
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
+use sha3::{Digest, Keccak256};
 
 use common::{
     constants::{
         DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+        GUARD_REGION_SIZE,
     },
-    rv_trace::JoltDevice,
+    rv_trace::{IoCodec, JoltDevice, MemoryLayout, RVTraceRow},
+};
+pub use tracer::{
+    DecodeError, ELFInstruction, FaultCause, GuestFault, HintProvider, MemoryAccess,
+    MemoryAccessKind, MmioHandler, TraceIter, TraceOptions,
 };
-pub use tracer::ELFInstruction;
 
 use crate::{
     field::JoltField,
     jolt::{
         instruction::{
-            div::DIVInstruction, divu::DIVUInstruction, lb::LBInstruction, lbu::LBUInstruction,
-            lh::LHInstruction, lhu::LHUInstruction, mulh::MULHInstruction,
-            mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction,
-            sb::SBInstruction, sh::SHInstruction, VirtualInstructionSequence,
+            atomic::{
+                AMOADDInstruction, AMOANDInstruction, AMOMAXInstruction, AMOMAXUInstruction,
+                AMOMINInstruction, AMOMINUInstruction, AMOORInstruction, AMOSWAPInstruction,
+                AMOXORInstruction, SCInstruction,
+            },
+            div::DIVInstruction,
+            divu::DIVUInstruction,
+            fixed_point::{FIXEDMULInstruction, SATURATINGADDInstruction},
+            lb::LBInstruction,
+            lbu::LBUInstruction,
+            lh::LHInstruction,
+            lhu::LHUInstruction,
+            misaligned::{LWMisalignedInstruction, SWMisalignedInstruction},
+            mulh::MULHInstruction,
+            mulhsu::MULHSUInstruction,
+            rem::REMInstruction,
+            remu::REMUInstruction,
+            sb::SBInstruction,
+            sh::SHInstruction,
+            tree_step::TREESTEPInstruction,
+            VirtualInstructionSequence,
         },
         vm::{bytecode::BytecodeRow, rv32i_vm::RV32I, JoltTraceStep},
     },
+    utils::errors::{GuestPanic, JoltError, TraceError, TraceRowError},
 };
 
 use self::analyze::ProgramSummary;
 #[cfg(not(target_arch = "wasm32"))]
-use self::toolchain::{install_no_std_toolchain, install_toolchain};
+use self::toolchain::{install_no_std_toolchain, install_toolchain, TOOLCHAIN_TAG};
+pub use self::trace_store::TraceStore;
 
 pub mod analyze;
+pub mod build_cache;
+pub mod session;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod toolchain;
+pub mod trace_store;
 
 #[derive(Clone)]
 pub struct Program {
     guest: String,
     func: Option<String>,
     input: Vec<u8>,
+    private_input: Vec<u8>,
+    input_stream: Vec<u8>,
+    advice_tape: Vec<u8>,
+    hint_provider: Option<HintProvider>,
+    mmio_handler: Option<MmioHandler>,
     memory_size: u64,
     stack_size: u64,
+    heap_size: Option<u64>,
     max_input_size: u64,
     max_output_size: u64,
+    max_advice_size: u64,
+    io_codec: IoCodec,
     std: bool,
+    misaligned_accesses: bool,
+    compressed_instructions: bool,
     pub elf: Option<PathBuf>,
 }
 
+/// The result of `Program::execute`.
+#[derive(Clone, Debug)]
+pub struct ExecutionSummary {
+    pub io_device: JoltDevice,
+    /// Number of RISC-V instructions executed by the guest.
+    pub cycle_count: usize,
+    /// Length of the trace the guest would be proven with, i.e. after expanding
+    /// virtual sequences.
+    pub trace_length: usize,
+    /// `trace_length` padded to the next power of two, which determines proving cost.
+    pub padded_trace_length: usize,
+}
+
 impl Program {
     pub fn new(guest: &str) -> Self {
         Self {
             guest: guest.to_string(),
             func: None,
             input: Vec::new(),
+            private_input: Vec::new(),
+            input_stream: Vec::new(),
+            advice_tape: Vec::new(),
+            hint_provider: None,
+            mmio_handler: None,
             memory_size: DEFAULT_MEMORY_SIZE,
             stack_size: DEFAULT_STACK_SIZE,
+            heap_size: None,
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
             max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+            max_advice_size: 0,
+            io_codec: IoCodec::default(),
             std: false,
+            misaligned_accesses: false,
+            compressed_instructions: false,
             elf: None,
         }
     }
 
+    /// A program for an RV32IM ELF built outside of Jolt (e.g. from C or Zig, or Rust
+    /// without `#[jolt::provable]`), which `build` leaves as is. The ELF must be linked
+    /// so that its program I/O is at the addresses given by `memory_layout`, which also
+    /// sets the program's maximum input and output sizes.
+    pub fn from_elf(elf: &Path, memory_layout: &MemoryLayout) -> Self {
+        let guest = elf
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        let mut program = Self::new(&guest);
+        program.max_input_size = memory_layout.max_input_size;
+        program.max_output_size = memory_layout.max_output_size;
+        program.max_advice_size = memory_layout.max_advice_size;
+        program.io_codec = memory_layout.io_codec;
+        program.elf = Some(elf.to_path_buf());
+        program
+    }
+
     pub fn set_std(&mut self, std: bool) {
         self.std = std;
     }
@@ -78,10 +156,110 @@ impl Program {
     }
 
     pub fn set_input<T: Serialize>(&mut self, input: &T) {
-        let mut serialized = postcard::to_stdvec(input).unwrap();
+        let mut serialized = self.serialize(input);
         self.input.append(&mut serialized);
     }
 
+    /// Appends `input`, already serialized with the program's codec, to the inputs.
+    pub fn set_input_bytes(&mut self, input: &[u8]) {
+        self.input.extend_from_slice(input);
+    }
+
+    /// Appends `input` to the private inputs, which are read by the guest as advice
+    /// and are not part of the program I/O the verifier sees.
+    pub fn set_private_input<T: Serialize>(&mut self, input: &T) {
+        let mut serialized = self.serialize(input);
+        self.private_input.append(&mut serialized);
+    }
+
+    /// Sets the codec the inputs and outputs are serialized with (postcard by default),
+    /// which must match the guest's. Inputs already set are not re-serialized.
+    pub fn set_io_codec(&mut self, io_codec: IoCodec) {
+        self.io_codec = io_codec;
+    }
+
+    /// The layout of the program's I/O in guest memory.
+    pub fn memory_layout(&self) -> MemoryLayout {
+        MemoryLayout::new(self.max_input_size, self.max_output_size)
+            .with_max_advice_size(self.max_advice_size)
+            .with_io_codec(self.io_codec)
+    }
+
+    fn trace_options(&self) -> TraceOptions<'_> {
+        TraceOptions {
+            inputs: &self.input,
+            private_inputs: &self.private_input,
+            input_stream: &self.input_stream,
+            advice_tape: &self.advice_tape,
+            hint_provider: self.hint_provider.clone(),
+            mmio_handler: self.mmio_handler.clone(),
+            memory_layout: self.memory_layout(),
+        }
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Vec<u8> {
+        match self.io_codec {
+            IoCodec::Postcard => postcard::to_stdvec(value).unwrap(),
+            // Must match the configuration of `jolt::codec::Bincode`
+            IoCodec::Bincode => {
+                bincode::serde::encode_to_vec(value, bincode::config::standard()).unwrap()
+            }
+        }
+    }
+
+    /// Keccak-256 digest of the private inputs. Used as the (only) public input of
+    /// guests that commit to their inputs (`#[jolt::provable(commit_inputs)]`).
+    pub fn private_input_digest(&self) -> [u8; 32] {
+        Keccak256::digest(&self.private_input).into()
+    }
+
+    /// Sets the input stream, which the guest reads on demand with `jolt::read_chunk`.
+    /// Unlike the inputs, its size is not bounded by `max_input_size`. The bytes the
+    /// guest reads are part of the program I/O (see `JoltDevice::input_stream`).
+    pub fn set_input_stream(&mut self, input_stream: &[u8]) {
+        self.input_stream = input_stream.to_vec();
+    }
+
+    /// Sets the initial contents of the advice tape (see `set_max_advice_size`), which
+    /// the guest reads and writes like memory with `jolt::io::advice_tape`. The tape is
+    /// committed to by the prover rather than being part of the program I/O, so it can
+    /// hold large witnesses at no cost to the verifier; like hints, its contents are
+    /// advice, which the guest must check.
+    pub fn set_advice_tape(&mut self, advice_tape: &[u8]) {
+        self.advice_tape = advice_tape.to_vec();
+    }
+
+    /// Sets the function answering the guest's `jolt::hint_with` requests. Requests
+    /// and hints are (de)serialized with postcard, so `Req` and `Resp` must match the
+    /// types used by the guest. Hints are advice: the guest must check them.
+    pub fn set_hint_provider<Req, Resp>(
+        &mut self,
+        hint_provider: impl Fn(Req) -> Resp + Send + Sync + 'static,
+    ) where
+        Req: DeserializeOwned,
+        Resp: Serialize,
+    {
+        self.hint_provider = Some(Arc::new(move |request: &[u8]| {
+            let request = postcard::from_bytes(request).expect("malformed hint request");
+            postcard::to_stdvec(&hint_provider(request)).unwrap()
+        }));
+    }
+
+    /// Sets the function supplying the advice tape on demand, for the guest's
+    /// `jolt::mmio_read`s: past the contents set with `set_advice_tape`, it is called
+    /// during tracing the first time the guest accesses each word of the tape, with the
+    /// word's byte offset in the tape, and returns the word, or `None` if it has none
+    /// (which faults the guest). The guest can thus fetch data as it needs it (e.g. a
+    /// Merkle branch) instead of receiving it all upfront. The words become part of the
+    /// tape, which the prover commits to; like hints, they are advice that the guest
+    /// must check. The guest needs a `max_advice_size` covering every offset it reads.
+    pub fn set_mmio_handler(
+        &mut self,
+        mmio_handler: impl Fn(u64) -> Option<u32> + Send + Sync + 'static,
+    ) {
+        self.mmio_handler = Some(Arc::new(mmio_handler));
+    }
+
     pub fn set_memory_size(&mut self, len: u64) {
         self.memory_size = len;
     }
@@ -90,6 +268,27 @@ impl Program {
         self.stack_size = len;
     }
 
+    /// Bounds the guest's heap; by default, it takes up the rest of the guest's memory.
+    pub fn set_heap_size(&mut self, len: u64) {
+        self.heap_size = Some(len);
+    }
+
+    /// Allows the guest's word loads and stores to access misaligned addresses (e.g. in
+    /// packed structs), which otherwise panic. Every LW and SW of the guest then costs
+    /// a virtual sequence of 11 and 21 cycles respectively, whether or not it is
+    /// misaligned. An ELF built outside of Jolt opts in by defining the
+    /// `_JOLT_MISALIGNED_ACCESSES` symbol.
+    pub fn set_misaligned_accesses(&mut self, enabled: bool) {
+        self.misaligned_accesses = enabled;
+    }
+
+    /// Builds the guest with the C extension, whose 16-bit compressed instructions make
+    /// the guest's bytecode smaller. An ELF built outside of Jolt with the C extension
+    /// needs no opt-in.
+    pub fn set_compressed_instructions(&mut self, enabled: bool) {
+        self.compressed_instructions = enabled;
+    }
+
     pub fn set_max_input_size(&mut self, size: u64) {
         self.max_input_size = size;
     }
@@ -98,6 +297,12 @@ impl Program {
         self.max_output_size = size;
     }
 
+    /// Reserves an advice tape of at least `size` bytes (rounded up to a power of two
+    /// number of words); the program has none by default.
+    pub fn set_max_advice_size(&mut self, size: u64) {
+        self.max_advice_size = size;
+    }
+
     #[tracing::instrument(skip_all, name = "Program::build")]
     pub fn build(&mut self) {
         if self.elf.is_none() {
@@ -106,20 +311,22 @@ impl Program {
             #[cfg(not(target_arch = "wasm32"))]
             install_no_std_toolchain().unwrap();
 
-            self.save_linker();
+            let linker_script = self.save_linker();
 
-            let rust_flags = [
+            let linker_arg = format!("link-arg=-T{}", self.linker_path());
+            let mut rust_flags = vec![
                 "-C",
-                &format!("link-arg=-T{}", self.linker_path()),
+                &linker_arg,
                 "-C",
                 "passes=lower-atomic",
                 "-C",
                 "panic=abort",
                 "-C",
-                "strip=symbols",
-                "-C",
                 "opt-level=z",
             ];
+            if self.compressed_instructions {
+                rust_flags.extend(["-C", "target-feature=+c"]);
+            }
 
             let toolchain = if self.std {
                 "riscv32im-jolt-zkvm-elf"
@@ -143,6 +350,15 @@ impl Program {
                 self.func.as_ref().unwrap_or(&"".to_string())
             );
 
+            let cache_key = self.build_cache_key(toolchain, &envs, &linker_script);
+            if let Some(elf) = cache_key
+                .as_ref()
+                .and_then(|key| build_cache::lookup(&self.guest, key))
+            {
+                self.elf = Some(elf);
+                return;
+            }
+
             let output = Command::new("cargo")
                 .envs(envs)
                 .args([
@@ -166,73 +382,158 @@ impl Program {
             }
 
             let elf = format!("{}/{}/release/{}", target, toolchain, self.guest);
-            self.elf = Some(PathBuf::from_str(&elf).unwrap());
+            let elf = PathBuf::from_str(&elf).unwrap();
+            let elf = match &cache_key {
+                Some(key) => build_cache::store(&self.guest, key, &elf).unwrap_or(elf),
+                None => elf,
+            };
+            self.elf = Some(elf);
+        }
+    }
+
+    /// The key this program's ELF is cached under (see `build_cache::cache_key`), or
+    /// `None` if it is not built in a cargo workspace.
+    fn build_cache_key(
+        &self,
+        toolchain: &str,
+        envs: &[(&str, String)],
+        linker_script: &str,
+    ) -> Option<String> {
+        let workspace_root = build_cache::workspace_root(&std::env::current_dir().ok()?)?;
+        let mut build_config: Vec<&[u8]> = vec![
+            toolchain.as_bytes(),
+            self.guest.as_bytes(),
+            linker_script.as_bytes(),
+        ];
+        #[cfg(not(target_arch = "wasm32"))]
+        build_config.push(TOOLCHAIN_TAG.as_bytes());
+        for (key, value) in envs {
+            build_config.push(key.as_bytes());
+            build_config.push(value.as_bytes());
         }
+        build_cache::cache_key(&workspace_root, &build_config).ok()
     }
 
+    /// Decodes the program's bytecode and initial memory. Panics if the ELF file cannot be
+    /// read or decoded; see `try_decode`.
     pub fn decode(&mut self) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
+        self.try_decode().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `decode`, but fails if the ELF file cannot be read or decoded.
+    pub fn try_decode(&mut self) -> Result<(Vec<ELFInstruction>, Vec<(u64, u8)>), JoltError> {
         self.build();
         let elf = self.elf.as_ref().unwrap();
-        let mut elf_file =
-            File::open(elf).unwrap_or_else(|_| panic!("could not open elf file: {:?}", elf));
+        let mut elf_file = File::open(elf)?;
         let mut elf_contents = Vec::new();
-        elf_file.read_to_end(&mut elf_contents).unwrap();
-        tracer::decode(&elf_contents)
+        elf_file.read_to_end(&mut elf_contents)?;
+        Ok(tracer::try_decode(&elf_contents)?)
     }
 
+    /// Runs the program in the tracer. The returned `JoltDevice` holds the program's
+    /// inputs, outputs and logs (see `JoltDevice::log_lines`). Panics if the guest
+    /// faults; see `try_trace`.
     // TODO(moodlezoup): Make this generic over InstructionSet
     #[tracing::instrument(skip_all, name = "Program::trace")]
     pub fn trace(&mut self) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>) {
+        self.trace_checked()
+            .unwrap_or_else(|fault| panic!("{}", fault))
+    }
+
+    fn trace_checked(&mut self) -> Result<(JoltDevice, Vec<JoltTraceStep<RV32I>>), TraceError> {
         self.build();
         let elf = self.elf.clone().unwrap();
-        let (raw_trace, io_device) =
-            tracer::trace(&elf, &self.input, self.max_input_size, self.max_output_size);
+        let (raw_trace, io_device) = tracer::trace(&elf, self.trace_options())?;
 
-        let trace: Vec<_> = raw_trace
+        let trace = raw_trace
             .into_par_iter()
-            .flat_map(|row| match row.instruction.opcode {
-                tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::REM => REMInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::SH => SHInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::SB => SBInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::LBU => LBUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::LHU => LHUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::LB => LBInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::LH => LHInstruction::<32>::virtual_trace(row),
-                _ => vec![row],
-            })
-            .map(|row| {
-                let instruction_lookup = if let Ok(jolt_instruction) = RV32I::try_from(&row) {
-                    Some(jolt_instruction)
-                } else {
-                    // Instruction does not use lookups
-                    None
-                };
-
-                JoltTraceStep {
-                    instruction_lookup,
-                    bytecode_row: BytecodeRow::from_instruction::<RV32I>(&row.instruction),
-                    memory_ops: (&row).into(),
-                    circuit_flags: row.instruction.to_circuit_flags(),
+            .flat_map(virtual_trace)
+            .map(|row| try_trace_step(&row))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((io_device, trace))
+    }
+
+    /// Like `trace`, but returns the raw trace as an iterator which runs the program as it
+    /// is consumed, so that rows can be processed (and dropped) as they are generated.
+    /// `trace_steps` converts the rows into the form consumed by `Jolt::prove`, and
+    /// `TraceIter::finish` returns the program I/O once they have been consumed.
+    pub fn trace_iter(&mut self) -> TraceIter {
+        self.build();
+        let elf = self.elf.clone().unwrap();
+        tracer::trace_iter(&elf, self.trace_options())
+    }
+
+    /// Runs the program without recording its trace, which is much faster than `trace`.
+    /// Useful to check a guest's outputs, or to estimate the cost of proving it.
+    #[tracing::instrument(skip_all, name = "Program::execute")]
+    pub fn execute(&mut self) -> ExecutionSummary {
+        self.build();
+        let elf = self.elf.clone().unwrap();
+        let (io_device, opcode_counts) =
+            tracer::execute(&elf, self.trace_options()).unwrap_or_else(|fault| panic!("{}", fault));
+
+        let cycle_count = opcode_counts.values().sum();
+        let trace_length = opcode_counts
+            .iter()
+            .map(|(opcode, count)| opcode.virtual_sequence_length() * count)
+            .sum::<usize>();
+
+        ExecutionSummary {
+            io_device,
+            cycle_count,
+            trace_length,
+            padded_trace_length: trace_length.next_power_of_two(),
+        }
+    }
+
+    /// Like `trace`, but writes the trace to `dir` (in chunks of `chunk_size` rows) as
+    /// it is generated, so that tracing never holds more than a chunk in memory. The
+    /// returned `TraceStore` reads it back in the form consumed by `Jolt::prove`, which
+    /// still collects the whole trace.
+    pub fn trace_to_disk(
+        &mut self,
+        dir: &Path,
+        chunk_size: usize,
+    ) -> io::Result<(JoltDevice, TraceStore)> {
+        self.build();
+        let elf = self.elf.clone().unwrap();
+        let mut store = TraceStore::create(dir)?;
+        let mut result = Ok(());
+        let (io_device, _) =
+            tracer::trace_chunked(&elf, self.trace_options(), chunk_size, |chunk| {
+                if result.is_ok() {
+                    result = store.append(&chunk);
                 }
             })
-            .collect();
+            .unwrap_or_else(|fault| panic!("{}", fault));
+        result?;
+        Ok((io_device, store))
+    }
 
-        (io_device, trace)
+    /// Like `trace`, but fails if the guest panicked or faulted. A panicking execution
+    /// can still be proven (the panic bit is part of the program I/O), so `trace` does
+    /// not fail on a panic. A fault (e.g. a stack overflow) cannot be proven; the
+    /// returned `GuestFault` holds the faulting access and the accesses preceding it.
+    pub fn try_trace(&mut self) -> Result<(JoltDevice, Vec<JoltTraceStep<RV32I>>), TraceError> {
+        let (io_device, trace) = self.trace_checked()?;
+        match io_device.panic_message() {
+            Some(message) => Err(GuestPanic { message }.into()),
+            None => Ok((io_device, trace)),
+        }
     }
 
     pub fn trace_analyze<F: JoltField>(mut self) -> ProgramSummary {
         self.build();
         let elf = self.elf.as_ref().unwrap();
-        let (raw_trace, _) =
-            tracer::trace(elf, &self.input, self.max_input_size, self.max_output_size);
+        let (raw_trace, _, cycle_markers) =
+            tracer::trace_with_cycle_markers(elf, self.trace_options())
+                .unwrap_or_else(|fault| panic!("{}", fault));
 
         let (bytecode, memory_init) = self.decode();
         let (io_device, processed_trace) = self.trace();
+        let elf_contents = fs::read(self.elf.as_ref().unwrap()).unwrap();
+        let symbols = tracer::function_symbols(&elf_contents);
 
         ProgramSummary {
             raw_trace,
@@ -240,10 +541,13 @@ impl Program {
             memory_init,
             io_device,
             processed_trace,
+            cycle_markers,
+            symbols,
         }
     }
 
-    fn save_linker(&self) {
+    /// Writes the program's linker script to `linker_path`, returning its contents.
+    fn save_linker(&self) -> String {
         let linker_path = PathBuf::from_str(&self.linker_path()).unwrap();
         if let Some(parent) = linker_path.parent() {
             fs::create_dir_all(parent).expect("could not create linker file");
@@ -251,11 +555,28 @@ impl Program {
 
         let linker_script = LINKER_SCRIPT_TEMPLATE
             .replace("{MEMORY_SIZE}", &self.memory_size.to_string())
-            .replace("{STACK_SIZE}", &self.stack_size.to_string());
+            .replace("{STACK_SIZE}", &self.stack_size.to_string())
+            .replace(
+                "{HEAP_SIZE}",
+                &self.heap_size.map_or(
+                    "ORIGIN(program) + LENGTH(program) - . - {GUARD_SIZE}".to_string(),
+                    |heap_size| heap_size.to_string(),
+                ),
+            )
+            .replace("{GUARD_SIZE}", &GUARD_REGION_SIZE.to_string())
+            .replace(
+                "{MISALIGNED_ACCESSES}",
+                if self.misaligned_accesses {
+                    "_JOLT_MISALIGNED_ACCESSES = 1;"
+                } else {
+                    ""
+                },
+            );
 
         let mut file = File::create(linker_path).expect("could not create linker file");
         file.write_all(linker_script.as_bytes())
             .expect("could not save linker");
+        linker_script
     }
 
     fn linker_path(&self) -> String {
@@ -263,6 +584,73 @@ impl Program {
     }
 }
 
+/// Converts raw trace rows (e.g. from `Program::trace_iter`) into the steps consumed by
+/// `Jolt::prove`, expanding virtual sequences.
+pub fn trace_steps(
+    rows: impl IntoIterator<Item = RVTraceRow>,
+) -> impl Iterator<Item = JoltTraceStep<RV32I>> {
+    rows.into_iter()
+        .flat_map(virtual_trace)
+        .map(|row| trace_step(&row))
+}
+
+/// Converts a (virtual) trace row into the step consumed by the prover. Panics if the row
+/// is missing an operand of its lookup; see `try_trace_step`.
+pub(crate) fn trace_step(row: &RVTraceRow) -> JoltTraceStep<RV32I> {
+    try_trace_step(row).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `trace_step`, but fails if the row is missing an operand of its lookup.
+pub(crate) fn try_trace_step(row: &RVTraceRow) -> Result<JoltTraceStep<RV32I>, TraceRowError> {
+    let instruction_lookup = match RV32I::try_from(row) {
+        Ok(jolt_instruction) => Some(jolt_instruction),
+        // Instruction does not use lookups
+        Err(TraceRowError::NoLookup(_)) => None,
+        Err(err) => return Err(err),
+    };
+
+    Ok(JoltTraceStep {
+        instruction_lookup,
+        bytecode_row: BytecodeRow::from_instruction::<RV32I>(&row.instruction),
+        memory_ops: row.into(),
+        circuit_flags: row.instruction.to_circuit_flags(),
+    })
+}
+
+/// Expands `row` into its virtual sequence, if its instruction has one.
+pub(crate) fn virtual_trace(row: RVTraceRow) -> Vec<RVTraceRow> {
+    match row.instruction.opcode {
+        tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::REM => REMInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SH => SHInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SB => SBInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::LBU => LBUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::LHU => LHUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::LB => LBInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::LH => LHInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SC_W => SCInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOSWAP_W => AMOSWAPInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOADD_W => AMOADDInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOXOR_W => AMOXORInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOAND_W => AMOANDInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOOR_W => AMOORInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOMIN_W => AMOMINInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOMAX_W => AMOMAXInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOMINU_W => AMOMINUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::AMOMAXU_W => AMOMAXUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::LW_MISALIGNED => LWMisalignedInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SW_MISALIGNED => SWMisalignedInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::TREE_STEP => TREESTEPInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::FIXED_MUL => FIXEDMULInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SATURATING_ADD => SATURATINGADDInstruction::<32>::virtual_trace(row),
+        _ => vec![row],
+    }
+}
+
 const LINKER_SCRIPT_TEMPLATE: &str = r#"
 MEMORY {
   program (rwx) : ORIGIN = 0x80000000, LENGTH = {MEMORY_SIZE}
@@ -286,9 +674,16 @@ SECTIONS {
   } > program
 
   . = ALIGN(8);
+  _STACK_GUARD = .;
+  . = . + {GUARD_SIZE};
   . = . + {STACK_SIZE};
   _STACK_PTR = .;
   . = ALIGN(8);
   _HEAP_PTR = .;
+  . = . + {HEAP_SIZE};
+  _HEAP_END = .;
+  . = . + {GUARD_SIZE};
 }
+
+{MISALIGNED_ACCESSES}
 "#;
@@ -0,0 +1,388 @@
+//! A `ProvingSession` bundles a guest program with its preprocessing so that
+//! the decode → preprocess → trace → prove → verify flow is driven by a single
+//! object. Since the prover and verifier both use the preprocessing owned by the
+//! session, they cannot disagree on bytecode/memory/trace size bounds.
+use std::marker::PhantomData;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use common::{
+    constants::{
+        DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+    },
+    rv_trace::JoltDevice,
+};
+
+use super::Program;
+use crate::{
+    field::JoltField,
+    jolt::compat::{self, CompatReport, JoltParameters, ProofHeader, CRATE_VERSION},
+    jolt::vm::{
+        archive::ProverTelemetry,
+        config::JoltConfig,
+        rv32i_vm::{RV32IJoltProof, RV32IJoltVM, C, M, RV32I},
+        Jolt, JoltCommitments, JoltPreprocessing, JoltTraceStep, JoltVerifierKey, ProverDebugInfo,
+    },
+    poly::commitment::commitment_scheme::CommitmentScheme,
+    utils::{
+        errors::{JoltError, ProofVerifyError},
+        transcript::Transcript,
+    },
+};
+
+/// Default bound on the bytecode size, memory address and trace length used to
+/// size the commitment scheme's setup.
+pub const DEFAULT_MAX_PREPROCESSING_SIZE: usize = 1 << 20;
+
+/// Options for a `ProvingSession`, set via `ProvingSessionBuilder`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionConfig {
+    pub guest: String,
+    pub func: Option<String>,
+    pub std: bool,
+    pub memory_size: u64,
+    pub stack_size: u64,
+    pub max_input_size: u64,
+    pub max_output_size: u64,
+    pub max_bytecode_size: usize,
+    pub max_memory_address: usize,
+    pub max_trace_length: usize,
+    pub telemetry: bool,
+    pub segment_length: Option<usize>,
+    pub zero_knowledge: bool,
+}
+
+pub struct ProvingSessionBuilder<F, PCS, ProofTranscript> {
+    config: SessionConfig,
+    _marker: PhantomData<(F, PCS, ProofTranscript)>,
+}
+
+impl<F, PCS, ProofTranscript> ProvingSessionBuilder<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    fn new(guest: &str) -> Self {
+        Self {
+            config: SessionConfig {
+                guest: guest.to_string(),
+                func: None,
+                std: false,
+                memory_size: DEFAULT_MEMORY_SIZE,
+                stack_size: DEFAULT_STACK_SIZE,
+                max_input_size: DEFAULT_MAX_INPUT_SIZE,
+                max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+                max_bytecode_size: DEFAULT_MAX_PREPROCESSING_SIZE,
+                max_memory_address: DEFAULT_MAX_PREPROCESSING_SIZE,
+                max_trace_length: DEFAULT_MAX_PREPROCESSING_SIZE,
+                telemetry: false,
+                segment_length: None,
+                zero_knowledge: false,
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn func(mut self, func: &str) -> Self {
+        self.config.func = Some(func.to_string());
+        self
+    }
+
+    pub fn std(mut self, std: bool) -> Self {
+        self.config.std = std;
+        self
+    }
+
+    pub fn memory_size(mut self, memory_size: u64) -> Self {
+        self.config.memory_size = memory_size;
+        self
+    }
+
+    pub fn stack_size(mut self, stack_size: u64) -> Self {
+        self.config.stack_size = stack_size;
+        self
+    }
+
+    pub fn max_input_size(mut self, max_input_size: u64) -> Self {
+        self.config.max_input_size = max_input_size;
+        self
+    }
+
+    pub fn max_output_size(mut self, max_output_size: u64) -> Self {
+        self.config.max_output_size = max_output_size;
+        self
+    }
+
+    pub fn max_bytecode_size(mut self, max_bytecode_size: usize) -> Self {
+        self.config.max_bytecode_size = max_bytecode_size;
+        self
+    }
+
+    pub fn max_memory_address(mut self, max_memory_address: usize) -> Self {
+        self.config.max_memory_address = max_memory_address;
+        self
+    }
+
+    pub fn max_trace_length(mut self, max_trace_length: usize) -> Self {
+        self.config.max_trace_length = max_trace_length;
+        self
+    }
+
+    /// Records `ProverTelemetry` for every proof produced by the session.
+    pub fn telemetry(mut self, telemetry: bool) -> Self {
+        self.config.telemetry = telemetry;
+        self
+    }
+
+    /// Splits execution into segments of `segment_length` cycles, each proven
+    /// separately. Not yet supported by the prover; `build` rejects it.
+    pub fn segment_length(mut self, segment_length: usize) -> Self {
+        self.config.segment_length = Some(segment_length);
+        self
+    }
+
+    /// Hides the witness from the verifier. Not yet supported by the prover;
+    /// `build` rejects it.
+    pub fn zero_knowledge(mut self, zero_knowledge: bool) -> Self {
+        self.config.zero_knowledge = zero_knowledge;
+        self
+    }
+
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// Builds the guest program and runs preprocessing.
+    #[tracing::instrument(skip_all, name = "ProvingSessionBuilder::build")]
+    pub fn build(self) -> Result<ProvingSession<F, PCS, ProofTranscript>, JoltError> {
+        let config = self.config;
+        if let Some(segment_length) = config.segment_length {
+            return Err(JoltError::InvalidConfig(format!(
+                "segmented proving (segment length {segment_length}) is not supported"
+            )));
+        }
+        if config.zero_knowledge {
+            return Err(JoltError::InvalidConfig(
+                "zero-knowledge proving is not supported".to_string(),
+            ));
+        }
+
+        let mut program = Program::new(&config.guest);
+        program.set_std(config.std);
+        if let Some(func) = &config.func {
+            program.set_func(func);
+        }
+        program.set_memory_size(config.memory_size);
+        program.set_stack_size(config.stack_size);
+        program.set_max_input_size(config.max_input_size);
+        program.set_max_output_size(config.max_output_size);
+
+        let (bytecode, memory_init) = program.try_decode()?;
+        let jolt_config = JoltConfig::<C, M, F, PCS, ProofTranscript>::builder()
+            .max_input_size(config.max_input_size)
+            .max_output_size(config.max_output_size)
+            .max_bytecode_size(config.max_bytecode_size)
+            .max_memory_address(config.max_memory_address)
+            .max_trace_length(config.max_trace_length)
+            .build()?;
+        let preprocessing =
+            <RV32IJoltVM as Jolt<F, PCS, C, M, ProofTranscript>>::preprocess_with_config(
+                bytecode,
+                memory_init,
+                &jolt_config,
+            );
+
+        let verifier_key = preprocessing.verifier_key();
+        Ok(ProvingSession {
+            config,
+            program,
+            preprocessing,
+            verifier_key,
+        })
+    }
+}
+
+/// A proof produced by a `ProvingSession`.
+pub struct SessionProof<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    pub proof: RV32IJoltProof<F, PCS, ProofTranscript>,
+    pub commitments: JoltCommitments<PCS, ProofTranscript>,
+    pub debug_info: Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+    pub telemetry: Option<ProverTelemetry>,
+    pub header: ProofHeader,
+}
+
+pub struct ProvingSession<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    config: SessionConfig,
+    program: Program,
+    preprocessing: JoltPreprocessing<C, F, PCS, ProofTranscript>,
+    verifier_key: JoltVerifierKey<C, F, PCS, ProofTranscript>,
+}
+
+impl<F, PCS, ProofTranscript> ProvingSession<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    pub fn builder(guest: &str) -> ProvingSessionBuilder<F, PCS, ProofTranscript> {
+        ProvingSessionBuilder::new(guest)
+    }
+
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    pub fn preprocessing(&self) -> &JoltPreprocessing<C, F, PCS, ProofTranscript> {
+        &self.preprocessing
+    }
+
+    pub fn verifier_key(&self) -> &JoltVerifierKey<C, F, PCS, ProofTranscript> {
+        &self.verifier_key
+    }
+
+    pub fn parameters(&self) -> JoltParameters {
+        JoltParameters::from_preprocessing::<C, M, _, _, _>(&self.preprocessing)
+    }
+
+    /// Checks whether a proof with the given header can be verified by this session.
+    pub fn check_compat(&self, header: &ProofHeader) -> CompatReport {
+        compat::check(header, &self.parameters(), CRATE_VERSION)
+    }
+
+    /// Executes the guest on `input`, returning its I/O and execution trace.
+    pub fn trace<T: Serialize>(&self, input: &T) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>) {
+        let mut program = self.program.clone();
+        program.set_input(input);
+        program.trace()
+    }
+
+    /// Traces the guest on `input` and proves its execution.
+    #[tracing::instrument(skip_all, name = "ProvingSession::prove")]
+    pub fn prove<T: Serialize>(&self, input: &T) -> SessionProof<F, PCS, ProofTranscript> {
+        let (program_io, trace) = self.trace(input);
+        let trace_length = trace.len();
+
+        let start = Instant::now();
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<F, PCS, C, M, ProofTranscript>>::prove(
+                program_io,
+                trace,
+                &self.preprocessing,
+            );
+        let prover_time_ms = start.elapsed().as_millis() as u64;
+
+        let telemetry = self.config.telemetry.then(|| ProverTelemetry {
+            trace_length: trace_length as u64,
+            prover_time_ms,
+            peak_memory_bytes: peak_memory_bytes(),
+        });
+
+        SessionProof {
+            proof,
+            commitments,
+            debug_info,
+            telemetry,
+            header: ProofHeader::new(self.parameters()),
+        }
+    }
+
+    /// Verifies a proof against this session's verifier key.
+    #[tracing::instrument(skip_all, name = "ProvingSession::verify")]
+    pub fn verify(
+        &self,
+        proof: SessionProof<F, PCS, ProofTranscript>,
+    ) -> Result<(), ProofVerifyError> {
+        let report = self.check_compat(&proof.header);
+        if !report.is_ok() {
+            return Err(ProofVerifyError::Incompatible(report.to_string()));
+        }
+        if proof.proof.program_io.memory_layout != self.verifier_key.memory_layout {
+            return Err(ProofVerifyError::InternalError);
+        }
+        RV32IJoltVM::verify(
+            &self.verifier_key,
+            proof.proof,
+            proof.commitments,
+            proof.debug_info,
+        )
+    }
+}
+
+/// Peak resident set size of the process (`VmHWM`), sampled once proving has
+/// finished. Returns 0 on platforms without `/proc/self/status`.
+#[cfg(target_os = "linux")]
+fn peak_memory_bytes() -> u64 {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+            let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+            Some(kib * 1024)
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_memory_bytes() -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::hyperkzg::HyperKZG;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::{Bn254, Fr};
+
+    #[test]
+    fn builder_options() {
+        let builder =
+            ProvingSession::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>::builder(
+                "fibonacci-guest",
+            )
+            .max_trace_length(1 << 16)
+            .max_input_size(1024)
+            .telemetry(true);
+
+        let config = builder.config();
+        assert_eq!(config.guest, "fibonacci-guest");
+        assert_eq!(config.max_trace_length, 1 << 16);
+        assert_eq!(config.max_bytecode_size, DEFAULT_MAX_PREPROCESSING_SIZE);
+        assert_eq!(config.max_input_size, 1024);
+        assert_eq!(config.max_output_size, DEFAULT_MAX_OUTPUT_SIZE);
+        assert!(config.telemetry);
+        assert_eq!(config.segment_length, None);
+        assert!(!config.zero_knowledge);
+    }
+
+    #[test]
+    fn unsupported_options_rejected() {
+        let result =
+            ProvingSession::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>::builder(
+                "fibonacci-guest",
+            )
+            .segment_length(1 << 20)
+            .build();
+        assert!(matches!(result, Err(JoltError::InvalidConfig(_))));
+
+        let result =
+            ProvingSession::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>::builder(
+                "fibonacci-guest",
+            )
+            .zero_knowledge(true)
+            .build();
+        assert!(matches!(result, Err(JoltError::InvalidConfig(_))));
+    }
+}
@@ -12,7 +12,7 @@ use reqwest::Client;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::runtime::Runtime;
 
-const TOOLCHAIN_TAG: &str = include_str!("../../../guest-toolchain-tag");
+pub(crate) const TOOLCHAIN_TAG: &str = include_str!("../../../guest-toolchain-tag");
 const DOWNLOAD_RETRIES: usize = 5;
 const DELAY_BASE_MS: u64 = 500;
 
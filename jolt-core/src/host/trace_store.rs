@@ -0,0 +1,130 @@
+//! Disk-backed storage for execution traces, so that a trace can be recorded once and
+//! read back later (e.g. to prove it, or to inspect it) without holding it in memory
+//! while the guest runs.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use common::rv_trace::RVTraceRow;
+
+use super::trace_steps;
+use crate::jolt::vm::{rv32i_vm::RV32I, JoltTraceStep};
+
+/// A trace written to a directory as a sequence of chunk files, each holding the
+/// postcard-serialized rows of one chunk. The chunk files are deleted when the store
+/// is dropped.
+pub struct TraceStore {
+    dir: PathBuf,
+    num_chunks: usize,
+    num_rows: usize,
+}
+
+impl TraceStore {
+    /// Creates an empty store in `dir`, creating the directory if needed.
+    pub fn create(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            num_chunks: 0,
+            num_rows: 0,
+        })
+    }
+
+    /// Appends `rows` to the trace, as a new chunk.
+    pub fn append(&mut self, rows: &[RVTraceRow]) -> io::Result<()> {
+        let bytes = postcard::to_stdvec(rows).map_err(io::Error::other)?;
+        fs::write(self.chunk_path(self.num_chunks), bytes)?;
+        self.num_chunks += 1;
+        self.num_rows += rows.len();
+        Ok(())
+    }
+
+    /// Number of (RISC-V) rows in the trace, before expanding virtual sequences.
+    pub fn len(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_rows == 0
+    }
+
+    /// The rows of the trace, read back one chunk at a time.
+    pub fn rows(&self) -> impl Iterator<Item = RVTraceRow> + '_ {
+        (0..self.num_chunks).flat_map(|index| self.read_chunk(index))
+    }
+
+    /// The trace in the form consumed by `Jolt::prove`, i.e. with virtual sequences
+    /// expanded. Panics if a chunk cannot be read back. Note that `Jolt::prove` collects
+    /// these steps, so proving from a store still holds the whole trace in memory.
+    pub fn steps(&self) -> impl Iterator<Item = JoltTraceStep<RV32I>> + '_ {
+        trace_steps(self.rows())
+    }
+
+    fn read_chunk(&self, index: usize) -> Vec<RVTraceRow> {
+        let path = self.chunk_path(index);
+        let bytes = fs::read(&path)
+            .unwrap_or_else(|e| panic!("could not read trace chunk {:?}: {}", path, e));
+        postcard::from_bytes(&bytes)
+            .unwrap_or_else(|e| panic!("could not decode trace chunk {:?}: {}", path, e))
+    }
+
+    fn chunk_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("chunk-{index}.bin"))
+    }
+}
+
+impl Drop for TraceStore {
+    fn drop(&mut self) {
+        for index in 0..self.num_chunks {
+            let _ = fs::remove_file(self.chunk_path(index));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rv_trace::{ELFInstruction, RegisterState, RV32IM};
+
+    fn row(address: u64) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address,
+                opcode: RV32IM::ADD,
+                rs1: Some(1),
+                rs2: Some(2),
+                rd: Some(3),
+                imm: None,
+                virtual_sequence_remaining: None,
+                compressed: false,
+            },
+            register_state: RegisterState {
+                rs1_val: Some(1),
+                rs2_val: Some(2),
+                rd_post_val: Some(3),
+            },
+            memory_state: None,
+            advice_value: None,
+            precompile_input: None,
+            precompile_output_address: None,
+        }
+    }
+
+    #[test]
+    fn append_and_read_back() {
+        let dir = std::env::temp_dir().join("jolt-trace-store-test");
+        let rows: Vec<_> = (0..10).map(|i| row(0x8000_0000 + 4 * i)).collect();
+        let mut store = TraceStore::create(&dir).unwrap();
+        for chunk in rows.chunks(4) {
+            store.append(chunk).unwrap();
+        }
+
+        assert_eq!(store.len(), rows.len());
+        assert_eq!(store.rows().collect::<Vec<_>>(), rows);
+        assert_eq!(store.steps().count(), rows.len());
+
+        drop(store);
+        assert!(!dir.join("chunk-0.bin").exists());
+    }
+}
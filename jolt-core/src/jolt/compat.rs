@@ -0,0 +1,468 @@
+//! Pre-flight compatibility checks between a proof, the verifier's parameters and
+//! the verifier's version of Jolt.
+//!
+//! A proof produced by an incompatible version of Jolt, or against different
+//! preprocessing parameters, typically fails verification with an opaque
+//! deserialization or sumcheck error. A `ProofHeader` travels alongside the proof
+//! and records what it was produced with, so that `check` can explain up front
+//! why a proof cannot be verified.
+use std::fmt;
+use std::io::Read;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::MemoryLayout;
+
+use crate::field::JoltField;
+use crate::jolt::vm::{JoltPreprocessing, JoltVerifierKey};
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::utils::errors::FormatError;
+use crate::utils::transcript::Transcript;
+
+/// Version of the proof serialization format. Bumped whenever a change to the
+/// proof system or its serialization makes older proofs unverifiable.
+pub const PROOF_FORMAT_VERSION: u32 = 21;
+
+/// Bound into the transcript at the start of `Jolt::prove` and `Jolt::verify`, so
+/// that a proof for one version of the protocol never verifies under another.
+/// Changes along with `PROOF_FORMAT_VERSION`.
+pub const PROTOCOL_VERSION: &[u8] = b"jolt-proof-v21";
+
+/// Version of this crate.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Parameters that the prover and verifier must agree on.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct JoltParameters {
+    /// `CommitmentScheme::protocol_name` of the polynomial commitment scheme.
+    pub pcs: String,
+    /// `Transcript::protocol_name` of the Fiat-Shamir transcript.
+    pub transcript: String,
+    pub c: u64,
+    pub m: u64,
+    /// Size of the (padded) bytecode.
+    pub bytecode_size: u64,
+    pub max_input_size: u64,
+    pub max_output_size: u64,
+    /// `IoCodec::name` of the codec guest inputs and outputs are serialized with.
+    pub io_codec: String,
+}
+
+impl JoltParameters {
+    fn new<const C: usize, const M: usize, PCS, ProofTranscript>(
+        bytecode_size: usize,
+        memory_layout: &MemoryLayout,
+    ) -> Self
+    where
+        PCS: CommitmentScheme<ProofTranscript>,
+        ProofTranscript: Transcript,
+    {
+        Self {
+            pcs: String::from_utf8_lossy(PCS::protocol_name()).into_owned(),
+            transcript: String::from_utf8_lossy(ProofTranscript::protocol_name()).into_owned(),
+            c: C as u64,
+            m: M as u64,
+            bytecode_size: bytecode_size as u64,
+            max_input_size: memory_layout.max_input_size,
+            max_output_size: memory_layout.max_output_size,
+            io_codec: memory_layout.io_codec.name().to_string(),
+        }
+    }
+
+    pub fn from_preprocessing<const C: usize, const M: usize, F, PCS, ProofTranscript>(
+        preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>,
+    ) -> Self
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<ProofTranscript, Field = F>,
+        ProofTranscript: Transcript,
+    {
+        Self::new::<C, M, PCS, ProofTranscript>(
+            preprocessing.bytecode.code_size(),
+            &preprocessing.memory_layout,
+        )
+    }
+
+    pub fn from_verifier_key<const C: usize, const M: usize, F, PCS, ProofTranscript>(
+        verifier_key: &JoltVerifierKey<C, F, PCS, ProofTranscript>,
+    ) -> Self
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<ProofTranscript, Field = F>,
+        ProofTranscript: Transcript,
+    {
+        Self::new::<C, M, PCS, ProofTranscript>(
+            verifier_key.bytecode.code_size(),
+            &verifier_key.memory_layout,
+        )
+    }
+
+    /// The parameters in which `self` differs from every program proven with `C`, `M`,
+    /// `PCS` and `ProofTranscript`, i.e. which rule out data serialized for `self` before
+    /// any program is known.
+    pub fn instance_mismatches<const C: usize, const M: usize, PCS, ProofTranscript>(
+        &self,
+    ) -> Vec<ParameterMismatch>
+    where
+        PCS: CommitmentScheme<ProofTranscript>,
+        ProofTranscript: Transcript,
+    {
+        let expected = Self {
+            pcs: String::from_utf8_lossy(PCS::protocol_name()).into_owned(),
+            transcript: String::from_utf8_lossy(ProofTranscript::protocol_name()).into_owned(),
+            c: C as u64,
+            m: M as u64,
+            ..self.clone()
+        };
+        expected.mismatches(self)
+    }
+
+    fn mismatches(&self, found: &Self) -> Vec<ParameterMismatch> {
+        let mut mismatches = vec![];
+        let mut compare = |parameter: &'static str, expected: String, found: String| {
+            if expected != found {
+                mismatches.push(ParameterMismatch {
+                    parameter,
+                    expected,
+                    found,
+                });
+            }
+        };
+        compare("pcs", self.pcs.clone(), found.pcs.clone());
+        compare(
+            "transcript",
+            self.transcript.clone(),
+            found.transcript.clone(),
+        );
+        compare("C", self.c.to_string(), found.c.to_string());
+        compare("M", self.m.to_string(), found.m.to_string());
+        compare(
+            "bytecode_size",
+            self.bytecode_size.to_string(),
+            found.bytecode_size.to_string(),
+        );
+        compare(
+            "max_input_size",
+            self.max_input_size.to_string(),
+            found.max_input_size.to_string(),
+        );
+        compare(
+            "max_output_size",
+            self.max_output_size.to_string(),
+            found.max_output_size.to_string(),
+        );
+        compare("io_codec", self.io_codec.clone(), found.io_codec.clone());
+        mismatches
+    }
+}
+
+/// Metadata recorded by the prover alongside a proof.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProofHeader {
+    pub format_version: u32,
+    pub crate_version: String,
+    pub parameters: JoltParameters,
+}
+
+impl ProofHeader {
+    /// A header for a proof produced by this version of Jolt.
+    pub fn new(parameters: JoltParameters) -> Self {
+        Self {
+            format_version: PROOF_FORMAT_VERSION,
+            crate_version: CRATE_VERSION.to_string(),
+            parameters,
+        }
+    }
+
+    /// Reads a header serialized with `CanonicalSerialize`, checking its format version
+    /// before the rest of it, whose layout may differ between versions.
+    pub fn deserialize_checked<R: Read>(mut reader: R) -> Result<Self, FormatError> {
+        let format_version =
+            u32::deserialize_compressed(&mut reader).map_err(|_| FormatError::MissingHeader)?;
+        if format_version != PROOF_FORMAT_VERSION {
+            return Err(FormatError::IncompatibleVersion {
+                expected: PROOF_FORMAT_VERSION,
+                found: format_version,
+            });
+        }
+        Ok(Self {
+            format_version,
+            crate_version: String::deserialize_compressed(&mut reader)
+                .map_err(|_| FormatError::MissingHeader)?,
+            parameters: JoltParameters::deserialize_compressed(&mut reader)
+                .map_err(|_| FormatError::MissingHeader)?,
+        })
+    }
+}
+
+/// Which side of a version mismatch needs to be upgraded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Upgrade {
+    /// The proof is newer than the verifier.
+    Verifier,
+    /// The proof is older than the verifier and must be regenerated.
+    Prover,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterMismatch {
+    pub parameter: &'static str,
+    /// The verifier's value.
+    pub expected: String,
+    /// The proof's value.
+    pub found: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompatReport {
+    Ok,
+    NeedsUpgrade {
+        upgrade: Upgrade,
+        proof_version: String,
+        verifier_version: String,
+    },
+    ParametersMismatch(Vec<ParameterMismatch>),
+}
+
+impl CompatReport {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, CompatReport::Ok)
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatReport::Ok => write!(f, "proof is compatible with the verifier"),
+            CompatReport::NeedsUpgrade {
+                upgrade: Upgrade::Verifier,
+                proof_version,
+                verifier_version,
+            } => write!(
+                f,
+                "proof was produced by Jolt {proof_version}, which this verifier (Jolt {verifier_version}) cannot verify; upgrade the verifier"
+            ),
+            CompatReport::NeedsUpgrade {
+                upgrade: Upgrade::Prover,
+                proof_version,
+                verifier_version,
+            } => write!(
+                f,
+                "proof was produced by Jolt {proof_version}, which is no longer supported by this verifier (Jolt {verifier_version}); regenerate the proof"
+            ),
+            CompatReport::ParametersMismatch(mismatches) => {
+                write!(f, "proof parameters do not match the verifier's:")?;
+                for mismatch in mismatches {
+                    write!(
+                        f,
+                        "\n  {}: verifier expects {}, proof has {}",
+                        mismatch.parameter, mismatch.expected, mismatch.found
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parses the `major.minor.patch` part of a version string, ignoring any
+/// pre-release or build metadata.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+/// Semver compatibility: the same major version, or for 0.x releases, the same minor version.
+fn versions_compatible(a: (u64, u64, u64), b: (u64, u64, u64)) -> bool {
+    a.0 == b.0 && (a.0 > 0 || a.1 == b.1)
+}
+
+/// Checks whether a proof with the given `header` can be verified by a verifier
+/// running Jolt `crate_version` with parameters `vk`.
+pub fn check(header: &ProofHeader, vk: &JoltParameters, crate_version: &str) -> CompatReport {
+    let needs_upgrade = |upgrade| CompatReport::NeedsUpgrade {
+        upgrade,
+        proof_version: header.crate_version.clone(),
+        verifier_version: crate_version.to_string(),
+    };
+
+    if header.format_version != PROOF_FORMAT_VERSION {
+        return needs_upgrade(if header.format_version > PROOF_FORMAT_VERSION {
+            Upgrade::Verifier
+        } else {
+            Upgrade::Prover
+        });
+    }
+    match (
+        parse_version(&header.crate_version),
+        parse_version(crate_version),
+    ) {
+        (Some(proof_version), Some(verifier_version)) => {
+            if !versions_compatible(proof_version, verifier_version) {
+                return needs_upgrade(if proof_version > verifier_version {
+                    Upgrade::Verifier
+                } else {
+                    Upgrade::Prover
+                });
+            }
+        }
+        // An unrecognized version was presumably produced by a newer release
+        _ => return needs_upgrade(Upgrade::Verifier),
+    }
+
+    let mismatches = vk.mismatches(&header.parameters);
+    if mismatches.is_empty() {
+        CompatReport::Ok
+    } else {
+        CompatReport::ParametersMismatch(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> JoltParameters {
+        JoltParameters {
+            pcs: "hyperkzg".to_string(),
+            transcript: "keccak".to_string(),
+            c: 4,
+            m: 1 << 16,
+            bytecode_size: 1 << 10,
+            max_input_size: 4096,
+            max_output_size: 4096,
+            io_codec: "postcard".to_string(),
+        }
+    }
+
+    #[test]
+    fn compatible() {
+        let header = ProofHeader::new(parameters());
+        assert_eq!(
+            check(&header, &parameters(), CRATE_VERSION),
+            CompatReport::Ok
+        );
+
+        let mut header = header;
+        header.crate_version = "0.1.7".to_string();
+        assert!(check(&header, &parameters(), "0.1.0").is_ok());
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let mut header = ProofHeader::new(parameters());
+        header.crate_version = "0.2.0".to_string();
+        assert!(matches!(
+            check(&header, &parameters(), "0.1.3"),
+            CompatReport::NeedsUpgrade {
+                upgrade: Upgrade::Verifier,
+                ..
+            }
+        ));
+        assert!(matches!(
+            check(&header, &parameters(), "0.3.0"),
+            CompatReport::NeedsUpgrade {
+                upgrade: Upgrade::Prover,
+                ..
+            }
+        ));
+
+        let mut header = ProofHeader::new(parameters());
+        header.format_version = PROOF_FORMAT_VERSION + 1;
+        assert!(matches!(
+            check(&header, &parameters(), CRATE_VERSION),
+            CompatReport::NeedsUpgrade {
+                upgrade: Upgrade::Verifier,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parameters_mismatch() {
+        let mut proof_parameters = parameters();
+        proof_parameters.pcs = "zeromorph".to_string();
+        proof_parameters.max_input_size = 1024;
+        proof_parameters.io_codec = "bincode".to_string();
+        let header = ProofHeader::new(proof_parameters);
+
+        let report = check(&header, &parameters(), CRATE_VERSION);
+        let CompatReport::ParametersMismatch(mismatches) = &report else {
+            panic!("unexpected report: {report}");
+        };
+        assert_eq!(mismatches.len(), 3);
+        assert_eq!(mismatches[0].parameter, "pcs");
+        assert_eq!(mismatches[1].expected, "4096");
+        assert_eq!(mismatches[1].found, "1024");
+        assert_eq!(mismatches[2].parameter, "io_codec");
+        assert!(report.to_string().contains("max_input_size"));
+    }
+
+    #[test]
+    fn header_serialization() {
+        let header = ProofHeader::new(parameters());
+        let mut bytes = vec![];
+        header.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            ProofHeader::deserialize_compressed(bytes.as_slice()).unwrap(),
+            header
+        );
+    }
+
+    #[test]
+    fn deserialize_checked() {
+        let header = ProofHeader::new(parameters());
+        let mut bytes = vec![];
+        header.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            ProofHeader::deserialize_checked(bytes.as_slice()).unwrap(),
+            header
+        );
+
+        // The version is checked before the rest of the header is read
+        let mut newer = ProofHeader::new(parameters());
+        newer.format_version = PROOF_FORMAT_VERSION + 1;
+        let mut bytes = vec![];
+        newer
+            .format_version
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        bytes.extend_from_slice(b"a layout of the future");
+        assert_eq!(
+            ProofHeader::deserialize_checked(bytes.as_slice()),
+            Err(FormatError::IncompatibleVersion {
+                expected: PROOF_FORMAT_VERSION,
+                found: PROOF_FORMAT_VERSION + 1,
+            })
+        );
+
+        assert_eq!(
+            ProofHeader::deserialize_checked(&bytes[..2]),
+            Err(FormatError::MissingHeader)
+        );
+    }
+
+    #[test]
+    fn instance_mismatches() {
+        use crate::poly::commitment::hyperkzg::HyperKZG;
+        use crate::utils::blake3_transcript::Blake3Transcript;
+        use crate::utils::transcript::KeccakTranscript;
+        use ark_bn254::Bn254;
+
+        let parameters = parameters();
+        assert!(parameters
+            .instance_mismatches::<4, { 1 << 16 }, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>()
+            .is_empty());
+
+        let mismatches = parameters
+            .instance_mismatches::<8, { 1 << 16 }, HyperKZG<Bn254, Blake3Transcript>, Blake3Transcript>();
+        let names: Vec<_> = mismatches
+            .iter()
+            .map(|mismatch| mismatch.parameter)
+            .collect();
+        assert_eq!(names, ["transcript", "C"]);
+        assert_eq!(mismatches[0].expected, "blake3");
+        assert_eq!(mismatches[0].found, "keccak");
+    }
+}
@@ -0,0 +1,438 @@
+//! C bindings for proving and verifying RV32IM ELFs with `RV32IJoltVM` and HyperKZG,
+//! for services written in other languages. Build jolt-core with the `ffi` feature
+//! from a `cdylib` or `staticlib` crate that depends on it; `include/jolt.h`, which
+//! cbindgen generates from this file (see `cbindgen.toml`), declares the items below.
+//!
+//! A `JoltProver` decodes and preprocesses an ELF once, and then proves any number of
+//! executions of it; a `JoltVerifier` likewise deserializes a verifier key once.
+//! Proofs and verifier keys cross the boundary as the bytes written by
+//! `Serializable::serialize_to_bytes`. Every fallible function returns a `JOLT_*`
+//! status code and, if `error` is not null, writes a message describing the failure
+//! to it (or null on success). Buffers, messages and handles returned by these
+//! functions are owned by Rust and must be released with the matching `jolt_free_*`
+//! function.
+use std::ffi::{c_char, CStr, CString};
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+
+use common::constants::{
+    DEFAULT_MAX_BYTECODE_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_MEMORY_ADDRESS,
+    DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_TRACE_LENGTH,
+};
+use common::rv_trace::MemoryLayout;
+
+use crate::host::Program;
+use crate::jolt::vm::rv32i_vm::{
+    JoltHyperKZGProof, JoltHyperKZGVerifierKey, ProofTranscript, RV32IJoltVM, Serializable, C, M,
+    PCS,
+};
+use crate::jolt::vm::{Jolt, JoltPreprocessing};
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+
+type F = <PCS as CommitmentScheme<ProofTranscript>>::Field;
+
+pub const JOLT_OK: i32 = 0;
+/// A pointer argument was null, or the ELF path was not valid UTF-8.
+pub const JOLT_INVALID_ARGUMENT: i32 = 1;
+/// The prover failed, e.g. because the trace is longer than the preprocessing supports.
+pub const JOLT_PROVE_FAILED: i32 = 2;
+/// The proof or verifier key could not be deserialized, e.g. because it was
+/// produced by an incompatible version of Jolt.
+pub const JOLT_DESERIALIZATION_FAILED: i32 = 3;
+/// The proof did not verify.
+pub const JOLT_INVALID_PROOF: i32 = 4;
+/// Jolt panicked.
+pub const JOLT_PANIC: i32 = 5;
+/// The ELF could not be read or decoded.
+pub const JOLT_DECODE_FAILED: i32 = 6;
+/// The guest panicked or faulted.
+pub const JOLT_TRACE_FAILED: i32 = 7;
+
+/// A buffer of bytes owned by Jolt; release it with `jolt_free_bytes`.
+#[repr(C)]
+pub struct JoltBytes {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+/// The bounds an ELF is preprocessed for. Proving cost does not depend on them, but
+/// preprocessing cost and memory grow with `max_trace_length`, so it should not be
+/// much larger than the longest execution that will be proven.
+#[repr(C)]
+pub struct JoltProverOptions {
+    /// The sizes of the program I/O regions the ELF was built for, as in
+    /// `MemoryLayout::new(max_input_size, max_output_size)`.
+    pub max_input_size: u64,
+    pub max_output_size: u64,
+    pub max_bytecode_size: usize,
+    pub max_memory_address: usize,
+    pub max_trace_length: usize,
+}
+
+/// An ELF decoded and preprocessed by `jolt_prover_new`. It is only read by
+/// `jolt_prover_prove`, so it may prove on several threads at once.
+pub struct JoltProver {
+    program: Program,
+    preprocessing: JoltPreprocessing<C, F, PCS, ProofTranscript>,
+}
+
+/// A verifier key deserialized by `jolt_verifier_new`.
+pub struct JoltVerifier {
+    verifier_key: JoltHyperKZGVerifierKey,
+}
+
+/// A status code other than `JOLT_OK`, and a message describing it.
+struct Failure(i32, String);
+
+impl Failure {
+    fn new(status: i32, error: impl Display) -> Self {
+        Self(status, error.to_string())
+    }
+}
+
+/// The default bounds of `jolt::provable`.
+#[no_mangle]
+pub extern "C" fn jolt_prover_options_default() -> JoltProverOptions {
+    JoltProverOptions {
+        max_input_size: DEFAULT_MAX_INPUT_SIZE,
+        max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+        max_bytecode_size: DEFAULT_MAX_BYTECODE_SIZE as usize,
+        max_memory_address: DEFAULT_MAX_MEMORY_ADDRESS as usize,
+        max_trace_length: DEFAULT_MAX_TRACE_LENGTH as usize,
+    }
+}
+
+/// Decodes the RV32IM ELF at `elf_path` and preprocesses it for `options`, writing
+/// the prover to `out`. The ELF is read again by every proof, so it must stay at
+/// `elf_path` while the prover is in use.
+///
+/// # Safety
+/// `elf_path` must be a NUL-terminated string, `options` must be readable, `out` must
+/// be writable, and `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_prover_new(
+    elf_path: *const c_char,
+    options: *const JoltProverOptions,
+    out: *mut *mut JoltProver,
+    error: *mut *mut c_char,
+) -> i32 {
+    report(error, || {
+        if elf_path.is_null() || options.is_null() || out.is_null() {
+            return Err(Failure::new(JOLT_INVALID_ARGUMENT, "null argument"));
+        }
+        let elf_path = CStr::from_ptr(elf_path)
+            .to_str()
+            .map_err(|error| Failure::new(JOLT_INVALID_ARGUMENT, error))?;
+        let prover = JoltProver::new(Path::new(elf_path), &*options)?;
+        out.write(Box::into_raw(Box::new(prover)));
+        Ok(())
+    })
+}
+
+/// Writes the serialized verifier key of `prover`'s proofs to `out`.
+///
+/// # Safety
+/// `prover` must have been returned by `jolt_prover_new` and not freed since, `out`
+/// must be writable, and `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_prover_verifier_key(
+    prover: *const JoltProver,
+    out: *mut JoltBytes,
+    error: *mut *mut c_char,
+) -> i32 {
+    report(error, || {
+        let (Some(prover), false) = (prover.as_ref(), out.is_null()) else {
+            return Err(Failure::new(JOLT_INVALID_ARGUMENT, "null argument"));
+        };
+        let verifier_key = prover
+            .preprocessing
+            .verifier_key()
+            .serialize_to_bytes()
+            .map_err(|error| Failure::new(JOLT_PROVE_FAILED, error))?;
+        out.write(JoltBytes::new(verifier_key));
+        Ok(())
+    })
+}
+
+/// Proves an execution of `prover`'s ELF on `input` (serialized with the guest's
+/// codec), writing the serialized proof to `out`.
+///
+/// # Safety
+/// `prover` must have been returned by `jolt_prover_new` and not freed since, `input`
+/// must point to `input_len` readable bytes (or be null if `input_len` is 0), `out`
+/// must be writable, and `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_prover_prove(
+    prover: *const JoltProver,
+    input: *const u8,
+    input_len: usize,
+    out: *mut JoltBytes,
+    error: *mut *mut c_char,
+) -> i32 {
+    report(error, || {
+        let (Some(prover), false) = (prover.as_ref(), out.is_null()) else {
+            return Err(Failure::new(JOLT_INVALID_ARGUMENT, "null argument"));
+        };
+        if input.is_null() && input_len != 0 {
+            return Err(Failure::new(JOLT_INVALID_ARGUMENT, "null input"));
+        }
+        let proof = prover.prove(bytes(input, input_len))?;
+        out.write(JoltBytes::new(proof));
+        Ok(())
+    })
+}
+
+/// Releases a prover returned by `jolt_prover_new`.
+///
+/// # Safety
+/// `prover` must be null or have been returned by `jolt_prover_new`, and not freed since.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_prover_free(prover: *mut JoltProver) {
+    if !prover.is_null() {
+        drop(Box::from_raw(prover));
+    }
+}
+
+/// Deserializes a verifier key written by `jolt_prover_verifier_key`, writing the
+/// verifier to `out`.
+///
+/// # Safety
+/// `verifier_key` must point to `verifier_key_len` readable bytes, `out` must be
+/// writable, and `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_verifier_new(
+    verifier_key: *const u8,
+    verifier_key_len: usize,
+    out: *mut *mut JoltVerifier,
+    error: *mut *mut c_char,
+) -> i32 {
+    report(error, || {
+        if verifier_key.is_null() || out.is_null() {
+            return Err(Failure::new(JOLT_INVALID_ARGUMENT, "null argument"));
+        }
+        let verifier_key =
+            JoltHyperKZGVerifierKey::deserialize_from_bytes(bytes(verifier_key, verifier_key_len))
+                .map_err(|error| Failure::new(JOLT_DESERIALIZATION_FAILED, error))?;
+        out.write(Box::into_raw(Box::new(JoltVerifier { verifier_key })));
+        Ok(())
+    })
+}
+
+/// Verifies a serialized `JoltHyperKZGProof`, returning `JOLT_OK` if it is valid.
+///
+/// # Safety
+/// `verifier` must have been returned by `jolt_verifier_new` and not freed since,
+/// `proof` must point to `proof_len` readable bytes, and `error` must be null or
+/// writable.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_verifier_verify(
+    verifier: *const JoltVerifier,
+    proof: *const u8,
+    proof_len: usize,
+    error: *mut *mut c_char,
+) -> i32 {
+    report(error, || {
+        let (Some(verifier), false) = (verifier.as_ref(), proof.is_null()) else {
+            return Err(Failure::new(JOLT_INVALID_ARGUMENT, "null argument"));
+        };
+        let proof = JoltHyperKZGProof::deserialize_from_bytes(bytes(proof, proof_len))
+            .map_err(|error| Failure::new(JOLT_DESERIALIZATION_FAILED, error))?;
+        RV32IJoltVM::verify(&verifier.verifier_key, proof.proof, proof.commitments, None)
+            .map_err(|error| Failure::new(JOLT_INVALID_PROOF, error))
+    })
+}
+
+/// Releases a verifier returned by `jolt_verifier_new`.
+///
+/// # Safety
+/// `verifier` must be null or have been returned by `jolt_verifier_new`, and not
+/// freed since.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_verifier_free(verifier: *mut JoltVerifier) {
+    if !verifier.is_null() {
+        drop(Box::from_raw(verifier));
+    }
+}
+
+/// Releases a buffer written by `jolt_prover_verifier_key` or `jolt_prover_prove`,
+/// and nulls it out.
+///
+/// # Safety
+/// `bytes` must be null or have been written by one of those functions, and not
+/// freed since.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_free_bytes(bytes: *mut JoltBytes) {
+    let Some(bytes) = bytes.as_mut() else {
+        return;
+    };
+    if !bytes.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            bytes.data, bytes.len,
+        )));
+    }
+    bytes.data = ptr::null_mut();
+    bytes.len = 0;
+}
+
+/// Releases an error message written by any of the functions above.
+///
+/// # Safety
+/// `error` must be null or have been written by one of those functions, and not
+/// freed since.
+#[no_mangle]
+pub unsafe extern "C" fn jolt_free_error(error: *mut c_char) {
+    if !error.is_null() {
+        drop(CString::from_raw(error));
+    }
+}
+
+impl JoltProver {
+    fn new(elf_path: &Path, options: &JoltProverOptions) -> Result<Self, Failure> {
+        let memory_layout = MemoryLayout::new(options.max_input_size, options.max_output_size);
+        let mut program = Program::from_elf(elf_path, &memory_layout);
+        let (bytecode, memory_init) = program
+            .try_decode()
+            .map_err(|error| Failure::new(JOLT_DECODE_FAILED, error))?;
+        let preprocessing = <RV32IJoltVM as Jolt<F, PCS, C, M, ProofTranscript>>::preprocess(
+            bytecode,
+            memory_layout,
+            memory_init,
+            options.max_bytecode_size,
+            options.max_memory_address,
+            options.max_trace_length,
+        );
+        Ok(Self {
+            program,
+            preprocessing,
+        })
+    }
+
+    fn prove(&self, input: &[u8]) -> Result<Vec<u8>, Failure> {
+        let mut program = self.program.clone();
+        program.set_input_bytes(input);
+        let (io_device, trace) = program
+            .try_trace()
+            .map_err(|error| Failure::new(JOLT_TRACE_FAILED, error))?;
+        let (proof, commitments, _) = RV32IJoltVM::try_prove(io_device, trace, &self.preprocessing)
+            .map_err(|error| Failure::new(JOLT_PROVE_FAILED, error))?;
+        JoltHyperKZGProof::new(proof, commitments, &self.preprocessing)
+            .serialize_to_bytes()
+            .map_err(|error| Failure::new(JOLT_PROVE_FAILED, error))
+    }
+}
+
+impl JoltBytes {
+    fn new(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        Self {
+            data: Box::into_raw(bytes.into_boxed_slice()) as *mut u8,
+            len,
+        }
+    }
+}
+
+/// Runs `f`, catching panics, and returns its status code, writing its message to
+/// `error` if that is not null.
+unsafe fn report(error: *mut *mut c_char, f: impl FnOnce() -> Result<(), Failure>) -> i32 {
+    let result = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".to_string());
+            Err(Failure(JOLT_PANIC, message))
+        }
+    };
+    let (status, message) = match result {
+        Ok(()) => (JOLT_OK, None),
+        Err(Failure(status, message)) => (status, Some(message)),
+    };
+    if !error.is_null() {
+        error.write(message.map_or(ptr::null_mut(), |message| {
+            // Interior NULs would truncate the message on the C side anyway
+            CString::new(message.replace('\0', "")).unwrap().into_raw()
+        }));
+    }
+    status
+}
+
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Takes the message written by a binding, releasing it.
+    unsafe fn take_error(error: *mut c_char) -> String {
+        assert!(!error.is_null());
+        let message = CStr::from_ptr(error).to_string_lossy().into_owned();
+        jolt_free_error(error);
+        message
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let mut prover = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        let options = jolt_prover_options_default();
+        unsafe {
+            assert_eq!(
+                jolt_prover_new(ptr::null(), &options, &mut prover, &mut error),
+                JOLT_INVALID_ARGUMENT
+            );
+            assert_eq!(take_error(error), "null argument");
+            assert!(prover.is_null());
+            assert_eq!(
+                jolt_verifier_verify(ptr::null(), ptr::null(), 0, ptr::null_mut()),
+                JOLT_INVALID_ARGUMENT
+            );
+            jolt_prover_free(ptr::null_mut());
+            jolt_verifier_free(ptr::null_mut());
+            jolt_free_bytes(ptr::null_mut());
+            jolt_free_error(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn reports_decode_failures() {
+        let elf_path = CString::new("/nonexistent/guest.elf").unwrap();
+        let options = jolt_prover_options_default();
+        let mut prover = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        let status =
+            unsafe { jolt_prover_new(elf_path.as_ptr(), &options, &mut prover, &mut error) };
+        assert_eq!(status, JOLT_DECODE_FAILED);
+        assert!(!unsafe { take_error(error) }.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_bytes() {
+        let garbage = [1u8; 64];
+        let mut verifier = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        let status = unsafe {
+            jolt_verifier_new(garbage.as_ptr(), garbage.len(), &mut verifier, &mut error)
+        };
+        assert_eq!(status, JOLT_DESERIALIZATION_FAILED);
+        assert!(!unsafe { take_error(error) }.is_empty());
+        assert!(verifier.is_null());
+    }
+
+    #[test]
+    fn frees_buffers() {
+        let mut bytes = JoltBytes::new(vec![1, 2, 3]);
+        unsafe { jolt_free_bytes(&mut bytes) };
+        assert!(bytes.data.is_null());
+        assert_eq!(bytes.len, 0);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::field::JoltField;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{JoltInstruction, SubtableIndices};
+use crate::{
+    jolt::subtable::{argmax::ArgmaxSubtable, eq::EqSubtable, ltu::LtuSubtable, LassoSubtable},
+    utils::instruction_utils::chunk_and_concatenate_operands,
+};
+
+/// The index (0 or 1) of the larger of two signed words, 0 if they are equal.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ARGMAXInstruction<const WORD_SIZE: usize>(pub u64, pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for ARGMAXInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, self.1)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        let vals_by_subtable = self.slice_values(vals, C, M);
+        let argmax = vals_by_subtable[0];
+        let ltu = vals_by_subtable[1];
+        let eq = vals_by_subtable[2];
+
+        // The most significant chunks are compared as signed values, the rest as unsigned
+        let mut sum = argmax[0];
+        let mut eq_prod = eq[0];
+
+        for i in 0..C - 2 {
+            sum += ltu[i] * eq_prod;
+            eq_prod *= eq[i + 1];
+        }
+        // Do not need to update `eq_prod` for the last iteration
+        sum + ltu[C - 2] * eq_prod
+    }
+
+    fn g_poly_degree(&self, C: usize) -> usize {
+        C
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![
+            (Box::new(ArgmaxSubtable::new()), SubtableIndices::from(0)),
+            (Box::new(LtuSubtable::new()), SubtableIndices::from(1..C)),
+            (Box::new(EqSubtable::new()), SubtableIndices::from(0..C - 1)),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, self.1, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        if WORD_SIZE == 32 {
+            let x = self.0 as i32;
+            let y = self.1 as i32;
+            (y > x) as u64
+        } else if WORD_SIZE == 64 {
+            let x = self.0 as i64;
+            let y = self.1 as i64;
+            (y > x) as u64
+        } else {
+            panic!("ARGMAX is only implemented for 32-bit or 64-bit word sizes")
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64, rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64(), rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ARGMAXInstruction;
+
+    #[test]
+    fn argmax_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u32() as u64, rng.next_u32() as u64);
+            let instruction = ARGMAXInstruction::<WORD_SIZE>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+
+        // x == y
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            jolt_instruction_test!(ARGMAXInstruction::<WORD_SIZE>(x, x));
+        }
+
+        // Edge cases
+        let i32_min = i32::MIN as u32 as u64;
+        let i32_max = i32::MAX as u64;
+        let minus_one = u32::MAX as u64;
+        let instructions = vec![
+            ARGMAXInstruction::<WORD_SIZE>(100, 0),
+            ARGMAXInstruction::<WORD_SIZE>(0, 100),
+            ARGMAXInstruction::<WORD_SIZE>(minus_one, 0),
+            ARGMAXInstruction::<WORD_SIZE>(0, minus_one),
+            ARGMAXInstruction::<WORD_SIZE>(i32_min, i32_max),
+            ARGMAXInstruction::<WORD_SIZE>(i32_max, i32_min),
+            ARGMAXInstruction::<WORD_SIZE>(i32_min, minus_one),
+            ARGMAXInstruction::<WORD_SIZE>(minus_one, 1 << 8),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn argmax_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        // Random
+        for _ in 0..256 {
+            let (x, y) = (rng.next_u64(), rng.next_u64());
+            let instruction = ARGMAXInstruction::<WORD_SIZE>(x, y);
+            jolt_instruction_test!(instruction);
+        }
+
+        // x == y
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            jolt_instruction_test!(ARGMAXInstruction::<WORD_SIZE>(x, x));
+        }
+
+        // Edge cases
+        let instructions = vec![
+            ARGMAXInstruction::<WORD_SIZE>(u64::MAX, 0),
+            ARGMAXInstruction::<WORD_SIZE>(0, u64::MAX),
+            ARGMAXInstruction::<WORD_SIZE>(i64::MIN as u64, i64::MAX as u64),
+            ARGMAXInstruction::<WORD_SIZE>(i64::MAX as u64, i64::MIN as u64),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Virtual sequences for the RV32A word instructions. Execution is single-threaded,
+//! so atomics reduce to plain loads and stores: LR.W is traced as LW, SC.W always
+//! succeeds, and each AMO is a load, an ALU operation and a store.
+use common::constants::virtual_register_index;
+use tracer::{ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM};
+
+use super::virtual_sequence::{Operand, VirtualSequenceBuilder};
+use super::VirtualInstructionSequence;
+
+/// Register operands of an atomic instruction, and the memory word it accesses
+/// before and after.
+struct AtomicOperands {
+    rs1: Operand,
+    rs2: Operand,
+    rd: u64,
+    pre_value: u64,
+    post_value: u64,
+}
+
+impl AtomicOperands {
+    fn new(trace_row: &RVTraceRow, opcode: RV32IM) -> Self {
+        assert_eq!(trace_row.instruction.opcode, opcode);
+        let (pre_value, post_value) = match trace_row.memory_state {
+            Some(MemoryState::Write {
+                address: _,
+                pre_value,
+                post_value,
+            }) => (pre_value, post_value),
+            _ => panic!("Expected MemoryState::Write"),
+        };
+        Self {
+            rs1: (
+                trace_row.instruction.rs1.unwrap(),
+                trace_row.register_state.rs1_val.unwrap(),
+            ),
+            rs2: (
+                trace_row.instruction.rs2.unwrap(),
+                trace_row.register_state.rs2_val.unwrap(),
+            ),
+            rd: trace_row.instruction.rd.unwrap(),
+            pre_value,
+            post_value,
+        }
+    }
+}
+
+// Virtual registers used in AMO sequences
+const V_LOADED: u64 = virtual_register_index(0);
+const V_RESULT: u64 = virtual_register_index(1);
+const V_MASK: u64 = virtual_register_index(2);
+const V_DIFF: u64 = virtual_register_index(3);
+
+/// Traces an AMO: loads the word at `rs1` into a virtual register, stores the
+/// result of `op` applied to it and `rs2`, and finally writes the loaded word to
+/// `rd` (which may alias `rs1` or `rs2`).
+fn amo_trace<const WORD_SIZE: usize>(
+    trace_row: RVTraceRow,
+    opcode: RV32IM,
+    sequence_length: usize,
+    op: impl FnOnce(&mut VirtualSequenceBuilder<WORD_SIZE>, Operand, Operand) -> Operand,
+) -> Vec<RVTraceRow> {
+    let operands = AtomicOperands::new(&trace_row, opcode);
+
+    let mut builder = VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(
+        &trace_row.instruction,
+        sequence_length,
+    );
+    let loaded = builder.lw(operands.rs1, 0, V_LOADED, operands.pre_value);
+    let result = op(&mut builder, (V_LOADED, loaded), operands.rs2);
+    assert_eq!(result.1, operands.post_value);
+    builder.sw(operands.rs1, result, 0, operands.pre_value);
+    builder.add((V_LOADED, loaded), (0, 0), operands.rd);
+    builder.finish()
+}
+
+/// Computes `rs1 ^ ((rs1 ^ rs2) & -choose_rs2)` into `V_RESULT`, i.e. `rs2` if
+/// `choose_rs2` is 1 and `rs1` if it is 0. Uses 4 instructions.
+fn select<const WORD_SIZE: usize>(
+    builder: &mut VirtualSequenceBuilder<WORD_SIZE>,
+    choose_rs2: Operand,
+    rs1: Operand,
+    rs2: Operand,
+) -> Operand {
+    let mask = builder.sub((0, 0), choose_rs2, V_MASK);
+    let diff = builder.xor(rs1, rs2, V_DIFF);
+    let diff = builder.and((V_DIFF, diff), (V_MASK, mask), V_DIFF);
+    (V_RESULT, builder.xor(rs1, (V_DIFF, diff), V_RESULT))
+}
+
+fn dummy_trace_row(instruction: ELFInstruction) -> RVTraceRow {
+    RVTraceRow {
+        instruction,
+        register_state: RegisterState {
+            rs1_val: Some(0),
+            rs2_val: Some(0),
+            rd_post_val: Some(0),
+        },
+        memory_state: Some(MemoryState::Write {
+            address: 0,
+            pre_value: 0,
+            post_value: 0,
+        }),
+        advice_value: None,
+        precompile_input: None,
+        precompile_output_address: None,
+    }
+}
+
+/// Stores a word conditionally; since there is no other hart to break the
+/// reservation, the store always happens and 0 is written to `rd`.
+pub struct SCInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for SCInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = RV32IM::SC_W.virtual_sequence_length();
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        let operands = AtomicOperands::new(&trace_row, RV32IM::SC_W);
+        assert_eq!(operands.rs2.1, operands.post_value);
+
+        let mut builder =
+            VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(&trace_row.instruction, 2);
+        builder.sw(operands.rs1, operands.rs2, 0, operands.pre_value);
+        builder.add((0, 0), (0, 0), operands.rd);
+        builder.finish()
+    }
+
+    fn sequence_output(_: u64, _: u64) -> u64 {
+        0
+    }
+
+    fn virtual_sequence(instruction: ELFInstruction) -> Vec<ELFInstruction> {
+        Self::virtual_trace(dummy_trace_row(instruction))
+            .into_iter()
+            .map(|trace_row| trace_row.instruction)
+            .collect()
+    }
+}
+
+/// Defines the virtual sequence of an AMO, given its opcode and
+/// the operation producing the stored word from the loaded word and `rs2`.
+macro_rules! amo_instruction {
+    ($(#[$doc:meta])* $name:ident, $opcode:ident, $op:expr) => {
+        $(#[$doc])*
+        pub struct $name<const WORD_SIZE: usize>;
+
+        impl<const WORD_SIZE: usize> VirtualInstructionSequence for $name<WORD_SIZE> {
+            const SEQUENCE_LENGTH: usize = RV32IM::$opcode.virtual_sequence_length();
+
+            fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+                amo_trace::<WORD_SIZE>(trace_row, RV32IM::$opcode, Self::SEQUENCE_LENGTH, $op)
+            }
+
+            fn sequence_output(_: u64, _: u64) -> u64 {
+                unimplemented!("AMOs write the loaded memory word to rd")
+            }
+
+            fn virtual_sequence(instruction: ELFInstruction) -> Vec<ELFInstruction> {
+                Self::virtual_trace(dummy_trace_row(instruction))
+                    .into_iter()
+                    .map(|trace_row| trace_row.instruction)
+                    .collect()
+            }
+        }
+    };
+}
+
+amo_instruction!(
+    /// Swaps a word in memory with `rs2`
+    AMOSWAPInstruction,
+    AMOSWAP_W,
+    |_, _, rs2| rs2
+);
+amo_instruction!(
+    /// Adds `rs2` to a word in memory
+    AMOADDInstruction,
+    AMOADD_W,
+    |builder, loaded, rs2| (V_RESULT, builder.add(loaded, rs2, V_RESULT))
+);
+amo_instruction!(
+    /// XORs `rs2` into a word in memory
+    AMOXORInstruction,
+    AMOXOR_W,
+    |builder, loaded, rs2| (V_RESULT, builder.xor(loaded, rs2, V_RESULT))
+);
+amo_instruction!(
+    /// ANDs `rs2` into a word in memory
+    AMOANDInstruction,
+    AMOAND_W,
+    |builder, loaded, rs2| (V_RESULT, builder.and(loaded, rs2, V_RESULT))
+);
+amo_instruction!(
+    /// ORs `rs2` into a word in memory
+    AMOORInstruction,
+    AMOOR_W,
+    |builder, loaded, rs2| (V_RESULT, builder.or(loaded, rs2, V_RESULT))
+);
+amo_instruction!(
+    /// Replaces a word in memory with `rs2` if `rs2` is smaller (signed)
+    AMOMINInstruction,
+    AMOMIN_W,
+    |builder, loaded, rs2| {
+        let choose_rs2 = builder.slt(rs2, loaded, V_RESULT);
+        select(builder, (V_RESULT, choose_rs2), loaded, rs2)
+    }
+);
+amo_instruction!(
+    /// Replaces a word in memory with `rs2` if `rs2` is larger (signed)
+    AMOMAXInstruction,
+    AMOMAX_W,
+    |builder, loaded, rs2| {
+        let choose_rs2 = builder.slt(loaded, rs2, V_RESULT);
+        select(builder, (V_RESULT, choose_rs2), loaded, rs2)
+    }
+);
+amo_instruction!(
+    /// Replaces a word in memory with `rs2` if `rs2` is smaller (unsigned)
+    AMOMINUInstruction,
+    AMOMINU_W,
+    |builder, loaded, rs2| {
+        let choose_rs2 = builder.sltu(rs2, loaded, V_RESULT);
+        select(builder, (V_RESULT, choose_rs2), loaded, rs2)
+    }
+);
+amo_instruction!(
+    /// Replaces a word in memory with `rs2` if `rs2` is larger (unsigned)
+    AMOMAXUInstruction,
+    AMOMAXU_W,
+    |builder, loaded, rs2| {
+        let choose_rs2 = builder.sltu(loaded, rs2, V_RESULT);
+        select(builder, (V_RESULT, choose_rs2), loaded, rs2)
+    }
+);
+
+#[cfg(test)]
+mod test {
+    use ark_std::test_rng;
+    use common::constants::REGISTER_COUNT;
+    use rand_core::RngCore;
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::jolt::instruction::virtual_sequence::tests::replay;
+
+    /// Checks the virtual sequence for `opcode` against `memory_op`, which maps the
+    /// word in memory and `rs2` to the word stored.
+    fn atomic_virtual_sequence<I: VirtualInstructionSequence>(
+        opcode: RV32IM,
+        memory_op: impl Fn(u32, u32) -> u32,
+        rd_output: impl Fn(u32) -> u32,
+    ) {
+        let mut rng = test_rng();
+        for _ in 0..256 {
+            // The base register is never x0, as no memory is mapped at address 0
+            let rs1 = 1 + rng.next_u64() % 31;
+            let rs2 = rng.next_u64() % 32;
+            let rd = rng.next_u64() % 32;
+
+            let address = 0x8000_0000 + (rng.next_u32() as u64 % (1 << 20)) * 4;
+            let rs2_val = if rs2 == 0 {
+                0
+            } else if rs2 == rs1 {
+                address
+            } else {
+                rng.next_u32() as u64
+            };
+            let word_before = rng.next_u32();
+            let word_after = memory_op(word_before, rs2_val as u32);
+
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs1 as usize] = address;
+            registers[rs2 as usize] = rs2_val;
+            let mut memory = HashMap::from([(address, word_before as u64)]);
+
+            let trace_row = RVTraceRow {
+                instruction: ELFInstruction {
+                    address: rng.next_u64(),
+                    opcode,
+                    rs1: Some(rs1),
+                    rs2: Some(rs2),
+                    rd: Some(rd),
+                    imm: None,
+                    virtual_sequence_remaining: None,
+                    compressed: false,
+                },
+                register_state: RegisterState {
+                    rs1_val: Some(address),
+                    rs2_val: Some(rs2_val),
+                    rd_post_val: None,
+                },
+                memory_state: Some(MemoryState::Write {
+                    address,
+                    pre_value: word_before as u64,
+                    post_value: word_after as u64,
+                }),
+                advice_value: None,
+                precompile_input: None,
+                precompile_output_address: None,
+            };
+
+            let trace = I::virtual_trace(trace_row);
+            assert_eq!(trace.len(), I::SEQUENCE_LENGTH);
+            replay(&trace, &mut registers, &mut memory);
+
+            assert_eq!(memory[&address], word_after as u64);
+            if rd != 0 {
+                assert_eq!(registers[rd as usize], rd_output(word_before) as u64);
+            }
+            assert_eq!(registers[0], 0);
+            for register in 1..32 {
+                if register != rd && register != rs1 && register != rs2 {
+                    assert_eq!(registers[register as usize], 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sc_virtual_sequence_32() {
+        atomic_virtual_sequence::<SCInstruction<32>>(RV32IM::SC_W, |_, y| y, |_| 0);
+    }
+
+    #[test]
+    fn amo_virtual_sequence_32() {
+        let loaded = |x| x;
+        atomic_virtual_sequence::<AMOSWAPInstruction<32>>(RV32IM::AMOSWAP_W, |_, y| y, loaded);
+        atomic_virtual_sequence::<AMOADDInstruction<32>>(
+            RV32IM::AMOADD_W,
+            u32::wrapping_add,
+            loaded,
+        );
+        atomic_virtual_sequence::<AMOXORInstruction<32>>(RV32IM::AMOXOR_W, |x, y| x ^ y, loaded);
+        atomic_virtual_sequence::<AMOANDInstruction<32>>(RV32IM::AMOAND_W, |x, y| x & y, loaded);
+        atomic_virtual_sequence::<AMOORInstruction<32>>(RV32IM::AMOOR_W, |x, y| x | y, loaded);
+        atomic_virtual_sequence::<AMOMINInstruction<32>>(
+            RV32IM::AMOMIN_W,
+            |x, y| (x as i32).min(y as i32) as u32,
+            loaded,
+        );
+        atomic_virtual_sequence::<AMOMAXInstruction<32>>(
+            RV32IM::AMOMAX_W,
+            |x, y| (x as i32).max(y as i32) as u32,
+            loaded,
+        );
+        atomic_virtual_sequence::<AMOMINUInstruction<32>>(RV32IM::AMOMINU_W, u32::min, loaded);
+        atomic_virtual_sequence::<AMOMAXUInstruction<32>>(RV32IM::AMOMAXU_W, u32::max, loaded);
+    }
+}
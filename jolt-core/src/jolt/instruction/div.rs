@@ -11,7 +11,7 @@ use crate::jolt::instruction::{
 pub struct DIVInstruction<const WORD_SIZE: usize>;
 
 impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_SIZE> {
-    const SEQUENCE_LENGTH: usize = 8;
+    const SEQUENCE_LENGTH: usize = RV32IM::DIV.virtual_sequence_length();
 
     fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
         assert_eq!(trace_row.instruction.opcode, RV32IM::DIV);
@@ -69,6 +69,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_q,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: None,
@@ -91,6 +92,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_r,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: None,
@@ -114,6 +116,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(r),
@@ -137,6 +140,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(y),
@@ -159,6 +163,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_qy,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q),
@@ -181,6 +186,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: v_0,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q_y),
@@ -203,6 +209,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(add_0),
@@ -224,6 +231,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVInstruction<WORD_
                 rd: trace_row.instruction.rd,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q),
@@ -13,7 +13,7 @@ use crate::jolt::instruction::{
 pub struct DIVUInstruction<const WORD_SIZE: usize>;
 
 impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD_SIZE> {
-    const SEQUENCE_LENGTH: usize = 9;
+    const SEQUENCE_LENGTH: usize = RV32IM::DIVU.virtual_sequence_length();
 
     fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
         assert_eq!(trace_row.instruction.opcode, RV32IM::DIVU);
@@ -52,6 +52,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: v_q,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: None,
@@ -74,6 +75,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: v_r,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: None,
@@ -96,6 +98,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: v_qy,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q),
@@ -119,6 +122,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(r),
@@ -142,6 +146,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q_y),
@@ -165,6 +170,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(y),
@@ -187,6 +193,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: v_0,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q_y),
@@ -209,6 +216,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: None,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(add_0),
@@ -230,6 +238,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for DIVUInstruction<WORD
                 rd: trace_row.instruction.rd,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(q),
@@ -0,0 +1,119 @@
+use common::fixed_point;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::JoltInstruction;
+use crate::{
+    field::JoltField,
+    jolt::{
+        instruction::SubtableIndices,
+        subtable::{exp::ExpSubtable, identity::IdentitySubtable, LassoSubtable},
+    },
+    utils::instruction_utils::chunk_operand_usize,
+};
+
+/// `e^x` as an unsigned Q16.16 value, where `x` is the lower half of a word as a signed
+/// Q8.8 value (see `common::fixed_point::exp`).
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EXPInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for EXPInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        assert!(M == 1 << 16);
+        let vals_by_subtable = self.slice_values(vals, C, M);
+        // The identity lookups only range-check the query chunks
+        vals_by_subtable[0][0]
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        assert!(M == 1 << 16);
+        vec![
+            (
+                // The least significant chunk, i.e. the lower half of the word
+                Box::new(ExpSubtable::<F>::new()),
+                SubtableIndices::from(C - 1),
+            ),
+            (
+                Box::new(IdentitySubtable::<F>::new()),
+                SubtableIndices::from(0..C),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_operand_usize(self.0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        fixed_point::exp(self.0 as i16) as u64
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::EXPInstruction;
+
+    #[test]
+    fn exp_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..64 {
+            let x = rng.next_u32() as u64;
+            let instruction = EXPInstruction::<WORD_SIZE>(x);
+            jolt_instruction_test!(instruction);
+        }
+
+        // Edge cases: 0, ±1.0, the most negative and positive inputs, and the point
+        // where the result saturates
+        let instructions = vec![
+            EXPInstruction::<WORD_SIZE>(0),
+            EXPInstruction::<WORD_SIZE>(0x100),
+            EXPInstruction::<WORD_SIZE>(0xff00),
+            EXPInstruction::<WORD_SIZE>(0x8000),
+            EXPInstruction::<WORD_SIZE>(0x7fff),
+            EXPInstruction::<WORD_SIZE>(2839),
+            EXPInstruction::<WORD_SIZE>(0xffff_0100),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+        assert_eq!(EXPInstruction::<WORD_SIZE>(0).lookup_entry(), 1 << 16);
+        assert_eq!(
+            EXPInstruction::<WORD_SIZE>(0xffff_0100).lookup_entry(),
+            EXPInstruction::<WORD_SIZE>(0x100).lookup_entry()
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Virtual sequences for Q16.16 fixed-point arithmetic (see
+//! `common::constants::FIXED_MUL_INSTRUCTION` and `SATURATING_ADD_INSTRUCTION`). Both
+//! are composed of lookups Jolt already has: a guest saves the shifts, rounding and
+//! overflow checks it would otherwise execute (and branch on) itself.
+use common::constants::virtual_register_index;
+use tracer::{RVTraceRow, RV32IM};
+
+use super::virtual_sequence::VirtualSequenceBuilder;
+use super::VirtualInstructionSequence;
+
+// Virtual registers used in the sequences
+const V_0: u64 = virtual_register_index(0);
+const V_1: u64 = virtual_register_index(1);
+const V_2: u64 = virtual_register_index(2);
+const V_3: u64 = virtual_register_index(3);
+
+/// Multiplies two signed Q16.16 values, rounding the product to the nearest Q16.16 value
+/// (halves round up). Like MUL, the result wraps around if it overflows.
+pub struct FIXEDMULInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for FIXEDMULInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = RV32IM::FIXED_MUL.virtual_sequence_length();
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::FIXED_MUL);
+        let x = (
+            trace_row.instruction.rs1.unwrap(),
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let y = (
+            trace_row.instruction.rs2.unwrap(),
+            trace_row.register_state.rs2_val.unwrap(),
+        );
+        let rd = trace_row.instruction.rd.unwrap();
+
+        let mut builder = VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(
+            &trace_row.instruction,
+            Self::SEQUENCE_LENGTH,
+        );
+        // The high word of the signed product, as in MULH
+        let s_x = builder.movsign(x, V_0);
+        let s_y = builder.movsign(y, V_1);
+        let hi = builder.mulhu(x, y, V_2);
+        let t = builder.mulu((V_0, s_x), y, V_3);
+        let hi = builder.add((V_2, hi), (V_3, t), V_2);
+        let t = builder.mulu((V_1, s_y), x, V_3);
+        let hi = builder.add((V_2, hi), (V_3, t), V_2);
+        // Add 2^15 to the product, carrying into the high word
+        let lo = builder.mulu(x, y, V_3);
+        let rounded = builder.addi((V_3, lo), 1 << 15, V_0);
+        let carry = builder.sltu((V_0, rounded), (V_3, lo), V_1);
+        let hi = builder.add((V_2, hi), (V_1, carry), V_2);
+        // Bits 16..48 of the rounded product
+        let lo = builder.srli((V_0, rounded), 16, V_0);
+        let hi = builder.slli((V_2, hi), 16, V_2);
+        let result = builder.or((V_0, lo), (V_2, hi), rd);
+        assert_eq!(result, trace_row.register_state.rd_post_val.unwrap());
+        builder.finish()
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        match WORD_SIZE {
+            32 => {
+                let product = (x as i32 as i64) * (y as i32 as i64);
+                ((product + (1 << 15)) >> 16) as u32 as u64
+            }
+            _ => panic!("Q16.16 arithmetic is only implemented for 32-bit words"),
+        }
+    }
+}
+
+/// Adds two signed words, clamping the sum to the range of a signed word.
+pub struct SATURATINGADDInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for SATURATINGADDInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = RV32IM::SATURATING_ADD.virtual_sequence_length();
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::SATURATING_ADD);
+        let x = (
+            trace_row.instruction.rs1.unwrap(),
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let y = (
+            trace_row.instruction.rs2.unwrap(),
+            trace_row.register_state.rs2_val.unwrap(),
+        );
+        let rd = trace_row.instruction.rd.unwrap();
+
+        let mut builder = VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(
+            &trace_row.instruction,
+            Self::SEQUENCE_LENGTH,
+        );
+        let sum = builder.add(x, y, V_0);
+        // The sum overflowed iff it differs in sign from both operands
+        let t_x = builder.xor(x, (V_0, sum), V_1);
+        let t_y = builder.xor(y, (V_0, sum), V_2);
+        let overflow = builder.and((V_1, t_x), (V_2, t_y), V_1);
+        let overflow = builder.srai((V_1, overflow), 31, V_1);
+        // i32::MIN if the operands are negative, i32::MAX otherwise
+        let saturated = builder.srai(x, 31, V_2);
+        let saturated = builder.xori((V_2, saturated), i32::MAX as i64, V_2);
+        // sum ^ ((sum ^ saturated) & overflow)
+        let diff = builder.xor((V_0, sum), (V_2, saturated), V_2);
+        let diff = builder.and((V_2, diff), (V_1, overflow), V_2);
+        let result = builder.xor((V_0, sum), (V_2, diff), rd);
+        assert_eq!(result, trace_row.register_state.rd_post_val.unwrap());
+        builder.finish()
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        match WORD_SIZE {
+            32 => (x as i32).saturating_add(y as i32) as u32 as u64,
+            _ => panic!("Q16.16 arithmetic is only implemented for 32-bit words"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jolt::instruction::virtual_sequence::tests::replay;
+    use crate::{jolt::instruction::JoltInstruction, jolt_virtual_sequence_test};
+    use common::constants::REGISTER_COUNT;
+    use std::collections::HashMap;
+    use tracer::{ELFInstruction, RegisterState};
+
+    /// Checks the sequence of `opcode` on operands around the points where its
+    /// rounding or saturation changes.
+    fn edge_cases<I: VirtualInstructionSequence>(opcode: RV32IM) {
+        let values = [
+            0,
+            1,
+            -1,
+            1 << 15,
+            -(1 << 15),
+            (1 << 15) - 1,
+            1 << 16,
+            -(1 << 16),
+            3 << 15,
+            i32::MAX,
+            i32::MIN,
+            i32::MAX - 1,
+            i32::MIN + 1,
+            1 << 30,
+            -(1 << 30),
+        ];
+        for x in values {
+            for y in values {
+                let (x, y) = (x as u32 as u64, y as u32 as u64);
+                let result = I::sequence_output(x, y);
+                let (rs1, rs2, rd) = (10, 11, 12);
+                let trace = I::virtual_trace(RVTraceRow {
+                    instruction: ELFInstruction {
+                        address: 0x8000_0000,
+                        opcode,
+                        rs1: Some(rs1),
+                        rs2: Some(rs2),
+                        rd: Some(rd),
+                        imm: None,
+                        virtual_sequence_remaining: None,
+                        compressed: false,
+                    },
+                    register_state: RegisterState {
+                        rs1_val: Some(x),
+                        rs2_val: Some(y),
+                        rd_post_val: Some(result),
+                    },
+                    memory_state: None,
+                    advice_value: None,
+                    precompile_input: None,
+                    precompile_output_address: None,
+                });
+
+                let mut registers = vec![0u64; REGISTER_COUNT as usize];
+                registers[rs1 as usize] = x;
+                registers[rs2 as usize] = y;
+                replay(&trace, &mut registers, &mut HashMap::new());
+                assert_eq!(registers[rd as usize], result);
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_mul_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(FIXEDMULInstruction::<32>, RV32IM::FIXED_MUL);
+    }
+
+    #[test]
+    fn fixed_mul_edge_cases() {
+        edge_cases::<FIXEDMULInstruction<32>>(RV32IM::FIXED_MUL);
+
+        let q16 = |x: f64| (x * 65536.0) as i32 as u32 as u64;
+        let output = FIXEDMULInstruction::<32>::sequence_output;
+        assert_eq!(output(q16(1.5), q16(-2.25)), q16(-3.375));
+        // 2^-16 * 0.5 rounds up to 2^-16, and 2^-16 * -0.5 up to 0
+        assert_eq!(output(1, q16(0.5)), 1);
+        assert_eq!(output(1, q16(-0.5)), 0);
+    }
+
+    #[test]
+    fn saturating_add_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(SATURATINGADDInstruction::<32>, RV32IM::SATURATING_ADD);
+    }
+
+    #[test]
+    fn saturating_add_edge_cases() {
+        edge_cases::<SATURATINGADDInstruction<32>>(RV32IM::SATURATING_ADD);
+
+        let output = SATURATINGADDInstruction::<32>::sequence_output;
+        assert_eq!(output(i32::MAX as u64, 1), i32::MAX as u64);
+        assert_eq!(output(i32::MIN as u32 as u64, -1i32 as u32 as u64), 1 << 31);
+        assert_eq!(output(5, -7i32 as u32 as u64), -2i32 as u32 as u64);
+    }
+}
@@ -10,7 +10,7 @@ use crate::jolt::instruction::{
 pub struct LBInstruction<const WORD_SIZE: usize>;
 
 impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_SIZE> {
-    const SEQUENCE_LENGTH: usize = 7;
+    const SEQUENCE_LENGTH: usize = RV32IM::LB.virtual_sequence_length();
 
     fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
         assert_eq!(trace_row.instruction.opcode, RV32IM::LB);
@@ -45,6 +45,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd: v_address,
                 imm: Some(offset),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(rs1_val),
@@ -69,6 +70,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd: v_word_address,
                 imm: Some(word_address_bitmask as i64),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(ram_address),
@@ -106,6 +108,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd: v_word,
                 imm: Some(0),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(word_address),
@@ -131,6 +134,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd: v_shift,
                 imm: Some(0b11),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(ram_address),
@@ -153,6 +157,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd: v_shift,
                 imm: Some(3),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(byte_shift),
@@ -175,6 +180,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(word),
@@ -198,6 +204,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LBInstruction<WORD_S
                 rd,
                 imm: Some(24),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(left_aligned_byte),
@@ -284,6 +291,7 @@ mod test {
                     rd: Some(rd),
                     imm: Some(imm),
                     virtual_sequence_remaining: None,
+                    compressed: false,
                 },
                 register_state: RegisterState {
                     rs1_val: Some(rs1_val),
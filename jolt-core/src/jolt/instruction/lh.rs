@@ -11,7 +11,7 @@ use crate::jolt::instruction::{
 pub struct LHInstruction<const WORD_SIZE: usize>;
 
 impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_SIZE> {
-    const SEQUENCE_LENGTH: usize = 8;
+    const SEQUENCE_LENGTH: usize = RV32IM::LH.virtual_sequence_length();
 
     fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
         assert_eq!(trace_row.instruction.opcode, RV32IM::LH);
@@ -49,6 +49,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd: None,
                 imm: Some(offset),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(rs1_val),
@@ -71,6 +72,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd: v_address,
                 imm: Some(offset),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(rs1_val),
@@ -95,6 +97,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd: v_word_address,
                 imm: Some(word_address_bitmask as i64),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(ram_address),
@@ -132,6 +135,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd: v_word,
                 imm: Some(0),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(word_address),
@@ -157,6 +161,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd: v_shift,
                 imm: Some(0b10),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(ram_address),
@@ -179,6 +184,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd: v_shift,
                 imm: Some(3),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(byte_shift),
@@ -201,6 +207,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd,
                 imm: None,
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(word),
@@ -225,6 +232,7 @@ impl<const WORD_SIZE: usize> VirtualInstructionSequence for LHInstruction<WORD_S
                 rd,
                 imm: Some(16),
                 virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+                compressed: trace_row.instruction.compressed,
             },
             register_state: RegisterState {
                 rs1_val: Some(left_aligned_halfword),
@@ -309,6 +317,7 @@ mod test {
                     rd: Some(rd),
                     imm: Some(imm),
                     virtual_sequence_remaining: None,
+                    compressed: false,
                 },
                 register_state: RegisterState {
                     rs1_val: Some(rs1_val),
@@ -0,0 +1,375 @@
+//! Virtual sequences for the word loads and stores of guests that opt into misaligned
+//! accesses (see `Program::set_misaligned_accesses`). Their bytecode cannot depend on
+//! the addresses accessed at runtime, so every LW and SW of such a guest is expanded:
+//! each accesses the two aligned words spanned by its address (the same word twice
+//! if the address is aligned), and shifts and masks them into place.
+//!
+//! The tracer records a misaligned access as one access to a doubleword at the
+//! address of the lower word, holding the lower word in its low 32 bits and the
+//! upper word in its high 32 bits; an aligned access is recorded as usual.
+use common::constants::virtual_register_index;
+use tracer::{ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM};
+
+use super::virtual_sequence::{Operand, RegisterPair, VirtualSequenceBuilder};
+use super::VirtualInstructionSequence;
+
+// Virtual registers used in the sequences
+const V_ADDRESS: u64 = virtual_register_index(0);
+const V_LO_ADDRESS: u64 = virtual_register_index(1);
+const V_HI_ADDRESS: u64 = virtual_register_index(2);
+const V_SHIFT: u64 = virtual_register_index(3);
+const V_LO: u64 = virtual_register_index(4);
+const V_HI: u64 = virtual_register_index(5);
+const V_MASK: u64 = virtual_register_index(6);
+
+/// `ANDI` immediate clearing the low two bits of an address
+const WORD_ADDRESS_MASK: i64 = -4;
+
+/// Computes the address `rs1 + imm` accessed by a load or store into `V_ADDRESS`,
+/// the addresses of the lower and upper words it spans into `V_LO_ADDRESS` and
+/// `V_HI_ADDRESS`, and the offset of the address within the lower word, in bits,
+/// into `V_SHIFT`. Shifts use the low 5 bits of `V_SHIFT` only. Uses 5 instructions.
+fn word_addresses<const WORD_SIZE: usize>(
+    builder: &mut VirtualSequenceBuilder<WORD_SIZE>,
+    rs1: Operand,
+    imm: i64,
+) -> (Operand, Operand, Operand, Operand) {
+    let address = builder.addi(rs1, imm, V_ADDRESS);
+    let lo_address = builder.andi((V_ADDRESS, address), WORD_ADDRESS_MASK, V_LO_ADDRESS);
+    let hi_address = builder.addi((V_ADDRESS, address), 3, V_HI_ADDRESS);
+    let hi_address = builder.andi((V_HI_ADDRESS, hi_address), WORD_ADDRESS_MASK, V_HI_ADDRESS);
+    let shift = builder.slli((V_ADDRESS, address), 3, V_SHIFT);
+    (
+        (V_ADDRESS, address),
+        (V_LO_ADDRESS, lo_address),
+        (V_HI_ADDRESS, hi_address),
+        (V_SHIFT, shift),
+    )
+}
+
+fn dummy_trace_row(instruction: ELFInstruction, memory_state: MemoryState) -> RVTraceRow {
+    RVTraceRow {
+        instruction,
+        register_state: RegisterState {
+            rs1_val: Some(0),
+            rs2_val: Some(0),
+            rd_post_val: Some(0),
+        },
+        memory_state: Some(memory_state),
+        advice_value: None,
+        precompile_input: None,
+        precompile_output_address: None,
+    }
+}
+
+/// Loads a word from a possibly misaligned address
+pub struct LWMisalignedInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for LWMisalignedInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = RV32IM::LW_MISALIGNED.virtual_sequence_length();
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::LW_MISALIGNED);
+        let rs1 = (
+            trace_row.instruction.rs1.unwrap(),
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let rd = trace_row.instruction.rd.unwrap();
+        let imm = trace_row.instruction.imm.unwrap();
+        let value = match trace_row.memory_state {
+            Some(MemoryState::Read { address: _, value }) => value,
+            _ => panic!("Expected MemoryState::Read"),
+        };
+
+        let mut builder =
+            VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(&trace_row.instruction, 11);
+        let (address, lo_address, hi_address, shift) = word_addresses(&mut builder, rs1, imm);
+        let (hi_word, lo_word) = if address.1 % 4 == 0 {
+            (value, value)
+        } else {
+            RegisterPair::split(value)
+        };
+        let lo = builder.lw(lo_address, 0, V_LO, lo_word);
+        let hi = builder.lw(hi_address, 0, V_HI, hi_word);
+        // The low bytes of the result are the high bytes of the lower word...
+        let lo = builder.srl((V_LO, lo), shift, V_LO);
+        // ...and its high bytes the low bytes of the upper word. The shift is
+        // 32 - shift (mod 32), so the upper word is the lower word again, and
+        // shifted by 0, if the address is aligned.
+        let shift = builder.sub((0, 0), shift, V_SHIFT);
+        let hi = builder.sll((V_HI, hi), (V_SHIFT, shift), V_HI);
+        let result = builder.or((V_LO, lo), (V_HI, hi), rd);
+        assert_eq!(result, trace_row.register_state.rd_post_val.unwrap());
+        builder.finish()
+    }
+
+    fn sequence_output(_: u64, _: u64) -> u64 {
+        unimplemented!("LW loads its output from memory")
+    }
+
+    fn virtual_sequence(instruction: ELFInstruction) -> Vec<ELFInstruction> {
+        let memory_state = MemoryState::Read {
+            address: 0,
+            value: 0,
+        };
+        Self::virtual_trace(dummy_trace_row(instruction, memory_state))
+            .into_iter()
+            .map(|trace_row| trace_row.instruction)
+            .collect()
+    }
+}
+
+/// Stores a word to a possibly misaligned address
+pub struct SWMisalignedInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for SWMisalignedInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = RV32IM::SW_MISALIGNED.virtual_sequence_length();
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::SW_MISALIGNED);
+        let rs1 = (
+            trace_row.instruction.rs1.unwrap(),
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let rs2 = (
+            trace_row.instruction.rs2.unwrap(),
+            trace_row.register_state.rs2_val.unwrap(),
+        );
+        let imm = trace_row.instruction.imm.unwrap();
+        let (pre_value, post_value) = match trace_row.memory_state {
+            Some(MemoryState::Write {
+                address: _,
+                pre_value,
+                post_value,
+            }) => (pre_value, post_value),
+            _ => panic!("Expected MemoryState::Write"),
+        };
+
+        let mut builder =
+            VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(&trace_row.instruction, 21);
+        let (address, lo_address, hi_address, shift) = word_addresses(&mut builder, rs1, imm);
+        let is_aligned = address.1 % 4 == 0;
+        let (pre_hi, pre_lo) = if is_aligned {
+            (0, pre_value)
+        } else {
+            RegisterPair::split(pre_value)
+        };
+        let ones = builder.addi((0, 0), -1, V_MASK);
+
+        // Replace the bytes of the lower word from the address onwards
+        let lo = builder.lw(lo_address, 0, V_LO, pre_lo);
+        let mask = builder.sll((V_MASK, ones), shift, V_HI);
+        let mask = builder.xor((V_HI, mask), (V_MASK, ones), V_HI);
+        let lo = builder.and((V_LO, lo), (V_HI, mask), V_LO);
+        let bytes = builder.sll(rs2, shift, V_HI);
+        let lo = builder.or((V_LO, lo), (V_HI, bytes), V_LO);
+        builder.sw(lo_address, (V_LO, lo), 0, pre_lo);
+
+        // Replace the bytes of the upper word before the address + 4. If the address is
+        // aligned, the "upper word" is the word just stored, and is overwritten by the
+        // same value.
+        let pre_hi = if is_aligned { lo } else { pre_hi };
+        let hi = builder.lw(hi_address, 0, V_LO, pre_hi);
+        let shift = builder.sub((0, 0), shift, V_SHIFT);
+        let mask = builder.srl((V_MASK, ones), (V_SHIFT, shift), V_HI);
+        let mask = builder.xor((V_HI, mask), (V_MASK, ones), V_HI);
+        let hi = builder.and((V_LO, hi), (V_HI, mask), V_LO);
+        let bytes = builder.srl(rs2, (V_SHIFT, shift), V_HI);
+        let hi = builder.or((V_LO, hi), (V_HI, bytes), V_LO);
+        builder.sw(hi_address, (V_LO, hi), 0, pre_hi);
+
+        if is_aligned {
+            assert_eq!(hi, post_value);
+        } else {
+            assert_eq!((hi << 32) | lo, post_value);
+        }
+        builder.finish()
+    }
+
+    fn sequence_output(_: u64, _: u64) -> u64 {
+        unimplemented!("SW has no output")
+    }
+
+    fn virtual_sequence(instruction: ELFInstruction) -> Vec<ELFInstruction> {
+        let memory_state = MemoryState::Write {
+            address: 0,
+            pre_value: 0,
+            post_value: 0,
+        };
+        Self::virtual_trace(dummy_trace_row(instruction, memory_state))
+            .into_iter()
+            .map(|trace_row| trace_row.instruction)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jolt::instruction::virtual_sequence::tests::replay;
+    use ark_std::test_rng;
+    use common::constants::REGISTER_COUNT;
+    use rand_core::RngCore;
+    use std::collections::HashMap;
+
+    /// Memory holding `words` at the word addresses starting at `start`.
+    fn memory(start: u64, words: &[u64]) -> HashMap<u64, u64> {
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (start + 4 * i as u64, *word))
+            .collect()
+    }
+
+    /// Reads the (possibly misaligned) word at `address` from `memory`.
+    fn read_word(memory: &HashMap<u64, u64>, address: u64) -> u64 {
+        let word_address = address & !3;
+        let doubleword = memory[&word_address] | (memory[&(word_address + 4)] << 32);
+        (doubleword >> ((address % 4) * 8)) & u32::MAX as u64
+    }
+
+    fn row(
+        opcode: RV32IM,
+        rs1: (u64, u64),
+        rs2: Option<(u64, u64)>,
+        rd: Option<(u64, u64)>,
+        imm: i64,
+        memory_state: MemoryState,
+    ) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address: 0x8000_0000,
+                opcode,
+                rs1: Some(rs1.0),
+                rs2: rs2.map(|(register, _)| register),
+                rd: rd.map(|(register, _)| register),
+                imm: Some(imm),
+                virtual_sequence_remaining: None,
+                compressed: false,
+            },
+            register_state: RegisterState {
+                rs1_val: Some(rs1.1),
+                rs2_val: rs2.map(|(_, value)| value),
+                rd_post_val: rd.map(|(_, value)| value),
+            },
+            memory_state: Some(memory_state),
+            advice_value: None,
+            precompile_input: None,
+            precompile_output_address: None,
+        }
+    }
+
+    #[test]
+    fn lw_misaligned_virtual_sequence_32() {
+        let mut rng = test_rng();
+        let base_address = 0x8000_1000u64;
+        for _ in 0..256 {
+            let words: Vec<u64> = (0..4).map(|_| rng.next_u32() as u64).collect();
+            let mut memory = memory(base_address, &words);
+            let offset = rng.next_u64() % 8;
+            let imm = (rng.next_u64() % 8) as i64 - 4;
+            let rs1_val = (base_address as i64 + 4 + offset as i64 - imm) as u64;
+            let address = (rs1_val as i64 + imm) as u64;
+            let word_address = address & !3;
+            let value = read_word(&memory, address);
+            let recorded = if address % 4 == 0 {
+                memory[&word_address]
+            } else {
+                memory[&word_address] | (memory[&(word_address + 4)] << 32)
+            };
+
+            let (rs1, rd) = (1, 2);
+            let trace = LWMisalignedInstruction::<32>::virtual_trace(row(
+                RV32IM::LW_MISALIGNED,
+                (rs1, rs1_val),
+                None,
+                Some((rd, value)),
+                imm,
+                MemoryState::Read {
+                    address: word_address,
+                    value: recorded,
+                },
+            ));
+            assert_eq!(trace.len(), LWMisalignedInstruction::<32>::SEQUENCE_LENGTH);
+
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs1 as usize] = rs1_val;
+            replay(&trace, &mut registers, &mut memory);
+            assert_eq!(registers[rd as usize], value);
+        }
+    }
+
+    #[test]
+    fn sw_misaligned_virtual_sequence_32() {
+        let mut rng = test_rng();
+        let base_address = 0x8000_1000u64;
+        for _ in 0..256 {
+            let words: Vec<u64> = (0..4).map(|_| rng.next_u32() as u64).collect();
+            let mut memory = memory(base_address, &words);
+            let address = base_address + 4 + rng.next_u64() % 8;
+            let word_address = address & !3;
+            let value = rng.next_u32() as u64;
+
+            let mut expected = memory.clone();
+            let shift = (address % 4) * 8;
+            let pre_value = memory[&word_address] | (memory[&(word_address + 4)] << 32);
+            let post_value = (pre_value & !(0xffffffffu64 << shift)) | (value << shift);
+            expected.insert(word_address, post_value & u32::MAX as u64);
+            expected.insert(word_address + 4, post_value >> 32);
+            let memory_state = if address % 4 == 0 {
+                MemoryState::Write {
+                    address: word_address,
+                    pre_value: pre_value & u32::MAX as u64,
+                    post_value: value,
+                }
+            } else {
+                MemoryState::Write {
+                    address: word_address,
+                    pre_value,
+                    post_value,
+                }
+            };
+
+            let (rs1, rs2) = (1, 2);
+            let trace = SWMisalignedInstruction::<32>::virtual_trace(row(
+                RV32IM::SW_MISALIGNED,
+                (rs1, address),
+                Some((rs2, value)),
+                None,
+                0,
+                memory_state,
+            ));
+            assert_eq!(trace.len(), SWMisalignedInstruction::<32>::SEQUENCE_LENGTH);
+
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs1 as usize] = address;
+            registers[rs2 as usize] = value;
+            replay(&trace, &mut registers, &mut memory);
+            assert_eq!(memory, expected);
+            assert_eq!(read_word(&memory, address), value);
+        }
+    }
+
+    #[test]
+    fn misaligned_virtual_sequences() {
+        // The bytecode of the sequences cannot depend on the alignment of the address
+        for imm in 0..4 {
+            let instruction = |opcode| ELFInstruction {
+                address: 0x8000_0000,
+                opcode,
+                rs1: Some(1),
+                rs2: Some(2),
+                rd: Some(3),
+                imm: Some(imm),
+                virtual_sequence_remaining: None,
+                compressed: false,
+            };
+            let lw =
+                LWMisalignedInstruction::<32>::virtual_sequence(instruction(RV32IM::LW_MISALIGNED));
+            let sw =
+                SWMisalignedInstruction::<32>::virtual_sequence(instruction(RV32IM::SW_MISALIGNED));
+            assert_eq!(lw.len(), LWMisalignedInstruction::<32>::SEQUENCE_LENGTH);
+            assert_eq!(sw.len(), SWMisalignedInstruction::<32>::SEQUENCE_LENGTH);
+        }
+    }
+}
@@ -151,32 +151,40 @@ pub trait VirtualInstructionSequence {
 
 pub mod add;
 pub mod and;
+pub mod argmax;
+pub mod atomic;
 pub mod beq;
 pub mod bge;
 pub mod bgeu;
 pub mod bne;
 pub mod div;
 pub mod divu;
+pub mod exp;
+pub mod fixed_point;
 pub mod lb;
 pub mod lbu;
 pub mod lh;
 pub mod lhu;
+pub mod misaligned;
 pub mod mul;
 pub mod mulh;
 pub mod mulhsu;
 pub mod mulhu;
 pub mod mulu;
 pub mod or;
+pub mod relu;
 pub mod rem;
 pub mod remu;
 pub mod sb;
 pub mod sh;
+pub mod sigmoid;
 pub mod sll;
 pub mod slt;
 pub mod sltu;
 pub mod sra;
 pub mod srl;
 pub mod sub;
+pub mod tree_step;
 pub mod virtual_advice;
 pub mod virtual_assert_aligned_memory_access;
 pub mod virtual_assert_lte;
@@ -185,6 +193,7 @@ pub mod virtual_assert_valid_signed_remainder;
 pub mod virtual_assert_valid_unsigned_remainder;
 pub mod virtual_move;
 pub mod virtual_movsign;
+pub mod virtual_sequence;
 pub mod xor;
 
 #[cfg(test)]
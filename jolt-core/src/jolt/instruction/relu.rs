@@ -0,0 +1,163 @@
+use ark_std::log2;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::JoltInstruction;
+use crate::{
+    field::JoltField,
+    jolt::{
+        instruction::SubtableIndices,
+        subtable::{
+            identity::IdentitySubtable, left_msb::LeftMSBSubtable, relu::ReluSubtable,
+            LassoSubtable,
+        },
+    },
+    utils::instruction_utils::{chunk_operand_usize, concatenate_lookups},
+};
+
+/// `max(x, 0)` for a signed word `x`.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RELUInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for RELUInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        // TODO(moodlezoup): make this work with different M
+        assert!(M == 1 << 16);
+        let vals_by_subtable = self.slice_values(vals, C, M);
+        let relu = vals_by_subtable[0];
+        let sign = vals_by_subtable[1];
+        let identity = vals_by_subtable[2];
+
+        // The most significant chunk goes through the ReLU subtable, and the remaining
+        // chunks are zeroed if it is negative
+        let msb_chunk_index = C - (WORD_SIZE / 16);
+        let low_chunks = &identity[msb_chunk_index + 1..];
+        relu[0] * F::from_u64(1 << (16 * low_chunks.len()))
+            + (F::one() - sign[0])
+                * concatenate_lookups(low_chunks, low_chunks.len(), log2(M) as usize)
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        2
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        assert!(M == 1 << 16);
+        let msb_chunk_index = C - (WORD_SIZE / 16);
+        vec![
+            (
+                Box::new(ReluSubtable::<F>::new()),
+                SubtableIndices::from(msb_chunk_index),
+            ),
+            (
+                // The sign bit, i.e. the MSB of the most significant chunk
+                Box::new(LeftMSBSubtable::<F>::new()),
+                SubtableIndices::from(msb_chunk_index),
+            ),
+            (
+                // Also range-checks the remaining query chunks
+                Box::new(IdentitySubtable::<F>::new()),
+                SubtableIndices::from(0..C),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_operand_usize(self.0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        match WORD_SIZE {
+            32 => (self.0 as i32).max(0) as u64,
+            64 => (self.0 as i64).max(0) as u64,
+            _ => panic!("only implemented for u32 / u64"),
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::RELUInstruction;
+
+    #[test]
+    fn relu_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            let instruction = RELUInstruction::<WORD_SIZE>(x);
+            jolt_instruction_test!(instruction);
+        }
+
+        // Edge cases
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            RELUInstruction::<WORD_SIZE>(0),
+            RELUInstruction::<WORD_SIZE>(1),
+            RELUInstruction::<WORD_SIZE>(1 << 16),
+            RELUInstruction::<WORD_SIZE>((1 << 31) - 1),
+            RELUInstruction::<WORD_SIZE>(1 << 31),
+            RELUInstruction::<WORD_SIZE>(u32_max),
+            RELUInstruction::<WORD_SIZE>(u32_max - 0xffff),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn relu_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            let instruction = RELUInstruction::<WORD_SIZE>(x);
+            jolt_instruction_test!(instruction);
+        }
+
+        // Edge cases
+        let instructions = vec![
+            RELUInstruction::<WORD_SIZE>(0),
+            RELUInstruction::<WORD_SIZE>(1 << 32),
+            RELUInstruction::<WORD_SIZE>(i64::MAX as u64),
+            RELUInstruction::<WORD_SIZE>(1 << 63),
+            RELUInstruction::<WORD_SIZE>(u64::MAX),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use common::fixed_point;
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::JoltInstruction;
+use crate::{
+    field::JoltField,
+    jolt::{
+        instruction::SubtableIndices,
+        subtable::{identity::IdentitySubtable, sigmoid::SigmoidSubtable, LassoSubtable},
+    },
+    utils::instruction_utils::chunk_operand_usize,
+};
+
+/// The logistic function `1 / (1 + e^-x)` as a Q16.16 value, where `x` is the lower half
+/// of a word as a signed Q8.8 value (see `common::fixed_point::sigmoid`).
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SIGMOIDInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for SIGMOIDInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        assert!(M == 1 << 16);
+        let vals_by_subtable = self.slice_values(vals, C, M);
+        // The identity lookups only range-check the query chunks
+        vals_by_subtable[0][0]
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        assert!(M == 1 << 16);
+        vec![
+            (
+                // The least significant chunk, i.e. the lower half of the word
+                Box::new(SigmoidSubtable::<F>::new()),
+                SubtableIndices::from(C - 1),
+            ),
+            (
+                Box::new(IdentitySubtable::<F>::new()),
+                SubtableIndices::from(0..C),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_operand_usize(self.0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        fixed_point::sigmoid(self.0 as i16) as u64
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::SIGMOIDInstruction;
+
+    #[test]
+    fn sigmoid_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..64 {
+            let x = rng.next_u32() as u64;
+            let instruction = SIGMOIDInstruction::<WORD_SIZE>(x);
+            jolt_instruction_test!(instruction);
+        }
+
+        // Edge cases: 0, ±1.0, the most negative and positive inputs, and the points
+        // where the result saturates
+        let instructions = vec![
+            SIGMOIDInstruction::<WORD_SIZE>(0),
+            SIGMOIDInstruction::<WORD_SIZE>(0x100),
+            SIGMOIDInstruction::<WORD_SIZE>(0xff00),
+            SIGMOIDInstruction::<WORD_SIZE>(0x8000),
+            SIGMOIDInstruction::<WORD_SIZE>(0x7fff),
+            SIGMOIDInstruction::<WORD_SIZE>(4096),
+            SIGMOIDInstruction::<WORD_SIZE>(-4096i32 as u32 as u64),
+            SIGMOIDInstruction::<WORD_SIZE>(0xffff_0100),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+        assert_eq!(SIGMOIDInstruction::<WORD_SIZE>(0).lookup_entry(), 1 << 15);
+        assert_eq!(
+            SIGMOIDInstruction::<WORD_SIZE>(0xffff_0100).lookup_entry(),
+            SIGMOIDInstruction::<WORD_SIZE>(0x100).lookup_entry()
+        );
+    }
+}
@@ -65,6 +65,7 @@ macro_rules! jolt_virtual_sequence_test {
                 rd: Some(rd),
                 imm: None,
                 virtual_sequence_remaining: None,
+                compressed: false,
             },
             register_state: RegisterState {
                 rs1_val: Some(x),
@@ -0,0 +1,161 @@
+//! The virtual sequence of `TREE_STEP`, which takes one step down a decision tree (see
+//! `common::constants::TREE_STEP_INSTRUCTION`). A guest walks a depth-k tree with k of
+//! them, so each level costs one signed comparison lookup (plus the loads and shifts
+//! around it), and the node table is an ordinary part of the guest's memory, checked
+//! like any other.
+//!
+//! The tracer records the three words the instruction reads (the node's split and child
+//! offset, then the feature) in the row's `precompile_input`.
+use common::constants::virtual_register_index;
+use tracer::{ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM};
+
+use super::virtual_sequence::VirtualSequenceBuilder;
+use super::VirtualInstructionSequence;
+
+// Virtual registers used in the sequence
+const V_SPLIT: u64 = virtual_register_index(0);
+const V_CHILD: u64 = virtual_register_index(1);
+const V_FEATURE: u64 = virtual_register_index(2);
+
+/// Steps from the node at `rs1` to one of its children, by comparing a feature at `rs2`
+/// with the node's threshold
+pub struct TREESTEPInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for TREESTEPInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = RV32IM::TREE_STEP.virtual_sequence_length();
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::TREE_STEP);
+        let node = (
+            trace_row.instruction.rs1.unwrap(),
+            trace_row.register_state.rs1_val.unwrap(),
+        );
+        let features = (
+            trace_row.instruction.rs2.unwrap(),
+            trace_row.register_state.rs2_val.unwrap(),
+        );
+        let rd = trace_row.instruction.rd.unwrap();
+        let [split, offset, feature] = match trace_row.precompile_input {
+            Some(words) => [words[0], words[1], words[2]].map(u64::from),
+            None => panic!("Expected the words read by TREE_STEP"),
+        };
+
+        let mut builder = VirtualSequenceBuilder::<WORD_SIZE>::for_instruction(
+            &trace_row.instruction,
+            Self::SEQUENCE_LENGTH,
+        );
+        let split = builder.lw(node, 0, V_SPLIT, split);
+        let offset = builder.lw(node, 4, V_CHILD, offset);
+        // The feature's address is `rs2` plus 4 times the lower half of the split
+        let index = builder.slli((V_SPLIT, split), 16, V_FEATURE);
+        let index = builder.srli((V_FEATURE, index), 14, V_FEATURE);
+        let address = builder.add(features, (V_FEATURE, index), V_FEATURE);
+        let feature = builder.lw((V_FEATURE, address), 0, V_FEATURE, feature);
+        // Step to the right child, 8 bytes past the left one, iff threshold < feature
+        let threshold = builder.srai((V_SPLIT, split), 16, V_SPLIT);
+        let right = builder.slt((V_SPLIT, threshold), (V_FEATURE, feature), V_SPLIT);
+        let right = builder.slli((V_SPLIT, right), 3, V_SPLIT);
+        let left_child = builder.add(node, (V_CHILD, offset), V_CHILD);
+        let child = builder.add((V_CHILD, left_child), (V_SPLIT, right), rd);
+        assert_eq!(child, trace_row.register_state.rd_post_val.unwrap());
+        builder.finish()
+    }
+
+    fn sequence_output(_: u64, _: u64) -> u64 {
+        unimplemented!("TREE_STEP loads its operands from memory")
+    }
+
+    fn virtual_sequence(instruction: ELFInstruction) -> Vec<ELFInstruction> {
+        let dummy_trace_row = RVTraceRow {
+            instruction,
+            register_state: RegisterState {
+                rs1_val: Some(0),
+                rs2_val: Some(0),
+                rd_post_val: Some(0),
+            },
+            memory_state: Some(MemoryState::Read {
+                address: 0,
+                value: 0,
+            }),
+            advice_value: None,
+            precompile_input: Some([0; 16]),
+            precompile_output_address: None,
+        };
+        Self::virtual_trace(dummy_trace_row)
+            .into_iter()
+            .map(|trace_row| trace_row.instruction)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jolt::instruction::virtual_sequence::tests::replay;
+    use ark_std::test_rng;
+    use common::constants::REGISTER_COUNT;
+    use rand_core::RngCore;
+    use std::collections::HashMap;
+
+    #[test]
+    fn tree_step_virtual_sequence_32() {
+        let mut rng = test_rng();
+        let (nodes_address, features_address) = (0x8000_1000u64, 0x8000_2000u64);
+        let num_features = 16;
+        for i in 0..256 {
+            let feature_index = rng.next_u64() % num_features;
+            let threshold = rng.next_u32() as u16;
+            let split = ((threshold as u64) << 16) | feature_index;
+            let offset = 8 * (1 + rng.next_u64() % 64);
+            let node = nodes_address + 8 * (rng.next_u64() % 64);
+            let feature = match i % 3 {
+                0 => rng.next_u32() as u64,
+                // Ties go left
+                1 => threshold as i16 as i32 as u32 as u64,
+                _ => (threshold as i16 as i32 + 1) as u32 as u64,
+            };
+
+            let mut memory = HashMap::new();
+            memory.insert(node, split);
+            memory.insert(node + 4, offset);
+            memory.insert(features_address + 4 * feature_index, feature);
+            let right = (feature as u32 as i32) > (threshold as i16 as i32);
+            let child = node + offset + if right { 8 } else { 0 };
+
+            let (rs1, rs2, rd) = (10, 11, 10);
+            let mut words = [0; 16];
+            words[..3].copy_from_slice(&[split as u32, offset as u32, feature as u32]);
+            let trace = TREESTEPInstruction::<32>::virtual_trace(RVTraceRow {
+                instruction: ELFInstruction {
+                    address: 0x8000_0000,
+                    opcode: RV32IM::TREE_STEP,
+                    rs1: Some(rs1),
+                    rs2: Some(rs2),
+                    rd: Some(rd),
+                    imm: None,
+                    virtual_sequence_remaining: None,
+                    compressed: false,
+                },
+                register_state: RegisterState {
+                    rs1_val: Some(node),
+                    rs2_val: Some(features_address),
+                    rd_post_val: Some(child),
+                },
+                memory_state: Some(MemoryState::Read {
+                    address: features_address + 4 * feature_index,
+                    value: feature,
+                }),
+                advice_value: None,
+                precompile_input: Some(words),
+                precompile_output_address: None,
+            });
+            assert_eq!(trace.len(), TREESTEPInstruction::<32>::SEQUENCE_LENGTH);
+
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            registers[rs1 as usize] = node;
+            registers[rs2 as usize] = features_address;
+            replay(&trace, &mut registers, &mut memory);
+            assert_eq!(registers[rd as usize], child);
+        }
+    }
+}
@@ -0,0 +1,627 @@
+use tracer::{ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM};
+
+use crate::jolt::instruction::{
+    add::ADDInstruction, and::ANDInstruction, mulhu::MULHUInstruction, mulu::MULUInstruction,
+    or::ORInstruction, sll::SLLInstruction, slt::SLTInstruction, sltu::SLTUInstruction,
+    sra::SRAInstruction, srl::SRLInstruction, sub::SUBInstruction,
+    virtual_movsign::MOVSIGNInstruction, xor::XORInstruction, JoltInstruction,
+};
+
+/// A 64-bit value held in two 32-bit registers, as `hi:lo`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterPair {
+    pub hi: u64,
+    pub lo: u64,
+}
+
+impl RegisterPair {
+    pub fn new(hi: u64, lo: u64) -> Self {
+        Self { hi, lo }
+    }
+
+    /// Splits `value` into its (hi, lo) words.
+    pub fn split(value: u64) -> (u64, u64) {
+        (value >> 32, value & u32::MAX as u64)
+    }
+
+    fn contains(&self, register: u64) -> bool {
+        self.hi == register || self.lo == register
+    }
+}
+
+/// A register index together with its value at this point in the sequence.
+pub type Operand = (u64, u64);
+
+/// Builds the trace of a virtual instruction sequence one instruction at a time,
+/// computing each instruction's output and filling in `virtual_sequence_remaining`.
+pub struct VirtualSequenceBuilder<const WORD_SIZE: usize> {
+    address: u64,
+    compressed: bool,
+    sequence_length: usize,
+    trace: Vec<RVTraceRow>,
+}
+
+impl<const WORD_SIZE: usize> VirtualSequenceBuilder<WORD_SIZE> {
+    /// `address` is the address of the instruction being expanded into a sequence
+    /// of `sequence_length` instructions.
+    pub fn new(address: u64, sequence_length: usize) -> Self {
+        Self {
+            address,
+            compressed: false,
+            sequence_length,
+            trace: Vec::with_capacity(sequence_length),
+        }
+    }
+
+    /// A builder for the sequence that `instruction` is expanded into, which takes
+    /// its address and length.
+    pub fn for_instruction(instruction: &ELFInstruction, sequence_length: usize) -> Self {
+        Self {
+            compressed: instruction.compressed,
+            ..Self::new(instruction.address, sequence_length)
+        }
+    }
+
+    fn push(
+        &mut self,
+        opcode: RV32IM,
+        rs1: Option<Operand>,
+        rs2: Option<Operand>,
+        rd: Option<Operand>,
+        imm: Option<i64>,
+        memory_state: Option<MemoryState>,
+    ) {
+        assert!(
+            self.trace.len() < self.sequence_length,
+            "virtual sequence is longer than {}",
+            self.sequence_length
+        );
+        self.trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: self.address,
+                opcode,
+                rs1: rs1.map(|(register, _)| register),
+                rs2: rs2.map(|(register, _)| register),
+                rd: rd.map(|(register, _)| register),
+                imm,
+                virtual_sequence_remaining: Some(self.sequence_length - self.trace.len() - 1),
+                compressed: self.compressed,
+            },
+            register_state: RegisterState {
+                rs1_val: rs1.map(|(_, value)| value),
+                rs2_val: rs2.map(|(_, value)| value),
+                // x0 is hardwired to zero
+                rd_post_val: rd.map(|(register, value)| if register == 0 { 0 } else { value }),
+            },
+            memory_state,
+            advice_value: None,
+            precompile_input: None,
+            precompile_output_address: None,
+        });
+    }
+
+    pub fn add(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = ADDInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::ADD,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn sub(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = SUBInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::SUB,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn slt(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = SLTInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::SLT,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn sltu(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = SLTUInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::SLTU,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn or(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = ORInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::OR,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn and(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = ANDInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::AND,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn xor(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = XORInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::XOR,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn sll(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = SLLInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::SLL,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn srl(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = SRLInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::SRL,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    /// The low `WORD_SIZE` bits of `rs1 * rs2`.
+    pub fn mulu(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = MULUInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::MULU,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    pub fn mulhu(&mut self, rs1: Operand, rs2: Operand, rd: u64) -> u64 {
+        let result = MULHUInstruction::<WORD_SIZE>(rs1.1, rs2.1).lookup_entry();
+        self.push(
+            RV32IM::MULHU,
+            Some(rs1),
+            Some(rs2),
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    /// All ones if `rs1` is negative, and zero otherwise.
+    pub fn movsign(&mut self, rs1: Operand, rd: u64) -> u64 {
+        let result = MOVSIGNInstruction::<WORD_SIZE>(rs1.1).lookup_entry();
+        self.push(
+            RV32IM::VIRTUAL_MOVSIGN,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            None,
+            None,
+        );
+        result
+    }
+
+    /// The second lookup operand of an instruction with immediate `imm`, which (like
+    /// the bytecode) keeps only its low `WORD_SIZE` bits.
+    fn imm_operand(imm: i64) -> u64 {
+        match WORD_SIZE {
+            32 => imm as u64 & u32::MAX as u64,
+            64 => imm as u64,
+            _ => panic!("Unsupported WORD_SIZE: {}", WORD_SIZE),
+        }
+    }
+
+    pub fn addi(&mut self, rs1: Operand, imm: i64, rd: u64) -> u64 {
+        let result = ADDInstruction::<WORD_SIZE>(rs1.1, Self::imm_operand(imm)).lookup_entry();
+        self.push(
+            RV32IM::ADDI,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            Some(imm),
+            None,
+        );
+        result
+    }
+
+    pub fn andi(&mut self, rs1: Operand, imm: i64, rd: u64) -> u64 {
+        let result = ANDInstruction::<WORD_SIZE>(rs1.1, Self::imm_operand(imm)).lookup_entry();
+        self.push(
+            RV32IM::ANDI,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            Some(imm),
+            None,
+        );
+        result
+    }
+
+    pub fn xori(&mut self, rs1: Operand, imm: i64, rd: u64) -> u64 {
+        let result = XORInstruction::<WORD_SIZE>(rs1.1, Self::imm_operand(imm)).lookup_entry();
+        self.push(
+            RV32IM::XORI,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            Some(imm),
+            None,
+        );
+        result
+    }
+
+    pub fn slli(&mut self, rs1: Operand, imm: i64, rd: u64) -> u64 {
+        let result = SLLInstruction::<WORD_SIZE>(rs1.1, Self::imm_operand(imm)).lookup_entry();
+        self.push(
+            RV32IM::SLLI,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            Some(imm),
+            None,
+        );
+        result
+    }
+
+    pub fn srli(&mut self, rs1: Operand, imm: i64, rd: u64) -> u64 {
+        let result = SRLInstruction::<WORD_SIZE>(rs1.1, Self::imm_operand(imm)).lookup_entry();
+        self.push(
+            RV32IM::SRLI,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            Some(imm),
+            None,
+        );
+        result
+    }
+
+    pub fn srai(&mut self, rs1: Operand, imm: i64, rd: u64) -> u64 {
+        let result = SRAInstruction::<WORD_SIZE>(rs1.1, Self::imm_operand(imm)).lookup_entry();
+        self.push(
+            RV32IM::SRAI,
+            Some(rs1),
+            None,
+            Some((rd, result)),
+            Some(imm),
+            None,
+        );
+        result
+    }
+
+    /// Loads `value`, the word at `base + imm`, into `rd`.
+    pub fn lw(&mut self, base: Operand, imm: i64, rd: u64, value: u64) -> u64 {
+        let address = ADDInstruction::<WORD_SIZE>(base.1, imm as u64).lookup_entry();
+        self.push(
+            RV32IM::LW,
+            Some(base),
+            None,
+            Some((rd, value)),
+            Some(imm),
+            Some(MemoryState::Read { address, value }),
+        );
+        value
+    }
+
+    /// Stores `rs2` to the word at `base + imm`, which previously held `pre_value`.
+    pub fn sw(&mut self, base: Operand, rs2: Operand, imm: i64, pre_value: u64) {
+        let address = ADDInstruction::<WORD_SIZE>(base.1, imm as u64).lookup_entry();
+        self.push(
+            RV32IM::SW,
+            Some(base),
+            Some(rs2),
+            None,
+            Some(imm),
+            Some(MemoryState::Write {
+                address,
+                pre_value,
+                post_value: rs2.1,
+            }),
+        );
+    }
+
+    /// Returns the sequence's trace, checking that it has the declared length.
+    pub fn finish(self) -> Vec<RVTraceRow> {
+        assert_eq!(
+            self.trace.len(),
+            self.sequence_length,
+            "virtual sequence is shorter than declared"
+        );
+        self.trace
+    }
+}
+
+/// Helpers for 64-bit values held in register pairs. Carries and comparisons are
+/// derived from SLTU, so these need no lookup tables beyond those of RV32I.
+impl VirtualSequenceBuilder<32> {
+    /// Loads the little-endian doubleword at `base + imm` into `rd`. `value` is the
+    /// doubleword in memory. Uses 2 instructions.
+    pub fn load_pair(&mut self, base: Operand, imm: i64, rd: RegisterPair, value: u64) -> u64 {
+        assert!(
+            rd.lo != base.0,
+            "loading the low word would clobber the base register"
+        );
+        let (hi, lo) = RegisterPair::split(value);
+        self.lw(base, imm, rd.lo, lo);
+        self.lw(base, imm + 4, rd.hi, hi);
+        value
+    }
+
+    /// Stores `rs2` as a little-endian doubleword at `base + imm`, which previously
+    /// held `pre_value`. Uses 2 instructions.
+    pub fn store_pair(
+        &mut self,
+        base: Operand,
+        imm: i64,
+        rs2: (RegisterPair, u64),
+        pre_value: u64,
+    ) {
+        let (pair, value) = rs2;
+        let (hi, lo) = RegisterPair::split(value);
+        let (pre_hi, pre_lo) = RegisterPair::split(pre_value);
+        self.sw(base, (pair.lo, lo), imm, pre_lo);
+        self.sw(base, (pair.hi, hi), imm + 4, pre_hi);
+    }
+
+    /// Computes `rs1 + rs2` (mod 2^64) into `rd`, using `carry` as a scratch
+    /// register. Uses 4 instructions.
+    pub fn add_pair(
+        &mut self,
+        rs1: (RegisterPair, u64),
+        rs2: (RegisterPair, u64),
+        rd: RegisterPair,
+        carry: u64,
+    ) -> u64 {
+        let ((x, x_val), (y, y_val)) = (rs1, rs2);
+        for register in [rd.hi, rd.lo, carry] {
+            assert!(
+                !x.contains(register) && !y.contains(register),
+                "destination registers must not overlap the operands"
+            );
+        }
+        assert!(!rd.contains(carry));
+
+        let (x_hi, x_lo) = RegisterPair::split(x_val);
+        let (y_hi, y_lo) = RegisterPair::split(y_val);
+        let lo = self.add((x.lo, x_lo), (y.lo, y_lo), rd.lo);
+        // The low word overflowed iff the sum is less than either summand
+        let c = self.sltu((rd.lo, lo), (x.lo, x_lo), carry);
+        let hi = self.add((x.hi, x_hi), (y.hi, y_hi), rd.hi);
+        let hi = self.add((rd.hi, hi), (carry, c), rd.hi);
+        (hi << 32) | lo
+    }
+
+    /// Computes `rs1 < rs2` (unsigned) into `rd`, using `scratch` as two scratch
+    /// registers. Uses 6 instructions.
+    pub fn sltu_pair(
+        &mut self,
+        rs1: (RegisterPair, u64),
+        rs2: (RegisterPair, u64),
+        rd: u64,
+        scratch: RegisterPair,
+    ) -> u64 {
+        let ((x, x_val), (y, y_val)) = (rs1, rs2);
+        for register in [rd, scratch.hi, scratch.lo] {
+            assert!(
+                !x.contains(register) && !y.contains(register),
+                "destination registers must not overlap the operands"
+            );
+        }
+        assert!(!scratch.contains(rd));
+
+        let (x_hi, x_lo) = RegisterPair::split(x_val);
+        let (y_hi, y_lo) = RegisterPair::split(y_val);
+        // x < y iff x_hi < y_hi, or x_hi == y_hi and x_lo < y_lo
+        let hi_lt = self.sltu((x.hi, x_hi), (y.hi, y_hi), scratch.lo);
+        let hi_gt = self.sltu((y.hi, y_hi), (x.hi, x_hi), scratch.hi);
+        let hi_ne = self.or((scratch.lo, hi_lt), (scratch.hi, hi_gt), scratch.hi);
+        let lo_lt = self.sltu((x.lo, x_lo), (y.lo, y_lo), rd);
+        // 1 iff hi_ne == 0 and lo_lt == 1
+        let hi_eq_lo_lt = self.sltu((scratch.hi, hi_ne), (rd, lo_lt), rd);
+        self.or((scratch.lo, hi_lt), (rd, hi_eq_lo_lt), rd)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ark_std::test_rng;
+    use common::constants::{virtual_register_index, REGISTER_COUNT};
+    use rand_chacha::rand_core::RngCore;
+    use std::collections::HashMap;
+
+    /// Replays `trace`, checking that every instruction reads the values currently
+    /// in its source registers and memory.
+    pub(crate) fn replay(
+        trace: &[RVTraceRow],
+        registers: &mut [u64],
+        memory: &mut HashMap<u64, u64>,
+    ) {
+        for (i, row) in trace.iter().enumerate() {
+            assert_eq!(
+                row.instruction.virtual_sequence_remaining,
+                Some(trace.len() - i - 1)
+            );
+            if let Some(rs1) = row.instruction.rs1 {
+                assert_eq!(registers[rs1 as usize], row.register_state.rs1_val.unwrap());
+            }
+            if let Some(rs2) = row.instruction.rs2 {
+                assert_eq!(registers[rs2 as usize], row.register_state.rs2_val.unwrap());
+            }
+            match row.memory_state {
+                Some(MemoryState::Read { address, value }) => {
+                    assert_eq!(memory[&address], value);
+                }
+                Some(MemoryState::Write {
+                    address,
+                    pre_value,
+                    post_value,
+                }) => {
+                    assert_eq!(memory[&address], pre_value);
+                    memory.insert(address, post_value);
+                }
+                None => {}
+            }
+            if let Some(rd) = row.instruction.rd {
+                registers[rd as usize] = row.register_state.rd_post_val.unwrap();
+            }
+        }
+    }
+
+    fn pair(hi: u64, lo: u64) -> RegisterPair {
+        RegisterPair::new(virtual_register_index(hi), virtual_register_index(lo))
+    }
+
+    fn set_pair(registers: &mut [u64], pair: RegisterPair, value: u64) {
+        let (hi, lo) = RegisterPair::split(value);
+        registers[pair.hi as usize] = hi;
+        registers[pair.lo as usize] = lo;
+    }
+
+    fn get_pair(registers: &[u64], pair: RegisterPair) -> u64 {
+        (registers[pair.hi as usize] << 32) | registers[pair.lo as usize]
+    }
+
+    #[test]
+    fn add_pair() {
+        let mut rng = test_rng();
+        let (x, y, rd) = (pair(0, 1), pair(2, 3), pair(4, 5));
+        let carry = virtual_register_index(6);
+        for i in 0..256 {
+            let x_val = rng.next_u64();
+            // Exercise carries out of the low word
+            let y_val = if i % 2 == 0 {
+                rng.next_u64()
+            } else {
+                (rng.next_u64() & !(u32::MAX as u64)) | (u32::MAX as u64 - (x_val & 0xff))
+            };
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            set_pair(&mut registers, x, x_val);
+            set_pair(&mut registers, y, y_val);
+
+            let mut builder = VirtualSequenceBuilder::<32>::new(0, 4);
+            let result = builder.add_pair((x, x_val), (y, y_val), rd, carry);
+            replay(&builder.finish(), &mut registers, &mut HashMap::new());
+
+            assert_eq!(result, x_val.wrapping_add(y_val));
+            assert_eq!(get_pair(&registers, rd), result);
+        }
+    }
+
+    #[test]
+    fn sltu_pair() {
+        let mut rng = test_rng();
+        let (x, y, scratch) = (pair(0, 1), pair(2, 3), pair(4, 5));
+        let rd = virtual_register_index(6);
+        for i in 0..256 {
+            let x_val = rng.next_u64();
+            let y_val = match i % 3 {
+                0 => rng.next_u64(),
+                // Equal high words
+                1 => (x_val & !(u32::MAX as u64)) | (rng.next_u32() as u64),
+                _ => x_val,
+            };
+            let mut registers = vec![0u64; REGISTER_COUNT as usize];
+            set_pair(&mut registers, x, x_val);
+            set_pair(&mut registers, y, y_val);
+
+            let mut builder = VirtualSequenceBuilder::<32>::new(0, 6);
+            let result = builder.sltu_pair((x, x_val), (y, y_val), rd, scratch);
+            replay(&builder.finish(), &mut registers, &mut HashMap::new());
+
+            assert_eq!(result, (x_val < y_val) as u64);
+            assert_eq!(registers[rd as usize], result);
+        }
+    }
+
+    #[test]
+    fn load_store_pair() {
+        let mut rng = test_rng();
+        let base = virtual_register_index(0);
+        let (src, dst) = (pair(1, 2), pair(3, 4));
+        let base_address = 0x8000_0000u64;
+        let (old, new) = (rng.next_u64(), rng.next_u64());
+
+        let mut memory = HashMap::new();
+        memory.insert(base_address + 8, old & u32::MAX as u64);
+        memory.insert(base_address + 12, old >> 32);
+        let mut registers = vec![0u64; REGISTER_COUNT as usize];
+        registers[base as usize] = base_address;
+        set_pair(&mut registers, src, new);
+
+        let mut builder = VirtualSequenceBuilder::<32>::new(0, 6);
+        assert_eq!(builder.load_pair((base, base_address), 8, dst, old), old);
+        builder.store_pair((base, base_address), 8, (src, new), old);
+        assert_eq!(builder.load_pair((base, base_address), 8, dst, new), new);
+        replay(&builder.finish(), &mut registers, &mut memory);
+
+        assert_eq!(get_pair(&registers, dst), new);
+        assert_eq!(memory[&(base_address + 8)], new & u32::MAX as u64);
+        assert_eq!(memory[&(base_address + 12)], new >> 32);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sequence_length_mismatch() {
+        let mut builder = VirtualSequenceBuilder::<32>::new(0, 2);
+        builder.add((1, 1), (2, 2), 3);
+        builder.finish();
+    }
+}
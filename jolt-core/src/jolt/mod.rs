@@ -1,4 +1,11 @@
+pub mod compat;
+#[cfg(all(feature = "ffi", not(target_arch = "wasm32")))]
+pub mod ffi;
 pub mod instruction;
 pub mod subtable;
 pub mod trace;
+#[cfg(feature = "fs")]
+pub mod verifier_service;
 pub mod vm;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
@@ -0,0 +1,85 @@
+use crate::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+#[derive(Default)]
+pub struct ArgmaxSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> ArgmaxSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for ArgmaxSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<u32> {
+        // table[x | y] = (x < y), where x and y are signed (log2(M) / 2)-bit values, i.e. the
+        // index of the larger of x and y (0 if they are equal)
+        let mut entries = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+        let sign_bit = 1 << (bits_per_operand - 1);
+
+        // Materialize table entries in order where (x | y) ranges 0..M
+        for idx in 0..M {
+            let (x, y) = split_bits(idx, bits_per_operand);
+            // Flipping the sign bits maps signed order onto unsigned order
+            let row = if (x ^ sign_bit) < (y ^ sign_bit) {
+                1
+            } else {
+                0
+            };
+            entries.push(row);
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // x_0 * (1 - y_0) + ((1 - x_0) * (1 - y_0) + x_0 * y_0) * LT_ABS(x, y)
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        let (x, y) = point.split_at(b);
+
+        let mut lt_abs = F::zero();
+        let mut eq_term = F::one();
+        for i in 1..b {
+            lt_abs += (F::one() - x[i]) * y[i] * eq_term;
+            eq_term *= F::one() - x[i] - y[i] + x[i] * y[i] + x[i] * y[i];
+        }
+        let eq_sign = F::one() - x[0] - y[0] + x[0] * y[0] + x[0] * y[0];
+        x[0] * (F::one() - y[0]) + eq_sign * lt_abs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use binius_field::BinaryField128b;
+
+    use crate::{
+        field::binius::BiniusField,
+        field::JoltField,
+        jolt::subtable::{argmax::ArgmaxSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(
+        argmax_materialize_mle_parity,
+        ArgmaxSubtable<Fr>,
+        Fr,
+        256
+    );
+
+    subtable_materialize_mle_parity_test!(
+        argmax_binius_materialize_mle_parity,
+        ArgmaxSubtable<BiniusField<BinaryField128b>>,
+        BiniusField<BinaryField128b>,
+        1 << 16
+    );
+}
@@ -0,0 +1,92 @@
+use crate::field::JoltField;
+use crate::poly::eq_poly::EqPolynomial;
+use ark_std::log2;
+use common::fixed_point;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+
+#[derive(Default)]
+pub struct ExpSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> ExpSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for ExpSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<u32> {
+        // table[x] = e^x in Q16.16, where x is a signed log2(M)-bit Q8.8 value (i.e.
+        // x / 256 for M = 2^16)
+        let bits = log2(M) as usize;
+        let mut entries = Vec::with_capacity(M);
+
+        // Materialize table entries in order from 0..M
+        for idx in 0..M {
+            // Sign-extend idx to 16 bits
+            let x = ((idx << (16 - bits)) as u16 as i16) >> (16 - bits);
+            entries.push(fixed_point::exp(x));
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // The table has no structure to exploit, so this is the inner product of the
+        // materialized table with eq(point, .), which takes O(M) time
+        let M = 1 << point.len();
+        EqPolynomial::evals(point)
+            .into_iter()
+            .zip(self.materialize(M))
+            .map(|(eq, entry)| eq * F::from_u64(entry as u64))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use binius_field::BinaryField128b;
+
+    use crate::{
+        field::binius::BiniusField,
+        field::JoltField,
+        jolt::subtable::{exp::ExpSubtable, LassoSubtable},
+        poly::dense_mlpoly::DensePolynomial,
+        subtable_materialize_mle_parity_test,
+    };
+
+    // `evaluate_mle` takes O(M) time, so these check the whole hypercube of a small
+    // subtable only
+    subtable_materialize_mle_parity_test!(exp_materialize_mle_parity, ExpSubtable<Fr>, Fr, 256);
+
+    subtable_materialize_mle_parity_test!(
+        exp_binius_materialize_mle_parity,
+        ExpSubtable<BiniusField<BinaryField128b>>,
+        BiniusField<BinaryField128b>,
+        256
+    );
+
+    #[test]
+    fn exp_mle_random_points() {
+        const M: usize = 1 << 16;
+        let mut rng = test_rng();
+        let subtable = ExpSubtable::<Fr>::new();
+        let table = DensePolynomial::new(
+            subtable
+                .materialize(M)
+                .into_iter()
+                .map(|entry| Fr::from_u64(entry as u64))
+                .collect(),
+        );
+        for _ in 0..4 {
+            let point: Vec<Fr> = (0..16).map(|_| Fr::random(&mut rng)).collect();
+            assert_eq!(subtable.evaluate_mle(&point), table.evaluate(&point));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::field::JoltField;
+use crate::utils::errors::JoltError;
 use enum_dispatch::enum_dispatch;
 use std::any::TypeId;
 use std::marker::Sync;
@@ -22,17 +23,32 @@ pub trait LassoSubtable<F: JoltField>: 'static + Sync {
 
 pub type SubtableId = TypeId;
 pub trait JoltSubtableSet<F: JoltField>:
-    LassoSubtable<F> + IntoEnumIterator + EnumCount + From<SubtableId> + Into<usize> + Send + Sync
+    LassoSubtable<F>
+    + IntoEnumIterator
+    + EnumCount
+    + TryFrom<SubtableId, Error = JoltError>
+    + Into<usize>
+    + Send
+    + Sync
 {
+    /// The index of `subtable` in this set. Panics if `subtable` is not in the set; see
+    /// `try_enum_index`.
     fn enum_index(subtable: Box<dyn LassoSubtable<F>>) -> usize {
-        Self::from(subtable.subtable_id()).into()
+        Self::try_enum_index(subtable).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `enum_index`, but fails if `subtable` is not in the set.
+    fn try_enum_index(subtable: Box<dyn LassoSubtable<F>>) -> Result<usize, JoltError> {
+        Ok(Self::try_from(subtable.subtable_id())?.into())
     }
 }
 
 pub mod and;
+pub mod argmax;
 pub mod div_by_zero;
 pub mod eq;
 pub mod eq_abs;
+pub mod exp;
 pub mod identity;
 pub mod left_is_zero;
 pub mod left_msb;
@@ -40,8 +56,10 @@ pub mod low_bit;
 pub mod lt_abs;
 pub mod ltu;
 pub mod or;
+pub mod relu;
 pub mod right_is_zero;
 pub mod right_msb;
+pub mod sigmoid;
 pub mod sign_extend;
 pub mod sll;
 pub mod sra_sign;
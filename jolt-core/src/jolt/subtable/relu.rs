@@ -0,0 +1,65 @@
+use crate::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+
+#[derive(Default)]
+pub struct ReluSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> ReluSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for ReluSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<u32> {
+        // table[x] = x if the MSB of x is 0, 0 otherwise, i.e. ReLU of x as a signed
+        // log2(M)-bit value
+        let mut entries = Vec::with_capacity(M);
+        let sign_bit = 1usize << (log2(M) - 1);
+
+        // Materialize table entries in order from 0..M
+        for idx in 0..M {
+            entries.push(if idx & sign_bit == 0 { idx as u32 } else { 0 });
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // (1 - x_0) * \sum_{i > 0} 2^{b - i - 1} * x_i
+        let b = point.len();
+        let mut result = F::zero();
+        for i in 1..b {
+            result += F::from_u64(1u64 << (b - i - 1)) * point[i];
+        }
+        (F::one() - point[0]) * result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use binius_field::BinaryField128b;
+
+    use crate::{
+        field::binius::BiniusField,
+        field::JoltField,
+        jolt::subtable::{relu::ReluSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    subtable_materialize_mle_parity_test!(relu_materialize_mle_parity, ReluSubtable<Fr>, Fr, 256);
+
+    subtable_materialize_mle_parity_test!(
+        relu_binius_materialize_mle_parity,
+        ReluSubtable<BiniusField<BinaryField128b>>,
+        BiniusField<BinaryField128b>,
+        1 << 16
+    );
+}
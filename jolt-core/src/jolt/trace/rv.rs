@@ -1,12 +1,16 @@
 use crate::jolt::instruction::and::ANDInstruction;
+use crate::jolt::instruction::argmax::ARGMAXInstruction;
 use crate::jolt::instruction::beq::BEQInstruction;
 use crate::jolt::instruction::bge::BGEInstruction;
 use crate::jolt::instruction::bgeu::BGEUInstruction;
 use crate::jolt::instruction::bne::BNEInstruction;
+use crate::jolt::instruction::exp::EXPInstruction;
 use crate::jolt::instruction::mul::MULInstruction;
 use crate::jolt::instruction::mulhu::MULHUInstruction;
 use crate::jolt::instruction::mulu::MULUInstruction;
 use crate::jolt::instruction::or::ORInstruction;
+use crate::jolt::instruction::relu::RELUInstruction;
+use crate::jolt::instruction::sigmoid::SIGMOIDInstruction;
 use crate::jolt::instruction::sll::SLLInstruction;
 use crate::jolt::instruction::slt::SLTInstruction;
 use crate::jolt::instruction::sltu::SLTUInstruction;
@@ -23,6 +27,7 @@ use crate::jolt::instruction::virtual_move::MOVEInstruction;
 use crate::jolt::instruction::xor::XORInstruction;
 use crate::jolt::instruction::{add::ADDInstruction, virtual_movsign::MOVSIGNInstruction};
 use crate::jolt::vm::rv32i_vm::RV32I;
+use crate::utils::errors::TraceRowError;
 use common::rv_trace::{ELFInstruction, RVTraceRow, RV32IM};
 
 impl TryFrom<&ELFInstruction> for RV32I {
@@ -67,7 +72,13 @@ impl TryFrom<&ELFInstruction> for RV32I {
             RV32IM::MULU => Ok(MULUInstruction::default().into()),
             RV32IM::MULHU => Ok(MULHUInstruction::default().into()),
 
+            RV32IM::RELU    => Ok(RELUInstruction::default().into()),
+            RV32IM::ARGMAX  => Ok(ARGMAXInstruction::default().into()),
+            RV32IM::EXP     => Ok(EXPInstruction::default().into()),
+            RV32IM::SIGMOID => Ok(SIGMOIDInstruction::default().into()),
+
             RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction::default().into()),
+            RV32IM::RDCYCLE => Ok(ADVICEInstruction::default().into()),
             RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction::default().into()),
             RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction::default().into()),
             RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction::default().into()),
@@ -83,58 +94,73 @@ impl TryFrom<&ELFInstruction> for RV32I {
 }
 
 impl TryFrom<&RVTraceRow> for RV32I {
-    type Error = &'static str;
+    type Error = TraceRowError;
 
     #[rustfmt::skip] // keep matches pretty
     fn try_from(row: &RVTraceRow) -> Result<Self, Self::Error> {
+        let missing = |operand| TraceRowError::MissingOperand {
+            opcode: format!("{:?}", row.instruction.opcode),
+            address: row.instruction.address,
+            operand,
+        };
+        let rs1 = || row.register_state.rs1_val.ok_or_else(|| missing("rs1 value"));
+        let rs2 = || row.register_state.rs2_val.ok_or_else(|| missing("rs2 value"));
+        let advice = || row.advice_value.ok_or_else(|| missing("advice value"));
+
         match row.instruction.opcode {
-            RV32IM::ADD => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SUB => Ok(SUBInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::XOR => Ok(XORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::OR  => Ok(ORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::AND => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SLL => Ok(SLLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SRL => Ok(SRLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SRA => Ok(SRAInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SLT  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SLTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-
-            RV32IM::ADDI  => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::XORI  => Ok(XORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::ORI   => Ok(ORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::ANDI  => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::SLLI  => Ok(SLLInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::SRLI  => Ok(SRLInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::SRAI  => Ok(SRAInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::SLTI  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-            RV32IM::SLTIU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-
-            RV32IM::BEQ  => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BNE  => Ok(BNEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BLT  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BLTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BGE  => Ok(BGEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BGEU => Ok(BGEUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+            RV32IM::ADD => Ok(ADDInstruction(rs1()?, rs2()?).into()),
+            RV32IM::SUB => Ok(SUBInstruction(rs1()?, rs2()?).into()),
+            RV32IM::XOR => Ok(XORInstruction(rs1()?, rs2()?).into()),
+            RV32IM::OR  => Ok(ORInstruction(rs1()?, rs2()?).into()),
+            RV32IM::AND => Ok(ANDInstruction(rs1()?, rs2()?).into()),
+            RV32IM::SLL => Ok(SLLInstruction(rs1()?, rs2()?).into()),
+            RV32IM::SRL => Ok(SRLInstruction(rs1()?, rs2()?).into()),
+            RV32IM::SRA => Ok(SRAInstruction(rs1()?, rs2()?).into()),
+            RV32IM::SLT  => Ok(SLTInstruction(rs1()?, rs2()?).into()),
+            RV32IM::SLTU => Ok(SLTUInstruction(rs1()?, rs2()?).into()),
+
+            RV32IM::ADDI  => Ok(ADDInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::XORI  => Ok(XORInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::ORI   => Ok(ORInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::ANDI  => Ok(ANDInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::SLLI  => Ok(SLLInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::SRLI  => Ok(SRLInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::SRAI  => Ok(SRAInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::SLTI  => Ok(SLTInstruction(rs1()?, row.imm_u32() as u64).into()),
+            RV32IM::SLTIU => Ok(SLTUInstruction(rs1()?, row.imm_u32() as u64).into()),
+
+            RV32IM::BEQ  => Ok(BEQInstruction(rs1()?, rs2()?).into()),
+            RV32IM::BNE  => Ok(BNEInstruction(rs1()?, rs2()?).into()),
+            RV32IM::BLT  => Ok(SLTInstruction(rs1()?, rs2()?).into()),
+            RV32IM::BLTU => Ok(SLTUInstruction(rs1()?, rs2()?).into()),
+            RV32IM::BGE  => Ok(BGEInstruction(rs1()?, rs2()?).into()),
+            RV32IM::BGEU => Ok(BGEUInstruction(rs1()?, rs2()?).into()),
 
             RV32IM::JAL  => Ok(ADDInstruction(row.instruction.address, row.imm_u32() as u64).into()),
-            RV32IM::JALR => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
+            RV32IM::JALR => Ok(ADDInstruction(rs1()?, row.imm_u32() as u64).into()),
             RV32IM::AUIPC => Ok(ADDInstruction(row.instruction.address, row.imm_u32() as u64).into()),
 
-            RV32IM::MUL => Ok(MULInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::MULU => Ok(MULUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::MULHU => Ok(MULHUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-
-            RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction(row.advice_value.unwrap()).into()),
-            RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction(row.register_state.rs1_val.unwrap()).into()),
-            RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction(row.register_state.rs1_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_LTE => Ok(ASSERTLTEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER => Ok(AssertValidUnsignedRemainderInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER => Ok(AssertValidSignedRemainderInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_DIV0 => Ok(AssertValidDiv0Instruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_HALFWORD_ALIGNMENT => Ok(AssertAlignedMemoryAccessInstruction::<32, 2>(row.register_state.rs1_val.unwrap(), row.imm_u32() as u64).into()),
-
-            _ => Err("No corresponding RV32I instruction")
+            RV32IM::MUL => Ok(MULInstruction(rs1()?, rs2()?).into()),
+            RV32IM::MULU => Ok(MULUInstruction(rs1()?, rs2()?).into()),
+            RV32IM::MULHU => Ok(MULHUInstruction(rs1()?, rs2()?).into()),
+
+            RV32IM::RELU    => Ok(RELUInstruction(rs1()?).into()),
+            RV32IM::ARGMAX  => Ok(ARGMAXInstruction(rs1()?, rs2()?).into()),
+            RV32IM::EXP     => Ok(EXPInstruction(rs1()?).into()),
+            RV32IM::SIGMOID => Ok(SIGMOIDInstruction(rs1()?).into()),
+
+            RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction(advice()?).into()),
+            RV32IM::RDCYCLE => Ok(ADVICEInstruction(advice()?).into()),
+            RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction(rs1()?).into()),
+            RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction(rs1()?).into()),
+            RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction(rs1()?, rs2()?).into()),
+            RV32IM::VIRTUAL_ASSERT_LTE => Ok(ASSERTLTEInstruction(rs1()?, rs2()?).into()),
+            RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER => Ok(AssertValidUnsignedRemainderInstruction(rs1()?, rs2()?).into()),
+            RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER => Ok(AssertValidSignedRemainderInstruction(rs1()?, rs2()?).into()),
+            RV32IM::VIRTUAL_ASSERT_VALID_DIV0 => Ok(AssertValidDiv0Instruction(rs1()?, rs2()?).into()),
+            RV32IM::VIRTUAL_ASSERT_HALFWORD_ALIGNMENT => Ok(AssertAlignedMemoryAccessInstruction::<32, 2>(rs1()?, row.imm_u32() as u64).into()),
+
+            opcode => Err(TraceRowError::NoLookup(format!("{:?}", opcode))),
         }
     }
 }
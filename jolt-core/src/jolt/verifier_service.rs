@@ -0,0 +1,232 @@
+//! A long-running verifier for `JoltHyperKZGProof`s, as used by `jolt-verifyd`.
+//!
+//! Each program's preprocessing is loaded once at startup and reduced to its
+//! verifier key, which is all that is kept of it. Proofs are verified concurrently by the
+//! caller's threads with the final HyperKZG pairing checks deferred; a single
+//! batcher thread then collects the pairing checks of concurrent requests and
+//! verifies them together, with two pairings per batch rather than per proof.
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ark_bn254::{Bn254, Fr};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+
+use crate::jolt::vm::rv32i_vm::{
+    JoltHyperKZGProof, JoltHyperKZGVerifierKey, ProofTranscript, RV32IJoltVM, C, M, PCS,
+};
+use crate::jolt::vm::{Jolt, JoltPreprocessing};
+use crate::poly::commitment::hyperkzg::{defer_pairing_checks, PairingCheck, PreparedVerifierKey};
+use crate::utils::errors::VerifierServiceError;
+
+/// File extension of the preprocessing files loaded by `ProgramRegistry::load_dir`.
+pub const PREPROCESSING_EXTENSION: &str = "preprocessing";
+
+pub type Preprocessing = JoltPreprocessing<C, Fr, PCS, ProofTranscript>;
+
+/// The programs a `VerifierService` accepts proofs for, by name.
+#[derive(Default)]
+pub struct ProgramRegistry {
+    verifier_keys: HashMap<String, JoltHyperKZGVerifierKey>,
+}
+
+impl ProgramRegistry {
+    /// Loads every `<name>.preprocessing` file in `dir`, as written by
+    /// `Jolt::save_preprocessing`.
+    pub fn load_dir(dir: &Path) -> Result<Self, VerifierServiceError> {
+        let mut registry = Self::default();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(PREPROCESSING_EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let reader = BufReader::new(File::open(&path)?);
+            let preprocessing =
+                <RV32IJoltVM as Jolt<Fr, PCS, C, M, ProofTranscript>>::load_preprocessing(reader)
+                    .map_err(|source| VerifierServiceError::Load {
+                    program: name.to_string(),
+                    source,
+                })?;
+            registry.insert(name, preprocessing);
+        }
+        Ok(registry)
+    }
+
+    pub fn insert(&mut self, name: &str, preprocessing: Preprocessing) {
+        self.verifier_keys
+            .insert(name.to_string(), preprocessing.verifier_key());
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.verifier_keys.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verifier_keys.is_empty()
+    }
+}
+
+/// When the batcher stops collecting pairing checks and verifies a batch.
+#[derive(Clone, Copy, Debug)]
+pub struct BatchConfig {
+    /// Maximum number of proofs whose pairing checks are verified together.
+    pub max_batch_size: usize,
+    /// Maximum time the first proof of a batch waits for others to join it.
+    pub max_wait: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 64,
+            max_wait: Duration::from_millis(5),
+        }
+    }
+}
+
+struct PendingChecks {
+    program: String,
+    checks: Vec<PairingCheck<Bn254>>,
+    response: Sender<bool>,
+}
+
+pub struct VerifierService {
+    registry: Arc<ProgramRegistry>,
+    sender: Sender<PendingChecks>,
+}
+
+impl VerifierService {
+    /// Starts the batcher thread. It exits once the service is dropped.
+    pub fn new(registry: ProgramRegistry, config: BatchConfig) -> Self {
+        let registry = Arc::new(registry);
+        let (sender, receiver) = mpsc::channel();
+        let batcher_registry = registry.clone();
+        thread::Builder::new()
+            .name("jolt-verifier-batcher".to_string())
+            .spawn(move || run_batcher(&batcher_registry, receiver, config))
+            .expect("failed to spawn batcher thread");
+        Self { registry, sender }
+    }
+
+    pub fn registry(&self) -> &ProgramRegistry {
+        &self.registry
+    }
+
+    /// Verifies `proof` against the preprocessing of `program`. Blocks until the
+    /// batch containing its pairing checks has been verified. Fails with
+    /// `MemoryLayoutMismatch` if the proof is for a different memory layout, `Verify`
+    /// if any other check of the proof fails, and `PairingCheckFailed` if its
+    /// HyperKZG openings fail the deferred pairing checks.
+    #[tracing::instrument(skip_all, name = "VerifierService::verify")]
+    pub fn verify(
+        &self,
+        program: &str,
+        proof: JoltHyperKZGProof,
+    ) -> Result<(), VerifierServiceError> {
+        let verifier_key = self
+            .registry
+            .verifier_keys
+            .get(program)
+            .ok_or_else(|| VerifierServiceError::UnknownProgram(program.to_string()))?;
+        if proof.proof.program_io.memory_layout != verifier_key.memory_layout {
+            return Err(VerifierServiceError::MemoryLayoutMismatch(
+                program.to_string(),
+            ));
+        }
+
+        let (result, checks) = defer_pairing_checks::<Bn254, _>(|| {
+            RV32IJoltVM::verify(verifier_key, proof.proof, proof.commitments, None)
+        });
+        result?;
+
+        let (response, receiver) = mpsc::channel();
+        self.sender
+            .send(PendingChecks {
+                program: program.to_string(),
+                checks,
+                response,
+            })
+            .map_err(|_| VerifierServiceError::Shutdown)?;
+        match receiver.recv() {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(VerifierServiceError::PairingCheckFailed),
+            Err(_) => Err(VerifierServiceError::Shutdown),
+        }
+    }
+}
+
+fn run_batcher(registry: &ProgramRegistry, receiver: Receiver<PendingChecks>, config: BatchConfig) {
+    // The batching coefficients must be unpredictable to provers
+    let mut rng = ChaCha20Rng::from_seed(rand::random());
+    while let Ok(first) = receiver.recv() {
+        let deadline = Instant::now() + config.max_wait;
+        let mut batch = vec![first];
+        while batch.len() < config.max_batch_size {
+            match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(pending) => batch.push(pending),
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        let mut by_program: HashMap<String, Vec<PendingChecks>> = HashMap::new();
+        for pending in batch {
+            by_program
+                .entry(pending.program.clone())
+                .or_default()
+                .push(pending);
+        }
+        for (program, batch) in by_program {
+            // Preparing the key costs far less than the pairings it is used for
+            let vk = PreparedVerifierKey::from(&registry.verifier_keys[&program].generators);
+            let checks: Vec<_> = batch
+                .iter()
+                .flat_map(|pending| pending.checks.iter().copied())
+                .collect();
+            if PairingCheck::batch_verify(&checks, &vk, &mut rng) {
+                for pending in batch {
+                    let _ = pending.response.send(true);
+                }
+            } else {
+                // Find the offending proofs by checking each individually
+                for pending in batch {
+                    let valid = pending.checks.iter().all(|check| check.verify(&vk));
+                    let _ = pending.response.send(valid);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_dir() {
+        let dir = std::env::temp_dir().join(format!("jolt-verifyd-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(dir.join("README.txt"), b"not a preprocessing file").unwrap();
+        let registry = ProgramRegistry::load_dir(&dir).unwrap();
+        assert!(registry.is_empty());
+
+        std::fs::write(dir.join("fibonacci.preprocessing"), [0u8; 8]).unwrap();
+        let result = ProgramRegistry::load_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        match result {
+            Err(VerifierServiceError::Load { program, .. }) => assert_eq!(program, "fibonacci"),
+            _ => panic!("expected a load error"),
+        }
+    }
+}
@@ -0,0 +1,426 @@
+//! A self-describing container for long-term proof storage.
+//!
+//! A `ProofArchive` bundles a serialized proof together with the digest of the
+//! verifying key it should be checked against, the public claims (program I/O),
+//! prover telemetry, and a SHA-256 manifest over all of the above. The manifest makes
+//! it possible to audit the integrity of an archive (i.e. detect bit rot or truncation)
+//! without deserializing the proof or running the verifier.
+//!
+//! Layout (all integers little-endian):
+//! ```text
+//! magic: [u8; 8] | num_entries: u32 | entry* | manifest_digest: [u8; 32]
+//! entry = name_len: u16 | name: [u8; name_len] | data_len: u64 | data: [u8; data_len]
+//! ```
+//! The last entry is always the manifest, which has one line per preceding entry:
+//! `<sha256 hex> <data_len> <name>`. `manifest_digest` is the SHA-256 of the manifest.
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::JoltDevice;
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+use crate::jolt::compat::ProofHeader;
+
+const ARCHIVE_MAGIC: &[u8; 8] = b"JOLTARC1";
+
+pub const PROOF_ENTRY: &str = "proof";
+pub const VK_DIGEST_ENTRY: &str = "vk_digest";
+pub const PUBLIC_CLAIMS_ENTRY: &str = "public_claims";
+pub const TELEMETRY_ENTRY: &str = "telemetry";
+pub const HEADER_ENTRY: &str = "header";
+pub const MANIFEST_ENTRY: &str = "MANIFEST";
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] ark_serialize::SerializationError),
+    #[error("Not a proof archive")]
+    InvalidMagic,
+    #[error("Malformed archive: {0}")]
+    Malformed(String),
+    #[error("Missing entry \"{0}\"")]
+    MissingEntry(String),
+    #[error("Manifest digest mismatch")]
+    ManifestDigestMismatch,
+    #[error("Digest mismatch for entry \"{0}\"")]
+    EntryDigestMismatch(String),
+    #[error("Invalid entry name: {0}")]
+    InvalidEntryName(String),
+}
+
+/// Telemetry recorded by the prover, archived alongside the proof.
+#[derive(Clone, Debug, Default, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProverTelemetry {
+    pub trace_length: u64,
+    pub prover_time_ms: u64,
+    /// High-water mark of the prover process's resident memory, or 0 where the
+    /// platform doesn't report one.
+    pub peak_memory_bytes: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ManifestLine {
+    digest: [u8; 32],
+    len: u64,
+    name: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProofArchive {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl ProofArchive {
+    pub fn new<Proof: CanonicalSerialize>(
+        proof: &Proof,
+        vk_digest: [u8; 32],
+        public_claims: &JoltDevice,
+        telemetry: &ProverTelemetry,
+    ) -> Result<Self, ArchiveError> {
+        let mut archive = Self::default();
+
+        let mut proof_bytes = vec![];
+        proof.serialize_compressed(&mut proof_bytes)?;
+        archive.add_entry(PROOF_ENTRY, proof_bytes)?;
+        archive.add_entry(VK_DIGEST_ENTRY, vk_digest.to_vec())?;
+
+        let mut claims_bytes = vec![];
+        public_claims.serialize_compressed(&mut claims_bytes)?;
+        archive.add_entry(PUBLIC_CLAIMS_ENTRY, claims_bytes)?;
+
+        let mut telemetry_bytes = vec![];
+        telemetry.serialize_compressed(&mut telemetry_bytes)?;
+        archive.add_entry(TELEMETRY_ENTRY, telemetry_bytes)?;
+
+        Ok(archive)
+    }
+
+    /// Adds an arbitrary named entry (e.g. application metadata) to the archive.
+    pub fn add_entry(&mut self, name: &str, data: Vec<u8>) -> Result<(), ArchiveError> {
+        if name == MANIFEST_ENTRY {
+            return Err(ArchiveError::InvalidEntryName(format!(
+                "{MANIFEST_ENTRY} is reserved"
+            )));
+        }
+        if name.contains('\n') || name.len() > u16::MAX as usize {
+            return Err(ArchiveError::InvalidEntryName(
+                "names must be at most 65535 bytes and contain no newlines".to_string(),
+            ));
+        }
+        self.entries.push((name.to_string(), data));
+        Ok(())
+    }
+
+    pub fn entry(&self, name: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    pub fn proof<Proof: CanonicalDeserialize>(&self) -> Result<Proof, ArchiveError> {
+        let bytes = self.required_entry(PROOF_ENTRY)?;
+        Ok(Proof::deserialize_compressed(bytes)?)
+    }
+
+    pub fn vk_digest(&self) -> Result<[u8; 32], ArchiveError> {
+        self.required_entry(VK_DIGEST_ENTRY)?
+            .try_into()
+            .map_err(|_| ArchiveError::Malformed("vk digest must be 32 bytes".to_string()))
+    }
+
+    pub fn public_claims(&self) -> Result<JoltDevice, ArchiveError> {
+        let bytes = self.required_entry(PUBLIC_CLAIMS_ENTRY)?;
+        Ok(JoltDevice::deserialize_compressed(bytes)?)
+    }
+
+    pub fn telemetry(&self) -> Result<ProverTelemetry, ArchiveError> {
+        let bytes = self.required_entry(TELEMETRY_ENTRY)?;
+        Ok(ProverTelemetry::deserialize_compressed(bytes)?)
+    }
+
+    /// Records the `ProofHeader` of the archived proof, so that a verifier can run
+    /// `compat::check` before attempting to deserialize the proof.
+    pub fn set_header(&mut self, header: &ProofHeader) -> Result<(), ArchiveError> {
+        let mut header_bytes = vec![];
+        header.serialize_compressed(&mut header_bytes)?;
+        self.entries.retain(|(name, _)| name != HEADER_ENTRY);
+        self.add_entry(HEADER_ENTRY, header_bytes)
+    }
+
+    pub fn header(&self) -> Result<ProofHeader, ArchiveError> {
+        let bytes = self.required_entry(HEADER_ENTRY)?;
+        Ok(ProofHeader::deserialize_compressed(bytes)?)
+    }
+
+    fn required_entry(&self, name: &str) -> Result<&[u8], ArchiveError> {
+        self.entry(name)
+            .ok_or_else(|| ArchiveError::MissingEntry(name.to_string()))
+    }
+
+    fn manifest(&self) -> Vec<u8> {
+        let mut manifest = String::new();
+        for (name, data) in self.entries.iter() {
+            let digest: [u8; 32] = Sha256::digest(data).into();
+            manifest.push_str(&format!("{} {} {}\n", to_hex(&digest), data.len(), name));
+        }
+        manifest.into_bytes()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ArchiveError> {
+        let manifest = self.manifest();
+        writer.write_all(ARCHIVE_MAGIC)?;
+        writer.write_all(&(self.entries.len() as u32 + 1).to_le_bytes())?;
+        for (name, data) in self
+            .entries
+            .iter()
+            .map(|(name, data)| (name.as_str(), data.as_slice()))
+            .chain(std::iter::once((MANIFEST_ENTRY, manifest.as_slice())))
+        {
+            writer.write_all(&(name.len() as u16).to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&(data.len() as u64).to_le_bytes())?;
+            writer.write_all(data)?;
+        }
+        writer.write_all(&Sha256::digest(&manifest))?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ArchiveError> {
+        let mut bytes = vec![];
+        self.write(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Reads an archive, checking its integrity against the embedded manifest.
+    pub fn read<R: Read>(reader: R) -> Result<Self, ArchiveError> {
+        let (entries, manifest, manifest_digest) = read_raw(reader)?;
+        let archive = Self { entries };
+        check_manifest(&archive.entries, &manifest, &manifest_digest)?;
+        Ok(archive)
+    }
+
+    /// Checks the integrity of a serialized archive without deserializing the proof
+    /// or any other entry. Returns the number of bytes of archived (non-manifest) data.
+    pub fn verify_integrity<R: Read>(reader: R) -> Result<u64, ArchiveError> {
+        let (entries, manifest, manifest_digest) = read_raw(reader)?;
+        check_manifest(&entries, &manifest, &manifest_digest)?;
+        Ok(entries.iter().map(|(_, data)| data.len() as u64).sum())
+    }
+}
+
+fn read_raw<R: Read>(
+    mut reader: R,
+) -> Result<(Vec<(String, Vec<u8>)>, Vec<u8>, [u8; 32]), ArchiveError> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != ARCHIVE_MAGIC {
+        return Err(ArchiveError::InvalidMagic);
+    }
+
+    let mut buf4 = [0u8; 4];
+    reader.read_exact(&mut buf4)?;
+    let num_entries = u32::from_le_bytes(buf4);
+    if num_entries == 0 {
+        return Err(ArchiveError::MissingEntry(MANIFEST_ENTRY.to_string()));
+    }
+
+    // `num_entries` is untrusted, so grow `entries` as they are read instead of
+    // preallocating; a bogus count is caught by the truncation checks below
+    let mut entries = vec![];
+    for _ in 0..num_entries {
+        let mut buf2 = [0u8; 2];
+        reader.read_exact(&mut buf2)?;
+        let mut name = vec![0u8; u16::from_le_bytes(buf2) as usize];
+        reader.read_exact(&mut name)?;
+        let name = String::from_utf8(name)
+            .map_err(|_| ArchiveError::Malformed("entry name is not UTF-8".to_string()))?;
+
+        let mut buf8 = [0u8; 8];
+        reader.read_exact(&mut buf8)?;
+        let len = u64::from_le_bytes(buf8);
+        let mut data = vec![];
+        (&mut reader).take(len).read_to_end(&mut data)?;
+        if data.len() as u64 != len {
+            return Err(ArchiveError::Malformed(format!(
+                "entry \"{name}\" is truncated"
+            )));
+        }
+        entries.push((name, data));
+    }
+
+    let (manifest_name, manifest) = entries.pop().unwrap();
+    if manifest_name != MANIFEST_ENTRY {
+        return Err(ArchiveError::MissingEntry(MANIFEST_ENTRY.to_string()));
+    }
+    let mut manifest_digest = [0u8; 32];
+    reader.read_exact(&mut manifest_digest)?;
+
+    Ok((entries, manifest, manifest_digest))
+}
+
+fn check_manifest(
+    entries: &[(String, Vec<u8>)],
+    manifest: &[u8],
+    manifest_digest: &[u8; 32],
+) -> Result<(), ArchiveError> {
+    if Sha256::digest(manifest)[..] != manifest_digest[..] {
+        return Err(ArchiveError::ManifestDigestMismatch);
+    }
+
+    let manifest = parse_manifest(manifest)?;
+    if manifest.len() != entries.len() {
+        return Err(ArchiveError::Malformed(
+            "manifest does not match entries".to_string(),
+        ));
+    }
+    for (line, (name, data)) in manifest.iter().zip(entries.iter()) {
+        if &line.name != name || line.len != data.len() as u64 {
+            return Err(ArchiveError::Malformed(format!(
+                "manifest does not match entry \"{name}\""
+            )));
+        }
+        if Sha256::digest(data)[..] != line.digest[..] {
+            return Err(ArchiveError::EntryDigestMismatch(name.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn parse_manifest(manifest: &[u8]) -> Result<Vec<ManifestLine>, ArchiveError> {
+    let malformed = || ArchiveError::Malformed("invalid manifest".to_string());
+    let manifest = std::str::from_utf8(manifest).map_err(|_| malformed())?;
+    manifest
+        .lines()
+        .map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let digest = parts.next().and_then(from_hex).ok_or_else(malformed)?;
+            let len = parts
+                .next()
+                .and_then(|len| len.parse().ok())
+                .ok_or_else(malformed)?;
+            let name = parts.next().ok_or_else(malformed)?.to_string();
+            Ok(ManifestLine { digest, len, name })
+        })
+        .collect()
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jolt::compat::JoltParameters;
+
+    fn test_archive() -> ProofArchive {
+        let proof: Vec<u64> = (0..100).collect();
+        let mut claims = JoltDevice::new(64, 64);
+        claims.inputs = vec![1, 2, 3];
+        claims.outputs = vec![4, 5];
+        let telemetry = ProverTelemetry {
+            trace_length: 1 << 10,
+            prover_time_ms: 1234,
+            peak_memory_bytes: 1 << 30,
+        };
+        ProofArchive::new(&proof, [7u8; 32], &claims, &telemetry).unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let archive = test_archive();
+        let bytes = archive.to_bytes().unwrap();
+        assert!(ProofArchive::verify_integrity(bytes.as_slice()).is_ok());
+
+        let read = ProofArchive::read(bytes.as_slice()).unwrap();
+        assert_eq!(read, archive);
+        assert_eq!(
+            read.proof::<Vec<u64>>().unwrap(),
+            (0..100).collect::<Vec<_>>()
+        );
+        assert_eq!(read.vk_digest().unwrap(), [7u8; 32]);
+        assert_eq!(read.public_claims().unwrap().inputs, vec![1, 2, 3]);
+        assert_eq!(read.telemetry().unwrap().prover_time_ms, 1234);
+        assert!(matches!(read.header(), Err(ArchiveError::MissingEntry(_))));
+    }
+
+    #[test]
+    fn header() {
+        let mut archive = test_archive();
+        let mut header = ProofHeader::new(JoltParameters {
+            pcs: "hyperkzg".to_string(),
+            transcript: "keccak".to_string(),
+            c: 4,
+            m: 1 << 16,
+            bytecode_size: 1 << 10,
+            max_input_size: 64,
+            max_output_size: 64,
+            io_codec: "postcard".to_string(),
+        });
+        archive.set_header(&header).unwrap();
+        header.parameters.bytecode_size = 1 << 11;
+        archive.set_header(&header).unwrap();
+
+        let read = ProofArchive::read(archive.to_bytes().unwrap().as_slice()).unwrap();
+        assert_eq!(read.header().unwrap(), header);
+    }
+
+    #[test]
+    fn detects_corruption() {
+        let bytes = test_archive().to_bytes().unwrap();
+
+        // Flip a bit inside the proof entry
+        let mut corrupted = bytes.clone();
+        corrupted[8 + 4 + 2 + PROOF_ENTRY.len() + 8 + 16] ^= 1;
+        assert!(matches!(
+            ProofArchive::verify_integrity(corrupted.as_slice()),
+            Err(ArchiveError::EntryDigestMismatch(name)) if name == PROOF_ENTRY
+        ));
+
+        // Flip a bit in the trailing manifest digest
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            ProofArchive::verify_integrity(corrupted.as_slice()),
+            Err(ArchiveError::ManifestDigestMismatch)
+        ));
+
+        // Truncation
+        assert!(ProofArchive::verify_integrity(&bytes[..bytes.len() - 40]).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        // A huge entry count must not be trusted for allocation
+        let mut bytes = ARCHIVE_MAGIC.to_vec();
+        bytes.extend(u32::MAX.to_le_bytes());
+        assert!(matches!(
+            ProofArchive::read(bytes.as_slice()),
+            Err(ArchiveError::Io(_))
+        ));
+
+        let mut archive = test_archive();
+        assert!(matches!(
+            archive.add_entry(MANIFEST_ENTRY, vec![]),
+            Err(ArchiveError::InvalidEntryName(_))
+        ));
+        assert!(matches!(
+            archive.add_entry("a\nb", vec![]),
+            Err(ArchiveError::InvalidEntryName(_))
+        ));
+    }
+}
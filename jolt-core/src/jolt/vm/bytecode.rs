@@ -3,6 +3,7 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::rngs::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 use std::collections::BTreeMap;
 #[cfg(test)]
 use std::collections::HashSet;
@@ -16,7 +17,7 @@ use crate::lasso::memory_checking::{
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::compact_polynomial::{CompactPolynomial, SmallScalar};
 use crate::poly::multilinear_polynomial::{MultilinearPolynomial, PolynomialEvaluation};
-use common::constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS};
+use common::constants::{INSTRUCTION_ALIGNMENT, RAM_START_ADDRESS};
 use common::rv_trace::ELFInstruction;
 
 use rayon::prelude::*;
@@ -219,7 +220,7 @@ pub fn random_bytecode_trace(
     trace
 }
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BytecodePreprocessing<F: JoltField> {
     /// Size of the (padded) bytecode.
     code_size: usize,
@@ -241,10 +242,10 @@ impl<F: JoltField> BytecodePreprocessing<F> {
         let mut virtual_address = 1; // Account for no-op instruction prepended to bytecode
         for instruction in bytecode.iter_mut() {
             assert!(instruction.address >= RAM_START_ADDRESS as usize);
-            assert!(instruction.address % BYTES_PER_INSTRUCTION == 0);
+            assert!(instruction.address % INSTRUCTION_ALIGNMENT == 0);
             // Compress instruction address for more efficient commitment:
             instruction.address =
-                1 + (instruction.address - RAM_START_ADDRESS as usize) / BYTES_PER_INSTRUCTION;
+                1 + (instruction.address - RAM_START_ADDRESS as usize) / INSTRUCTION_ALIGNMENT;
             assert_eq!(
                 virtual_address_map.insert(
                     (
@@ -297,6 +298,18 @@ impl<F: JoltField> BytecodePreprocessing<F> {
             virtual_address_map,
         }
     }
+
+    /// Size of the (padded) bytecode.
+    pub fn code_size(&self) -> usize {
+        self.code_size
+    }
+
+    /// Keccak256 digest of the (preprocessed) bytecode.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        self.serialize_compressed(&mut bytes).unwrap();
+        Keccak256::digest(bytes).into()
+    }
 }
 
 impl<F, PCS, ProofTranscript> BytecodeProof<F, PCS, ProofTranscript>
@@ -319,11 +332,11 @@ where
         for (step_index, step) in trace.iter_mut().enumerate() {
             if !step.bytecode_row.address.is_zero() {
                 assert!(step.bytecode_row.address >= RAM_START_ADDRESS as usize);
-                assert!(step.bytecode_row.address % BYTES_PER_INSTRUCTION == 0);
+                assert!(step.bytecode_row.address % INSTRUCTION_ALIGNMENT == 0);
                 // Compress instruction address for more efficient commitment:
                 step.bytecode_row.address = 1
                     + (step.bytecode_row.address - RAM_START_ADDRESS as usize)
-                        / BYTES_PER_INSTRUCTION;
+                        / INSTRUCTION_ALIGNMENT;
             }
 
             let virtual_address = preprocessing
@@ -713,7 +726,7 @@ mod tests {
     use super::*;
     use ark_bn254::Fr;
     use common::{
-        constants::MEMORY_OPS_PER_INSTRUCTION,
+        constants::{BYTES_PER_INSTRUCTION, MEMORY_OPS_PER_INSTRUCTION},
         rv_trace::{MemoryOp, NUM_CIRCUIT_FLAGS},
     };
     use std::collections::HashSet;
@@ -748,4 +761,24 @@ mod tests {
         let preprocessing = BytecodePreprocessing::<Fr>::preprocess(program);
         BytecodeOpenings::<Fr>::test_ordering_consistency(&preprocessing);
     }
+
+    #[test]
+    fn bytecode_digest() {
+        let program = vec![
+            BytecodeRow::new(to_ram_address(0), 2, 2, 2, 2, 2),
+            BytecodeRow::new(to_ram_address(1), 4, 4, 4, 4, 4),
+        ];
+        let digest = BytecodePreprocessing::<Fr>::preprocess(program.clone()).digest();
+        assert_eq!(
+            digest,
+            BytecodePreprocessing::<Fr>::preprocess(program.clone()).digest()
+        );
+
+        let mut modified = program;
+        modified[1] = BytecodeRow::new(to_ram_address(1), 4, 4, 4, 4, 5);
+        assert_ne!(
+            digest,
+            BytecodePreprocessing::<Fr>::preprocess(modified).digest()
+        );
+    }
 }
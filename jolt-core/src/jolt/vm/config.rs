@@ -0,0 +1,197 @@
+//! The parameters of a Jolt instantiation that the prover and verifier must agree on,
+//! set via `JoltConfigBuilder` (see `Jolt::preprocess_with_config`).
+use std::fmt;
+use std::marker::PhantomData;
+
+use common::constants::{
+    DEFAULT_MAX_BYTECODE_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_MEMORY_ADDRESS,
+    DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_TRACE_LENGTH,
+};
+use common::rv_trace::{IoCodec, MemoryLayout};
+
+use crate::field::JoltField;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::utils::errors::JoltError;
+use crate::utils::transcript::Transcript;
+
+/// The memory layout and size bounds Jolt is preprocessed with, for the decomposition
+/// parameters `C` and `M`, the commitment scheme `PCS` and the transcript
+/// `ProofTranscript`. These are part of the type, so a config can only be used with a
+/// VM instantiated with the same choices.
+pub struct JoltConfig<const C: usize, const M: usize, F, PCS, ProofTranscript> {
+    pub memory_layout: MemoryLayout,
+    pub max_bytecode_size: usize,
+    pub max_memory_address: usize,
+    pub max_trace_length: usize,
+    _marker: PhantomData<(F, PCS, ProofTranscript)>,
+}
+
+impl<const C: usize, const M: usize, F, PCS, ProofTranscript>
+    JoltConfig<C, M, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    pub fn builder() -> JoltConfigBuilder<C, M, F, PCS, ProofTranscript> {
+        JoltConfigBuilder::new()
+    }
+}
+
+impl<const C: usize, const M: usize, F, PCS, ProofTranscript> Clone
+    for JoltConfig<C, M, F, PCS, ProofTranscript>
+{
+    fn clone(&self) -> Self {
+        Self {
+            memory_layout: self.memory_layout.clone(),
+            max_bytecode_size: self.max_bytecode_size,
+            max_memory_address: self.max_memory_address,
+            max_trace_length: self.max_trace_length,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<const C: usize, const M: usize, F, PCS, ProofTranscript> fmt::Debug
+    for JoltConfig<C, M, F, PCS, ProofTranscript>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoltConfig")
+            .field("C", &C)
+            .field("M", &M)
+            .field("memory_layout", &self.memory_layout)
+            .field("max_bytecode_size", &self.max_bytecode_size)
+            .field("max_memory_address", &self.max_memory_address)
+            .field("max_trace_length", &self.max_trace_length)
+            .finish()
+    }
+}
+
+/// Builds a `JoltConfig`. Every bound defaults to that of the `jolt::provable` macro.
+pub struct JoltConfigBuilder<const C: usize, const M: usize, F, PCS, ProofTranscript> {
+    max_input_size: u64,
+    max_output_size: u64,
+    max_advice_size: u64,
+    io_codec: IoCodec,
+    max_bytecode_size: usize,
+    max_memory_address: usize,
+    max_trace_length: usize,
+    _marker: PhantomData<(F, PCS, ProofTranscript)>,
+}
+
+impl<const C: usize, const M: usize, F, PCS, ProofTranscript>
+    JoltConfigBuilder<C, M, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    fn new() -> Self {
+        Self {
+            max_input_size: DEFAULT_MAX_INPUT_SIZE,
+            max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+            max_advice_size: 0,
+            io_codec: IoCodec::default(),
+            max_bytecode_size: DEFAULT_MAX_BYTECODE_SIZE as usize,
+            max_memory_address: DEFAULT_MAX_MEMORY_ADDRESS as usize,
+            max_trace_length: DEFAULT_MAX_TRACE_LENGTH as usize,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn max_input_size(mut self, max_input_size: u64) -> Self {
+        self.max_input_size = max_input_size;
+        self
+    }
+
+    pub fn max_output_size(mut self, max_output_size: u64) -> Self {
+        self.max_output_size = max_output_size;
+        self
+    }
+
+    /// Reserves an advice tape of (at least) `max_advice_size` bytes; there is none by
+    /// default (see `MemoryLayout::advice_start`).
+    pub fn max_advice_size(mut self, max_advice_size: u64) -> Self {
+        self.max_advice_size = max_advice_size;
+        self
+    }
+
+    pub fn io_codec(mut self, io_codec: IoCodec) -> Self {
+        self.io_codec = io_codec;
+        self
+    }
+
+    pub fn max_bytecode_size(mut self, max_bytecode_size: usize) -> Self {
+        self.max_bytecode_size = max_bytecode_size;
+        self
+    }
+
+    pub fn max_memory_address(mut self, max_memory_address: usize) -> Self {
+        self.max_memory_address = max_memory_address;
+        self
+    }
+
+    pub fn max_trace_length(mut self, max_trace_length: usize) -> Self {
+        self.max_trace_length = max_trace_length;
+        self
+    }
+
+    /// Fails if a bound is zero, or if `M` is not a power of two.
+    pub fn build(self) -> Result<JoltConfig<C, M, F, PCS, ProofTranscript>, JoltError> {
+        if C == 0 || !M.is_power_of_two() {
+            return Err(JoltError::InvalidConfig(format!(
+                "C must be positive and M a power of two (C = {C}, M = {M})"
+            )));
+        }
+        for (name, bound) in [
+            ("max_bytecode_size", self.max_bytecode_size),
+            ("max_memory_address", self.max_memory_address),
+            ("max_trace_length", self.max_trace_length),
+        ] {
+            if bound == 0 {
+                return Err(JoltError::InvalidConfig(format!("{name} must be positive")));
+            }
+        }
+
+        Ok(JoltConfig {
+            memory_layout: MemoryLayout::new(self.max_input_size, self.max_output_size)
+                .with_max_advice_size(self.max_advice_size)
+                .with_io_codec(self.io_codec),
+            max_bytecode_size: self.max_bytecode_size,
+            max_memory_address: self.max_memory_address,
+            max_trace_length: self.max_trace_length,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::mock::MockCommitScheme;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+
+    type Config<const M: usize> =
+        JoltConfig<4, M, Fr, MockCommitScheme<Fr, KeccakTranscript>, KeccakTranscript>;
+
+    #[test]
+    fn builder() {
+        let config = Config::<{ 1 << 16 }>::builder()
+            .max_input_size(100)
+            .max_trace_length(1 << 10)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.memory_layout,
+            MemoryLayout::new(100, DEFAULT_MAX_OUTPUT_SIZE)
+        );
+        assert_eq!(config.max_trace_length, 1 << 10);
+        assert_eq!(config.max_bytecode_size, DEFAULT_MAX_BYTECODE_SIZE as usize);
+
+        let result = Config::<{ 1 << 16 }>::builder().max_trace_length(0).build();
+        assert!(matches!(result, Err(JoltError::InvalidConfig(_))));
+        let result = Config::<1000>::builder().build();
+        assert!(matches!(result, Err(JoltError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,268 @@
+//! Verification of a Jolt proof one component at a time, so that a verifier can
+//! bound the time it spends before reporting back, and finish later.
+use std::marker::PhantomData;
+use std::time::Instant;
+
+use ark_serialize::Valid;
+use common::rv_trace::JoltDevice;
+
+use super::bytecode::BytecodeProof;
+use super::instruction_lookups::InstructionLookupsProof;
+use super::read_write_memory::ReadWriteMemoryProof;
+use super::{Jolt, JoltCommitments, JoltProof, JoltVerifierKey, ProverDebugInfo};
+use crate::field::JoltField;
+use crate::lasso::memory_checking::StructuredPolynomialData;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::opening_proof::{ReducedOpeningProof, VerifierOpeningAccumulator};
+use crate::r1cs::constraints::R1CSConstraints;
+use crate::r1cs::inputs::R1CSProof;
+use crate::r1cs::spartan::UniformSpartanProof;
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::transcript::{AppendToTranscript, Transcript};
+
+/// A component of a Jolt proof. Components are verified in the order of `ALL`,
+/// which is the order in which the prover appended them to the transcript.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProofComponent {
+    Bytecode,
+    InstructionLookups,
+    ReadWriteMemory,
+    R1CS,
+    /// The batched opening proof for the polynomial openings claimed by all other
+    /// components.
+    Openings,
+}
+
+impl ProofComponent {
+    pub const ALL: [ProofComponent; 5] = [
+        ProofComponent::Bytecode,
+        ProofComponent::InstructionLookups,
+        ProofComponent::ReadWriteMemory,
+        ProofComponent::R1CS,
+        ProofComponent::Openings,
+    ];
+}
+
+/// The components of a proof verified so far, and those that remain.
+///
+/// Every component but `Openings` reduces to claims about polynomial openings,
+/// which are only checked once `Openings` is verified. Until then, a partial
+/// result shows that the proof is well-formed up to that point, not that it is
+/// valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationProgress {
+    pub verified: Vec<ProofComponent>,
+    pub remaining: Vec<ProofComponent>,
+}
+
+impl VerificationProgress {
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+/// Verifies the components of a Jolt proof one at a time. `Jolt::verify` is
+/// `IncrementalVerifier::new(..)?.finish()`.
+pub struct IncrementalVerifier<'a, J, F, PCS, const C: usize, const M: usize, ProofTranscript>
+where
+    J: Jolt<F, PCS, C, M, ProofTranscript>,
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    verifier_key: &'a JoltVerifierKey<C, F, PCS, ProofTranscript>,
+    commitments: JoltCommitments<PCS, ProofTranscript>,
+    transcript: ProofTranscript,
+    opening_accumulator: VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
+    /// Number of components of `ProofComponent::ALL` verified so far.
+    num_verified: usize,
+    program_io: Option<JoltDevice>,
+    bytecode: Option<BytecodeProof<F, PCS, ProofTranscript>>,
+    instruction_lookups: Option<
+        InstructionLookupsProof<C, M, F, PCS, J::InstructionSet, J::Subtables, ProofTranscript>,
+    >,
+    read_write_memory: Option<ReadWriteMemoryProof<F, PCS, ProofTranscript>>,
+    r1cs:
+        Option<R1CSProof<C, <J::Constraints as R1CSConstraints<C, F>>::Inputs, F, ProofTranscript>>,
+    opening_proof: Option<ReducedOpeningProof<F, PCS, ProofTranscript>>,
+}
+
+impl<'a, J, F, PCS, const C: usize, const M: usize, ProofTranscript>
+    IncrementalVerifier<'a, J, F, PCS, C, M, ProofTranscript>
+where
+    J: Jolt<F, PCS, C, M, ProofTranscript>,
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    /// Checks the commitments and sets up the transcript; no component of the
+    /// proof is verified yet.
+    pub fn new(
+        verifier_key: &'a JoltVerifierKey<C, F, PCS, ProofTranscript>,
+        proof: JoltProof<
+            C,
+            M,
+            <J::Constraints as R1CSConstraints<C, F>>::Inputs,
+            F,
+            PCS,
+            J::InstructionSet,
+            J::Subtables,
+            ProofTranscript,
+        >,
+        commitments: JoltCommitments<PCS, ProofTranscript>,
+        _debug_info: Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+    ) -> Result<Self, ProofVerifyError> {
+        // The commitments and proof come from the prover, and their group elements
+        // are not validated on deserialization (see `JoltHyperKZGProof`), so every
+        // one of them is checked here, once
+        commitments
+            .check()
+            .map_err(|_| ProofVerifyError::InvalidCommitment)?;
+        proof
+            .check()
+            .map_err(|_| ProofVerifyError::InvalidProofElement)?;
+        let max_trace_length = verifier_key.bounds.max_trace_length.next_power_of_two();
+        if proof.trace_length.next_power_of_two() > max_trace_length {
+            return Err(ProofVerifyError::TraceTooLong(
+                proof.trace_length,
+                max_trace_length,
+            ));
+        }
+
+        let mut transcript = ProofTranscript::new(b"Jolt transcript");
+        let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS, ProofTranscript> =
+            VerifierOpeningAccumulator::new();
+
+        #[cfg(test)]
+        if let Some(debug_info) = _debug_info {
+            transcript.compare_to(debug_info.transcript);
+            opening_accumulator.compare_to(debug_info.opening_accumulator, &debug_info.pcs_setup);
+        }
+        J::fiat_shamir_preamble(
+            &mut transcript,
+            &verifier_key.digest(),
+            &proof.program_io,
+            &verifier_key.memory_layout,
+            proof.trace_length,
+        );
+
+        // Regenerate the uniform Spartan key
+        let padded_trace_length = proof.trace_length.next_power_of_two();
+        let memory_start = verifier_key.memory_layout.input_start;
+        let r1cs_builder = J::Constraints::construct_constraints(padded_trace_length, memory_start);
+        let spartan_key = UniformSpartanProof::<C, _, F, ProofTranscript>::setup(
+            &r1cs_builder,
+            padded_trace_length,
+        );
+        transcript.append_scalar(&spartan_key.vk_digest);
+
+        let r1cs_proof = R1CSProof {
+            key: spartan_key,
+            proof: proof.r1cs,
+            _marker: PhantomData,
+        };
+
+        commitments
+            .read_write_values()
+            .iter()
+            .for_each(|value| value.append_to_transcript(&mut transcript));
+        commitments
+            .init_final_values()
+            .iter()
+            .for_each(|value| value.append_to_transcript(&mut transcript));
+        verifier_key
+            .memory_image
+            .append_to_transcript(&mut transcript);
+        commitments
+            .read_write_memory
+            .v_advice
+            .append_to_transcript(&mut transcript);
+
+        Ok(Self {
+            verifier_key,
+            commitments,
+            transcript,
+            opening_accumulator,
+            num_verified: 0,
+            program_io: Some(proof.program_io),
+            bytecode: Some(proof.bytecode),
+            instruction_lookups: Some(proof.instruction_lookups),
+            read_write_memory: Some(proof.read_write_memory),
+            r1cs: Some(r1cs_proof),
+            opening_proof: Some(proof.opening_proof),
+        })
+    }
+
+    pub fn progress(&self) -> VerificationProgress {
+        let (verified, remaining) = ProofComponent::ALL.split_at(self.num_verified);
+        VerificationProgress {
+            verified: verified.to_vec(),
+            remaining: remaining.to_vec(),
+        }
+    }
+
+    /// Verifies components until all are verified or `deadline` has passed. The
+    /// deadline is only checked between components, so it may be overrun by the
+    /// time taken to verify one component.
+    pub fn verify_until(mut self, deadline: Instant) -> Result<Self, ProofVerifyError> {
+        while self.num_verified < ProofComponent::ALL.len() && Instant::now() < deadline {
+            self.verify_next()?;
+        }
+        Ok(self)
+    }
+
+    /// Verifies all remaining components.
+    pub fn finish(mut self) -> Result<(), ProofVerifyError> {
+        while self.num_verified < ProofComponent::ALL.len() {
+            self.verify_next()?;
+        }
+        Ok(())
+    }
+
+    fn verify_next(&mut self) -> Result<(), ProofVerifyError> {
+        let verifier_key = self.verifier_key;
+        match ProofComponent::ALL[self.num_verified] {
+            ProofComponent::Bytecode => J::verify_bytecode(
+                &verifier_key.bytecode,
+                &verifier_key.generators,
+                self.bytecode.take().unwrap(),
+                &self.commitments,
+                &mut self.opening_accumulator,
+                &mut self.transcript,
+            )?,
+            ProofComponent::InstructionLookups => J::verify_instruction_lookups(
+                &verifier_key.instruction_lookups,
+                &verifier_key.generators,
+                self.instruction_lookups.take().unwrap(),
+                &self.commitments,
+                &mut self.opening_accumulator,
+                &mut self.transcript,
+            )?,
+            ProofComponent::ReadWriteMemory => J::verify_memory(
+                &mut verifier_key.read_write_memory.clone(),
+                &verifier_key.generators,
+                &verifier_key.memory_layout,
+                self.read_write_memory.take().unwrap(),
+                &self.commitments,
+                &verifier_key.memory_image,
+                self.program_io.take().unwrap(),
+                &mut self.opening_accumulator,
+                &mut self.transcript,
+            )?,
+            ProofComponent::R1CS => J::verify_r1cs(
+                self.r1cs.take().unwrap(),
+                &self.commitments,
+                &mut self.opening_accumulator,
+                &mut self.transcript,
+            )?,
+            // Batch-verify all openings
+            ProofComponent::Openings => self.opening_accumulator.reduce_and_verify(
+                &verifier_key.generators,
+                &self.opening_proof.take().unwrap(),
+                &mut self.transcript,
+            )?,
+        }
+        self.num_verified += 1;
+        Ok(())
+    }
+}
@@ -4,7 +4,9 @@ use crate::poly::multilinear_polynomial::{
 };
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::subprotocols::grand_product::BatchedGrandProduct;
-use crate::subprotocols::sparse_grand_product::ToggledBatchedGrandProduct;
+use crate::subprotocols::sparse_grand_product::{
+    SparseGrandProductConfig, ToggledBatchedGrandProduct,
+};
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use itertools::{interleave, Itertools};
@@ -16,6 +18,7 @@ use tracing::trace_span;
 use crate::field::JoltField;
 use crate::jolt::instruction::{JoltInstructionSet, SubtableIndices};
 use crate::jolt::subtable::JoltSubtableSet;
+use crate::jolt::vm::progress::check_cancelled;
 use crate::lasso::memory_checking::{
     Initializable, MultisetHashes, NoExogenousOpenings, StructuredPolynomialData,
     VerifierComputedOpening,
@@ -144,16 +147,16 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T>
 
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 /// Polynomial openings associated with the "primary sumcheck" of Jolt instruction lookups.
-struct PrimarySumcheckOpenings<F>
+pub(crate) struct PrimarySumcheckOpenings<F>
 where
     F: JoltField,
 {
     /// Evaluations of the E_i polynomials at the opening point. Vector is of length NUM_MEMORIES.
-    E_poly_openings: Vec<F>,
+    pub(crate) E_poly_openings: Vec<F>,
     /// Evaluations of the flag polynomials at the opening point. Vector is of length NUM_INSTRUCTIONS.
-    flag_openings: Vec<F>,
+    pub(crate) flag_openings: Vec<F>,
     /// Evaluation of the lookup_outputs polynomial at the opening point.
-    lookup_outputs_opening: F,
+    pub(crate) lookup_outputs_opening: F,
 }
 
 impl<const C: usize, const M: usize, F, PCS, InstructionSet, Subtables, ProofTranscript>
@@ -588,8 +591,8 @@ pub struct InstructionLookupsProof<
 {
     _instructions: PhantomData<InstructionSet>,
     _subtables: PhantomData<Subtables>,
-    primary_sumcheck: PrimarySumcheck<F, ProofTranscript>,
-    memory_checking: MemoryCheckingProof<
+    pub(crate) primary_sumcheck: PrimarySumcheck<F, ProofTranscript>,
+    pub(crate) memory_checking: MemoryCheckingProof<
         F,
         PCS,
         InstructionLookupOpenings<F>,
@@ -600,14 +603,29 @@ pub struct InstructionLookupsProof<
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct PrimarySumcheck<F: JoltField, ProofTranscript: Transcript> {
-    sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
     num_rounds: usize,
-    openings: PrimarySumcheckOpenings<F>,
+    pub(crate) openings: PrimarySumcheckOpenings<F>,
     // opening_proof: PCS::BatchedProof,
     _marker: PhantomData<ProofTranscript>,
 }
 
-#[derive(Clone)]
+impl<F: JoltField, ProofTranscript: Transcript> PrimarySumcheck<F, ProofTranscript> {
+    /// Reassembles a primary sumcheck decoded from another encoding (see `sol_types`).
+    pub(crate) fn new(
+        sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+        openings: PrimarySumcheckOpenings<F>,
+    ) -> Self {
+        Self {
+            num_rounds: sumcheck_proof.compressed_polys.len(),
+            sumcheck_proof,
+            openings,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct InstructionLookupsPreprocessing<const C: usize, F: JoltField> {
     subtable_to_memory_indices: Vec<Vec<usize>>, // Vec<Range<usize>>?
     instruction_to_memory_indices: Vec<Vec<usize>>,
@@ -676,6 +694,20 @@ impl<const C: usize, F: JoltField> InstructionLookupsPreprocessing<C, F> {
         }
     }
 
+    /// Returns a copy of this preprocessing without the materialized subtables,
+    /// which only the prover uses.
+    pub fn verifier_preprocessing(&self) -> Self {
+        Self {
+            num_memories: self.num_memories,
+            materialized_subtables: vec![],
+            subtable_to_memory_indices: self.subtable_to_memory_indices.clone(),
+            memory_to_subtable_index: self.memory_to_subtable_index.clone(),
+            memory_to_dimension_index: self.memory_to_dimension_index.clone(),
+            instruction_to_memory_indices: self.instruction_to_memory_indices.clone(),
+            _field: PhantomData,
+        }
+    }
+
     /// Materializes all subtables used by this Jolt instance.
     #[tracing::instrument(skip_all)]
     fn materialize_subtables<const M: usize, Subtables>() -> Vec<Vec<u32>>
@@ -702,6 +734,25 @@ where
     const NUM_SUBTABLES: usize = Subtables::COUNT;
     const NUM_INSTRUCTIONS: usize = InstructionSet::COUNT;
 
+    /// Reassembles a proof decoded from another encoding (see `sol_types`).
+    pub(crate) fn new(
+        primary_sumcheck: PrimarySumcheck<F, ProofTranscript>,
+        memory_checking: MemoryCheckingProof<
+            F,
+            PCS,
+            InstructionLookupOpenings<F>,
+            NoExogenousOpenings,
+            ProofTranscript,
+        >,
+    ) -> Self {
+        Self {
+            _instructions: PhantomData,
+            _subtables: PhantomData,
+            primary_sumcheck,
+            memory_checking,
+        }
+    }
+
     #[tracing::instrument(skip_all, name = "InstructionLookups::prove")]
     pub fn prove<'a>(
         generators: &PCS::Setup,
@@ -776,6 +827,7 @@ where
             preprocessing,
             &polynomials.instruction_lookups,
             polynomials,
+            SparseGrandProductConfig::default(),
             opening_accumulator,
             transcript,
         );
@@ -790,7 +842,7 @@ where
 
     pub fn verify(
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
-        pcs_setup: &PCS::Setup,
+        pcs_setup: &PCS::VerifierSetup,
         proof: InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables, ProofTranscript>,
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
@@ -853,6 +905,7 @@ where
             proof.memory_checking,
             &commitments.instruction_lookups,
             commitments,
+            &[],
             opening_accumulator,
             transcript,
         )?;
@@ -1007,6 +1060,7 @@ where
         let mut compressed_polys: Vec<CompressedUniPoly<F>> = Vec::with_capacity(num_rounds);
 
         for _round in 0..num_rounds {
+            check_cancelled();
             let univariate_poly = Self::primary_sumcheck_prover_message(
                 preprocessing,
                 &eq_poly,
@@ -6,23 +6,38 @@ use crate::poly::multilinear_polynomial::MultilinearPolynomial;
 use crate::poly::opening_proof::{
     ProverOpeningAccumulator, ReducedOpeningProof, VerifierOpeningAccumulator,
 };
-use crate::r1cs::constraints::R1CSConstraints;
+use crate::r1cs::constraints::{check_satisfiability, R1CSConstraints};
 use crate::r1cs::spartan::{self, UniformSpartanProof};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use common::rv_trace::{MemoryLayout, NUM_CIRCUIT_FLAGS};
+use itertools::Either;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
-use strum::EnumCount;
+use sha3::{Digest, Keccak256};
+#[cfg(feature = "fs")]
+use std::fs::File;
+#[cfg(feature = "fs")]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(feature = "fs")]
+use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+use strum::{EnumCount, IntoEnumIterator};
 use timestamp_range_check::TimestampRangeCheckStuff;
 
-use crate::join_conditional;
+use crate::jolt::compat::{PROOF_FORMAT_VERSION, PROTOCOL_VERSION};
 use crate::jolt::{
     instruction::{
         div::DIVInstruction, divu::DIVUInstruction, mulh::MULHInstruction,
-        mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction,
+        mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction, JoltInstruction,
         VirtualInstructionSequence,
     },
-    subtable::JoltSubtableSet,
+    subtable::{JoltSubtableSet, LassoSubtable},
     vm::timestamp_range_check::TimestampValidityProof,
 };
 use crate::lasso::memory_checking::{
@@ -31,29 +46,46 @@ use crate::lasso::memory_checking::{
 use crate::msm::icicle;
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::r1cs::inputs::{ConstraintInput, R1CSPolynomials, R1CSProof, R1CSStuff};
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{JoltError, PreprocessingLoadError, ProofVerifyError};
+use crate::utils::math::Math;
+use crate::utils::serialization::{par_batch_check, HashingReader, HashingWriter};
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::{AppendToTranscript, Transcript};
 use common::{
-    constants::MEMORY_OPS_PER_INSTRUCTION,
+    constants::{MAX_LOG_SIZE, MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS},
     rv_trace::{ELFInstruction, JoltDevice, MemoryOp},
 };
 
+use self::archive::to_hex;
 use self::bytecode::{BytecodePreprocessing, BytecodeProof, BytecodeRow, BytecodeStuff};
+use self::config::JoltConfig;
+use self::incremental::IncrementalVerifier;
 use self::instruction_lookups::{
     InstructionLookupStuff, InstructionLookupsPreprocessing, InstructionLookupsProof,
 };
+use self::progress::{ProgressTracker, ProverPhase};
+use self::prover_config::JoltProverConfig;
+#[cfg(not(target_arch = "wasm32"))]
+use self::proving_handle::ProvingHandle;
 use self::read_write_memory::{
     ReadWriteMemoryPolynomials, ReadWriteMemoryPreprocessing, ReadWriteMemoryProof,
     ReadWriteMemoryStuff,
 };
+use self::size_report::ProofSizeReport;
 
+use super::instruction::atomic::{
+    AMOADDInstruction, AMOANDInstruction, AMOMAXInstruction, AMOMAXUInstruction, AMOMINInstruction,
+    AMOMINUInstruction, AMOORInstruction, AMOSWAPInstruction, AMOXORInstruction, SCInstruction,
+};
+use super::instruction::fixed_point::{FIXEDMULInstruction, SATURATINGADDInstruction};
 use super::instruction::lb::LBInstruction;
 use super::instruction::lbu::LBUInstruction;
 use super::instruction::lh::LHInstruction;
 use super::instruction::lhu::LHUInstruction;
+use super::instruction::misaligned::{LWMisalignedInstruction, SWMisalignedInstruction};
 use super::instruction::sb::SBInstruction;
 use super::instruction::sh::SHInstruction;
+use super::instruction::tree_step::TREESTEPInstruction;
 use super::instruction::JoltInstructionSet;
 
 #[derive(Clone)]
@@ -67,8 +99,210 @@ where
     pub instruction_lookups: InstructionLookupsPreprocessing<C, F>,
     pub bytecode: BytecodePreprocessing<F>,
     pub read_write_memory: ReadWriteMemoryPreprocessing,
+    /// Commitment to the initial memory image (`ReadWriteMemoryPreprocessing::image`),
+    /// which the verifier checks `v_init` against instead of the image itself.
+    pub memory_image: PCS::Commitment,
     pub memory_layout: MemoryLayout,
     field: F::SmallValueLookupTables,
+    /// Maximum polynomial size supported by `generators`.
+    setup_size: usize,
+    /// Digest of the arguments `Jolt::preprocess` was called with; see
+    /// `Jolt::preprocess_or_load`.
+    inputs_digest: [u8; 32],
+    pub bounds: PreprocessingBounds,
+}
+
+/// The size bounds a program is preprocessed with (see `Jolt::preprocess`), either
+/// chosen up front or derived from an execution by `PreprocessingBounds::tight`.
+/// Recorded in the verifier key: proving or verifying an execution that exceeds
+/// them fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PreprocessingBounds {
+    pub max_bytecode_size: usize,
+    pub max_memory_address: usize,
+    pub max_trace_length: usize,
+}
+
+impl PreprocessingBounds {
+    /// The smallest bounds that fit `bytecode` executed as `trace`, rounded up to the
+    /// sizes of the polynomials they determine.
+    pub fn tight<InstructionSet: JoltInstructionSet>(
+        bytecode: &[ELFInstruction],
+        memory_layout: &MemoryLayout,
+        trace: &[JoltTraceStep<InstructionSet>],
+    ) -> Self {
+        let bytecode_size = expand_virtual_sequences(bytecode.to_vec()).len();
+        Self {
+            // Account for the no-op prepended to bytecode
+            max_bytecode_size: (bytecode_size + 1).next_power_of_two() - 1,
+            max_memory_address: read_write_memory::memory_size(memory_layout, trace),
+            max_trace_length: trace.len().next_power_of_two(),
+        }
+    }
+
+    /// Size of the largest polynomial committed to by a proof within these bounds.
+    fn max_poly_len(&self, m: usize) -> usize {
+        [
+            (self.max_bytecode_size + 1).next_power_of_two(), // Account for no-op prepended to bytecode
+            self.max_trace_length.next_power_of_two(),
+            self.max_memory_address.next_power_of_two(),
+            m,
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    /// Fails if a bytecode of `code_size` rows (including padding), a memory of
+    /// `memory_size` words or a trace of `trace_length` steps exceeds these bounds.
+    fn check_execution(
+        &self,
+        code_size: usize,
+        memory_size: usize,
+        trace_length: usize,
+    ) -> Result<(), JoltError> {
+        for (bound, limit, required) in [
+            (
+                "max_bytecode_size",
+                (self.max_bytecode_size + 1).next_power_of_two(),
+                code_size,
+            ),
+            (
+                "max_memory_address",
+                self.max_memory_address.next_power_of_two(),
+                memory_size,
+            ),
+            (
+                "max_trace_length",
+                self.max_trace_length.next_power_of_two(),
+                trace_length.next_power_of_two(),
+            ),
+        ] {
+            if required > limit {
+                return Err(JoltError::BoundExceeded {
+                    bound,
+                    limit,
+                    required,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const C: usize, F, PCS, ProofTranscript> JoltPreprocessing<C, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    /// Digest of the program's bytecode. Bound into the transcript of every proof,
+    /// so that a proof of one program never verifies against another.
+    pub fn digest(&self) -> [u8; 32] {
+        self.bytecode.digest()
+    }
+
+    /// Extracts the parts of this preprocessing needed to verify proofs.
+    pub fn verifier_key(&self) -> JoltVerifierKey<C, F, PCS, ProofTranscript> {
+        JoltVerifierKey {
+            generators: PCS::verifier_setup(&self.generators),
+            instruction_lookups: self.instruction_lookups.verifier_preprocessing(),
+            bytecode: self.bytecode.clone(),
+            read_write_memory: self.read_write_memory.verifier_preprocessing(),
+            memory_image: self.memory_image.clone(),
+            memory_layout: self.memory_layout.clone(),
+            bounds: self.bounds,
+        }
+    }
+}
+
+/// The part of `JoltPreprocessing` needed by the verifier: the verifier's half of the
+/// PCS setup, and none of the preprocessing only the prover uses (e.g. the
+/// materialized subtables).
+///
+/// Deserializing a verifier key also initializes the field's small-value lookup
+/// tables, which are otherwise initialized by `Jolt::preprocess`.
+#[derive(Clone, CanonicalSerialize)]
+pub struct JoltVerifierKey<const C: usize, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    pub generators: PCS::VerifierSetup,
+    pub instruction_lookups: InstructionLookupsPreprocessing<C, F>,
+    pub bytecode: BytecodePreprocessing<F>,
+    pub read_write_memory: ReadWriteMemoryPreprocessing,
+    pub memory_image: PCS::Commitment,
+    pub memory_layout: MemoryLayout,
+    pub bounds: PreprocessingBounds,
+}
+
+impl<const C: usize, F, PCS, ProofTranscript> JoltVerifierKey<C, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    /// Digest of the program's bytecode; see `JoltPreprocessing::digest`.
+    pub fn digest(&self) -> [u8; 32] {
+        self.bytecode.digest()
+    }
+}
+
+impl<const C: usize, F, PCS, ProofTranscript> Valid for JoltVerifierKey<C, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.generators.check()?;
+        self.instruction_lookups.check()?;
+        self.bytecode.check()?;
+        self.read_write_memory.check()?;
+        self.memory_image.check()?;
+        self.memory_layout.check()?;
+        self.bounds.check()
+    }
+}
+
+impl<const C: usize, F, PCS, ProofTranscript> CanonicalDeserialize
+    for JoltVerifierKey<C, F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let key = Self {
+            generators: PCS::VerifierSetup::deserialize_with_mode(&mut reader, compress, validate)?,
+            instruction_lookups: InstructionLookupsPreprocessing::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            bytecode: BytecodePreprocessing::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            read_write_memory: ReadWriteMemoryPreprocessing::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            memory_image: PCS::Commitment::deserialize_with_mode(&mut reader, compress, validate)?,
+            memory_layout: MemoryLayout::deserialize_with_mode(&mut reader, compress, validate)?,
+            bounds: PreprocessingBounds::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        F::initialize_lookup_tables(F::compute_lookup_tables());
+        Ok(key)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -79,13 +313,17 @@ pub struct JoltTraceStep<InstructionSet: JoltInstructionSet> {
     pub circuit_flags: [bool; NUM_CIRCUIT_FLAGS],
 }
 
-pub struct ProverDebugInfo<F, ProofTranscript>
+pub struct ProverDebugInfo<F, PCS, ProofTranscript>
 where
     F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
     pub(crate) transcript: ProofTranscript,
     pub(crate) opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript>,
+    /// The prover's setup, which the verifier does not otherwise have, so that it can
+    /// recompute the commitments to the prover's polynomials.
+    pub(crate) pcs_setup: PCS::Setup,
 }
 
 impl<InstructionSet: JoltInstructionSet> JoltTraceStep<InstructionSet> {
@@ -138,7 +376,35 @@ pub struct JoltProof<
     pub opening_proof: ReducedOpeningProof<F, PCS, ProofTranscript>,
 }
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+impl<const C: usize, const M: usize, I, F, PCS, InstructionSet, Subtables, ProofTranscript>
+    JoltProof<C, M, I, F, PCS, InstructionSet, Subtables, ProofTranscript>
+where
+    I: ConstraintInput,
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    InstructionSet: JoltInstructionSet,
+    Subtables: JoltSubtableSet<F>,
+    ProofTranscript: Transcript,
+{
+    /// The compressed size of each component of this proof and of `commitments`, so
+    /// that e.g. on-chain verifiers can see where the bytes go.
+    pub fn size_report(
+        &self,
+        commitments: &JoltCommitments<PCS, ProofTranscript>,
+    ) -> ProofSizeReport {
+        ProofSizeReport {
+            program_io: self.trace_length.compressed_size() + self.program_io.compressed_size(),
+            bytecode: self.bytecode.compressed_size(),
+            read_write_memory: self.read_write_memory.compressed_size(),
+            instruction_lookups: self.instruction_lookups.compressed_size(),
+            r1cs: self.r1cs.compressed_size(),
+            openings: self.opening_proof.compressed_size(),
+            commitments: commitments.compressed_size(),
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct JoltStuff<T: CanonicalSerialize + CanonicalDeserialize + Sync> {
     pub(crate) bytecode: BytecodeStuff<T>,
     pub(crate) read_write_memory: ReadWriteMemoryStuff<T>,
@@ -195,6 +461,90 @@ impl<T: CanonicalSerialize + CanonicalDeserialize + Sync> StructuredPolynomialDa
     }
 }
 
+impl<T: CanonicalSerialize + CanonicalDeserialize + Sync> JoltStuff<T> {
+    /// Every value that is committed to by the prover.
+    fn committed_values(&self) -> Vec<&T> {
+        self.read_write_values()
+            .into_iter()
+            .chain(self.init_final_values())
+            .chain([&self.read_write_memory.v_advice])
+            .collect()
+    }
+}
+
+/// Serializes the values committed to per proof, omitting the fields of
+/// `read_write_memory` that have only openings.
+impl<T: CanonicalSerialize + CanonicalDeserialize + Default + Sync> CanonicalSerialize
+    for JoltStuff<T>
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.bytecode.serialize_with_mode(&mut writer, compress)?;
+        self.read_write_memory
+            .serialize_proof_values(&mut writer, compress)?;
+        self.instruction_lookups
+            .serialize_with_mode(&mut writer, compress)?;
+        self.timestamp_range_check
+            .serialize_with_mode(&mut writer, compress)?;
+        self.r1cs.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.bytecode.serialized_size(compress)
+            + self.read_write_memory.proof_values_size(compress)
+            + self.instruction_lookups.serialized_size(compress)
+            + self.timestamp_range_check.serialized_size(compress)
+            + self.r1cs.serialized_size(compress)
+    }
+}
+
+/// Validates every committed value in parallel, batching the checks over chunks; for
+/// commitments, this checks that each group element is on the curve and in the
+/// prime-order subgroup.
+impl<T: CanonicalSerialize + CanonicalDeserialize + Sync> Valid for JoltStuff<T> {
+    fn check(&self) -> Result<(), SerializationError> {
+        par_batch_check(&self.committed_values())
+    }
+}
+
+impl<T: CanonicalSerialize + CanonicalDeserialize + Default + Sync> CanonicalDeserialize
+    for JoltStuff<T>
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        // Defer validation, so that all values are checked at once, in parallel
+        let stuff = Self {
+            bytecode: BytecodeStuff::deserialize_with_mode(&mut reader, compress, Validate::No)?,
+            read_write_memory: ReadWriteMemoryStuff::deserialize_proof_values(
+                &mut reader,
+                compress,
+                Validate::No,
+            )?,
+            instruction_lookups: InstructionLookupStuff::deserialize_with_mode(
+                &mut reader,
+                compress,
+                Validate::No,
+            )?,
+            timestamp_range_check: TimestampRangeCheckStuff::deserialize_with_mode(
+                &mut reader,
+                compress,
+                Validate::No,
+            )?,
+            r1cs: R1CSStuff::deserialize_with_mode(&mut reader, compress, Validate::No)?,
+        };
+        if let Validate::Yes = validate {
+            stuff.check()?;
+        }
+        Ok(stuff)
+    }
+}
+
 /// Note –– F: JoltField bound is not enforced.
 ///
 /// See issue #112792 <https://github.com/rust-lang/rust/issues/112792>.
@@ -231,6 +581,28 @@ impl<
     }
 }
 
+impl<
+        const C: usize,
+        T: CanonicalSerialize + CanonicalDeserialize + Default + Sync,
+        PCS: CommitmentScheme<ProofTranscript>,
+        ProofTranscript: Transcript,
+    > Initializable<T, JoltVerifierKey<C, PCS::Field, PCS, ProofTranscript>> for JoltStuff<T>
+{
+    fn initialize(preprocessing: &JoltVerifierKey<C, PCS::Field, PCS, ProofTranscript>) -> Self {
+        Self {
+            bytecode: BytecodeStuff::initialize(&preprocessing.bytecode),
+            read_write_memory: ReadWriteMemoryStuff::initialize(&preprocessing.read_write_memory),
+            instruction_lookups: InstructionLookupStuff::initialize(
+                &preprocessing.instruction_lookups,
+            ),
+            timestamp_range_check: TimestampRangeCheckStuff::initialize(
+                &crate::lasso::memory_checking::NoPreprocessing,
+            ),
+            r1cs: R1CSStuff::initialize(&C),
+        }
+    }
+}
+
 impl<F: JoltField> JoltPolynomials<F> {
     #[tracing::instrument(skip_all, name = "JoltPolynomials::commit")]
     pub fn commit<const C: usize, PCS, ProofTranscript>(
@@ -247,40 +619,216 @@ impl<F: JoltField> JoltPolynomials<F> {
         drop(_guard);
         drop(span);
 
-        let trace_polys = self.read_write_values();
-        let trace_commitments = PCS::batch_commit(&trace_polys, &preprocessing.generators);
+        let generators = &preprocessing.generators;
+        commit_witness::<PCS, ProofTranscript, _, _>(
+            &self.bytecode,
+            &mut commitments.bytecode,
+            generators,
+        );
+        commit_witness::<PCS, ProofTranscript, _, _>(
+            &self.read_write_memory,
+            &mut commitments.read_write_memory,
+            generators,
+        );
+        self.read_write_memory
+            .commit_advice(&mut commitments.read_write_memory, generators);
+        commit_witness::<PCS, ProofTranscript, _, _>(
+            &self.instruction_lookups,
+            &mut commitments.instruction_lookups,
+            generators,
+        );
+        commit_witness::<PCS, ProofTranscript, _, _>(
+            &self.timestamp_range_check,
+            &mut commitments.timestamp_range_check,
+            generators,
+        );
+        commit_witness::<PCS, ProofTranscript, _, _>(&self.r1cs, &mut commitments.r1cs, generators);
 
         commitments
-            .read_write_values_mut()
-            .into_iter()
-            .zip(trace_commitments.into_iter())
-            .for_each(|(dest, src)| *dest = src);
+    }
+}
 
-        let span = tracing::span!(tracing::Level::INFO, "commit::t_final");
-        let _guard = span.enter();
-        commitments.bytecode.t_final =
-            PCS::commit(&self.bytecode.t_final, &preprocessing.generators);
-        drop(_guard);
-        drop(span);
+/// Describes how `program_io` exceeds the input, output or log space of `memory_layout`,
+/// if it does.
+fn program_io_mismatch(program_io: &JoltDevice, memory_layout: &MemoryLayout) -> Option<String> {
+    if program_io.inputs.len() > memory_layout.max_input_size as usize {
+        Some(format!(
+            "{} bytes of input exceed the maximum of {}",
+            program_io.inputs.len(),
+            memory_layout.max_input_size
+        ))
+    } else if program_io.outputs.len() > memory_layout.max_output_size as usize {
+        Some(format!(
+            "{} bytes of output exceed the maximum of {}",
+            program_io.outputs.len(),
+            memory_layout.max_output_size
+        ))
+    } else if program_io.logs.len() > MAX_LOG_SIZE as usize {
+        Some(format!(
+            "{} bytes of logs exceed the maximum of {}",
+            program_io.logs.len(),
+            MAX_LOG_SIZE
+        ))
+    } else if program_io.advice_tape.len() > memory_layout.max_advice_size as usize {
+        Some(format!(
+            "{} bytes of advice exceed the maximum of {}",
+            program_io.advice_tape.len(),
+            memory_layout.max_advice_size
+        ))
+    } else if !program_io.journal_matches_digest() {
+        Some("journal entries do not match the journal digest".to_string())
+    } else {
+        None
+    }
+}
 
-        let span = tracing::span!(tracing::Level::INFO, "commit::read_write_memory");
-        let _guard = span.enter();
-        (
-            commitments.read_write_memory.v_final,
-            commitments.read_write_memory.t_final,
-        ) = join_conditional!(
-            || PCS::commit(&self.read_write_memory.v_final, &preprocessing.generators),
-            || PCS::commit(&self.read_write_memory.t_final, &preprocessing.generators)
-        );
-        commitments.instruction_lookups.final_cts = PCS::batch_commit(
-            &self.instruction_lookups.final_cts,
-            &preprocessing.generators,
-        );
-        drop(_guard);
-        drop(span);
+/// Commits to the polynomials of one part of the witness (e.g. `BytecodeStuff`), into
+/// the corresponding part of `commitments`. Lets the prover commit to each part as soon
+/// as it has been generated, while it generates the next one (see `Jolt::prove`).
+#[tracing::instrument(skip_all)]
+fn commit_witness<PCS, ProofTranscript, Polynomials, Commitments>(
+    polynomials: &Polynomials,
+    commitments: &mut Commitments,
+    generators: &PCS::Setup,
+) where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+    Polynomials: StructuredPolynomialData<MultilinearPolynomial<PCS::Field>>,
+    Commitments: StructuredPolynomialData<PCS::Commitment>,
+{
+    let read_write_commitments =
+        commit_read_write::<PCS, ProofTranscript>(polynomials.read_write_values(), generators);
+    for (dest, commitment) in commitments
+        .read_write_values_mut()
+        .into_iter()
+        .zip(read_write_commitments)
+    {
+        *dest = commitment;
+    }
 
-        commitments
+    // The init/final polynomials of a part all have the same length
+    let init_final_polys = polynomials.init_final_values();
+    if !init_final_polys.is_empty() {
+        let init_final_commitments = PCS::batch_commit(&init_final_polys, generators);
+        for (dest, commitment) in commitments
+            .init_final_values_mut()
+            .into_iter()
+            .zip(init_final_commitments)
+        {
+            *dest = commitment;
+        }
+    }
+}
+
+/// Commits to `polys`, which all have the trace's length. The flag polynomials are
+/// extremely sparse 0/1 vectors, which are committed to by summing the generators at
+/// their ones.
+fn commit_read_write<PCS, ProofTranscript>(
+    polys: Vec<&MultilinearPolynomial<PCS::Field>>,
+    generators: &PCS::Setup,
+) -> Vec<PCS::Commitment>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    let num_polys = polys.len();
+    let (sparse_polys, dense_polys): (Vec<_>, Vec<_>) = polys
+        .into_par_iter()
+        .enumerate()
+        .partition_map(|(i, poly)| match poly.binary_indices() {
+            Some(indices) => Either::Left((i, indices, poly.len())),
+            None => Either::Right((i, poly)),
+        });
+
+    let (dense_indices, dense_polys): (Vec<_>, Vec<_>) = dense_polys.into_iter().unzip();
+    let dense_commitments = if dense_polys.is_empty() {
+        vec![]
+    } else {
+        PCS::batch_commit(&dense_polys, generators)
+    };
+    let span = tracing::span!(tracing::Level::INFO, "commit::sparse");
+    let _guard = span.enter();
+    let sparse_commitments: Vec<_> = sparse_polys
+        .par_iter()
+        .map(|(i, indices, len)| (*i, PCS::commit_sparse_binary(indices, *len, generators)))
+        .collect();
+    drop(_guard);
+    drop(span);
+
+    let mut commitments: Vec<_> = (0..num_polys).map(|_| PCS::Commitment::default()).collect();
+    for (i, commitment) in dense_indices
+        .into_iter()
+        .zip(dense_commitments)
+        .chain(sparse_commitments)
+    {
+        commitments[i] = commitment;
     }
+    commitments
+}
+
+/// Replaces each instruction of `bytecode` that Jolt proves with a virtual sequence by
+/// that sequence.
+fn expand_virtual_sequences(bytecode: Vec<ELFInstruction>) -> Vec<ELFInstruction> {
+    bytecode
+        .into_iter()
+        .flat_map(|instruction| match instruction.opcode {
+            tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::REM => REMInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::SH => SHInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::SB => SBInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::LBU => LBUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::LHU => LHUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::LB => LBInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::LH => LHInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::SC_W => SCInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOSWAP_W => AMOSWAPInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOADD_W => AMOADDInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOXOR_W => AMOXORInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOAND_W => AMOANDInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOOR_W => AMOORInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOMIN_W => AMOMINInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOMAX_W => AMOMAXInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOMINU_W => AMOMINUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::AMOMAXU_W => AMOMAXUInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::LW_MISALIGNED => {
+                LWMisalignedInstruction::<32>::virtual_sequence(instruction)
+            }
+            tracer::RV32IM::SW_MISALIGNED => {
+                SWMisalignedInstruction::<32>::virtual_sequence(instruction)
+            }
+            tracer::RV32IM::TREE_STEP => TREESTEPInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::FIXED_MUL => FIXEDMULInstruction::<32>::virtual_sequence(instruction),
+            tracer::RV32IM::SATURATING_ADD => {
+                SATURATINGADDInstruction::<32>::virtual_sequence(instruction)
+            }
+            _ => vec![instruction],
+        })
+        .collect()
+}
+
+/// Keccak256 digest of the arguments of `Jolt::preprocess`, which (with the protocol)
+/// determine its output.
+fn preprocessing_inputs_digest(
+    bytecode: &[ELFInstruction],
+    memory_layout: &MemoryLayout,
+    memory_init: &[(u64, u8)],
+    max_bytecode_size: usize,
+    max_memory_address: usize,
+    max_trace_length: usize,
+) -> [u8; 32] {
+    let inputs = (
+        bytecode,
+        memory_layout,
+        memory_init,
+        max_bytecode_size as u64,
+        max_memory_address as u64,
+        max_trace_length as u64,
+    );
+    Keccak256::digest(postcard::to_stdvec(&inputs).unwrap()).into()
 }
 
 pub trait Jolt<F, PCS, const C: usize, const M: usize, ProofTranscript>
@@ -293,7 +841,8 @@ where
     type Subtables: JoltSubtableSet<F>;
     type Constraints: R1CSConstraints<C, F>;
 
-    #[tracing::instrument(skip_all, name = "Jolt::preprocess")]
+    /// Preprocesses `bytecode` and `memory_init` for proofs of executions within the
+    /// given bounds. Panics if the program does not fit them; see `try_preprocess`.
     fn preprocess(
         bytecode: Vec<ELFInstruction>,
         memory_layout: MemoryLayout,
@@ -302,6 +851,36 @@ where
         max_memory_address: usize,
         max_trace_length: usize,
     ) -> JoltPreprocessing<C, F, PCS, ProofTranscript> {
+        Self::try_preprocess(
+            bytecode,
+            memory_layout,
+            memory_init,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        )
+        .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `preprocess`, but fails with `JoltError::BoundExceeded` if the initial
+    /// memory image is larger than the bounds allow.
+    #[tracing::instrument(skip_all, name = "Jolt::preprocess")]
+    fn try_preprocess(
+        bytecode: Vec<ELFInstruction>,
+        memory_layout: MemoryLayout,
+        memory_init: Vec<(u64, u8)>,
+        max_bytecode_size: usize,
+        max_memory_address: usize,
+        max_trace_length: usize,
+    ) -> Result<JoltPreprocessing<C, F, PCS, ProofTranscript>, JoltError> {
+        let inputs_digest = preprocessing_inputs_digest(
+            &bytecode,
+            &memory_layout,
+            &memory_init,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        );
         let small_value_lookup_tables = F::compute_lookup_tables();
         F::initialize_lookup_tables(small_value_lookup_tables.clone());
         icicle::icicle_init();
@@ -312,55 +891,303 @@ where
             Self::Subtables,
         >();
 
-        let read_write_memory_preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init);
+        let read_write_memory_preprocessing =
+            ReadWriteMemoryPreprocessing::preprocess(memory_init, &memory_layout);
 
-        let bytecode_rows: Vec<BytecodeRow> = bytecode
+        let bytecode_rows: Vec<BytecodeRow> = expand_virtual_sequences(bytecode)
             .into_iter()
-            .flat_map(|instruction| match instruction.opcode {
-                tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::REM => REMInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::SH => SHInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::SB => SBInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::LBU => LBUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::LHU => LHUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::LB => LBInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::LH => LHInstruction::<32>::virtual_sequence(instruction),
-                _ => vec![instruction],
-            })
             .map(|instruction| BytecodeRow::from_instruction::<Self::InstructionSet>(&instruction))
             .collect();
         let bytecode_preprocessing = BytecodePreprocessing::<F>::preprocess(bytecode_rows);
 
-        let max_poly_len: usize = [
-            (max_bytecode_size + 1).next_power_of_two(), // Account for no-op prepended to bytecode
-            max_trace_length.next_power_of_two(),
-            max_memory_address.next_power_of_two(),
-            M,
-        ]
-        .into_iter()
-        .max()
-        .unwrap();
+        let bounds = PreprocessingBounds {
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        };
+        let max_poly_len = bounds.max_poly_len(M);
+        let image_len = read_write_memory_preprocessing.image_num_vars.pow2();
+        if image_len > max_poly_len {
+            return Err(JoltError::BoundExceeded {
+                bound: "max_memory_address",
+                limit: max_poly_len,
+                required: image_len,
+            });
+        }
         let generators = PCS::setup(max_poly_len);
+        let memory_image = PCS::commit(&read_write_memory_preprocessing.image(), &generators);
 
-        JoltPreprocessing {
+        Ok(JoltPreprocessing {
             generators,
             memory_layout,
             instruction_lookups: instruction_lookups_preprocessing,
             bytecode: bytecode_preprocessing,
             read_write_memory: read_write_memory_preprocessing,
+            memory_image,
             field: small_value_lookup_tables,
+            setup_size: max_poly_len,
+            inputs_digest,
+            bounds,
+        })
+    }
+
+    /// Preprocesses `bytecode` and `memory_init` with the tightest bounds that fit the
+    /// execution `trace` (see `PreprocessingBounds::tight`), rather than fixed ones.
+    /// The PCS setup is then no larger than this execution needs, but proving a longer
+    /// execution, or one that touches more memory, fails with
+    /// `JoltError::BoundExceeded`.
+    fn preprocess_auto(
+        bytecode: Vec<ELFInstruction>,
+        memory_layout: MemoryLayout,
+        memory_init: Vec<(u64, u8)>,
+        trace: &[JoltTraceStep<Self::InstructionSet>],
+    ) -> JoltPreprocessing<C, F, PCS, ProofTranscript> {
+        let bounds = PreprocessingBounds::tight(&bytecode, &memory_layout, trace);
+        Self::preprocess(
+            bytecode,
+            memory_layout,
+            memory_init,
+            bounds.max_bytecode_size,
+            bounds.max_memory_address,
+            bounds.max_trace_length,
+        )
+    }
+
+    /// Preprocesses `bytecode` and `memory_init` with the memory layout and size bounds
+    /// of `config`, which is built for this VM's `C`, `M`, `PCS` and `ProofTranscript`.
+    fn preprocess_with_config(
+        bytecode: Vec<ELFInstruction>,
+        memory_init: Vec<(u64, u8)>,
+        config: &JoltConfig<C, M, F, PCS, ProofTranscript>,
+    ) -> JoltPreprocessing<C, F, PCS, ProofTranscript> {
+        Self::preprocess(
+            bytecode,
+            config.memory_layout.clone(),
+            memory_init,
+            config.max_bytecode_size,
+            config.max_memory_address,
+            config.max_trace_length,
+        )
+    }
+
+    /// A digest of everything that determines the proof system for this Jolt
+    /// instantiation (proof format, PCS, parameters, subtables and R1CS constraints).
+    /// Saved preprocessing is bound to it, so that artifacts generated by a version
+    /// of Jolt with different constraints or subtables are never silently reused.
+    #[tracing::instrument(skip_all, name = "Jolt::protocol_hash")]
+    fn protocol_hash() -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(PROOF_FORMAT_VERSION.to_le_bytes());
+        hasher.update(PCS::protocol_name());
+        hasher.update((C as u64).to_le_bytes());
+        hasher.update((M as u64).to_le_bytes());
+
+        for instruction in Self::InstructionSet::iter() {
+            hasher.update((Self::InstructionSet::enum_index(&instruction) as u64).to_le_bytes());
+            for (subtable, indices) in instruction.subtables::<F>(C, M) {
+                hasher.update((Self::Subtables::enum_index(subtable) as u64).to_le_bytes());
+                for index in indices.iter() {
+                    hasher.update((index as u64).to_le_bytes());
+                }
+            }
         }
+        for subtable in Self::Subtables::iter() {
+            let entries: Vec<u8> = subtable
+                .materialize(M)
+                .into_iter()
+                .flat_map(u32::to_le_bytes)
+                .collect();
+            hasher.update(entries);
+        }
+
+        hasher.update(<Self::Constraints as R1CSConstraints<C, F>>::name());
+        // The memory start address only enters the constraints as a constant, so
+        // any fixed value captures their structure
+        let r1cs_builder = Self::Constraints::construct_constraints(1, RAM_START_ADDRESS);
+        let mut r1cs_bytes = vec![];
+        r1cs_builder
+            .materialize_uniform()
+            .serialize_compressed(&mut r1cs_bytes)
+            .unwrap();
+        r1cs_builder
+            .materialize_offset_eq()
+            .serialize_compressed(&mut r1cs_bytes)
+            .unwrap();
+        hasher.update(r1cs_bytes);
+
+        hasher.finalize().into()
     }
 
-    #[tracing::instrument(skip_all, name = "Jolt::prove")]
+    /// Saves `preprocessing`, bound to `Self::protocol_hash()` and followed by a
+    /// Keccak256 digest of the rest of the artifact, so that `load_preprocessing`
+    /// detects truncated or corrupted files. The PCS setup is saved with
+    /// `CommitmentScheme::save_setup` (e.g. HyperKZG's powers of tau); schemes whose
+    /// setup is cheap to regenerate write nothing. The artifact is streamed to `writer`.
+    #[tracing::instrument(skip_all, name = "Jolt::save_preprocessing")]
+    fn save_preprocessing<W: Write>(
+        preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>,
+        mut writer: W,
+    ) -> Result<(), SerializationError> {
+        writer.write_all(&Self::protocol_hash())?;
+
+        let mut body = HashingWriter::new(&mut writer);
+        (preprocessing.setup_size as u64).serialize_compressed(&mut body)?;
+        body.write_all(&preprocessing.inputs_digest)?;
+        preprocessing.bounds.serialize_compressed(&mut body)?;
+        preprocessing
+            .memory_layout
+            .serialize_compressed(&mut body)?;
+        preprocessing
+            .instruction_lookups
+            .serialize_compressed(&mut body)?;
+        preprocessing.bytecode.serialize_compressed(&mut body)?;
+        preprocessing
+            .read_write_memory
+            .serialize_compressed(&mut body)?;
+        preprocessing.memory_image.serialize_compressed(&mut body)?;
+        preprocessing.field.serialize_compressed(&mut body)?;
+        PCS::save_setup(&preprocessing.generators, &mut body)?;
+        let (digest, _) = body.finalize();
+
+        writer.write_all(&digest)?;
+        Ok(())
+    }
+
+    /// Loads preprocessing saved by `save_preprocessing`, refusing artifacts that
+    /// were generated for a different protocol or whose contents don't match their
+    /// digest. The artifact is deserialized as it is read from `reader`, and its digest
+    /// is checked once it has been read; contents too malformed to deserialize are
+    /// reported as `PreprocessingLoadError::Serialization` instead.
+    #[tracing::instrument(skip_all, name = "Jolt::load_preprocessing")]
+    fn load_preprocessing<R: Read>(
+        mut reader: R,
+    ) -> Result<JoltPreprocessing<C, F, PCS, ProofTranscript>, PreprocessingLoadError> {
+        let mut found = [0u8; 32];
+        reader
+            .read_exact(&mut found)
+            .map_err(SerializationError::from)?;
+        let expected = Self::protocol_hash();
+        if found != expected {
+            return Err(PreprocessingLoadError::ProtocolMismatch {
+                expected: to_hex(&expected),
+                found: to_hex(&found),
+            });
+        }
+
+        let mut body = HashingReader::new(reader);
+        let setup_size = u64::deserialize_compressed(&mut body)? as usize;
+        let mut inputs_digest = [0u8; 32];
+        body.read_exact(&mut inputs_digest)
+            .map_err(SerializationError::from)?;
+        let bounds = PreprocessingBounds::deserialize_compressed(&mut body)?;
+        let memory_layout = MemoryLayout::deserialize_compressed(&mut body)?;
+        let instruction_lookups =
+            InstructionLookupsPreprocessing::deserialize_compressed(&mut body)?;
+        let bytecode = BytecodePreprocessing::deserialize_compressed(&mut body)?;
+        let read_write_memory = ReadWriteMemoryPreprocessing::deserialize_compressed(&mut body)?;
+        let memory_image = PCS::Commitment::deserialize_compressed(&mut body)?;
+        let field = F::SmallValueLookupTables::deserialize_compressed(&mut body)?;
+        let generators = PCS::load_setup(&mut body, setup_size)?;
+        let (body_digest, mut reader) = body.finalize();
+
+        let mut digest = [0u8; 32];
+        reader
+            .read_exact(&mut digest)
+            .map_err(SerializationError::from)?;
+        if body_digest != digest {
+            return Err(PreprocessingLoadError::Corrupted {
+                expected: to_hex(&digest),
+                found: to_hex(&body_digest),
+            });
+        }
+
+        F::initialize_lookup_tables(field.clone());
+        icicle::icicle_init();
+
+        Ok(JoltPreprocessing {
+            generators,
+            memory_layout,
+            instruction_lookups,
+            bytecode,
+            read_write_memory,
+            memory_image,
+            field,
+            setup_size,
+            inputs_digest,
+            bounds,
+        })
+    }
+
+    /// Loads the preprocessing saved at `path` if it was generated by `preprocess`
+    /// with the same arguments (and for the same protocol); otherwise preprocesses and
+    /// saves the result to `path`. Lets services proving the same program many times
+    /// pay for the setup once.
+    #[cfg(feature = "fs")]
+    #[tracing::instrument(skip_all, name = "Jolt::preprocess_or_load")]
+    fn preprocess_or_load<P: AsRef<Path>>(
+        path: P,
+        bytecode: Vec<ELFInstruction>,
+        memory_layout: MemoryLayout,
+        memory_init: Vec<(u64, u8)>,
+        max_bytecode_size: usize,
+        max_memory_address: usize,
+        max_trace_length: usize,
+    ) -> Result<JoltPreprocessing<C, F, PCS, ProofTranscript>, SerializationError> {
+        let path = path.as_ref();
+        let inputs_digest = preprocessing_inputs_digest(
+            &bytecode,
+            &memory_layout,
+            &memory_init,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        );
+        if let Ok(file) = File::open(path) {
+            match Self::load_preprocessing(BufReader::new(file)) {
+                Ok(preprocessing) if preprocessing.inputs_digest == inputs_digest => {
+                    return Ok(preprocessing)
+                }
+                Ok(_) => tracing::info!("{} is for another program; regenerating", path.display()),
+                Err(err) => {
+                    tracing::warn!("failed to load {}: {err}; regenerating", path.display())
+                }
+            }
+        }
+
+        let preprocessing = Self::preprocess(
+            bytecode,
+            memory_layout,
+            memory_init,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        );
+        // Write to a uniquely named file in the same directory and rename it into place,
+        // so that concurrent or interrupted callers never leave a partial artifact behind
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        {
+            let mut writer = BufWriter::new(file.as_file_mut());
+            Self::save_preprocessing(&preprocessing, &mut writer)?;
+            writer.flush()?;
+        }
+        file.persist(path).map_err(|err| err.error)?;
+        Ok(preprocessing)
+    }
+
+    /// Proves the execution given by `trace`, which may be read back from disk (see
+    /// `host::TraceStore`). The trace is collected into memory before witness generation,
+    /// which needs random access to it, so proving needs memory proportional to the trace
+    /// length however the trace is stored. Uses every core and as much memory as it needs; see
+    /// `prove_with_config` to limit either. Panics if the execution cannot be proven; see
+    /// `try_prove`.
     fn prove(
         program_io: JoltDevice,
-        mut trace: Vec<JoltTraceStep<Self::InstructionSet>>,
-        mut preprocessing: JoltPreprocessing<C, F, PCS, ProofTranscript>,
+        trace: impl IntoIterator<Item = JoltTraceStep<Self::InstructionSet>>,
+        preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>,
     ) -> (
         JoltProof<
             C,
@@ -373,172 +1200,438 @@ where
             ProofTranscript,
         >,
         JoltCommitments<PCS, ProofTranscript>,
-        Option<ProverDebugInfo<F, ProofTranscript>>,
+        Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
     ) {
-        icicle::icicle_init();
-        let trace_length = trace.len();
-        let padded_trace_length = trace_length.next_power_of_two();
-        println!("Trace length: {}", trace_length);
-
-        F::initialize_lookup_tables(std::mem::take(&mut preprocessing.field));
-
-        // TODO(moodlezoup): Truncate generators
-
-        // TODO(JP): Drop padding on number of steps
-        JoltTraceStep::pad(&mut trace);
-
-        let mut transcript = ProofTranscript::new(b"Jolt transcript");
-        Self::fiat_shamir_preamble(
-            &mut transcript,
-            &program_io,
-            &program_io.memory_layout,
-            trace_length,
-        );
+        Self::try_prove(program_io, trace, preprocessing).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        let instruction_polynomials =
-            InstructionLookupsProof::<
+    /// Like `prove`, but fails if the program I/O does not fit the memory layout, if the
+    /// trace is longer than `preprocessing` supports, or if the R1CS proof fails.
+    fn try_prove(
+        program_io: JoltDevice,
+        trace: impl IntoIterator<Item = JoltTraceStep<Self::InstructionSet>>,
+        preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>,
+    ) -> Result<
+        (
+            JoltProof<
                 C,
                 M,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
                 F,
                 PCS,
                 Self::InstructionSet,
                 Self::Subtables,
                 ProofTranscript,
-            >::generate_witness(&preprocessing.instruction_lookups, &trace);
-
-        let memory_polynomials = ReadWriteMemoryPolynomials::generate_witness(
-            &program_io,
-            &preprocessing.read_write_memory,
-            &trace,
-        );
+            >,
+            JoltCommitments<PCS, ProofTranscript>,
+            Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+        ),
+        JoltError,
+    > {
+        Self::prove_with_config(
+            program_io,
+            trace,
+            preprocessing,
+            &JoltProverConfig::default(),
+        )
+    }
 
-        let (bytecode_polynomials, range_check_polys) = rayon::join(
-            || {
-                BytecodeProof::<F, PCS, ProofTranscript>::generate_witness(
-                    &preprocessing.bytecode,
-                    &mut trace,
-                )
-            },
-            || {
-                TimestampValidityProof::<F, PCS, ProofTranscript>::generate_witness(
-                    &memory_polynomials,
-                )
-            },
-        );
+    /// Proves the execution given by `trace` like `prove`, in a thread pool of
+    /// `config.num_threads` threads, streaming the output sumcheck as needed to stay within
+    /// `config.max_memory_bytes`, and with the memory-checking
+    /// grand products of `config.grand_product_strategy`, and reporting its progress to
+    /// `config.callback` (see `JoltProverConfig`).
+    #[tracing::instrument(skip_all, name = "Jolt::prove")]
+    fn prove_with_config(
+        program_io: JoltDevice,
+        trace: impl IntoIterator<Item = JoltTraceStep<Self::InstructionSet>>,
+        preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>,
+        config: &JoltProverConfig,
+    ) -> Result<
+        (
+            JoltProof<
+                C,
+                M,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+                PCS,
+                Self::InstructionSet,
+                Self::Subtables,
+                ProofTranscript,
+            >,
+            JoltCommitments<PCS, ProofTranscript>,
+            Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+        ),
+        JoltError,
+    > {
+        icicle::icicle_init();
+        if let Some(message) = program_io_mismatch(&program_io, &program_io.memory_layout) {
+            return Err(JoltError::IoMismatch(message));
+        }
+        let mut trace: Vec<_> = trace.into_iter().collect();
+        preprocessing.bounds.check_execution(
+            preprocessing.bytecode.code_size(),
+            read_write_memory::memory_size(&program_io.memory_layout, &trace),
+            trace.len(),
+        )?;
+        let required = trace.len().next_power_of_two();
+        if required > preprocessing.setup_size {
+            return Err(JoltError::PcsSetupTooSmall {
+                setup_size: preprocessing.setup_size,
+                required,
+            });
+        }
+        config.install_cancellable(move || {
+            let mut progress = ProgressTracker::new(config.callback.as_deref());
+            progress.segment_started(0, 1);
+            let trace_length = trace.len();
+            let padded_trace_length = trace_length.next_power_of_two();
+            println!("Trace length: {}", trace_length);
+
+            F::initialize_lookup_tables(preprocessing.field.clone());
+
+            // TODO(moodlezoup): Truncate generators
+
+            // TODO(JP): Drop padding on number of steps
+            JoltTraceStep::pad(&mut trace);
+
+            let mut transcript = ProofTranscript::new(b"Jolt transcript");
+            Self::fiat_shamir_preamble(
+                &mut transcript,
+                &preprocessing.digest(),
+                &program_io,
+                &program_io.memory_layout,
+                trace_length,
+            );
+
+            // Commit to each part of the witness as soon as it has been generated, so that
+            // the MSMs overlap with the generation of the next part
+            progress.phase_started(ProverPhase::Witness)?;
+            let generators = &preprocessing.generators;
+            let mut jolt_commitments =
+                JoltCommitments::<PCS, ProofTranscript>::initialize(&preprocessing);
+
+            let instruction_polynomials =
+                InstructionLookupsProof::<
+                    C,
+                    M,
+                    F,
+                    PCS,
+                    Self::InstructionSet,
+                    Self::Subtables,
+                    ProofTranscript,
+                >::generate_witness(&preprocessing.instruction_lookups, &trace);
+
+            let (memory_polynomials, _) = rayon::join(
+                || {
+                    ReadWriteMemoryPolynomials::generate_witness(
+                        &program_io,
+                        &preprocessing.read_write_memory,
+                        &trace,
+                    )
+                },
+                || {
+                    commit_witness::<PCS, ProofTranscript, _, _>(
+                        &instruction_polynomials,
+                        &mut jolt_commitments.instruction_lookups,
+                        generators,
+                    )
+                },
+            );
+
+            let ((bytecode_polynomials, range_check_polys), _) = rayon::join(
+                || {
+                    rayon::join(
+                        || {
+                            BytecodeProof::<F, PCS, ProofTranscript>::generate_witness(
+                                &preprocessing.bytecode,
+                                &mut trace,
+                            )
+                        },
+                        || {
+                            TimestampValidityProof::<F, PCS, ProofTranscript>::generate_witness(
+                                &memory_polynomials,
+                            )
+                        },
+                    )
+                },
+                || {
+                    commit_witness::<PCS, ProofTranscript, _, _>(
+                        &memory_polynomials,
+                        &mut jolt_commitments.read_write_memory,
+                        generators,
+                    );
+                    memory_polynomials
+                        .commit_advice(&mut jolt_commitments.read_write_memory, generators);
+                },
+            );
+
+            let r1cs_builder = Self::Constraints::construct_constraints(
+                padded_trace_length,
+                program_io.memory_layout.input_start,
+            );
+            let spartan_key = spartan::UniformSpartanProof::<
+                C,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+                ProofTranscript,
+            >::setup(&r1cs_builder, padded_trace_length);
+
+            let (r1cs_polynomials, _) = rayon::join(
+                || {
+                    R1CSPolynomials::new::<
+                        C,
+                        M,
+                        Self::InstructionSet,
+                        <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                    >(&trace)
+                },
+                || {
+                    rayon::join(
+                        || {
+                            commit_witness::<PCS, ProofTranscript, _, _>(
+                                &bytecode_polynomials,
+                                &mut jolt_commitments.bytecode,
+                                generators,
+                            )
+                        },
+                        || {
+                            commit_witness::<PCS, ProofTranscript, _, _>(
+                                &range_check_polys,
+                                &mut jolt_commitments.timestamp_range_check,
+                                generators,
+                            )
+                        },
+                    )
+                },
+            );
+
+            let mut jolt_polynomials = JoltPolynomials {
+                bytecode: bytecode_polynomials,
+                read_write_memory: memory_polynomials,
+                timestamp_range_check: range_check_polys,
+                instruction_lookups: instruction_polynomials,
+                r1cs: r1cs_polynomials,
+            };
+
+            // The auxiliary R1CS inputs are computed from every other part of the witness
+            r1cs_builder.compute_aux(&mut jolt_polynomials);
+            if config.check_constraints {
+                let flattened_polys: Vec<_> =
+                    <Self::Constraints as R1CSConstraints<C, F>>::Inputs::flatten::<C>()
+                        .iter()
+                        .map(|var| var.get_ref(&jolt_polynomials))
+                        .collect();
+                if let Some(violation) = check_satisfiability(&r1cs_builder, &flattened_polys)
+                    .into_iter()
+                    .next()
+                {
+                    return Err(JoltError::UnsatisfiedConstraint(Box::new(violation)));
+                }
+            }
+            commit_witness::<PCS, ProofTranscript, _, _>(
+                &jolt_polynomials.r1cs,
+                &mut jolt_commitments.r1cs,
+                generators,
+            );
+            progress.phase_finished(ProverPhase::Witness);
+
+            // The output sumcheck binds a copy of v_final, which spans the whole memory;
+            // stream it over as many rounds as the memory budget requires
+            #[cfg(not(target_arch = "wasm32"))]
+            let resident_bytes = memory_stats::memory_stats().map_or(0, |usage| usage.physical_mem);
+            #[cfg(target_arch = "wasm32")]
+            let resident_bytes = 0;
+            let memory_size = jolt_polynomials.read_write_memory.v_final.len();
+            let streaming_rounds = config.streaming_sumcheck_rounds(
+                resident_bytes,
+                memory_size * std::mem::size_of::<F>(),
+                memory_size.log_2(),
+            );
+
+            transcript.append_scalar(&spartan_key.vk_digest);
+
+            jolt_commitments
+                .read_write_values()
+                .iter()
+                .for_each(|value| value.append_to_transcript(&mut transcript));
+            jolt_commitments
+                .init_final_values()
+                .iter()
+                .for_each(|value| value.append_to_transcript(&mut transcript));
+            preprocessing
+                .memory_image
+                .append_to_transcript(&mut transcript);
+            jolt_commitments
+                .read_write_memory
+                .v_advice
+                .append_to_transcript(&mut transcript);
+
+            let mut opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
+                ProverOpeningAccumulator::new();
+
+            progress.phase_started(ProverPhase::Bytecode)?;
+            let bytecode_proof = BytecodeProof::prove_memory_checking(
+                &preprocessing.generators,
+                &preprocessing.bytecode,
+                &jolt_polynomials.bytecode,
+                &jolt_polynomials,
+                config.grand_product_strategy,
+                &mut opening_accumulator,
+                &mut transcript,
+            );
+            progress.phase_finished(ProverPhase::Bytecode);
+
+            progress.phase_started(ProverPhase::InstructionLookups)?;
+            let instruction_proof = InstructionLookupsProof::prove(
+                &preprocessing.generators,
+                &mut jolt_polynomials,
+                &preprocessing.instruction_lookups,
+                &mut opening_accumulator,
+                &mut transcript,
+            );
+            progress.phase_finished(ProverPhase::InstructionLookups);
+
+            progress.phase_started(ProverPhase::ReadWriteMemory)?;
+            let memory_proof = ReadWriteMemoryProof::prove(
+                &preprocessing.generators,
+                &preprocessing.read_write_memory,
+                &jolt_polynomials,
+                &program_io,
+                config.grand_product_strategy,
+                streaming_rounds,
+                &mut opening_accumulator,
+                &mut transcript,
+            );
+            progress.phase_finished(ProverPhase::ReadWriteMemory);
+
+            progress.phase_started(ProverPhase::R1CS)?;
+            let spartan_proof = UniformSpartanProof::<
+                C,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+                ProofTranscript,
+            >::prove::<PCS>(
+                &r1cs_builder,
+                &spartan_key,
+                &jolt_polynomials,
+                &mut opening_accumulator,
+                &mut transcript,
+            )?;
+            progress.phase_finished(ProverPhase::R1CS);
+
+            // Batch-prove all openings
+            progress.phase_started(ProverPhase::Openings)?;
+            #[cfg(test)]
+            let debug_accumulator = opening_accumulator.clone();
+            let opening_proof = opening_accumulator
+                .reduce_and_prove::<PCS>(&preprocessing.generators, &mut transcript);
+            progress.phase_finished(ProverPhase::Openings);
+
+            drop_in_background_thread(jolt_polynomials);
+
+            // The advice tape is committed to; the verifier has no use for its contents
+            let mut program_io = program_io;
+            program_io.redact_advice_tape();
+
+            let jolt_proof = JoltProof {
+                trace_length,
+                program_io,
+                bytecode: bytecode_proof,
+                read_write_memory: memory_proof,
+                instruction_lookups: instruction_proof,
+                r1cs: spartan_proof,
+                opening_proof,
+            };
+
+            #[cfg(test)]
+            let debug_info = Some(ProverDebugInfo {
+                transcript,
+                opening_accumulator: debug_accumulator,
+                pcs_setup: preprocessing.generators.clone(),
+            });
+            #[cfg(not(test))]
+            let debug_info = None;
+            Ok((jolt_proof, jolt_commitments, debug_info))
+        })
+    }
 
-        let r1cs_builder = Self::Constraints::construct_constraints(
-            padded_trace_length,
-            program_io.memory_layout.input_start,
-        );
-        let spartan_key = spartan::UniformSpartanProof::<
+    /// Proves the execution given by `trace` like `prove_with_config`, as a task of
+    /// rayon's global thread pool rather than on the caller's thread, so that async
+    /// services can await the proof without blocking their executor. The returned handle reports the proof's progress
+    /// and can cancel it; `config.callback` is still notified. The task can outlive the
+    /// caller's stack frame, so it holds the preprocessing through an `Arc` rather than a
+    /// borrow; concurrent proofs share one copy.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn prove_async(
+        program_io: JoltDevice,
+        trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+        preprocessing: Arc<JoltPreprocessing<C, F, PCS, ProofTranscript>>,
+        config: JoltProverConfig,
+    ) -> ProvingHandle<(
+        JoltProof<
             C,
+            M,
             <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
             F,
+            PCS,
+            Self::InstructionSet,
+            Self::Subtables,
             ProofTranscript,
-        >::setup(&r1cs_builder, padded_trace_length);
+        >,
+        JoltCommitments<PCS, ProofTranscript>,
+        Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+    )>
+    where
+        Self: Sized + 'static,
+        Self::InstructionSet: 'static,
+    {
+        let callback = config.callback.clone();
+        ProvingHandle::spawn(callback, move |callback| {
+            let config = JoltProverConfig {
+                callback: Some(callback),
+                ..config
+            };
+            Self::prove_with_config(program_io, trace, &preprocessing, &config)
+        })
+    }
 
-        let r1cs_polynomials = R1CSPolynomials::new::<
+    #[tracing::instrument(skip_all)]
+    fn verify(
+        verifier_key: &JoltVerifierKey<C, F, PCS, ProofTranscript>,
+        proof: JoltProof<
             C,
             M,
-            Self::InstructionSet,
-            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
-        >(&trace);
-
-        let mut jolt_polynomials = JoltPolynomials {
-            bytecode: bytecode_polynomials,
-            read_write_memory: memory_polynomials,
-            timestamp_range_check: range_check_polys,
-            instruction_lookups: instruction_polynomials,
-            r1cs: r1cs_polynomials,
-        };
-
-        r1cs_builder.compute_aux(&mut jolt_polynomials);
-
-        let jolt_commitments = jolt_polynomials.commit::<C, PCS, ProofTranscript>(&preprocessing);
-
-        transcript.append_scalar(&spartan_key.vk_digest);
-
-        jolt_commitments
-            .read_write_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-        jolt_commitments
-            .init_final_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-
-        let mut opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
-            ProverOpeningAccumulator::new();
-
-        let bytecode_proof = BytecodeProof::prove_memory_checking(
-            &preprocessing.generators,
-            &preprocessing.bytecode,
-            &jolt_polynomials.bytecode,
-            &jolt_polynomials,
-            &mut opening_accumulator,
-            &mut transcript,
-        );
-
-        let instruction_proof = InstructionLookupsProof::prove(
-            &preprocessing.generators,
-            &mut jolt_polynomials,
-            &preprocessing.instruction_lookups,
-            &mut opening_accumulator,
-            &mut transcript,
-        );
-
-        let memory_proof = ReadWriteMemoryProof::prove(
-            &preprocessing.generators,
-            &preprocessing.read_write_memory,
-            &jolt_polynomials,
-            &program_io,
-            &mut opening_accumulator,
-            &mut transcript,
-        );
-
-        let spartan_proof = UniformSpartanProof::<
-            C,
             <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
             F,
+            PCS,
+            Self::InstructionSet,
+            Self::Subtables,
             ProofTranscript,
-        >::prove::<PCS>(
-            &r1cs_builder,
-            &spartan_key,
-            &jolt_polynomials,
-            &mut opening_accumulator,
-            &mut transcript,
-        )
-        .expect("r1cs proof failed");
-
-        // Batch-prove all openings
-        let opening_proof =
-            opening_accumulator.reduce_and_prove::<PCS>(&preprocessing.generators, &mut transcript);
-
-        drop_in_background_thread(jolt_polynomials);
-
-        let jolt_proof = JoltProof {
-            trace_length,
-            program_io,
-            bytecode: bytecode_proof,
-            read_write_memory: memory_proof,
-            instruction_lookups: instruction_proof,
-            r1cs: spartan_proof,
-            opening_proof,
-        };
-
-        #[cfg(test)]
-        let debug_info = Some(ProverDebugInfo {
-            transcript,
-            opening_accumulator,
-        });
-        #[cfg(not(test))]
-        let debug_info = None;
-        (jolt_proof, jolt_commitments, debug_info)
+        >,
+        commitments: JoltCommitments<PCS, ProofTranscript>,
+        _debug_info: Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+    ) -> Result<(), ProofVerifyError>
+    where
+        Self: Sized,
+    {
+        IncrementalVerifier::<Self, F, PCS, C, M, ProofTranscript>::new(
+            verifier_key,
+            proof,
+            commitments,
+            _debug_info,
+        )?
+        .finish()
     }
 
+    /// Verifies as many components of `proof` as fit in `duration`. The returned
+    /// verifier reports which components were verified and which remain (see
+    /// `VerificationProgress` for what a partial result does and does not
+    /// establish), and can complete verification later with `finish`.
+    #[cfg(not(target_arch = "wasm32"))]
     #[tracing::instrument(skip_all)]
-    fn verify(
-        mut preprocessing: JoltPreprocessing<C, F, PCS, ProofTranscript>,
+    fn verify_with_deadline(
+        verifier_key: &JoltVerifierKey<C, F, PCS, ProofTranscript>,
         proof: JoltProof<
             C,
             M,
@@ -550,98 +1643,19 @@ where
             ProofTranscript,
         >,
         commitments: JoltCommitments<PCS, ProofTranscript>,
-        _debug_info: Option<ProverDebugInfo<F, ProofTranscript>>,
-    ) -> Result<(), ProofVerifyError> {
-        let mut transcript = ProofTranscript::new(b"Jolt transcript");
-        let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS, ProofTranscript> =
-            VerifierOpeningAccumulator::new();
-
-        #[cfg(test)]
-        if let Some(debug_info) = _debug_info {
-            transcript.compare_to(debug_info.transcript);
-            opening_accumulator
-                .compare_to(debug_info.opening_accumulator, &preprocessing.generators);
-        }
-        Self::fiat_shamir_preamble(
-            &mut transcript,
-            &proof.program_io,
-            &preprocessing.memory_layout,
-            proof.trace_length,
-        );
-
-        // Regenerate the uniform Spartan key
-        let padded_trace_length = proof.trace_length.next_power_of_two();
-        let memory_start = preprocessing.memory_layout.input_start;
-        let r1cs_builder =
-            Self::Constraints::construct_constraints(padded_trace_length, memory_start);
-        let spartan_key = spartan::UniformSpartanProof::<C, _, F, ProofTranscript>::setup(
-            &r1cs_builder,
-            padded_trace_length,
-        );
-        transcript.append_scalar(&spartan_key.vk_digest);
-
-        let r1cs_proof = R1CSProof {
-            key: spartan_key,
-            proof: proof.r1cs,
-            _marker: PhantomData,
-        };
-
-        commitments
-            .read_write_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-        commitments
-            .init_final_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-
-        Self::verify_bytecode(
-            &preprocessing.bytecode,
-            &preprocessing.generators,
-            proof.bytecode,
-            &commitments,
-            &mut opening_accumulator,
-            &mut transcript,
-        )?;
-        Self::verify_instruction_lookups(
-            &preprocessing.instruction_lookups,
-            &preprocessing.generators,
-            proof.instruction_lookups,
-            &commitments,
-            &mut opening_accumulator,
-            &mut transcript,
-        )?;
-        Self::verify_memory(
-            &mut preprocessing.read_write_memory,
-            &preprocessing.generators,
-            &preprocessing.memory_layout,
-            proof.read_write_memory,
-            &commitments,
-            proof.program_io,
-            &mut opening_accumulator,
-            &mut transcript,
-        )?;
-        Self::verify_r1cs(
-            r1cs_proof,
-            &commitments,
-            &mut opening_accumulator,
-            &mut transcript,
-        )?;
-
-        // Batch-verify all openings
-        opening_accumulator.reduce_and_verify(
-            &preprocessing.generators,
-            &proof.opening_proof,
-            &mut transcript,
-        )?;
-
-        Ok(())
+        duration: Duration,
+    ) -> Result<IncrementalVerifier<'_, Self, F, PCS, C, M, ProofTranscript>, ProofVerifyError>
+    where
+        Self: Sized,
+    {
+        let deadline = Instant::now() + duration;
+        IncrementalVerifier::new(verifier_key, proof, commitments, None)?.verify_until(deadline)
     }
 
     #[tracing::instrument(skip_all)]
     fn verify_instruction_lookups<'a>(
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         proof: InstructionLookupsProof<
             C,
             M,
@@ -668,7 +1682,7 @@ where
     #[tracing::instrument(skip_all)]
     fn verify_bytecode<'a>(
         preprocessing: &BytecodePreprocessing<F>,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         proof: BytecodeProof<F, PCS, ProofTranscript>,
         commitments: &'a JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
@@ -680,6 +1694,7 @@ where
             proof,
             &commitments.bytecode,
             commitments,
+            &[],
             opening_accumulator,
             transcript,
         )
@@ -689,20 +1704,28 @@ where
     #[tracing::instrument(skip_all)]
     fn verify_memory<'a>(
         preprocessing: &mut ReadWriteMemoryPreprocessing,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         memory_layout: &MemoryLayout,
         proof: ReadWriteMemoryProof<F, PCS, ProofTranscript>,
         commitment: &'a JoltCommitments<PCS, ProofTranscript>,
+        memory_image: &PCS::Commitment,
         program_io: JoltDevice,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        assert!(program_io.inputs.len() <= memory_layout.max_input_size as usize);
-        assert!(program_io.outputs.len() <= memory_layout.max_output_size as usize);
+        if let Some(message) = program_io_mismatch(&program_io, memory_layout) {
+            return Err(ProofVerifyError::IoMismatch(message));
+        }
         // pair the memory layout with the program io from the proof
         preprocessing.program_io = Some(JoltDevice {
             inputs: program_io.inputs,
             outputs: program_io.outputs,
+            logs: program_io.logs,
+            input_stream: program_io.input_stream,
+            advice_tape: vec![],
+            journal: program_io.journal,
+            journal_digest: program_io.journal_digest,
+            nondeterminism_digest: program_io.nondeterminism_digest,
             panic: program_io.panic,
             memory_layout: memory_layout.clone(),
         });
@@ -712,6 +1735,7 @@ where
             generators,
             preprocessing,
             commitment,
+            memory_image,
             opening_accumulator,
             transcript,
         )
@@ -736,25 +1760,44 @@ where
 
     fn fiat_shamir_preamble(
         transcript: &mut ProofTranscript,
+        bytecode_digest: &[u8; 32],
         program_io: &JoltDevice,
         memory_layout: &MemoryLayout,
         trace_length: usize,
     ) {
+        transcript.append_message(PROTOCOL_VERSION);
+        transcript.append_bytes(bytecode_digest);
+        let mut memory_layout_bytes = vec![];
+        memory_layout
+            .serialize_compressed(&mut memory_layout_bytes)
+            .unwrap();
+        transcript.append_bytes(&memory_layout_bytes);
         transcript.append_u64(trace_length as u64);
         transcript.append_u64(C as u64);
         transcript.append_u64(M as u64);
         transcript.append_u64(Self::InstructionSet::COUNT as u64);
         transcript.append_u64(Self::Subtables::COUNT as u64);
-        transcript.append_u64(memory_layout.max_input_size);
-        transcript.append_u64(memory_layout.max_output_size);
         transcript.append_bytes(&program_io.inputs);
         transcript.append_bytes(&program_io.outputs);
+        transcript.append_bytes(&program_io.logs);
+        transcript.append_bytes(&program_io.input_stream);
+        // The journal entries are bound by their digest, and may be redacted
+        transcript.append_bytes(&program_io.journal_digest);
+        transcript.append_bytes(&program_io.nondeterminism_digest);
         transcript.append_u64(program_io.panic as u64);
     }
 }
 
+pub mod archive;
 pub mod bytecode;
+pub mod config;
+pub mod incremental;
 pub mod instruction_lookups;
+pub mod progress;
+pub mod prover_config;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod proving_handle;
 pub mod read_write_memory;
 pub mod rv32i_vm;
+pub mod size_report;
 pub mod timestamp_range_check;
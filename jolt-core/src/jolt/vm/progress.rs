@@ -0,0 +1,218 @@
+//! Progress reporting for long-running proofs, so that callers can drive progress bars
+//! and health checks (see `JoltProverConfig::with_callback`).
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use crate::utils::errors::JoltError;
+
+/// A phase of `Jolt::prove_with_config`. Phases run in the order of `ALL`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProverPhase {
+    /// Generating the witness polynomials and committing to them, which overlap.
+    Witness,
+    Bytecode,
+    InstructionLookups,
+    ReadWriteMemory,
+    R1CS,
+    /// Batch-proving the polynomial openings claimed by all other phases.
+    Openings,
+}
+
+impl ProverPhase {
+    pub const ALL: [ProverPhase; 6] = [
+        ProverPhase::Witness,
+        ProverPhase::Bytecode,
+        ProverPhase::InstructionLookups,
+        ProverPhase::ReadWriteMemory,
+        ProverPhase::R1CS,
+        ProverPhase::Openings,
+    ];
+
+    /// A rough share of the proving time spent in this phase, in percent. Only used to
+    /// report progress, so it need not be accurate for every guest.
+    fn weight(&self) -> f64 {
+        match self {
+            ProverPhase::Witness => 35.0,
+            ProverPhase::Bytecode => 10.0,
+            ProverPhase::InstructionLookups => 25.0,
+            ProverPhase::ReadWriteMemory => 10.0,
+            ProverPhase::R1CS => 10.0,
+            ProverPhase::Openings => 10.0,
+        }
+    }
+}
+
+/// Receives the prover's progress. Every method defaults to doing nothing, and is
+/// called on the thread driving the proof, so should return quickly.
+pub trait ProverCallback: Send + Sync {
+    /// Called when the prover starts proving segment `index` of `count`. A proof without
+    /// continuations is a single segment.
+    fn segment_started(&self, _index: usize, _count: usize) {}
+
+    fn phase_started(&self, _phase: ProverPhase) {}
+
+    fn phase_finished(&self, _phase: ProverPhase) {}
+
+    /// Called after every phase with the approximate share of the proof completed so
+    /// far, from 0 to 100, weighing each phase by its typical share of the proving time.
+    fn progress(&self, _percent: f64) {}
+
+    /// Polled by the prover before every phase and between the rounds of its sumchecks;
+    /// once it returns `true`, the prover stops and fails with `JoltError::Cancelled`.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+thread_local! {
+    /// The callback of the proof this thread is driving, polled by `check_cancelled`.
+    static CALLBACK: RefCell<Option<Arc<dyn ProverCallback>>> = const { RefCell::new(None) };
+}
+
+/// The payload `check_cancelled` unwinds with, so that the prover's loops need not
+/// return errors.
+struct Cancelled;
+
+/// Runs the proof `op` on this thread, with `check_cancelled` polling `callback`. Fails
+/// with `JoltError::Cancelled` if `op` was cancelled mid-phase.
+pub(crate) fn cancellable<R>(
+    callback: Option<Arc<dyn ProverCallback>>,
+    op: impl FnOnce() -> Result<R, JoltError>,
+) -> Result<R, JoltError> {
+    let previous = CALLBACK.replace(callback);
+    let result = panic::catch_unwind(AssertUnwindSafe(op));
+    CALLBACK.set(previous);
+    match result {
+        Ok(result) => result,
+        Err(payload) if payload.is::<Cancelled>() => Err(JoltError::Cancelled),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Stops the proof this thread is driving (see `cancellable`) if its callback has
+/// cancelled it. Called between sumcheck rounds, so that a cancelled proof need not
+/// finish its current phase.
+pub(crate) fn check_cancelled() {
+    let cancelled = CALLBACK.with_borrow(|callback| {
+        callback
+            .as_ref()
+            .is_some_and(|callback| callback.is_cancelled())
+    });
+    if cancelled {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+/// Reports the phases of one proof to an optional `ProverCallback`.
+pub(crate) struct ProgressTracker<'a> {
+    callback: Option<&'a dyn ProverCallback>,
+    percent: f64,
+}
+
+impl<'a> ProgressTracker<'a> {
+    pub(crate) fn new(callback: Option<&'a dyn ProverCallback>) -> Self {
+        Self {
+            callback,
+            percent: 0.0,
+        }
+    }
+
+    pub(crate) fn segment_started(&self, index: usize, count: usize) {
+        if let Some(callback) = self.callback {
+            callback.segment_started(index, count);
+            callback.progress(self.percent);
+        }
+    }
+
+    /// Fails if the callback has cancelled the proof.
+    pub(crate) fn phase_started(&self, phase: ProverPhase) -> Result<(), JoltError> {
+        if let Some(callback) = self.callback {
+            if callback.is_cancelled() {
+                return Err(JoltError::Cancelled);
+            }
+            callback.phase_started(phase);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn phase_finished(&mut self, phase: ProverPhase) {
+        self.percent = (self.percent + phase.weight()).min(100.0);
+        if let Some(callback) = self.callback {
+            callback.phase_finished(phase);
+            callback.progress(self.percent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        phases: Mutex<Vec<ProverPhase>>,
+        percents: Mutex<Vec<f64>>,
+    }
+
+    impl ProverCallback for Recorder {
+        fn phase_finished(&self, phase: ProverPhase) {
+            self.phases.lock().unwrap().push(phase);
+        }
+
+        fn progress(&self, percent: f64) {
+            self.percents.lock().unwrap().push(percent);
+        }
+    }
+
+    #[test]
+    fn weights_sum_to_100() {
+        let total: f64 = ProverPhase::ALL.iter().map(ProverPhase::weight).sum();
+        assert_eq!(total, 100.0);
+    }
+
+    #[test]
+    fn reports_monotonic_progress() {
+        let recorder = Recorder::default();
+        let mut tracker = ProgressTracker::new(Some(&recorder));
+        tracker.segment_started(0, 1);
+        for phase in ProverPhase::ALL {
+            tracker.phase_started(phase).unwrap();
+            tracker.phase_finished(phase);
+        }
+
+        assert_eq!(*recorder.phases.lock().unwrap(), ProverPhase::ALL);
+        let percents = recorder.percents.lock().unwrap();
+        assert_eq!(percents.first(), Some(&0.0));
+        assert_eq!(percents.last(), Some(&100.0));
+        assert!(percents.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    struct CancelAfter(AtomicUsize);
+
+    impl ProverCallback for CancelAfter {
+        fn is_cancelled(&self) -> bool {
+            self.0.fetch_sub(1, Ordering::Relaxed) == 0
+        }
+    }
+
+    #[test]
+    fn cancel_mid_phase() {
+        let mut rounds = 0;
+        let result = cancellable(Some(Arc::new(CancelAfter(AtomicUsize::new(3)))), || {
+            for _ in 0..10 {
+                check_cancelled();
+                rounds += 1;
+            }
+            Ok(())
+        });
+        assert!(matches!(result, Err(JoltError::Cancelled)));
+        assert_eq!(rounds, 3);
+
+        // Outside of a proof, nothing is polled
+        check_cancelled();
+    }
+}
@@ -0,0 +1,215 @@
+//! Limits on the threads and memory the prover may use, the choice of arguments it
+//! proves with, and where it reports its progress (see `Jolt::prove_with_config`).
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::progress::{self, ProverCallback};
+use crate::msm::scheduler::{set_gpu_assignment, GpuAssignment, GpuPartition};
+use crate::subprotocols::grand_product::GrandProductStrategy;
+use crate::utils::errors::JoltError;
+
+/// The threads, GPUs and memory `Jolt::prove_with_config` may use, and how it proves.
+#[derive(Clone, Default)]
+pub struct JoltProverConfig {
+    /// Number of threads of the rayon pool the prover runs in; `None` uses one thread per
+    /// core. The prover's parallel loops split their work into chunks according to the size
+    /// of the pool they run in.
+    pub num_threads: Option<usize>,
+    /// With the `icicle` feature, the CUDA devices the large MSMs of each batch of
+    /// commitments are spread across, while the smaller ones run on the CPU at the same
+    /// time. Devices that were not detected are ignored; `None` uses every detected device,
+    /// and `Some(vec![])` keeps every MSM on the CPU.
+    pub gpus: Option<Vec<usize>>,
+    pub gpu_partition: GpuPartition,
+    /// Approximate bound on the prover's resident memory; `None` leaves it unbounded.
+    /// Once the witness has been committed to, the output sumcheck streams over as many
+    /// rounds as needed for the copy of the final memory state it binds to fit in what
+    /// remains. The witness itself is not bounded.
+    pub max_memory_bytes: Option<usize>,
+    /// Number of rounds the output sumcheck streams over (see
+    /// `SumcheckInstanceProof::prove_arbitrary_streaming`), overriding the number chosen
+    /// for `max_memory_bytes`. Each streamed round costs an extra pass over the final
+    /// memory state; the proof is the same either way.
+    pub sumcheck_streaming_rounds: Option<usize>,
+    /// The argument for the grand products of the read and write multisets of memory
+    /// checking, which grow with the trace: GKR is cheaper for short traces, while the
+    /// Quarks hybrid keeps the proof and the verifier's work small for very long ones.
+    /// Either way, the verifier accepts proofs of both.
+    pub grand_product_strategy: GrandProductStrategy,
+    /// Whether to check that the witness satisfies the R1CS constraints before proving, so
+    /// that a faulty trace fails with the unsatisfied constraint and step rather than with
+    /// an opaque Spartan error. Costs a pass over the constraints at every step.
+    pub check_constraints: bool,
+    /// Receives the prover's progress (phases started and finished, and the share of the
+    /// proof completed), so that long proofs can drive progress bars and health checks.
+    pub callback: Option<Arc<dyn ProverCallback>>,
+}
+
+impl fmt::Debug for JoltProverConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoltProverConfig")
+            .field("num_threads", &self.num_threads)
+            .field("gpus", &self.gpus)
+            .field("gpu_partition", &self.gpu_partition)
+            .field("max_memory_bytes", &self.max_memory_bytes)
+            .field("sumcheck_streaming_rounds", &self.sumcheck_streaming_rounds)
+            .field("grand_product_strategy", &self.grand_product_strategy)
+            .field("check_constraints", &self.check_constraints)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+// Configs are equal if they share the same callback, if any
+impl PartialEq for JoltProverConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_threads == other.num_threads
+            && self.gpus == other.gpus
+            && self.gpu_partition == other.gpu_partition
+            && self.max_memory_bytes == other.max_memory_bytes
+            && self.sumcheck_streaming_rounds == other.sumcheck_streaming_rounds
+            && self.grand_product_strategy == other.grand_product_strategy
+            && self.check_constraints == other.check_constraints
+            && match (&self.callback, &other.callback) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl Eq for JoltProverConfig {}
+
+impl JoltProverConfig {
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    pub fn with_gpus(mut self, gpus: impl IntoIterator<Item = usize>) -> Self {
+        self.gpus = Some(gpus.into_iter().collect());
+        self
+    }
+
+    pub fn with_gpu_partition(mut self, gpu_partition: GpuPartition) -> Self {
+        self.gpu_partition = gpu_partition;
+        self
+    }
+
+    pub fn with_max_memory_bytes(mut self, max_memory_bytes: usize) -> Self {
+        self.max_memory_bytes = Some(max_memory_bytes);
+        self
+    }
+
+    pub fn with_sumcheck_streaming_rounds(mut self, rounds: usize) -> Self {
+        self.sumcheck_streaming_rounds = Some(rounds);
+        self
+    }
+
+    pub fn with_grand_product_strategy(mut self, strategy: GrandProductStrategy) -> Self {
+        self.grand_product_strategy = strategy;
+        self
+    }
+
+    pub fn with_constraint_check(mut self) -> Self {
+        self.check_constraints = true;
+        self
+    }
+
+    pub fn with_callback(mut self, callback: Arc<dyn ProverCallback>) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    /// Runs `op` in a thread pool of `num_threads` threads, whose MSMs use `gpus` as laid
+    /// out by `gpu_partition`. Runs `op` in the current pool if neither is set.
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        let assignment = GpuAssignment {
+            gpus: self.gpus.clone(),
+            partition: self.gpu_partition,
+        };
+        if self.num_threads.is_none() && assignment == GpuAssignment::default() {
+            return op();
+        }
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .start_handler(move |_| set_gpu_assignment(assignment.clone()));
+        if let Some(num_threads) = self.num_threads {
+            builder = builder.num_threads(num_threads);
+        }
+        builder
+            .build()
+            .expect("failed to build the prover's thread pool")
+            .install(op)
+    }
+
+    /// Runs the proof `op` like `install`, stopping it with `JoltError::Cancelled` as soon
+    /// as `callback` cancels it, even mid-phase.
+    pub fn install_cancellable<R: Send>(
+        &self,
+        op: impl FnOnce() -> Result<R, JoltError> + Send,
+    ) -> Result<R, JoltError> {
+        self.install(|| progress::cancellable(self.callback.clone(), op))
+    }
+
+    /// The number of rounds (at most `max_rounds`) a memory-frugal sumcheck should stream
+    /// over: `sumcheck_streaming_rounds` if set, and otherwise enough for the copies it
+    /// binds, `sumcheck_bytes` without streaming and halving with every streamed round, to
+    /// fit in `max_memory_bytes` alongside `resident_bytes`. Returns 0 if neither is set.
+    pub fn streaming_sumcheck_rounds(
+        &self,
+        resident_bytes: usize,
+        sumcheck_bytes: usize,
+        max_rounds: usize,
+    ) -> usize {
+        if let Some(rounds) = self.sumcheck_streaming_rounds {
+            return rounds.min(max_rounds);
+        }
+        let Some(max_memory_bytes) = self.max_memory_bytes else {
+            return 0;
+        };
+        let available = max_memory_bytes.saturating_sub(resident_bytes);
+        (0..max_rounds)
+            .find(|&rounds| sumcheck_bytes >> rounds <= available)
+            .unwrap_or(max_rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msm::scheduler::{active_gpus, gpu_partition};
+
+    #[test]
+    fn streaming_rounds_within_budget() {
+        let config = JoltProverConfig::default();
+        assert_eq!(config.streaming_sumcheck_rounds(1 << 20, 1 << 30, 20), 0);
+
+        let config = config.with_max_memory_bytes(1 << 21);
+        assert_eq!(config.streaming_sumcheck_rounds(1 << 20, 1 << 19, 20), 0);
+        assert_eq!(config.streaming_sumcheck_rounds(1 << 20, 1 << 22, 20), 2);
+        // The copies cannot shrink past `max_rounds` streamed rounds
+        assert_eq!(config.streaming_sumcheck_rounds(1 << 21, 1 << 22, 4), 4);
+
+        // An explicit number of rounds overrides the budget
+        let config = config.with_sumcheck_streaming_rounds(3);
+        assert_eq!(config.streaming_sumcheck_rounds(1 << 21, 1 << 22, 20), 3);
+        assert_eq!(config.streaming_sumcheck_rounds(1 << 21, 1 << 22, 2), 2);
+    }
+
+    #[test]
+    fn install_thread_pool() {
+        let config = JoltProverConfig::default().with_num_threads(3);
+        assert_eq!(config.install(rayon::current_num_threads), 3);
+
+        // The GPU assignment only applies to the threads of the prover's pool
+        let config = config
+            .with_gpus([])
+            .with_gpu_partition(GpuPartition::PerSegment);
+        assert_eq!(
+            config.install(|| (active_gpus(), gpu_partition())),
+            (vec![], GpuPartition::PerSegment)
+        );
+        assert_eq!(gpu_partition(), GpuPartition::PerPolynomial);
+    }
+}
@@ -0,0 +1,216 @@
+//! Proving in the background for async services (see `Jolt::prove_async`).
+//!
+//! The prover runs as a task of rayon's global thread pool, which its parallel loops
+//! share (unless its `JoltProverConfig` asks for a pool of its own), rather than on the
+//! caller's executor. The returned `ProvingHandle` is a future that resolves to the
+//! proof, and does not depend on any particular async runtime.
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use super::progress::{ProverCallback, ProverPhase};
+use crate::utils::errors::JoltError;
+
+/// Records the progress of a background proof for its `ProvingHandle`, and forwards it
+/// to the caller's own callback, if any.
+struct HandleProgress {
+    /// The last reported percentage, as the bits of an `f64`.
+    percent: AtomicU64,
+    cancelled: AtomicBool,
+    inner: Option<Arc<dyn ProverCallback>>,
+}
+
+impl ProverCallback for HandleProgress {
+    fn segment_started(&self, index: usize, count: usize) {
+        if let Some(inner) = &self.inner {
+            inner.segment_started(index, count);
+        }
+    }
+
+    fn phase_started(&self, phase: ProverPhase) {
+        if let Some(inner) = &self.inner {
+            inner.phase_started(phase);
+        }
+    }
+
+    fn phase_finished(&self, phase: ProverPhase) {
+        if let Some(inner) = &self.inner {
+            inner.phase_finished(phase);
+        }
+    }
+
+    fn progress(&self, percent: f64) {
+        self.percent.store(percent.to_bits(), Ordering::Relaxed);
+        if let Some(inner) = &self.inner {
+            inner.progress(percent);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .inner
+                .as_ref()
+                .is_some_and(|inner| inner.is_cancelled())
+    }
+}
+
+struct Completion<T> {
+    result: Option<Result<T, JoltError>>,
+    waker: Option<Waker>,
+}
+
+/// A proof running in the background, returned by `Jolt::prove_async`. Resolves to the
+/// result of `Jolt::prove_with_config` when awaited. Dropping the handle cancels the proof.
+pub struct ProvingHandle<T> {
+    completion: Arc<Mutex<Completion<T>>>,
+    progress: Arc<HandleProgress>,
+}
+
+impl<T: Send + 'static> ProvingHandle<T> {
+    /// Queues `prove` on rayon's global thread pool, passing it the callback to prove
+    /// with, which reports to the handle and to `callback`.
+    pub(crate) fn spawn(
+        callback: Option<Arc<dyn ProverCallback>>,
+        prove: impl FnOnce(Arc<dyn ProverCallback>) -> Result<T, JoltError> + Send + 'static,
+    ) -> Self {
+        let completion = Arc::new(Mutex::new(Completion {
+            result: None,
+            waker: None,
+        }));
+        let progress = Arc::new(HandleProgress {
+            percent: AtomicU64::new(0f64.to_bits()),
+            cancelled: AtomicBool::new(false),
+            inner: callback,
+        });
+
+        let prover_completion = completion.clone();
+        let prover_callback: Arc<dyn ProverCallback> = progress.clone();
+        rayon::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| prove(prover_callback)))
+                .unwrap_or_else(|payload| {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    Err(JoltError::Panicked(message))
+                });
+            let mut completion = prover_completion.lock().unwrap();
+            completion.result = Some(result);
+            if let Some(waker) = completion.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Self {
+            completion,
+            progress,
+        }
+    }
+}
+
+impl<T> ProvingHandle<T> {
+    /// The approximate share of the proof completed so far, from 0 to 100 (see
+    /// `ProverCallback::progress`).
+    pub fn progress(&self) -> f64 {
+        f64::from_bits(self.progress.percent.load(Ordering::Relaxed))
+    }
+
+    /// Asks the prover to stop. It stops before its next phase or sumcheck round, and the
+    /// handle then resolves to `JoltError::Cancelled`.
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the proof has finished (or failed), so that awaiting the handle will not
+    /// wait.
+    pub fn is_finished(&self) -> bool {
+        self.completion.lock().unwrap().result.is_some()
+    }
+}
+
+impl<T> Future for ProvingHandle<T> {
+    type Output = Result<T, JoltError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut completion = self.completion.lock().unwrap();
+        match completion.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                completion.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for ProvingHandle<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::task::Wake;
+    use std::thread;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn resolves_to_result() {
+        let handle = ProvingHandle::spawn(None, |callback| {
+            callback.progress(100.0);
+            Ok(42)
+        });
+        assert_eq!(block_on(handle).unwrap(), 42);
+    }
+
+    #[test]
+    fn cancel() {
+        let (started, wait_for_start) = mpsc::channel();
+        let handle = ProvingHandle::spawn(None, move |callback| {
+            started.send(()).unwrap();
+            while !callback.is_cancelled() {
+                thread::yield_now();
+            }
+            Err::<(), _>(JoltError::Cancelled)
+        });
+        wait_for_start.recv().unwrap();
+        handle.cancel();
+        assert!(matches!(block_on(handle), Err(JoltError::Cancelled)));
+    }
+
+    #[test]
+    fn panic_is_an_error() {
+        let handle = ProvingHandle::<()>::spawn(None, |_| panic!("out of memory"));
+        match block_on(handle) {
+            Err(JoltError::Panicked(message)) => assert_eq!(message, "out of memory"),
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+    }
+}
@@ -1,15 +1,18 @@
 use crate::field::JoltField;
 use crate::jolt::instruction::JoltInstructionSet;
 use crate::lasso::memory_checking::{
-    ExogenousOpenings, Initializable, StructuredPolynomialData, VerifierComputedOpening,
+    ExogenousOpenings, Initializable, PreprocessedOpening, StructuredPolynomialData,
+    VerifierComputedOpening,
 };
 use crate::poly::compact_polynomial::{CompactPolynomial, SmallScalar};
 use crate::poly::multilinear_polynomial::{MultilinearPolynomial, PolynomialEvaluation};
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::subprotocols::grand_product::GrandProductStrategy;
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use rayon::prelude::*;
 #[cfg(test)]
 use std::collections::HashSet;
+use std::io::{Read, Write};
 use std::marker::PhantomData;
 
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
@@ -24,7 +27,9 @@ use crate::{
     subprotocols::sumcheck::SumcheckInstanceProof,
     utils::{errors::ProofVerifyError, math::Math},
 };
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
 use common::constants::{
     BYTES_PER_INSTRUCTION, MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT,
 };
@@ -33,10 +38,18 @@ use common::rv_trace::{JoltDevice, MemoryLayout, MemoryOp};
 use super::{timestamp_range_check::TimestampValidityProof, JoltCommitments};
 use super::{JoltPolynomials, JoltStuff, JoltTraceStep};
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemoryPreprocessing {
     min_bytecode_address: u64,
+    /// The initial memory image, as words. Only the prover needs these: the verifier
+    /// checks `v_init` against the commitment to `image` instead (see
+    /// `JoltPreprocessing::memory_image`), so they are dropped from the verifier key.
     bytecode_words: Vec<u32>,
+    /// Witness index of the first word of the memory image.
+    image_start: usize,
+    /// Number of variables of `image`, whose coefficients cover the smallest aligned
+    /// block of `2^image_num_vars` witness indices containing the memory image.
+    pub(crate) image_num_vars: usize,
     // HACK: The verifier will populate this field by copying inputs/outputs from the
     // `ReadWriteMemoryProof` and the memory layout from preprocessing.
     // Having `program_io` in this preprocessing struct allows the verifier to access it
@@ -47,7 +60,7 @@ pub struct ReadWriteMemoryPreprocessing {
 
 impl ReadWriteMemoryPreprocessing {
     #[tracing::instrument(skip_all, name = "ReadWriteMemoryPreprocessing::preprocess")]
-    pub fn preprocess(memory_init: Vec<(u64, u8)>) -> Self {
+    pub fn preprocess(memory_init: Vec<(u64, u8)>, memory_layout: &MemoryLayout) -> Self {
         let min_bytecode_address = memory_init
             .iter()
             .map(|(address, _)| *address)
@@ -76,12 +89,55 @@ impl ReadWriteMemoryPreprocessing {
             bytecode_words[remapped_index] = word;
         }
 
+        let image_start = if memory_init.is_empty() {
+            0
+        } else {
+            memory_address_to_witness_index(min_bytecode_address, memory_layout)
+        };
+        // The image must lie within a single aligned block, so that the block's index
+        // alone (i.e. the high-order bits of its witness indices) selects it
+        let image_end = image_start + bytecode_words.len();
+        let mut image_num_vars = bytecode_words.len().next_power_of_two().log_2();
+        while image_start >> image_num_vars != (image_end - 1) >> image_num_vars {
+            image_num_vars += 1;
+        }
+
         Self {
             min_bytecode_address,
             bytecode_words,
+            image_start,
+            image_num_vars,
             program_io: None,
         }
     }
+
+    /// The initial memory image as a polynomial over the aligned block of
+    /// `2^image_num_vars` witness indices containing it; zero outside of the image.
+    /// Committed to during preprocessing.
+    pub fn image<F: JoltField>(&self) -> MultilinearPolynomial<F> {
+        let mut image = vec![0u32; self.image_num_vars.pow2()];
+        let offset = self.image_start % self.image_num_vars.pow2();
+        image[offset..offset + self.bytecode_words.len()].copy_from_slice(&self.bytecode_words);
+        MultilinearPolynomial::from(image)
+    }
+
+    /// The index of the aligned block of `2^image_num_vars` witness indices containing
+    /// the memory image.
+    fn image_block(&self) -> usize {
+        self.image_start >> self.image_num_vars
+    }
+
+    /// The part of this preprocessing needed by the verifier, which has no use for the
+    /// memory image itself.
+    pub fn verifier_preprocessing(&self) -> Self {
+        Self {
+            min_bytecode_address: self.min_bytecode_address,
+            bytecode_words: vec![],
+            image_start: self.image_start,
+            image_num_vars: self.image_num_vars,
+            program_io: self.program_io.clone(),
+        }
+    }
 }
 
 fn memory_address_to_witness_index(address: u64, memory_layout: &MemoryLayout) -> usize {
@@ -100,12 +156,127 @@ fn remap_address(a: u64, memory_layout: &MemoryLayout) -> u64 {
     }
 }
 
+/// Size of the memory polynomials for `trace`: the power of two covering the (remapped)
+/// addresses of its RAM accesses.
+pub fn memory_size<InstructionSet: JoltInstructionSet>(
+    memory_layout: &MemoryLayout,
+    trace: &[JoltTraceStep<InstructionSet>],
+) -> usize {
+    let max_trace_address = trace
+        .iter()
+        .map(|step| match step.memory_ops[RAM] {
+            MemoryOp::Read(a) => remap_address(a, memory_layout),
+            MemoryOp::Write(a, _) => remap_address(a, memory_layout),
+        })
+        .max()
+        .unwrap_or_default();
+    // The advice tape is committed to in full, so memory must span all of it
+    let advice_end = if memory_layout.max_advice_size > 0 {
+        memory_address_to_witness_index(memory_layout.advice_end, memory_layout) as u64
+    } else {
+        0
+    };
+    max_trace_address.max(advice_end).next_power_of_two() as usize
+}
+
+/// Writes the inputs digest (as little-endian words) into `v` at the witness indices
+/// corresponding to the inputs digest region.
+fn populate_inputs_digest<T: From<u32>>(program_io: &JoltDevice, v: &mut [T]) {
+    let memory_layout = &program_io.memory_layout;
+    let start_index = memory_address_to_witness_index(memory_layout.inputs_digest, memory_layout);
+    for (i, chunk) in program_io.inputs_digest().chunks(4).enumerate() {
+        v[start_index + i] = T::from(u32::from_le_bytes(chunk.try_into().unwrap()));
+    }
+}
+
+/// Evaluates the multilinear extension of the indicator of witness index `index` at
+/// `r`, where `r[0]` binds the most significant bit of `index`.
+fn eq_at_index<F: JoltField>(r: &[F], index: usize) -> F {
+    r.iter()
+        .rev()
+        .enumerate()
+        .map(|(i, r_i)| {
+            if (index >> i) & 1 == 1 {
+                *r_i
+            } else {
+                F::one() - r_i
+            }
+        })
+        .product()
+}
+
+/// Writes the digest of the input stream (as little-endian words) into `v` at the
+/// witness indices corresponding to the stream digest region, if the guest read any
+/// of the stream.
+fn populate_stream_digest<T: From<u32>>(program_io: &JoltDevice, v: &mut [T]) {
+    let memory_layout = &program_io.memory_layout;
+    let start_index = memory_address_to_witness_index(memory_layout.stream_digest, memory_layout);
+    if let Some(digest) = program_io.stream_digest() {
+        for (i, chunk) in digest.chunks(4).enumerate() {
+            v[start_index + i] = T::from(u32::from_le_bytes(chunk.try_into().unwrap()));
+        }
+    }
+}
+
+/// Writes the journal digest (as little-endian words) into `v` at the witness indices
+/// corresponding to the journal digest region. The digest of an empty journal is
+/// zero, which is also the initial value of the region.
+fn populate_journal_digest<T: From<u32>>(program_io: &JoltDevice, v: &mut [T]) {
+    let memory_layout = &program_io.memory_layout;
+    let start_index = memory_address_to_witness_index(memory_layout.journal_digest, memory_layout);
+    for (i, chunk) in program_io.journal_digest.chunks(4).enumerate() {
+        v[start_index + i] = T::from(u32::from_le_bytes(chunk.try_into().unwrap()));
+    }
+}
+
+/// Writes the nondeterminism digest (as little-endian words) into `v` at the witness
+/// indices corresponding to the nondeterminism digest region. Like the outputs, it is
+/// whatever the guest wrote, so it is populated even if the guest panicked.
+fn populate_nondeterminism_digest<T: From<u32>>(program_io: &JoltDevice, v: &mut [T]) {
+    let memory_layout = &program_io.memory_layout;
+    let start_index =
+        memory_address_to_witness_index(memory_layout.nondeterminism_digest, memory_layout);
+    for (i, word) in le_words(&program_io.nondeterminism_digest).enumerate() {
+        v[start_index + i] = T::from(word);
+    }
+}
+
+/// Writes the guest's log bytes, packed into little-endian words, into `v` at
+/// the log region of the witness.
+fn populate_logs<T: From<u32>>(program_io: &JoltDevice, v: &mut [T]) {
+    let memory_layout = &program_io.memory_layout;
+    let start_index = memory_address_to_witness_index(memory_layout.log_start, memory_layout);
+    for (i, word) in le_words(&program_io.logs).enumerate() {
+        v[start_index + i] = T::from(word);
+    }
+}
+
+/// Packs `bytes` into little-endian words, zero-padding the last one. Program I/O is
+/// laid out in memory this way regardless of the host's byte order.
+pub(crate) fn le_words(bytes: &[u8]) -> impl Iterator<Item = u32> + '_ {
+    bytes.chunks(4).map(|chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u32::from_le_bytes(word)
+    })
+}
+
+/// The initial contents of the advice tape as little-endian words, zero-padded to the
+/// size of the tape; empty if the program has none.
+fn advice_words(program_io: &JoltDevice) -> Vec<u32> {
+    let mut words = vec![0u32; (program_io.memory_layout.max_advice_size / 4) as usize];
+    for (word, advice_word) in words.iter_mut().zip(le_words(&program_io.advice_tape)) {
+        *word = advice_word;
+    }
+    words
+}
+
 const RS1: usize = 0;
 const RS2: usize = 1;
 const RD: usize = 2;
 const RAM: usize = 3;
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Default, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemoryStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     /// Read/write addresses. For offline memory checking, each read is paired with a "virtual" write
     /// and vice versa, so the read addresses and write addresses are the same.
@@ -138,6 +309,15 @@ pub struct ReadWriteMemoryStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     a_init_final: VerifierComputedOpening<T>,
     /// Initial memory values. RAM is initialized to contain the program bytecode and inputs.
     v_init: VerifierComputedOpening<T>,
+    /// The initial memory image (see `ReadWriteMemoryPreprocessing::image`), opened
+    /// alongside the init/final values. Only its opening is part of a proof: the
+    /// verifier takes its commitment from the verifier key.
+    v_image: PreprocessedOpening<T>,
+    /// The initial contents of the advice tape (see `MemoryLayout::advice_start`), which
+    /// only the prover knows. Committed to separately from the init/final values, since
+    /// it spans only the tape; the verifier's `v_init` selects it at the tape's witness
+    /// indices, so it has no bearing on the rest of memory.
+    pub v_advice: T,
     identity: VerifierComputedOpening<T>,
 }
 
@@ -207,6 +387,54 @@ pub type ReadWriteMemoryCommitments<
     ProofTranscript: Transcript,
 > = ReadWriteMemoryStuff<PCS::Commitment>;
 
+impl<T: CanonicalSerialize + CanonicalDeserialize + Default> ReadWriteMemoryStuff<T> {
+    /// The values committed to per proof, i.e. those with a commitment.
+    fn proof_values(&self) -> Vec<&T> {
+        self.read_write_values()
+            .into_iter()
+            .chain(self.init_final_values())
+            .chain([&self.v_advice])
+            .collect()
+    }
+
+    /// Serializes `proof_values`, as `JoltCommitments` are serialized into proofs.
+    pub(crate) fn serialize_proof_values<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        for value in self.proof_values() {
+            value.serialize_with_mode(&mut writer, compress)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn proof_values_size(&self, compress: Compress) -> usize {
+        self.proof_values()
+            .into_iter()
+            .map(|value| value.serialized_size(compress))
+            .sum()
+    }
+
+    /// Inverse of `serialize_proof_values`.
+    pub(crate) fn deserialize_proof_values<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut stuff = Self::default();
+        for value in stuff
+            .read_write_values_mut()
+            .into_iter()
+            .chain(stuff.init_final_values_mut())
+        {
+            *value = T::deserialize_with_mode(&mut reader, compress, validate)?;
+        }
+        stuff.v_advice = T::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(stuff)
+    }
+}
+
 impl<T: CanonicalSerialize + CanonicalDeserialize + Default>
     Initializable<T, ReadWriteMemoryPreprocessing> for ReadWriteMemoryStuff<T>
 {
@@ -258,26 +486,15 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
     ) -> Self {
         assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
         assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
+        assert!(program_io.advice_tape.len() <= program_io.memory_layout.max_advice_size as usize);
 
         let m = trace.len();
         assert!(m.is_power_of_two());
 
-        let max_trace_address = trace
-            .iter()
-            .map(|step| match step.memory_ops[RAM] {
-                MemoryOp::Read(a) => remap_address(a, &program_io.memory_layout),
-                MemoryOp::Write(a, _) => remap_address(a, &program_io.memory_layout),
-            })
-            .max()
-            .unwrap();
-
-        let memory_size = max_trace_address.next_power_of_two() as usize;
+        let memory_size = memory_size(&program_io.memory_layout, trace);
         let mut v_init: Vec<u32> = vec![0; memory_size];
         // Copy bytecode
-        let mut v_init_index = memory_address_to_witness_index(
-            preprocessing.min_bytecode_address,
-            &program_io.memory_layout,
-        );
+        let mut v_init_index = preprocessing.image_start;
         for word in preprocessing.bytecode_words.iter() {
             v_init[v_init_index] = *word;
             v_init_index += 1;
@@ -297,6 +514,15 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
             v_init[v_init_index] = word;
             v_init_index += 1;
         }
+        populate_inputs_digest(program_io, &mut v_init);
+        let v_advice = advice_words(program_io);
+        if !v_advice.is_empty() {
+            let advice_start = memory_address_to_witness_index(
+                program_io.memory_layout.advice_start,
+                &program_io.memory_layout,
+            );
+            v_init[advice_start..advice_start + v_advice.len()].copy_from_slice(&v_advice);
+        }
 
         #[cfg(test)]
         let mut init_tuples: HashSet<(usize, u32, u32)> = HashSet::new();
@@ -488,10 +714,32 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
             t_read_ram: t_read_ram_poly,
             t_final,
             v_init: Some(v_init),
+            v_image: None,
+            v_advice: if v_advice.is_empty() {
+                MultilinearPolynomial::default()
+            } else {
+                MultilinearPolynomial::from(v_advice)
+            },
             a_init_final: None,
             identity: None,
         }
     }
+
+    /// Commits to the advice tape, if the program has one. Its commitment is made by
+    /// the prover, like the other witness polynomials, but is neither a read/write nor
+    /// an init/final value (see `v_advice`).
+    pub fn commit_advice<PCS, ProofTranscript>(
+        &self,
+        commitments: &mut ReadWriteMemoryCommitments<PCS, ProofTranscript>,
+        generators: &PCS::Setup,
+    ) where
+        PCS: CommitmentScheme<ProofTranscript, Field = F>,
+        ProofTranscript: Transcript,
+    {
+        if self.v_advice.len() > 0 {
+            commitments.v_advice = PCS::commit(&self.v_advice, generators);
+        }
+    }
 }
 
 impl<F, PCS, ProofTranscript> MemoryCheckingProver<F, PCS, ProofTranscript>
@@ -703,6 +951,48 @@ where
         );
     }
 
+    /// Opens the memory image at the last `image_num_vars` coordinates of
+    /// `r_init_final`, which span the block containing it (the verifier selects the
+    /// block with the remaining coordinates). Likewise opens the advice tape, if any, at
+    /// the coordinates spanning the tape.
+    fn compute_preprocessed_openings(
+        preprocessing: &Self::Preprocessing,
+        opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
+        polynomials: &Self::Polynomials,
+        openings: &mut Self::Openings,
+        r_init_final: &[F],
+        transcript: &mut ProofTranscript,
+    ) {
+        let image = preprocessing.image();
+        let r_image = r_init_final[r_init_final.len() - preprocessing.image_num_vars..].to_vec();
+        let eq_image = EqPolynomial::evals(&r_image);
+        let v_image = image.evaluate(&r_image);
+        openings.v_image = Some(v_image);
+
+        opening_accumulator.append(
+            &[&image],
+            DensePolynomial::new(eq_image),
+            r_image,
+            &[v_image],
+            transcript,
+        );
+
+        if polynomials.v_advice.len() > 0 {
+            let advice_num_vars = polynomials.v_advice.get_num_vars();
+            let r_advice = r_init_final[r_init_final.len() - advice_num_vars..].to_vec();
+            let eq_advice = EqPolynomial::evals(&r_advice);
+            openings.v_advice = polynomials.v_advice.evaluate(&r_advice);
+
+            opening_accumulator.append(
+                &[&polynomials.v_advice],
+                DensePolynomial::new(eq_advice),
+                r_advice,
+                &[openings.v_advice],
+                transcript,
+            );
+        }
+    }
+
     fn protocol_name() -> &'static [u8] {
         b"Registers/RAM memory checking"
     }
@@ -715,6 +1005,55 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
+    fn verify_preprocessed_openings(
+        preprocessing: &Self::Preprocessing,
+        commitments: &Self::Commitments,
+        preprocessed_commitments: &[&PCS::Commitment],
+        openings: &Self::Openings,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
+        r_init_final: &[F],
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        let image_end = (preprocessing.image_block() + 1) << preprocessing.image_num_vars;
+        if r_init_final.len().pow2() < image_end {
+            return Err(ProofVerifyError::MemoryImageTooLarge(
+                r_init_final.len().pow2(),
+                image_end,
+            ));
+        }
+        let v_image = openings
+            .v_image
+            .as_ref()
+            .ok_or(ProofVerifyError::InternalError)?;
+        // `preprocessed_commitments` is the verifier key's commitment to the memory image
+        opening_accumulator.append(
+            preprocessed_commitments,
+            r_init_final[r_init_final.len() - preprocessing.image_num_vars..].to_vec(),
+            &[v_image],
+            transcript,
+        );
+
+        let memory_layout = &preprocessing.program_io.as_ref().unwrap().memory_layout;
+        if memory_layout.max_advice_size > 0 {
+            let advice_end =
+                memory_address_to_witness_index(memory_layout.advice_end, memory_layout);
+            if r_init_final.len().pow2() < advice_end {
+                return Err(ProofVerifyError::AdviceTapeOutOfRange(
+                    r_init_final.len().pow2(),
+                    advice_end,
+                ));
+            }
+            let advice_num_vars = (memory_layout.max_advice_size / 4).log_2();
+            opening_accumulator.append(
+                &[&commitments.v_advice],
+                r_init_final[r_init_final.len() - advice_num_vars..].to_vec(),
+                &[&openings.v_advice],
+                transcript,
+            );
+        }
+        Ok(())
+    }
+
     fn compute_verifier_openings(
         openings: &mut Self::Openings,
         preprocessing: &Self::Preprocessing,
@@ -727,31 +1066,42 @@ where
         openings.a_init_final =
             Some(IdentityPolynomial::new(r_init_final.len()).evaluate(r_init_final));
 
-        let memory_layout = &preprocessing.program_io.as_ref().unwrap().memory_layout;
+        let program_io = preprocessing.program_io.as_ref().unwrap();
+        let memory_layout = &program_io.memory_layout;
 
-        // TODO(moodlezoup): Compute opening without instantiating v_init polynomial itself
-        let memory_size = r_init_final.len().pow2();
-        let mut v_init: Vec<u64> = vec![0; memory_size];
-        // Copy bytecode
-        let mut v_init_index =
-            memory_address_to_witness_index(preprocessing.min_bytecode_address, memory_layout);
-        for word in preprocessing.bytecode_words.iter() {
-            v_init[v_init_index] = *word as u64;
-            v_init_index += 1;
-        }
-        v_init_index = memory_address_to_witness_index(memory_layout.input_start, memory_layout);
-        // Convert input bytes into words and populate `v_init`
-        for chunk in preprocessing.program_io.as_ref().unwrap().inputs.chunks(4) {
+        // The memory image spans the aligned block of 2^image_num_vars witness indices
+        // selected by the high-order variables of `r_init_final`
+        let (r_high, _) = r_init_final.split_at(r_init_final.len() - preprocessing.image_num_vars);
+        let mut v_init =
+            eq_at_index(r_high, preprocessing.image_block()) * openings.v_image.unwrap();
+
+        // The inputs and their digest lie outside of the image, and are sparse enough to
+        // evaluate word by word.
+        let input_start = memory_address_to_witness_index(memory_layout.input_start, memory_layout);
+        for (i, chunk) in program_io.inputs.chunks(4).enumerate() {
             let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            v_init[v_init_index] = word as u64;
-            v_init_index += 1;
+            word[..chunk.len()].copy_from_slice(chunk);
+            v_init +=
+                eq_at_index(r_init_final, input_start + i) * F::from_u32(u32::from_le_bytes(word));
+        }
+        let digest_start =
+            memory_address_to_witness_index(memory_layout.inputs_digest, memory_layout);
+        for (i, chunk) in program_io.inputs_digest().chunks(4).enumerate() {
+            v_init += eq_at_index(r_init_final, digest_start + i)
+                * F::from_u32(u32::from_le_bytes(chunk.try_into().unwrap()));
         }
 
-        openings.v_init = Some(DensePolynomial::from_u64(&v_init).evaluate(r_init_final));
+        // The advice tape spans the witness indices sharing the high-order bits of its
+        // (size-aligned) start, so the prover's commitment only determines those
+        if memory_layout.max_advice_size > 0 {
+            let advice_num_vars = (memory_layout.max_advice_size / 4).log_2();
+            let advice_start =
+                memory_address_to_witness_index(memory_layout.advice_start, memory_layout);
+            let (r_high, _) = r_init_final.split_at(r_init_final.len() - advice_num_vars);
+            v_init += eq_at_index(r_high, advice_start >> advice_num_vars) * openings.v_advice;
+        }
+
+        openings.v_init = Some(v_init);
     }
 
     fn read_tuples(
@@ -841,9 +1191,9 @@ where
     _pcs: PhantomData<(PCS, ProofTranscript)>,
     num_rounds: usize,
     /// Sumcheck proof that v_final is equal to the program outputs at the relevant indices.
-    sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
     /// Opening of v_final at the random point chosen over the course of sumcheck
-    opening: F,
+    pub(crate) opening: F,
 }
 
 impl<F, PCS, ProofTranscript> OutputSumcheckProof<F, PCS, ProofTranscript>
@@ -852,9 +1202,23 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
+    /// Reassembles a proof decoded from another encoding (see `sol_types`).
+    pub(crate) fn new(
+        sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+        opening: F,
+    ) -> Self {
+        Self {
+            _pcs: PhantomData,
+            num_rounds: sumcheck_proof.compressed_polys.len(),
+            sumcheck_proof,
+            opening,
+        }
+    }
+
     fn prove_outputs(
         polynomials: &ReadWriteMemoryPolynomials<F>,
         program_io: &JoltDevice,
+        streaming_rounds: usize,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Self {
@@ -867,12 +1231,15 @@ where
             program_io.memory_layout.input_start,
             &program_io.memory_layout,
         ) as u64;
-        let ram_start_index =
-            memory_address_to_witness_index(RAM_START_ADDRESS, &program_io.memory_layout) as u64;
+        // The advice tape, which ends at RAM_START_ADDRESS, is not part of the program I/O
+        let advice_start_index = memory_address_to_witness_index(
+            program_io.memory_layout.advice_start,
+            &program_io.memory_layout,
+        ) as u64;
 
         let io_witness_range: Vec<u8> = (0..memory_size as u64)
             .map(|i| {
-                if i >= input_start_index && i < ram_start_index {
+                if i >= input_start_index && i < advice_start_index {
                     1
                 } else {
                     0
@@ -895,6 +1262,7 @@ where
             v_io[input_index] = word;
             input_index += 1;
         }
+        populate_inputs_digest(program_io, &mut v_io);
         let mut output_index = memory_address_to_witness_index(
             program_io.memory_layout.output_start,
             &program_io.memory_layout,
@@ -909,6 +1277,8 @@ where
             v_io[output_index] = word;
             output_index += 1;
         }
+        populate_logs(program_io, &mut v_io);
+        populate_nondeterminism_digest(program_io, &mut v_io);
 
         // Copy panic bit
         v_io[memory_address_to_witness_index(
@@ -921,27 +1291,41 @@ where
                 program_io.memory_layout.termination,
                 &program_io.memory_layout,
             )] = 1;
+            // The guest only writes the stream and journal digests when it terminates
+            populate_stream_digest(program_io, &mut v_io);
+            populate_journal_digest(program_io, &mut v_io);
         }
 
-        let mut sumcheck_polys = vec![
-            eq,
-            MultilinearPolynomial::from(io_witness_range),
-            polynomials.v_final.clone(),
-            MultilinearPolynomial::from(v_io),
-        ];
+        let io_witness_range = MultilinearPolynomial::from(io_witness_range);
+        let v_io = MultilinearPolynomial::from(v_io);
 
         // eq * io_witness_range * (v_final - v_io)
         let output_check_fn = |vals: &[F]| -> F { vals[0] * vals[1] * (vals[2] - vals[3]) };
 
-        let (sumcheck_proof, r_sumcheck, sumcheck_openings) =
-            SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary::<_>(
+        let (sumcheck_proof, r_sumcheck, sumcheck_openings) = match streaming_rounds {
+            0 => {
+                let mut sumcheck_polys =
+                    vec![eq, io_witness_range, polynomials.v_final.clone(), v_io];
+                SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary::<_>(
+                    &F::zero(),
+                    num_rounds,
+                    &mut sumcheck_polys,
+                    output_check_fn,
+                    3,
+                    transcript,
+                )
+            }
+            // Stream over v_final rather than binding a copy of it
+            _ => SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary_streaming(
                 &F::zero(),
                 num_rounds,
-                &mut sumcheck_polys,
+                &[&eq, &io_witness_range, &polynomials.v_final, &v_io],
                 output_check_fn,
                 3,
+                streaming_rounds,
                 transcript,
-            );
+            ),
+        };
 
         opening_accumulator.append(
             &[&polynomials.v_final],
@@ -990,9 +1374,12 @@ where
         let io_memory_size = ram_start_index as usize;
         let log_io_memory_size = io_memory_size.log_2();
 
+        let advice_start_index =
+            memory_address_to_witness_index(memory_layout.advice_start, memory_layout);
+
         let io_witness_range: Vec<_> = (0..io_memory_size)
             .map(|i| {
-                if i >= input_start_index {
+                if i >= input_start_index && i < advice_start_index {
                     F::one()
                 } else {
                     F::zero()
@@ -1021,6 +1408,7 @@ where
             v_io[input_index] = word as u64;
             input_index += 1;
         }
+        populate_inputs_digest(program_io, &mut v_io);
         let mut output_index =
             memory_address_to_witness_index(memory_layout.output_start, memory_layout);
         // Convert output bytes into words and populate `v_io`
@@ -1033,12 +1421,17 @@ where
             v_io[output_index] = word as u64;
             output_index += 1;
         }
+        populate_logs(program_io, &mut v_io);
+        populate_nondeterminism_digest(program_io, &mut v_io);
         // Copy panic bit
         v_io[memory_address_to_witness_index(memory_layout.panic, memory_layout)] =
             program_io.panic as u64;
         if !program_io.panic {
             // Set termination bit
             v_io[memory_address_to_witness_index(memory_layout.termination, memory_layout)] = 1;
+            // The guest only writes the stream and journal digests when it terminates
+            populate_stream_digest(program_io, &mut v_io);
+            populate_journal_digest(program_io, &mut v_io);
         }
 
         let mut v_io_eval = DensePolynomial::from_u64(&v_io)
@@ -1086,12 +1479,18 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
+    /// Proves the memory checking, output and timestamp validity arguments. The read and
+    /// write multisets are proven with `grand_product_strategy`, and the output sumcheck
+    /// streams over its first `streaming_rounds` rounds rather than binding a copy of
+    /// `v_final` (see `SumcheckInstanceProof::prove_arbitrary_streaming`).
     #[tracing::instrument(skip_all, name = "ReadWriteMemoryProof::prove")]
     pub fn prove<'a>(
         generators: &PCS::Setup,
         preprocessing: &ReadWriteMemoryPreprocessing,
         polynomials: &'a JoltPolynomials<F>,
         program_io: &JoltDevice,
+        grand_product_strategy: GrandProductStrategy,
+        streaming_rounds: usize,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Self {
@@ -1100,6 +1499,7 @@ where
             preprocessing,
             &polynomials.read_write_memory,
             polynomials,
+            grand_product_strategy,
             opening_accumulator,
             transcript,
         );
@@ -1107,6 +1507,7 @@ where
         let output_proof = OutputSumcheckProof::prove_outputs(
             &polynomials.read_write_memory,
             program_io,
+            streaming_rounds,
             opening_accumulator,
             transcript,
         );
@@ -1126,11 +1527,14 @@ where
         }
     }
 
+    /// Verifies this proof against `commitments` and `memory_image`, the verifier key's
+    /// commitment to the initial memory image (which proofs do not carry).
     pub fn verify(
         mut self,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         preprocessing: &ReadWriteMemoryPreprocessing,
         commitments: &JoltCommitments<PCS, ProofTranscript>,
+        memory_image: &PCS::Commitment,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
@@ -1140,6 +1544,7 @@ where
             self.memory_checking_proof,
             &commitments.read_write_memory,
             commitments,
+            &[memory_image],
             opening_accumulator,
             transcript,
         )?;
@@ -1163,12 +1568,85 @@ where
 #[cfg(test)]
 mod tests {
     use ark_bn254::Fr;
+    use ark_std::{test_rng, One};
 
     use super::*;
 
     #[test]
     fn read_write_memory_stuff_ordering() {
-        let preprocessing = ReadWriteMemoryPreprocessing::preprocess(vec![]);
+        let preprocessing =
+            ReadWriteMemoryPreprocessing::preprocess(vec![], &MemoryLayout::new(4096, 4096));
         ReadWriteMemoryOpenings::<Fr>::test_ordering_consistency(&preprocessing);
     }
+
+    #[test]
+    fn memory_image_opening() {
+        let memory_layout = MemoryLayout::new(4096, 4096);
+        let memory_init: Vec<(u64, u8)> = (0..64)
+            .map(|i| (RAM_START_ADDRESS + i, i as u8 + 1))
+            .collect();
+        let preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init, &memory_layout);
+        let image_start = preprocessing.image_start;
+        let image_end = image_start + preprocessing.bytecode_words.len();
+        // The image polynomial spans the image, not the witness indices before it
+        assert!(preprocessing.image_num_vars.pow2() < 2 * preprocessing.bytecode_words.len());
+        assert!(preprocessing.image_num_vars.pow2() < image_start);
+
+        // A memory four times the size needed to hold the image, initialized to the
+        // image alone
+        let num_vars = image_end.next_power_of_two().log_2() + 2;
+        let mut v_init = vec![0u32; num_vars.pow2()];
+        v_init[image_start..image_end].copy_from_slice(&preprocessing.bytecode_words);
+        let v_init: MultilinearPolynomial<Fr> = MultilinearPolynomial::from(v_init);
+
+        let mut rng = test_rng();
+        let r: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let (r_high, r_image) = r.split_at(num_vars - preprocessing.image_num_vars);
+        let v_image = preprocessing.image::<Fr>().evaluate(r_image);
+        assert_eq!(
+            eq_at_index(r_high, preprocessing.image_block()) * v_image,
+            v_init.evaluate(&r)
+        );
+
+        let eq = EqPolynomial::evals(&r);
+        for index in [0, image_start, image_end - 1, num_vars.pow2() - 1] {
+            assert_eq!(eq_at_index(&r, index), eq[index]);
+        }
+    }
+
+    #[test]
+    fn advice_tape_opening() {
+        let mut program_io = JoltDevice::new(4096, 4096);
+        program_io.memory_layout = program_io.memory_layout.with_max_advice_size(100);
+        program_io.advice_tape = (0..100).map(|i| i as u8 + 1).collect();
+        let memory_layout = &program_io.memory_layout;
+        assert_eq!(memory_layout.max_advice_size, 128);
+
+        let advice = advice_words(&program_io);
+        let advice_num_vars = advice.len().log_2();
+        let advice_start =
+            memory_address_to_witness_index(memory_layout.advice_start, memory_layout);
+        let ram_start = memory_address_to_witness_index(RAM_START_ADDRESS, memory_layout);
+        assert_eq!(advice_start + advice.len(), ram_start);
+        assert_eq!(advice_start % advice.len(), 0);
+        assert!(
+            memory_address_to_witness_index(memory_layout.termination, memory_layout)
+                < advice_start
+        );
+
+        // A memory twice the size of the I/O region, initialized to the tape alone
+        let num_vars = ram_start.log_2() + 1;
+        let mut v_init = vec![0u32; num_vars.pow2()];
+        v_init[advice_start..ram_start].copy_from_slice(&advice);
+        let v_init: MultilinearPolynomial<Fr> = MultilinearPolynomial::from(v_init);
+
+        let mut rng = test_rng();
+        let r: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let (r_high, r_advice) = r.split_at(num_vars - advice_num_vars);
+        let v_advice = MultilinearPolynomial::<Fr>::from(advice).evaluate(r_advice);
+        assert_eq!(
+            eq_at_index(r_high, advice_start >> advice_num_vars) * v_advice,
+            v_init.evaluate(&r)
+        );
+    }
 }
@@ -9,6 +9,7 @@ use crate::jolt::subtable::right_is_zero::RightIsZeroSubtable;
 use crate::poly::commitment::hyperkzg::HyperKZG;
 use crate::r1cs::constraints::JoltRV32IMConstraints;
 use crate::r1cs::inputs::JoltR1CSInputs;
+use crate::utils::errors::{AbiEncodingError, FormatError, JoltError};
 use ark_bn254::{Bn254, Fr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use enum_dispatch::enum_dispatch;
@@ -16,13 +17,16 @@ use rand::{prelude::StdRng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::any::TypeId;
 use strum::{EnumCount, IntoEnumIterator};
-use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
+use strum_macros::{EnumCount as EnumCountMacro, EnumIter, IntoStaticStr};
 
-use super::{Jolt, JoltCommitments, JoltProof};
+use super::archive::to_hex;
+use super::size_report::ProofSizeReport;
+use super::{Jolt, JoltCommitments, JoltPreprocessing, JoltProof, JoltVerifierKey};
 use crate::jolt::instruction::{
-    add::ADDInstruction, and::ANDInstruction, beq::BEQInstruction, bge::BGEInstruction,
-    bgeu::BGEUInstruction, bne::BNEInstruction, mul::MULInstruction, mulhu::MULHUInstruction,
-    mulu::MULUInstruction, or::ORInstruction, sll::SLLInstruction, slt::SLTInstruction,
+    add::ADDInstruction, and::ANDInstruction, argmax::ARGMAXInstruction, beq::BEQInstruction,
+    bge::BGEInstruction, bgeu::BGEUInstruction, bne::BNEInstruction, exp::EXPInstruction,
+    mul::MULInstruction, mulhu::MULHUInstruction, mulu::MULUInstruction, or::ORInstruction,
+    relu::RELUInstruction, sigmoid::SIGMOIDInstruction, sll::SLLInstruction, slt::SLTInstruction,
     sltu::SLTUInstruction, sra::SRAInstruction, srl::SRLInstruction, sub::SUBInstruction,
     virtual_advice::ADVICEInstruction, virtual_assert_lte::ASSERTLTEInstruction,
     virtual_assert_valid_signed_remainder::AssertValidSignedRemainderInstruction,
@@ -30,11 +34,12 @@ use crate::jolt::instruction::{
     SubtableIndices,
 };
 use crate::jolt::subtable::{
-    and::AndSubtable, eq::EqSubtable, eq_abs::EqAbsSubtable, identity::IdentitySubtable,
-    left_is_zero::LeftIsZeroSubtable, left_msb::LeftMSBSubtable, lt_abs::LtAbsSubtable,
-    ltu::LtuSubtable, or::OrSubtable, right_msb::RightMSBSubtable, sign_extend::SignExtendSubtable,
-    sll::SllSubtable, sra_sign::SraSignSubtable, srl::SrlSubtable, xor::XorSubtable,
-    JoltSubtableSet, LassoSubtable, SubtableId,
+    and::AndSubtable, argmax::ArgmaxSubtable, eq::EqSubtable, eq_abs::EqAbsSubtable,
+    exp::ExpSubtable, identity::IdentitySubtable, left_is_zero::LeftIsZeroSubtable,
+    left_msb::LeftMSBSubtable, lt_abs::LtAbsSubtable, ltu::LtuSubtable, or::OrSubtable,
+    relu::ReluSubtable, right_msb::RightMSBSubtable, sigmoid::SigmoidSubtable,
+    sign_extend::SignExtendSubtable, sll::SllSubtable, sra_sign::SraSignSubtable, srl::SrlSubtable,
+    xor::XorSubtable, JoltSubtableSet, LassoSubtable, SubtableId,
 };
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 
@@ -44,7 +49,9 @@ macro_rules! instruction_set {
     ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
         #[allow(non_camel_case_types)]
         #[repr(u8)]
-        #[derive(Copy, Clone, Debug, PartialEq, EnumIter, EnumCountMacro, Serialize, Deserialize)]
+        #[derive(
+            Copy, Clone, Debug, PartialEq, EnumIter, EnumCountMacro, IntoStaticStr, Serialize, Deserialize,
+        )]
         #[enum_dispatch(JoltInstruction)]
         pub enum $enum_name {
             $($alias($struct)),+
@@ -78,16 +85,18 @@ macro_rules! subtable_enum {
         #[allow(non_camel_case_types)]
         #[repr(u8)]
         #[enum_dispatch(LassoSubtable<F>)]
-        #[derive(EnumCountMacro, EnumIter)]
+        #[derive(EnumCountMacro, EnumIter, IntoStaticStr)]
         pub enum $enum_name<F: JoltField> { $($alias($struct)),+ }
-        impl<F: JoltField> From<SubtableId> for $enum_name<F> {
-          fn from(subtable_id: SubtableId) -> Self {
+        impl<F: JoltField> TryFrom<SubtableId> for $enum_name<F> {
+          type Error = JoltError;
+
+          fn try_from(subtable_id: SubtableId) -> Result<Self, Self::Error> {
             $(
               if subtable_id == TypeId::of::<$struct>() {
-                $enum_name::from(<$struct>::new())
+                Ok($enum_name::from(<$struct>::new()))
               } else
             )+
-            { panic!("Unexpected subtable id {:?}", subtable_id) } // TODO(moodlezoup): better error handling
+            { Err(JoltError::UnknownSubtable(format!("{:?}", subtable_id))) }
           }
         }
 
@@ -124,6 +133,10 @@ instruction_set!(
   MUL: MULInstruction<WORD_SIZE>,
   MULU: MULUInstruction<WORD_SIZE>,
   MULHU: MULHUInstruction<WORD_SIZE>,
+  RELU: RELUInstruction<WORD_SIZE>,
+  ARGMAX: ARGMAXInstruction<WORD_SIZE>,
+  EXP: EXPInstruction<WORD_SIZE>,
+  SIGMOID: SIGMOIDInstruction<WORD_SIZE>,
   VIRTUAL_ADVICE: ADVICEInstruction<WORD_SIZE>,
   VIRTUAL_MOVE: MOVEInstruction<WORD_SIZE>,
   VIRTUAL_ASSERT_LTE: ASSERTLTEInstruction<WORD_SIZE>,
@@ -159,7 +172,11 @@ subtable_enum!(
   RIGHT_IS_ZERO: RightIsZeroSubtable<F>,
   DIV_BY_ZERO: DivByZeroSubtable<F>,
   LSB: LowBitSubtable<F, 0>,
-  SECOND_LEAST_SIGNIFICANT_BIT: LowBitSubtable<F, 1>
+  SECOND_LEAST_SIGNIFICANT_BIT: LowBitSubtable<F, 1>,
+  RELU: ReluSubtable<F>,
+  ARGMAX: ArgmaxSubtable<F>,
+  EXP: ExpSubtable<F>,
+  SIGMOID: SigmoidSubtable<F>
 );
 
 // ==================== JOLT ====================
@@ -183,80 +200,294 @@ where
 pub type RV32IJoltProof<F, PCS, ProofTranscript> =
     JoltProof<C, M, JoltR1CSInputs, F, PCS, RV32I, RV32ISubtables<F>, ProofTranscript>;
 
+use crate::jolt::compat::{CompatReport, JoltParameters, ProofHeader};
+#[cfg(feature = "json")]
+use crate::utils::proof_json::proof_to_json;
+use crate::utils::serialization::ValidationPolicy;
+use crate::utils::sol_types::{decode_calldata, encode_calldata, CalldataReport};
 use crate::utils::transcript::{KeccakTranscript, Transcript};
+use ark_serialize::{Compress, SerializationError, Validate};
 use eyre::Result;
+#[cfg(feature = "fs")]
 use std::fs::File;
-use std::io::Cursor;
+#[cfg(feature = "fs")]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(feature = "fs")]
 use std::path::PathBuf;
 
-pub trait Serializable: CanonicalSerialize + CanonicalDeserialize + Sized {
-    /// Gets the byte size of the serialized data
+/// Serialized data starts with a `ProofHeader` recording the proof format version and
+/// the parameters the data was produced for, so that data from another version of Jolt
+/// or another instantiation fails with a `FormatError` before the rest of it is read,
+/// rather than with a garbled deserialization error.
+///
+/// Deserialization validates group elements according to a `ValidationPolicy`:
+/// `from_file` and `deserialize_from_bytes` always run the full checks, while the
+/// `_with_policy` variants can skip them for data from a trusted source. Proofs are
+/// the exception: `Jolt::verify` validates every group element of a proof, so
+/// deserializing a `JoltHyperKZGProof` never does (see its `deserialize_body`).
+pub trait Serializable: CanonicalSerialize + Sized {
+    /// The header the data is serialized behind.
+    fn header(&self) -> ProofHeader;
+
+    /// Reads the data following `header`, once `check_header` has accepted it.
+    fn deserialize_body<R: Read>(
+        header: ProofHeader,
+        reader: R,
+        validate: Validate,
+    ) -> std::result::Result<Self, SerializationError>;
+
+    /// Fails if the data behind `header` was serialized for another `C`, `M`, PCS or
+    /// transcript. Whether it was produced for a particular program is left to
+    /// `compat::check`.
+    fn check_header(header: &ProofHeader) -> std::result::Result<(), FormatError> {
+        let mismatches = header
+            .parameters
+            .instance_mismatches::<C, M, PCS, ProofTranscript>();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(FormatError::IncompatibleParameters(
+                CompatReport::ParametersMismatch(mismatches).to_string(),
+            ))
+        }
+    }
+
+    /// Gets the byte size of the serialized data, excluding the header
     fn size(&self) -> Result<usize> {
         let mut buffer = Vec::new();
         self.serialize_compressed(&mut buffer)?;
         Ok(buffer.len())
     }
 
+    /// Writes the header followed by the data
+    fn serialize_to_writer<W: Write>(&self, mut writer: W) -> Result<()> {
+        self.header().serialize_compressed(&mut writer)?;
+        self.serialize_compressed(writer)?;
+        Ok(())
+    }
+
+    /// Reads data written by `serialize_to_writer`, checking its header before the rest
+    /// and validating group elements according to `policy`
+    fn deserialize_from_reader<R: Read>(mut reader: R, policy: ValidationPolicy) -> Result<Self> {
+        let header = ProofHeader::deserialize_checked(&mut reader)?;
+        Self::check_header(&header)?;
+        Ok(Self::deserialize_body(header, reader, policy.into())?)
+    }
+
     /// Saves the data to a file
+    #[cfg(feature = "fs")]
     fn save_to_file<P: Into<PathBuf>>(&self, path: P) -> Result<()> {
-        let file = File::create(path.into())?;
-        self.serialize_compressed(file)?;
+        let mut writer = BufWriter::new(File::create(path.into())?);
+        self.serialize_to_writer(&mut writer)?;
+        writer.flush()?;
         Ok(())
     }
 
     /// Reads data from a file
+    #[cfg(feature = "fs")]
     fn from_file<P: Into<PathBuf>>(path: P) -> Result<Self> {
-        let file = File::open(path.into())?;
-        Ok(Self::deserialize_compressed(file)?)
+        Self::from_file_with_policy(path, ValidationPolicy::Full)
+    }
+
+    /// Reads data from a file, validating group elements according to `policy`
+    #[cfg(feature = "fs")]
+    fn from_file_with_policy<P: Into<PathBuf>>(path: P, policy: ValidationPolicy) -> Result<Self> {
+        let reader = BufReader::new(File::open(path.into())?);
+        Self::deserialize_from_reader(reader, policy)
     }
 
     /// Serializes the data to a byte vector
     fn serialize_to_bytes(&self) -> Result<Vec<u8>> {
         let mut buffer = Vec::new();
-        self.serialize_compressed(&mut buffer)?;
+        self.serialize_to_writer(&mut buffer)?;
         Ok(buffer)
     }
 
     /// Deserializes data from a byte vector
     fn deserialize_from_bytes(bytes: &[u8]) -> Result<Self> {
-        let cursor = Cursor::new(bytes);
-        Ok(Self::deserialize_compressed(cursor)?)
+        Self::deserialize_from_bytes_with_policy(bytes, ValidationPolicy::Full)
+    }
+
+    /// Deserializes data from a byte vector, validating group elements according to `policy`
+    fn deserialize_from_bytes_with_policy(bytes: &[u8], policy: ValidationPolicy) -> Result<Self> {
+        Self::deserialize_from_reader(bytes, policy)
     }
 }
 
 pub type ProofTranscript = KeccakTranscript;
 pub type PCS = HyperKZG<Bn254, ProofTranscript>;
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+
 pub struct JoltHyperKZGProof {
     pub proof: RV32IJoltProof<Fr, PCS, ProofTranscript>,
     pub commitments: JoltCommitments<PCS, ProofTranscript>,
+    pub header: ProofHeader,
+}
+
+/// The proof and its commitments; `Serializable` writes the header in front of them.
+impl CanonicalSerialize for JoltHyperKZGProof {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> std::result::Result<(), SerializationError> {
+        self.proof.serialize_with_mode(&mut writer, compress)?;
+        self.commitments.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.proof.serialized_size(compress) + self.commitments.serialized_size(compress)
+    }
 }
 
-impl Serializable for JoltHyperKZGProof {}
+impl Serializable for JoltHyperKZGProof {
+    fn header(&self) -> ProofHeader {
+        self.header.clone()
+    }
+
+    /// Proofs are untrusted whatever the `ValidationPolicy` they were read with, and
+    /// `Jolt::verify` validates all of their group elements (commitments and opening
+    /// proofs alike). Deserialization therefore ignores `validate`, so that each point
+    /// is checked exactly once.
+    fn deserialize_body<R: Read>(
+        header: ProofHeader,
+        mut reader: R,
+        _validate: Validate,
+    ) -> std::result::Result<Self, SerializationError> {
+        Ok(Self {
+            proof: RV32IJoltProof::deserialize_with_mode(&mut reader, Compress::Yes, Validate::No)?,
+            commitments: JoltCommitments::deserialize_with_mode(
+                &mut reader,
+                Compress::Yes,
+                Validate::No,
+            )?,
+            header,
+        })
+    }
+}
+
+pub type JoltHyperKZGVerifierKey = JoltVerifierKey<C, Fr, PCS, ProofTranscript>;
+
+impl Serializable for JoltHyperKZGVerifierKey {
+    fn header(&self) -> ProofHeader {
+        ProofHeader::new(JoltParameters::from_verifier_key::<C, M, _, _, _>(self))
+    }
+
+    fn deserialize_body<R: Read>(
+        _header: ProofHeader,
+        reader: R,
+        validate: Validate,
+    ) -> std::result::Result<Self, SerializationError> {
+        Self::deserialize_with_mode(reader, Compress::Yes, validate)
+    }
+}
+
+impl JoltHyperKZGProof {
+    /// Pairs a proof with its commitments, under a header recording the parameters of
+    /// the `preprocessing` it was proven with.
+    pub fn new(
+        proof: RV32IJoltProof<Fr, PCS, ProofTranscript>,
+        commitments: JoltCommitments<PCS, ProofTranscript>,
+        preprocessing: &JoltPreprocessing<C, Fr, PCS, ProofTranscript>,
+    ) -> Self {
+        Self {
+            proof,
+            commitments,
+            header: ProofHeader::new(JoltParameters::from_preprocessing::<C, M, _, _, _>(
+                preprocessing,
+            )),
+        }
+    }
+
+    /// An alternative to the compressed arkworks serialization for EVM integrations:
+    /// the ABI encoding of the proof as a `sol_types::JoltProofSol`, with every
+    /// field element and point coordinate in its own 32-byte word.
+    /// Fails if the proof contains a Quark grand product proof, which has no ABI encoding.
+    pub fn serialize_to_abi(&self) -> std::result::Result<Vec<u8>, AbiEncodingError> {
+        encode_calldata(self)
+    }
+
+    /// Inverse of `serialize_to_abi`. The encoding omits what the verifier takes
+    /// from its key, so `verifier_key` must be the one the proof is verified against.
+    pub fn deserialize_from_abi(
+        bytes: &[u8],
+        verifier_key: &JoltHyperKZGVerifierKey,
+    ) -> std::result::Result<Self, AbiEncodingError> {
+        decode_calldata(bytes, verifier_key)
+    }
+
+    /// The proof as JSON, for tooling that does not link arkworks: it mirrors
+    /// `sol_types::JoltProofSol`, with field elements as `0x`-prefixed big-endian
+    /// hex words and G1 points as `[x, y]` pairs. Unlike the ABI encoding, it also
+    /// covers proofs with Quark grand products.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> serde_json::Value {
+        proof_to_json(self)
+    }
+
+    /// `serialize_to_bytes` as a hex string.
+    pub fn to_hex(&self) -> Result<String> {
+        Ok(to_hex(&self.serialize_to_bytes()?))
+    }
+
+    /// Size and gas cost of the proof's ABI encoding as calldata.
+    pub fn calldata_report(&self) -> std::result::Result<CalldataReport, AbiEncodingError> {
+        Ok(CalldataReport::new(&self.serialize_to_abi()?))
+    }
+
+    /// The compressed size of each component of the proof and its commitments.
+    pub fn size_report(&self) -> ProofSizeReport {
+        self.proof.size_report(&self.commitments)
+    }
+}
 
 // ==================== TEST ====================
 
 #[cfg(test)]
 mod tests {
-    use ark_bn254::{Bn254, Fr};
+    use ark_bn254::{Bn254, Fq, Fr, G1Affine, G1Projective};
+    use ark_ec::AffineRepr;
+    use ark_ff::One;
 
     use std::collections::HashSet;
 
+    use common::constants::{DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE};
+    use common::rv_trace::{CircuitFlags, JoltDevice, MemoryLayout};
+
     use crate::field::JoltField;
     use crate::host;
+    use crate::host::session::ProvingSession;
+    use crate::jolt::instruction::virtual_advice::ADVICEInstruction;
     use crate::jolt::instruction::JoltInstruction;
-    use crate::jolt::vm::rv32i_vm::{Jolt, RV32IJoltVM, C, M};
+    use crate::jolt::verifier_service::{BatchConfig, ProgramRegistry, VerifierService};
+    use crate::jolt::vm::incremental::ProofComponent;
+    use crate::jolt::vm::progress::{ProverCallback, ProverPhase};
+    use crate::jolt::vm::prover_config::JoltProverConfig;
+    use crate::jolt::vm::rv32i_vm::{
+        Jolt, JoltHyperKZGProof, JoltHyperKZGVerifierKey, RV32IJoltVM, Serializable, C, M, RV32I,
+    };
+    use crate::jolt::vm::{JoltPreprocessing, JoltTraceStep};
+    use crate::lasso::memory_checking::StructuredPolynomialData;
     use crate::poly::commitment::commitment_scheme::CommitmentScheme;
     use crate::poly::commitment::hyperkzg::HyperKZG;
+    use crate::poly::commitment::hyrax::HyraxScheme;
     use crate::poly::commitment::mock::MockCommitScheme;
     use crate::poly::commitment::zeromorph::Zeromorph;
+    use crate::utils::blake3_transcript::Blake3Transcript;
+    use crate::utils::errors::{
+        FormatError, JoltError, PreprocessingLoadError, ProofVerifyError, VerifierServiceError,
+    };
     use crate::utils::transcript::{KeccakTranscript, Transcript};
-    use std::sync::{LazyLock, Mutex};
+    use std::sync::{Arc, LazyLock, Mutex};
+    use std::time::Duration;
     use strum::{EnumCount, IntoEnumIterator};
 
     // If multiple tests try to read the same trace artifacts simultaneously, they will fail
     static FIB_FILE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
     static SHA3_FILE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+    static CYCLE_COUNTER_FILE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+    static ML_OPS_FILE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+    static ADVICE_TAPE_FILE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
     fn test_instruction_set_subtables<PCS, ProofTranscript>()
     where
@@ -268,10 +499,10 @@ mod tests {
             <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::InstructionSet::iter()
         {
             for (subtable, _) in instruction.subtables::<Fr>(C, M) {
-                // panics if subtable cannot be cast to enum variant
-                let _ = <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::Subtables::from(
+                <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::Subtables::try_from(
                     subtable.subtable_id(),
-                );
+                )
+                .expect("subtable cannot be cast to enum variant");
                 subtable_set.insert(subtable.subtable_id());
             }
         }
@@ -288,7 +519,15 @@ mod tests {
         test_instruction_set_subtables::<HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>();
     }
 
-    fn fib_e2e<F, PCS, ProofTranscript>()
+    /// Traces the Fibonacci guest on input 9 and preprocesses it with `1 << 20` bounds.
+    /// The guest is built with compressed (RVC) instructions if `compressed` is set.
+    fn fib_fixture<F, PCS, ProofTranscript>(
+        compressed: bool,
+    ) -> (
+        JoltDevice,
+        Vec<JoltTraceStep<RV32I>>,
+        JoltPreprocessing<C, F, PCS, ProofTranscript>,
+    )
     where
         F: JoltField,
         PCS: CommitmentScheme<ProofTranscript, Field = F>,
@@ -296,27 +535,42 @@ mod tests {
     {
         let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
         let mut program = host::Program::new("fibonacci-guest");
+        program.set_compressed_instructions(compressed);
         program.set_input(&9u32);
         let (bytecode, memory_init) = program.decode();
         let (io_device, trace) = program.trace();
         drop(artifact_guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
-            bytecode.clone(),
+        let preprocessing = <RV32IJoltVM as Jolt<F, PCS, C, M, ProofTranscript>>::preprocess(
+            bytecode,
             io_device.memory_layout.clone(),
             memory_init,
             1 << 20,
             1 << 20,
             1 << 20,
         );
+        (io_device, trace, preprocessing)
+    }
+
+    fn fib_e2e<F, PCS, ProofTranscript>()
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<ProofTranscript, Field = F>,
+        ProofTranscript: Transcript,
+    {
+        let (io_device, trace, preprocessing) = fib_fixture::<F, PCS, ProofTranscript>(false);
         let (proof, commitments, debug_info) =
             <RV32IJoltVM as Jolt<F, PCS, C, M, ProofTranscript>>::prove(
                 io_device,
                 trace,
-                preprocessing.clone(),
+                &preprocessing,
             );
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -324,6 +578,457 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fib_e2e_compressed_instructions() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let (io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(true);
+        assert!(trace
+            .iter()
+            .any(|step| step.circuit_flags[CircuitFlags::Compressed as usize]));
+
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+                io_device,
+                trace,
+                &preprocessing,
+            );
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn fib_e2e_session() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let session =
+            ProvingSession::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>::builder(
+                "fibonacci-guest",
+            )
+            .telemetry(true)
+            .build()
+            .unwrap();
+        let proof = session.prove(&9u32);
+        drop(artifact_guard);
+
+        assert!(proof.telemetry.is_some());
+        let verification_result = session.verify(proof);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn fib_e2e_prover_config() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let (io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(false);
+        #[derive(Default)]
+        struct PhaseRecorder(Mutex<Vec<ProverPhase>>);
+        impl ProverCallback for PhaseRecorder {
+            fn phase_finished(&self, phase: ProverPhase) {
+                self.0.lock().unwrap().push(phase);
+            }
+        }
+        let recorder = Arc::new(PhaseRecorder::default());
+
+        // A budget of a single byte streams the output sumcheck over every round
+        let config = JoltProverConfig::default()
+            .with_num_threads(2)
+            .with_max_memory_bytes(1)
+            .with_constraint_check()
+            .with_callback(recorder.clone());
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove_with_config(
+                io_device,
+                trace,
+                &preprocessing,
+                &config,
+            )
+            .unwrap();
+        assert_eq!(*recorder.0.lock().unwrap(), ProverPhase::ALL);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn fib_e2e_prove_async() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let (io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(false);
+        let preprocessing = Arc::new(preprocessing);
+        let handle = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove_async(
+            io_device,
+            trace,
+            preprocessing.clone(),
+            JoltProverConfig::default(),
+        );
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (proof, commitments, debug_info) = runtime.block_on(handle).unwrap();
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn try_prove_io_mismatch() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let (mut io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(false);
+        // Journal entries that do not hash to the journal digest are rejected
+        let mut forged_io = io_device.clone();
+        forged_io.journal = Some(vec![b"forged".to_vec()]);
+        let result = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::try_prove(
+            forged_io,
+            trace.clone(),
+            &preprocessing,
+        );
+        assert!(matches!(result, Err(JoltError::IoMismatch(_))));
+
+        io_device.inputs = vec![0; io_device.memory_layout.max_input_size as usize + 1];
+        let result = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::try_prove(
+            io_device,
+            trace,
+            &preprocessing,
+        );
+        assert!(matches!(result, Err(JoltError::IoMismatch(_))));
+    }
+
+    #[test]
+    fn fib_e2e_auto_sized() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::preprocess_auto(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            &trace,
+        );
+        let verifier_key = preprocessing.verifier_key();
+        assert_eq!(verifier_key.bounds, preprocessing.bounds);
+        assert_eq!(
+            verifier_key.bounds.max_trace_length,
+            trace.len().next_power_of_two()
+        );
+
+        // An execution twice as long no longer fits
+        let longer_trace: Vec<_> = trace.iter().chain(&trace).cloned().collect();
+        let result = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::try_prove(
+            io_device.clone(),
+            longer_trace,
+            &preprocessing,
+        );
+        assert!(matches!(
+            result,
+            Err(JoltError::BoundExceeded {
+                bound: "max_trace_length",
+                ..
+            })
+        ));
+
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+            io_device,
+            trace,
+            &preprocessing,
+        );
+        let verification_result = RV32IJoltVM::verify(&verifier_key, proof, commitments, None);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn verify_with_deadline() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let (io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(false);
+        let verifier_key = preprocessing.verifier_key();
+        let (mut proof, commitments, _) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+                io_device,
+                trace,
+                &preprocessing,
+            );
+        // The digest alone binds the journal
+        proof.program_io.redact_journal();
+
+        // A deadline that has already passed leaves every component to be verified later
+        let verifier =
+            RV32IJoltVM::verify_with_deadline(&verifier_key, proof, commitments, Duration::ZERO)
+                .unwrap();
+        let progress = verifier.progress();
+        assert!(progress.verified.is_empty());
+        assert_eq!(progress.remaining, ProofComponent::ALL);
+        assert!(verifier.finish().is_ok());
+    }
+
+    #[test]
+    fn fib_e2e_from_elf() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut built = host::Program::new("fibonacci-guest");
+        built.build();
+        let memory_layout = MemoryLayout::new(DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE);
+        let mut program = host::Program::from_elf(built.elf.as_ref().unwrap(), &memory_layout);
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::preprocess(
+            bytecode,
+            memory_layout,
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let verifier_key = preprocessing.verifier_key();
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+            io_device,
+            trace,
+            &preprocessing,
+        );
+        assert!(RV32IJoltVM::verify(&verifier_key, proof, commitments, None).is_ok());
+    }
+
+    #[test]
+    fn fib_e2e_trace_to_disk() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let dir = std::env::temp_dir().join("jolt-fib-trace");
+        let (io_device, trace_store) = program.trace_to_disk(&dir, 64).unwrap();
+        drop(artifact_guard);
+
+        let preprocessing = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let verifier_key = preprocessing.verifier_key();
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+            io_device,
+            trace_store.steps(),
+            &preprocessing,
+        );
+        assert!(RV32IJoltVM::verify(&verifier_key, proof, commitments, None).is_ok());
+    }
+
+    #[test]
+    fn fib_trace_iter_matches_trace() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let mut trace_iter = program.trace_iter();
+        let steps: Vec<_> = host::trace_steps(trace_iter.by_ref()).collect();
+        let (io_device, _) = trace_iter.finish().unwrap();
+        let (expected_io_device, expected_steps) = program.trace();
+        drop(artifact_guard);
+
+        assert_eq!(io_device, expected_io_device);
+        assert_eq!(steps.len(), expected_steps.len());
+        for (step, expected_step) in steps.iter().zip(expected_steps.iter()) {
+            assert_eq!(step.bytecode_row, expected_step.bytecode_row);
+            assert_eq!(step.memory_ops, expected_step.memory_ops);
+        }
+    }
+
+    #[test]
+    fn fib_execute_matches_trace() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let summary = program.execute();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        assert_eq!(summary.io_device.outputs, io_device.outputs);
+        assert_eq!(summary.trace_length, trace.len());
+        assert_eq!(summary.padded_trace_length, trace.len().next_power_of_two());
+        assert!(summary.cycle_count <= summary.trace_length);
+    }
+
+    #[test]
+    fn preprocessing_save_load() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let (io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(false);
+        let mut bytes = vec![];
+        RV32IJoltVM::save_preprocessing(&preprocessing, &mut bytes).unwrap();
+
+        let loaded = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::load_preprocessing(
+            bytes.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(loaded.memory_layout, preprocessing.memory_layout);
+        assert_eq!(
+            loaded.bytecode.code_size(),
+            preprocessing.bytecode.code_size()
+        );
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+                io_device,
+                trace,
+                &preprocessing,
+            );
+        assert!(
+            RV32IJoltVM::verify(&loaded.verifier_key(), proof, commitments, debug_info).is_ok()
+        );
+
+        // Preprocessing bound to a different protocol hash is rejected
+        bytes[0] ^= 1;
+        let result = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::load_preprocessing(
+            bytes.as_slice(),
+        );
+        assert!(matches!(
+            result,
+            Err(PreprocessingLoadError::ProtocolMismatch { .. })
+        ));
+
+        // So is preprocessing whose contents don't match their digest
+        bytes[0] ^= 1;
+        *bytes.last_mut().unwrap() ^= 1;
+        let result = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::load_preprocessing(
+            bytes.as_slice(),
+        );
+        assert!(matches!(
+            result,
+            Err(PreprocessingLoadError::Corrupted { .. })
+        ));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn preprocess_or_load() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        let (bytecode, memory_init) = program.decode();
+        drop(artifact_guard);
+        let memory_layout = program.memory_layout();
+
+        let path = std::env::temp_dir().join(format!(
+            "jolt_preprocess_or_load_{}.bin",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let preprocess_or_load = |max_trace_length| {
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::preprocess_or_load(
+                &path,
+                bytecode.clone(),
+                memory_layout.clone(),
+                memory_init.clone(),
+                1 << 16,
+                1 << 16,
+                max_trace_length,
+            )
+            .unwrap()
+        };
+
+        let generated = preprocess_or_load(1 << 16);
+        let saved = std::fs::read(&path).unwrap();
+        // The HyperKZG powers of tau are loaded rather than regenerated
+        let loaded = preprocess_or_load(1 << 16);
+        assert_eq!(std::fs::read(&path).unwrap(), saved);
+        assert_eq!(
+            loaded.generators.0.kzg_pk.g1_powers(),
+            generated.generators.0.kzg_pk.g1_powers()
+        );
+        assert_eq!(
+            loaded.verifier_key().serialize_to_bytes().unwrap(),
+            generated.verifier_key().serialize_to_bytes().unwrap()
+        );
+
+        // Different arguments invalidate the saved preprocessing
+        let regenerated = preprocess_or_load(1 << 17);
+        assert_eq!(
+            regenerated.generators.0.kzg_pk.g1_powers().len(),
+            (1 << 17) + 1
+        );
+        assert_ne!(std::fs::read(&path).unwrap(), saved);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn verifier_key_serialization() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let verifier_key: JoltHyperKZGVerifierKey = preprocessing.verifier_key();
+        let bytes = verifier_key.serialize_to_bytes().unwrap();
+        let loaded = JoltHyperKZGVerifierKey::deserialize_from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.memory_layout, preprocessing.memory_layout);
+
+        // A verifier key for another transcript is rejected by its header
+        let mut header = verifier_key.header();
+        assert_eq!(header.parameters.transcript, "keccak");
+        header.parameters.transcript = "blake3".to_string();
+        let mut mismatched = vec![];
+        header.serialize_compressed(&mut mismatched).unwrap();
+        verifier_key.serialize_compressed(&mut mismatched).unwrap();
+        let err = JoltHyperKZGVerifierKey::deserialize_from_bytes(&mismatched).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<FormatError>(),
+            Some(FormatError::IncompatibleParameters(_))
+        ));
+
+        let (proof, commitments, debug_info) = <RV32IJoltVM as Jolt<
+            Fr,
+            HyperKZG<Bn254, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, &preprocessing);
+        assert!(RV32IJoltVM::verify(&loaded, proof, commitments, debug_info).is_ok());
+    }
+
     #[test]
     fn fib_e2e_mock() {
         fib_e2e::<Fr, MockCommitScheme<Fr, KeccakTranscript>, KeccakTranscript>();
@@ -339,6 +1044,157 @@ mod tests {
         fib_e2e::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>();
     }
 
+    #[test]
+    fn fib_hyperkzg_abi_encoding() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<
+            Fr,
+            HyperKZG<Bn254, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, &preprocessing);
+        let proof = JoltHyperKZGProof::new(proof, commitments, &preprocessing);
+
+        let encoded = proof.serialize_to_abi().unwrap();
+        assert_eq!(encoded.len() % 32, 0);
+        let report = proof.calldata_report().unwrap();
+        assert_eq!(report.size(), encoded.len());
+        assert!(report.size() > proof.size().unwrap());
+
+        // The ABI encoding round-trips, and the decoded proof verifies
+        let verifier_key = preprocessing.verifier_key();
+        let decoded = JoltHyperKZGProof::deserialize_from_abi(&encoded, &verifier_key).unwrap();
+        assert_eq!(decoded.serialize_to_abi().unwrap(), encoded);
+        let verification_result =
+            RV32IJoltVM::verify(&verifier_key, decoded.proof, decoded.commitments, None);
+        assert!(
+            verification_result.is_ok(),
+            "{:?}",
+            verification_result.err()
+        );
+        assert!(JoltHyperKZGProof::deserialize_from_abi(
+            &encoded[..encoded.len() - 32],
+            &verifier_key
+        )
+        .is_err());
+
+        let size_report = proof.size_report();
+        assert_eq!(size_report.total(), proof.size().unwrap());
+        assert!([
+            size_report.program_io,
+            size_report.bytecode,
+            size_report.read_write_memory,
+            size_report.instruction_lookups,
+            size_report.r1cs,
+            size_report.openings,
+            size_report.commitments,
+        ]
+        .iter()
+        .all(|&size| size > 0));
+        let rendered = size_report.to_string();
+        assert!(rendered.starts_with(&format!("{} bytes\n", size_report.total())));
+        assert!(rendered.contains(&format!("  {:<20} {:>9} (", "R1CS", size_report.r1cs)));
+
+        #[cfg(feature = "json")]
+        {
+            let json = proof.to_json();
+            let commitments = json["commitments"].as_array().unwrap();
+            // Every commitment, including v_advice's (the memory image's is in the
+            // verifier key)
+            assert_eq!(
+                commitments.len(),
+                proof.commitments.read_write_values().len()
+                    + proof.commitments.init_final_values().len()
+                    + 1
+            );
+            assert_eq!(commitments[0].as_array().unwrap().len(), 2);
+            assert_eq!(json["traceLength"].as_str().unwrap().len(), 2 + 64);
+            assert!(json["bytecode"]["readWriteGrandProduct"]["quarkProof"].is_null());
+        }
+
+        let hex = proof.to_hex().unwrap();
+        assert_eq!(hex.len(), 2 * proof.serialize_to_bytes().unwrap().len());
+
+        // The verifier checks the opening proof's group elements, not just the commitments
+        let JoltHyperKZGProof {
+            proof: mut tampered,
+            commitments,
+            ..
+        } = proof;
+        tampered.opening_proof.joint_opening_proof.com[0] =
+            G1Affine::new_unchecked(Fq::one(), Fq::one());
+        let verification_result = RV32IJoltVM::verify(&verifier_key, tampered, commitments, None);
+        assert!(matches!(
+            verification_result,
+            Err(ProofVerifyError::InvalidProofElement)
+        ));
+    }
+
+    #[test]
+    fn verifier_service_errors() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let (io_device, trace, preprocessing) = fib_fixture::<Fr, PCS, KeccakTranscript>(false);
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+            io_device,
+            trace,
+            &preprocessing,
+        );
+        let bytes = JoltHyperKZGProof::new(proof, commitments, &preprocessing)
+            .serialize_to_bytes()
+            .unwrap();
+        let proof = || JoltHyperKZGProof::deserialize_from_bytes(&bytes).unwrap();
+
+        let mut registry = ProgramRegistry::default();
+        registry.insert("fibonacci", preprocessing);
+        let service = VerifierService::new(registry, BatchConfig::default());
+        service.verify("fibonacci", proof()).unwrap();
+        assert!(matches!(
+            service.verify("sha3", proof()),
+            Err(VerifierServiceError::UnknownProgram(_))
+        ));
+
+        let mut relaid = proof();
+        relaid.proof.program_io.memory_layout.max_output_size += 4096;
+        assert!(matches!(
+            service.verify("fibonacci", relaid),
+            Err(VerifierServiceError::MemoryLayoutMismatch(_))
+        ));
+
+        // A valid group element in place of an opening witness passes every check
+        // except the deferred pairing check
+        let mut tampered = proof();
+        tampered.proof.opening_proof.joint_opening_proof.w[0] = G1Affine::generator();
+        assert!(matches!(
+            service.verify("fibonacci", tampered),
+            Err(VerifierServiceError::PairingCheckFailed)
+        ));
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_blake3() {
+        fib_e2e::<Fr, HyperKZG<Bn254, Blake3Transcript>, Blake3Transcript>();
+    }
+
+    #[test]
+    fn fib_e2e_hyrax() {
+        fib_e2e::<Fr, HyraxScheme<G1Projective, KeccakTranscript>, KeccakTranscript>();
+    }
+
     // TODO(sragss): Finish Binius.
     // #[test]
     // fn fib_e2e_binius() {
@@ -371,11 +1227,15 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
+            io_device, trace, &preprocessing
         );
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -408,11 +1268,55 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
+            io_device, trace, &preprocessing
         );
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn sha3_e2e_hyrax() {
+        let guard = SHA3_FILE_LOCK.lock().unwrap();
+
+        let mut program = host::Program::new("sha3-guest");
+        program.set_input(&[5u8; 32]);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(guard);
+
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode.clone(),
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let (jolt_proof, jolt_commitments, debug_info) =
+            <RV32IJoltVM as Jolt<
+                _,
+                HyraxScheme<G1Projective, KeccakTranscript>,
+                C,
+                M,
+                KeccakTranscript,
+            >>::prove(io_device, trace, &preprocessing);
+
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -441,11 +1345,127 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
+            io_device, trace, &preprocessing
         );
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn misaligned_ops_e2e() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let mut program = host::Program::new("misaligned-ops-guest");
+        // As `#[jolt::provable(misaligned_accesses)]` does for the guest's own host
+        program.set_misaligned_accesses(true);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+
+        // An LW one byte past a word boundary, an SW straddling two words and LWs of the
+        // words it wrote
+        let (loaded, stored, overlap): (u32, u32, u32) =
+            postcard::from_bytes(&io_device.outputs).unwrap();
+        assert_eq!(loaded, 0x04030201);
+        assert_eq!(stored, 0x12345678);
+        assert_eq!(overlap, 0x09080712);
+
+        let preprocessing = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+                io_device,
+                trace,
+                &preprocessing,
+            );
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    /// Traces the cycle counter guest, returning its preprocessing, I/O and trace along
+    /// with the indices of its `rdcycle` steps.
+    fn trace_cycle_counter() -> (
+        JoltPreprocessing<C, Fr, MockCommitScheme<Fr, KeccakTranscript>, KeccakTranscript>,
+        JoltDevice,
+        Vec<JoltTraceStep<RV32I>>,
+        Vec<usize>,
+    ) {
+        let guard = CYCLE_COUNTER_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("cycle-counter-guest");
+        program.set_input(&100u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(guard);
+
+        let rdcycle_steps = trace
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.circuit_flags[CircuitFlags::CycleCounter as usize])
+            .map(|(index, _)| index)
+            .collect();
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        (preprocessing, io_device, trace, rdcycle_steps)
+    }
+
+    #[test]
+    fn cycle_counter_e2e() {
+        let (preprocessing, io_device, trace, rdcycle_steps) = trace_cycle_counter();
+
+        // Each `rdcycle` reads its own index in the trace
+        assert_eq!(rdcycle_steps.len(), 2);
+        for &index in &rdcycle_steps {
+            assert_eq!(
+                trace[index].instruction_lookup,
+                Some(RV32I::VIRTUAL_ADVICE(ADVICEInstruction(index as u64)))
+            );
+        }
+        let (sum, cycles): (u32, u32) = postcard::from_bytes(&io_device.outputs).unwrap();
+        assert_eq!(sum, 5050);
+        assert_eq!(cycles as usize, rdcycle_steps[1] - rdcycle_steps[0]);
+
+        let (proof, commitments, debug_info) = <RV32IJoltVM as Jolt<
+            Fr,
+            MockCommitScheme<Fr, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, &preprocessing);
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -453,6 +1473,228 @@ mod tests {
         );
     }
 
+    #[test]
+    #[should_panic]
+    fn forged_cycle_counter() {
+        let (preprocessing, io_device, mut trace, rdcycle_steps) = trace_cycle_counter();
+
+        // Claim that the first `rdcycle` ran one step later than it did
+        let index = rdcycle_steps[0];
+        trace[index].instruction_lookup =
+            Some(RV32I::VIRTUAL_ADVICE(ADVICEInstruction(index as u64 + 1)));
+
+        let (proof, commitments, debug_info) = <RV32IJoltVM as Jolt<
+            Fr,
+            MockCommitScheme<Fr, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, &preprocessing);
+        let _verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+    }
+
+    /// Traces the advice tape guest summing the 16 words of its tape, returning its
+    /// preprocessing, I/O and trace.
+    fn trace_advice_sum() -> (
+        JoltPreprocessing<C, Fr, MockCommitScheme<Fr, KeccakTranscript>, KeccakTranscript>,
+        JoltDevice,
+        Vec<JoltTraceStep<RV32I>>,
+    ) {
+        let guard = ADVICE_TAPE_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("advice-tape-guest");
+        // As `#[jolt::provable(max_advice_size = 64)]` does for the guest's own host
+        program.set_max_advice_size(64);
+        program.set_input(&16u32);
+        let tape: Vec<u8> = (1..=16u32).flat_map(u32::to_le_bytes).collect();
+        program.set_advice_tape(&tape);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(guard);
+
+        let preprocessing = RV32IJoltVM::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        (preprocessing, io_device, trace)
+    }
+
+    #[test]
+    fn advice_tape_e2e() {
+        let (preprocessing, io_device, trace) = trace_advice_sum();
+        let (sum, first_word): (u32, u32) = postcard::from_bytes(&io_device.outputs).unwrap();
+        assert_eq!(sum, 136);
+        assert_eq!(first_word, sum);
+
+        let (proof, commitments, debug_info) = <RV32IJoltVM as Jolt<
+            Fr,
+            MockCommitScheme<Fr, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, &preprocessing);
+        // The proof carries a commitment to the tape, not the tape itself
+        assert!(proof.program_io.advice_tape.is_empty());
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn forged_advice_tape() {
+        let (preprocessing, mut io_device, trace) = trace_advice_sum();
+
+        // Commit to a tape whose first word differs from the one the guest read
+        io_device.advice_tape[0] ^= 1;
+
+        let (proof, commitments, debug_info) = <RV32IJoltVM as Jolt<
+            Fr,
+            MockCommitScheme<Fr, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, &preprocessing);
+        let _verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+    }
+
+    /// Traces `func` of the ML ops guest on `input`, then proves and verifies it,
+    /// returning the guest's I/O and trace.
+    fn ml_ops_e2e<T: serde::Serialize>(
+        func: &str,
+        input: &T,
+    ) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>) {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let guard = ML_OPS_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("ml-ops-guest");
+        program.set_func(func);
+        program.set_input(input);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(guard);
+
+        let preprocessing = <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+                io_device.clone(),
+                trace.clone(),
+                &preprocessing,
+            );
+        let verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+        (io_device, trace)
+    }
+
+    #[test]
+    fn relu_argmax_e2e() {
+        let (io_device, trace) = ml_ops_e2e("classify", &[-3i32, 7, 0, 12, -40, 12, 5, 1]);
+
+        // ReLU and argmax are single lookups
+        let count = |matches: fn(&RV32I) -> bool| {
+            trace
+                .iter()
+                .filter(|step| step.instruction_lookup.as_ref().is_some_and(matches))
+                .count()
+        };
+        assert_eq!(count(|lookup| matches!(lookup, RV32I::RELU(_))), 8);
+        assert_eq!(count(|lookup| matches!(lookup, RV32I::ARGMAX(_))), 7);
+        let (activations, class): ([i32; 8], u32) =
+            postcard::from_bytes(&io_device.outputs).unwrap();
+        assert_eq!(activations, [0, 7, 0, 12, 0, 12, 5, 1]);
+        assert_eq!(class, 3);
+    }
+
+    #[test]
+    fn decision_tree_e2e() {
+        // Right at the root (12 > 10), right again (150 > 100), then left (-7 <= -1)
+        let (io_device, _) = ml_ops_e2e("predict", &[12i32, 0, 150, -7]);
+        let prediction: i32 = postcard::from_bytes(&io_device.outputs).unwrap();
+        assert_eq!(prediction, 45);
+    }
+
+    #[test]
+    fn fixed_point_e2e() {
+        let q16 = |x: f64| (x * 65536.0) as i32;
+        let a = [1.5, -2.25, 0.5, 4.0].map(q16);
+        let b = [2.0, 1.0, -0.25, 0.125].map(q16);
+        let (io_device, _) = ml_ops_e2e("dot_q16", &(a, b));
+        let dot: i32 = postcard::from_bytes(&io_device.outputs).unwrap();
+        assert_eq!(dot, q16(1.125));
+    }
+
+    #[test]
+    fn softmax_e2e() {
+        // 1.0, 2.0, -0.5 and 0.25 in Q8.8
+        let logits = [256i16, 512, -128, 64];
+        let (io_device, trace) = ml_ops_e2e("softmax", &logits);
+
+        // One exp lookup per logit
+        let exps = trace
+            .iter()
+            .filter(|step| matches!(step.instruction_lookup, Some(RV32I::EXP(_))))
+            .count();
+        assert_eq!(exps, 4);
+        let probabilities: [u32; 4] = postcard::from_bytes(&io_device.outputs).unwrap();
+        let total: f64 = logits.iter().map(|&x| (x as f64 / 256.0).exp()).sum();
+        for (x, p) in logits.into_iter().zip(probabilities) {
+            let expected = (x as f64 / 256.0).exp() / total * 65536.0;
+            // Within (N + 3) / 2 units of 2^-16, as `jolt::softmax` documents
+            assert!((p as f64 - expected).abs() <= 3.5, "{p} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn gradient_boost_e2e() {
+        // The trees score 2.0 (right, right) and 0.5 (left, right)
+        let (io_device, trace) = ml_ops_e2e("boosted_probability", &[12i32, 0, 150, -7]);
+
+        let sigmoids = trace
+            .iter()
+            .filter(|step| matches!(step.instruction_lookup, Some(RV32I::SIGMOID(_))))
+            .count();
+        assert_eq!(sigmoids, 1);
+        let probability: u32 = postcard::from_bytes(&io_device.outputs).unwrap();
+        // sigmoid(2.5) = 0.92414..., in Q16.16
+        assert_eq!(probability, 60565);
+    }
+
     #[test]
     #[should_panic]
     fn truncated_trace() {
@@ -479,11 +1721,13 @@ mod tests {
             C,
             M,
             KeccakTranscript,
-        >>::prove(
-            io_device, trace, preprocessing.clone()
+        >>::prove(io_device, trace, &preprocessing);
+        let _verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
         );
-        let _verification_result =
-            RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
     }
 
     #[test]
@@ -518,10 +1762,12 @@ mod tests {
             C,
             M,
             KeccakTranscript,
-        >>::prove(
-            io_device, trace, preprocessing.clone()
+        >>::prove(io_device, trace, &preprocessing);
+        let _verification_result = RV32IJoltVM::verify(
+            &preprocessing.verifier_key(),
+            proof,
+            commitments,
+            debug_info,
         );
-        let _verification_result =
-            RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
     }
 }
@@ -0,0 +1,83 @@
+//! The bytes a serialized Jolt proof spends on each of its components (see
+//! `JoltProof::size_report`).
+use std::fmt;
+
+/// Size in bytes of each component of a compressed `JoltProof` and its commitments.
+/// The sizes add up to that of the proof and commitments serialized together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofSizeReport {
+    /// The trace length and program I/O.
+    pub program_io: usize,
+    pub bytecode: usize,
+    pub read_write_memory: usize,
+    pub instruction_lookups: usize,
+    pub r1cs: usize,
+    /// The batched opening proof for the polynomial openings claimed by all other
+    /// components.
+    pub openings: usize,
+    pub commitments: usize,
+}
+
+impl ProofSizeReport {
+    fn components(&self) -> [(&'static str, usize); 7] {
+        [
+            ("program I/O", self.program_io),
+            ("bytecode", self.bytecode),
+            ("read-write memory", self.read_write_memory),
+            ("instruction lookups", self.instruction_lookups),
+            ("R1CS", self.r1cs),
+            ("openings", self.openings),
+            ("commitments", self.commitments),
+        ]
+    }
+
+    pub fn total(&self) -> usize {
+        self.components().iter().map(|(_, size)| size).sum()
+    }
+}
+
+impl fmt::Display for ProofSizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        write!(f, "{total} bytes")?;
+        for (name, size) in self.components() {
+            let percent = if total == 0 {
+                0.0
+            } else {
+                100.0 * size as f64 / total as f64
+            };
+            write!(f, "\n  {name:<20} {size:>9} ({percent:.1}%)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let report = ProofSizeReport {
+            program_io: 100,
+            bytecode: 200,
+            read_write_memory: 300,
+            instruction_lookups: 150,
+            r1cs: 50,
+            openings: 100,
+            commitments: 100,
+        };
+        assert_eq!(report.total(), 1000);
+        assert_eq!(
+            report.to_string(),
+            "1000 bytes
+  program I/O                100 (10.0%)
+  bytecode                   200 (20.0%)
+  read-write memory          300 (30.0%)
+  instruction lookups        150 (15.0%)
+  R1CS                        50 (5.0%)
+  openings                   100 (10.0%)
+  commitments                100 (10.0%)"
+        );
+    }
+}
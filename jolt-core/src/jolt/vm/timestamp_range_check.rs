@@ -283,6 +283,7 @@ where
         _: &NoPreprocessing,
         _: &Self::Polynomials,
         _: &JoltPolynomials<F>,
+        _: <Self::ReadWriteGrandProduct as BatchedGrandProduct<F, PCS, ProofTranscript>>::Config,
         _: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         _: &mut ProofTranscript,
     ) -> MemoryCheckingProof<F, PCS, Self::Openings, Self::ExogenousOpenings, ProofTranscript> {
@@ -519,7 +520,7 @@ where
 
     fn verify_memory_checking(
         _: &NoPreprocessing,
-        _: &PCS::Setup,
+        _: &PCS::VerifierSetup,
         mut _proof: MemoryCheckingProof<
             F,
             PCS,
@@ -529,6 +530,7 @@ where
         >,
         _commitments: &Self::Commitments,
         _: &JoltCommitments<PCS, ProofTranscript>,
+        _: &[&PCS::Commitment],
         _opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         _transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
@@ -649,7 +651,7 @@ where
         _claims: &[F],
         _opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         _transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
+        _setup: Option<&PCS::VerifierSetup>,
     ) -> (F, Vec<F>) {
         unimplemented!("init/final grand products are batched with read/write grand products")
     }
@@ -662,10 +664,10 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
-    multiset_hashes: MultisetHashes<F>,
-    openings: TimestampRangeCheckOpenings<F>,
-    exogenous_openings: ReadTimestampOpenings<F>,
-    batched_grand_product: BatchedGrandProductProof<PCS, ProofTranscript>,
+    pub(crate) multiset_hashes: MultisetHashes<F>,
+    pub(crate) openings: TimestampRangeCheckOpenings<F>,
+    pub(crate) exogenous_openings: ReadTimestampOpenings<F>,
+    pub(crate) batched_grand_product: BatchedGrandProductProof<PCS, ProofTranscript>,
 }
 
 impl<F, PCS, ProofTranscript> TimestampValidityProof<F, PCS, ProofTranscript>
@@ -795,7 +797,7 @@ where
 
     pub fn verify(
         &mut self,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
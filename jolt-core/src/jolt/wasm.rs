@@ -0,0 +1,30 @@
+//! wasm-bindgen bindings for verifying `JoltHyperKZGProof`s in the browser or a
+//! light client. Build jolt-core for `wasm32-unknown-unknown` without the `fs`
+//! and `host` features, from a `cdylib` crate that depends on it.
+//!
+//! rayon needs no special handling: on `wasm32-unknown-unknown` its global pool
+//! cannot spawn threads, so parallel iterators run on the calling thread.
+use wasm_bindgen::prelude::*;
+
+use crate::jolt::vm::rv32i_vm::{
+    JoltHyperKZGProof, JoltHyperKZGVerifierKey, RV32IJoltVM, Serializable,
+};
+use crate::jolt::vm::Jolt;
+
+/// Verifies a compressed `JoltHyperKZGProof` against a compressed
+/// `JoltHyperKZGVerifierKey`, as written by `Serializable::serialize_to_bytes`.
+/// Returns `false` if either fails to deserialize.
+#[wasm_bindgen]
+pub fn verify_bytes(verifier_key_bytes: &[u8], proof_bytes: &[u8]) -> bool {
+    let Ok(verifier_key) = JoltHyperKZGVerifierKey::deserialize_from_bytes(verifier_key_bytes)
+    else {
+        return false;
+    };
+    let Ok(proof) = JoltHyperKZGProof::deserialize_from_bytes(proof_bytes) else {
+        return false;
+    };
+    if proof.proof.program_io.memory_layout != verifier_key.memory_layout {
+        return false;
+    }
+    RV32IJoltVM::verify(&verifier_key, proof.proof, proof.commitments, None).is_ok()
+}
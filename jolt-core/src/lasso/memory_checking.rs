@@ -14,6 +14,7 @@ use crate::{
     poly::commitment::commitment_scheme::CommitmentScheme,
     subprotocols::grand_product::{
         BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductProof,
+        SelectableGrandProduct,
     },
 };
 
@@ -74,6 +75,12 @@ where
 /// the verifier can compute the opening on its own.
 pub type VerifierComputedOpening<T> = Option<T>;
 
+/// This type, used within a `StructuredPolynomialData` struct, indicates that the
+/// field has a corresponding opening, but its polynomial and commitment are computed in
+/// preprocessing rather than per proof: the prover derives the polynomial from its
+/// preprocessing, and the verifier takes the commitment from its key.
+pub type PreprocessedOpening<T> = Option<T>;
+
 /// This trait is used to capture the relationship between polynomials, commitments, and
 /// openings in offline memory-checking. For a given offline memory-checking instance,
 /// the "shape" of its polynomials, commitments, and openings is the same. We can define a
@@ -210,8 +217,10 @@ where
     ProofTranscript: Transcript,
     Self: Sync,
 {
+    /// By default, the read and write multisets (which grow with the trace) are proven with
+    /// the `GrandProductStrategy` passed to `prove_memory_checking`.
     type ReadWriteGrandProduct: BatchedGrandProduct<F, PCS, ProofTranscript> + Send + 'static =
-        BatchedDenseGrandProduct<F>;
+        SelectableGrandProduct<F, ProofTranscript>;
     type InitFinalGrandProduct: BatchedGrandProduct<F, PCS, ProofTranscript> + Send + 'static =
         BatchedDenseGrandProduct<F>;
 
@@ -226,12 +235,18 @@ where
     type MemoryTuple: Copy + Clone = (F, F, F);
 
     #[tracing::instrument(skip_all, name = "MemoryCheckingProver::prove_memory_checking")]
-    /// Generates a memory checking proof for the given committed polynomials.
+    /// Generates a memory checking proof for the given committed polynomials, constructing
+    /// the read-write grand product with `read_write_config`.
     fn prove_memory_checking(
         pcs_setup: &PCS::Setup,
         preprocessing: &Self::Preprocessing,
         polynomials: &Self::Polynomials,
         jolt_polynomials: &JoltPolynomials<F>,
+        read_write_config: <Self::ReadWriteGrandProduct as BatchedGrandProduct<
+            F,
+            PCS,
+            ProofTranscript,
+        >>::Config,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> MemoryCheckingProof<F, PCS, Self::Openings, Self::ExogenousOpenings, ProofTranscript> {
@@ -245,6 +260,7 @@ where
             preprocessing,
             polynomials,
             jolt_polynomials,
+            read_write_config,
             opening_accumulator,
             transcript,
             pcs_setup,
@@ -263,7 +279,7 @@ where
         let (_, r_init_final_opening) =
             r_init_final.split_at(init_final_batch_size.next_power_of_two().log_2());
 
-        let (openings, exogenous_openings) = Self::compute_openings(
+        let (mut openings, exogenous_openings) = Self::compute_openings(
             preprocessing,
             opening_accumulator,
             polynomials,
@@ -272,6 +288,14 @@ where
             r_init_final_opening,
             transcript,
         );
+        Self::compute_preprocessed_openings(
+            preprocessing,
+            opening_accumulator,
+            polynomials,
+            &mut openings,
+            r_init_final_opening,
+            transcript,
+        );
 
         MemoryCheckingProof {
             multiset_hashes,
@@ -288,6 +312,11 @@ where
         preprocessing: &Self::Preprocessing,
         polynomials: &Self::Polynomials,
         jolt_polynomials: &JoltPolynomials<F>,
+        read_write_config: <Self::ReadWriteGrandProduct as BatchedGrandProduct<
+            F,
+            PCS,
+            ProofTranscript,
+        >>::Config,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
         pcs_setup: &PCS::Setup,
@@ -307,8 +336,12 @@ where
 
         let (read_write_leaves, init_final_leaves) =
             Self::compute_leaves(preprocessing, polynomials, jolt_polynomials, &gamma, &tau);
-        let (mut read_write_circuit, read_write_hashes) =
-            Self::read_write_grand_product(preprocessing, polynomials, read_write_leaves);
+        let (mut read_write_circuit, read_write_hashes) = Self::read_write_grand_product(
+            preprocessing,
+            polynomials,
+            read_write_leaves,
+            read_write_config,
+        );
         let (mut init_final_circuit, init_final_hashes) =
             Self::init_final_grand_product(preprocessing, polynomials, init_final_leaves);
 
@@ -401,6 +434,22 @@ where
         (openings, exogenous_openings)
     }
 
+    /// Some memories are initialized from a polynomial committed to in preprocessing
+    /// rather than as part of the witness, or from one the prover commits to apart from
+    /// the init/final values (e.g. one spanning only part of memory). This function
+    /// opens any such polynomials in `polynomials` at (a suffix of) `r_init_final`,
+    /// populating the corresponding fields of `openings`; see
+    /// `MemoryCheckingVerifier::verify_preprocessed_openings`.
+    fn compute_preprocessed_openings(
+        _preprocessing: &Self::Preprocessing,
+        _opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
+        _polynomials: &Self::Polynomials,
+        _openings: &mut Self::Openings,
+        _r_init_final: &[F],
+        _transcript: &mut ProofTranscript,
+    ) {
+    }
+
     /// Constructs a batched grand product circuit for the read and write multisets associated
     /// with the given leaves. Also returns the corresponding multiset hashes for each memory.
     #[tracing::instrument(skip_all, name = "MemoryCheckingProver::read_write_grand_product")]
@@ -412,8 +461,14 @@ where
             PCS,
             ProofTranscript,
         >>::Leaves,
+        config: <Self::ReadWriteGrandProduct as BatchedGrandProduct<
+            F,
+            PCS,
+            ProofTranscript,
+        >>::Config,
     ) -> (Self::ReadWriteGrandProduct, Vec<F>) {
-        let batched_circuit = Self::ReadWriteGrandProduct::construct(read_write_leaves);
+        let batched_circuit =
+            Self::ReadWriteGrandProduct::construct_with_config(read_write_leaves, config);
         let claims = batched_circuit.claimed_outputs();
         (batched_circuit, claims)
     }
@@ -529,10 +584,12 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
-    /// Verifies a memory checking proof, given its associated polynomial `commitment`.
+    /// Verifies a memory checking proof, given its associated polynomial `commitment`
+    /// and the commitments made in preprocessing, if any (see
+    /// `verify_preprocessed_openings`).
     fn verify_memory_checking(
         preprocessing: &Self::Preprocessing,
-        pcs_setup: &PCS::Setup,
+        pcs_setup: &PCS::VerifierSetup,
         mut proof: MemoryCheckingProof<
             F,
             PCS,
@@ -542,6 +599,7 @@ where
         >,
         commitments: &Self::Commitments,
         jolt_commitments: &JoltCommitments<PCS, ProofTranscript>,
+        preprocessed_commitments: &[&PCS::Commitment],
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
@@ -611,6 +669,15 @@ where
             &proof.openings.init_final_values(),
             transcript,
         );
+        Self::verify_preprocessed_openings(
+            preprocessing,
+            commitments,
+            preprocessed_commitments,
+            &proof.openings,
+            opening_accumulator,
+            r_init_final_opening,
+            transcript,
+        )?;
 
         Self::compute_verifier_openings(
             &mut proof.openings,
@@ -634,6 +701,23 @@ where
         Ok(())
     }
 
+    /// Verifier counterpart of `MemoryCheckingProver::compute_preprocessed_openings`:
+    /// appends the openings of the polynomials committed to in preprocessing (whose
+    /// commitments are `preprocessed_commitments`, from the verifier key) or apart from
+    /// the init/final values to `opening_accumulator`, failing if they cannot be opened
+    /// at `r_init_final`.
+    fn verify_preprocessed_openings(
+        _preprocessing: &Self::Preprocessing,
+        _commitments: &Self::Commitments,
+        _preprocessed_commitments: &[&PCS::Commitment],
+        _openings: &Self::Openings,
+        _opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
+        _r_init_final: &[F],
+        _transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        Ok(())
+    }
+
     /// Often some of the openings do not require an opening proof provided by the prover, and
     /// instead can be efficiently computed by the verifier by itself. This function populates
     /// any such fields in `self`.
@@ -21,7 +21,7 @@ use crate::{
         commitment::commitment_scheme::CommitmentScheme, dense_mlpoly::DensePolynomial,
         eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
     },
-    subprotocols::sumcheck::SumcheckInstanceProof,
+    subprotocols::{grand_product::GrandProductStrategy, sumcheck::SumcheckInstanceProof},
     utils::{errors::ProofVerifyError, math::Math, transcript::Transcript},
 };
 
@@ -398,12 +398,24 @@ where
         )
     }
 
-    #[tracing::instrument(skip_all, name = "Surge::prove")]
     pub fn prove(
         preprocessing: &SurgePreprocessing<F, Instruction, C, M>,
         generators: &PCS::Setup,
         ops: Vec<Instruction>,
-    ) -> (Self, Option<ProverDebugInfo<F, ProofTranscript>>) {
+    ) -> (Self, Option<ProverDebugInfo<F, PCS, ProofTranscript>>) {
+        Self::prove_streaming(preprocessing, generators, ops, 0)
+    }
+
+    /// Like `prove`, but computes the first `streaming_rounds` rounds of the primary
+    /// sumcheck by streaming over the E polynomials rather than binding a copy of them (see
+    /// `SumcheckInstanceProof::prove_arbitrary_streaming`). The proof is the same.
+    #[tracing::instrument(skip_all, name = "Surge::prove")]
+    pub fn prove_streaming(
+        preprocessing: &SurgePreprocessing<F, Instruction, C, M>,
+        generators: &PCS::Setup,
+        ops: Vec<Instruction>,
+        streaming_rounds: usize,
+    ) -> (Self, Option<ProverDebugInfo<F, PCS, ProofTranscript>>) {
         let mut transcript = ProofTranscript::new(b"Surge transcript");
         let mut opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
             ProverOpeningAccumulator::new();
@@ -434,8 +446,6 @@ where
         let sumcheck_claim: F = Self::compute_primary_sumcheck_claim(&polynomials, &eq);
 
         transcript.append_scalar(&sumcheck_claim);
-        let mut combined_sumcheck_polys = polynomials.E_polys.clone();
-        combined_sumcheck_polys.push(eq);
 
         let combine_lookups_eq = |vals: &[F]| -> F {
             let vals_no_eq: &[F] = &vals[0..(vals.len() - 1)];
@@ -443,18 +453,36 @@ where
             instruction.combine_lookups(vals_no_eq, C, M) * eq
         };
 
-        let (primary_sumcheck_proof, r_z, mut sumcheck_openings) =
-            SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary::<_>(
-                &sumcheck_claim,
-                num_rounds,
-                &mut combined_sumcheck_polys,
-                combine_lookups_eq,
-                instruction.g_poly_degree(C) + 1, // combined degree + eq term
-                &mut transcript,
-            );
+        let (primary_sumcheck_proof, r_z, mut sumcheck_openings) = match streaming_rounds {
+            0 => {
+                let mut combined_sumcheck_polys = polynomials.E_polys.clone();
+                combined_sumcheck_polys.push(eq);
+                SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary::<_>(
+                    &sumcheck_claim,
+                    num_rounds,
+                    &mut combined_sumcheck_polys,
+                    combine_lookups_eq,
+                    instruction.g_poly_degree(C) + 1, // combined degree + eq term
+                    &mut transcript,
+                )
+            }
+            // Stream over the E polynomials rather than binding a copy of them
+            _ => {
+                let combined_sumcheck_polys: Vec<_> =
+                    polynomials.E_polys.iter().chain([&eq]).collect();
+                SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary_streaming(
+                    &sumcheck_claim,
+                    num_rounds,
+                    &combined_sumcheck_polys,
+                    combine_lookups_eq,
+                    instruction.g_poly_degree(C) + 1, // combined degree + eq term
+                    streaming_rounds,
+                    &mut transcript,
+                )
+            }
+        };
 
         // Remove EQ
-        let _ = combined_sumcheck_polys.pop();
         let _ = sumcheck_openings.pop();
         opening_accumulator.append(
             &polynomials.E_polys.iter().collect::<Vec<_>>(),
@@ -477,6 +505,7 @@ where
             preprocessing,
             &polynomials,
             &JoltPolynomials::default(), // Hack: required by the memory-checking trait, but unused in Surge
+            GrandProductStrategy::Gkr,
             &mut opening_accumulator,
             &mut transcript,
         );
@@ -491,6 +520,7 @@ where
         let debug_info = Some(ProverDebugInfo {
             transcript,
             opening_accumulator,
+            pcs_setup: generators.clone(),
         });
         #[cfg(not(test))]
         let debug_info = None;
@@ -500,9 +530,9 @@ where
 
     pub fn verify(
         preprocessing: &SurgePreprocessing<F, Instruction, C, M>,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         proof: SurgeProof<F, PCS, Instruction, C, M, ProofTranscript>,
-        _debug_info: Option<ProverDebugInfo<F, ProofTranscript>>,
+        _debug_info: Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
     ) -> Result<(), ProofVerifyError> {
         let mut transcript = ProofTranscript::new(b"Surge transcript");
         let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS, ProofTranscript> =
@@ -510,7 +540,7 @@ where
         #[cfg(test)]
         if let Some(debug_info) = _debug_info {
             transcript.compare_to(debug_info.transcript);
-            opening_accumulator.compare_to(debug_info.opening_accumulator, &generators);
+            opening_accumulator.compare_to(debug_info.opening_accumulator, &debug_info.pcs_setup);
         }
 
         let protocol_name = Self::protocol_name();
@@ -552,6 +582,7 @@ where
             proof.memory_checking,
             &proof.commitments,
             &JoltCommitments::<PCS, ProofTranscript>::default(),
+            &[],
             &mut opening_accumulator,
             &mut transcript,
         )
@@ -704,7 +735,48 @@ mod tests {
             KeccakTranscript,
         >::prove(&preprocessing, &generators, ops);
 
-        SurgeProof::verify(&preprocessing, &generators, proof, debug_info).expect("should work");
+        SurgeProof::verify(&preprocessing, &generators.1, proof, debug_info).expect("should work");
+    }
+
+    #[test]
+    fn surge_32_e2e_streaming() {
+        let mut rng = test_rng();
+        const WORD_SIZE: usize = 32;
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const NUM_OPS: usize = 1024;
+
+        let ops: Vec<_> = std::iter::repeat_with(|| {
+            XORInstruction::<WORD_SIZE>(rng.next_u32() as u64, rng.next_u32() as u64)
+        })
+        .take(NUM_OPS)
+        .collect();
+
+        let preprocessing = SurgePreprocessing::preprocess();
+        let generators = HyperKZG::<_, KeccakTranscript>::setup(M);
+        type Surge = SurgeProof<
+            Fr,
+            HyperKZG<Bn254, KeccakTranscript>,
+            XORInstruction<WORD_SIZE>,
+            C,
+            M,
+            KeccakTranscript,
+        >;
+        let (proof, debug_info) =
+            Surge::prove_streaming(&preprocessing, &generators, ops.clone(), 3);
+        let (expected, _) = Surge::prove(&preprocessing, &generators, ops);
+        let coeffs = |proof: &Surge| -> Vec<Vec<Fr>> {
+            proof
+                .primary_sumcheck
+                .sumcheck_proof
+                .compressed_polys
+                .iter()
+                .map(|poly| poly.coeffs_except_linear_term.clone())
+                .collect()
+        };
+        assert_eq!(coeffs(&proof), coeffs(&expected));
+
+        SurgeProof::verify(&preprocessing, &generators.1, proof, debug_info).expect("should work");
     }
 
     #[test]
@@ -733,6 +805,6 @@ mod tests {
             KeccakTranscript,
         >::prove(&preprocessing, &generators, ops);
 
-        SurgeProof::verify(&preprocessing, &generators, proof, debug_info).expect("should work");
+        SurgeProof::verify(&preprocessing, &generators.1, proof, debug_info).expect("should work");
     }
 }
@@ -1,5 +1,6 @@
 use crate::field::JoltField;
-use crate::msm::{GpuBaseType, VariableBaseMSM};
+use crate::msm::scheduler::{balance, segments, GpuPartition};
+use crate::msm::{total_memory_bits, GpuBaseType, VariableBaseMSM};
 use ark_bn254::G1Projective;
 use ark_ec::{CurveGroup, ScalarMul};
 use ark_ff::{BigInteger, Field, PrimeField};
@@ -14,6 +15,7 @@ use icicle_runtime::stream::IcicleStreamHandle;
 use icicle_runtime::{
     memory::{DeviceVec, HostSlice},
     stream::IcicleStream,
+    Device,
 };
 use rayon::prelude::*;
 use std::os::raw::c_void;
@@ -321,6 +323,149 @@ where
         .collect()
 }
 
+/// Runs `op(i)` for every `devices[i]`, each on a thread of its own whose active icicle
+/// device is `devices[i]`. Device 0, which `icicle_init` activates, is used in place.
+pub fn on_devices<R: Send>(devices: &[usize], op: impl Fn(usize) -> R + Sync) -> Vec<R> {
+    if devices == [0] {
+        return vec![op(0)];
+    }
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = devices
+            .iter()
+            .enumerate()
+            .map(|(i, &device)| {
+                let op = &op;
+                scope.spawn(move || {
+                    icicle_runtime::set_device(&Device::new("CUDA", device as i32)).unwrap();
+                    op(i)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// `icicle_msm`, split into one segment per device.
+#[tracing::instrument(skip_all)]
+pub fn icicle_msm_on_devices<V>(
+    devices: &[usize],
+    bases: &[GpuBaseType<V>],
+    scalars: &[V::ScalarField],
+    max_num_bits: usize,
+) -> V
+where
+    V: VariableBaseMSM,
+    V::ScalarField: JoltField,
+{
+    let segments = segments(scalars.len(), devices.len());
+    on_devices(&devices[..segments.len()], |i| {
+        let segment = segments[i].clone();
+        icicle_msm::<V>(&bases[segment.clone()], &scalars[segment], max_num_bits)
+    })
+    .into_iter()
+    .fold(V::zero(), |sum, result| sum + result)
+}
+
+/// `icicle_batch_msm` over batches that may not fit in GPU memory at once.
+#[tracing::instrument(skip_all)]
+pub fn icicle_chunked_batch_msm<V>(
+    bases: &[GpuBaseType<V>],
+    batch_info: &[BatchInfo<V>],
+) -> Vec<(usize, V)>
+where
+    V: VariableBaseMSM,
+    V::ScalarField: JoltField,
+{
+    if batch_info.is_empty() {
+        return vec![];
+    }
+    let slice_bit_size = 256 * batch_info[0].2.len() * 2;
+    let slices_at_a_time = (total_memory_bits() / slice_bit_size).max(1);
+
+    let mut results = Vec::with_capacity(batch_info.len());
+    for work_chunk in batch_info.chunks(slices_at_a_time) {
+        let (max_num_bits, chunk_polys): (Vec<_>, Vec<_>) = work_chunk
+            .par_iter()
+            .map(|(_, max_num_bits, poly)| (*max_num_bits, *poly))
+            .unzip();
+
+        let max_num_bits = max_num_bits.iter().max().unwrap();
+        let batch_results = icicle_batch_msm(bases, &chunk_polys, *max_num_bits);
+        results.extend(
+            work_chunk
+                .iter()
+                .zip(batch_results)
+                .map(|((index, _, _), result)| (*index, result)),
+        );
+    }
+    results
+}
+
+/// Computes the MSMs of `batch_info` across `devices`, as laid out by `partition`.
+/// Unless `variable_batches` is set, every batch must have as many scalars as `bases`.
+#[tracing::instrument(skip_all)]
+pub fn icicle_batch_msm_on_devices<V>(
+    devices: &[usize],
+    bases: &[GpuBaseType<V>],
+    batch_info: &[BatchInfo<V>],
+    variable_batches: bool,
+    partition: GpuPartition,
+) -> Vec<(usize, V)>
+where
+    V: VariableBaseMSM,
+    V::ScalarField: JoltField,
+{
+    if partition == GpuPartition::PerSegment && !variable_batches && devices.len() > 1 {
+        let segments = segments(bases.len(), devices.len());
+        let partial_results = on_devices(&devices[..segments.len()], |i| {
+            let segment = segments[i].clone();
+            let batch: Vec<_> = batch_info
+                .iter()
+                .map(|(index, max_num_bits, scalars)| {
+                    (*index, *max_num_bits, &scalars[segment.clone()])
+                })
+                .collect();
+            icicle_chunked_batch_msm(&bases[segment], &batch)
+        });
+
+        let mut results: Vec<_> = batch_info
+            .iter()
+            .map(|(index, _, _)| (*index, V::zero()))
+            .collect();
+        for device_results in partial_results {
+            for (result, (_, partial_result)) in results.iter_mut().zip(device_results) {
+                result.1 += partial_result;
+            }
+        }
+        return results;
+    }
+
+    let sizes: Vec<_> = batch_info
+        .iter()
+        .map(|(_, _, scalars)| scalars.len())
+        .collect();
+    let (devices, assignment): (Vec<_>, Vec<_>) = devices
+        .iter()
+        .copied()
+        .zip(balance(&sizes, devices.len()))
+        .filter(|(_, jobs)| !jobs.is_empty())
+        .unzip();
+    on_devices(&devices, |i| {
+        let batch: Vec<_> = assignment[i].iter().map(|&job| batch_info[job]).collect();
+        if variable_batches {
+            icicle_variable_batch_msm(bases, &batch)
+        } else {
+            icicle_chunked_batch_msm(bases, &batch)
+        }
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
 pub fn copy_offset_from_host_async<T>(
     dest: &mut DeviceVec<T>,
     src: &HostSlice<T>,
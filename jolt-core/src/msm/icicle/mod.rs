@@ -1,7 +1,7 @@
 #[cfg(not(feature = "icicle"))]
 use ark_bn254::G1Projective;
 use ark_ec::{CurveGroup, ScalarMul};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Once;
 
 #[cfg(feature = "icicle")]
@@ -11,6 +11,7 @@ pub use adapter::*;
 
 static ICICLE_INIT: Once = Once::new();
 static ICICLE_READY: AtomicBool = AtomicBool::new(false);
+static ICICLE_DEVICE_COUNT: AtomicUsize = AtomicUsize::new(0);
 
 #[cfg(feature = "icicle")]
 pub trait CurveGroupConfig: CurveGroup + Icicle {}
@@ -46,6 +47,9 @@ pub fn icicle_init() -> bool {
                 let device = icicle_runtime::Device::new("CUDA", 0);
                 if icicle_runtime::set_device(&device).is_ok() {
                     println!("icicle using device: {:?}", device);
+                    let device_count = icicle_runtime::get_device_count()
+                        .map_or(1, |device_count| device_count.max(1) as usize);
+                    ICICLE_DEVICE_COUNT.store(device_count, Ordering::Relaxed);
                     initialized = true;
                 } else {
                     println!("Failed to set CUDA device; falling back to CPU.");
@@ -69,6 +73,12 @@ pub fn icicle_init() -> bool {
     ICICLE_READY.load(Ordering::Relaxed)
 }
 
+/// The ids of the CUDA devices detected by `icicle_init`; none if icicle could not be
+/// initialized.
+pub fn icicle_devices() -> Vec<usize> {
+    (0..ICICLE_DEVICE_COUNT.load(Ordering::Relaxed)).collect()
+}
+
 /// Returns the total memory available on the system in bits.
 ///
 /// If icicle is enabled, it will return the total memory available on the GPU in bits.
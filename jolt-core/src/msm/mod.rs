@@ -7,13 +7,18 @@ use icicle_core::curve::Affine;
 use num_integer::Integer;
 use rayon::prelude::*;
 use std::borrow::Borrow;
+use std::sync::atomic::{AtomicU8, Ordering as AtomicOrdering};
 
 pub(crate) mod icicle;
+pub mod scheduler;
 use crate::field::JoltField;
 use crate::poly::multilinear_polynomial::MultilinearPolynomial;
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 pub use icicle::*;
+#[cfg(feature = "icicle")]
+use scheduler::{active_gpus, gpu_partition};
+pub use scheduler::GpuPartition;
 
 impl<F: JoltField, G: CurveGroup<ScalarField = F> + Icicle> VariableBaseMSM for G {}
 
@@ -49,6 +54,16 @@ where
             .ok_or(ProofVerifyError::KeyLengthError(bases.len(), scalars.len()))
     }
 
+    /// MSM of `bases` with a 0/1 scalar vector, given by the indices of its ones. Only
+    /// touches the bases at `indices`, so is much cheaper than `msm_u8` for very sparse
+    /// vectors (e.g. circuit and instruction flags).
+    fn msm_sparse_binary(bases: &[Self::MulBase], indices: &[usize]) -> Self {
+        indices
+            .par_iter()
+            .fold(Self::zero, |sum, &index| sum + bases[index])
+            .reduce(Self::zero, |sum, partial_sum| sum + partial_sum)
+    }
+
     fn msm_u16(
         bases: &[Self::MulBase],
         gpu_bases: Option<&[GpuBaseType<Self>]>,
@@ -148,7 +163,17 @@ where
                                     backup = Self::get_gpu_bases(bases);
                                     &backup
                                 });
-                                return icicle_msm::<Self>(gpu_bases, scalars, max_num_bits);
+                                let devices = active_gpus();
+                                let devices = match gpu_partition() {
+                                    GpuPartition::PerPolynomial => &devices[..1],
+                                    GpuPartition::PerSegment => &devices[..],
+                                };
+                                return icicle_msm_on_devices::<Self>(
+                                    devices,
+                                    gpu_bases,
+                                    scalars,
+                                    max_num_bits,
+                                );
                             }
                             #[cfg(not(feature = "icicle"))]
                             {
@@ -290,76 +315,59 @@ where
             });
         drop(_guard);
         drop(span);
-        let mut results = vec![Self::zero(); polys.len()];
-
-        // Handle CPU computations in parallel
-        let span = tracing::span!(tracing::Level::INFO, "batch_msm_cpu");
-        let _guard = span.enter();
-        let cpu_results: Vec<(usize, Self)> = cpu_batch
-            .into_par_iter()
-            .map(|(i, max_num_bits, poly)| {
-                let bases_slice = &bases[..poly.len()];
-                (
-                    i,
-                    Self::msm(bases_slice, None, poly, Some(max_num_bits)).unwrap(),
-                )
-            })
-            .collect();
-        drop(_guard);
-        drop(span);
-
-        // Store CPU results
-        for (i, result) in cpu_results {
-            results[i] = result;
-        }
 
-        // Handle GPU computations if available
-        if !gpu_batch.is_empty() && use_icicle {
-            #[cfg(feature = "icicle")]
-            {
-                let span = tracing::span!(tracing::Level::INFO, "batch_msms_gpu");
+        // The CPU computes the small MSMs while the GPUs compute the large ones
+        let (cpu_results, gpu_results) = rayon::join(
+            || {
+                let span = tracing::span!(tracing::Level::INFO, "batch_msm_cpu");
                 let _guard = span.enter();
-                let mut backup = vec![];
-                let gpu_bases = gpu_bases.unwrap_or_else(|| {
-                    backup = Self::get_gpu_bases(bases);
-                    &backup
-                });
+                cpu_batch
+                    .into_par_iter()
+                    .map(|(i, max_num_bits, poly)| {
+                        let bases_slice = &bases[..poly.len()];
+                        (
+                            i,
+                            Self::msm(bases_slice, None, poly, Some(max_num_bits)).unwrap(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            },
+            || -> Vec<(usize, Self)> {
+                if gpu_batch.is_empty() {
+                    return vec![];
+                }
+                #[cfg(feature = "icicle")]
+                {
+                    let span = tracing::span!(tracing::Level::INFO, "batch_msms_gpu");
+                    let _guard = span.enter();
+                    let mut backup = vec![];
+                    let gpu_bases = gpu_bases.unwrap_or_else(|| {
+                        backup = Self::get_gpu_bases(bases);
+                        &backup
+                    });
 
-                if variable_batches {
-                    // Variable-length batch processing
                     let batch = gpu_batch
                         .iter()
                         .map(|(i, max_num_bits, poly)| (*i, *max_num_bits, poly.as_slice()))
                         .collect::<Vec<_>>();
-                    let batched_results = icicle_variable_batch_msm(gpu_bases, &batch);
-                    for (index, result) in batched_results {
-                        results[index] = result;
-                    }
-                } else {
-                    // Fixed-length batch processing
-                    let slice_bit_size = 256 * gpu_batch[0].2.len() * 2;
-                    let slices_at_a_time = total_memory_bits() / slice_bit_size;
-
-                    for work_chunk in gpu_batch.chunks(slices_at_a_time) {
-                        let (max_num_bits, chunk_polys): (Vec<_>, Vec<_>) = work_chunk
-                            .par_iter()
-                            .map(|(_, max_num_bits, poly)| (*max_num_bits, poly.as_slice()))
-                            .unzip();
-
-                        let max_num_bits = max_num_bits.iter().max().unwrap();
-                        let batch_results =
-                            icicle_batch_msm(gpu_bases, &chunk_polys, *max_num_bits);
-
-                        for ((index, _, _), result) in work_chunk.iter().zip(batch_results) {
-                            results[*index] = result;
-                        }
-                    }
+                    return icicle_batch_msm_on_devices(
+                        &active_gpus(),
+                        gpu_bases,
+                        &batch,
+                        variable_batches,
+                        gpu_partition(),
+                    );
                 }
-            }
-            #[cfg(not(feature = "icicle"))]
-            {
-                unreachable!("icicle_init must not return true without the icicle feature");
-            }
+                #[cfg(not(feature = "icicle"))]
+                {
+                    unreachable!("icicle_init must not return true without the icicle feature");
+                }
+            },
+        );
+
+        let mut results = vec![Self::zero(); polys.len()];
+        for (i, result) in cpu_results.into_iter().chain(gpu_results) {
+            results[i] = result;
         }
         results
     }
@@ -440,56 +448,61 @@ where
             });
         drop(_guard);
         drop(span);
-        let mut results = vec![Self::zero(); polys.len()];
 
-        // Handle CPU computations in parallel
-        let span = tracing::span!(tracing::Level::INFO, "batch_msm_cpu");
-        let _guard = span.enter();
-        let cpu_results: Vec<(usize, Self)> = cpu_batch
-            .into_par_iter()
-            .map(|(i, max_num_bits, poly)| {
-                (
-                    i,
-                    Self::msm_field_elements(
-                        &bases[..poly.borrow().coeffs.len()],
-                        None,
-                        &poly.borrow().coeffs,
-                        Some(max_num_bits),
-                        false,
-                    )
-                    .unwrap(),
-                )
-            })
-            .collect();
-        drop(_guard);
-        drop(span);
-
-        // Store CPU results
-        for (i, result) in cpu_results {
-            results[i] = result;
-        }
-
-        // Handle GPU computations if available
-        if !gpu_batch.is_empty() && use_icicle {
-            #[cfg(feature = "icicle")]
-            {
-                let span = tracing::span!(tracing::Level::INFO, "batch_msms_gpu");
+        // The CPU computes the small MSMs while the GPUs compute the large ones
+        let (cpu_results, gpu_results) = rayon::join(
+            || {
+                let span = tracing::span!(tracing::Level::INFO, "batch_msm_cpu");
                 let _guard = span.enter();
-                let mut backup = vec![];
-                let gpu_bases = gpu_bases.unwrap_or_else(|| {
-                    backup = Self::get_gpu_bases(bases);
-                    &backup
-                });
-
-                let batched_results = icicle_variable_batch_msm(gpu_bases, &gpu_batch);
-                for (index, result) in batched_results {
-                    results[index] = result;
+                cpu_batch
+                    .into_par_iter()
+                    .map(|(i, max_num_bits, poly)| {
+                        (
+                            i,
+                            Self::msm_field_elements(
+                                &bases[..poly.borrow().coeffs.len()],
+                                None,
+                                &poly.borrow().coeffs,
+                                Some(max_num_bits),
+                                false,
+                            )
+                            .unwrap(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            },
+            || -> Vec<(usize, Self)> {
+                if gpu_batch.is_empty() {
+                    return vec![];
                 }
-            }
-            #[cfg(not(feature = "icicle"))]
-            {
-                unreachable!("icicle_init must not return true without the icicle feature");
-            }
+                #[cfg(feature = "icicle")]
+                {
+                    let span = tracing::span!(tracing::Level::INFO, "batch_msms_gpu");
+                    let _guard = span.enter();
+                    let mut backup = vec![];
+                    let gpu_bases = gpu_bases.unwrap_or_else(|| {
+                        backup = Self::get_gpu_bases(bases);
+                        &backup
+                    });
+
+                    return icicle_batch_msm_on_devices(
+                        &active_gpus(),
+                        gpu_bases,
+                        &gpu_batch,
+                        true,
+                        gpu_partition(),
+                    );
+                }
+                #[cfg(not(feature = "icicle"))]
+                {
+                    unreachable!("icicle_init must not return true without the icicle feature");
+                }
+            },
+        );
+
+        let mut results = vec![Self::zero(); polys.len()];
+        for (i, result) in cpu_results.into_iter().chain(gpu_results) {
+            results[i] = result;
         }
         results
     }
@@ -504,11 +517,68 @@ where
     }
 }
 
+/// Which backend to use for large MSMs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MsmBackend {
+    /// Use the GPU if the `icicle` feature is enabled and a CUDA device is available,
+    /// otherwise use the CPU.
+    Auto = 0,
+    /// Always use the CPU (Pippenger) implementation.
+    Cpu = 1,
+}
+
+/// Environment variable used to select the MSM backend: `auto` (or `gpu`) or `cpu`.
+pub const MSM_BACKEND_ENV_VAR: &str = "JOLT_MSM_BACKEND";
+
+const MSM_BACKEND_UNSET: u8 = u8::MAX;
+static MSM_BACKEND: AtomicU8 = AtomicU8::new(MSM_BACKEND_UNSET);
+
+impl std::str::FromStr for MsmBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" | "gpu" | "cuda" | "icicle" => Ok(Self::Auto),
+            "cpu" => Ok(Self::Cpu),
+            _ => Err(format!("Unknown MSM backend: {s}")),
+        }
+    }
+}
+
+/// Overrides the MSM backend for the remainder of the process.
+pub fn set_msm_backend(backend: MsmBackend) {
+    MSM_BACKEND.store(backend as u8, AtomicOrdering::Relaxed);
+}
+
+/// Returns the selected MSM backend. Unless overridden by `set_msm_backend`, this is
+/// read from the `JOLT_MSM_BACKEND` environment variable, defaulting to `Auto`.
+pub fn msm_backend() -> MsmBackend {
+    match MSM_BACKEND.load(AtomicOrdering::Relaxed) {
+        0 => MsmBackend::Auto,
+        1 => MsmBackend::Cpu,
+        _ => {
+            let backend = match std::env::var(MSM_BACKEND_ENV_VAR) {
+                Ok(value) => value.parse().unwrap_or_else(|err| {
+                    tracing::warn!("{err}; using the auto MSM backend");
+                    MsmBackend::Auto
+                }),
+                Err(_) => MsmBackend::Auto,
+            };
+            set_msm_backend(backend);
+            backend
+        }
+    }
+}
+
 pub fn use_icicle() -> bool {
-    #[cfg(feature = "icicle")]
-    return icicle_init();
-    #[cfg(not(feature = "icicle"))]
-    false
+    match msm_backend() {
+        MsmBackend::Cpu => false,
+        #[cfg(feature = "icicle")]
+        MsmBackend::Auto => icicle_init() && !active_gpus().is_empty(),
+        #[cfg(not(feature = "icicle"))]
+        MsmBackend::Auto => false,
+    }
 }
 
 fn map_field_elements_to_u16<F: PrimeField>(field_elements: &[F]) -> Vec<u16> {
@@ -733,6 +803,14 @@ fn make_digits_bigint(
     })
 }
 
+/// Number of scalars each thread handles in the small-scalar MSMs, which split their
+/// scalars into (at least `min_chunk_size`-sized) chunks and sum the chunks' results.
+fn msm_chunk_size(num_scalars: usize, min_chunk_size: usize) -> usize {
+    num_scalars
+        .div_ceil(rayon::current_num_threads())
+        .max(min_chunk_size)
+}
+
 /// Optimized implementation of multi-scalar multiplication.
 #[tracing::instrument(skip_all)]
 fn msm_medium<F, V, T>(
@@ -742,6 +820,22 @@ fn msm_medium<F, V, T>(
     max_num_bits: usize,
     _use_icicle: bool,
 ) -> V
+where
+    F: JoltField,
+    V: VariableBaseMSM<ScalarField = F>,
+    T: Into<u64> + Zero + Copy + Sync,
+{
+    // There are only a couple of windows for scalars of up to 64 bits, so the windows
+    // alone would leave most threads idle
+    let chunk_size = msm_chunk_size(scalars.len(), 1 << 12);
+    scalars
+        .par_chunks(chunk_size)
+        .zip(bases.par_chunks(chunk_size))
+        .map(|(scalars, bases)| msm_medium_chunk::<F, V, T>(bases, scalars, max_num_bits))
+        .reduce(V::zero, |sum, partial_sum| sum + partial_sum)
+}
+
+fn msm_medium_chunk<F, V, T>(bases: &[V::MulBase], scalars: &[T], max_num_bits: usize) -> V
 where
     F: JoltField,
     V: VariableBaseMSM<ScalarField = F>,
@@ -835,16 +929,23 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn msm_binary<F: JoltField, V: VariableBaseMSM<ScalarField = F>, T: Integer>(
+fn msm_binary<F: JoltField, V: VariableBaseMSM<ScalarField = F>, T: Integer + Sync>(
     bases: &[V::MulBase],
     scalars: &[T],
 ) -> V {
+    let chunk_size = msm_chunk_size(scalars.len(), 1 << 10);
     scalars
-        .iter()
-        .zip(bases)
-        .filter(|(scalar, _base)| !scalar.is_zero())
-        .map(|(_scalar, base)| base)
-        .fold(V::zero(), |sum, base| sum + base)
+        .par_chunks(chunk_size)
+        .zip(bases.par_chunks(chunk_size))
+        .map(|(scalars, bases)| {
+            scalars
+                .iter()
+                .zip(bases)
+                .filter(|(scalar, _base)| !scalar.is_zero())
+                .map(|(_scalar, base)| base)
+                .fold(V::zero(), |sum, base| sum + base)
+        })
+        .reduce(V::zero, |sum, partial_sum| sum + partial_sum)
 }
 
 #[tracing::instrument(skip_all)]
@@ -852,27 +953,36 @@ fn msm_small<F, V, T>(bases: &[V::MulBase], scalars: &[T], max_num_bits: usize)
 where
     F: JoltField,
     V: VariableBaseMSM<ScalarField = F>,
-    T: Into<u64> + Zero + Copy,
+    T: Into<u64> + Zero + Copy + Sync,
 {
     let num_buckets: usize = 1 << max_num_bits;
-    // Assign things to buckets based on the scalar
-    let mut buckets: Vec<V> = vec![V::zero(); num_buckets];
+    // Summing the buckets costs two additions per bucket, so each chunk should be
+    // considerably larger than the number of buckets
+    let chunk_size = msm_chunk_size(scalars.len(), 4 * num_buckets);
     scalars
-        .iter()
-        .zip(bases)
-        .filter(|(scalar, _base)| !scalar.is_zero())
-        .for_each(|(scalar, base)| {
-            let bucket_index: u64 = (*scalar).into();
-            buckets[bucket_index as usize] += base;
-        });
+        .par_chunks(chunk_size)
+        .zip(bases.par_chunks(chunk_size))
+        .map(|(scalars, bases)| {
+            // Assign things to buckets based on the scalar
+            let mut buckets: Vec<V> = vec![V::zero(); num_buckets];
+            scalars
+                .iter()
+                .zip(bases)
+                .filter(|(scalar, _base)| !scalar.is_zero())
+                .for_each(|(scalar, base)| {
+                    let bucket_index: u64 = (*scalar).into();
+                    buckets[bucket_index as usize] += base;
+                });
 
-    let mut result = V::zero();
-    let mut running_sum = V::zero();
-    buckets.iter().skip(1).rev().for_each(|bucket| {
-        running_sum += bucket;
-        result += running_sum;
-    });
-    result
+            let mut result = V::zero();
+            let mut running_sum = V::zero();
+            buckets.iter().skip(1).rev().for_each(|bucket| {
+                running_sum += bucket;
+                result += running_sum;
+            });
+            result
+        })
+        .reduce(V::zero, |sum, partial_sum| sum + partial_sum)
 }
 
 /// The result of this function is only approximately `ln(a)`
@@ -883,3 +993,54 @@ fn ln_without_floats(a: usize) -> usize {
     // log2(a) * ln(2)
     (ark_std::log2(a) * 69 / 100) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_ec::VariableBaseMSM as ark_VariableBaseMSM;
+    use ark_std::UniformRand;
+    use rand_core::{RngCore, SeedableRng};
+
+    #[test]
+    fn small_scalar_msms_match_arkworks() {
+        let n = 1 << 13;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(n as u64);
+        let bases: Vec<G1Affine> = std::iter::repeat_with(|| G1Affine::rand(&mut rng))
+            .take(n)
+            .collect();
+        let mut random_scalars = |num_bits: usize| -> Vec<u64> {
+            (0..n).map(|_| rng.next_u64() >> (64 - num_bits)).collect()
+        };
+        let expected = |scalars: &[u64]| -> G1Projective {
+            let scalars: Vec<Fr> = scalars.iter().map(|&scalar| Fr::from(scalar)).collect();
+            ark_VariableBaseMSM::msm(&bases, &scalars).unwrap()
+        };
+
+        // Split the MSMs into several chunks each
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            for num_bits in [1, 4, 8] {
+                let scalars = random_scalars(num_bits);
+                let scalars_u8: Vec<u8> = scalars.iter().map(|&scalar| scalar as u8).collect();
+                let msm: G1Projective = VariableBaseMSM::msm_u8(&bases, &scalars_u8, None).unwrap();
+                assert_eq!(msm, expected(&scalars));
+            }
+            for num_bits in [10, 16] {
+                let scalars = random_scalars(num_bits);
+                let scalars_u16: Vec<u16> = scalars.iter().map(|&scalar| scalar as u16).collect();
+                let msm: G1Projective =
+                    VariableBaseMSM::msm_u16(&bases, None, &scalars_u16, None, false).unwrap();
+                assert_eq!(msm, expected(&scalars));
+            }
+            let scalars = random_scalars(32);
+            let scalars_u32: Vec<u32> = scalars.iter().map(|&scalar| scalar as u32).collect();
+            let msm: G1Projective =
+                VariableBaseMSM::msm_u32(&bases, None, &scalars_u32, None, false).unwrap();
+            assert_eq!(msm, expected(&scalars));
+        });
+    }
+}
@@ -0,0 +1,111 @@
+//! Scheduling of prover work across the GPUs and CPU cores of a machine.
+//!
+//! With the `icicle` feature, the large MSMs of a batch of commitments are spread across
+//! every GPU in `JoltProverConfig::gpus`, while the MSMs too small to be worth offloading
+//! run concurrently on the CPU. The rest of the prover (witness generation and the
+//! sumchecks) runs on the CPU, in the thread pool set up by `JoltProverConfig::install`.
+use std::cell::RefCell;
+use std::ops::Range;
+
+use super::icicle::icicle_devices;
+
+/// How MSMs are split across GPUs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GpuPartition {
+    /// Each MSM of a batch runs on a single GPU, with the MSMs assigned so as to balance
+    /// the number of scalars each GPU handles.
+    #[default]
+    PerPolynomial,
+    /// Each MSM is split into one contiguous segment per GPU, and the partial results
+    /// are summed. Better suited to batches of fewer (large) polynomials than GPUs.
+    /// Batches of polynomials of different sizes are still split per polynomial.
+    PerSegment,
+}
+
+/// The GPUs the MSMs of a thread are spread across, and how.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct GpuAssignment {
+    /// CUDA devices MSMs are spread across; devices that were not detected are ignored.
+    /// `None` uses every detected device.
+    pub gpus: Option<Vec<usize>>,
+    pub partition: GpuPartition,
+}
+
+thread_local! {
+    /// Set on every thread of the pool `JoltProverConfig::install` runs the prover in, so
+    /// that provers configured differently can run side by side; threads outside such a
+    /// pool use every detected GPU.
+    static GPU_ASSIGNMENT: RefCell<GpuAssignment> = RefCell::new(GpuAssignment::default());
+}
+
+pub(crate) fn set_gpu_assignment(assignment: GpuAssignment) {
+    GPU_ASSIGNMENT.with(|current| *current.borrow_mut() = assignment);
+}
+
+/// How MSMs on the current thread are split across its GPUs.
+pub fn gpu_partition() -> GpuPartition {
+    GPU_ASSIGNMENT.with(|current| current.borrow().partition)
+}
+
+/// The GPUs MSMs on the current thread are spread across: the detected devices among
+/// those assigned to it.
+pub fn active_gpus() -> Vec<usize> {
+    let detected = icicle_devices();
+    match GPU_ASSIGNMENT.with(|current| current.borrow().gpus.clone()) {
+        Some(gpus) => gpus
+            .into_iter()
+            .filter(|gpu| detected.contains(gpu))
+            .collect(),
+        None => detected,
+    }
+}
+
+/// Assigns jobs of the given `sizes` to `num_workers` workers, handing the largest
+/// remaining job to the least loaded worker. Returns the indices of each worker's jobs.
+pub fn balance(sizes: &[usize], num_workers: usize) -> Vec<Vec<usize>> {
+    assert!(num_workers > 0);
+    let mut jobs: Vec<usize> = (0..sizes.len()).collect();
+    jobs.sort_by_key(|&job| std::cmp::Reverse(sizes[job]));
+
+    let mut assignment = vec![vec![]; num_workers];
+    let mut loads = vec![0; num_workers];
+    for job in jobs {
+        let worker = (0..num_workers)
+            .min_by_key(|&worker| loads[worker])
+            .unwrap();
+        loads[worker] += sizes[job];
+        assignment[worker].push(job);
+    }
+    assignment
+}
+
+/// Splits `0..len` into (at most) `num_segments` contiguous ranges of near-equal length.
+pub fn segments(len: usize, num_segments: usize) -> Vec<Range<usize>> {
+    assert!(num_segments > 0);
+    let num_segments = num_segments.min(len.max(1));
+    (0..num_segments)
+        .map(|i| i * len / num_segments..(i + 1) * len / num_segments)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_jobs() {
+        let assignment = balance(&[1, 8, 4, 4, 2], 2);
+        assert_eq!(assignment, vec![vec![1, 4], vec![2, 3, 0]]);
+
+        // Every job is assigned exactly once, even with more workers than jobs
+        let assignment = balance(&[3, 1], 4);
+        assert_eq!(assignment, vec![vec![0], vec![1], vec![], vec![]]);
+    }
+
+    #[test]
+    fn split_segments() {
+        assert_eq!(segments(10, 3), vec![0..3, 3..6, 6..10]);
+        assert_eq!(segments(2, 4), vec![0..1, 1..2]);
+        assert_eq!(segments(0, 2), vec![0..0]);
+    }
+}
@@ -13,7 +13,7 @@ pub struct Binius128Scheme<ProofTranscript: Transcript> {
     _phantom: PhantomData<ProofTranscript>,
 }
 
-#[derive(Default, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Default, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BiniusCommitment {}
 
 impl AppendToTranscript for BiniusCommitment {
@@ -28,7 +28,7 @@ pub struct BiniusProof {}
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct BiniusBatchedProof {}
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct None {}
 
 impl<ProofTranscript: Transcript> CommitmentScheme<ProofTranscript>
@@ -36,6 +36,7 @@ impl<ProofTranscript: Transcript> CommitmentScheme<ProofTranscript>
 {
     type Field = crate::field::binius::BiniusField<binius_field::BinaryField128bPolyval>;
     type Setup = None;
+    type VerifierSetup = None;
     type Commitment = BiniusCommitment;
     type Proof = BiniusProof;
     type BatchedProof = BiniusBatchedProof;
@@ -43,6 +44,9 @@ impl<ProofTranscript: Transcript> CommitmentScheme<ProofTranscript>
     fn setup(_max_poly_len: usize) -> Self::Setup {
         None {}
     }
+    fn verifier_setup(_setup: &Self::Setup) -> Self::VerifierSetup {
+        None {}
+    }
     fn commit(
         _poly: &MultilinearPolynomial<Self::Field>,
         _setup: &Self::Setup,
@@ -65,7 +69,7 @@ impl<ProofTranscript: Transcript> CommitmentScheme<ProofTranscript>
     }
     fn verify(
         _proof: &Self::Proof,
-        _setup: &Self::Setup,
+        _setup: &Self::VerifierSetup,
         _transcript: &mut ProofTranscript,
         _opening_point: &[Self::Field],
         _opening: &Self::Field,
@@ -1,6 +1,7 @@
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::io::{Read, Write};
 
 use crate::utils::transcript::Transcript;
 use crate::{
@@ -9,10 +10,16 @@ use crate::{
     utils::{errors::ProofVerifyError, transcript::AppendToTranscript},
 };
 
+/// Number of coefficients consumed at a time by `CommitmentScheme::commit_streaming`.
+pub const STREAMING_CHUNK_SIZE: usize = 1 << 16;
+
 pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + 'static {
     type Field: JoltField + Sized;
     type Setup: Clone + Sync + Send;
+    /// The part of `Setup` needed to verify opening proofs.
+    type VerifierSetup: Clone + Sync + Send + CanonicalSerialize + CanonicalDeserialize;
     type Commitment: Default
+        + Clone
         + Debug
         + Sync
         + Send
@@ -24,11 +31,57 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
     type BatchedProof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
 
     fn setup(max_len: usize) -> Self::Setup;
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup;
+
+    /// Writes `setup` so that `load_setup` can restore it without regenerating it (see
+    /// `Jolt::save_preprocessing`). By default nothing is written, and `load_setup`
+    /// regenerates the setup; schemes with an expensive setup override both.
+    fn save_setup<W: Write>(_setup: &Self::Setup, _writer: W) -> Result<(), SerializationError> {
+        Ok(())
+    }
+
+    /// Reads the setup for polynomials of up to `max_len` coefficients written by
+    /// `save_setup`.
+    fn load_setup<R: Read>(_reader: R, max_len: usize) -> Result<Self::Setup, SerializationError> {
+        Ok(Self::setup(max_len))
+    }
+
     fn commit(poly: &MultilinearPolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment;
     fn batch_commit<U>(polys: &[U], gens: &Self::Setup) -> Vec<Self::Commitment>
     where
         U: Borrow<MultilinearPolynomial<Self::Field>> + Sync;
 
+    /// Commits to the multilinear polynomial whose coefficients are yielded, in order,
+    /// by `coeffs`; the number of coefficients must be a power of two. Produces the same
+    /// commitment as `commit`. Schemes whose commitments are linear in the coefficients
+    /// consume `coeffs` in chunks of `STREAMING_CHUNK_SIZE`, so polynomials too large to
+    /// hold in memory can be committed without materializing the coefficient vector; the
+    /// default implementation just collects them.
+    fn commit_streaming<I>(coeffs: I, setup: &Self::Setup) -> Self::Commitment
+    where
+        I: Iterator<Item = Self::Field>,
+    {
+        let coeffs: Vec<Self::Field> = coeffs.collect();
+        Self::commit(&MultilinearPolynomial::from(coeffs), setup)
+    }
+
+    /// Commits to the multilinear polynomial of `len` coefficients (a power of two) that
+    /// are all zero but those at `indices`, which are one, such as the (very sparse) flag
+    /// polynomials. Produces the same commitment as `commit`. Schemes whose commitments are
+    /// linear in the coefficients only touch the generators at `indices`; the default
+    /// implementation builds the dense polynomial.
+    fn commit_sparse_binary(
+        indices: &[usize],
+        len: usize,
+        setup: &Self::Setup,
+    ) -> Self::Commitment {
+        let mut coeffs = vec![0u8; len];
+        for &index in indices {
+            coeffs[index] = 1;
+        }
+        Self::commit(&MultilinearPolynomial::from(coeffs), setup)
+    }
+
     /// Homomorphically combines multiple commitments into a single commitment, computed as a
     /// linear combination with the given coefficients.
     fn combine_commitments(
@@ -47,7 +100,7 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
 
     fn verify(
         proof: &Self::Proof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         opening: &Self::Field,         // evaluation \widetilde{Z}(r)
@@ -7,30 +7,50 @@
 //! This means that Spartan's polynomial IOP can use commit to its polynomials as-is without incurring any interpolations or FFTs.
 //! (2) HyperKZG is specialized to use KZG as the univariate commitment scheme, so it includes several optimizations (both during the transformation of multilinear-to-univariate claims
 //! and within the KZG commitment scheme implementation itself).
+#[cfg(feature = "fs")]
+use super::ptau::PowersOfTau;
 use super::{
-    commitment_scheme::CommitmentScheme,
+    commitment_scheme::{CommitmentScheme, STREAMING_CHUNK_SIZE},
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG},
+    ptau::PtauError,
 };
 use crate::field::JoltField;
 use crate::poly::multilinear_polynomial::{MultilinearPolynomial, PolynomialEvaluation};
+use crate::utils::serialization::deserialize_points;
 use crate::utils::transcript::Transcript;
 use crate::{
     msm::{Icicle, VariableBaseMSM},
     poly::{commitment::kzg::SRS, dense_mlpoly::DensePolynomial, unipoly::UniPoly},
     utils::{errors::ProofVerifyError, transcript::AppendToTranscript},
 };
+use ark_bn254::Bn254;
+#[cfg(feature = "fs")]
+use ark_bn254::{G1Affine, G2Affine};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{One, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use ark_std::{One, UniformRand, Zero};
 use rand_chacha::ChaCha20Rng;
 use rand_core::{CryptoRng, RngCore, SeedableRng};
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
     IntoParallelRefMutIterator, ParallelIterator,
 };
+use sha3::{Digest, Keccak256};
+use std::any::Any;
 use std::borrow::Borrow;
+use std::cell::RefCell;
+#[cfg(feature = "fs")]
+use std::fs::File;
+#[cfg(feature = "fs")]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
 use std::{marker::PhantomData, sync::Arc};
 
+/// Number of G2 powers needed by HyperKZG: [G2, tau * G2]
+const NUM_G2_POWERS: usize = 2;
+
 pub struct HyperKZGSRS<P: Pairing>(Arc<SRS<P>>)
 where
     P::G1: Icicle;
@@ -50,6 +70,152 @@ where
         let (kzg_pk, kzg_vk) = SRS::trim(self.0, max_degree);
         (HyperKZGProverKey { kzg_pk }, HyperKZGVerifierKey { kzg_vk })
     }
+
+    /// Checks that `g1_powers` = [G1, tau * G1, tau^2 * G1, ...] and
+    /// `g2_powers` = [G2, tau * G2] for the same tau. Rather than checking each pair of
+    /// successive powers individually, we check a random linear combination:
+    ///   e(sum_i r_i * g1_powers[i + 1], G2) == e(sum_i r_i * g1_powers[i], tau * G2)
+    fn check_powers_of_tau(
+        g1_powers: &[P::G1Affine],
+        g2_powers: &[P::G2Affine],
+    ) -> Result<(), PtauError>
+    where
+        P::ScalarField: JoltField,
+    {
+        if g1_powers.len() < 2 || g2_powers.len() < 2 {
+            return Err(PtauError::NotEnoughPowers(g1_powers.len(), 2));
+        }
+        if g1_powers[0] != P::G1Affine::generator() || g2_powers[0] != P::G2Affine::generator() {
+            return Err(PtauError::PairingCheckFailed);
+        }
+
+        // Derive the random coefficients by hashing the powers (Fiat-Shamir), so that
+        // they cannot be anticipated by whoever produced the ceremony file.
+        let mut hasher = Keccak256::new();
+        g1_powers.serialize_uncompressed(&mut hasher)?;
+        g2_powers.serialize_uncompressed(&mut hasher)?;
+        let mut rng = ChaCha20Rng::from_seed(hasher.finalize().into());
+        let n = g1_powers.len() - 1;
+        let r: Vec<P::ScalarField> = (0..n).map(|_| P::ScalarField::random(&mut rng)).collect();
+        let (lhs, rhs) = rayon::join(
+            || {
+                <P::G1 as VariableBaseMSM>::msm_field_elements(
+                    &g1_powers[1..],
+                    None,
+                    &r,
+                    None,
+                    false,
+                )
+            },
+            || {
+                <P::G1 as VariableBaseMSM>::msm_field_elements(
+                    &g1_powers[..n],
+                    None,
+                    &r,
+                    None,
+                    false,
+                )
+            },
+        );
+        let (lhs, rhs) = (lhs.unwrap(), rhs.unwrap());
+
+        // Since g1_powers[0] = G1, this also implies g1_powers[i] = tau^i * G1
+        if P::multi_pairing([lhs, -rhs], [g2_powers[0], g2_powers[1]]).is_zero() {
+            Ok(())
+        } else {
+            Err(PtauError::PairingCheckFailed)
+        }
+    }
+}
+
+impl HyperKZGSRS<Bn254> {
+    /// Loads an SRS supporting polynomials of up to `max_degree + 1` coefficients from a
+    /// snarkjs/Perpetual Powers of Tau `.ptau` ceremony file. Every point is checked to
+    /// be on the curve and in the correct subgroup, and the powers are checked to be
+    /// consistent (i.e. successive powers of the same tau) using randomized pairing checks.
+    #[cfg(feature = "fs")]
+    #[tracing::instrument(skip_all, name = "HyperKZGSRS::from_ptau")]
+    pub fn from_ptau<Path: AsRef<std::path::Path>>(
+        path: Path,
+        max_degree: usize,
+    ) -> Result<Self, PtauError> {
+        let reader = BufReader::new(File::open(path)?);
+        let ptau = PowersOfTau::read(reader, max_degree + 1, NUM_G2_POWERS)?;
+        Self::check_powers_of_tau(&ptau.g1_powers, &ptau.g2_powers)?;
+        Ok(Self(Arc::new(SRS::from_powers(
+            ptau.g1_powers,
+            ptau.g2_powers,
+        ))))
+    }
+
+    /// Same as `from_ptau`, but caches the truncated powers in `cache_dir` so that
+    /// subsequent loads skip parsing the ceremony file. The cache entry is keyed by a
+    /// hash of the ceremony file's contents and `max_degree`. Cached points are
+    /// re-validated on load (subgroup and pairing checks), and a cache entry that is
+    /// missing, corrupt or inconsistent is regenerated from the ceremony file.
+    #[cfg(feature = "fs")]
+    pub fn from_ptau_cached<Path: AsRef<std::path::Path>, CacheDir: AsRef<std::path::Path>>(
+        path: Path,
+        max_degree: usize,
+        cache_dir: CacheDir,
+    ) -> Result<Self, PtauError> {
+        let mut hasher = Keccak256::new();
+        let mut reader = BufReader::new(File::open(path.as_ref())?);
+        let mut buffer = vec![0u8; 1 << 20];
+        loop {
+            let n = reader.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+        }
+        let digest: String = hasher.finalize()[..16]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let cache_path = cache_dir
+            .as_ref()
+            .join(format!("{digest}_{max_degree}.srs"));
+
+        if let Ok(srs) = Self::read_cached_powers(&cache_path, max_degree) {
+            return Ok(srs);
+        }
+
+        let srs = Self::from_ptau(path, max_degree)?;
+        // Write the entry under a unique name and rename it into place, so that a
+        // concurrent or interrupted writer never leaves a partial entry behind
+        std::fs::create_dir_all(cache_dir.as_ref())?;
+        let mut file = tempfile::NamedTempFile::new_in(cache_dir.as_ref())?;
+        {
+            let mut writer = BufWriter::new(file.as_file_mut());
+            srs.0.g1_powers.serialize_uncompressed(&mut writer)?;
+            srs.0.g2_powers.serialize_uncompressed(&mut writer)?;
+            writer.flush()?;
+        }
+        file.persist(&cache_path).map_err(|err| err.error)?;
+        Ok(srs)
+    }
+
+    #[cfg(feature = "fs")]
+    fn read_cached_powers(
+        cache_path: &std::path::Path,
+        max_degree: usize,
+    ) -> Result<Self, PtauError> {
+        let mut reader = BufReader::new(File::open(cache_path)?);
+        // Points are re-validated (on-curve and subgroup checks) on deserialization
+        let g1_powers: Vec<G1Affine> =
+            deserialize_points(&mut reader, Compress::No, Validate::Yes)?;
+        let g2_powers: Vec<G2Affine> =
+            deserialize_points(&mut reader, Compress::No, Validate::Yes)?;
+        if g1_powers.len() != max_degree + 1 {
+            return Err(PtauError::NotEnoughPowers(g1_powers.len(), max_degree + 1));
+        }
+        if g2_powers.len() != NUM_G2_POWERS {
+            return Err(PtauError::NotEnoughPowers(g2_powers.len(), NUM_G2_POWERS));
+        }
+        Self::check_powers_of_tau(&g1_powers, &g2_powers)?;
+        Ok(Self(Arc::new(SRS::from_powers(g1_powers, g2_powers))))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -60,7 +226,7 @@ where
     pub kzg_pk: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyperKZGVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
 }
@@ -180,6 +346,93 @@ where
     (w, v)
 }
 
+/// The final check e(L, vk.H) == e(R, vk.tau_H) of a HyperKZG verification,
+/// deferred so that checks from many proofs can be batched.
+#[derive(Clone, Copy, Debug)]
+pub struct PairingCheck<P: Pairing> {
+    pub L: P::G1,
+    pub R: P::G1,
+}
+
+/// `vk.H` and `vk.tau_H` prepared for pairing, so that repeated checks against
+/// the same verifier key skip the G2 precomputation.
+#[derive(Clone, Debug)]
+pub struct PreparedVerifierKey<P: Pairing> {
+    g2: P::G2Prepared,
+    beta_g2: P::G2Prepared,
+}
+
+impl<P: Pairing> From<&HyperKZGVerifierKey<P>> for PreparedVerifierKey<P> {
+    fn from(vk: &HyperKZGVerifierKey<P>) -> Self {
+        Self {
+            g2: vk.kzg_vk.g2.into(),
+            beta_g2: vk.kzg_vk.beta_g2.into(),
+        }
+    }
+}
+
+impl<P: Pairing> PairingCheck<P> {
+    pub fn verify(&self, vk: &PreparedVerifierKey<P>) -> bool {
+        P::multi_pairing([self.L, -self.R], [vk.g2.clone(), vk.beta_g2.clone()]).is_zero()
+    }
+
+    /// Verifies all of `checks` with a single two-pairing check on a random linear
+    /// combination of them. If this fails, at least one of the checks is invalid.
+    pub fn batch_verify<R: RngCore>(
+        checks: &[Self],
+        vk: &PreparedVerifierKey<P>,
+        rng: &mut R,
+    ) -> bool {
+        let (L, R) = checks
+            .iter()
+            .fold((P::G1::zero(), P::G1::zero()), |(L, R), check| {
+                let rho = P::ScalarField::rand(rng);
+                (L + check.L * rho, R + check.R * rho)
+            });
+        PairingCheck { L, R }.verify(vk)
+    }
+}
+
+thread_local! {
+    /// Pairing checks deferred by `defer_pairing_checks` on this thread.
+    static DEFERRED_PAIRING_CHECKS: RefCell<Option<Vec<Box<dyn Any>>>> =
+        const { RefCell::new(None) };
+}
+
+/// Restores the checks deferred by an enclosing `defer_pairing_checks` when dropped,
+/// including when `f` panics, so that later verifications on the thread pair again.
+struct RestoreDeferredPairingChecks(Option<Vec<Box<dyn Any>>>);
+
+impl Drop for RestoreDeferredPairingChecks {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        DEFERRED_PAIRING_CHECKS.with(|checks| *checks.borrow_mut() = previous);
+    }
+}
+
+/// Runs `f`, deferring the final pairing check of every HyperKZG verification it
+/// performs on the current thread. Those verifications succeed if everything but
+/// the pairing check passes; the caller is responsible for verifying the returned
+/// checks, e.g. with `PairingCheck::batch_verify`.
+pub fn defer_pairing_checks<P: Pairing, T>(f: impl FnOnce() -> T) -> (T, Vec<PairingCheck<P>>) {
+    let previous = DEFERRED_PAIRING_CHECKS.with(|checks| checks.replace(Some(vec![])));
+    let restore = RestoreDeferredPairingChecks(previous);
+    let result = f();
+    let deferred = DEFERRED_PAIRING_CHECKS
+        .with(|checks| checks.take())
+        .unwrap_or_default();
+    drop(restore);
+    let checks = deferred
+        .into_iter()
+        .map(|check| {
+            *check
+                .downcast::<PairingCheck<P>>()
+                .expect("pairing checks deferred over a different curve")
+        })
+        .collect();
+    (result, checks)
+}
+
 // vk is hashed in transcript already, so we do not add it here
 fn kzg_verify_batch<P: Pairing, ProofTranscript: Transcript>(
     vk: &HyperKZGVerifierKey<P>,
@@ -263,6 +516,17 @@ where
 
     let R = W[0] + W[1] * d_0 + W[2] * d_1;
 
+    let deferred = DEFERRED_PAIRING_CHECKS.with(|checks| match checks.borrow_mut().as_mut() {
+        Some(checks) => {
+            checks.push(Box::new(PairingCheck::<P> { L, R }));
+            true
+        }
+        None => false,
+    });
+    if deferred {
+        return true;
+    }
+
     // Check that e(L, vk.H) == e(R, vk.tau_H)
     P::multi_pairing([L, -R], [vk.kzg_vk.g2, vk.kzg_vk.beta_g2]).is_zero()
 }
@@ -414,6 +678,7 @@ where
 {
     type Field = P::ScalarField;
     type Setup = (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>);
+    type VerifierSetup = HyperKZGVerifierKey<P>;
     type Commitment = HyperKZGCommitment<P>;
     type Proof = HyperKZGProof<P>;
     type BatchedProof = HyperKZGProof<P>;
@@ -427,6 +692,40 @@ where
         .trim(max_poly_len)
     }
 
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.1
+    }
+
+    fn save_setup<W: Write>(setup: &Self::Setup, mut writer: W) -> Result<(), SerializationError> {
+        let kzg_vk = &setup.1.kzg_vk;
+        setup
+            .0
+            .kzg_pk
+            .g1_powers()
+            .serialize_uncompressed(&mut writer)?;
+        vec![kzg_vk.g2, kzg_vk.beta_g2].serialize_uncompressed(&mut writer)?;
+        Ok(())
+    }
+
+    fn load_setup<R: Read>(
+        mut reader: R,
+        max_len: usize,
+    ) -> Result<Self::Setup, SerializationError> {
+        // Points are re-validated (on-curve and subgroup checks) on deserialization
+        let g1_powers: Vec<P::G1Affine> =
+            deserialize_points(&mut reader, Compress::No, Validate::Yes)?;
+        let g2_powers: Vec<P::G2Affine> =
+            deserialize_points(&mut reader, Compress::No, Validate::Yes)?;
+        if g1_powers.len() != max_len + 1 || g2_powers.len() != NUM_G2_POWERS {
+            return Err(SerializationError::InvalidData);
+        }
+        // A corrupted or substituted file may still hold valid points; check that they
+        // are successive powers of the same tau
+        HyperKZGSRS::<P>::check_powers_of_tau(&g1_powers, &g2_powers)
+            .map_err(|_| SerializationError::InvalidData)?;
+        Ok(HyperKZGSRS(Arc::new(SRS::from_powers(g1_powers, g2_powers))).trim(max_len))
+    }
+
     #[tracing::instrument(skip_all, name = "HyperKZG::commit")]
     fn commit(poly: &MultilinearPolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
         assert!(
@@ -438,6 +737,27 @@ where
         HyperKZGCommitment(UnivariateKZG::commit_as_univariate(&setup.0.kzg_pk, poly).unwrap())
     }
 
+    #[tracing::instrument(skip_all, name = "HyperKZG::commit_streaming")]
+    fn commit_streaming<I>(coeffs: I, setup: &Self::Setup) -> Self::Commitment
+    where
+        I: Iterator<Item = Self::Field>,
+    {
+        HyperKZGCommitment(
+            UnivariateKZG::commit_streaming(&setup.0.kzg_pk, coeffs, STREAMING_CHUNK_SIZE).unwrap(),
+        )
+    }
+
+    #[tracing::instrument(skip_all, name = "HyperKZG::commit_sparse_binary")]
+    fn commit_sparse_binary(
+        indices: &[usize],
+        len: usize,
+        setup: &Self::Setup,
+    ) -> Self::Commitment {
+        HyperKZGCommitment(
+            UnivariateKZG::commit_sparse_binary(&setup.0.kzg_pk, indices, len).unwrap(),
+        )
+    }
+
     #[tracing::instrument(skip_all, name = "HyperKZG::batch_commit")]
     fn batch_commit<U>(polys: &[U], gens: &Self::Setup) -> Vec<Self::Commitment>
     where
@@ -475,14 +795,14 @@ where
 
     fn verify(
         proof: &Self::Proof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         opening: &Self::Field,         // evaluation \widetilde{Z}(r)
         commitment: &Self::Commitment,
     ) -> Result<(), ProofVerifyError> {
         HyperKZG::<P, ProofTranscript>::verify(
-            &setup.1,
+            setup,
             commitment,
             opening_point,
             opening,
@@ -504,6 +824,93 @@ mod tests {
     use ark_std::UniformRand;
     use rand_core::SeedableRng;
 
+    #[test]
+    fn test_hyperkzg_commit_streaming() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let setup = PCS::setup(1 << 10);
+        let coeffs: Vec<Fr> = (0..1 << 10).map(|_| Fr::rand(&mut rng)).collect();
+
+        let expected = PCS::commit(&MultilinearPolynomial::from(coeffs.clone()), &setup);
+        assert_eq!(PCS::commit_streaming(coeffs.into_iter(), &setup), expected);
+
+        // Chunks smaller than the polynomial
+        let coeffs: Vec<Fr> = (0..1 << 10).map(|_| Fr::rand(&mut rng)).collect();
+        let streamed =
+            UnivariateKZG::commit_streaming(&setup.0.kzg_pk, coeffs.iter().copied(), 100).unwrap();
+        let expected = PCS::commit(&MultilinearPolynomial::from(coeffs), &setup);
+        assert_eq!(HyperKZGCommitment(streamed), expected);
+    }
+
+    #[test]
+    fn test_hyperkzg_commit_sparse_binary() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let setup = PCS::setup(1 << 10);
+        let coeffs: Vec<u8> = (0..1 << 10)
+            .map(|_| (rng.next_u32() % 16 == 0) as u8)
+            .collect();
+        let poly = MultilinearPolynomial::from(coeffs);
+        let indices = poly.binary_indices().unwrap();
+        assert_eq!(
+            PCS::commit_sparse_binary(&indices, 1 << 10, &setup),
+            PCS::commit(&poly, &setup)
+        );
+
+        assert_eq!(
+            PCS::commit_sparse_binary(&[], 1 << 10, &setup),
+            PCS::commit(&MultilinearPolynomial::from(vec![0u8; 1 << 10]), &setup)
+        );
+        assert!(MultilinearPolynomial::<Fr>::from(vec![0u8, 2])
+            .binary_indices()
+            .is_none());
+    }
+
+    #[test]
+    fn test_hyperkzg_deferred_pairing_checks() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let ell = 4;
+        let srs = HyperKZGSRS::setup(&mut rng, 1 << ell);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(1 << ell);
+        let prepared_vk = PreparedVerifierKey::from(&vk);
+
+        let mut checks = vec![];
+        for _ in 0..4 {
+            let poly = MultilinearPolynomial::from(
+                (0..1 << ell)
+                    .map(|_| Fr::rand(&mut rng))
+                    .collect::<Vec<_>>(),
+            );
+            let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+            let eval = poly.evaluate(&point);
+            let C = HyperKZG::<_, KeccakTranscript>::commit(&pk, &poly).unwrap();
+            let mut tr = KeccakTranscript::new(b"TestEval");
+            let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut tr).unwrap();
+
+            let mut tr = KeccakTranscript::new(b"TestEval");
+            let (result, deferred) = defer_pairing_checks::<Bn254, _>(|| {
+                HyperKZG::verify(&vk, &C, &point, &eval, &proof, &mut tr)
+            });
+            assert!(result.is_ok());
+            assert_eq!(deferred.len(), 1);
+            assert!(deferred[0].verify(&prepared_vk));
+            checks.extend(deferred);
+        }
+        assert!(PairingCheck::batch_verify(&checks, &prepared_vk, &mut rng));
+
+        // A single bad check invalidates the batch
+        checks[2].L += <Bn254 as Pairing>::G1::rand(&mut rng);
+        assert!(!checks[2].verify(&prepared_vk));
+        assert!(!PairingCheck::batch_verify(&checks, &prepared_vk, &mut rng));
+
+        // A panic while deferring does not leave the thread deferring
+        let panicked = std::panic::catch_unwind(|| {
+            defer_pairing_checks::<Bn254, ()>(|| panic!("verification panicked"))
+        });
+        assert!(panicked.is_err());
+        assert!(DEFERRED_PAIRING_CHECKS.with(|checks| checks.borrow().is_none()));
+    }
+
     #[test]
     fn test_hyperkzg_eval() {
         // Test with poly(X1, X2) = 1 + X1 + X2 + X1*X2
@@ -654,4 +1061,79 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_hyperkzg_load_setup() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let setup = PCS::setup(1 << 4);
+        let mut bytes = vec![];
+        PCS::save_setup(&setup, &mut bytes).unwrap();
+        let loaded = PCS::load_setup(bytes.as_slice(), 1 << 4).unwrap();
+        assert_eq!(loaded.0.kzg_pk.g1_powers(), setup.0.kzg_pk.g1_powers());
+
+        // Valid points that are not successive powers of the same tau are rejected
+        let mut g1_powers = setup.0.kzg_pk.g1_powers().to_vec();
+        g1_powers.swap(1, 2);
+        let mut bytes = vec![];
+        g1_powers.serialize_uncompressed(&mut bytes).unwrap();
+        vec![setup.1.kzg_vk.g2, setup.1.kzg_vk.beta_g2]
+            .serialize_uncompressed(&mut bytes)
+            .unwrap();
+        assert!(PCS::load_setup(bytes.as_slice(), 1 << 4).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_hyperkzg_from_ptau() {
+        let tau = Fr::from(0xdeadbeefu64);
+        let ptau_bytes = crate::poly::commitment::ptau::tests::write_ptau(tau, 3);
+        let dir = std::env::temp_dir().join("jolt_test_hyperkzg_from_ptau");
+        std::fs::create_dir_all(&dir).unwrap();
+        let ptau_path = dir.join("test.ptau");
+        std::fs::write(&ptau_path, &ptau_bytes).unwrap();
+
+        let ell = 3;
+        let n = 1 << ell;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let poly =
+            MultilinearPolynomial::from((0..n).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>());
+        let point = (0..ell).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let eval = poly.evaluate(&point);
+
+        // Load twice: the second load is served from the cache
+        for _ in 0..2 {
+            let srs = HyperKZGSRS::from_ptau_cached(&ptau_path, n, &dir).unwrap();
+            let (pk, vk) = srs.trim(n);
+
+            let C = HyperKZG::<_, KeccakTranscript>::commit(&pk, &poly).unwrap();
+            let mut prover_transcript = KeccakTranscript::new(b"TestEval");
+            let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap();
+            let mut verifier_transcript = KeccakTranscript::new(b"TestEval");
+            assert!(
+                HyperKZG::verify(&vk, &C, &point, &eval, &proof, &mut verifier_transcript).is_ok()
+            );
+        }
+
+        // A corrupt cache entry is regenerated from the ceremony file
+        let cache_entries: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "srs"))
+            .collect();
+        assert_eq!(cache_entries.len(), 1);
+        std::fs::write(&cache_entries[0], [0xffu8; 100]).unwrap();
+        let srs = HyperKZGSRS::from_ptau_cached(&ptau_path, n, &dir).unwrap();
+        assert_eq!(srs.0.g1_powers.len(), n + 1);
+        assert!(HyperKZGSRS::read_cached_powers(&cache_entries[0], n).is_ok());
+
+        // Tamper with one of the G1 powers; the pairing check should catch it
+        let mut ptau = PowersOfTau::read(std::io::Cursor::new(&ptau_bytes), n + 1, 2).unwrap();
+        ptau.g1_powers[4] = ptau.g1_powers[5];
+        assert!(matches!(
+            HyperKZGSRS::<Bn254>::check_powers_of_tau(&ptau.g1_powers, &ptau.g2_powers),
+            Err(PtauError::PairingCheckFailed)
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,11 +1,14 @@
 //! This file implements the Hyrax polynomial commitment scheme.
-//! Note that we choose not to implement the `CommitmentScheme` trait, as
-//! Hyrax is not intended to be used as the core PCS for Jolt itself (in fact,
-//! there are some incompatibilities with the batched opening proof protocol
-//! used in Jolt).
 //!
-//! Instead, Hyrax will be used as the PCS in Spartan to prove the verification
-//! of a Jolt proof (i.e. SNARK composition).
+//! `HyraxCommitment`/`HyraxOpeningProof` size the commitment matrix according to
+//! each polynomial's own length, which is what we want when Hyrax is used as the
+//! PCS in Spartan to prove the verification of a Jolt proof (i.e. SNARK composition).
+//! `HyraxScheme` instead fixes the row size at setup, which makes it compatible with
+//! the batched opening proof protocol used in Jolt (see `HyraxScheme`).
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+use super::commitment_scheme::CommitmentScheme;
 use super::pedersen::{PedersenCommitment, PedersenGenerators};
 use crate::field::JoltField;
 use crate::poly::dense_mlpoly::DensePolynomial;
@@ -346,3 +349,270 @@ impl<const RATIO: usize, F: JoltField, G: CurveGroup<ScalarField = F> + Icicle>
         )
     }
 }
+
+/// Hyrax as a `CommitmentScheme`, so that it can be used as the PCS for Jolt itself.
+///
+/// Every polynomial is committed to as a matrix whose row size is fixed by the setup
+/// (the row size for the largest supported polynomial), rather than depending on the
+/// polynomial's own length; a polynomial with fewer coefficients than a row occupies
+/// a prefix of the first row. As a result, the commitment to a polynomial is also a
+/// commitment to that polynomial zero-padded to any larger size, so commitments to
+/// polynomials of different sizes can be combined homomorphically, as required by
+/// `ProverOpeningAccumulator::reduce_and_prove`.
+#[derive(Clone)]
+pub struct HyraxScheme<G: CurveGroup, ProofTranscript: Transcript> {
+    _marker: PhantomData<(G, ProofTranscript)>,
+}
+
+impl<G: CurveGroup, ProofTranscript: Transcript> HyraxScheme<G, ProofTranscript> {
+    /// The number of rows and the row length of the matrix of coefficients of a
+    /// polynomial with `num_vars` variables, given the setup's row size.
+    fn matrix_shape(num_vars: usize, row_size: usize) -> (usize, usize) {
+        let len = num_vars.pow2();
+        if len <= row_size {
+            (1, len)
+        } else {
+            (len / row_size, row_size)
+        }
+    }
+}
+
+impl<F, G, ProofTranscript> CommitmentScheme<ProofTranscript> for HyraxScheme<G, ProofTranscript>
+where
+    F: JoltField,
+    G: CurveGroup<ScalarField = F> + Icicle,
+    ProofTranscript: Transcript,
+{
+    type Field = F;
+    type Setup = HyraxGenerators<1, G>;
+    type VerifierSetup = HyraxGenerators<1, G>;
+    type Commitment = HyraxCommitment<1, G>;
+    type Proof = HyraxOpeningProof<1, G>;
+    type BatchedProof = BatchedHyraxOpeningProof<1, G>;
+
+    fn setup(max_len: usize) -> Self::Setup {
+        HyraxGenerators::new(max_len.log_2().max(1))
+    }
+
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.clone()
+    }
+
+    #[tracing::instrument(skip_all, name = "HyraxScheme::commit")]
+    fn commit(poly: &MultilinearPolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
+        let (_, row_len) = Self::matrix_shape(poly.get_num_vars(), setup.gens.generators.len());
+        let gens = CurveGroup::normalize_batch(&setup.gens.generators[..row_len]);
+        let coeffs: Vec<F> = (0..poly.len())
+            .into_par_iter()
+            .map(|i| poly.get_coeff(i))
+            .collect();
+        let row_commitments = coeffs
+            .par_chunks(row_len)
+            .map(|row| PedersenCommitment::commit_vector(row, &gens))
+            .collect();
+        HyraxCommitment { row_commitments }
+    }
+
+    /// Commits to one row of `setup`'s row size at a time; a stream shorter than a row
+    /// is committed as a single partial row, consistent with `commit`.
+    #[tracing::instrument(skip_all, name = "HyraxScheme::commit_streaming")]
+    fn commit_streaming<I>(mut coeffs: I, setup: &Self::Setup) -> Self::Commitment
+    where
+        I: Iterator<Item = Self::Field>,
+    {
+        let row_size = setup.gens.generators.len();
+        let gens = CurveGroup::normalize_batch(&setup.gens.generators);
+        let mut row_commitments = vec![];
+        loop {
+            let row: Vec<F> = coeffs.by_ref().take(row_size).collect();
+            if row.is_empty() {
+                break;
+            }
+            row_commitments.push(PedersenCommitment::commit_vector(&row, &gens[..row.len()]));
+        }
+        HyraxCommitment { row_commitments }
+    }
+
+    fn batch_commit<U>(polys: &[U], gens: &Self::Setup) -> Vec<Self::Commitment>
+    where
+        U: Borrow<MultilinearPolynomial<Self::Field>> + Sync,
+    {
+        polys
+            .par_iter()
+            .map(|poly| Self::commit(poly.borrow(), gens))
+            .collect()
+    }
+
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        // Commitments to smaller polynomials are implicitly padded with commitments
+        // to zero rows.
+        let num_rows = commitments
+            .iter()
+            .map(|commitment| commitment.row_commitments.len())
+            .max()
+            .unwrap_or(0);
+        let row_commitments = (0..num_rows)
+            .into_par_iter()
+            .map(|i| {
+                commitments
+                    .iter()
+                    .zip(coeffs.iter())
+                    .filter_map(|(commitment, coeff)| {
+                        commitment
+                            .row_commitments
+                            .get(i)
+                            .map(|row_commitment| *row_commitment * coeff)
+                    })
+                    .fold(G::zero(), |acc, x| acc + x)
+            })
+            .collect();
+        HyraxCommitment { row_commitments }
+    }
+
+    #[tracing::instrument(skip_all, name = "HyraxScheme::prove")]
+    fn prove(
+        setup: &Self::Setup,
+        poly: &MultilinearPolynomial<Self::Field>,
+        opening_point: &[Self::Field],
+        _transcript: &mut ProofTranscript,
+    ) -> Self::Proof {
+        assert_eq!(poly.get_num_vars(), opening_point.len());
+        let (num_rows, row_len) =
+            Self::matrix_shape(opening_point.len(), setup.gens.generators.len());
+        let L = EqPolynomial::evals(&opening_point[..num_rows.log_2()]);
+
+        let vector_matrix_product = (0..num_rows)
+            .into_par_iter()
+            .map(|i| {
+                (0..row_len)
+                    .map(|j| mul_0_1_optimized(&L[i], &poly.get_coeff(i * row_len + j)))
+                    .collect::<Vec<F>>()
+            })
+            .reduce(
+                || vec![F::zero(); row_len],
+                |mut acc: Vec<_>, row| {
+                    acc.iter_mut().zip(row).for_each(|(x, y)| *x += y);
+                    acc
+                },
+            );
+
+        HyraxOpeningProof {
+            vector_matrix_product,
+        }
+    }
+
+    fn verify(
+        proof: &Self::Proof,
+        setup: &Self::VerifierSetup,
+        _transcript: &mut ProofTranscript,
+        opening_point: &[Self::Field],
+        opening: &Self::Field,
+        commitment: &Self::Commitment,
+    ) -> Result<(), ProofVerifyError> {
+        let (num_rows, row_len) =
+            Self::matrix_shape(opening_point.len(), setup.gens.generators.len());
+        if commitment.row_commitments.len() != num_rows
+            || proof.vector_matrix_product.len() != row_len
+        {
+            return Err(ProofVerifyError::InternalError);
+        }
+        let L = EqPolynomial::evals(&opening_point[..num_rows.log_2()]);
+        let R = EqPolynomial::evals(&opening_point[num_rows.log_2()..]);
+
+        // Verifier-derived commitment to u * a = \prod Com(u_j)^{a_j}
+        let homomorphically_derived_commitment: G = VariableBaseMSM::msm(
+            &G::normalize_batch(&commitment.row_commitments),
+            None,
+            &MultilinearPolynomial::from(L),
+            None,
+        )
+        .unwrap();
+
+        let product_commitment = VariableBaseMSM::msm_field_elements(
+            &G::normalize_batch(&setup.gens.generators[..row_len]),
+            None,
+            &proof.vector_matrix_product,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let dot_product = compute_dotproduct(&proof.vector_matrix_product, &R);
+
+        if (homomorphically_derived_commitment == product_commitment) && (dot_product == *opening) {
+            Ok(())
+        } else {
+            Err(ProofVerifyError::InternalError)
+        }
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"hyrax"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::multilinear_polynomial::PolynomialEvaluation;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{test_rng, UniformRand};
+
+    #[test]
+    fn combine_commitments_of_different_sizes() {
+        type PCS = HyraxScheme<G1Projective, KeccakTranscript>;
+        let mut rng = test_rng();
+        let setup = PCS::setup(1 << 10);
+
+        // Smaller than a row, a few rows, and the maximum size
+        let polys: Vec<MultilinearPolynomial<Fr>> = [3, 7, 10]
+            .into_iter()
+            .map(|num_vars| {
+                let coeffs: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::rand(&mut rng)).collect();
+                MultilinearPolynomial::from(coeffs)
+            })
+            .collect();
+        let commitments = PCS::batch_commit(&polys, &setup);
+        let coeffs: Vec<Fr> = (0..polys.len()).map(|_| Fr::rand(&mut rng)).collect();
+
+        let joint_poly =
+            MultilinearPolynomial::linear_combination(&polys.iter().collect::<Vec<_>>(), &coeffs);
+        let joint_commitment =
+            PCS::combine_commitments(&commitments.iter().collect::<Vec<_>>(), &coeffs);
+        assert_eq!(joint_commitment, PCS::commit(&joint_poly, &setup));
+
+        for (poly, commitment) in polys.iter().zip(commitments.iter()) {
+            let coeffs = (0..poly.len()).map(|i| poly.get_coeff(i));
+            assert_eq!(PCS::commit_streaming(coeffs, &setup), *commitment);
+        }
+
+        let opening_point: Vec<Fr> = (0..10).map(|_| Fr::rand(&mut rng)).collect();
+        let opening = joint_poly.evaluate(&opening_point);
+        let mut prover_transcript = KeccakTranscript::new(b"test");
+        let proof = PCS::prove(&setup, &joint_poly, &opening_point, &mut prover_transcript);
+
+        let mut verifier_transcript = KeccakTranscript::new(b"test");
+        assert!(PCS::verify(
+            &proof,
+            &setup,
+            &mut verifier_transcript,
+            &opening_point,
+            &opening,
+            &joint_commitment
+        )
+        .is_ok());
+        assert!(PCS::verify(
+            &proof,
+            &setup,
+            &mut verifier_transcript,
+            &opening_point,
+            &(opening + Fr::from(1u64)),
+            &joint_commitment
+        )
+        .is_err());
+    }
+}
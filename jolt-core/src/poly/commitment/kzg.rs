@@ -6,6 +6,7 @@ use crate::utils::errors::ProofVerifyError;
 use ark_ec::scalar_mul::fixed_base::FixedBase;
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{One, UniformRand, Zero};
 use rand_core::{CryptoRng, RngCore};
 use rayon::prelude::*;
@@ -76,9 +77,18 @@ where
             || P::G2::normalize_batch(&g2_powers_projective),
         );
 
+        Self::from_powers(g1_powers, g2_powers)
+    }
+
+    /// Constructs an SRS from existing powers-of-tau, e.g. ones produced by a trusted
+    /// setup ceremony. Does not check that the powers are consistent with each other.
+    pub fn from_powers(g1_powers: Vec<P::G1Affine>, g2_powers: Vec<P::G2Affine>) -> Self
+    where
+        P::ScalarField: JoltField,
+    {
         // Precompute a commitment to each power-of-two length vector of ones, which is just the sum of each power-of-two length prefix of the SRS
         let num_powers = (g1_powers.len() as f64).log2().floor() as usize + 1;
-        let all_ones_coeffs: Vec<u8> = vec![1; num_g1_powers + 1];
+        let all_ones_coeffs: Vec<u8> = vec![1; g1_powers.len()];
         let powers_of_2 = (0..num_powers).into_par_iter().map(|i| 1usize << i);
         let g_products = powers_of_2
             .map(|power| {
@@ -168,7 +178,7 @@ where
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct KZGVerifierKey<P: Pairing> {
     pub g1: P::G1Affine,
     pub g2: P::G2Affine,
@@ -266,6 +276,54 @@ where
         Ok(commitments.into_iter().map(|c| c.into_affine()).collect())
     }
 
+    /// Commits to the polynomial whose coefficients are yielded by `coeffs`, computing
+    /// one MSM per `chunk_size` coefficients and accumulating the results.
+    #[tracing::instrument(skip_all, name = "KZG::commit_streaming")]
+    pub fn commit_streaming<I: Iterator<Item = P::ScalarField>>(
+        pk: &KZGProverKey<P>,
+        mut coeffs: I,
+        chunk_size: usize,
+    ) -> Result<P::G1Affine, ProofVerifyError> {
+        let g1_powers = pk.g1_powers();
+        let mut commitment = P::G1::zero();
+        let mut offset = 0;
+        loop {
+            let chunk: Vec<P::ScalarField> = coeffs.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            let end = offset + chunk.len();
+            if end > g1_powers.len() {
+                return Err(ProofVerifyError::KeyLengthError(g1_powers.len(), end));
+            }
+            commitment += <P::G1 as VariableBaseMSM>::msm_field_elements(
+                &g1_powers[offset..end],
+                pk.gpu_g1().map(|g| &g[offset..end]),
+                &chunk,
+                None,
+                use_icicle(),
+            )?;
+            offset = end;
+        }
+        Ok(commitment.into_affine())
+    }
+
+    /// Commits to the polynomial of `len` coefficients that are all zero but those at
+    /// `indices`, which are one.
+    #[tracing::instrument(skip_all, name = "KZG::commit_sparse_binary")]
+    pub fn commit_sparse_binary(
+        pk: &KZGProverKey<P>,
+        indices: &[usize],
+        len: usize,
+    ) -> Result<P::G1Affine, ProofVerifyError> {
+        if pk.g1_powers().len() < len {
+            return Err(ProofVerifyError::KeyLengthError(pk.g1_powers().len(), len));
+        }
+        debug_assert!(indices.iter().all(|&index| index < len));
+        let c = <P::G1 as VariableBaseMSM>::msm_sparse_binary(&pk.g1_powers()[..len], indices);
+        Ok(c.into_affine())
+    }
+
     #[tracing::instrument(skip_all, name = "KZG::commit_offset")]
     pub fn commit_offset(
         pk: &KZGProverKey<P>,
@@ -19,7 +19,7 @@ pub struct MockCommitScheme<F: JoltField, ProofTranscript: Transcript> {
     _marker: PhantomData<(F, ProofTranscript)>,
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize, Default, Debug, PartialEq)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Default, Debug, PartialEq)]
 pub struct MockCommitment<F: JoltField> {
     poly: MultilinearPolynomial<F>,
 }
@@ -42,11 +42,13 @@ where
 {
     type Field = F;
     type Setup = ();
+    type VerifierSetup = ();
     type Commitment = MockCommitment<F>;
     type Proof = MockProof<F>;
     type BatchedProof = MockProof<F>;
 
     fn setup(_max_poly_len: usize) -> Self::Setup {}
+    fn verifier_setup(_setup: &Self::Setup) -> Self::VerifierSetup {}
     fn commit(poly: &MultilinearPolynomial<Self::Field>, _setup: &Self::Setup) -> Self::Commitment {
         MockCommitment { poly: poly.clone() }
     }
@@ -86,7 +88,7 @@ where
 
     fn verify(
         proof: &Self::Proof,
-        _setup: &Self::Setup,
+        _setup: &Self::VerifierSetup,
         _transcript: &mut ProofTranscript,
         opening_point: &[Self::Field],
         opening: &Self::Field,
@@ -4,6 +4,7 @@ pub mod hyperkzg;
 pub mod hyrax;
 pub mod kzg;
 pub mod pedersen;
+pub mod ptau;
 pub mod zeromorph;
 
 #[cfg(test)]
@@ -0,0 +1,290 @@
+//! Parser for the `.ptau` files produced by snarkjs and the Perpetual Powers of Tau
+//! ceremony (<https://github.com/privacy-scaling-explorations/perpetualpowersoftau>).
+//!
+//! A `.ptau` file is laid out as
+//! ```text
+//! magic ("ptau") | version: u32 | num_sections: u32 | section*
+//! section = section_type: u32 | section_len: u64 | data
+//! ```
+//! where all integers are little-endian. We only read the header section (1), the
+//! tau*G1 section (2) and the tau*G2 section (3). Field elements are stored as
+//! little-endian limbs in Montgomery form, which coincides with arkworks' internal
+//! representation for BN254.
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use thiserror::Error;
+
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+
+/// Size in bytes of a serialized BN254 base field element.
+const FQ_BYTES: usize = 32;
+const G1_BYTES: usize = 2 * FQ_BYTES;
+const G2_BYTES: usize = 4 * FQ_BYTES;
+
+#[derive(Error, Debug)]
+pub enum PtauError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Not a .ptau file")]
+    InvalidMagic,
+    #[error("Missing section {0}")]
+    MissingSection(u32),
+    #[error("Unsupported base field (element size {0} bytes)")]
+    UnsupportedField(u32),
+    #[error("Ceremony supports at most {0} G1 powers, but {1} were requested")]
+    NotEnoughPowers(usize, usize),
+    #[error("Point {0} of section {1} is not a valid curve point")]
+    InvalidPoint(usize, u32),
+    #[error("Powers of tau failed the pairing consistency check")]
+    PairingCheckFailed,
+    #[error("Failed to (de)serialize cached SRS: {0}")]
+    Serialization(#[from] ark_serialize::SerializationError),
+}
+
+/// Powers of tau read from a `.ptau` file.
+pub struct PowersOfTau {
+    /// `[G1, tau*G1, tau^2*G1, ...]`
+    pub g1_powers: Vec<G1Affine>,
+    /// `[G2, tau*G2, ...]`
+    pub g2_powers: Vec<G2Affine>,
+    /// log2 of the maximum number of constraints supported by the ceremony.
+    pub power: u32,
+}
+
+impl PowersOfTau {
+    /// Reads `num_g1_powers` G1 powers and `num_g2_powers` G2 powers from a `.ptau`
+    /// file, checking that every point is on the curve and in the prime-order subgroup.
+    pub fn read<R: Read + Seek>(
+        mut reader: R,
+        num_g1_powers: usize,
+        num_g2_powers: usize,
+    ) -> Result<Self, PtauError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != PTAU_MAGIC {
+            return Err(PtauError::InvalidMagic);
+        }
+        let _version = read_u32(&mut reader)?;
+        let num_sections = read_u32(&mut reader)?;
+
+        // Map from section type to (offset, length)
+        let mut sections: HashMap<u32, (u64, u64)> = HashMap::new();
+        for _ in 0..num_sections {
+            let section_type = read_u32(&mut reader)?;
+            let section_len = read_u64(&mut reader)?;
+            let offset = reader.stream_position()?;
+            sections.insert(section_type, (offset, section_len));
+            reader.seek(SeekFrom::Current(section_len as i64))?;
+        }
+
+        let (header_offset, _) = *sections
+            .get(&HEADER_SECTION)
+            .ok_or(PtauError::MissingSection(HEADER_SECTION))?;
+        reader.seek(SeekFrom::Start(header_offset))?;
+        let n8 = read_u32(&mut reader)?;
+        if n8 as usize != FQ_BYTES {
+            return Err(PtauError::UnsupportedField(n8));
+        }
+        let mut modulus = [0u8; FQ_BYTES];
+        reader.read_exact(&mut modulus)?;
+        if modulus[..] != Fq::MODULUS.to_bytes_le()[..] {
+            return Err(PtauError::UnsupportedField(n8));
+        }
+        let power = read_u32(&mut reader)?;
+
+        // The ceremony contains 2^(power + 1) - 1 G1 powers and 2^power G2 powers;
+        // counts that don't fit in a usize can't be requested anyway
+        let max_g1_powers = 1usize
+            .checked_shl(power.saturating_add(1))
+            .map_or(usize::MAX, |n| n - 1);
+        let max_g2_powers = 1usize.checked_shl(power).unwrap_or(usize::MAX);
+        if num_g1_powers > max_g1_powers {
+            return Err(PtauError::NotEnoughPowers(max_g1_powers, num_g1_powers));
+        }
+        if num_g2_powers > max_g2_powers {
+            return Err(PtauError::NotEnoughPowers(max_g2_powers, num_g2_powers));
+        }
+
+        let g1_bytes = read_section(
+            &mut reader,
+            &sections,
+            TAU_G1_SECTION,
+            num_g1_powers * G1_BYTES,
+        )?;
+        let g2_bytes = read_section(
+            &mut reader,
+            &sections,
+            TAU_G2_SECTION,
+            num_g2_powers * G2_BYTES,
+        )?;
+
+        let g1_powers = g1_bytes
+            .par_chunks_exact(G1_BYTES)
+            .enumerate()
+            .map(|(i, bytes)| {
+                let x = fq_from_bytes(&bytes[..FQ_BYTES]);
+                let y = fq_from_bytes(&bytes[FQ_BYTES..]);
+                let point = G1Affine::new_unchecked(x, y);
+                (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+                    .then_some(point)
+                    .ok_or(PtauError::InvalidPoint(i, TAU_G1_SECTION))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2_powers = g2_bytes
+            .par_chunks_exact(G2_BYTES)
+            .enumerate()
+            .map(|(i, bytes)| {
+                let x = Fq2::new(
+                    fq_from_bytes(&bytes[..FQ_BYTES]),
+                    fq_from_bytes(&bytes[FQ_BYTES..2 * FQ_BYTES]),
+                );
+                let y = Fq2::new(
+                    fq_from_bytes(&bytes[2 * FQ_BYTES..3 * FQ_BYTES]),
+                    fq_from_bytes(&bytes[3 * FQ_BYTES..]),
+                );
+                let point = G2Affine::new_unchecked(x, y);
+                (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+                    .then_some(point)
+                    .ok_or(PtauError::InvalidPoint(i, TAU_G2_SECTION))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            g1_powers,
+            g2_powers,
+            power,
+        })
+    }
+}
+
+fn read_section<R: Read + Seek>(
+    reader: &mut R,
+    sections: &HashMap<u32, (u64, u64)>,
+    section_type: u32,
+    num_bytes: usize,
+) -> Result<Vec<u8>, PtauError> {
+    let (offset, len) = *sections
+        .get(&section_type)
+        .ok_or(PtauError::MissingSection(section_type))?;
+    if (num_bytes as u64) > len {
+        return Err(PtauError::MissingSection(section_type));
+    }
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut bytes = vec![0u8; num_bytes];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Interprets 32 little-endian bytes as a BN254 base field element in Montgomery form.
+fn fq_from_bytes(bytes: &[u8]) -> Fq {
+    let limbs: [u64; 4] =
+        std::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * (i + 1)].try_into().unwrap()));
+    Fq::new_unchecked(BigInt(limbs))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> std::io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective, G2Projective};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::Field;
+    use std::io::Cursor;
+
+    fn fq_to_bytes(x: &Fq) -> Vec<u8> {
+        // Montgomery form, little-endian
+        x.0.to_bytes_le()
+    }
+
+    /// Serializes a tiny fake ceremony in the `.ptau` format.
+    pub(crate) fn write_ptau(tau: Fr, power: u32) -> Vec<u8> {
+        let num_g1 = (1usize << (power + 1)) - 1;
+        let num_g2 = 1usize << power;
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let mut header = vec![];
+        header.extend((FQ_BYTES as u32).to_le_bytes());
+        header.extend(Fq::MODULUS.to_bytes_le());
+        header.extend(power.to_le_bytes());
+        header.extend(power.to_le_bytes());
+
+        let mut tau_g1 = vec![];
+        let mut tau_i = Fr::ONE;
+        for _ in 0..num_g1 {
+            let p = (G1Projective::from(g1) * tau_i).into_affine();
+            tau_g1.extend(fq_to_bytes(&p.x));
+            tau_g1.extend(fq_to_bytes(&p.y));
+            tau_i *= tau;
+        }
+
+        let mut tau_g2 = vec![];
+        let mut tau_i = Fr::ONE;
+        for _ in 0..num_g2 {
+            let p = (G2Projective::from(g2) * tau_i).into_affine();
+            for c in [p.x.c0, p.x.c1, p.y.c0, p.y.c1] {
+                tau_g2.extend(fq_to_bytes(&c));
+            }
+            tau_i *= tau;
+        }
+
+        let mut bytes = PTAU_MAGIC.to_vec();
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend(3u32.to_le_bytes());
+        for (section_type, data) in [
+            (HEADER_SECTION, header),
+            (TAU_G1_SECTION, tau_g1),
+            (TAU_G2_SECTION, tau_g2),
+        ] {
+            bytes.extend(section_type.to_le_bytes());
+            bytes.extend((data.len() as u64).to_le_bytes());
+            bytes.extend(data);
+        }
+        bytes
+    }
+
+    #[test]
+    fn parse_ptau() {
+        let tau = Fr::from(1234567u64);
+        let bytes = write_ptau(tau, 3);
+
+        let ptau = PowersOfTau::read(Cursor::new(&bytes), 10, 2).unwrap();
+        assert_eq!(ptau.power, 3);
+        assert_eq!(ptau.g1_powers.len(), 10);
+        assert_eq!(ptau.g1_powers[0], G1Affine::generator());
+        assert_eq!(
+            ptau.g1_powers[2],
+            (G1Projective::from(G1Affine::generator()) * tau.square()).into_affine()
+        );
+        assert_eq!(
+            ptau.g2_powers[1],
+            (G2Projective::from(G2Affine::generator()) * tau).into_affine()
+        );
+
+        assert!(matches!(
+            PowersOfTau::read(Cursor::new(&bytes), 16, 2),
+            Err(PtauError::NotEnoughPowers(15, 16))
+        ));
+        assert!(matches!(
+            PowersOfTau::read(Cursor::new(&bytes[1..]), 1, 1),
+            Err(PtauError::InvalidMagic)
+        ));
+    }
+}
@@ -20,7 +20,7 @@ use std::sync::Arc;
 use std::{iter, marker::PhantomData};
 
 use super::{
-    commitment_scheme::CommitmentScheme,
+    commitment_scheme::{CommitmentScheme, STREAMING_CHUNK_SIZE},
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG, SRS},
 };
 use crate::field::JoltField;
@@ -66,13 +66,13 @@ where
     pub open_pp: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
     pub tau_N_max_sub_2_N: P::G2Affine,
 }
 
-#[derive(Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphCommitment<P: Pairing>(P::G1Affine);
 
 impl<P: Pairing> Default for ZeromorphCommitment<P> {
@@ -423,6 +423,7 @@ where
 {
     type Field = P::ScalarField;
     type Setup = (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>);
+    type VerifierSetup = ZeromorphVerifierKey<P>;
     type Commitment = ZeromorphCommitment<P>;
     type Proof = ZeromorphProof<P>;
     type BatchedProof = ZeromorphProof<P>;
@@ -440,6 +441,10 @@ where
         .trim(max_poly_len)
     }
 
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.1
+    }
+
     fn commit(poly: &MultilinearPolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
         assert!(
             setup.0.commit_pp.g1_powers().len() > poly.len(),
@@ -450,6 +455,26 @@ where
         ZeromorphCommitment(UnivariateKZG::commit_as_univariate(&setup.0.commit_pp, poly).unwrap())
     }
 
+    fn commit_streaming<I>(coeffs: I, setup: &Self::Setup) -> Self::Commitment
+    where
+        I: Iterator<Item = Self::Field>,
+    {
+        ZeromorphCommitment(
+            UnivariateKZG::commit_streaming(&setup.0.commit_pp, coeffs, STREAMING_CHUNK_SIZE)
+                .unwrap(),
+        )
+    }
+
+    fn commit_sparse_binary(
+        indices: &[usize],
+        len: usize,
+        setup: &Self::Setup,
+    ) -> Self::Commitment {
+        ZeromorphCommitment(
+            UnivariateKZG::commit_sparse_binary(&setup.0.commit_pp, indices, len).unwrap(),
+        )
+    }
+
     fn batch_commit<U>(polys: &[U], gens: &Self::Setup) -> Vec<Self::Commitment>
     where
         U: Borrow<MultilinearPolynomial<Self::Field>> + Sync,
@@ -486,14 +511,14 @@ where
 
     fn verify(
         proof: &Self::Proof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         opening: &Self::Field,         // evaluation \widetilde{Z}(r)
         commitment: &Self::Commitment,
     ) -> Result<(), ProofVerifyError> {
         Zeromorph::<P, ProofTranscript>::verify(
-            &setup.1,
+            setup,
             commitment,
             opening_point,
             opening,
@@ -2,7 +2,8 @@ use std::ops::Index;
 
 use super::multilinear_polynomial::{BindingOrder, PolynomialBinding};
 use crate::utils::math::Math;
-use crate::utils::thread::unsafe_allocate_zero_vec;
+use crate::utils::profiling::count_binding_allocation;
+use crate::utils::thread::{drop_in_background_thread, unsafe_allocate_zero_vec};
 use crate::{field::JoltField, utils};
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
@@ -75,12 +76,25 @@ impl SmallScalar for i64 {
     }
 }
 
-#[derive(Default, Debug, PartialEq)]
+#[derive(Default, Debug)]
 pub struct CompactPolynomial<T: SmallScalar, F: JoltField> {
     num_vars: usize,
     len: usize,
     pub coeffs: Vec<T>,
     pub bound_coeffs: Vec<F>,
+    /// The buffer `bind_parallel` writes `bound_coeffs` bound from low to high into
+    /// before swapping the two, so that it is reused across rounds (see
+    /// `DensePolynomial::bound_poly_var_bot_01_optimized`).
+    binding_scratch_space: Vec<F>,
+}
+
+impl<T: SmallScalar, F: JoltField> PartialEq for CompactPolynomial<T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_vars == other.num_vars
+            && self.len == other.len
+            && self.coeffs == other.coeffs
+            && self.bound_coeffs == other.bound_coeffs
+    }
 }
 
 impl<T: SmallScalar, F: JoltField> Valid for CompactPolynomial<T, F> {
@@ -126,6 +140,7 @@ impl<T: SmallScalar, F: JoltField> CompactPolynomial<T, F> {
             len: coeffs.len(),
             coeffs,
             bound_coeffs: vec![],
+            binding_scratch_space: vec![],
         }
     }
 
@@ -181,6 +196,7 @@ impl<T: SmallScalar, F: JoltField> PolynomialBinding<F> for CompactPolynomial<T,
                 }
             }
         } else {
+            count_binding_allocation();
             let r_r2 = r * F::montgomery_r2().unwrap_or(F::one());
             let one_minus_r_r2 = (F::one() - r) * F::montgomery_r2().unwrap_or(F::one());
             match order {
@@ -222,17 +238,27 @@ impl<T: SmallScalar, F: JoltField> PolynomialBinding<F> for CompactPolynomial<T,
         if self.is_bound() {
             match order {
                 BindingOrder::LowToHigh => {
-                    // TODO(moodlezoup): Use `binding_scratch_space` trick
-                    let mut new_coeffs = unsafe_allocate_zero_vec(n);
-                    for i in 0..n {
-                        if self.bound_coeffs[2 * i + 1] == self.bound_coeffs[2 * i] {
-                            new_coeffs[i] = self.bound_coeffs[2 * i];
-                        } else {
-                            new_coeffs[i] = self.bound_coeffs[2 * i]
-                                + r * (self.bound_coeffs[2 * i + 1] - self.bound_coeffs[2 * i]);
-                        }
+                    if self.binding_scratch_space.len() < n {
+                        count_binding_allocation();
+                        self.binding_scratch_space = unsafe_allocate_zero_vec(n);
+                    }
+                    self.binding_scratch_space.truncate(n);
+                    self.binding_scratch_space
+                        .par_iter_mut()
+                        .enumerate()
+                        .for_each(|(i, z)| {
+                            let (low, high) =
+                                (self.bound_coeffs[2 * i], self.bound_coeffs[2 * i + 1]);
+                            *z = if high == low {
+                                low
+                            } else {
+                                low + r * (high - low)
+                            };
+                        });
+                    std::mem::swap(&mut self.bound_coeffs, &mut self.binding_scratch_space);
+                    if n == 1 {
+                        drop_in_background_thread(std::mem::take(&mut self.binding_scratch_space));
                     }
-                    self.bound_coeffs = new_coeffs;
                 }
                 BindingOrder::HighToLow => {
                     let (left, right) = self.bound_coeffs.split_at_mut(n);
@@ -245,6 +271,7 @@ impl<T: SmallScalar, F: JoltField> PolynomialBinding<F> for CompactPolynomial<T,
                 }
             }
         } else {
+            count_binding_allocation();
             let r_r2 = r * F::montgomery_r2().unwrap_or(F::one());
             let one_minus_r_r2 = (F::one() - r) * F::montgomery_r2().unwrap_or(F::one());
             match order {
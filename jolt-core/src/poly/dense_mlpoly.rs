@@ -1,20 +1,27 @@
 #![allow(clippy::too_many_arguments)]
 use crate::poly::eq_poly::EqPolynomial;
+use crate::utils::profiling::count_binding_allocation;
 use crate::utils::thread::{drop_in_background_thread, unsafe_allocate_zero_vec};
 use crate::utils::{self, compute_dotproduct, compute_dotproduct_low_optimized};
 
 use crate::field::JoltField;
 use crate::utils::math::Math;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use core::ops::Index;
 use rand_core::{CryptoRng, RngCore};
 use rayon::prelude::*;
 
-#[derive(Default, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Default, Debug)]
 pub struct DensePolynomial<F: JoltField> {
     num_vars: usize, // the number of variables in the multilinear polynomial
     len: usize,
     pub Z: Vec<F>, // evaluations of the polynomial in all the 2^num_vars Boolean inputs
+    /// The buffer `bound_poly_var_bot_01_optimized` writes the bound evaluations into
+    /// before swapping it with `Z`, so that binding reuses the previous round's buffer
+    /// instead of allocating a new one every round. Neither compared nor serialized.
+    binding_scratch_space: Vec<F>,
 }
 
 impl<F: JoltField> DensePolynomial<F> {
@@ -29,6 +36,7 @@ impl<F: JoltField> DensePolynomial<F> {
             num_vars: Z.len().log_2(),
             len: Z.len(),
             Z,
+            binding_scratch_space: vec![],
         }
     }
 
@@ -43,6 +51,7 @@ impl<F: JoltField> DensePolynomial<F> {
             num_vars: poly_evals.len().log_2(),
             len: poly_evals.len(),
             Z: poly_evals,
+            binding_scratch_space: vec![],
         }
     }
 
@@ -116,6 +125,7 @@ impl<F: JoltField> DensePolynomial<F> {
     #[tracing::instrument(skip_all)]
     pub fn new_poly_from_bound_poly_var_top(&self, r: &F) -> Self {
         let n = self.len() / 2;
+        count_binding_allocation();
         let mut new_evals: Vec<F> = unsafe_allocate_zero_vec(n);
 
         for i in 0..n {
@@ -134,12 +144,14 @@ impl<F: JoltField> DensePolynomial<F> {
             num_vars,
             len,
             Z: new_evals,
+            binding_scratch_space: vec![],
         }
     }
 
     #[tracing::instrument(skip_all)]
     pub fn new_poly_from_bound_poly_var_top_flags(&self, r: &F) -> Self {
         let n = self.len() / 2;
+        count_binding_allocation();
         let mut new_evals: Vec<F> = unsafe_allocate_zero_vec(n);
 
         for i in 0..n {
@@ -174,6 +186,7 @@ impl<F: JoltField> DensePolynomial<F> {
             num_vars,
             len,
             Z: new_evals,
+            binding_scratch_space: vec![],
         }
     }
 
@@ -189,22 +202,34 @@ impl<F: JoltField> DensePolynomial<F> {
         self.len = n;
     }
 
+    /// Binds the polynomial's least significant index bit to `r`, in parallel and
+    /// optimized for evaluations in {0, 1}. The bound evaluations are written to
+    /// `binding_scratch_space` and swapped with `Z`, so only the first round
+    /// allocates; the scratch buffer is freed once the polynomial is fully bound.
     pub fn bound_poly_var_bot_01_optimized(&mut self, r: &F) {
         let n = self.len() / 2;
-        let mut new_z = unsafe_allocate_zero_vec(n);
-        new_z.par_iter_mut().enumerate().for_each(|(i, z)| {
-            let m = self.Z[2 * i + 1] - self.Z[2 * i];
-            *z = if m.is_zero() {
-                self.Z[2 * i]
-            } else if m.is_one() {
-                self.Z[2 * i] + r
-            } else {
-                self.Z[2 * i] + *r * m
-            }
-        });
-
-        let old_Z = std::mem::replace(&mut self.Z, new_z);
-        drop_in_background_thread(old_Z);
+        if self.binding_scratch_space.len() < n {
+            count_binding_allocation();
+            self.binding_scratch_space = unsafe_allocate_zero_vec(n);
+        }
+        self.binding_scratch_space.truncate(n);
+        self.binding_scratch_space
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(i, z)| {
+                let m = self.Z[2 * i + 1] - self.Z[2 * i];
+                *z = if m.is_zero() {
+                    self.Z[2 * i]
+                } else if m.is_one() {
+                    self.Z[2 * i] + r
+                } else {
+                    self.Z[2 * i] + *r * m
+                }
+            });
+        std::mem::swap(&mut self.Z, &mut self.binding_scratch_space);
+        if n == 1 {
+            drop_in_background_thread(std::mem::take(&mut self.binding_scratch_space));
+        }
 
         self.num_vars -= 1;
         self.len = n;
@@ -265,6 +290,51 @@ impl<F: JoltField> Clone for DensePolynomial<F> {
     }
 }
 
+impl<F: JoltField> PartialEq for DensePolynomial<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_vars == other.num_vars && self.len == other.len && self.Z == other.Z
+    }
+}
+
+impl<F: JoltField> CanonicalSerialize for DensePolynomial<F> {
+    fn serialize_with_mode<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.num_vars.serialize_with_mode(&mut writer, compress)?;
+        self.len.serialize_with_mode(&mut writer, compress)?;
+        self.Z.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.num_vars.serialized_size(compress)
+            + self.len.serialized_size(compress)
+            + self.Z.serialized_size(compress)
+    }
+}
+
+impl<F: JoltField> CanonicalDeserialize for DensePolynomial<F> {
+    fn deserialize_with_mode<R: std::io::Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            num_vars: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            len: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+            Z: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            binding_scratch_space: vec![],
+        })
+    }
+}
+
+impl<F: JoltField> Valid for DensePolynomial<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.Z.check()
+    }
+}
+
 impl<F: JoltField> Index<usize> for DensePolynomial<F> {
     type Output = F;
 
@@ -339,4 +409,23 @@ mod tests {
             Fr::from(8)
         );
     }
+
+    #[test]
+    fn bind_reuses_scratch_space() {
+        let mut rng = test_rng();
+        let mut poly = DensePolynomial::<Fr>::random(10, &mut rng);
+        let mut expected = poly.clone();
+
+        let mut buffers = std::collections::HashSet::new();
+        for _ in 0..10 {
+            let r = Fr::random(&mut rng);
+            poly.bound_poly_var_bot_01_optimized(&r);
+            expected.bound_poly_var_bot(&r);
+            assert_eq!(poly.Z[..poly.len()], expected.Z[..expected.len()]);
+            buffers.insert(poly.Z.as_ptr());
+        }
+        // The original buffer and the one allocated in the first round alternate
+        assert_eq!(buffers.len(), 2);
+        assert!(poly.binding_scratch_space.is_empty());
+    }
 }
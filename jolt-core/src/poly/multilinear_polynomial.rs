@@ -196,6 +196,24 @@ impl<F: JoltField> MultilinearPolynomial<F> {
         }
     }
 
+    /// If the (unbound) polynomial is a 0/1 vector, as flag polynomials are, returns the
+    /// indices of its ones (see `CommitmentScheme::commit_sparse_binary`).
+    pub fn binary_indices(&self) -> Option<Vec<usize>> {
+        match self {
+            MultilinearPolynomial::U8Scalars(poly)
+                if !poly.is_bound() && poly.coeffs.par_iter().all(|coeff| *coeff <= 1) =>
+            {
+                Some(
+                    (0..poly.coeffs.len())
+                        .into_par_iter()
+                        .filter(|&index| poly.coeffs[index] == 1)
+                        .collect(),
+                )
+            }
+            _ => None,
+        }
+    }
+
     /// Gets the polynomial coefficient at the given `index`, as an `i64`.
     /// Panics if the polynomial is a large-scalar polynomial.
     pub fn get_coeff_i64(&self, index: usize) -> i64 {
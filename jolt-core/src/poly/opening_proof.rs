@@ -15,6 +15,7 @@ use super::{
     multilinear_polynomial::{BindingOrder, MultilinearPolynomial, PolynomialBinding},
     unipoly::{CompressedUniPoly, UniPoly},
 };
+use crate::jolt::vm::progress::check_cancelled;
 #[cfg(test)]
 use crate::poly::multilinear_polynomial::PolynomialEvaluation;
 use crate::{
@@ -33,6 +34,7 @@ use crate::{
 /// at the (same) point.
 /// Multiple `ProverOpening`s can be accumulated and further
 /// batched/reduced using a `ProverOpeningAccumulator`.
+#[derive(Clone)]
 pub struct ProverOpening<F: JoltField> {
     /// The polynomial being opened. May be a random linear combination
     /// of multiple polynomials all being opened at the same point.
@@ -137,9 +139,9 @@ pub struct ReducedOpeningProof<
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 > {
-    sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
-    sumcheck_claims: Vec<F>,
-    joint_opening_proof: PCS::Proof,
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+    pub(crate) sumcheck_claims: Vec<F>,
+    pub(crate) joint_opening_proof: PCS::Proof,
 }
 
 impl<F: JoltField, ProofTranscript: Transcript> Default
@@ -150,6 +152,18 @@ impl<F: JoltField, ProofTranscript: Transcript> Default
     }
 }
 
+#[cfg(test)]
+impl<F: JoltField, ProofTranscript: Transcript> Clone
+    for ProverOpeningAccumulator<F, ProofTranscript>
+{
+    fn clone(&self) -> Self {
+        Self {
+            openings: self.openings.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, ProofTranscript> {
     pub fn new() -> Self {
         Self {
@@ -234,38 +248,76 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
     }
 
     /// Reduces the multiple openings accumulated into a single opening proof,
-    /// using a single sumcheck.
+    /// using a single sumcheck. Consumes the accumulator, so that the accumulated
+    /// polynomials are moved into the reduction rather than copied.
     #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::reduce_and_prove")]
     pub fn reduce_and_prove<PCS: CommitmentScheme<ProofTranscript, Field = F>>(
-        &mut self,
+        self,
         pcs_setup: &PCS::Setup,
         transcript: &mut ProofTranscript,
     ) -> ReducedOpeningProof<F, PCS, ProofTranscript> {
-        // Generate coefficients for random linear combination
-        let rho: F = transcript.challenge_scalar();
-        let mut rho_powers = vec![F::one()];
-        for i in 1..self.openings.len() {
-            rho_powers.push(rho_powers[i - 1] * rho);
-        }
-
-        // TODO(moodlezoup): surely there's a better way to do this
-        let unbound_polys = self
-            .openings
+        // Different subprotocols may open their polynomials at the same point. Merge
+        // such openings so that every distinct opening point contributes a single
+        // claim to the reduction sumcheck.
+        let beta: F = transcript.challenge_scalar();
+        let groups =
+            group_by_opening_point(self.openings.iter().map(|opening| &opening.opening_point));
+        let mut accumulated: Vec<Option<ProverOpening<F>>> =
+            self.openings.into_iter().map(Some).collect();
+        let mut openings: Vec<ProverOpening<F>> = groups
             .iter()
+            .map(|group| {
+                if group.len() == 1 {
+                    return accumulated[group[0]].take().unwrap();
+                }
+                let coeffs = powers(beta, group.len());
+                let members: Vec<ProverOpening<F>> = group
+                    .iter()
+                    .map(|i| accumulated[*i].take().unwrap())
+                    .collect();
+                let claim = coeffs
+                    .iter()
+                    .zip(members.iter())
+                    .map(|(coeff, opening)| *coeff * opening.claim)
+                    .sum();
+                let polynomial = MultilinearPolynomial::linear_combination(
+                    &members
+                        .iter()
+                        .map(|opening| &opening.polynomial)
+                        .collect::<Vec<_>>(),
+                    &coeffs,
+                );
+                let first = members.into_iter().next().unwrap();
+                ProverOpening {
+                    polynomial,
+                    eq_poly: first.eq_poly,
+                    opening_point: first.opening_point,
+                    claim,
+                    #[cfg(test)]
+                    batch: vec![],
+                }
+            })
+            .collect();
+
+        // The sumcheck binds the openings' polynomials in place, so keep a copy of
+        // the unbound polynomials for the joint opening proof
+        let unbound_polys: Vec<MultilinearPolynomial<F>> = openings
+            .par_iter()
             .map(|opening| opening.polynomial.clone())
-            .collect::<Vec<_>>();
+            .collect();
+
+        // Generate coefficients for random linear combination
+        let rho: F = transcript.challenge_scalar();
+        let rho_powers = powers(rho, openings.len());
 
         // Use sumcheck reduce many openings to one
         let (sumcheck_proof, r_sumcheck, sumcheck_claims) =
-            self.prove_batch_opening_reduction(&rho_powers, transcript);
+            Self::prove_batch_opening_reduction(&mut openings, &rho_powers, transcript);
 
         transcript.append_scalars(&sumcheck_claims);
 
         let gamma: F = transcript.challenge_scalar();
-        let mut gamma_powers = vec![F::one()];
-        for i in 1..self.openings.len() {
-            gamma_powers.push(gamma_powers[i - 1] * gamma);
-        }
+        let gamma_powers = powers(gamma, openings.len());
 
         let joint_poly = MultilinearPolynomial::linear_combination(
             &unbound_polys.iter().collect::<Vec<_>>(),
@@ -275,12 +327,6 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
         // Reduced opening proof
         let joint_opening_proof = PCS::prove(pcs_setup, &joint_poly, &r_sumcheck, transcript);
 
-        #[cfg(test)]
-        self.openings
-            .iter_mut()
-            .zip(unbound_polys.into_iter())
-            .for_each(|(opening, poly)| opening.polynomial = poly);
-
         ReducedOpeningProof {
             sumcheck_proof,
             sumcheck_claims,
@@ -291,12 +337,11 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
     /// Proves the sumcheck used to prove the reduction of many openings into one.
     #[tracing::instrument(skip_all, name = "prove_batch_opening_reduction")]
     pub fn prove_batch_opening_reduction(
-        &mut self,
+        openings: &mut [ProverOpening<F>],
         coeffs: &[F],
         transcript: &mut ProofTranscript,
     ) -> (SumcheckInstanceProof<F, ProofTranscript>, Vec<F>, Vec<F>) {
-        let max_num_vars = self
-            .openings
+        let max_num_vars = openings
             .iter()
             .map(|opening| opening.polynomial.get_num_vars())
             .max()
@@ -306,7 +351,7 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
         // polynomials may be of different sizes
         let mut e: F = coeffs
             .par_iter()
-            .zip(self.openings.par_iter())
+            .zip(openings.par_iter())
             .map(|(coeff, opening)| {
                 let scaled_claim = if opening.polynomial.get_num_vars() != max_num_vars {
                     F::from_u64(1 << (max_num_vars - opening.polynomial.get_num_vars()))
@@ -322,8 +367,9 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
         let mut compressed_polys: Vec<CompressedUniPoly<F>> = Vec::new();
 
         for round in 0..max_num_vars {
+            check_cancelled();
             let remaining_rounds = max_num_vars - round;
-            let uni_poly = self.compute_quadratic(coeffs, remaining_rounds, e);
+            let uni_poly = Self::compute_quadratic(openings, coeffs, remaining_rounds, e);
             let compressed_poly = uni_poly.compress();
 
             // append the prover's message to the transcript
@@ -331,7 +377,7 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
             let r_j = transcript.challenge_scalar();
             r.push(r_j);
 
-            self.openings.par_iter_mut().for_each(|opening| {
+            openings.par_iter_mut().for_each(|opening| {
                 if remaining_rounds <= opening.opening_point.len() {
                     rayon::join(
                         || opening.eq_poly.bind(r_j, BindingOrder::HighToLow),
@@ -344,8 +390,7 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
             compressed_polys.push(compressed_poly);
         }
 
-        let claims: Vec<_> = self
-            .openings
+        let claims: Vec<_> = openings
             .iter()
             .map(|opening| opening.polynomial.final_sumcheck_claim())
             .collect();
@@ -357,13 +402,12 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
     /// prover's message in each round of the sumcheck in `prove_batch_opening_reduction`.
     #[tracing::instrument(skip_all)]
     fn compute_quadratic(
-        &self,
+        openings: &[ProverOpening<F>],
         coeffs: &[F],
         remaining_sumcheck_rounds: usize,
         previous_round_claim: F,
     ) -> UniPoly<F> {
-        let evals: Vec<(F, F)> = self
-            .openings
+        let evals: Vec<(F, F)> = openings
             .par_iter()
             .map(|opening| {
                 if remaining_sumcheck_rounds <= opening.opening_point.len() {
@@ -539,12 +583,43 @@ where
     /// and a single opening proof) indeed proves the openings accumulated.
     pub fn reduce_and_verify(
         &self,
-        pcs_setup: &PCS::Setup,
+        pcs_setup: &PCS::VerifierSetup,
         reduced_opening_proof: &ReducedOpeningProof<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        let num_sumcheck_rounds = self
-            .openings
+        // Merge openings that share an opening point, mirroring
+        // `ProverOpeningAccumulator::reduce_and_prove`.
+        let beta: F = transcript.challenge_scalar();
+        let openings: Vec<VerifierOpening<F, PCS, ProofTranscript>> =
+            group_by_opening_point(self.openings.iter().map(|opening| &opening.opening_point))
+                .into_iter()
+                .map(|group| {
+                    let beta_powers = powers(beta, group.len());
+                    let commitment = PCS::combine_commitments(
+                        &group
+                            .iter()
+                            .map(|i| &self.openings[*i].commitment)
+                            .collect::<Vec<_>>(),
+                        &beta_powers,
+                    );
+                    let claim = beta_powers
+                        .iter()
+                        .zip(group.iter())
+                        .map(|(coeff, i)| *coeff * self.openings[*i].claim)
+                        .sum();
+                    VerifierOpening::new(
+                        commitment,
+                        self.openings[group[0]].opening_point.clone(),
+                        claim,
+                    )
+                })
+                .collect();
+
+        if reduced_opening_proof.sumcheck_claims.len() != openings.len() {
+            return Err(ProofVerifyError::InternalError);
+        }
+
+        let num_sumcheck_rounds = openings
             .iter()
             .map(|opening| opening.opening_point.len())
             .max()
@@ -552,13 +627,11 @@ where
 
         // Generate coefficients for random linear combination
         let rho: F = transcript.challenge_scalar();
-        let mut rho_powers = vec![F::one()];
-        for i in 1..self.openings.len() {
-            rho_powers.push(rho_powers[i - 1] * rho);
-        }
+        let rho_powers = powers(rho, openings.len());
 
         // Verify the sumcheck
-        let (sumcheck_claim, r_sumcheck) = self.verify_batch_opening_reduction(
+        let (sumcheck_claim, r_sumcheck) = Self::verify_batch_opening_reduction(
+            &openings,
             &rho_powers,
             num_sumcheck_rounds,
             &reduced_opening_proof.sumcheck_proof,
@@ -567,8 +640,7 @@ where
 
         // Compute random linear combination of the claims, accounting for the fact that the
         // polynomials may be of different sizes
-        let expected_sumcheck_claim: F = self
-            .openings
+        let expected_sumcheck_claim: F = openings
             .iter()
             .zip(rho_powers.iter())
             .zip(reduced_opening_proof.sumcheck_claims.iter())
@@ -587,15 +659,11 @@ where
         transcript.append_scalars(&reduced_opening_proof.sumcheck_claims);
 
         let gamma: F = transcript.challenge_scalar();
-        let mut gamma_powers = vec![F::one()];
-        for i in 1..self.openings.len() {
-            gamma_powers.push(gamma_powers[i - 1] * gamma);
-        }
+        let gamma_powers = powers(gamma, openings.len());
 
         // Compute joint commitment = ∑ᵢ γⁱ⋅ commitmentᵢ
         let joint_commitment = PCS::combine_commitments(
-            &self
-                .openings
+            &openings
                 .iter()
                 .map(|opening| &opening.commitment)
                 .collect::<Vec<_>>(),
@@ -605,7 +673,7 @@ where
         let joint_claim: F = gamma_powers
             .iter()
             .zip(reduced_opening_proof.sumcheck_claims.iter())
-            .zip(self.openings.iter())
+            .zip(openings.iter())
             .map(|((coeff, claim), opening)| {
                 let (r_lo, _) =
                     r_sumcheck.split_at(num_sumcheck_rounds - opening.opening_point.len());
@@ -628,7 +696,7 @@ where
 
     /// Verifies the sumcheck proven in `ProverOpeningAccumulator::prove_batch_opening_reduction`.
     fn verify_batch_opening_reduction(
-        &self,
+        openings: &[VerifierOpening<F, PCS, ProofTranscript>],
         coeffs: &[F],
         num_sumcheck_rounds: usize,
         sumcheck_proof: &SumcheckInstanceProof<F, ProofTranscript>,
@@ -636,7 +704,7 @@ where
     ) -> Result<(F, Vec<F>), ProofVerifyError> {
         let combined_claim: F = coeffs
             .par_iter()
-            .zip(self.openings.par_iter())
+            .zip(openings.par_iter())
             .map(|(coeff, opening)| {
                 let scaled_claim = if opening.opening_point.len() != num_sumcheck_rounds {
                     F::from_u64(1 << (num_sumcheck_rounds - opening.opening_point.len()))
@@ -651,3 +719,101 @@ where
         sumcheck_proof.verify(combined_claim, num_sumcheck_rounds, 2, transcript)
     }
 }
+
+/// Partitions the indices of `opening_points` into groups of identical opening points,
+/// ordered by first occurrence.
+fn group_by_opening_point<'a, F: JoltField>(
+    opening_points: impl Iterator<Item = &'a Vec<F>>,
+) -> Vec<Vec<usize>> {
+    let mut representatives: Vec<&Vec<F>> = vec![];
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (i, point) in opening_points.enumerate() {
+        match representatives.iter().position(|r| *r == point) {
+            Some(group_index) => groups[group_index].push(i),
+            None => {
+                representatives.push(point);
+                groups.push(vec![i]);
+            }
+        }
+    }
+    groups
+}
+
+/// Returns `[1, x, x^2, ..., x^{n-1}]`.
+fn powers<F: JoltField>(x: F, n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n);
+    let mut current = F::one();
+    for _ in 0..n {
+        powers.push(current);
+        current *= x;
+    }
+    powers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::mock::MockCommitScheme;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+    use ark_std::{test_rng, UniformRand};
+
+    #[test]
+    fn openings_at_shared_point_are_merged() {
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let mut rng = test_rng();
+        let polys: Vec<MultilinearPolynomial<Fr>> = [4, 4, 3, 4]
+            .into_iter()
+            .map(|num_vars| {
+                let coeffs: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::rand(&mut rng)).collect();
+                MultilinearPolynomial::from(coeffs)
+            })
+            .collect();
+        let r_shared: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let r_other: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        // (polynomial indices, opening point) for each "subprotocol"
+        let openings = [
+            (vec![0, 1], r_shared.clone()),
+            (vec![2], r_other),
+            (vec![3], r_shared),
+        ];
+
+        let mut prover_transcript = KeccakTranscript::new(b"test");
+        let mut prover_accumulator = ProverOpeningAccumulator::<Fr, KeccakTranscript>::new();
+        for (indices, point) in openings.iter() {
+            let batch: Vec<_> = indices.iter().map(|i| &polys[*i]).collect();
+            let claims: Vec<Fr> = batch.iter().map(|poly| poly.evaluate(point)).collect();
+            prover_accumulator.append(
+                &batch,
+                DensePolynomial::new(EqPolynomial::evals(point)),
+                point.clone(),
+                &claims,
+                &mut prover_transcript,
+            );
+        }
+        let proof = prover_accumulator
+            .clone()
+            .reduce_and_prove::<PCS>(&(), &mut prover_transcript);
+        // Three subprotocol openings, but only two distinct opening points
+        assert_eq!(prover_accumulator.len(), 3);
+        assert_eq!(proof.sumcheck_claims.len(), 2);
+
+        let commitments: Vec<_> = polys.iter().map(|poly| PCS::commit(poly, &())).collect();
+        let mut verifier_transcript = KeccakTranscript::new(b"test");
+        let mut verifier_accumulator = VerifierOpeningAccumulator::<Fr, PCS, _>::new();
+        verifier_accumulator.compare_to(prover_accumulator, &());
+        for (indices, point) in openings.iter() {
+            let batch: Vec<_> = indices.iter().map(|i| &commitments[*i]).collect();
+            let claims: Vec<Fr> = indices.iter().map(|i| polys[*i].evaluate(point)).collect();
+            verifier_accumulator.append(
+                &batch,
+                point.clone(),
+                &claims.iter().collect::<Vec<_>>(),
+                &mut verifier_transcript,
+            );
+        }
+        assert!(verifier_accumulator
+            .reduce_and_verify(&(), &proof, &mut verifier_transcript)
+            .is_ok());
+    }
+}
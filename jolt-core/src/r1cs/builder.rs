@@ -15,6 +15,7 @@ use rayon::prelude::*;
 use std::{
     collections::BTreeMap,
     marker::PhantomData,
+    panic::Location,
     sync::atomic::{AtomicBool, Ordering},
 };
 
@@ -24,9 +25,22 @@ pub struct Constraint {
     pub(crate) a: LC,
     pub(crate) b: LC,
     pub(crate) c: LC,
+    /// Where the constraint was added to its builder.
+    pub(crate) location: &'static Location<'static>,
 }
 
 impl Constraint {
+    /// Writes the constraint out over its inputs, e.g. `OpFlags(Load) ⋅ (RAM_Read - RAM_Write) == 0`.
+    pub(crate) fn describe<const C: usize, I: ConstraintInput>(&self) -> String {
+        let mut f = String::new();
+        let _ = self.a.pretty_fmt::<C, I>(&mut f);
+        f.push_str(" ⋅ ");
+        let _ = self.b.pretty_fmt::<C, I>(&mut f);
+        f.push_str(" == ");
+        let _ = self.c.pretty_fmt::<C, I>(&mut f);
+        f
+    }
+
     #[cfg(test)]
     pub(crate) fn pretty_fmt<const C: usize, I: ConstraintInput, F: JoltField>(
         &self,
@@ -216,6 +230,7 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
         new_aux
     }
 
+    #[track_caller]
     pub fn constrain_eq(&mut self, left: impl Into<LC>, right: impl Into<LC>) {
         // left - right == 0
         let left: LC = left.into();
@@ -227,10 +242,12 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
             a,
             b,
             c: LC::zero(),
+            location: Location::caller(),
         };
         self.constraints.push(constraint);
     }
 
+    #[track_caller]
     pub fn constrain_eq_conditional(
         &mut self,
         condition: impl Into<LC>,
@@ -245,10 +262,12 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
         let a = condition;
         let b = left - right;
         let c = LC::zero();
-        let constraint = Constraint { a, b, c }; // TODO(sragss): Can do better on middle term.
+        let location = Location::caller();
+        let constraint = Constraint { a, b, c, location }; // TODO(sragss): Can do better on middle term.
         self.constraints.push(constraint);
     }
 
+    #[track_caller]
     pub fn constrain_binary(&mut self, value: impl Into<LC>) {
         let one: LC = Variable::Constant.into();
         let a: LC = value.into();
@@ -258,10 +277,12 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
             a,
             b,
             c: LC::zero(),
+            location: Location::caller(),
         };
         self.constraints.push(constraint);
     }
 
+    #[track_caller]
     pub fn constrain_if_else(
         &mut self,
         condition: impl Into<LC>,
@@ -281,11 +302,13 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
             a: condition.clone(),
             b: (result_true - result_false.clone()),
             c: (alleged_result - result_false),
+            location: Location::caller(),
         };
         self.constraints.push(constraint);
     }
 
     #[must_use]
+    #[track_caller]
     pub fn allocate_if_else(
         &mut self,
         aux_symbol: I,
@@ -347,6 +370,7 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
         packed.into()
     }
 
+    #[track_caller]
     pub fn constrain_pack_le(
         &mut self,
         unpacked: Vec<Variable>,
@@ -363,6 +387,7 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
         self.constrain_eq(packed, result);
     }
 
+    #[track_caller]
     pub fn constrain_pack_be(
         &mut self,
         unpacked: Vec<Variable>,
@@ -382,16 +407,19 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> R1CSBuilder<C, F, I> {
     }
 
     /// Constrain x * y == z
+    #[track_caller]
     pub fn constrain_prod(&mut self, x: impl Into<LC>, y: impl Into<LC>, z: impl Into<LC>) {
         let constraint = Constraint {
             a: x.into(),
             b: y.into(),
             c: z.into(),
+            location: Location::caller(),
         };
         self.constraints.push(constraint);
     }
 
     #[must_use]
+    #[track_caller]
     pub fn allocate_prod(&mut self, aux_symbol: I, x: impl Into<LC>, y: impl Into<LC>) -> Variable {
         let (x, y) = (x.into(), y.into());
         let z = self.aux_prod(aux_symbol, &x, &y);
@@ -464,9 +492,12 @@ pub struct OffsetEqConstraint {
     pub(crate) cond: OffsetLC,
     pub(crate) a: OffsetLC,
     pub(crate) b: OffsetLC,
+    /// Where the constraint was constructed.
+    pub(crate) location: &'static Location<'static>,
 }
 
 impl OffsetEqConstraint {
+    #[track_caller]
     pub fn new(
         condition: (impl Into<LC>, bool),
         a: (impl Into<LC>, bool),
@@ -476,9 +507,31 @@ impl OffsetEqConstraint {
             cond: (condition.1, condition.0.into()),
             a: (a.1, a.0.into()),
             b: (b.1, b.0.into()),
+            location: Location::caller(),
         }
     }
 
+    /// Writes the constraint out over its inputs, with the inputs of the next step
+    /// wrapped in `next[..]`, e.g. `if OpFlags(Virtual): next[Bytecode_A] == (Bytecode_A + 1)`.
+    pub(crate) fn describe<const C: usize, I: ConstraintInput>(&self) -> String {
+        let describe_offset_lc = |f: &mut String, (is_next, lc): &OffsetLC| {
+            if *is_next {
+                f.push_str("next[");
+            }
+            let _ = lc.pretty_fmt::<C, I>(f);
+            if *is_next {
+                f.push(']');
+            }
+        };
+        let mut f = String::from("if ");
+        describe_offset_lc(&mut f, &self.cond);
+        f.push_str(": ");
+        describe_offset_lc(&mut f, &self.a);
+        f.push_str(" == ");
+        describe_offset_lc(&mut f, &self.b);
+        f
+    }
+
     #[cfg(test)]
     pub fn empty() -> Self {
         Self::new(
@@ -621,6 +674,14 @@ impl<const C: usize, F: JoltField, I: ConstraintInput> CombinedUniformBuilder<C,
         CrossStepR1CS { constraints }
     }
 
+    pub(super) fn uniform_constraints(&self) -> &[Constraint] {
+        &self.uniform_builder.constraints
+    }
+
+    pub(super) fn cross_step_constraints(&self) -> &[OffsetEqConstraint] {
+        &self.offset_equality_constraints
+    }
+
     #[tracing::instrument(skip_all)]
     pub fn compute_spartan_Az_Bz_Cz(
         &self,
@@ -1,28 +1,40 @@
-use common::{constants::REGISTER_COUNT, rv_trace::CircuitFlags};
+use std::fmt;
+use std::marker::PhantomData;
+use std::panic::Location;
+
+use common::{
+    constants::{INSTRUCTION_ALIGNMENT, REGISTER_COUNT},
+    rv_trace::CircuitFlags,
+};
+use rayon::prelude::*;
 use strum::IntoEnumIterator;
 
 use crate::{
     field::JoltField,
     jolt::{
         instruction::{
-            add::ADDInstruction, mul::MULInstruction, mulhu::MULHUInstruction,
-            mulu::MULUInstruction, sll::SLLInstruction, sra::SRAInstruction, srl::SRLInstruction,
-            sub::SUBInstruction,
+            add::ADDInstruction, exp::EXPInstruction, mul::MULInstruction, mulhu::MULHUInstruction,
+            mulu::MULUInstruction, relu::RELUInstruction, sigmoid::SIGMOIDInstruction,
+            sll::SLLInstruction, sra::SRAInstruction, srl::SRLInstruction, sub::SUBInstruction,
             virtual_assert_aligned_memory_access::AssertAlignedMemoryAccessInstruction,
             virtual_move::MOVEInstruction, virtual_movsign::MOVSIGNInstruction,
         },
         vm::rv32i_vm::RV32I,
     },
+    poly::multilinear_polynomial::MultilinearPolynomial,
 };
 
 use super::{
-    builder::{CombinedUniformBuilder, OffsetEqConstraint, R1CSBuilder},
+    builder::{eval_offset_lc, CombinedUniformBuilder, OffsetEqConstraint, R1CSBuilder},
     inputs::{AuxVariable, ConstraintInput, JoltR1CSInputs},
-    ops::Variable,
+    ops::{Variable, LC},
 };
 
 pub const PC_START_ADDRESS: i64 = 0x80000000;
-const PC_NOOP_SHIFT: i64 = 4;
+/// Bytecode addresses are compressed to `INSTRUCTION_ALIGNMENT`-byte units, and offset
+/// by the no-op prepended to the bytecode, so the PC is `2 * ELFAddress + PC_START_ADDRESS
+/// - PC_NOOP_SHIFT`.
+const PC_NOOP_SHIFT: i64 = INSTRUCTION_ALIGNMENT as i64;
 const LOG_M: usize = 16;
 const OPERAND_SIZE: usize = LOG_M / 2;
 
@@ -53,12 +65,48 @@ pub trait R1CSConstraints<const C: usize, F: JoltField> {
     ///     if condition { some constraint on steps i and i+1 }
     /// This structure is captured in `OffsetEqConstraint`.
     fn cross_step_constraints() -> Vec<OffsetEqConstraint>;
+    /// Identifies the constraint set beyond its structure, e.g. by the name of its
+    /// auxiliary constraints. Folded into `Jolt::protocol_hash` along with the
+    /// constraints themselves.
+    fn name() -> &'static str {
+        ""
+    }
+}
+
+/// Extra per-cycle constraints over the existing `JoltR1CSInputs`, added after Jolt's
+/// own by `JoltRV32IMConstraints<Self>`. Lets downstream crates constrain how their
+/// instructions' operands and lookups are used without patching jolt-core: to prove
+/// with them, implement `Jolt` for a VM whose `Constraints` are
+/// `JoltRV32IMConstraints<Self>`.
+///
+/// The constraints must hold at every step, including the no-op steps the trace is
+/// padded with (whose inputs are all 0).
+pub trait AuxiliaryConstraints<const C: usize, F: JoltField> {
+    /// Identifies these constraints, so that preprocessing saved with one set of
+    /// auxiliary constraints is never loaded with another.
+    const NAME: &'static str;
+
+    fn uniform_constraints(cs: &mut R1CSBuilder<C, F, JoltR1CSInputs>, memory_start: u64);
+}
+
+/// No auxiliary constraints.
+impl<const C: usize, F: JoltField> AuxiliaryConstraints<C, F> for () {
+    const NAME: &'static str = "";
+
+    fn uniform_constraints(_cs: &mut R1CSBuilder<C, F, JoltR1CSInputs>, _memory_start: u64) {}
 }
 
-pub struct JoltRV32IMConstraints;
-impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstraints {
+/// The constraints of RV32IM, followed by the auxiliary constraints `Aux`.
+pub struct JoltRV32IMConstraints<Aux = ()>(PhantomData<Aux>);
+impl<const C: usize, F: JoltField, Aux: AuxiliaryConstraints<C, F>> R1CSConstraints<C, F>
+    for JoltRV32IMConstraints<Aux>
+{
     type Inputs = JoltR1CSInputs;
 
+    fn name() -> &'static str {
+        Aux::NAME
+    }
+
     fn uniform_constraints(cs: &mut R1CSBuilder<C, F, Self::Inputs>, memory_start: u64) {
         for flag in RV32I::iter() {
             cs.constrain_binary(JoltR1CSInputs::InstructionFlags(flag));
@@ -74,7 +122,7 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         cs.constrain_pack_be(flags, JoltR1CSInputs::Bytecode_Bitflags, 1);
 
         let real_pc =
-            4i64 * JoltR1CSInputs::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
+            2i64 * JoltR1CSInputs::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
         let x = cs.allocate_if_else(
             JoltR1CSInputs::Aux(AuxVariable::LeftLookupOperand),
             JoltR1CSInputs::OpFlags(CircuitFlags::LeftOperandIsPC),
@@ -146,7 +194,10 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         cs.constrain_eq_conditional(is_mul, packed_query.clone(), product);
         cs.constrain_eq_conditional(
             JoltR1CSInputs::InstructionFlags(MOVSIGNInstruction::default().into())
-                + JoltR1CSInputs::InstructionFlags(MOVEInstruction::default().into()),
+                + JoltR1CSInputs::InstructionFlags(MOVEInstruction::default().into())
+                + JoltR1CSInputs::InstructionFlags(RELUInstruction::default().into())
+                + JoltR1CSInputs::InstructionFlags(EXPInstruction::default().into())
+                + JoltR1CSInputs::InstructionFlags(SIGMOIDInstruction::default().into()),
             packed_query.clone(),
             x,
         );
@@ -157,6 +208,14 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
             1,
         );
 
+        // The cycle counter is read with an advice lookup (the identity on its query), whose
+        // query must be the index of the step
+        cs.constrain_eq_conditional(
+            JoltR1CSInputs::OpFlags(CircuitFlags::CycleCounter),
+            packed_query.clone(),
+            JoltR1CSInputs::Step,
+        );
+
         let x_chunks: Vec<Variable> = (0..C)
             .map(|i| Variable::Input(JoltR1CSInputs::ChunksX(i).to_index::<C>()))
             .collect();
@@ -205,7 +264,8 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
             JoltR1CSInputs::RD_Write,
             JoltR1CSInputs::LookupOutput,
         );
-        // if (rd != 0 && is_jump_instr == 1) constrain(rd_val == 4 * PC)
+        // if (rd != 0 && is_jump_instr == 1) constrain(rd_val == PC + 4), or PC + 2 if the
+        // jump is compressed
         let rd_nonzero_and_jmp = cs.allocate_prod(
             JoltR1CSInputs::Aux(AuxVariable::WritePCtoRD),
             JoltR1CSInputs::Bytecode_RD,
@@ -213,15 +273,19 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         );
         cs.constrain_eq_conditional(
             rd_nonzero_and_jmp,
-            4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS,
+            2 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS + 2
+                - 2 * JoltR1CSInputs::OpFlags(CircuitFlags::Compressed),
             JoltR1CSInputs::RD_Write,
         );
 
+        // Like the bytecode addresses, `NextPC` is offset by `PC_NOOP_SHIFT`. A compressed
+        // instruction is 2 bytes long rather than 4, and is never `DoNotUpdatePC`.
         let next_pc_jump = cs.allocate_if_else(
             JoltR1CSInputs::Aux(AuxVariable::NextPCJump),
             JoltR1CSInputs::OpFlags(CircuitFlags::Jump),
-            JoltR1CSInputs::LookupOutput + 4,
-            4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS + 4
+            JoltR1CSInputs::LookupOutput + PC_NOOP_SHIFT,
+            2 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS + 4
+                - 2 * JoltR1CSInputs::OpFlags(CircuitFlags::Compressed)
                 - 4 * JoltR1CSInputs::OpFlags(CircuitFlags::DoNotUpdatePC),
         );
 
@@ -233,11 +297,13 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         let _next_pc = cs.allocate_if_else(
             JoltR1CSInputs::Aux(AuxVariable::NextPC),
             should_branch,
-            4 * JoltR1CSInputs::Bytecode_ELFAddress
+            2 * JoltR1CSInputs::Bytecode_ELFAddress
                 + PC_START_ADDRESS
                 + JoltR1CSInputs::Bytecode_Imm,
             next_pc_jump,
         );
+
+        Aux::uniform_constraints(cs, memory_start);
     }
 
     fn cross_step_constraints() -> Vec<OffsetEqConstraint> {
@@ -247,7 +313,7 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
             (JoltR1CSInputs::Bytecode_ELFAddress, true),
             (JoltR1CSInputs::Aux(AuxVariable::NextPC), false),
             (
-                4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS,
+                2 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS,
                 true,
             ),
         );
@@ -268,3 +334,230 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         vec![pc_constraint, virtual_sequence_constraint]
     }
 }
+
+/// A constraint that a witness violates at some step, as found by
+/// `check_satisfiability`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    /// The step of the trace the constraint is violated at.
+    pub step: usize,
+    /// The constraint, written out over its inputs.
+    pub constraint: String,
+    /// Where the constraint is defined.
+    pub location: &'static Location<'static>,
+    /// The step's opcode and registers; see `ConstraintInput::describe_step`.
+    pub context: String,
+    /// The value of each input of the constraint. Inputs of the next step (of a
+    /// cross-step constraint) are wrapped in `next[..]`.
+    pub inputs: Vec<(String, i128)>,
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "constraint `{}` ({}) is unsatisfied at step {}",
+            self.constraint, self.location, self.step
+        )?;
+        if !self.context.is_empty() {
+            write!(f, " ({})", self.context)?;
+        }
+        for (input, value) in &self.inputs {
+            write!(f, "\n    {input} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks a witness against the uniform and cross-step constraints of `builder`,
+/// reporting the first constraint violated at each step that violates any, in step
+/// order. As when proving, inputs of the step after the last are taken to be 0.
+#[tracing::instrument(skip_all)]
+pub fn check_satisfiability<const C: usize, F: JoltField, I: ConstraintInput>(
+    builder: &CombinedUniformBuilder<C, F, I>,
+    flattened_polynomials: &[&MultilinearPolynomial<F>], // N variables of (S steps)
+) -> Vec<ConstraintViolation> {
+    let num_steps = flattened_polynomials[0].len();
+    (0..num_steps)
+        .into_par_iter()
+        .filter_map(|step| first_violation(builder, flattened_polynomials, step, num_steps))
+        .collect()
+}
+
+fn first_violation<const C: usize, F: JoltField, I: ConstraintInput>(
+    builder: &CombinedUniformBuilder<C, F, I>,
+    flattened_polynomials: &[&MultilinearPolynomial<F>],
+    step: usize,
+    num_steps: usize,
+) -> Option<ConstraintViolation> {
+    let violation = |constraint: String, location, inputs| ConstraintViolation {
+        step,
+        constraint,
+        location,
+        context: I::describe_step::<C, F>(flattened_polynomials, step),
+        inputs,
+    };
+
+    for constraint in builder.uniform_constraints() {
+        let az = constraint.a.evaluate_row(flattened_polynomials, step);
+        let bz = constraint.b.evaluate_row(flattened_polynomials, step);
+        let cz = constraint.c.evaluate_row(flattened_polynomials, step);
+        if az * bz != cz {
+            let inputs = input_values::<C, F, I>(
+                [&constraint.a, &constraint.b, &constraint.c].map(|lc| (false, lc)),
+                flattened_polynomials,
+                step,
+                None,
+            );
+            return Some(violation(
+                constraint.describe::<C, I>(),
+                constraint.location,
+                inputs,
+            ));
+        }
+    }
+
+    let next_step = (step + 1 < num_steps).then_some(step + 1);
+    for constraint in builder.cross_step_constraints() {
+        let eq = eval_offset_lc(&constraint.a, flattened_polynomials, step, next_step)
+            - eval_offset_lc(&constraint.b, flattened_polynomials, step, next_step);
+        let condition = eval_offset_lc(&constraint.cond, flattened_polynomials, step, next_step);
+        if eq != 0 && condition != 0 {
+            let inputs = input_values::<C, F, I>(
+                [&constraint.cond, &constraint.a, &constraint.b]
+                    .map(|(is_next, lc)| (*is_next, lc)),
+                flattened_polynomials,
+                step,
+                next_step,
+            );
+            return Some(violation(
+                constraint.describe::<C, I>(),
+                constraint.location,
+                inputs,
+            ));
+        }
+    }
+    None
+}
+
+/// The distinct inputs of `lcs`, in order of appearance, with their values at `step`
+/// (or at `next_step`, or 0 past the last step, for the LCs over the next step).
+fn input_values<const C: usize, F: JoltField, I: ConstraintInput>(
+    lcs: [(bool, &LC); 3],
+    flattened_polynomials: &[&MultilinearPolynomial<F>],
+    step: usize,
+    next_step: Option<usize>,
+) -> Vec<(String, i128)> {
+    let mut seen = vec![];
+    let mut inputs = vec![];
+    for (is_next, lc) in lcs {
+        for term in lc.terms() {
+            let (Variable::Input(index) | Variable::Auxiliary(index)) = term.0 else {
+                continue;
+            };
+            if seen.contains(&(is_next, index)) {
+                continue;
+            }
+            seen.push((is_next, index));
+            let input = I::from_index::<C>(index);
+            inputs.push(if is_next {
+                let value = next_step.map_or(0, |next_step| {
+                    flattened_polynomials[index].get_coeff_i128(next_step)
+                });
+                (format!("next[{input:?}]"), value)
+            } else {
+                (
+                    format!("{input:?}"),
+                    flattened_polynomials[index].get_coeff_i128(step),
+                )
+            });
+        }
+    }
+    inputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn reports_first_violation_per_step() {
+        let mut cs = R1CSBuilder::<4, Fr, JoltR1CSInputs>::new();
+        cs.constrain_eq_conditional(
+            JoltR1CSInputs::OpFlags(CircuitFlags::Load),
+            JoltR1CSInputs::RAM_Read,
+            JoltR1CSInputs::RD_Write,
+        );
+        cs.constrain_binary(JoltR1CSInputs::OpFlags(CircuitFlags::Load));
+        let builder = CombinedUniformBuilder::construct(cs, 4, vec![]);
+
+        // Step 1 loads 5 into rd, but writes 4; step 3 also sets an invalid flag
+        let mut witness = vec![vec![0u64; 4]; JoltR1CSInputs::num_inputs::<4>()];
+        let mut set = |input: JoltR1CSInputs, step: usize, value: u64| {
+            witness[input.to_index::<4>()][step] = value;
+        };
+        set(JoltR1CSInputs::OpFlags(CircuitFlags::Load), 1, 1);
+        set(JoltR1CSInputs::RAM_Read, 1, 5);
+        set(JoltR1CSInputs::RD_Write, 1, 4);
+        set(JoltR1CSInputs::Bytecode_RD, 1, 7);
+        set(JoltR1CSInputs::OpFlags(CircuitFlags::Load), 3, 2);
+        set(JoltR1CSInputs::RAM_Read, 3, 1);
+        let polynomials: Vec<MultilinearPolynomial<Fr>> = witness
+            .into_iter()
+            .map(MultilinearPolynomial::from)
+            .collect();
+        let flattened_polynomials: Vec<_> = polynomials.iter().collect();
+
+        let violations = check_satisfiability(&builder, &flattened_polynomials);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].step, 1);
+        assert_eq!(violations[0].location.file(), file!());
+        assert!(violations[0].constraint.contains("RAM_Read"));
+        assert!(violations[0].context.contains("rd = x7 (4)"));
+        assert!(violations[0].inputs.contains(&("RAM_Read".to_string(), 5)));
+        // The first of the violated constraints is reported
+        assert_eq!(violations[1].step, 3);
+        assert!(violations[1].constraint.contains("RD_Write"));
+        assert!(violations[1].to_string().contains("at step 3"));
+    }
+
+    struct StoresLeaveRdUnchanged;
+    impl<const C: usize, F: JoltField> AuxiliaryConstraints<C, F> for StoresLeaveRdUnchanged {
+        const NAME: &'static str = "stores-leave-rd-unchanged";
+
+        fn uniform_constraints(cs: &mut R1CSBuilder<C, F, JoltR1CSInputs>, _memory_start: u64) {
+            cs.constrain_eq_conditional(
+                JoltR1CSInputs::OpFlags(CircuitFlags::Store),
+                JoltR1CSInputs::RD_Write,
+                JoltR1CSInputs::RD_Read,
+            );
+        }
+    }
+
+    #[test]
+    fn auxiliary_constraints() {
+        type Base = JoltRV32IMConstraints;
+        type Extended = JoltRV32IMConstraints<StoresLeaveRdUnchanged>;
+        let base =
+            <Base as R1CSConstraints<4, Fr>>::construct_constraints(4, PC_START_ADDRESS as u64);
+        let extended =
+            <Extended as R1CSConstraints<4, Fr>>::construct_constraints(4, PC_START_ADDRESS as u64);
+        assert_eq!(
+            extended.uniform_constraints().len(),
+            base.uniform_constraints().len() + 1
+        );
+        assert_eq!(<Base as R1CSConstraints<4, Fr>>::name(), "");
+        assert_eq!(
+            <Extended as R1CSConstraints<4, Fr>>::name(),
+            "stores-leave-rd-unchanged"
+        );
+
+        // The auxiliary constraint comes last, and is reported like Jolt's own
+        let auxiliary = extended.uniform_constraints().last().unwrap();
+        assert_eq!(auxiliary.location.file(), file!());
+        assert!(auxiliary
+            .describe::<4, JoltR1CSInputs>()
+            .contains("OpFlags(Store) ⋅ (-RD_Read + RD_Write) == 0"));
+    }
+}
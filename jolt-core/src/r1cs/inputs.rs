@@ -10,16 +10,19 @@ use crate::jolt::vm::rv32i_vm::RV32I;
 use crate::jolt::vm::{JoltCommitments, JoltStuff, JoltTraceStep};
 use crate::lasso::memory_checking::{Initializable, StructuredPolynomialData};
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
-use crate::poly::multilinear_polynomial::MultilinearPolynomial;
+use crate::poly::identity_poly::IdentityPolynomial;
+use crate::poly::multilinear_polynomial::{MultilinearPolynomial, PolynomialEvaluation};
 use crate::poly::opening_proof::VerifierOpeningAccumulator;
 use crate::utils::transcript::Transcript;
 
+use super::constraints::PC_START_ADDRESS;
 use super::key::UniformSpartanKey;
 use super::spartan::{SpartanError, UniformSpartanProof};
 
 use crate::field::JoltField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::log2;
+use common::constants::INSTRUCTION_ALIGNMENT;
 use common::rv_trace::{CircuitFlags, NUM_CIRCUIT_FLAGS};
 use std::fmt::Debug;
 use std::marker::PhantomData;
@@ -95,6 +98,10 @@ pub struct R1CSStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     pub chunks_y: Vec<T>,
     pub circuit_flags: [T; NUM_CIRCUIT_FLAGS],
     pub aux: AuxVariableStuff<T>,
+    /// The index of each step in the trace, i.e. the value of the cycle counter. Not
+    /// committed to, as the verifier evaluates it itself; see
+    /// `ConstraintInput::evaluate_public`.
+    pub step: T,
 }
 
 impl<T: CanonicalSerialize + CanonicalDeserialize + Default> Initializable<T, usize>
@@ -106,6 +113,7 @@ impl<T: CanonicalSerialize + CanonicalDeserialize + Default> Initializable<T, us
             chunks_y: std::iter::repeat_with(|| T::default()).take(*C).collect(),
             circuit_flags: std::array::from_fn(|_| T::default()),
             aux: AuxVariableStuff::initialize(C),
+            step: T::default(),
         }
     }
 }
@@ -206,6 +214,7 @@ impl<F: JoltField> R1CSPolynomials<F> {
                 .unwrap(),
             // Actual aux variable polynomials will be computed afterwards
             aux: AuxVariableStuff::initialize(&C),
+            step: MultilinearPolynomial::from((0..trace.len() as u32).collect::<Vec<_>>()),
         }
     }
 }
@@ -280,6 +289,22 @@ pub trait ConstraintInput: Clone + Copy + Debug + PartialEq + Sync + Send + 'sta
         &self,
         jolt_stuff: &'a mut JoltStuff<T>,
     ) -> &'a mut T;
+
+    /// The evaluation at `r` of this input's polynomial, if it is a fixed polynomial
+    /// that the verifier evaluates itself rather than a committed one (e.g. the index of
+    /// each step). Such inputs are not opened by the PCS.
+    fn evaluate_public<F: JoltField>(&self, _r: &[F]) -> Option<F> {
+        None
+    }
+
+    /// Describes the step `step` of the witness (e.g. its opcode and registers), for
+    /// reports of the constraints it violates; see `constraints::check_satisfiability`.
+    fn describe_step<const C: usize, F: JoltField>(
+        _flattened_polynomials: &[&MultilinearPolynomial<F>],
+        _step: usize,
+    ) -> String {
+        String::new()
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -302,6 +327,9 @@ pub enum JoltR1CSInputs {
     RD_Write,
     RAM_Write,
 
+    /// The index of the step in the trace
+    Step,
+
     ChunksQuery(usize),
     LookupOutput,
     ChunksX(usize),
@@ -369,6 +397,7 @@ impl ConstraintInput for JoltR1CSInputs {
             JoltR1CSInputs::RAM_Read => &jolt.read_write_memory.v_read_ram,
             JoltR1CSInputs::RD_Write => &jolt.read_write_memory.v_write_rd,
             JoltR1CSInputs::RAM_Write => &jolt.read_write_memory.v_write_ram,
+            JoltR1CSInputs::Step => &jolt.r1cs.step,
             JoltR1CSInputs::ChunksQuery(i) => &jolt.instruction_lookups.dim[*i],
             JoltR1CSInputs::LookupOutput => &jolt.instruction_lookups.lookup_outputs,
             JoltR1CSInputs::ChunksX(i) => &jolt.r1cs.chunks_x[*i],
@@ -413,6 +442,43 @@ impl ConstraintInput for JoltR1CSInputs {
             _ => panic!("get_ref_mut should only be invoked when computing aux polynomials"),
         }
     }
+
+    fn evaluate_public<F: JoltField>(&self, r: &[F]) -> Option<F> {
+        match self {
+            JoltR1CSInputs::Step => Some(IdentityPolynomial::new(r.len()).evaluate(r)),
+            _ => None,
+        }
+    }
+
+    fn describe_step<const C: usize, F: JoltField>(
+        flattened_polynomials: &[&MultilinearPolynomial<F>],
+        step: usize,
+    ) -> String {
+        let value = |input: Self| flattened_polynomials[input.to_index::<C>()].get_coeff_i128(step);
+        let opcode: &'static str = RV32I::iter()
+            .find(|instruction| value(Self::InstructionFlags(*instruction)) == 1)
+            .map_or("no lookup", Into::into);
+        // Bytecode addresses are compressed, and offset by the no-op prepended to the bytecode
+        let elf_address = value(Self::Bytecode_ELFAddress);
+        let pc = if elf_address == 0 {
+            "padding".to_string()
+        } else {
+            format!(
+                "pc 0x{:x}",
+                INSTRUCTION_ALIGNMENT as i128 * (elf_address - 1) + PC_START_ADDRESS as i128
+            )
+        };
+        format!(
+            "{opcode} at {pc}: rs1 = x{} ({}), rs2 = x{} ({}), rd = x{} ({}), imm = {}",
+            value(Self::Bytecode_RS1),
+            value(Self::RS1_Read),
+            value(Self::Bytecode_RS2),
+            value(Self::RS2_Read),
+            value(Self::Bytecode_RD),
+            value(Self::RD_Write),
+            value(Self::Bytecode_Imm),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,9 @@
 //! Defines the Linear Combination (LC) object and associated operations.
 //! A LinearCombination is a vector of Terms, where each Term is a pair of a Variable and a coefficient.
 
-#[cfg(test)]
 use super::inputs::ConstraintInput;
 use crate::{field::JoltField, poly::multilinear_polynomial::MultilinearPolynomial};
 use std::fmt::Debug;
-#[cfg(test)]
 use std::fmt::Write as _;
 use std::hash::Hash;
 
@@ -19,14 +17,14 @@ pub enum Variable {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Term(pub Variable, pub i64);
 impl Term {
-    #[cfg(test)]
+    /// Writes the term without its sign, which `LC::pretty_fmt` writes.
     fn pretty_fmt<const C: usize, I: ConstraintInput>(&self, f: &mut String) -> std::fmt::Result {
         match self.0 {
             Variable::Input(var_index) | Variable::Auxiliary(var_index) => match self.1.abs() {
                 1 => write!(f, "{:?}", I::from_index::<C>(var_index)),
-                _ => write!(f, "{}⋅{:?}", self.1, I::from_index::<C>(var_index)),
+                _ => write!(f, "{}⋅{:?}", self.1.abs(), I::from_index::<C>(var_index)),
             },
-            Variable::Constant => write!(f, "{}", self.1),
+            Variable::Constant => write!(f, "{}", self.1.abs()),
         }
     }
 }
@@ -101,7 +99,6 @@ impl LC {
             .sum()
     }
 
-    #[cfg(test)]
     pub fn pretty_fmt<const C: usize, I: ConstraintInput>(
         &self,
         f: &mut String,
@@ -122,6 +119,8 @@ impl LC {
                     } else {
                         write!(f, " + ")?;
                     }
+                } else if term.1 < 0 {
+                    write!(f, "-")?;
                 }
                 term.pretty_fmt::<C, I>(f)?;
             }
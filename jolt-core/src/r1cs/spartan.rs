@@ -66,6 +66,22 @@ pub enum SpartanError {
     /// returned when an invalid PCS proof is provided
     #[error("InvalidPCSProof")]
     InvalidPCSProof,
+
+    /// returned if the claimed evaluation of an input the verifier evaluates itself
+    /// (see `ConstraintInput::evaluate_public`) is wrong
+    #[error("InvalidPublicInputEval")]
+    InvalidPublicInputEval,
+}
+
+/// The indices (in `ConstraintInput::flatten`) of the inputs that are committed to, i.e.
+/// all but those the verifier evaluates itself (at `r`, or any other point).
+fn committed_inputs<const C: usize, F: JoltField, I: ConstraintInput>(r: &[F]) -> Vec<usize> {
+    I::flatten::<C>()
+        .iter()
+        .enumerate()
+        .filter(|(_, var)| var.evaluate_public(r).is_none())
+        .map(|(i, _)| i)
+        .collect()
 }
 
 /// A succinct proof of knowledge of a witness to a relaxed R1CS instance
@@ -95,6 +111,29 @@ where
     F: JoltField,
     ProofTranscript: Transcript,
 {
+    /// Reassembles a proof decoded from another encoding (see `sol_types`).
+    pub(crate) fn new(
+        outer_sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+        outer_sumcheck_claims: (F, F, F),
+        inner_sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+        shift_sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+        shift_sumcheck_claim: F,
+        claimed_witness_evals: Vec<F>,
+        shift_sumcheck_witness_evals: Vec<F>,
+    ) -> Self {
+        Self {
+            _inputs: PhantomData,
+            outer_sumcheck_proof,
+            outer_sumcheck_claims,
+            inner_sumcheck_proof,
+            shift_sumcheck_proof,
+            shift_sumcheck_claim,
+            claimed_witness_evals,
+            shift_sumcheck_witness_evals,
+            _marker: PhantomData,
+        }
+    }
+
     #[tracing::instrument(skip_all, name = "Spartan::setup")]
     pub fn setup(
         constraint_builder: &CombinedUniformBuilder<C, F, I>,
@@ -293,15 +332,22 @@ where
 
         drop_in_background_thread(shift_sumcheck_polys);
 
+        // Inputs the verifier evaluates itself are not opened
+        let committed = committed_inputs::<C, F, I>(rx_step);
+        let committed_polys: Vec<_> = committed.iter().map(|&i| flattened_polys[i]).collect();
+
         // Inner sumcheck evaluations: evaluate z on rx_step
         let (claimed_witness_evals, chis) =
             MultilinearPolynomial::batch_evaluate(&flattened_polys, rx_step);
 
         opening_accumulator.append(
-            &flattened_polys,
+            &committed_polys,
             DensePolynomial::new(chis),
             rx_step.to_vec(),
-            &claimed_witness_evals,
+            &committed
+                .iter()
+                .map(|&i| claimed_witness_evals[i])
+                .collect::<Vec<_>>(),
             transcript,
         );
 
@@ -310,10 +356,13 @@ where
             MultilinearPolynomial::batch_evaluate(&flattened_polys, &shift_sumcheck_r);
 
         opening_accumulator.append(
-            &flattened_polys,
+            &committed_polys,
             DensePolynomial::new(chis2),
             shift_sumcheck_r.to_vec(),
-            &shift_sumcheck_witness_evals,
+            &committed
+                .iter()
+                .map(|&i| shift_sumcheck_witness_evals[i])
+                .collect::<Vec<_>>(),
             transcript,
         );
 
@@ -449,22 +498,47 @@ where
             return Err(SpartanError::InvalidInnerSumcheckClaim);
         }
 
-        let flattened_commitments: Vec<_> = I::flatten::<C>()
+        // The evaluations of the inputs the verifier evaluates itself are checked here,
+        // and the rest are opened
+        let inputs = I::flatten::<C>();
+        for (i, var) in inputs.iter().enumerate() {
+            let evals = [
+                (rx_step, &self.claimed_witness_evals[i]),
+                (&shift_sumcheck_r[..], &self.shift_sumcheck_witness_evals[i]),
+            ];
+            for (r, claimed_eval) in evals {
+                if var
+                    .evaluate_public(r)
+                    .is_some_and(|eval| eval != *claimed_eval)
+                {
+                    return Err(SpartanError::InvalidPublicInputEval);
+                }
+            }
+        }
+
+        let committed = committed_inputs::<C, F, I>(rx_step);
+        let committed_commitments: Vec<_> = committed
             .iter()
-            .map(|var| var.get_ref(commitments))
+            .map(|&i| inputs[i].get_ref(commitments))
             .collect();
 
         opening_accumulator.append(
-            &flattened_commitments,
+            &committed_commitments,
             rx_step.to_vec(),
-            &self.claimed_witness_evals.iter().collect::<Vec<_>>(),
+            &committed
+                .iter()
+                .map(|&i| &self.claimed_witness_evals[i])
+                .collect::<Vec<_>>(),
             transcript,
         );
 
         opening_accumulator.append(
-            &flattened_commitments,
+            &committed_commitments,
             shift_sumcheck_r.to_vec(),
-            &self.shift_sumcheck_witness_evals.iter().collect::<Vec<_>>(),
+            &committed
+                .iter()
+                .map(|&i| &self.shift_sumcheck_witness_evals[i])
+                .collect::<Vec<_>>(),
             transcript,
         );
 
@@ -1,5 +1,8 @@
-use super::grand_product_quarks::QuarkGrandProductProof;
+use super::grand_product_quarks::{
+    QuarkGrandProduct, QuarkGrandProductBase, QuarkGrandProductConfig, QuarkGrandProductProof,
+};
 use super::sumcheck::{BatchedCubicSumcheck, SumcheckInstanceProof};
+use super::QuarkHybridLayerDepth;
 use crate::field::JoltField;
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::dense_interleaved_poly::DenseInterleavedPolynomial;
@@ -10,7 +13,7 @@ use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::Transcript;
 use ark_serialize::*;
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use rayon::prelude::*;
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
@@ -175,7 +178,7 @@ where
         claimed_outputs: &[F],
         _opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
+        _setup: Option<&PCS::VerifierSetup>,
     ) -> (F, Vec<F>) {
         // Evaluate the MLE of the output layer at a random point to reduce the outputs to
         // a single claim.
@@ -304,16 +307,158 @@ where
     }
 }
 
+/// The argument `SelectableGrandProduct` proves its grand products with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GrandProductStrategy {
+    /// The layered GKR grand product of [Thaler13](https://eprint.iacr.org/2013/351)
+    /// (`BatchedDenseGrandProduct`), which proves one sumcheck per layer of the product tree
+    /// and commits to nothing. Its proof and verifier time grow quadratically with the
+    /// depth of the tree.
+    #[default]
+    Gkr,
+    /// The hybrid of GKR and the Quarks grand product (`QuarkGrandProduct`), which proves
+    /// the layers of the tree below the given depth with GKR and the rest with a single
+    /// sumcheck, at the cost of committing to (and opening) the layer at that depth.
+    Quark(QuarkHybridLayerDepth),
+}
+
+/// A batched grand product proven with either argument of `GrandProductStrategy`. The
+/// proofs of both verify the same way (a GKR proof is a hybrid proof without a Quarks
+/// part), so the verifier does not need to know the strategy.
+pub enum SelectableGrandProduct<F: JoltField, ProofTranscript: Transcript> {
+    Gkr(BatchedDenseGrandProduct<F>),
+    Quark(QuarkGrandProduct<F, ProofTranscript>),
+}
+
+impl<F, PCS, ProofTranscript> BatchedGrandProduct<F, PCS, ProofTranscript>
+    for SelectableGrandProduct<F, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    // (leaf values, batch size)
+    type Leaves = (Vec<F>, usize);
+    type Config = GrandProductStrategy;
+
+    fn construct_with_config(leaves: Self::Leaves, strategy: Self::Config) -> Self {
+        match strategy {
+            GrandProductStrategy::Gkr => Self::Gkr(
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::construct(leaves),
+            ),
+            GrandProductStrategy::Quark(hybrid_layer_depth) => {
+                let config = QuarkGrandProductConfig { hybrid_layer_depth };
+                Self::Quark(
+                    BatchedGrandProduct::<F, PCS, ProofTranscript>::construct_with_config(
+                        leaves, config,
+                    ),
+                )
+            }
+        }
+    }
+
+    fn num_layers(&self) -> usize {
+        match self {
+            Self::Gkr(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::num_layers(grand_product)
+            }
+            Self::Quark(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::num_layers(grand_product)
+            }
+        }
+    }
+
+    fn claimed_outputs(&self) -> Vec<F> {
+        match self {
+            Self::Gkr(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::claimed_outputs(grand_product)
+            }
+            Self::Quark(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::claimed_outputs(grand_product)
+            }
+        }
+    }
+
+    fn layers(
+        &'_ mut self,
+    ) -> impl Iterator<Item = &'_ mut dyn BatchedGrandProductLayer<F, ProofTranscript>> {
+        match self {
+            Self::Gkr(grand_product) => Either::Left(
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::layers(grand_product),
+            ),
+            Self::Quark(grand_product) => {
+                Either::Right(BatchedGrandProduct::<F, PCS, ProofTranscript>::layers(
+                    grand_product,
+                ))
+            }
+        }
+    }
+
+    fn prove_grand_product(
+        &mut self,
+        opening_accumulator: Option<&mut ProverOpeningAccumulator<F, ProofTranscript>>,
+        transcript: &mut ProofTranscript,
+        setup: Option<&PCS::Setup>,
+    ) -> (BatchedGrandProductProof<PCS, ProofTranscript>, Vec<F>) {
+        match self {
+            Self::Gkr(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::prove_grand_product(
+                    grand_product,
+                    opening_accumulator,
+                    transcript,
+                    setup,
+                )
+            }
+            Self::Quark(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::prove_grand_product(
+                    grand_product,
+                    opening_accumulator,
+                    transcript,
+                    setup,
+                )
+            }
+        }
+    }
+
+    fn verify_grand_product(
+        proof: &BatchedGrandProductProof<PCS, ProofTranscript>,
+        claimed_outputs: &[F],
+        opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
+        transcript: &mut ProofTranscript,
+        _setup: Option<&PCS::VerifierSetup>,
+    ) -> (F, Vec<F>) {
+        QuarkGrandProductBase::verify_quark_grand_product::<Self, PCS>(
+            proof,
+            claimed_outputs,
+            opening_accumulator,
+            transcript,
+        )
+    }
+
+    fn quark_poly(&self) -> Option<&[F]> {
+        match self {
+            Self::Gkr(_) => None,
+            Self::Quark(grand_product) => {
+                BatchedGrandProduct::<F, PCS, ProofTranscript>::quark_poly(grand_product)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils::transcript::{KeccakTranscript, Transcript};
     use crate::{
-        poly::{commitment::zeromorph::Zeromorph, dense_interleaved_poly::bind_left_and_right},
+        poly::{
+            commitment::zeromorph::{Zeromorph, ZeromorphSRS},
+            dense_interleaved_poly::bind_left_and_right,
+        },
         subprotocols::sumcheck::Bindable,
     };
     use ark_bn254::{Bn254, Fr};
     use ark_std::test_rng;
+    use rand_core::SeedableRng;
 
     #[test]
     fn dense_construct() {
@@ -448,4 +593,75 @@ mod tests {
             assert_eq!(r_prover, r_verifier);
         }
     }
+
+    #[test]
+    fn selectable_prove_verify() {
+        type PCS = Zeromorph<Bn254, KeccakTranscript>;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(9_u64);
+        const LAYER_SIZE: usize = 1 << 8;
+        const BATCH_SIZE: usize = 4;
+        let leaves: Vec<Fr> = std::iter::repeat_with(|| Fr::random(&mut rng))
+            .take(LAYER_SIZE * BATCH_SIZE)
+            .collect();
+        let srs = ZeromorphSRS::<Bn254>::setup(&mut rng, 1 << 10);
+        let setup = srs.trim(1 << 10);
+
+        for strategy in [
+            GrandProductStrategy::Gkr,
+            GrandProductStrategy::Quark(QuarkHybridLayerDepth::Default),
+        ] {
+            let mut batched_circuit =
+                <SelectableGrandProduct<Fr, KeccakTranscript> as BatchedGrandProduct<
+                    Fr,
+                    PCS,
+                    KeccakTranscript,
+                >>::construct_with_config((leaves.clone(), BATCH_SIZE), strategy);
+            let claims = <SelectableGrandProduct<Fr, KeccakTranscript> as BatchedGrandProduct<
+                Fr,
+                PCS,
+                KeccakTranscript,
+            >>::claimed_outputs(&batched_circuit);
+
+            let mut prover_transcript = KeccakTranscript::new(b"test_transcript");
+            let mut prover_accumulator = ProverOpeningAccumulator::new();
+            let (proof, r_prover) =
+                <SelectableGrandProduct<Fr, KeccakTranscript> as BatchedGrandProduct<
+                    Fr,
+                    PCS,
+                    KeccakTranscript,
+                >>::prove_grand_product(
+                    &mut batched_circuit,
+                    Some(&mut prover_accumulator),
+                    &mut prover_transcript,
+                    Some(&setup),
+                );
+            // Only the Quarks hybrid commits to (and opens) a polynomial
+            let quark = strategy != GrandProductStrategy::Gkr;
+            assert_eq!(proof.quark_proof.is_some(), quark);
+            let batched_proof = quark.then(|| {
+                prover_accumulator
+                    .clone()
+                    .reduce_and_prove(&setup, &mut prover_transcript)
+            });
+
+            let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
+            verifier_transcript.compare_to(prover_transcript);
+            let mut verifier_accumulator: VerifierOpeningAccumulator<Fr, PCS, KeccakTranscript> =
+                VerifierOpeningAccumulator::new();
+            verifier_accumulator.compare_to(prover_accumulator, &setup);
+            let (_, r_verifier) = SelectableGrandProduct::verify_grand_product(
+                &proof,
+                &claims,
+                Some(&mut verifier_accumulator),
+                &mut verifier_transcript,
+                None,
+            );
+            assert_eq!(r_prover, r_verifier);
+            if let Some(batched_proof) = batched_proof {
+                assert!(verifier_accumulator
+                    .reduce_and_verify(&setup.1, &batched_proof, &mut verifier_transcript)
+                    .is_ok());
+            }
+        }
+    }
 }
@@ -24,11 +24,11 @@ pub struct QuarkGrandProductProof<
     PCS: CommitmentScheme<ProofTranscript>,
     ProofTranscript: Transcript,
 > {
-    sumcheck_proof: SumcheckInstanceProof<PCS::Field, ProofTranscript>,
-    g_commitment: PCS::Commitment,
-    g_r_sumcheck: PCS::Field,
-    g_r_prime: (PCS::Field, PCS::Field),
-    v_r_prime: (PCS::Field, PCS::Field),
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<PCS::Field, ProofTranscript>,
+    pub(crate) g_commitment: PCS::Commitment,
+    pub(crate) g_r_sumcheck: PCS::Field,
+    pub(crate) g_r_prime: (PCS::Field, PCS::Field),
+    pub(crate) v_r_prime: (PCS::Field, PCS::Field),
     pub num_vars: usize,
 }
 
@@ -169,7 +169,7 @@ where
         claimed_outputs: &[F],
         opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
+        _setup: Option<&PCS::VerifierSetup>,
     ) -> (F, Vec<F>) {
         QuarkGrandProductBase::verify_quark_grand_product::<Self, PCS>(
             proof,
@@ -699,7 +699,9 @@ mod quark_grand_product_tests {
                     Some(&setup),
                 )
                 .0;
-        let batched_proof = prover_accumulator.reduce_and_prove(&setup, &mut prover_transcript);
+        let batched_proof = prover_accumulator
+            .clone()
+            .reduce_and_prove(&setup, &mut prover_transcript);
 
         // Note resetting the transcript is important
         let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
@@ -719,7 +721,7 @@ mod quark_grand_product_tests {
             None,
         );
         assert!(verifier_accumulator
-            .reduce_and_verify(&setup, &batched_proof, &mut verifier_transcript)
+            .reduce_and_verify(&setup.1, &batched_proof, &mut verifier_transcript)
             .is_ok());
     }
 
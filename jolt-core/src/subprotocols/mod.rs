@@ -7,7 +7,7 @@ pub mod sparse_grand_product;
 pub mod sumcheck;
 pub mod twist;
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum QuarkHybridLayerDepth {
     #[default]
     Default,
@@ -1068,7 +1068,7 @@ where
         claimed_outputs: &[F],
         opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
+        _setup: Option<&PCS::VerifierSetup>,
     ) -> (F, Vec<F>) {
         QuarkGrandProductBase::verify_quark_grand_product::<Self, PCS>(
             proof,
@@ -1326,7 +1326,7 @@ mod tests {
             &claims,
             Some(&mut verifier_accumulator),
             &mut verifier_transcript,
-            Some(&setup),
+            Some(&setup.1),
         );
 
         assert_eq!(
@@ -2,7 +2,9 @@
 #![allow(clippy::type_complexity)]
 
 use crate::field::JoltField;
+use crate::jolt::vm::progress::check_cancelled;
 use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
 use crate::poly::multilinear_polynomial::{
     BindingOrder, MultilinearPolynomial, PolynomialBinding, PolynomialEvaluation,
 };
@@ -15,6 +17,7 @@ use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::{AppendToTranscript, Transcript};
 use ark_serialize::*;
 use rayon::prelude::*;
+use std::borrow::Borrow;
 use std::marker::PhantomData;
 
 pub trait Bindable<F: JoltField>: Sync {
@@ -47,6 +50,7 @@ where
         let mut cubic_polys: Vec<CompressedUniPoly<F>> = Vec::new();
 
         for _ in 0..num_rounds {
+            check_cancelled();
             #[cfg(test)]
             self.sumcheck_sanity_check(eq_poly, previous_claim);
 
@@ -120,6 +124,7 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
         }
 
         for _round in 0..num_rounds {
+            check_cancelled();
             // Vector storing evaluations of combined polynomials g(x) = P_0(x) * ... P_{num_polys} (x)
             // for points {0, ..., |g(x)|}
             let mut eval_points = vec![F::zero(); combined_degree];
@@ -186,6 +191,126 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
         (SumcheckInstanceProof::new(compressed_polys), r, final_evals)
     }
 
+    /// Same as `prove_arbitrary` (and produces the same proof), but leaves `polys`
+    /// untouched, trading time for memory: binding a polynomial of small scalars
+    /// allocates field elements for half of its coefficients, whereas here the first
+    /// `streaming_rounds` rounds are computed in a pass over the unbound `polys`, which
+    /// evaluates their bound coefficients on the fly. The polynomials are only
+    /// materialized once they have been bound to 1 / 2^`streaming_rounds` of their size.
+    /// Each streamed round costs a pass over every coefficient of `polys`.
+    #[tracing::instrument(skip_all, name = "Sumcheck.prove_streaming")]
+    pub fn prove_arbitrary_streaming<P, Func>(
+        claim: &F,
+        num_rounds: usize,
+        polys: &[P],
+        comb_func: Func,
+        combined_degree: usize,
+        streaming_rounds: usize,
+        transcript: &mut ProofTranscript,
+    ) -> (Self, Vec<F>, Vec<F>)
+    where
+        P: Borrow<MultilinearPolynomial<F>> + Sync,
+        Func: Fn(&[F]) -> F + std::marker::Sync,
+    {
+        let streaming_rounds = streaming_rounds.min(num_rounds);
+        let len = polys[0].borrow().len();
+        let mut previous_claim = *claim;
+        let mut r: Vec<F> = Vec::new();
+        let mut compressed_polys: Vec<CompressedUniPoly<F>> = Vec::new();
+
+        // The coefficient at `index` of `poly` bound to `r`, where `eq_r` holds eq(r, b)
+        // for every assignment b of the bound (high-order) variables
+        let bound_coeff = |poly: &MultilinearPolynomial<F>, eq_r: &[F], index: usize| -> F {
+            let bound_len = len / eq_r.len();
+            eq_r.iter()
+                .enumerate()
+                .map(|(b, eq)| mul_0_optimized(&poly.get_coeff(b * bound_len + index), eq))
+                .sum()
+        };
+
+        for _round in 0..streaming_rounds {
+            check_cancelled();
+            let eq_r = EqPolynomial::evals(&r);
+            let mle_half = len / eq_r.len() / 2;
+
+            let mut eval_points = (0..mle_half)
+                .into_par_iter()
+                .map(|poly_term_i| {
+                    let mut accum = vec![F::zero(); combined_degree];
+                    let evals: Vec<_> = polys
+                        .iter()
+                        .map(|poly| {
+                            let poly = poly.borrow();
+                            let mut evals = vec![bound_coeff(poly, &eq_r, poly_term_i)];
+                            if combined_degree > 1 {
+                                let mut eval = bound_coeff(poly, &eq_r, poly_term_i + mle_half);
+                                let m = eval - evals[0];
+                                for _ in 1..combined_degree {
+                                    eval += m;
+                                    evals.push(eval);
+                                }
+                            }
+                            evals
+                        })
+                        .collect();
+                    for j in 0..combined_degree {
+                        let evals_j: Vec<_> = evals.iter().map(|x| x[j]).collect();
+                        accum[j] += comb_func(&evals_j);
+                    }
+
+                    accum
+                })
+                .reduce(
+                    || vec![F::zero(); combined_degree],
+                    |mut accum, other| {
+                        accum.iter_mut().zip(other).for_each(|(a, b)| *a += b);
+                        accum
+                    },
+                );
+
+            eval_points.insert(1, previous_claim - eval_points[0]);
+            let univariate_poly = UniPoly::from_evals(&eval_points);
+            let compressed_poly = univariate_poly.compress();
+
+            // append the prover's message to the transcript
+            compressed_poly.append_to_transcript(transcript);
+            let r_j = transcript.challenge_scalar();
+            r.push(r_j);
+
+            previous_claim = univariate_poly.evaluate(&r_j);
+            compressed_polys.push(compressed_poly);
+        }
+
+        // Materialize the bound polynomials for the remaining rounds
+        let eq_r = EqPolynomial::evals(&r);
+        let mut bound_polys: Vec<MultilinearPolynomial<F>> = polys
+            .par_iter()
+            .map(|poly| {
+                let poly = poly.borrow();
+                let coeffs: Vec<F> = (0..len / eq_r.len())
+                    .into_par_iter()
+                    .map(|i| bound_coeff(poly, &eq_r, i))
+                    .collect();
+                MultilinearPolynomial::from(coeffs)
+            })
+            .collect();
+        drop(eq_r);
+
+        let (proof, r_rest, final_evals) = Self::prove_arbitrary(
+            &previous_claim,
+            num_rounds - streaming_rounds,
+            &mut bound_polys,
+            comb_func,
+            combined_degree,
+            transcript,
+        );
+        drop_in_background_thread(bound_polys);
+
+        compressed_polys.extend(proof.compressed_polys);
+        r.extend(r_rest);
+        (SumcheckInstanceProof::new(compressed_polys), r, final_evals)
+    }
+
     #[tracing::instrument(skip_all, name = "Spartan2::sumcheck::prove_spartan_cubic")]
     pub fn prove_spartan_cubic(
         num_rounds: usize,
@@ -198,6 +323,7 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
         let mut claim = F::zero();
 
         for round in 0..num_rounds {
+            check_cancelled();
             if round == 0 {
                 az_bz_cz_poly
                     .first_sumcheck_round(eq_poly, transcript, &mut r, &mut polys, &mut claim);
@@ -324,6 +450,7 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
         /*          Round 0 END          */
 
         for _i in 1..num_rounds {
+            check_cancelled();
             let poly = {
                 let (eval_point_0, eval_point_2) =
                     Self::compute_eval_points_spartan_quadratic(poly_A, &poly_B);
@@ -454,3 +581,77 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
         Ok((e, r))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_core::RngCore;
+
+    #[test]
+    fn streaming_matches_prove_arbitrary() {
+        const NUM_VARS: usize = 6;
+        let mut rng = test_rng();
+        let polys: Vec<MultilinearPolynomial<Fr>> = vec![
+            MultilinearPolynomial::from(
+                (0..1 << NUM_VARS)
+                    .map(|_| Fr::random(&mut rng))
+                    .collect::<Vec<_>>(),
+            ),
+            MultilinearPolynomial::from(
+                (0..1 << NUM_VARS)
+                    .map(|_| rng.next_u32() as u8)
+                    .collect::<Vec<_>>(),
+            ),
+            MultilinearPolynomial::from(
+                (0..1 << NUM_VARS)
+                    .map(|_| rng.next_u32())
+                    .collect::<Vec<_>>(),
+            ),
+        ];
+        let comb_func = |vals: &[Fr]| -> Fr { vals[0] * vals[1] * (vals[2] - vals[1]) };
+        let claim: Fr = (0..1 << NUM_VARS)
+            .map(|i| {
+                let vals: Vec<_> = polys.iter().map(|poly| poly.get_coeff(i)).collect();
+                comb_func(&vals)
+            })
+            .sum();
+
+        let mut transcript = KeccakTranscript::new(b"test_transcript");
+        let (expected_proof, expected_r, expected_evals) =
+            SumcheckInstanceProof::<Fr, KeccakTranscript>::prove_arbitrary(
+                &claim,
+                NUM_VARS,
+                &mut polys.clone(),
+                comb_func,
+                3,
+                &mut transcript,
+            );
+
+        for streaming_rounds in [0, 1, 3, NUM_VARS] {
+            let mut transcript = KeccakTranscript::new(b"test_transcript");
+            let (proof, r, evals) =
+                SumcheckInstanceProof::<Fr, KeccakTranscript>::prove_arbitrary_streaming(
+                    &claim,
+                    NUM_VARS,
+                    &polys,
+                    comb_func,
+                    3,
+                    streaming_rounds,
+                    &mut transcript,
+                );
+            let coeffs = |proof: &SumcheckInstanceProof<Fr, KeccakTranscript>| -> Vec<Vec<Fr>> {
+                proof
+                    .compressed_polys
+                    .iter()
+                    .map(|poly| poly.coeffs_except_linear_term.clone())
+                    .collect()
+            };
+            assert_eq!(coeffs(&proof), coeffs(&expected_proof));
+            assert_eq!(r, expected_r);
+            assert_eq!(evals, expected_evals);
+        }
+    }
+}
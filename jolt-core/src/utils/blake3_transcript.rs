@@ -0,0 +1,161 @@
+use crate::field::JoltField;
+use crate::utils::transcript::Transcript;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::CanonicalSerialize;
+use std::borrow::Borrow;
+
+/// A Fiat-Shamir transcript backed by BLAKE3. Considerably faster than
+/// `KeccakTranscript` natively, but not cheap to verify on-chain, so it should
+/// only be used when EVM verification is not a goal.
+#[derive(Clone)]
+pub struct Blake3Transcript {
+    /// 256-bit running state
+    pub state: [u8; 32],
+    /// We append an ordinal to each invocation of the hash
+    n_rounds: u32,
+    #[cfg(test)]
+    /// A complete history of the transcript's `state`; used for testing.
+    state_history: Vec<[u8; 32]>,
+    #[cfg(test)]
+    /// For a proof to be valid, the verifier's `state_history` should always match
+    /// the prover's. In testing, the Jolt verifier may be provided the prover's
+    /// `state_history` so that we can detect any deviations and the backtrace can
+    /// tell us where it happened.
+    expected_state_history: Option<Vec<[u8; 32]>>,
+}
+
+impl Blake3Transcript {
+    /// Gives the hasher object with the running seed and index added
+    fn hasher(&self) -> blake3::Hasher {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.state);
+        hasher.update(&self.n_rounds.to_be_bytes());
+        hasher
+    }
+
+    /// Fills `out` with challenge bytes, using BLAKE3's extendable output.
+    fn challenge_bytes(&mut self, out: &mut [u8]) {
+        let mut output_reader = self.hasher().finalize_xof();
+        output_reader.fill(out);
+        let mut new_state = [0u8; 32];
+        output_reader.fill(&mut new_state);
+        self.update_state(new_state);
+    }
+
+    fn update_state(&mut self, new_state: [u8; 32]) {
+        self.state = new_state;
+        self.n_rounds += 1;
+        #[cfg(test)]
+        {
+            if let Some(expected_state_history) = &self.expected_state_history {
+                assert!(
+                    new_state == expected_state_history[self.n_rounds as usize],
+                    "Fiat-Shamir transcript mismatch"
+                );
+            }
+            self.state_history.push(new_state);
+        }
+    }
+}
+
+impl Transcript for Blake3Transcript {
+    fn new(label: &'static [u8]) -> Self {
+        assert!(label.len() < 33);
+        let out: [u8; 32] = blake3::hash(label).into();
+
+        Self {
+            state: out,
+            n_rounds: 0,
+            #[cfg(test)]
+            state_history: vec![out],
+            #[cfg(test)]
+            expected_state_history: None,
+        }
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"blake3"
+    }
+
+    #[cfg(test)]
+    /// Compare this transcript to `other` and panic if/when they deviate.
+    /// Typically used to compare the verifier's transcript to the prover's.
+    fn compare_to(&mut self, other: Self) {
+        self.expected_state_history = Some(other.state_history);
+    }
+
+    fn append_message(&mut self, msg: &'static [u8]) {
+        assert!(msg.len() < 33);
+        self.append_bytes(msg);
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        // Length-prefix the input so that consecutive appends are unambiguous
+        let mut hasher = self.hasher();
+        hasher.update(&(bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+        self.update_state(hasher.finalize().into());
+    }
+
+    fn append_u64(&mut self, x: u64) {
+        self.append_bytes(&x.to_le_bytes());
+    }
+
+    fn append_scalar<F: JoltField>(&mut self, scalar: &F) {
+        let mut buf = vec![];
+        scalar.serialize_uncompressed(&mut buf).unwrap();
+        self.append_bytes(&buf);
+    }
+
+    fn append_scalars<F: JoltField>(&mut self, scalars: &[impl Borrow<F>]) {
+        self.append_message(b"begin_append_vector");
+        for item in scalars.iter() {
+            self.append_scalar(item.borrow());
+        }
+        self.append_message(b"end_append_vector");
+    }
+
+    fn append_point<G: CurveGroup>(&mut self, point: &G) {
+        // If we add the point at infinity then we hash over a region of zeros
+        if point.is_zero() {
+            self.append_bytes(&[0_u8; 64]);
+            return;
+        }
+
+        let aff = point.into_affine();
+        let mut buf = vec![];
+        aff.x().unwrap().serialize_compressed(&mut buf).unwrap();
+        aff.y().unwrap().serialize_compressed(&mut buf).unwrap();
+        self.append_bytes(&buf);
+    }
+
+    fn append_points<G: CurveGroup>(&mut self, points: &[G]) {
+        self.append_message(b"begin_append_vector");
+        for item in points.iter() {
+            self.append_point(item);
+        }
+        self.append_message(b"end_append_vector");
+    }
+
+    fn challenge_scalar<F: JoltField>(&mut self) -> F {
+        let mut buf = vec![0u8; F::NUM_BYTES];
+        self.challenge_bytes(&mut buf);
+        F::from_bytes(&buf)
+    }
+
+    fn challenge_vector<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        (0..len)
+            .map(|_i| self.challenge_scalar())
+            .collect::<Vec<F>>()
+    }
+
+    // Compute powers of scalar q : (1, q, q^2, ..., q^(len-1))
+    fn challenge_scalar_powers<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        let q: F = self.challenge_scalar();
+        let mut q_powers = vec![F::one(); len];
+        for i in 1..len {
+            q_powers[i] = q_powers[i - 1] * q;
+        }
+        q_powers
+    }
+}
@@ -0,0 +1,180 @@
+//! The byte order of every place where Jolt turns values into bytes that end up in
+//! proofs, transcripts or digests. Each is pinned to a fixed byte order rather than
+//! the host's, so that proofs produced on big-endian hosts verify everywhere else;
+//! `audit` checks that this holds on the current host.
+
+use alloy_primitives::U256;
+use alloy_sol_types::{sol_data, SolType};
+use ark_bn254::Fr;
+use ark_serialize::CanonicalSerialize;
+use binius_field::BinaryField128b;
+
+use crate::field::binius::BiniusField;
+use crate::field::JoltField;
+use crate::jolt::vm::read_write_memory::le_words;
+use crate::utils::blake3_transcript::Blake3Transcript;
+use crate::utils::errors::ByteOrderMismatch;
+use crate::utils::sol_types::into_uint256;
+use crate::utils::transcript::{KeccakTranscript, Transcript};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    /// `value` in this byte order, right-aligned (big-endian) or left-aligned
+    /// (little-endian) in `len` bytes.
+    fn encode(self, value: u64, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        match self {
+            ByteOrder::LittleEndian => bytes[..8].copy_from_slice(&value.to_le_bytes()),
+            ByteOrder::BigEndian => bytes[len - 8..].copy_from_slice(&value.to_be_bytes()),
+        }
+        bytes
+    }
+}
+
+/// A serialization touchpoint and the byte order it is pinned to.
+#[derive(Clone, Copy, Debug)]
+pub struct Touchpoint {
+    pub name: &'static str,
+    pub byte_order: ByteOrder,
+    /// Whether the touchpoint encodes `SAMPLE` in `byte_order` on this host.
+    check: fn() -> bool,
+}
+
+/// Every byte of the sample differs, so a swap of any two bytes is caught.
+const SAMPLE: u64 = 0x0102030405060708;
+
+pub const TOUCHPOINTS: &[Touchpoint] = &[
+    Touchpoint {
+        name: "Keccak transcript u64 absorption",
+        byte_order: ByteOrder::BigEndian,
+        check: || {
+            absorbs::<KeccakTranscript>(
+                |transcript| transcript.append_u64(SAMPLE),
+                &ByteOrder::BigEndian.encode(SAMPLE, 32),
+            )
+        },
+    },
+    Touchpoint {
+        name: "Keccak transcript scalar absorption",
+        byte_order: ByteOrder::BigEndian,
+        check: || {
+            absorbs::<KeccakTranscript>(
+                |transcript| transcript.append_scalar(&Fr::from_u64(SAMPLE)),
+                &ByteOrder::BigEndian.encode(SAMPLE, 32),
+            )
+        },
+    },
+    Touchpoint {
+        name: "Blake3 transcript u64 absorption",
+        byte_order: ByteOrder::LittleEndian,
+        check: || {
+            absorbs::<Blake3Transcript>(
+                |transcript| transcript.append_u64(SAMPLE),
+                &ByteOrder::LittleEndian.encode(SAMPLE, 8),
+            )
+        },
+    },
+    Touchpoint {
+        name: "Blake3 transcript scalar absorption",
+        byte_order: ByteOrder::LittleEndian,
+        check: || {
+            absorbs::<Blake3Transcript>(
+                |transcript| transcript.append_scalar(&Fr::from_u64(SAMPLE)),
+                &ByteOrder::LittleEndian.encode(SAMPLE, 32),
+            )
+        },
+    },
+    Touchpoint {
+        name: "Program I/O words",
+        byte_order: ByteOrder::LittleEndian,
+        check: || {
+            le_words(&SAMPLE.to_le_bytes()).collect::<Vec<_>>()
+                == [SAMPLE as u32, (SAMPLE >> 32) as u32]
+        },
+    },
+    Touchpoint {
+        name: "Field element decoding",
+        byte_order: ByteOrder::LittleEndian,
+        check: || {
+            Fr::from_bytes(&ByteOrder::LittleEndian.encode(SAMPLE, 32)) == Fr::from_u64(SAMPLE)
+        },
+    },
+    Touchpoint {
+        name: "Field element serialization",
+        byte_order: ByteOrder::LittleEndian,
+        check: || serializes_to(&Fr::from_u64(SAMPLE), ByteOrder::LittleEndian, 32),
+    },
+    Touchpoint {
+        name: "Binius field element serialization",
+        byte_order: ByteOrder::LittleEndian,
+        check: || {
+            serializes_to(
+                &BiniusField::<BinaryField128b>::from_u64(SAMPLE),
+                ByteOrder::LittleEndian,
+                16,
+            )
+        },
+    },
+    Touchpoint {
+        name: "ABI-encoded proof words",
+        byte_order: ByteOrder::BigEndian,
+        check: || {
+            let word = into_uint256(Fr::from_u64(SAMPLE));
+            word == U256::from(SAMPLE)
+                && sol_data::Uint::<256>::abi_encode(&word)
+                    == ByteOrder::BigEndian.encode(SAMPLE, 32)
+        },
+    },
+];
+
+/// Whether appending with `append` changes the transcript's state exactly as
+/// appending `bytes` does.
+fn absorbs<ProofTranscript: Transcript>(
+    append: impl FnOnce(&mut ProofTranscript),
+    bytes: &[u8],
+) -> bool {
+    let mut transcript = ProofTranscript::new(b"byte_order");
+    append(&mut transcript);
+    let mut expected = ProofTranscript::new(b"byte_order");
+    expected.append_bytes(bytes);
+    transcript.challenge_scalar::<Fr>() == expected.challenge_scalar::<Fr>()
+}
+
+fn serializes_to(value: &impl CanonicalSerialize, byte_order: ByteOrder, len: usize) -> bool {
+    let mut bytes = vec![];
+    value.serialize_compressed(&mut bytes).unwrap();
+    bytes == byte_order.encode(SAMPLE, len)
+}
+
+/// Checks every entry of `TOUCHPOINTS` on the current host, returning those that do
+/// not encode in their documented byte order.
+pub fn audit() -> Result<(), Vec<ByteOrderMismatch>> {
+    let mismatches: Vec<_> = TOUCHPOINTS
+        .iter()
+        .filter(|touchpoint| !(touchpoint.check)())
+        .map(|touchpoint| ByteOrderMismatch {
+            touchpoint: touchpoint.name,
+            expected: touchpoint.byte_order,
+        })
+        .collect();
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_order_audit() {
+        assert_eq!(audit(), Ok(()));
+    }
+}
@@ -1,5 +1,11 @@
+use ark_serialize::SerializationError;
 use core::fmt::Debug;
 use thiserror::Error;
+use tracer::{DecodeError, GuestFault};
+
+use crate::r1cs::constraints::ConstraintViolation;
+use crate::r1cs::spartan::SpartanError;
+use crate::utils::byte_order::ByteOrder;
 
 #[derive(Error, Debug, Default)]
 pub enum ProofVerifyError {
@@ -18,4 +24,165 @@ pub enum ProofVerifyError {
     KeyLengthError(usize, usize),
     #[error("Invalid key length: {0}, expected power of 2")]
     InvalidKeyLength(usize),
+    #[error("Commitment is not a valid group element")]
+    InvalidCommitment,
+    #[error("Proof contains an invalid group element")]
+    InvalidProofElement,
+    #[error("Incompatible proof: {0}")]
+    Incompatible(String),
+    #[error("Program I/O does not fit the memory layout: {0}")]
+    IoMismatch(String),
+    #[error("Trace of length {0} exceeds the verifier key's max_trace_length of {1}")]
+    TraceTooLong(usize, usize),
+    #[error("Memory of {0} words cannot hold the initial memory image, which ends at word {1}")]
+    MemoryImageTooLarge(usize, usize),
+    #[error("Memory of {0} words cannot hold the advice tape, which ends at word {1}")]
+    AdviceTapeOutOfRange(usize, usize),
+}
+
+/// The guest panicked; see `JoltDevice::panic_message`.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Guest panicked: {message}")]
+pub struct GuestPanic {
+    pub message: String,
+}
+
+/// Why `Program::try_trace` failed.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TraceError {
+    #[error(transparent)]
+    Panic(#[from] GuestPanic),
+    #[error(transparent)]
+    Fault(#[from] GuestFault),
+    #[error(transparent)]
+    Row(#[from] TraceRowError),
+}
+
+/// Why a trace row could not be converted into an instruction lookup.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TraceRowError {
+    #[error("Instruction {0} has no lookup")]
+    NoLookup(String),
+    #[error("Trace row for instruction {opcode} at 0x{address:x} is missing {operand}")]
+    MissingOperand {
+        opcode: String,
+        address: u64,
+        operand: &'static str,
+    },
+}
+
+/// Why Jolt failed to decode, trace, prove or verify a program.
+#[derive(Error, Debug)]
+pub enum JoltError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+    #[error(transparent)]
+    Trace(#[from] TraceError),
+    #[error("Subtable {0} is not in the VM's subtable set")]
+    UnknownSubtable(String),
+    #[error("R1CS {0}")]
+    UnsatisfiedConstraint(Box<ConstraintViolation>),
+    #[error("Program I/O does not fit the memory layout: {0}")]
+    IoMismatch(String),
+    #[error(
+        "PCS setup supports polynomials of {setup_size} coefficients, but {required} are needed"
+    )]
+    PcsSetupTooSmall { setup_size: usize, required: usize },
+    #[error("Execution needs {required} for {bound}, but was preprocessed for {limit}")]
+    BoundExceeded {
+        bound: &'static str,
+        limit: usize,
+        required: usize,
+    },
+    #[error("R1CS proof failed: {0}")]
+    Spartan(#[from] SpartanError),
+    #[error(transparent)]
+    Verify(#[from] ProofVerifyError),
+    #[error("Invalid Jolt config: {0}")]
+    InvalidConfig(String),
+    #[error("Proving was cancelled")]
+    Cancelled,
+    #[error("Prover panicked: {0}")]
+    Panicked(String),
+}
+
+/// Why serialized proofs or verifier keys cannot be read by this version of Jolt.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    #[error("Data does not start with a Jolt proof header")]
+    MissingHeader,
+    #[error("Incompatible version: data has format version {found}, but this version of Jolt reads version {expected}")]
+    IncompatibleVersion { expected: u32, found: u32 },
+    #[error("Incompatible parameters: {0}")]
+    IncompatibleParameters(String),
+}
+
+/// A serialization touchpoint that does not encode in its documented byte order on
+/// this host (see `byte_order::audit`).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{touchpoint} does not encode in {expected:?} byte order on this host")]
+pub struct ByteOrderMismatch {
+    pub touchpoint: &'static str,
+    pub expected: ByteOrder,
+}
+
+/// Why a proof cannot be ABI-encoded as a `sol_types::JoltProofSol`, or decoded from one.
+#[derive(Error, Debug)]
+pub enum AbiEncodingError {
+    #[error("Quark grand product proofs have no ABI encoding")]
+    UnsupportedQuarkProof,
+    #[error("Invalid ABI encoding: {0}")]
+    Abi(#[from] alloy_sol_types::Error),
+    #[error("Word {0} is not a canonical field element")]
+    NonCanonicalWord(String),
+    #[error("Expected {expected} words for {field}, but got {found}")]
+    LengthMismatch {
+        field: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("{0} has an odd number of words, so it does not encode G1 points")]
+    OddPointWords(&'static str),
+    #[error("Trace length {0} does not fit in a usize")]
+    TraceLengthOverflow(String),
+}
+
+#[derive(Error, Debug)]
+pub enum PreprocessingLoadError {
+    #[error("Preprocessing was generated for a different protocol (hash {found}, expected {expected}); regenerate preprocessing")]
+    ProtocolMismatch { expected: String, found: String },
+    #[error("Preprocessing is corrupted (digest {found}, expected {expected}); regenerate preprocessing")]
+    Corrupted { expected: String, found: String },
+    #[error("Failed to deserialize preprocessing: {0}")]
+    Serialization(#[from] SerializationError),
+}
+
+#[derive(Error, Debug)]
+pub enum VerifierServiceError {
+    #[error("Unknown program: {0}")]
+    UnknownProgram(String),
+    #[error("Failed to load preprocessing for {program}: {source}")]
+    Load {
+        program: String,
+        #[source]
+        source: PreprocessingLoadError,
+    },
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize proof: {0}")]
+    Deserialization(#[from] SerializationError),
+    #[error("Proof exceeds the maximum size of {0} bytes")]
+    ProofTooLarge(u64),
+    #[error(transparent)]
+    Format(#[from] FormatError),
+    #[error("Proof's memory layout does not match the preprocessing of {0}")]
+    MemoryLayoutMismatch(String),
+    #[error(transparent)]
+    Verify(#[from] ProofVerifyError),
+    #[error("HyperKZG pairing check failed")]
+    PairingCheckFailed,
+    #[error("Verifier service has shut down")]
+    Shutdown,
 }
@@ -4,11 +4,16 @@ use crate::field::JoltField;
 use ark_std::test_rng;
 use rayon::prelude::*;
 
+pub mod blake3_transcript;
+pub mod byte_order;
 pub mod errors;
 pub mod gaussian_elimination;
 pub mod instruction_utils;
 pub mod math;
 pub mod profiling;
+#[cfg(feature = "json")]
+pub mod proof_json;
+pub mod serialization;
 pub mod sol_types;
 pub mod thread;
 pub mod transcript;
@@ -2,13 +2,30 @@
 use memory_stats::memory_stats;
 use std::{
     collections::HashMap,
-    sync::{LazyLock, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        LazyLock, Mutex,
+    },
 };
 
 static MEMORY_USAGE_MAP: LazyLock<Mutex<HashMap<&'static str, f64>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 static MEMORY_DELTA_MAP: LazyLock<Mutex<HashMap<&'static str, f64>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
+static BINDING_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Records that binding a polynomial allocated a new buffer for its evaluations.
+pub fn count_binding_allocation() {
+    BINDING_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The number of evaluation buffers allocated by polynomial bindings so far in this
+/// process. A polynomial bound in parallel from low to high allocates at most two
+/// (its first bound evaluations and a scratch buffer swapped with them every round),
+/// rather than one per round.
+pub fn binding_allocations() -> usize {
+    BINDING_ALLOCATIONS.load(Ordering::Relaxed)
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn start_memory_tracing_span(label: &'static str) {
@@ -47,6 +64,10 @@ pub fn report_memory_usage() {
             println!("  \"{}\": {:.2} MB", label, delta * 1000.0);
         }
     }
+    println!(
+        "  Polynomial binding allocations: {}",
+        binding_allocations()
+    );
 
     println!("=====================================================");
 }
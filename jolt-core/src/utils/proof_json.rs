@@ -0,0 +1,205 @@
+//! `JoltHyperKZGProof` as JSON, for tooling that does not link arkworks (see
+//! `JoltHyperKZGProof::to_json`). The structure and field names follow the proof's
+//! ABI encoding, `sol_types::JoltProofSol`, but the proof is serialized directly, so
+//! that proofs without an ABI encoding (e.g. with Quark grand products) have one too.
+use alloy_primitives::U256;
+use ark_bn254::{Fq, Fr, G1Affine};
+use ark_ff::{BigInteger, PrimeField};
+use serde_json::{json, Value};
+
+use crate::jolt::vm::archive::to_hex;
+use crate::jolt::vm::rv32i_vm::{JoltHyperKZGProof, ProofTranscript, PCS};
+use crate::lasso::memory_checking::{
+    ExogenousOpenings, MemoryCheckingProof, MultisetHashes, StructuredPolynomialData,
+};
+use crate::poly::commitment::hyperkzg::HyperKZGProof;
+use crate::subprotocols::grand_product::BatchedGrandProductProof;
+use crate::subprotocols::sumcheck::SumcheckInstanceProof;
+use crate::utils::sol_types::into_uint256;
+
+/// A 32-byte word as a `0x`-prefixed, big-endian hex string.
+fn word_to_json(word: U256) -> Value {
+    Value::String(format!("0x{word:064x}"))
+}
+
+fn field_to_json(value: &Fr) -> Value {
+    word_to_json(into_uint256(*value))
+}
+
+fn fields_to_json<'a>(values: impl IntoIterator<Item = &'a Fr>) -> Value {
+    Value::Array(values.into_iter().map(field_to_json).collect())
+}
+
+/// A G1 point as the `[x, y]` pair of its affine coordinates, with `[0, 0]` as the
+/// point at infinity.
+fn point_to_json(point: &G1Affine) -> Value {
+    let coordinate = |coordinate: &Fq| {
+        word_to_json(U256::from_be_slice(&coordinate.into_bigint().to_bytes_be()))
+    };
+    Value::Array(vec![coordinate(&point.x), coordinate(&point.y)])
+}
+
+fn points_to_json<'a>(points: impl IntoIterator<Item = &'a G1Affine>) -> Value {
+    Value::Array(points.into_iter().map(point_to_json).collect())
+}
+
+fn bytes_to_json(bytes: &[u8]) -> Value {
+    Value::String(format!("0x{}", to_hex(bytes)))
+}
+
+fn sumcheck_to_json(proof: &SumcheckInstanceProof<Fr, ProofTranscript>) -> Value {
+    json!({
+        "compressedPolys": proof
+            .compressed_polys
+            .iter()
+            .map(|poly| fields_to_json(&poly.coeffs_except_linear_term))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn grand_product_to_json(proof: &BatchedGrandProductProof<PCS, ProofTranscript>) -> Value {
+    json!({
+        "layers": proof
+            .gkr_layers
+            .iter()
+            .map(|layer| json!({
+                "sumcheck": sumcheck_to_json(&layer.proof),
+                "leftClaim": field_to_json(&layer.left_claim),
+                "rightClaim": field_to_json(&layer.right_claim),
+            }))
+            .collect::<Vec<_>>(),
+        "quarkProof": proof.quark_proof.as_ref().map(|quark| json!({
+            "sumcheck": sumcheck_to_json(&quark.sumcheck_proof),
+            "gCommitment": point_to_json(&quark.g_commitment.0),
+            "gRSumcheck": field_to_json(&quark.g_r_sumcheck),
+            "gRPrime": fields_to_json([&quark.g_r_prime.0, &quark.g_r_prime.1]),
+            "vRPrime": fields_to_json([&quark.v_r_prime.0, &quark.v_r_prime.1]),
+            "numVars": quark.num_vars,
+        })),
+    })
+}
+
+fn multiset_hashes_to_json(hashes: &MultisetHashes<Fr>) -> Value {
+    json!({
+        "readHashes": fields_to_json(&hashes.read_hashes),
+        "writeHashes": fields_to_json(&hashes.write_hashes),
+        "initHashes": fields_to_json(&hashes.init_hashes),
+        "finalHashes": fields_to_json(&hashes.final_hashes),
+    })
+}
+
+/// Read/write openings followed by init/final openings.
+fn openings_to_json(openings: &impl StructuredPolynomialData<Fr>) -> Value {
+    fields_to_json(
+        openings
+            .read_write_values()
+            .into_iter()
+            .chain(openings.init_final_values()),
+    )
+}
+
+fn memory_checking_to_json<Openings, OtherOpenings>(
+    proof: &MemoryCheckingProof<Fr, PCS, Openings, OtherOpenings, ProofTranscript>,
+) -> Value
+where
+    Openings: StructuredPolynomialData<Fr>
+        + Sync
+        + ark_serialize::CanonicalSerialize
+        + ark_serialize::CanonicalDeserialize,
+    OtherOpenings: ExogenousOpenings<Fr> + Sync,
+{
+    json!({
+        "multisetHashes": multiset_hashes_to_json(&proof.multiset_hashes),
+        "readWriteGrandProduct": grand_product_to_json(&proof.read_write_grand_product),
+        "initFinalGrandProduct": grand_product_to_json(&proof.init_final_grand_product),
+        "openings": openings_to_json(&proof.openings),
+        "exogenousOpenings": fields_to_json(proof.exogenous_openings.openings()),
+    })
+}
+
+fn hyperkzg_to_json(proof: &HyperKZGProof<ark_bn254::Bn254>) -> Value {
+    json!({
+        "com": points_to_json(&proof.com),
+        "w": points_to_json(&proof.w),
+        "v_ypos": fields_to_json(&proof.v[0]),
+        "v_yneg": fields_to_json(&proof.v[1]),
+        "v_y": fields_to_json(&proof.v[2]),
+    })
+}
+
+/// Encodes `proof` as JSON with the same structure and field names as
+/// `JoltProofSol`, plus the Quark proof (or `null`) of each grand product. Field
+/// elements are `0x`-prefixed, big-endian 32-byte hex strings and G1 points are
+/// `[x, y]` pairs of their affine coordinates.
+pub fn proof_to_json(proof: &JoltHyperKZGProof) -> Value {
+    let commitments = &proof.commitments;
+    let proof = &proof.proof;
+    let program_io = &proof.program_io;
+    let read_write_memory = &proof.read_write_memory;
+    let timestamp_validity = &read_write_memory.timestamp_validity_proof;
+    let output = &read_write_memory.output_proof;
+    let primary_sumcheck = &proof.instruction_lookups.primary_sumcheck;
+    let r1cs = &proof.r1cs;
+    let opening_proof = &proof.opening_proof;
+    json!({
+        "traceLength": field_to_json(&Fr::from(proof.trace_length as u64)),
+        "inputs": bytes_to_json(&program_io.inputs),
+        "outputs": bytes_to_json(&program_io.outputs),
+        "logs": bytes_to_json(&program_io.logs),
+        "inputStream": bytes_to_json(&program_io.input_stream),
+        "journalDigest": bytes_to_json(&program_io.journal_digest),
+        "journalRedacted": program_io.journal.is_none(),
+        "journal": program_io
+            .journal
+            .iter()
+            .flatten()
+            .map(|entry| bytes_to_json(entry))
+            .collect::<Vec<_>>(),
+        "nondeterminismDigest": bytes_to_json(&program_io.nondeterminism_digest),
+        "panic": program_io.panic,
+        // Every commitment (the memory image's is in the verifier key)
+        "commitments": points_to_json(
+            commitments
+                .read_write_values()
+                .into_iter()
+                .chain(commitments.init_final_values())
+                .chain([&commitments.read_write_memory.v_advice])
+                .map(|commitment| &commitment.0),
+        ),
+        "bytecode": memory_checking_to_json(&proof.bytecode),
+        "readWriteMemory": memory_checking_to_json(&read_write_memory.memory_checking_proof),
+        "timestampValidity": {
+            "multisetHashes": multiset_hashes_to_json(&timestamp_validity.multiset_hashes),
+            "openings": openings_to_json(&timestamp_validity.openings),
+            "exogenousOpenings": fields_to_json(timestamp_validity.exogenous_openings.openings()),
+            "grandProduct": grand_product_to_json(&timestamp_validity.batched_grand_product),
+        },
+        "output": {
+            "sumcheck": sumcheck_to_json(&output.sumcheck_proof),
+            "opening": field_to_json(&output.opening),
+        },
+        "primarySumcheck": {
+            "sumcheck": sumcheck_to_json(&primary_sumcheck.sumcheck_proof),
+            "EPolyOpenings": fields_to_json(&primary_sumcheck.openings.E_poly_openings),
+            "flagOpenings": fields_to_json(&primary_sumcheck.openings.flag_openings),
+            "lookupOutputsOpening": field_to_json(&primary_sumcheck.openings.lookup_outputs_opening),
+        },
+        "instructionLookups": memory_checking_to_json(&proof.instruction_lookups.memory_checking),
+        "spartan": {
+            "outer": sumcheck_to_json(&r1cs.outer_sumcheck_proof),
+            "outerClaimA": field_to_json(&r1cs.outer_sumcheck_claims.0),
+            "outerClaimB": field_to_json(&r1cs.outer_sumcheck_claims.1),
+            "outerClaimC": field_to_json(&r1cs.outer_sumcheck_claims.2),
+            "inner": sumcheck_to_json(&r1cs.inner_sumcheck_proof),
+            "shift": sumcheck_to_json(&r1cs.shift_sumcheck_proof),
+            "shiftClaim": field_to_json(&r1cs.shift_sumcheck_claim),
+            "claimedEvals": fields_to_json(&r1cs.claimed_witness_evals),
+            "shiftWitnessEvals": fields_to_json(&r1cs.shift_sumcheck_witness_evals),
+        },
+        "openingProof": {
+            "sumcheck": sumcheck_to_json(&opening_proof.sumcheck_proof),
+            "sumcheckClaims": fields_to_json(&opening_proof.sumcheck_claims),
+            "jointOpening": hyperkzg_to_json(&opening_proof.joint_opening_proof),
+        },
+    })
+}
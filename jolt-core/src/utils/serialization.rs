@@ -0,0 +1,249 @@
+//! Parallel deserialization of large vectors of group elements (e.g. SRS powers),
+//! and the validation policy applied to deserialized group elements.
+//!
+//! `Vec<A>::deserialize_*` decompresses and validates points one at a time, which
+//! dominates load time for multi-GB setups. `deserialize_points` reads the same
+//! format, but decompresses and validates chunks of points in parallel; it is used
+//! to load SRS powers (see `CommitmentScheme::load_setup`). Proof commitments,
+//! which are generic over the commitment scheme, are instead validated together
+//! with `par_batch_check` once they have all been read (see `JoltStuff`).
+//!
+//! `HashingReader` and `HashingWriter` digest an artifact as it is streamed, so that
+//! artifacts followed by a digest of their contents are checked in a single pass.
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, Compress, SerializationError, Valid, Validate};
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+use std::io::{Read, Write};
+
+/// Number of points decompressed and validated per rayon task.
+const CHUNK_SIZE: usize = 1 << 12;
+
+/// Whether deserialized group elements undergo on-curve and subgroup checks.
+///
+/// Skipping the checks on adversarial input is unsound: a point outside the
+/// prime-order subgroup can leak information or break the soundness of pairing
+/// checks (small-subgroup attacks). Only skip them for data produced by a trusted
+/// source, e.g. a cache written by this process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationPolicy {
+    /// Every group element is checked. Required for untrusted input, e.g. a verifier
+    /// key received from elsewhere. (Proofs are always validated by `Jolt::verify`.)
+    #[default]
+    Full,
+    /// No checks are performed.
+    Trusted,
+}
+
+impl From<ValidationPolicy> for Validate {
+    fn from(policy: ValidationPolicy) -> Self {
+        match policy {
+            ValidationPolicy::Full => Validate::Yes,
+            ValidationPolicy::Trusted => Validate::No,
+        }
+    }
+}
+
+/// Runs `T::batch_check` over chunks of `values` in parallel.
+#[tracing::instrument(skip_all, name = "par_batch_check")]
+pub fn par_batch_check<T: Valid + Sync>(values: &[&T]) -> Result<(), SerializationError> {
+    values
+        .par_chunks(CHUNK_SIZE)
+        .try_for_each(|chunk| T::batch_check(chunk.iter().copied()))
+}
+
+/// Deserializes a `Vec<A>` written by `CanonicalSerialize::serialize_with_mode`,
+/// decompressing points and running (batched) subgroup checks in parallel.
+#[tracing::instrument(skip_all, name = "deserialize_points")]
+pub fn deserialize_points<A, R>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Vec<A>, SerializationError>
+where
+    A: AffineRepr,
+    R: Read,
+{
+    let len = u64::deserialize_with_mode(&mut reader, compress, Validate::No)? as usize;
+    let point_size = A::zero().serialized_size(compress);
+    let num_bytes = len
+        .checked_mul(point_size)
+        .ok_or(SerializationError::InvalidData)?;
+
+    // Avoid trusting `len` for the allocation; a truncated input is caught below
+    let mut bytes = vec![];
+    (&mut reader)
+        .take(num_bytes as u64)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() != num_bytes {
+        return Err(SerializationError::IoError(std::io::Error::from(
+            std::io::ErrorKind::UnexpectedEof,
+        )));
+    }
+
+    let chunks = bytes
+        .par_chunks(point_size * CHUNK_SIZE)
+        .map(|chunk| {
+            let points = chunk
+                .chunks_exact(point_size)
+                .map(|point_bytes| A::deserialize_with_mode(point_bytes, compress, Validate::No))
+                .collect::<Result<Vec<A>, _>>()?;
+            if let Validate::Yes = validate {
+                A::batch_check(points.iter())?;
+            }
+            Ok(points)
+        })
+        .collect::<Result<Vec<Vec<A>>, SerializationError>>()?;
+    Ok(chunks.concat())
+}
+
+/// Passes reads through to `reader`, hashing (with Keccak256) every byte read.
+pub struct HashingReader<R> {
+    reader: R,
+    hasher: Keccak256,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            hasher: Keccak256::new(),
+        }
+    }
+
+    /// Returns the digest of the bytes read so far, and the underlying reader.
+    pub fn finalize(self) -> ([u8; 32], R) {
+        (self.hasher.finalize().into(), self.reader)
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Passes writes through to `writer`, hashing (with Keccak256) every byte written.
+pub struct HashingWriter<W> {
+    writer: W,
+    hasher: Keccak256,
+}
+
+impl<W: Write> HashingWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            hasher: Keccak256::new(),
+        }
+    }
+
+    /// Returns the digest of the bytes written so far, and the underlying writer.
+    pub fn finalize(self) -> ([u8; 32], W) {
+        (self.hasher.finalize().into(), self.writer)
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fq, G1Affine, G1Projective, G2Affine, G2Projective};
+    use ark_ec::CurveGroup;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::{test_rng, One, UniformRand};
+
+    #[test]
+    fn matches_vec_deserialization() {
+        let mut rng = test_rng();
+        let mut g1: Vec<G1Affine> = G1Projective::normalize_batch(
+            &(0..3 * CHUNK_SIZE + 5)
+                .map(|_| G1Projective::rand(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        g1.push(G1Affine::zero());
+        let g2: Vec<G2Affine> = (0..10)
+            .map(|_| G2Projective::rand(&mut rng).into_affine())
+            .collect();
+
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = vec![];
+            g1.serialize_with_mode(&mut bytes, compress).unwrap();
+            g2.serialize_with_mode(&mut bytes, compress).unwrap();
+
+            let mut reader = bytes.as_slice();
+            let g1_read: Vec<G1Affine> =
+                deserialize_points(&mut reader, compress, Validate::Yes).unwrap();
+            let g2_read: Vec<G2Affine> =
+                deserialize_points(&mut reader, compress, Validate::Yes).unwrap();
+            assert_eq!(g1_read, g1);
+            assert_eq!(g2_read, g2);
+            assert!(reader.is_empty());
+        }
+    }
+
+    #[test]
+    fn validation_policy() {
+        let mut rng = test_rng();
+        let mut points: Vec<G1Affine> = (0..100)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect();
+        assert!(par_batch_check(&points.iter().collect::<Vec<_>>()).is_ok());
+
+        points[7] = G1Affine::new_unchecked(Fq::one(), Fq::one());
+        assert!(par_batch_check(&points.iter().collect::<Vec<_>>()).is_err());
+
+        let mut bytes = vec![];
+        points.serialize_uncompressed(&mut bytes).unwrap();
+        for (policy, ok) in [
+            (ValidationPolicy::Full, false),
+            (ValidationPolicy::Trusted, true),
+        ] {
+            let result = Vec::<G1Affine>::deserialize_with_mode(
+                bytes.as_slice(),
+                Compress::No,
+                policy.into(),
+            );
+            assert_eq!(result.is_ok(), ok);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_points() {
+        let mut rng = test_rng();
+        let mut points: Vec<G1Affine> = (0..100)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect();
+        points[42] = G1Affine::new_unchecked(Fq::one(), Fq::one());
+
+        let mut bytes = vec![];
+        points.serialize_uncompressed(&mut bytes).unwrap();
+        assert!(
+            deserialize_points::<G1Affine, _>(bytes.as_slice(), Compress::No, Validate::Yes)
+                .is_err()
+        );
+        assert!(
+            deserialize_points::<G1Affine, _>(bytes.as_slice(), Compress::No, Validate::No).is_ok()
+        );
+
+        // Truncated input
+        assert!(deserialize_points::<G1Affine, _>(
+            &bytes[..bytes.len() - 1],
+            Compress::No,
+            Validate::No
+        )
+        .is_err());
+    }
+}
@@ -1,20 +1,46 @@
-use ark_bn254::Bn254;
+use ark_bn254::{Bn254, Fq, Fr, G1Affine};
 use ark_ff::BigInteger;
 use ark_ff::PrimeField;
+use ark_ff::Zero;
 
 use crate::field::JoltField;
-use crate::poly::commitment::hyperkzg::{HyperKZG, HyperKZGProof, HyperKZGVerifierKey};
+use crate::jolt::compat::{JoltParameters, ProofHeader};
+use crate::jolt::vm::bytecode::BytecodeOpenings;
+use crate::jolt::vm::instruction_lookups::{
+    InstructionLookupOpenings, InstructionLookupsProof, PrimarySumcheck, PrimarySumcheckOpenings,
+};
+use crate::jolt::vm::read_write_memory::{
+    OutputSumcheckProof, ReadWriteMemoryOpenings, ReadWriteMemoryProof,
+};
+use crate::jolt::vm::rv32i_vm::{JoltHyperKZGProof, JoltHyperKZGVerifierKey, M};
+use crate::jolt::vm::timestamp_range_check::{TimestampRangeCheckOpenings, TimestampValidityProof};
+use crate::jolt::vm::{JoltCommitments, JoltProof};
+use crate::lasso::memory_checking::{
+    ExogenousOpenings, Initializable, MemoryCheckingProof, MultisetHashes, NoPreprocessing,
+    StructuredPolynomialData,
+};
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::commitment::hyperkzg::{
+    HyperKZG, HyperKZGCommitment, HyperKZGProof, HyperKZGVerifierKey,
+};
+use crate::poly::opening_proof::ReducedOpeningProof;
+use crate::poly::unipoly::CompressedUniPoly;
 use crate::r1cs::inputs::JoltR1CSInputs;
 use crate::r1cs::spartan::UniformSpartanProof;
 use crate::subprotocols::grand_product::BatchedGrandProductLayerProof;
 use crate::subprotocols::grand_product::BatchedGrandProductProof;
 use crate::subprotocols::sumcheck::SumcheckInstanceProof;
-use crate::utils::transcript::Transcript;
+use crate::utils::errors::AbiEncodingError;
+use crate::utils::transcript::{KeccakTranscript, Transcript};
 use alloy_primitives::U256;
-use alloy_sol_types::sol;
+use alloy_sol_types::{sol, SolType};
 use ark_bn254::FrConfig;
+use ark_ff::BigInt;
 use ark_ff::Fp;
 use ark_ff::MontBackend;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::JoltDevice;
+use std::fmt;
 
 sol!(struct HyperKZGProofSol {
     uint256[] com; // G1 points represented pairwise
@@ -61,6 +87,96 @@ sol!(struct GrandProductProof {
     }
 );
 
+sol!(
+    struct MultisetHashesSol {
+        uint256[] readHashes;
+        uint256[] writeHashes;
+        uint256[] initHashes;
+        uint256[] finalHashes;
+    }
+);
+
+sol!(
+    struct MemoryCheckingProofSol {
+        MultisetHashesSol multisetHashes;
+        GrandProductProof readWriteGrandProduct;
+        GrandProductProof initFinalGrandProduct;
+        uint256[] openings; // Read/write openings followed by init/final openings
+        uint256[] exogenousOpenings;
+    }
+);
+
+sol!(
+    struct TimestampValidityProofSol {
+        MultisetHashesSol multisetHashes;
+        uint256[] openings; // Read/write openings followed by init/final openings
+        uint256[] exogenousOpenings;
+        GrandProductProof grandProduct;
+    }
+);
+
+sol!(
+    struct OutputSumcheckProofSol {
+        SumcheckProof sumcheck;
+        uint256 opening;
+    }
+);
+
+sol!(
+    struct PrimarySumcheckSol {
+        SumcheckProof sumcheck;
+        uint256[] EPolyOpenings;
+        uint256[] flagOpenings;
+        uint256 lookupOutputsOpening;
+    }
+);
+
+sol!(
+    struct JoltSpartanProofSol {
+        SumcheckProof outer;
+        uint256 outerClaimA;
+        uint256 outerClaimB;
+        uint256 outerClaimC;
+        SumcheckProof inner;
+        SumcheckProof shift;
+        uint256 shiftClaim;
+        uint256[] claimedEvals;
+        uint256[] shiftWitnessEvals;
+    }
+);
+
+sol!(
+    struct ReducedOpeningProofSol {
+        SumcheckProof sumcheck;
+        uint256[] sumcheckClaims;
+        HyperKZGProofSol jointOpening;
+    }
+);
+
+sol!(
+    struct JoltProofSol {
+        uint256 traceLength;
+        bytes inputs;
+        bytes outputs;
+        bytes logs;
+        bytes inputStream;
+        bytes32 journalDigest;
+        bool journalRedacted;
+        bytes[] journal;
+        bytes32 nondeterminismDigest;
+        bool panic;
+        uint256[] commitments; // G1 points represented pairwise, followed by v_advice
+        MemoryCheckingProofSol bytecode;
+        MemoryCheckingProofSol readWriteMemory;
+        TimestampValidityProofSol timestampValidity;
+        OutputSumcheckProofSol output;
+        PrimarySumcheckSol primarySumcheck;
+        MemoryCheckingProofSol instructionLookups;
+        JoltSpartanProofSol spartan;
+        ReducedOpeningProofSol openingProof;
+    }
+);
+
 impl Into<HyperKZGProofSol> for &HyperKZGProof<Bn254> {
     fn into(self) -> HyperKZGProofSol {
         let mut com = vec![];
@@ -181,23 +297,541 @@ impl<ProofTranscript: Transcript> Into<SpartanProof>
 }
 
 impl<F: JoltField, ProofTranscript: Transcript> Into<GKRLayer>
-    for BatchedGrandProductLayerProof<F, ProofTranscript>
+    for &BatchedGrandProductLayerProof<F, ProofTranscript>
 {
     fn into(self) -> GKRLayer {
         GKRLayer {
             sumcheck: (&self.proof).into(),
             leftClaim: into_uint256(self.left_claim),
-            rightClaim: into_uint256(self.left_claim),
+            rightClaim: into_uint256(self.right_claim),
+        }
+    }
+}
+
+impl<F: JoltField, ProofTranscript: Transcript> Into<GKRLayer>
+    for BatchedGrandProductLayerProof<F, ProofTranscript>
+{
+    fn into(self) -> GKRLayer {
+        (&self).into()
+    }
+}
+
+impl<ProofTranscript: Transcript>
+    TryFrom<BatchedGrandProductProof<HyperKZG<Bn254, ProofTranscript>, ProofTranscript>>
+    for GrandProductProof
+{
+    type Error = AbiEncodingError;
+
+    fn try_from(
+        proof: BatchedGrandProductProof<HyperKZG<Bn254, ProofTranscript>, ProofTranscript>,
+    ) -> Result<Self, Self::Error> {
+        (&proof).try_into()
+    }
+}
+
+impl<PCS: CommitmentScheme<ProofTranscript>, ProofTranscript: Transcript>
+    TryFrom<&BatchedGrandProductProof<PCS, ProofTranscript>> for GrandProductProof
+{
+    type Error = AbiEncodingError;
+
+    fn try_from(
+        proof: &BatchedGrandProductProof<PCS, ProofTranscript>,
+    ) -> Result<Self, Self::Error> {
+        if proof.quark_proof.is_some() {
+            return Err(AbiEncodingError::UnsupportedQuarkProof);
+        }
+        let layers = proof.gkr_layers.iter().map(|layer| layer.into()).collect();
+        Ok(GrandProductProof { layers })
+    }
+}
+
+impl<F: JoltField> Into<MultisetHashesSol> for &MultisetHashes<F> {
+    fn into(self) -> MultisetHashesSol {
+        let words = |hashes: &[F]| hashes.iter().map(|hash| into_uint256(*hash)).collect();
+        MultisetHashesSol {
+            readHashes: words(&self.read_hashes),
+            writeHashes: words(&self.write_hashes),
+            initHashes: words(&self.init_hashes),
+            finalHashes: words(&self.final_hashes),
+        }
+    }
+}
+
+/// Read/write openings followed by init/final openings.
+fn openings_to_uint256<F: JoltField>(openings: &impl StructuredPolynomialData<F>) -> Vec<U256> {
+    openings
+        .read_write_values()
+        .into_iter()
+        .chain(openings.init_final_values())
+        .map(|opening| into_uint256(*opening))
+        .collect()
+}
+
+impl<F, PCS, Openings, OtherOpenings, ProofTranscript>
+    TryFrom<&MemoryCheckingProof<F, PCS, Openings, OtherOpenings, ProofTranscript>>
+    for MemoryCheckingProofSol
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    Openings: StructuredPolynomialData<F> + Sync + CanonicalSerialize + CanonicalDeserialize,
+    OtherOpenings: ExogenousOpenings<F> + Sync,
+    ProofTranscript: Transcript,
+{
+    type Error = AbiEncodingError;
+
+    fn try_from(
+        proof: &MemoryCheckingProof<F, PCS, Openings, OtherOpenings, ProofTranscript>,
+    ) -> Result<Self, Self::Error> {
+        Ok(MemoryCheckingProofSol {
+            multisetHashes: (&proof.multiset_hashes).into(),
+            readWriteGrandProduct: (&proof.read_write_grand_product).try_into()?,
+            initFinalGrandProduct: (&proof.init_final_grand_product).try_into()?,
+            openings: openings_to_uint256::<F>(&proof.openings),
+            exogenousOpenings: proof
+                .exogenous_openings
+                .openings()
+                .into_iter()
+                .map(|opening| into_uint256(*opening))
+                .collect(),
+        })
+    }
+}
+
+/// Encodes a `JoltHyperKZGProof` into 32-byte words. The memory layout of
+/// `program_io` and the memory image commitment are not included, since the verifier
+/// takes them from its key; openings that the verifier computes itself are omitted.
+impl TryFrom<&JoltHyperKZGProof> for JoltProofSol {
+    type Error = AbiEncodingError;
+
+    fn try_from(proof: &JoltHyperKZGProof) -> Result<Self, Self::Error> {
+        let mut commitments = vec![];
+        for commitment in proof
+            .commitments
+            .read_write_values()
+            .into_iter()
+            .chain(proof.commitments.init_final_values())
+            .chain([&proof.commitments.read_write_memory.v_advice])
+        {
+            commitments.push(U256::from_be_slice(
+                &commitment.0.x.into_bigint().to_bytes_be(),
+            ));
+            commitments.push(U256::from_be_slice(
+                &commitment.0.y.into_bigint().to_bytes_be(),
+            ));
         }
+
+        let proof = &proof.proof;
+        let program_io = &proof.program_io;
+        let timestamp_validity = &proof.read_write_memory.timestamp_validity_proof;
+        let output = &proof.read_write_memory.output_proof;
+        let primary_sumcheck = &proof.instruction_lookups.primary_sumcheck;
+        let r1cs = &proof.r1cs;
+        let opening_proof = &proof.opening_proof;
+        let words = |values: &[Fr]| values.iter().map(|value| into_uint256(*value)).collect();
+
+        Ok(JoltProofSol {
+            traceLength: U256::from(proof.trace_length as u64),
+            inputs: program_io.inputs.clone().into(),
+            outputs: program_io.outputs.clone().into(),
+            logs: program_io.logs.clone().into(),
+            inputStream: program_io.input_stream.clone().into(),
+            journalDigest: program_io.journal_digest.into(),
+            journalRedacted: program_io.journal.is_none(),
+            journal: program_io
+                .journal
+                .iter()
+                .flatten()
+                .map(|entry| entry.clone().into())
+                .collect(),
+            nondeterminismDigest: program_io.nondeterminism_digest.into(),
+            panic: program_io.panic,
+            commitments,
+            bytecode: (&proof.bytecode).try_into()?,
+            readWriteMemory: (&proof.read_write_memory.memory_checking_proof).try_into()?,
+            timestampValidity: TimestampValidityProofSol {
+                multisetHashes: (&timestamp_validity.multiset_hashes).into(),
+                openings: openings_to_uint256::<Fr>(&timestamp_validity.openings),
+                exogenousOpenings: timestamp_validity
+                    .exogenous_openings
+                    .openings()
+                    .into_iter()
+                    .map(|opening| into_uint256(*opening))
+                    .collect(),
+                grandProduct: (&timestamp_validity.batched_grand_product).try_into()?,
+            },
+            output: OutputSumcheckProofSol {
+                sumcheck: (&output.sumcheck_proof).into(),
+                opening: into_uint256(output.opening),
+            },
+            primarySumcheck: PrimarySumcheckSol {
+                sumcheck: (&primary_sumcheck.sumcheck_proof).into(),
+                EPolyOpenings: words(&primary_sumcheck.openings.E_poly_openings),
+                flagOpenings: words(&primary_sumcheck.openings.flag_openings),
+                lookupOutputsOpening: into_uint256(
+                    primary_sumcheck.openings.lookup_outputs_opening,
+                ),
+            },
+            instructionLookups: (&proof.instruction_lookups.memory_checking).try_into()?,
+            spartan: JoltSpartanProofSol {
+                outer: (&r1cs.outer_sumcheck_proof).into(),
+                outerClaimA: into_uint256(r1cs.outer_sumcheck_claims.0),
+                outerClaimB: into_uint256(r1cs.outer_sumcheck_claims.1),
+                outerClaimC: into_uint256(r1cs.outer_sumcheck_claims.2),
+                inner: (&r1cs.inner_sumcheck_proof).into(),
+                shift: (&r1cs.shift_sumcheck_proof).into(),
+                shiftClaim: into_uint256(r1cs.shift_sumcheck_claim),
+                claimedEvals: words(&r1cs.claimed_witness_evals),
+                shiftWitnessEvals: words(&r1cs.shift_sumcheck_witness_evals),
+            },
+            openingProof: ReducedOpeningProofSol {
+                sumcheck: (&opening_proof.sumcheck_proof).into(),
+                sumcheckClaims: words(&opening_proof.sumcheck_claims),
+                jointOpening: (&opening_proof.joint_opening_proof).into(),
+            },
+        })
     }
 }
 
-impl<ProofTranscript: Transcript> Into<GrandProductProof>
-    for BatchedGrandProductProof<HyperKZG<Bn254, ProofTranscript>, ProofTranscript>
+/// ABI-encodes `proof` as a `JoltProofSol`, e.g. for use as calldata.
+pub fn encode_calldata(proof: &JoltHyperKZGProof) -> Result<Vec<u8>, AbiEncodingError> {
+    let proof = JoltProofSol::try_from(proof)?;
+    Ok(JoltProofSol::abi_encode(&proof))
+}
+
+/// The commitment scheme of `JoltHyperKZGProof`.
+type Bn254HyperKZG = HyperKZG<Bn254, KeccakTranscript>;
+
+/// A 32-byte word as an element of `F`, rejecting words that are not reduced.
+fn word_to_field<F: PrimeField<BigInt = BigInt<4>>>(word: &U256) -> Result<F, AbiEncodingError> {
+    F::from_bigint(BigInt(*word.as_limbs()))
+        .ok_or_else(|| AbiEncodingError::NonCanonicalWord(format!("0x{word:064x}")))
+}
+
+fn words_to_fields(words: &[U256]) -> Result<Vec<Fr>, AbiEncodingError> {
+    words.iter().map(word_to_field).collect()
+}
+
+/// Inverse of the pairwise point encoding, with `(0, 0)` as the point at infinity.
+/// Points are not checked to be on the curve; the verifier does that.
+fn words_to_points(words: &[U256], field: &'static str) -> Result<Vec<G1Affine>, AbiEncodingError> {
+    if words.len() % 2 != 0 {
+        return Err(AbiEncodingError::OddPointWords(field));
+    }
+    words
+        .chunks(2)
+        .map(|xy| {
+            let x: Fq = word_to_field(&xy[0])?;
+            let y: Fq = word_to_field(&xy[1])?;
+            if x.is_zero() && y.is_zero() {
+                Ok(G1Affine::identity())
+            } else {
+                Ok(G1Affine::new_unchecked(x, y))
+            }
+        })
+        .collect()
+}
+
+fn check_length(
+    field: &'static str,
+    expected: usize,
+    found: usize,
+) -> Result<(), AbiEncodingError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(AbiEncodingError::LengthMismatch {
+            field,
+            expected,
+            found,
+        })
+    }
+}
+
+/// Inverse of `openings_to_uint256`: assigns `values` to the read/write values of
+/// `data`, followed by its init/final values.
+fn assign_structured<T, Data: StructuredPolynomialData<T>>(
+    data: &mut Data,
+    values: Vec<T>,
+    field: &'static str,
+) -> Result<(), AbiEncodingError> {
+    let expected = data.read_write_values().len() + data.init_final_values().len();
+    check_length(field, expected, values.len())?;
+    let mut values = values.into_iter();
+    for (slot, value) in data.read_write_values_mut().into_iter().zip(&mut values) {
+        *slot = value;
+    }
+    for (slot, value) in data.init_final_values_mut().into_iter().zip(&mut values) {
+        *slot = value;
+    }
+    Ok(())
+}
+
+fn decode_exogenous_openings<Openings: ExogenousOpenings<Fr>>(
+    words: &[U256],
+    field: &'static str,
+) -> Result<Openings, AbiEncodingError> {
+    let mut openings = Openings::default();
+    let values = words_to_fields(words)?;
+    check_length(field, openings.openings().len(), values.len())?;
+    for (slot, value) in openings.openings_mut().into_iter().zip(values) {
+        *slot = value;
+    }
+    Ok(openings)
+}
+
+fn decode_sumcheck(
+    proof: &SumcheckProof,
+) -> Result<SumcheckInstanceProof<Fr, KeccakTranscript>, AbiEncodingError> {
+    let compressed_polys = proof
+        .compressedPolys
+        .iter()
+        .map(|coeffs| {
+            Ok(CompressedUniPoly {
+                coeffs_except_linear_term: words_to_fields(coeffs)?,
+            })
+        })
+        .collect::<Result<_, AbiEncodingError>>()?;
+    Ok(SumcheckInstanceProof::new(compressed_polys))
+}
+
+fn decode_grand_product(
+    proof: &GrandProductProof,
+) -> Result<BatchedGrandProductProof<Bn254HyperKZG, KeccakTranscript>, AbiEncodingError> {
+    let gkr_layers = proof
+        .layers
+        .iter()
+        .map(|layer| {
+            Ok(BatchedGrandProductLayerProof {
+                proof: decode_sumcheck(&layer.sumcheck)?,
+                left_claim: word_to_field(&layer.leftClaim)?,
+                right_claim: word_to_field(&layer.rightClaim)?,
+            })
+        })
+        .collect::<Result<_, AbiEncodingError>>()?;
+    Ok(BatchedGrandProductProof {
+        gkr_layers,
+        quark_proof: None,
+    })
+}
+
+fn decode_multiset_hashes(
+    hashes: &MultisetHashesSol,
+) -> Result<MultisetHashes<Fr>, AbiEncodingError> {
+    Ok(MultisetHashes {
+        read_hashes: words_to_fields(&hashes.readHashes)?,
+        write_hashes: words_to_fields(&hashes.writeHashes)?,
+        init_hashes: words_to_fields(&hashes.initHashes)?,
+        final_hashes: words_to_fields(&hashes.finalHashes)?,
+    })
+}
+
+/// Decodes a memory checking proof whose openings have the shape of `openings`.
+fn decode_memory_checking<Openings, OtherOpenings>(
+    proof: &MemoryCheckingProofSol,
+    mut openings: Openings,
+    field: &'static str,
+) -> Result<
+    MemoryCheckingProof<Fr, Bn254HyperKZG, Openings, OtherOpenings, KeccakTranscript>,
+    AbiEncodingError,
+>
+where
+    Openings: StructuredPolynomialData<Fr> + Sync + CanonicalSerialize + CanonicalDeserialize,
+    OtherOpenings: ExogenousOpenings<Fr> + Sync,
 {
-    fn into(self) -> GrandProductProof {
-        let layers: Vec<GKRLayer> = self.gkr_layers.into_iter().map(|i| i.into()).collect();
-        assert!(self.quark_proof.is_none(), "Quarks are unsupported");
-        GrandProductProof { layers }
+    assign_structured(&mut openings, words_to_fields(&proof.openings)?, field)?;
+    Ok(MemoryCheckingProof {
+        multiset_hashes: decode_multiset_hashes(&proof.multisetHashes)?,
+        read_write_grand_product: decode_grand_product(&proof.readWriteGrandProduct)?,
+        init_final_grand_product: decode_grand_product(&proof.initFinalGrandProduct)?,
+        openings,
+        exogenous_openings: decode_exogenous_openings(&proof.exogenousOpenings, field)?,
+    })
+}
+
+/// Decodes calldata produced by `encode_calldata`. What the encoding omits -- the
+/// shapes of the openings and the memory layout -- is taken from `verifier_key`.
+pub fn decode_calldata(
+    calldata: &[u8],
+    verifier_key: &JoltHyperKZGVerifierKey,
+) -> Result<JoltHyperKZGProof, AbiEncodingError> {
+    let proof = JoltProofSol::abi_decode(calldata, true)?;
+
+    let mut commitments: JoltCommitments<Bn254HyperKZG, KeccakTranscript> =
+        Initializable::initialize(verifier_key);
+    let mut points: Vec<_> = words_to_points(&proof.commitments, "commitments")?
+        .into_iter()
+        .map(HyperKZGCommitment)
+        .collect();
+    let v_advice = points.pop().ok_or(AbiEncodingError::LengthMismatch {
+        field: "commitments",
+        expected: 1,
+        found: 0,
+    })?;
+    assign_structured(&mut commitments, points, "commitments")?;
+    commitments.read_write_memory.v_advice = v_advice;
+
+    let program_io = JoltDevice {
+        inputs: proof.inputs.to_vec(),
+        outputs: proof.outputs.to_vec(),
+        logs: proof.logs.to_vec(),
+        input_stream: proof.inputStream.to_vec(),
+        advice_tape: vec![],
+        journal: (!proof.journalRedacted)
+            .then(|| proof.journal.iter().map(|entry| entry.to_vec()).collect()),
+        journal_digest: proof.journalDigest.0,
+        nondeterminism_digest: proof.nondeterminismDigest.0,
+        panic: proof.panic,
+        memory_layout: verifier_key.memory_layout.clone(),
+    };
+
+    let timestamp_validity = &proof.timestampValidity;
+    let mut timestamp_openings = TimestampRangeCheckOpenings::<Fr>::initialize(&NoPreprocessing);
+    assign_structured(
+        &mut timestamp_openings,
+        words_to_fields(&timestamp_validity.openings)?,
+        "timestampValidity",
+    )?;
+    let read_write_memory = ReadWriteMemoryProof {
+        memory_checking_proof: decode_memory_checking(
+            &proof.readWriteMemory,
+            ReadWriteMemoryOpenings::<Fr>::initialize(&verifier_key.read_write_memory),
+            "readWriteMemory",
+        )?,
+        timestamp_validity_proof: TimestampValidityProof {
+            multiset_hashes: decode_multiset_hashes(&timestamp_validity.multisetHashes)?,
+            openings: timestamp_openings,
+            exogenous_openings: decode_exogenous_openings(
+                &timestamp_validity.exogenousOpenings,
+                "timestampValidity",
+            )?,
+            batched_grand_product: decode_grand_product(&timestamp_validity.grandProduct)?,
+        },
+        output_proof: OutputSumcheckProof::new(
+            decode_sumcheck(&proof.output.sumcheck)?,
+            word_to_field(&proof.output.opening)?,
+        ),
+    };
+
+    let primary_sumcheck = &proof.primarySumcheck;
+    let instruction_lookups = InstructionLookupsProof::new(
+        PrimarySumcheck::new(
+            decode_sumcheck(&primary_sumcheck.sumcheck)?,
+            PrimarySumcheckOpenings {
+                E_poly_openings: words_to_fields(&primary_sumcheck.EPolyOpenings)?,
+                flag_openings: words_to_fields(&primary_sumcheck.flagOpenings)?,
+                lookup_outputs_opening: word_to_field(&primary_sumcheck.lookupOutputsOpening)?,
+            },
+        ),
+        decode_memory_checking(
+            &proof.instructionLookups,
+            InstructionLookupOpenings::<Fr>::initialize(&verifier_key.instruction_lookups),
+            "instructionLookups",
+        )?,
+    );
+
+    let spartan = &proof.spartan;
+    let r1cs = UniformSpartanProof::new(
+        decode_sumcheck(&spartan.outer)?,
+        (
+            word_to_field(&spartan.outerClaimA)?,
+            word_to_field(&spartan.outerClaimB)?,
+            word_to_field(&spartan.outerClaimC)?,
+        ),
+        decode_sumcheck(&spartan.inner)?,
+        decode_sumcheck(&spartan.shift)?,
+        word_to_field(&spartan.shiftClaim)?,
+        words_to_fields(&spartan.claimedEvals)?,
+        words_to_fields(&spartan.shiftWitnessEvals)?,
+    );
+
+    let opening_proof = &proof.openingProof;
+    let joint_opening = &opening_proof.jointOpening;
+    let opening_proof = ReducedOpeningProof {
+        sumcheck_proof: decode_sumcheck(&opening_proof.sumcheck)?,
+        sumcheck_claims: words_to_fields(&opening_proof.sumcheckClaims)?,
+        joint_opening_proof: HyperKZGProof {
+            com: words_to_points(&joint_opening.com, "com")?,
+            w: words_to_points(&joint_opening.w, "w")?,
+            v: vec![
+                words_to_fields(&joint_opening.v_ypos)?,
+                words_to_fields(&joint_opening.v_yneg)?,
+                words_to_fields(&joint_opening.v_y)?,
+            ],
+        },
+    };
+
+    let trace_length = usize::try_from(proof.traceLength)
+        .map_err(|_| AbiEncodingError::TraceLengthOverflow(proof.traceLength.to_string()))?;
+    let proof = JoltProof {
+        trace_length,
+        program_io,
+        bytecode: decode_memory_checking(
+            &proof.bytecode,
+            BytecodeOpenings::<Fr>::initialize(&verifier_key.bytecode),
+            "bytecode",
+        )?,
+        read_write_memory,
+        instruction_lookups,
+        r1cs,
+        opening_proof,
+    };
+    Ok(JoltHyperKZGProof {
+        proof,
+        commitments,
+        header: ProofHeader::new(JoltParameters::from_verifier_key::<C, M, _, _, _>(
+            verifier_key,
+        )),
+    })
+}
+
+/// Size and gas cost of calldata.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CalldataReport {
+    pub zero_bytes: usize,
+    pub nonzero_bytes: usize,
+}
+
+impl CalldataReport {
+    pub fn new(calldata: &[u8]) -> Self {
+        let zero_bytes = calldata.iter().filter(|byte| **byte == 0).count();
+        Self {
+            zero_bytes,
+            nonzero_bytes: calldata.len() - zero_bytes,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.zero_bytes + self.nonzero_bytes
+    }
+
+    /// Calldata gas cost: 4 per zero byte and 16 per non-zero byte (EIP-2028).
+    pub fn gas(&self) -> u64 {
+        4 * self.zero_bytes as u64 + 16 * self.nonzero_bytes as u64
+    }
+}
+
+impl fmt::Display for CalldataReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes of calldata ({} zero, {} non-zero), {} gas",
+            self.size(),
+            self.zero_bytes,
+            self.nonzero_bytes,
+            self.gas()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calldata_report() {
+        let report = CalldataReport::new(&[0, 1, 0, 0, 255]);
+        assert_eq!(report.size(), 5);
+        assert_eq!(report.zero_bytes, 3);
+        assert_eq!(report.gas(), 3 * 4 + 2 * 16);
     }
 }
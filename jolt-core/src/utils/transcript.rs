@@ -97,6 +97,10 @@ impl Transcript for KeccakTranscript {
         }
     }
 
+    fn protocol_name() -> &'static [u8] {
+        b"keccak"
+    }
+
     #[cfg(test)]
     /// Compare this transcript to `other` and panic if/when they deviate.
     /// Typically used to compare the verifier's transcript to the prover's.
@@ -210,6 +214,8 @@ impl Transcript for KeccakTranscript {
 
 pub trait Transcript: Clone + Sync + Send + 'static {
     fn new(label: &'static [u8]) -> Self;
+    /// Identifies the transcript's hash function in serialized proofs.
+    fn protocol_name() -> &'static [u8];
     #[cfg(test)]
     fn compare_to(&mut self, other: Self);
     fn append_message(&mut self, msg: &'static [u8]);
@@ -228,3 +234,84 @@ pub trait Transcript: Clone + Sync + Send + 'static {
 pub trait AppendToTranscript {
     fn append_to_transcript<ProofTranscript: Transcript>(&self, transcript: &mut ProofTranscript);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::blake3_transcript::Blake3Transcript;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{test_rng, UniformRand, Zero};
+
+    fn run_protocol<ProofTranscript: Transcript>(transcript: &mut ProofTranscript) -> Vec<Fr> {
+        let mut rng = test_rng();
+        transcript.append_message(b"test protocol");
+        transcript.append_u64(42);
+        transcript.append_bytes(&[1, 2, 3]);
+        transcript.append_scalar(&Fr::from(7u64));
+        transcript.append_scalars::<Fr>(&[Fr::from(1u64), Fr::from(2u64)]);
+        transcript.append_point(&G1Projective::rand(&mut rng));
+        transcript.append_points(&[G1Projective::zero(), G1Projective::rand(&mut rng)]);
+        let mut challenges = vec![transcript.challenge_scalar()];
+        challenges.extend(transcript.challenge_vector::<Fr>(3));
+        challenges.extend(transcript.challenge_scalar_powers::<Fr>(3));
+        challenges
+    }
+
+    fn transcript_consistency<ProofTranscript: Transcript>() {
+        let mut prover_transcript = ProofTranscript::new(b"test");
+        let mut verifier_transcript = ProofTranscript::new(b"test");
+        let prover_challenges = run_protocol(&mut prover_transcript);
+        verifier_transcript.compare_to(prover_transcript);
+        let verifier_challenges = run_protocol(&mut verifier_transcript);
+        assert_eq!(prover_challenges, verifier_challenges);
+
+        // Challenges within a transcript are distinct
+        assert_ne!(prover_challenges[0], prover_challenges[1]);
+        assert_eq!(prover_challenges[4], Fr::from(1u64));
+
+        // Domain separation
+        let mut other_transcript = ProofTranscript::new(b"other");
+        assert_ne!(run_protocol(&mut other_transcript), prover_challenges);
+
+        // Any change to the transcript changes subsequent challenges
+        let mut transcript = ProofTranscript::new(b"test");
+        transcript.append_u64(0);
+        assert_ne!(run_protocol(&mut transcript), prover_challenges);
+    }
+
+    #[test]
+    fn keccak_transcript_consistency() {
+        transcript_consistency::<KeccakTranscript>();
+    }
+
+    #[test]
+    fn blake3_transcript_consistency() {
+        transcript_consistency::<Blake3Transcript>();
+    }
+
+    /// Pins the byte encodings absorbed by the transcript, independent of the host's
+    /// byte order.
+    #[test]
+    fn byte_order() {
+        let mut expected = KeccakTranscript::new(b"test");
+        let mut transcript = KeccakTranscript::new(b"test");
+        let mut u64_bytes = [0u8; 32];
+        u64_bytes[24..].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        expected.append_bytes(&u64_bytes);
+        transcript.append_u64(0x0102030405060708);
+        assert_eq!(transcript.state, expected.state);
+
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes[30..].copy_from_slice(&[1, 2]);
+        expected.append_bytes(&scalar_bytes);
+        transcript.append_scalar(&Fr::from(0x0102u64));
+        assert_eq!(transcript.state, expected.state);
+    }
+
+    #[test]
+    fn backends_differ() {
+        let mut keccak = KeccakTranscript::new(b"test");
+        let mut blake3 = Blake3Transcript::new(b"test");
+        assert_ne!(run_protocol(&mut keccak), run_protocol(&mut blake3));
+    }
+}
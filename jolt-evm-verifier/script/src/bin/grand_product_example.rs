@@ -41,7 +41,7 @@ fn get_proof_data(batched_circuit: &mut BatchedDenseGrandProduct<Fr>) {
     let claims = claims.iter().map(fr_to_uint256).collect::<Vec<_>>();
 
     let proof_plus_results = SolProductProofAndClaims {
-        encoded_proof: proof.into(),
+        encoded_proof: proof.try_into().unwrap(),
         claims,
         r_prover,
     };
@@ -4,7 +4,10 @@ extern crate proc_macro;
 
 use core::panic;
 
-use common::{attributes::parse_attributes, rv_trace::MemoryLayout};
+use common::{
+    attributes::parse_attributes,
+    rv_trace::{IoCodec, MemoryLayout},
+};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -40,11 +43,18 @@ struct MacroBuilder {
     func: ItemFn,
     std: bool,
     func_args: Vec<(Ident, Box<Type>)>,
+    /// Arguments marked `#[private]`, which are passed to the guest as advice
+    /// rather than as (public) inputs.
+    private_args: Vec<Ident>,
 }
 
 impl MacroBuilder {
-    fn new(attr: AttributeArgs, func: ItemFn) -> Self {
+    fn new(attr: AttributeArgs, mut func: ItemFn) -> Self {
+        let mut private_args = Self::take_private_args(&mut func);
         let func_args = Self::get_func_args(&func);
+        if parse_attributes(&attr).commit_inputs {
+            private_args = func_args.iter().map(|(name, _)| name.clone()).collect();
+        }
         #[cfg(feature = "guest-std")]
         let std = true;
         #[cfg(not(feature = "guest-std"))]
@@ -55,6 +65,7 @@ impl MacroBuilder {
             func,
             std,
             func_args,
+            private_args,
         }
     }
 
@@ -64,6 +75,7 @@ impl MacroBuilder {
         let analyze_fn = self.make_analyze_function();
         let preprocess_fn = self.make_preprocess_func();
         let prove_fn = self.make_prove_func();
+        let output_fn = self.make_output_func();
 
         let main_fn = if let Some(func) = self.get_func_selector() {
             if *self.get_func_name() == func {
@@ -81,6 +93,7 @@ impl MacroBuilder {
             #analyze_fn
             #preprocess_fn
             #prove_fn
+            #output_fn
             #main_fn
         }
         .into()
@@ -107,22 +120,16 @@ impl MacroBuilder {
                 #imports
                 let (program, preprocessing) = #preprocess_fn_name();
                 let program = std::sync::Arc::new(program);
-                let preprocessing = std::sync::Arc::new(preprocessing);
-
-                let program_cp = program.clone();
-                let preprocessing_cp = preprocessing.clone();
+                let verifier_key = preprocessing.verifier_key();
 
                 let prove_closure = move |#inputs| {
                     let program = (*program).clone();
-                    let preprocessing = (*preprocessing).clone();
-                    #prove_fn_name(program, preprocessing, #(#input_names),*)
+                    #prove_fn_name(program, &preprocessing, #(#input_names),*)
                 };
 
 
                 let verify_closure = move |proof: jolt::JoltHyperKZGProof| {
-                    let program = (*program_cp).clone();
-                    let preprocessing = (*preprocessing_cp).clone();
-                    RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments, None).is_ok()
+                    RV32IJoltVM::verify(&verifier_key, proof.proof, proof.commitments, None).is_ok()
                 };
 
                 (prove_closure, verify_closure)
@@ -154,11 +161,7 @@ impl MacroBuilder {
         let fn_name_str = fn_name.to_string();
         let analyze_fn_name = Ident::new(&format!("analyze_{}", fn_name), fn_name.span());
         let inputs = &self.func.sig.inputs;
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                program.set_input(&#name);
-            }
-        });
+        let set_program_args = self.make_set_program_args();
 
         quote! {
              #[cfg(not(target_arch = "wasm32"))]
@@ -181,6 +184,11 @@ impl MacroBuilder {
         let attributes = parse_attributes(&self.attr);
         let max_input_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_input_size);
         let max_output_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_output_size);
+        let max_advice_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_advice_size);
+        let max_bytecode_size = attributes.max_bytecode_size as usize;
+        let max_memory_address = attributes.max_memory_address as usize;
+        let max_trace_length = attributes.max_trace_length as usize;
+        let io_codec = self.make_io_codec();
         let set_mem_size = self.make_set_linker_parameters();
         let guest_name = self.get_guest_name();
         let imports = self.make_imports();
@@ -202,18 +210,19 @@ impl MacroBuilder {
                 #set_std
                 #set_mem_size
                 let (bytecode, memory_init) = program.decode();
-                let memory_layout = MemoryLayout::new(#max_input_size, #max_output_size);
+                let config = jolt::JoltConfig::builder()
+                    .max_input_size(#max_input_size)
+                    .max_output_size(#max_output_size)
+                    .max_advice_size(#max_advice_size)
+                    .io_codec(#io_codec)
+                    .max_bytecode_size(#max_bytecode_size)
+                    .max_memory_address(#max_memory_address)
+                    .max_trace_length(#max_trace_length)
+                    .build()
+                    .expect("invalid jolt::provable attributes");
 
-                // TODO(moodlezoup): Feed in size parameters via macro
                 let preprocessing: JoltPreprocessing<4, jolt::F, jolt::PCS, jolt::ProofTranscript> =
-                    RV32IJoltVM::preprocess(
-                        bytecode,
-                        memory_layout,
-                        memory_init,
-                        1 << 20,
-                        1 << 20,
-                        1 << 24
-                    );
+                    RV32IJoltVM::preprocess_with_config(bytecode, memory_init, &config);
 
                 (program, preprocessing)
             }
@@ -222,21 +231,19 @@ impl MacroBuilder {
 
     fn make_prove_func(&self) -> TokenStream2 {
         let prove_output_ty = self.get_prove_output_type();
+        let codec = self.make_codec();
 
         let handle_return = match &self.func.sig.output {
             ReturnType::Default => quote! {
                 let ret_val = ();
             },
             ReturnType::Type(_, ty) => quote! {
-                let ret_val = jolt::postcard::from_bytes::<#ty>(&output_bytes).unwrap();
+                let ret_val =
+                    <#codec as jolt::codec::Codec>::from_bytes::<#ty>(&output_bytes).unwrap();
             },
         };
 
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                program.set_input(&#name);
-            }
-        });
+        let set_program_args = self.make_set_program_args();
 
         let fn_name = self.get_func_name();
         let inputs = &self.func.sig.inputs;
@@ -247,31 +254,50 @@ impl MacroBuilder {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #prove_fn_name(
                 mut program: jolt::host::Program,
-                preprocessing: jolt::JoltPreprocessing<4, jolt::F, jolt::PCS, jolt::ProofTranscript>,
+                preprocessing: &jolt::JoltPreprocessing<4, jolt::F, jolt::PCS, jolt::ProofTranscript>,
                 #inputs
             ) -> #prove_output_ty {
                 #imports
 
                 #(#set_program_args;)*
 
-                let (io_device, trace) = program.trace();
+                // Fails with `TraceError::Panic` if the guest panicked
+                let (io_device, trace) = program.try_trace()?;
 
                 let output_bytes = io_device.outputs.clone();
 
-                let (jolt_proof, jolt_commitments, _) = RV32IJoltVM::prove(
+                let (jolt_proof, jolt_commitments, _) = RV32IJoltVM::try_prove(
                     io_device,
                     trace,
                     preprocessing,
-                );
+                )?;
 
                 #handle_return
 
-                let proof = jolt::JoltHyperKZGProof {
-                    proof: jolt_proof,
-                    commitments: jolt_commitments,
-                };
+                let proof =
+                    jolt::JoltHyperKZGProof::new(jolt_proof, jolt_commitments, preprocessing);
 
-                (ret_val, proof)
+                Ok((ret_val, proof))
+            }
+        }
+    }
+
+    fn make_output_func(&self) -> TokenStream2 {
+        let return_ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+
+        let fn_name = self.get_func_name();
+        let output_fn_name = Ident::new(&format!("output_{}", fn_name), fn_name.span());
+        quote! {
+            /// Decodes the outcome claimed by `proof`: the value returned by the guest, or
+            /// that it panicked. Only meaningful once the proof is verified.
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
+            pub fn #output_fn_name(
+                proof: &jolt::JoltHyperKZGProof,
+            ) -> Result<jolt::GuestOutcome<#return_ty>, jolt::codec::Error> {
+                jolt::GuestOutcome::from_program_io(&proof.proof.program_io)
             }
         }
     }
@@ -279,12 +305,21 @@ impl MacroBuilder {
     fn make_main_func(&self) -> TokenStream2 {
         let attributes = parse_attributes(&self.attr);
         let memory_layout =
-            MemoryLayout::new(attributes.max_input_size, attributes.max_output_size);
+            MemoryLayout::new(attributes.max_input_size, attributes.max_output_size)
+                .with_max_advice_size(attributes.max_advice_size);
         let input_start = memory_layout.input_start;
+        let inputs_digest = memory_layout.inputs_digest as usize;
+        let log_start = memory_layout.log_start as usize;
+        let stream_digest = memory_layout.stream_digest as usize;
+        let journal_digest = memory_layout.journal_digest as usize;
+        let nondeterminism_digest = memory_layout.nondeterminism_digest as usize;
         let output_start = memory_layout.output_start;
         let max_input_len = attributes.max_input_size as usize;
         let max_output_len = attributes.max_output_size as usize;
         let termination_bit = memory_layout.termination as usize;
+        let advice_start = memory_layout.advice_start as usize;
+        let max_advice_len = memory_layout.max_advice_size as usize;
+        let codec = self.make_codec();
 
         let get_input_slice = quote! {
             let input_ptr = #input_start as *const u8;
@@ -293,19 +328,73 @@ impl MacroBuilder {
             };
         };
 
+        // Set before any advice (including the private inputs) is read
+        let set_nondeterminism_digest_address = if attributes.nondeterminism_digest {
+            quote! {
+                unsafe { jolt::io::set_nondeterminism_digest_address(#nondeterminism_digest) };
+            }
+        } else {
+            quote! {}
+        };
+
+        let max_private_input_len = attributes.max_private_input_size as usize;
+        let get_private_input_slice = if self.private_args.is_empty() && !attributes.commit_inputs {
+            quote! {}
+        } else {
+            quote! {
+                let private_input_slice = unsafe {
+                    static mut PRIVATE_INPUT: [u8; #max_private_input_len] =
+                        [0; #max_private_input_len];
+                    let buffer = &mut *core::ptr::addr_of_mut!(PRIVATE_INPUT);
+                    let len = jolt::io::read_private_input(buffer);
+                    &buffer[..len]
+                };
+            }
+        };
+
+        // The only public input is the digest of the private inputs
+        let check_inputs_digest = if attributes.commit_inputs {
+            quote! {
+                let (committed_digest, input_slice) =
+                    jolt::postcard::take_from_bytes::<[u8; 32]>(input_slice).unwrap();
+                assert!(
+                    jolt::io::keccak256(private_input_slice) == committed_digest,
+                    "private inputs do not match their digest"
+                );
+            }
+        } else {
+            quote! {}
+        };
+
         let args = &self.func_args;
         let args_fetch = args.iter().map(|(name, ty)| {
-            quote! {
-                let (#name, input_slice) =
-                    jolt::postcard::take_from_bytes::<#ty>(input_slice).unwrap();
+            if self.private_args.contains(name) {
+                quote! {
+                    let (#name, private_input_slice) =
+                        <#codec as jolt::codec::Codec>::take_from_bytes::<#ty>(
+                            private_input_slice,
+                        )
+                        .unwrap();
+                }
+            } else {
+                quote! {
+                    let (#name, input_slice) =
+                        <#codec as jolt::codec::Codec>::take_from_bytes::<#ty>(input_slice)
+                            .unwrap();
+                }
             }
         });
 
         // TODO: ensure that input slice hasn't overflown
         let check_input_len = quote! {};
 
+        // The closure's return type must be spelled out for `?` to type-check
         let block = &self.func.block;
-        let block = quote! {let to_return = (|| -> _ { #block })();};
+        let return_ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+        let block = quote! {let to_return = (|| -> #return_ty { #block })();};
 
         let handle_return = match &self.func.sig.output {
             ReturnType::Default => quote! {},
@@ -315,7 +404,8 @@ impl MacroBuilder {
                     core::slice::from_raw_parts_mut(output_ptr, #max_output_len)
                 };
 
-                jolt::postcard::to_slice::<#ty>(&to_return, output_slice).unwrap();
+                <#codec as jolt::codec::Codec>::to_slice::<#ty>(&to_return, output_slice)
+                    .unwrap();
             },
         };
 
@@ -342,11 +432,24 @@ impl MacroBuilder {
             #[no_mangle]
             pub extern "C" fn main() {
                 let mut offset = 0;
+                unsafe {
+                    jolt::io::set_inputs_digest_address(#inputs_digest);
+                    jolt::io::set_log_address(#log_start);
+                    jolt::io::set_stream_digest_address(#stream_digest);
+                    jolt::io::set_journal_digest_address(#journal_digest);
+                    jolt::io::set_advice_tape(#advice_start, #max_advice_len);
+                }
+                #set_nondeterminism_digest_address
                 #get_input_slice
+                #get_private_input_slice
+                #check_inputs_digest
                 #(#args_fetch;)*
                 #check_input_len
                 #block
                 #handle_return
+                jolt::io::finalize_input_stream();
+                jolt::io::finalize_journal();
+                jolt::io::finalize_nondeterminism_digest();
                 unsafe {
                     core::ptr::write_volatile(#termination_bit as *mut u8, 1);
                 }
@@ -376,7 +479,8 @@ impl MacroBuilder {
 
                 #[cfg(feature = "guest")]
                 #[panic_handler]
-                fn panic(_info: &PanicInfo) -> ! {
+                fn panic(info: &PanicInfo) -> ! {
+                    jolt::io::log_panic(info);
                     unsafe {
                         core::ptr::write_volatile(#panic_address as *mut u8, 1);
                     }
@@ -467,6 +571,24 @@ impl MacroBuilder {
             program.set_stack_size(#value);
         });
 
+        if let Some(value) = attributes.heap_size {
+            code.push(quote! {
+                program.set_heap_size(#value);
+            });
+        }
+
+        if attributes.misaligned_accesses {
+            code.push(quote! {
+                program.set_misaligned_accesses(true);
+            });
+        }
+
+        if attributes.compressed_instructions {
+            code.push(quote! {
+                program.set_compressed_instructions(true);
+            });
+        }
+
         let value = attributes.max_input_size;
         code.push(quote! {
             program.set_max_input_size(#value);
@@ -477,11 +599,61 @@ impl MacroBuilder {
             program.set_max_output_size(#value);
         });
 
+        let value = attributes.max_advice_size;
+        code.push(quote! {
+            program.set_max_advice_size(#value);
+        });
+
+        let value = self.make_io_codec();
+        code.push(quote! {
+            program.set_io_codec(#value);
+        });
+
         quote! {
             #(#code;)*
         }
     }
 
+    /// The `jolt::IoCodec` selected by the `codec` attribute.
+    fn make_io_codec(&self) -> TokenStream2 {
+        match parse_attributes(&self.attr).io_codec {
+            IoCodec::Postcard => quote! { jolt::IoCodec::Postcard },
+            IoCodec::Bincode => quote! { jolt::IoCodec::Bincode },
+        }
+    }
+
+    /// The `jolt::codec::Codec` implementation guest inputs and outputs are
+    /// (de)serialized with.
+    fn make_codec(&self) -> TokenStream2 {
+        match parse_attributes(&self.attr).io_codec {
+            IoCodec::Postcard => quote! { jolt::codec::Postcard },
+            IoCodec::Bincode => quote! { jolt::codec::Bincode },
+        }
+    }
+
+    fn make_set_program_args(&self) -> Vec<TokenStream2> {
+        let commit_inputs = parse_attributes(&self.attr).commit_inputs.then(|| {
+            quote! {
+                program.set_input(&program.private_input_digest());
+            }
+        });
+        self.func_args
+            .iter()
+            .map(|(name, _)| {
+                if self.private_args.contains(name) {
+                    quote! {
+                        program.set_private_input(&#name);
+                    }
+                } else {
+                    quote! {
+                        program.set_input(&#name);
+                    }
+                }
+            })
+            .chain(commit_inputs)
+            .collect()
+    }
+
     fn make_set_std(&self) -> TokenStream2 {
         if self.std {
             quote! {
@@ -497,14 +669,32 @@ impl MacroBuilder {
     fn get_prove_output_type(&self) -> TokenStream2 {
         match &self.func.sig.output {
             ReturnType::Default => quote! {
-                ((), jolt::JoltHyperKZGProof)
+                Result<((), jolt::JoltHyperKZGProof), jolt::JoltError>
             },
             ReturnType::Type(_, ty) => quote! {
-                (#ty, jolt::JoltHyperKZGProof)
+                Result<(#ty, jolt::JoltHyperKZGProof), jolt::JoltError>
             },
         }
     }
 
+    /// Removes the `#[private]` attribute from the arguments of `func`, returning
+    /// the names of the arguments that had it.
+    fn take_private_args(func: &mut ItemFn) -> Vec<Ident> {
+        let mut private_args = Vec::new();
+        for arg in func.sig.inputs.iter_mut() {
+            if let syn::FnArg::Typed(PatType { attrs, pat, .. }) = arg {
+                let num_attrs = attrs.len();
+                attrs.retain(|attr| !attr.path.is_ident("private"));
+                if attrs.len() != num_attrs {
+                    if let syn::Pat::Ident(pat_ident) = pat.as_ref() {
+                        private_args.push(pat_ident.ident.clone());
+                    }
+                }
+            }
+        }
+        private_args
+    }
+
     fn get_func_args(func: &ItemFn) -> Vec<(Ident, Box<Type>)> {
         let mut args = Vec::new();
         for arg in &func.sig.inputs {
@@ -539,6 +729,10 @@ impl MacroBuilder {
     }
 
     fn make_wasm_function(&self) -> TokenStream2 {
+        let attributes = parse_attributes(&self.attr);
+        let max_bytecode_size = attributes.max_bytecode_size as usize;
+        let max_memory_address = attributes.max_memory_address as usize;
+        let max_trace_length = attributes.max_trace_length as usize;
         let fn_name = self.get_func_name();
         let verify_wasm_fn_name = Ident::new(&format!("verify_{}", fn_name), fn_name.span());
 
@@ -554,9 +748,9 @@ impl MacroBuilder {
                 let preprocessing = RV32IJoltVM::preprocess(
                     decoded_preprocessing_data.bytecode,
                     decoded_preprocessing_data.memory_init,
-                    1 << 20,
-                    1 << 20,
-                    1 << 24,
+                    #max_bytecode_size,
+                    #max_memory_address,
+                    #max_trace_length,
                 );
 
                 let result = RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments);
@@ -12,6 +12,7 @@ unsafe impl GlobalAlloc for BumpAllocator {
 
 extern "C" {
     static _HEAP_PTR: u8;
+    static _HEAP_END: u8;
 }
 
 static mut ALLOC_NEXT: usize = 0;
@@ -27,6 +28,15 @@ pub unsafe extern "C" fn sys_alloc(size: usize, align: usize) -> *mut u8 {
 
     next = align_up(next, align);
 
+    // Out of memory. The heap is followed by a guard region, so touching it makes the
+    // tracer fail with `FaultCause::OutOfMemory` at this allocation; otherwise the caller
+    // reports the allocation failure.
+    let heap_end = unsafe { (&_HEAP_END) as *const u8 as usize };
+    if size > heap_end.saturating_sub(next) {
+        unsafe { core::ptr::read_volatile(heap_end as *const u8) };
+        return core::ptr::null_mut();
+    }
+
     let ptr = next as *mut u8;
     next += size;
 
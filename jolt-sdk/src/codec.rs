@@ -0,0 +1,84 @@
+//! Serialization formats for the inputs and outputs of provable functions, selected
+//! with the `codec` attribute of `#[jolt::provable]` (postcard by default).
+
+use core::fmt;
+
+#[cfg(feature = "host")]
+use common::rv_trace::IoCodec;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Configuration of the bincode codec. Must match the one used by
+/// `jolt::host::Program` to serialize inputs.
+const BINCODE_CONFIG: bincode::config::Configuration = bincode::config::standard();
+
+/// A serialization format for guest I/O.
+pub trait Codec {
+    /// Deserializes a `T` from the start of `bytes`, returning it along with the
+    /// remaining bytes.
+    fn take_from_bytes<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<(T, &'a [u8]), Error>;
+
+    /// Serializes `value` into `buffer`, returning the number of bytes written.
+    fn to_slice<T: Serialize>(value: &T, buffer: &mut [u8]) -> Result<usize, Error>;
+
+    fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+        Self::take_from_bytes(bytes).map(|(value, _)| value)
+    }
+}
+
+pub struct Postcard;
+
+impl Codec for Postcard {
+    fn take_from_bytes<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<(T, &'a [u8]), Error> {
+        postcard::take_from_bytes(bytes).map_err(Error::Postcard)
+    }
+
+    fn to_slice<T: Serialize>(value: &T, buffer: &mut [u8]) -> Result<usize, Error> {
+        postcard::to_slice(value, buffer)
+            .map(|written| written.len())
+            .map_err(Error::Postcard)
+    }
+}
+
+pub struct Bincode;
+
+impl Codec for Bincode {
+    fn take_from_bytes<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<(T, &'a [u8]), Error> {
+        let (value, len) = bincode::serde::borrow_decode_from_slice(bytes, BINCODE_CONFIG)
+            .map_err(Error::BincodeDecode)?;
+        Ok((value, &bytes[len..]))
+    }
+
+    fn to_slice<T: Serialize>(value: &T, buffer: &mut [u8]) -> Result<usize, Error> {
+        bincode::serde::encode_into_slice(value, buffer, BINCODE_CONFIG)
+            .map_err(Error::BincodeEncode)
+    }
+}
+
+/// Deserializes a `T` from `bytes` with the given codec.
+#[cfg(feature = "host")]
+pub fn from_bytes<T: DeserializeOwned>(codec: IoCodec, bytes: &[u8]) -> Result<T, Error> {
+    match codec {
+        IoCodec::Postcard => Postcard::from_bytes(bytes),
+        IoCodec::Bincode => Bincode::from_bytes(bytes),
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Postcard(postcard::Error),
+    BincodeEncode(bincode::error::EncodeError),
+    BincodeDecode(bincode::error::DecodeError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Postcard(e) => write!(f, "postcard: {}", e),
+            Error::BincodeEncode(e) => write!(f, "bincode: {}", e),
+            Error::BincodeDecode(e) => write!(f, "bincode: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+impl std::error::Error for Error {}
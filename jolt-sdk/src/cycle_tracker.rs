@@ -0,0 +1,78 @@
+//! Guest-side markers for profiling regions of a guest program.
+//!
+//! `start_cycle_tracker!("label")` and `end_cycle_tracker!("label")` each execute a
+//! single instruction that has no effect on the guest but is recognized by the
+//! tracer, so that `ProgramSummary::analyze_cycle_regions` can report the cycles
+//! spent between them. `cycle()` reads the trace index directly.
+
+/// Marks the start of the region `label`.
+#[inline(always)]
+pub fn start(label: &str) {
+    // `add x0, a0, a1`; see `common::constants::CYCLE_TRACKER_START`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    unsafe {
+        core::arch::asm!(
+            "add x0, a0, a1",
+            in("a0") label.as_ptr(),
+            in("a1") label.len(),
+            options(nostack, preserves_flags),
+        );
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    let _ = label;
+}
+
+/// Marks the end of the region `label`.
+#[inline(always)]
+pub fn end(label: &str) {
+    // `sub x0, a0, a1`; see `common::constants::CYCLE_TRACKER_END`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    unsafe {
+        core::arch::asm!(
+            "sub x0, a0, a1",
+            in("a0") label.as_ptr(),
+            in("a1") label.len(),
+            options(nostack, preserves_flags),
+        );
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    let _ = label;
+}
+
+/// The index of the current step in the Jolt trace, read with `rdcycle`. The proof
+/// constrains it to the step's actual position, so guests may branch on it.
+#[inline(always)]
+pub fn cycle() -> u32 {
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let cycle: u32;
+        unsafe {
+            core::arch::asm!(
+                "rdcycle {}",
+                out(reg) cycle,
+                options(nomem, nostack, preserves_flags),
+            );
+        }
+        cycle
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        0
+    }
+}
+
+/// Starts a profiling region; see `jolt::cycle_tracker`.
+#[macro_export]
+macro_rules! start_cycle_tracker {
+    ($label:expr) => {
+        $crate::cycle_tracker::start($label)
+    };
+}
+
+/// Ends a profiling region started by `start_cycle_tracker!` with the same label.
+#[macro_export]
+macro_rules! end_cycle_tracker {
+    ($label:expr) => {
+        $crate::cycle_tracker::end($label)
+    };
+}
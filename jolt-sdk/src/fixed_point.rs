@@ -0,0 +1,55 @@
+//! Q16.16 fixed-point arithmetic, i.e. on `i32`s holding a value times 2^16. Each
+//! function executes a single custom instruction, which Jolt proves with a short virtual
+//! sequence, in place of the shifts, rounding and overflow checks the compiler would
+//! emit for it.
+
+/// `a * b` for Q16.16 values `a` and `b`, rounded to the nearest Q16.16 value (halves
+/// round up). Wraps around on overflow, like `i32::wrapping_mul`. See
+/// `common::constants::FIXED_MUL_INSTRUCTION`.
+#[inline(always)]
+pub fn fixed_mul(a: i32, b: i32) -> i32 {
+    // `.insn r CUSTOM_0, 4, 0, rd, rs1, rs2`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let product: i32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 4, 0, {rd}, {rs1}, {rs2}",
+                rd = out(reg) product,
+                rs1 = in(reg) a,
+                rs2 = in(reg) b,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+        product
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        ((a as i64 * b as i64 + (1 << 15)) >> 16) as i32
+    }
+}
+
+/// `a + b`, clamped to `[i32::MIN, i32::MAX]` (for Q16.16 values as for integers). See
+/// `common::constants::SATURATING_ADD_INSTRUCTION`.
+#[inline(always)]
+pub fn saturating_add(a: i32, b: i32) -> i32 {
+    // `.insn r CUSTOM_0, 5, 0, rd, rs1, rs2`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let sum: i32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 5, 0, {rd}, {rs1}, {rs2}",
+                rd = out(reg) sum,
+                rs1 = in(reg) a,
+                rs2 = in(reg) b,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+        sum
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        a.saturating_add(b)
+    }
+}
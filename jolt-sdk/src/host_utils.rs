@@ -4,15 +4,65 @@ pub use jolt_core::{field::JoltField, poly::commitment::hyperkzg::HyperKZG};
 
 pub use common::{
     constants::MEMORY_OPS_PER_INSTRUCTION,
-    rv_trace::{MemoryLayout, MemoryOp, RV32IM},
+    rv_trace::{IoCodec, MemoryLayout, MemoryOp, RV32IM},
 };
 pub use jolt_core::host;
 pub use jolt_core::jolt::instruction;
 pub use jolt_core::jolt::vm::{
     bytecode::BytecodeRow,
+    config::{JoltConfig, JoltConfigBuilder},
+    progress::{ProverCallback, ProverPhase},
+    prover_config::JoltProverConfig,
+    proving_handle::ProvingHandle,
     rv32i_vm::{
-        JoltHyperKZGProof, ProofTranscript, RV32IJoltProof, RV32IJoltVM, Serializable, PCS, RV32I,
+        JoltHyperKZGProof, JoltHyperKZGVerifierKey, ProofTranscript, RV32IJoltProof, RV32IJoltVM,
+        Serializable, PCS, RV32I,
     },
-    Jolt, JoltCommitments, JoltPreprocessing, JoltProof,
+    size_report::ProofSizeReport,
+    Jolt, JoltCommitments, JoltPreprocessing, JoltProof, JoltVerifierKey,
 };
+pub use jolt_core::msm::{set_msm_backend, GpuPartition, MsmBackend};
+pub use jolt_core::subprotocols::grand_product::GrandProductStrategy;
+pub use jolt_core::subprotocols::QuarkHybridLayerDepth;
+pub use jolt_core::utils::errors::{GuestPanic, JoltError, TraceError};
 pub use tracer;
+pub use tracer::{FaultCause, GuestFault};
+
+use crate::codec;
+use common::rv_trace::JoltDevice;
+use serde::de::DeserializeOwned;
+
+/// What a proof's program I/O claims about the guest's execution: the value returned
+/// by the provable function, or that the guest panicked (in which case it did not
+/// terminate, and its outputs are meaningless).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuestOutcome<T> {
+    Returned(T),
+    Panicked,
+}
+
+impl<T: DeserializeOwned> GuestOutcome<T> {
+    /// Decodes the outcome of a guest execution from its program I/O, with the codec
+    /// given by its memory layout. Fails if the outputs are not a serialized `T`.
+    pub fn from_program_io(program_io: &JoltDevice) -> Result<Self, codec::Error> {
+        if program_io.panic {
+            return Ok(GuestOutcome::Panicked);
+        }
+        codec::from_bytes(program_io.memory_layout.io_codec, &program_io.outputs)
+            .map(GuestOutcome::Returned)
+    }
+}
+
+impl<T> GuestOutcome<T> {
+    pub fn is_panicked(&self) -> bool {
+        matches!(self, GuestOutcome::Panicked)
+    }
+
+    /// The returned value, or `None` if the guest panicked.
+    pub fn returned(self) -> Option<T> {
+        match self {
+            GuestOutcome::Returned(value) => Some(value),
+            GuestOutcome::Panicked => None,
+        }
+    }
+}
@@ -0,0 +1,499 @@
+//! Guest-side access to the program I/O regions.
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+
+/// Size in bytes of the inputs digest.
+pub const INPUTS_DIGEST_SIZE: usize = 32;
+
+static mut INPUTS_DIGEST_ADDRESS: usize = 0;
+
+/// Records the address of the inputs digest region. Called by the `#[jolt::provable]`
+/// entrypoint before the provable function body runs.
+#[doc(hidden)]
+pub unsafe fn set_inputs_digest_address(address: usize) {
+    INPUTS_DIGEST_ADDRESS = address;
+}
+
+/// Returns the Keccak-256 digest of the (serialized) program inputs.
+///
+/// The digest is computed by the tracer and placed in a reserved memory region. The
+/// verifier recomputes it from the public inputs, so the value read here is bound to
+/// the proof's public claim. This lets guests embed the digest in their outputs (or
+/// sign over it) without re-hashing the inputs in software.
+///
+/// Only available inside the guest; panics if called from a native execution.
+pub fn inputs_digest() -> [u8; INPUTS_DIGEST_SIZE] {
+    let address = unsafe { INPUTS_DIGEST_ADDRESS };
+    assert!(
+        address != 0,
+        "inputs_digest is only available inside the guest"
+    );
+
+    let mut digest = [0u8; INPUTS_DIGEST_SIZE];
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = unsafe { core::ptr::read_volatile((address + i) as *const u8) };
+    }
+    digest
+}
+
+static mut ADVICE_TAPE_ADDRESS: usize = 0;
+static mut ADVICE_TAPE_SIZE: usize = 0;
+
+/// Records the location and size of the advice tape. Called by the `#[jolt::provable]`
+/// entrypoint before the provable function body runs.
+#[doc(hidden)]
+pub unsafe fn set_advice_tape(address: usize, size: usize) {
+    ADVICE_TAPE_ADDRESS = address;
+    ADVICE_TAPE_SIZE = size;
+}
+
+/// Returns the advice tape: `max_advice_size` bytes of memory (see `jolt::provable`)
+/// whose initial contents the host sets with `Program::set_advice_tape`, zero-padded.
+///
+/// The tape is not part of the public inputs. The prover commits to its contents, and
+/// memory checking only ensures that the guest reads them consistently, so they are
+/// advice which the guest must check. In exchange, the guest reads them like any other
+/// memory, however large the tape, and may use the tape as scratch space.
+///
+/// Only available inside the guest; panics if the guest has no advice tape.
+///
+/// # Safety
+/// The returned slice aliases the whole tape, so it must not outlive any other slice
+/// returned by this function that is still in use.
+pub unsafe fn advice_tape() -> &'static mut [u8] {
+    let (address, size) = (ADVICE_TAPE_ADDRESS, ADVICE_TAPE_SIZE);
+    assert!(
+        size != 0,
+        "the advice tape is only available inside a guest with a max_advice_size"
+    );
+    core::slice::from_raw_parts_mut(address as *mut u8, size)
+}
+
+/// Reads the next word of advice. See `read_advice`.
+#[inline(always)]
+fn next_advice_word() -> u32 {
+    // `.insn i CUSTOM_0, 0, rd, x0, 0`; see `common::constants::ADVICE_INSTRUCTION`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let word: u32;
+        unsafe {
+            core::arch::asm!(
+                ".insn i 0x0b, 0, {rd}, x0, 0",
+                rd = out(reg) word,
+                options(nomem, nostack, preserves_flags),
+            );
+        }
+        word
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    panic!("advice is only available inside the guest")
+}
+
+/// Reads the next message of advice into `buffer`, returning its length in bytes,
+/// or `None` if it does not fit. The whole message is consumed either way.
+fn read_advice(buffer: &mut [u8]) -> Option<usize> {
+    let len = next_advice_word() as usize;
+    for i in (0..len).step_by(4) {
+        let word = next_advice_word().to_le_bytes();
+        if len <= buffer.len() {
+            let end = (i + 4).min(len);
+            buffer[i..end].copy_from_slice(&word[..end - i]);
+        }
+    }
+    let fits = len <= buffer.len();
+    if fits {
+        account_nondeterminism(&buffer[..len]);
+    }
+    fits.then_some(len)
+}
+
+/// Size in bytes of the nondeterminism digest.
+pub const NONDETERMINISM_DIGEST_SIZE: usize = 32;
+
+static mut NONDETERMINISM_DIGEST_ADDRESS: usize = 0;
+static mut NONDETERMINISM_DIGEST: Option<[u8; NONDETERMINISM_DIGEST_SIZE]> = None;
+
+/// Records the address of the nondeterminism digest region, which enables the
+/// digest. Called by the `#[jolt::provable(nondeterminism_digest)]` entrypoint before
+/// any advice is read.
+#[doc(hidden)]
+pub unsafe fn set_nondeterminism_digest_address(address: usize) {
+    NONDETERMINISM_DIGEST_ADDRESS = address;
+}
+
+/// Extends the nondeterminism digest over `message`, if it is enabled.
+///
+/// Every message of advice (private inputs, hints and input stream chunks) goes
+/// through `read_advice`, so the digest is a hash chain over all of the
+/// host-supplied data the guest read, in the order it read it: `digest =
+/// keccak256(digest || message)`, starting from zero, as for the journal (see
+/// `common::rv_trace::chain_journal_entry`). The guest writes it to the
+/// nondeterminism digest region when it terminates, which makes it part of the
+/// public inputs (`JoltDevice::nondeterminism_digest`): a verifier who knows the
+/// nondeterminism the guest should have used (e.g. a committed seed) recomputes the
+/// chain and compares. Hashing costs cycles proportional to the size of the advice.
+fn account_nondeterminism(message: &[u8]) {
+    use sha3::Digest;
+
+    if unsafe { NONDETERMINISM_DIGEST_ADDRESS } == 0 {
+        return;
+    }
+    let digest = unsafe { &mut *core::ptr::addr_of_mut!(NONDETERMINISM_DIGEST) };
+    let mut hasher = sha3::Keccak256::new();
+    hasher.update(digest.unwrap_or([0; NONDETERMINISM_DIGEST_SIZE]));
+    hasher.update(message);
+    *digest = Some(hasher.finalize().into());
+}
+
+/// Writes the nondeterminism digest to the nondeterminism digest region, if the
+/// guest read any advice. Called by the `#[jolt::provable]` entrypoint when the
+/// provable function returns.
+#[doc(hidden)]
+pub fn finalize_nondeterminism_digest() {
+    let digest = unsafe { (*core::ptr::addr_of_mut!(NONDETERMINISM_DIGEST)).take() };
+    let Some(digest) = digest else {
+        return;
+    };
+    let address = unsafe { NONDETERMINISM_DIGEST_ADDRESS };
+    // Written by whole words, each exactly once (see `JoltDevice::load`)
+    for (i, word) in digest.chunks(4).enumerate() {
+        let word = u32::from_le_bytes(word.try_into().unwrap());
+        unsafe { core::ptr::write_volatile((address + 4 * i) as *mut u32, word) };
+    }
+}
+
+/// Reads the private inputs into `buffer`, returning their length in bytes.
+///
+/// Private inputs are supplied by the prover as advice: unlike the (public) inputs,
+/// they are not part of the program I/O and the verifier never sees them. Nothing
+/// constrains their value, so the guest must treat them as untrusted. Note that
+/// Jolt proofs are not yet zero-knowledge, so a proof may still leak information
+/// about them.
+///
+/// Called by the `#[jolt::provable]` entrypoint when the provable function has
+/// `#[private]` arguments; the private inputs can only be read once.
+#[doc(hidden)]
+pub fn read_private_input(buffer: &mut [u8]) -> usize {
+    read_advice(buffer).expect("private inputs exceed max_private_input_size")
+}
+
+/// Maximum size in bytes of a serialized hint request, and of a serialized hint.
+pub const MAX_HINT_SIZE: usize = 4096;
+
+static mut HINT_BUFFER: [u8; MAX_HINT_SIZE] = [0; MAX_HINT_SIZE];
+
+/// Asks the host's hint provider to answer `request`; see
+/// `common::constants::HINT_REQUEST`.
+#[inline(always)]
+fn request_hint(request: &[u8]) {
+    // `and x0, a0, a1`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    unsafe {
+        core::arch::asm!(
+            "and x0, a0, a1",
+            in("a0") request.as_ptr(),
+            in("a1") request.len(),
+            options(nostack, preserves_flags, readonly),
+        );
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        let _ = request;
+        panic!("hints are only available inside the guest")
+    }
+}
+
+/// Asks the host for a hint answering `request`.
+///
+/// The host answers with the function passed to `Program::set_hint_provider`, which
+/// takes a `R` and returns a `T`; both are serialized with postcard, and must fit in
+/// `MAX_HINT_SIZE` bytes. Like private inputs, hints are advice supplied by the
+/// prover: nothing constrains their value, so the guest must check them (e.g. check
+/// that a hinted square root squares to its input), which is usually much cheaper
+/// than computing them.
+pub fn hint_with<R: serde::Serialize, T: serde::de::DeserializeOwned>(request: &R) -> T {
+    let buffer = unsafe { &mut *core::ptr::addr_of_mut!(HINT_BUFFER) };
+    let request = postcard::to_slice(request, buffer).expect("hint request exceeds MAX_HINT_SIZE");
+    request_hint(request);
+    let len = read_advice(buffer).expect("hint exceeds MAX_HINT_SIZE");
+    postcard::from_bytes(&buffer[..len]).expect("malformed hint")
+}
+
+/// Asks the host for a hint, for hint providers that take no request (`()`). See
+/// `hint_with`.
+pub fn hint<T: serde::de::DeserializeOwned>() -> T {
+    hint_with(&())
+}
+
+/// Reads `buffer.len()` bytes at `offset` of the advice tape into `buffer`, with
+/// loads that the host may service on demand.
+///
+/// Past the contents set with `Program::set_advice_tape`, the tape is supplied by the
+/// function passed to `Program::set_mmio_handler`, which the tracer calls the first
+/// time the guest accesses each word of it. The guest can thus fetch data as it needs
+/// it (e.g. the Merkle branch of a leaf it has just learned it must open) rather than
+/// receiving all of it upfront. The words become part of the tape's contents, which
+/// the prover commits to, but nothing else constrains their value: the guest must
+/// check them, e.g. against a root that is part of its inputs.
+///
+/// Only available inside a guest with a `max_advice_size`; panics if the read does
+/// not lie within the tape.
+pub fn mmio_read(offset: usize, buffer: &mut [u8]) {
+    let address = mmio_address(offset, buffer.len());
+    for (i, byte) in buffer.iter_mut().enumerate() {
+        *byte = unsafe { core::ptr::read_volatile((address + i) as *const u8) };
+    }
+}
+
+/// Reads the (little-endian) word at `offset` of the advice tape, which must be
+/// word-aligned, with a single load. See `mmio_read`.
+pub fn mmio_read_word(offset: usize) -> u32 {
+    assert!(offset % 4 == 0, "misaligned MMIO word read");
+    let address = mmio_address(offset, 4);
+    u32::from_le(unsafe { core::ptr::read_volatile(address as *const u32) })
+}
+
+/// The address of the `len` bytes at `offset` of the advice tape.
+fn mmio_address(offset: usize, len: usize) -> usize {
+    let (address, size) = unsafe { (ADVICE_TAPE_ADDRESS, ADVICE_TAPE_SIZE) };
+    assert!(
+        size != 0,
+        "MMIO reads are only available inside a guest with a max_advice_size"
+    );
+    assert!(
+        offset.checked_add(len).is_some_and(|end| end <= size),
+        "MMIO read outside of the advice tape"
+    );
+    address + offset
+}
+
+/// Size in bytes of the input stream digest.
+pub const STREAM_DIGEST_SIZE: usize = 32;
+
+static mut STREAM_DIGEST_ADDRESS: usize = 0;
+static mut STREAM_HASHER: Option<sha3::Keccak256> = None;
+
+/// Records the address of the input stream digest region. Called by the
+/// `#[jolt::provable]` entrypoint before the provable function body runs.
+#[doc(hidden)]
+pub unsafe fn set_stream_digest_address(address: usize) {
+    STREAM_DIGEST_ADDRESS = address;
+}
+
+/// Asks the host for the next (up to) `len` bytes of the input stream; see
+/// `common::constants::INPUT_STREAM_REQUEST`.
+#[inline(always)]
+fn request_input_stream(len: usize) {
+    // `or x0, a0, x0`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    unsafe {
+        core::arch::asm!(
+            "or x0, a0, x0",
+            in("a0") len,
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        let _ = len;
+        panic!("the input stream is only available inside the guest")
+    }
+}
+
+/// Reads the next (up to) `n` bytes of the input stream, which the host sets with
+/// `Program::set_input_stream`. Returns fewer than `n` bytes once the stream is
+/// exhausted.
+///
+/// Unlike the inputs, the stream is not copied into a reserved memory region, so its
+/// size is not bounded by `max_input_size`: the host supplies each chunk as advice
+/// when it is read. The guest hashes every chunk it reads and writes the digest to
+/// the stream digest region when it terminates; the verifier recomputes the digest
+/// from the bytes read (`JoltDevice::input_stream`), which binds them to the proof.
+/// Hashing costs cycles proportional to the number of bytes read.
+pub fn read_chunk(n: usize) -> Vec<u8> {
+    use sha3::Digest;
+
+    request_input_stream(n);
+    let mut chunk = vec![0u8; n];
+    let len = read_advice(&mut chunk).expect("input stream chunk exceeds requested size");
+    chunk.truncate(len);
+    if len > 0 {
+        let hasher = unsafe { &mut *core::ptr::addr_of_mut!(STREAM_HASHER) };
+        hasher
+            .get_or_insert_with(sha3::Keccak256::new)
+            .update(&chunk);
+    }
+    chunk
+}
+
+/// Writes the digest of the input stream read so far to the stream digest region,
+/// if the guest read any of it. Called by the `#[jolt::provable]` entrypoint when
+/// the provable function returns.
+#[doc(hidden)]
+pub fn finalize_input_stream() {
+    use sha3::Digest;
+
+    let hasher = unsafe { (*core::ptr::addr_of_mut!(STREAM_HASHER)).take() };
+    let Some(hasher) = hasher else {
+        return;
+    };
+    let address = unsafe { STREAM_DIGEST_ADDRESS };
+    let digest: [u8; STREAM_DIGEST_SIZE] = hasher.finalize().into();
+    // Written by whole words, each exactly once (see `JoltDevice::load`)
+    for (i, word) in digest.chunks(4).enumerate() {
+        let word = u32::from_le_bytes(word.try_into().unwrap());
+        unsafe { core::ptr::write_volatile((address + 4 * i) as *mut u32, word) };
+    }
+}
+
+/// Size in bytes of the journal digest.
+pub const JOURNAL_DIGEST_SIZE: usize = 32;
+
+static mut JOURNAL_DIGEST_ADDRESS: usize = 0;
+static mut JOURNAL_DIGEST: Option<[u8; JOURNAL_DIGEST_SIZE]> = None;
+
+/// Records the address of the journal digest region. Called by the
+/// `#[jolt::provable]` entrypoint before the provable function body runs.
+#[doc(hidden)]
+pub unsafe fn set_journal_digest_address(address: usize) {
+    JOURNAL_DIGEST_ADDRESS = address;
+}
+
+/// Tells the tracer to record `entry` in the journal; see
+/// `common::constants::JOURNAL_APPEND`.
+#[inline(always)]
+fn record_journal_entry(entry: &[u8]) {
+    // `or x0, a0, a1`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    unsafe {
+        core::arch::asm!(
+            "or x0, a0, a1",
+            in("a0") entry.as_ptr(),
+            in("a1") entry.len(),
+            options(nostack, preserves_flags, readonly),
+        );
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        let _ = entry;
+        panic!("the journal is only available inside the guest")
+    }
+}
+
+/// Appends `entry` to the journal.
+///
+/// Unlike the outputs, the journal is not bounded by `max_output_size`: the tracer
+/// records each entry in the program I/O (`JoltDevice::journal`), and the guest
+/// extends a hash chain over the entries (`digest = keccak256(digest || entry)`,
+/// starting from zero), which it writes to the journal digest region when it
+/// terminates. The verifier checks the entries against the digest, and the prover
+/// may redact them, in which case the proof attests to the digest alone. Hashing
+/// costs cycles proportional to the size of the entries.
+pub fn journal(entry: &[u8]) {
+    use sha3::Digest;
+
+    record_journal_entry(entry);
+    let digest = unsafe { &mut *core::ptr::addr_of_mut!(JOURNAL_DIGEST) };
+    let mut hasher = sha3::Keccak256::new();
+    hasher.update(digest.unwrap_or([0; JOURNAL_DIGEST_SIZE]));
+    hasher.update(entry);
+    *digest = Some(hasher.finalize().into());
+}
+
+/// Writes the journal digest to the journal digest region, if the guest appended
+/// any entries. Called by the `#[jolt::provable]` entrypoint when the provable
+/// function returns.
+#[doc(hidden)]
+pub fn finalize_journal() {
+    let digest = unsafe { (*core::ptr::addr_of_mut!(JOURNAL_DIGEST)).take() };
+    let Some(digest) = digest else {
+        return;
+    };
+    let address = unsafe { JOURNAL_DIGEST_ADDRESS };
+    // Written by whole words, each exactly once (see `JoltDevice::load`)
+    for (i, word) in digest.chunks(4).enumerate() {
+        let word = u32::from_le_bytes(word.try_into().unwrap());
+        unsafe { core::ptr::write_volatile((address + 4 * i) as *mut u32, word) };
+    }
+}
+
+/// Keccak-256 digest of `bytes`, computed in software. Used by the
+/// `#[jolt::provable(commit_inputs)]` entrypoint to check the private inputs
+/// against their digest.
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    use sha3::{Digest, Keccak256};
+    Keccak256::digest(bytes).into()
+}
+
+/// Size in bytes of the log region.
+pub const MAX_LOG_SIZE: usize = 1024;
+
+static mut LOG_ADDRESS: usize = 0;
+static mut LOG_OFFSET: usize = 0;
+
+/// Records the address of the log region. Called by the `#[jolt::provable]`
+/// entrypoint before the provable function body runs.
+#[doc(hidden)]
+pub unsafe fn set_log_address(address: usize) {
+    LOG_ADDRESS = address;
+    LOG_OFFSET = 0;
+}
+
+/// Appends `bytes` to the guest's log.
+///
+/// Inside the guest, the bytes are written to the log region, which the host reads
+/// back from the `JoltDevice` returned by the tracer (see `JoltDevice::log_lines`).
+/// Like the outputs, the logs are part of the proof's public claim. Bytes past
+/// `MAX_LOG_SIZE` are dropped. In a native execution, they are written to stdout.
+pub fn log(bytes: &[u8]) {
+    let address = unsafe { LOG_ADDRESS };
+    if address == 0 {
+        #[cfg(feature = "host")]
+        {
+            use std::io::Write;
+            let _ = std::io::stdout().write_all(bytes);
+        }
+        return;
+    }
+
+    let offset = unsafe { LOG_OFFSET };
+    let len = bytes.len().min(MAX_LOG_SIZE - offset);
+    for (i, byte) in bytes[..len].iter().enumerate() {
+        unsafe { core::ptr::write_volatile((address + offset + i) as *mut u8, *byte) };
+    }
+    unsafe { LOG_OFFSET = offset + len };
+}
+
+/// `core::fmt::Write` adapter for `log`, used by `jolt::println!`.
+pub struct Log;
+
+impl core::fmt::Write for Log {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        log(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Logs the panic message, for `JoltDevice::panic_message`. Called by the guest's
+/// panic handler.
+#[doc(hidden)]
+pub fn log_panic(info: &core::panic::PanicInfo) {
+    use core::fmt::Write;
+    let _ = writeln!(Log, "{}", info);
+}
+
+/// Formats a line into the guest's log; see `jolt::io::log`.
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::io::log(b"\n")
+    };
+    ($($arg:tt)*) => {{
+        let _ = core::fmt::Write::write_fmt(
+            &mut $crate::io::Log,
+            core::format_args!("{}\n", core::format_args!($($arg)*)),
+        );
+    }};
+}
@@ -10,5 +10,20 @@ pub mod host_utils;
 #[cfg(feature = "host")]
 pub use host_utils::*;
 
+pub mod codec;
+
 pub mod alloc;
 pub use alloc::*;
+
+pub mod cycle_tracker;
+
+pub mod io;
+pub use io::{hint, hint_with, journal, mmio_read, mmio_read_word, read_chunk};
+
+pub mod fixed_point;
+pub use fixed_point::{fixed_mul, saturating_add};
+
+pub mod ml;
+pub use ml::{argmax, relu, DecisionTree};
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64", feature = "host"))]
+pub use ml::{exp, sigmoid, softmax};
@@ -0,0 +1,220 @@
+//! Activation and selection functions common in ML inference, each executed as a single
+//! custom instruction that Jolt proves with one lookup (rather than the branches the
+//! compiler would emit for them), and decision trees, walked with one custom instruction
+//! per level.
+//!
+//! Off the guest target, `exp`, `softmax` and `sigmoid` need the `host` feature, since they fall
+//! back to the integer implementation in `common`, which the prover's lookup tables
+//! share.
+
+/// `max(x, 0)`; see `common::constants::RELU_INSTRUCTION`.
+#[inline(always)]
+pub fn relu(x: i32) -> i32 {
+    // `.insn r CUSTOM_0, 1, 0, rd, rs1, x0`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let y: i32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 1, 0, {rd}, {rs1}, x0",
+                rd = out(reg) y,
+                rs1 = in(reg) x,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+        y
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        x.max(0)
+    }
+}
+
+/// The index of the larger of `a` and `b`, i.e. 1 if `b > a` and 0 otherwise (so ties
+/// select `a`); see `common::constants::ARGMAX_INSTRUCTION`.
+#[inline(always)]
+pub fn argmax(a: i32, b: i32) -> u32 {
+    // `.insn r CUSTOM_0, 2, 0, rd, rs1, rs2`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let index: u32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 2, 0, {rd}, {rs1}, {rs2}",
+                rd = out(reg) index,
+                rs1 = in(reg) a,
+                rs2 = in(reg) b,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+        index
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        (b > a) as u32
+    }
+}
+
+/// `e^x` for a Q8.8 value `x` (i.e. `x / 256`), as an unsigned Q16.16 value rounded to
+/// nearest: within 2^-17 of the exact value, saturating to `u32::MAX` above about 11.09.
+/// See `common::constants::EXP_INSTRUCTION`.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64", feature = "host"))]
+#[inline(always)]
+pub fn exp(x: i16) -> u32 {
+    // `.insn r CUSTOM_0, 6, 0, rd, rs1, x0`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let y: u32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 6, 0, {rd}, {rs1}, x0",
+                rd = out(reg) y,
+                rs1 = in(reg) x as i32,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+        y
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        common::fixed_point::exp(x)
+    }
+}
+
+/// The softmax of the Q8.8 values `logits`, as Q16.16 probabilities (i.e. summing to
+/// about 2^16). Takes one `exp` per logit, of its difference from the largest, so it
+/// cannot overflow. Each probability is within `(N + 3) / 2` units of 2^-16 of the exact
+/// softmax of `logits`: the `exp`s are each off by at most half a unit, and the division
+/// truncates.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64", feature = "host"))]
+pub fn softmax<const N: usize>(logits: [i16; N]) -> [u32; N] {
+    let max = logits.iter().copied().max().unwrap_or(0);
+    // Differences below i16::MIN (-128.0) saturate, and their `exp`s round to 0 anyway
+    let weights = logits.map(|x| exp(x.saturating_sub(max)));
+    // At least 2^16, the weight of the largest logit
+    let total: u64 = weights.iter().map(|&weight| weight as u64).sum();
+    weights.map(|weight| (((weight as u64) << 16) / total) as u32)
+}
+
+/// The logistic function `1 / (1 + e^-x)` for a Q8.8 value `x`, as a Q16.16 value in
+/// `[0, 2^16]` rounded to nearest (within 2^-17 of the exact value), e.g. to turn the
+/// summed scores of a gradient-boosted classifier into a probability. See
+/// `common::constants::SIGMOID_INSTRUCTION`.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64", feature = "host"))]
+#[inline(always)]
+pub fn sigmoid(x: i16) -> u32 {
+    // `.insn r CUSTOM_0, 7, 0, rd, rs1, x0`
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        let y: u32;
+        unsafe {
+            core::arch::asm!(
+                ".insn r 0x0b, 7, 0, {rd}, {rs1}, x0",
+                rd = out(reg) y,
+                rs1 = in(reg) x as i32,
+                options(pure, nomem, nostack, preserves_flags),
+            );
+        }
+        y
+    }
+    #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+    {
+        common::fixed_point::sigmoid(x)
+    }
+}
+
+/// A complete binary decision tree over signed features, laid out as the `TREE_STEP`
+/// instruction reads it (see `common::constants::TREE_STEP_INSTRUCTION`): the nodes are
+/// in level order, so the children of node `i` are nodes `2i + 1` and `2i + 2`, and
+/// each is built with `DecisionTree::split` or `DecisionTree::leaf`.
+#[derive(Clone, Copy, Debug)]
+pub struct DecisionTree<'a> {
+    nodes: &'a [[u32; 2]],
+    depth: u32,
+    num_features: usize,
+}
+
+impl<'a> DecisionTree<'a> {
+    /// The internal node at level-order index `index`, which sends features whose
+    /// `feature`th value is at most `threshold` to its left child, and the others to
+    /// its right child.
+    pub const fn split(index: usize, feature: u16, threshold: i16) -> [u32; 2] {
+        [
+            ((threshold as u16 as u32) << 16) | feature as u32,
+            // The offset in bytes from the node to its left child
+            8 * (index as u32 + 1),
+        ]
+    }
+
+    /// A leaf, whose value is the prediction of the features that reach it.
+    pub const fn leaf(value: i32) -> [u32; 2] {
+        [value as u32, 0]
+    }
+
+    /// A tree of depth `depth` (i.e. whose leaves are the last `2^depth` of its
+    /// `2^(depth + 1) - 1` nodes) over `num_features` features. Panics if `nodes` is
+    /// not such a tree, since `predict` would then read outside of it.
+    pub fn new(nodes: &'a [[u32; 2]], depth: u32, num_features: usize) -> Self {
+        assert_eq!(
+            nodes.len(),
+            (2 << depth) - 1,
+            "a tree of depth {} has {} nodes",
+            depth,
+            (2 << depth) - 1
+        );
+        for (index, &[split, offset]) in nodes[..(1 << depth) - 1].iter().enumerate() {
+            assert!(
+                ((split & 0xffff) as usize) < num_features,
+                "node {} tests feature {} of {}",
+                index,
+                split & 0xffff,
+                num_features
+            );
+            assert_eq!(
+                [split, offset],
+                Self::split(index, split as u16, (split >> 16) as i16),
+                "node {} does not point to its children",
+                index
+            );
+        }
+        Self {
+            nodes,
+            depth,
+            num_features,
+        }
+    }
+
+    /// The value of the leaf that `features` reach from the root. Takes one `TREE_STEP`
+    /// instruction (i.e. one signed comparison lookup) per level of the tree.
+    pub fn predict(&self, features: &[i32]) -> i32 {
+        assert_eq!(features.len(), self.num_features);
+        #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+        {
+            let mut node = self.nodes.as_ptr();
+            for _ in 0..self.depth {
+                // `.insn r CUSTOM_0, 3, 0, rd, rs1, rs2`
+                unsafe {
+                    core::arch::asm!(
+                        ".insn r 0x0b, 3, 0, {node}, {node}, {features}",
+                        node = inout(reg) node,
+                        features = in(reg) features.as_ptr(),
+                        options(pure, readonly, nostack, preserves_flags),
+                    );
+                }
+            }
+            // `new` checked that every walk from the root ends at a leaf
+            unsafe { (*node)[0] as i32 }
+        }
+        #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+        {
+            let mut index = 0;
+            for _ in 0..self.depth {
+                let split = self.nodes[index][0];
+                let threshold = (split >> 16) as i16 as i32;
+                let right_child = features[(split & 0xffff) as usize] > threshold;
+                index = 2 * index + 1 + right_child as usize;
+            }
+            self.nodes[index][0] as i32
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod build_wasm;
+mod program;
 
 use std::{
     fs::{self, File},
@@ -12,6 +13,7 @@ use sysinfo::System;
 
 use build_wasm::{build_wasm, modify_cargo_toml};
 use jolt_core::host::toolchain;
+use program::{GuestArgs, ProveArgs, VerifyArgs};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -36,16 +38,29 @@ enum Command {
     UninstallToolchain,
     /// Handles preprocessing and generates WASM compatible files
     BuildWasm,
+    /// Builds a guest and prints the path of its ELF
+    Build(GuestArgs),
+    /// Proves an execution of a guest, writing the proof and verifier key
+    Prove(ProveArgs),
+    /// Verifies a proof against a verifier key
+    Verify(VerifyArgs),
+    /// Traces a guest and reports its cycle count and lookup usage
+    Analyze(GuestArgs),
 }
 
-fn main() {
+fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli.command {
         Command::New { name, wasm } => create_project(name, wasm),
         Command::InstallToolchain => install_toolchain(),
         Command::UninstallToolchain => uninstall_toolchain(),
         Command::BuildWasm => build_wasm(),
+        Command::Build(args) => program::build(args)?,
+        Command::Prove(args) => program::prove(args)?,
+        Command::Verify(args) => program::verify(args)?,
+        Command::Analyze(args) => program::analyze(args)?,
     }
+    Ok(())
 }
 
 fn create_project(name: String, wasm: bool) {
@@ -201,7 +216,7 @@ ark-serialize = { git = "https://github.com/a16z/arkworks-algebra", branch = "op
 const HOST_MAIN: &str = r#"pub fn main() {
     let (prove_fib, verify_fib) = guest::build_fib();
 
-    let (output, proof) = prove_fib(50);
+    let (output, proof) = prove_fib(50).unwrap();
     let is_valid = verify_fib(proof);
 
     println!("output: {}", output);
@@ -0,0 +1,195 @@
+//! `jolt build`, `jolt prove`, `jolt verify` and `jolt analyze`, which drive a guest
+//! through `host::Program` and `RV32IJoltVM` without a host program of its own.
+use std::{fs, path::PathBuf};
+
+use clap::Args;
+use eyre::{eyre, Result};
+
+use common::constants::{
+    DEFAULT_MAX_BYTECODE_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_MEMORY_ADDRESS,
+    DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+};
+use common::rv_trace::IoCodec;
+use jolt_core::host::Program;
+use jolt_core::jolt::vm::rv32i_vm::{
+    JoltHyperKZGProof, JoltHyperKZGVerifierKey, ProofTranscript, RV32IJoltVM, Serializable, C, M,
+    PCS,
+};
+use jolt_core::jolt::vm::Jolt;
+use jolt_core::poly::commitment::commitment_scheme::CommitmentScheme;
+
+type F = <PCS as CommitmentScheme<ProofTranscript>>::Field;
+
+/// The guest program and the bounds it is built and preprocessed with; the
+/// defaults match those of `#[jolt::provable]`.
+#[derive(Args)]
+pub struct GuestArgs {
+    /// Name of the guest package
+    guest: String,
+    /// The provable function to run
+    #[arg(long)]
+    func: Option<String>,
+    /// Whether the guest uses the standard library
+    #[arg(long)]
+    std: bool,
+    /// File holding the guest's inputs, serialized with the guest's codec
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Codec the guest's inputs and outputs are serialized with
+    #[arg(long, default_value = "postcard")]
+    codec: IoCodec,
+    #[arg(long, default_value_t = DEFAULT_MEMORY_SIZE)]
+    memory_size: u64,
+    #[arg(long, default_value_t = DEFAULT_STACK_SIZE)]
+    stack_size: u64,
+    #[arg(long, default_value_t = DEFAULT_MAX_INPUT_SIZE)]
+    max_input_size: u64,
+    #[arg(long, default_value_t = DEFAULT_MAX_OUTPUT_SIZE)]
+    max_output_size: u64,
+}
+
+impl GuestArgs {
+    fn program(&self) -> Result<Program> {
+        let mut program = Program::new(&self.guest);
+        if let Some(func) = &self.func {
+            program.set_func(func);
+        }
+        program.set_std(self.std);
+        program.set_io_codec(self.codec);
+        program.set_memory_size(self.memory_size);
+        program.set_stack_size(self.stack_size);
+        program.set_max_input_size(self.max_input_size);
+        program.set_max_output_size(self.max_output_size);
+        if let Some(input) = &self.input {
+            program.set_input_bytes(&fs::read(input)?);
+        }
+        Ok(program)
+    }
+}
+
+#[derive(Args)]
+pub struct ProveArgs {
+    #[command(flatten)]
+    guest: GuestArgs,
+    /// Where to write the proof; defaults to `<guest>.proof`
+    #[arg(long)]
+    proof: Option<PathBuf>,
+    /// Where to write the verifier key; defaults to `<guest>.vk`
+    #[arg(long)]
+    vk: Option<PathBuf>,
+    #[arg(long, default_value_t = DEFAULT_MAX_BYTECODE_SIZE as usize)]
+    max_bytecode_size: usize,
+    #[arg(long, default_value_t = DEFAULT_MAX_MEMORY_ADDRESS as usize)]
+    max_memory_address: usize,
+    #[arg(long, default_value_t = DEFAULT_MAX_TRACE_LENGTH as usize)]
+    max_trace_length: usize,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Proof written by `jolt prove`
+    proof: PathBuf,
+    /// Verifier key written by `jolt prove`
+    vk: PathBuf,
+}
+
+pub fn build(args: GuestArgs) -> Result<()> {
+    let mut program = args.program()?;
+    program.build();
+    println!("{}", program.elf.unwrap().display());
+    Ok(())
+}
+
+pub fn prove(args: ProveArgs) -> Result<()> {
+    let mut program = args.guest.program()?;
+    let (bytecode, memory_init) = program.try_decode()?;
+    let (io_device, trace) = program.try_trace()?;
+
+    let preprocessing = <RV32IJoltVM as Jolt<F, PCS, C, M, ProofTranscript>>::preprocess(
+        bytecode,
+        io_device.memory_layout.clone(),
+        memory_init,
+        args.max_bytecode_size,
+        args.max_memory_address,
+        args.max_trace_length,
+    );
+    let verifier_key = preprocessing.verifier_key();
+    let (proof, commitments, _) = RV32IJoltVM::try_prove(io_device, trace, &preprocessing)?;
+    let proof = JoltHyperKZGProof::new(proof, commitments, &preprocessing);
+
+    let guest = &args.guest.guest;
+    let proof_path = args
+        .proof
+        .unwrap_or_else(|| PathBuf::from(format!("{guest}.proof")));
+    let vk_path = args
+        .vk
+        .unwrap_or_else(|| PathBuf::from(format!("{guest}.vk")));
+    proof.save_to_file(&proof_path)?;
+    verifier_key.save_to_file(&vk_path)?;
+
+    print_program_io(&proof);
+    println!("proof:   {}", proof_path.display());
+    println!("vk:      {}", vk_path.display());
+    Ok(())
+}
+
+pub fn verify(args: VerifyArgs) -> Result<()> {
+    let proof = JoltHyperKZGProof::from_file(&args.proof)?;
+    let verifier_key = JoltHyperKZGVerifierKey::from_file(&args.vk)?;
+    print_program_io(&proof);
+    RV32IJoltVM::verify(&verifier_key, proof.proof, proof.commitments, None)
+        .map_err(|err| eyre!("proof is invalid: {err}"))?;
+    println!("valid");
+    Ok(())
+}
+
+pub fn analyze(args: GuestArgs) -> Result<()> {
+    let summary = args.program()?.trace_analyze::<F>();
+    println!("RV32IM cycles:       {}", summary.raw_trace.len());
+    println!("trace length:        {}", summary.trace_len());
+    println!(
+        "padded trace length: {}",
+        summary.trace_len().next_power_of_two()
+    );
+
+    let (instructions, subtables) = summary.analyze_lookups::<F>();
+    println!(
+        "\n{:<12} {:>10} {:>10} {:>12}",
+        "instruction", "steps", "lookups", "cost"
+    );
+    for usage in instructions {
+        println!(
+            "{:<12} {:>10} {:>10} {:>12}",
+            format!("{:?}", usage.opcode),
+            usage.count,
+            usage.lookups,
+            usage.estimated_commitment_cost()
+        );
+    }
+    println!("\n{:<30} {:>10} {:>12}", "subtable", "lookups", "cost");
+    for usage in subtables {
+        println!(
+            "{:<30} {:>10} {:>12}",
+            usage.name,
+            usage.lookups,
+            usage.estimated_commitment_cost()
+        );
+    }
+    Ok(())
+}
+
+fn print_program_io(proof: &JoltHyperKZGProof) {
+    let program_io = &proof.proof.program_io;
+    println!("inputs:  0x{}", to_hex(&program_io.inputs));
+    println!("outputs: 0x{}", to_hex(&program_io.outputs));
+    println!("journal: 0x{}", to_hex(&program_io.journal_digest));
+    println!(
+        "nondeterminism: 0x{}",
+        to_hex(&program_io.nondeterminism_digest)
+    );
+    println!("panic:   {}", program_io.panic);
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -5,8 +5,15 @@ extern crate fnv;
 use std::convert::TryInto;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use crate::trace::Tracer;
+use common::constants::{
+    ADVICE_INSTRUCTION, ARGMAX_INSTRUCTION, CYCLE_TRACKER_END, CYCLE_TRACKER_START,
+    EXP_INSTRUCTION, FIXED_MUL_INSTRUCTION, HINT_REQUEST, INPUT_STREAM_REQUEST, JOURNAL_APPEND,
+    RELU_INSTRUCTION, SATURATING_ADD_INSTRUCTION, SIGMOID_INSTRUCTION, TREE_STEP_INSTRUCTION,
+};
+use common::fixed_point;
 use common::rv_trace::*;
 
 use self::fnv::FnvHashMap;
@@ -65,6 +72,13 @@ pub const MIP_SEIP: u64 = 0x200;
 const MIP_STIP: u64 = 0x020;
 const MIP_SSIP: u64 = 0x002;
 
+/// Answers a guest's hint request (see `HINT_REQUEST`) with the bytes of the hint.
+pub type HintProvider = Arc<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+
+/// Supplies the word at the given byte offset of the advice tape the first time the
+/// guest accesses it, or `None` if there is no such word (see `Mmu::set_mmio_handler`).
+pub type MmioHandler = Arc<dyn Fn(u64) -> Option<u32> + Send + Sync>;
+
 /// Emulates a RISC-V CPU core
 pub struct Cpu {
     clock: u64,
@@ -83,6 +97,21 @@ pub struct Cpu {
     _dump_flag: bool,
     decode_cache: DecodeCache,
     unsigned_data_mask: u64,
+    /// Words read by the guest with `ADVICE_INSTRUCTION`: a sequence of messages,
+    /// each its length in bytes followed by its bytes.
+    advice: Vec<u32>,
+    advice_offset: usize,
+    hint_provider: Option<HintProvider>,
+    /// Whether LW and SW may access misaligned addresses, in which case they are
+    /// traced as `LW_MISALIGNED` and `SW_MISALIGNED`.
+    misaligned_accesses: bool,
+    /// Bytes of the input stream, read by the guest on demand.
+    input_stream: Vec<u8>,
+    input_stream_offset: usize,
+    /// The number of steps of the trace before the current instruction, counting each
+    /// virtual sequence in full (see `RV32IM::virtual_sequence_length`). Read by the
+    /// guest with `rdcycle` and `rdinstret`.
+    trace_length: u64,
     pub tracer: Rc<Tracer>,
 }
 
@@ -244,6 +273,13 @@ impl Cpu {
             _dump_flag: false,
             decode_cache: DecodeCache::new(),
             unsigned_data_mask: 0xffffffffffffffff,
+            advice: Vec::new(),
+            advice_offset: 0,
+            hint_provider: None,
+            misaligned_accesses: false,
+            input_stream: Vec::new(),
+            input_stream_offset: 0,
+            trace_length: 0,
             tracer,
         };
         cpu.x[0xb] = 0x1020; // I don't know why but Linux boot seems to require this initialization
@@ -320,21 +356,38 @@ impl Cpu {
             Err(e) => return Err(e),
         };
         let instruction_address = self.pc;
-        let word = match (original_word & 0x3) == 0x3 {
-            true => {
+        let compressed = (original_word & 0x3) != 0x3;
+        let word = match compressed {
+            false => {
                 self.pc = self.pc.wrapping_add(4); // 32-bit length non-compressed instruction
                 original_word
             }
-            false => {
+            true => {
                 self.pc = self.pc.wrapping_add(2); // 16-bit length compressed instruction
-                self.uncompress(original_word & 0xffff)
+                Self::uncompress(original_word & 0xffff, &self.xlen)
             }
         };
 
         match self.decode(word).cloned() {
             Ok(inst) => {
+                if word == CYCLE_TRACKER_START || word == CYCLE_TRACKER_END {
+                    self.record_cycle_marker(word == CYCLE_TRACKER_START);
+                } else if word == HINT_REQUEST {
+                    self.request_hint();
+                } else if word == INPUT_STREAM_REQUEST {
+                    self.read_input_stream();
+                } else if word == JOURNAL_APPEND {
+                    self.append_journal();
+                }
+
                 // setup trace
-                let trace_inst = inst.trace.unwrap()(&inst, &self.xlen, word, instruction_address);
+                let mut trace_inst =
+                    inst.trace.unwrap()(&inst, &self.xlen, word, instruction_address);
+                if self.misaligned_accesses {
+                    trace_inst.opcode = trace_inst.opcode.misaligned();
+                }
+                trace_inst.compressed = compressed;
+                let steps = trace_inst.opcode.virtual_sequence_length() as u64;
                 self.tracer.start_instruction(trace_inst);
                 self.tracer.capture_pre_state(self.x, &self.xlen);
 
@@ -345,6 +398,7 @@ impl Cpu {
                 // complete trace
                 self.tracer.capture_post_state(self.x, &self.xlen);
                 self.tracer.end_instruction();
+                self.trace_length += steps;
 
                 result
             }
@@ -357,6 +411,16 @@ impl Cpu {
         }
     }
 
+    /// Records a cycle-tracker marker, whose label is the `a1`-byte string at
+    /// address `a0`.
+    fn record_cycle_marker(&mut self, start: bool) {
+        let address = self.unsigned_data(self.x[10]);
+        let len = self.unsigned_data(self.x[11]) as usize;
+        let label = self.mmu.read_bytes_raw(address, len);
+        self.tracer
+            .push_cycle_marker(String::from_utf8_lossy(&label).into_owned(), start);
+    }
+
     /// Decodes a word instruction data and returns a reference to
     /// [`Instruction`](struct.Instruction.html). Using [`DecodeCache`](struct.DecodeCache.html)
     /// so if cache hits this method returns the result very quickly.
@@ -887,8 +951,10 @@ impl Cpu {
         }
     }
 
+    /// Expands the compressed (RVC) instruction `halfword` into the 32-bit instruction
+    /// it stands for, or returns an invalid instruction if it is reserved or unsupported.
     // @TODO: Optimize
-    fn uncompress(&self, halfword: u32) -> u32 {
+    pub(crate) fn uncompress(halfword: u32, xlen: &Xlen) -> u32 {
         let op = halfword & 0x3; // [1:0]
         let funct3 = (halfword >> 13) & 0x7; // [15:13]
 
@@ -927,7 +993,7 @@ impl Cpu {
 						((halfword << 1) & 0x40); // offset[6] <= [5]
                     return (offset << 20) | ((rs1 + 8) << 15) | (2 << 12) | ((rd + 8) << 7) | 0x3;
                 }
-                3 => {
+                3 if matches!(xlen, Xlen::Bit64) => {
                     // @TODO: Support C.FLW in 32-bit mode
                     // C.LD in 64-bit mode
                     // ld rd+8, offset(rs1+8)
@@ -973,7 +1039,7 @@ impl Cpu {
                         | (imm4_0 << 7)
                         | 0x23;
                 }
-                7 => {
+                7 if matches!(xlen, Xlen::Bit64) => {
                     // @TODO: Support C.FSW in 32-bit mode
                     // C.SD
                     // sd rs2+8, offset(rs1+8)
@@ -1014,8 +1080,12 @@ impl Cpu {
                         // @TODO: Support HINTs
                         // r == 0 and imm != 0 is HINTs
                     }
+                    1 if matches!(xlen, Xlen::Bit32) => {
+                        // C.JAL
+                        // jal x1, offset
+                        return (Self::c_jump_imm(halfword) << 12) | (1 << 7) | 0x6f;
+                    }
                     1 => {
-                        // @TODO: Support C.JAL in 32-bit mode
                         // C.ADDIW
                         // addiw r, r, imm
                         let r = (halfword >> 7) & 0x1f;
@@ -1203,24 +1273,8 @@ impl Cpu {
                     }
                     5 => {
                         // C.J
-                        // jal x0, imm
-                        let offset = match halfword & 0x1000 {
-								0x1000 => 0xfffff000,
-								_ => 0
-							} | // offset[31:12] <= [12]
-							((halfword >> 1) & 0x800) | // offset[11] <= [12]
-							((halfword >> 7) & 0x10) | // offset[4] <= [11]
-							((halfword >> 1) & 0x300) | // offset[9:8] <= [10:9]
-							((halfword << 2) & 0x400) | // offset[10] <= [8]
-							((halfword >> 1) & 0x40) | // offset[6] <= [7]
-							((halfword << 1) & 0x80) | // offset[7] <= [6]
-							((halfword >> 2) & 0xe) | // offset[3:1] <= [5:3]
-							((halfword << 3) & 0x20); // offset[5] <= [2]
-                        let imm = ((offset >> 1) & 0x80000) | // imm[19] <= offset[20]
-							((offset << 8) & 0x7fe00) | // imm[18:9] <= offset[10:1]
-							((offset >> 3) & 0x100) | // imm[8] <= offset[11]
-							((offset >> 12) & 0xff); // imm[7:0] <= offset[19:12]
-                        return (imm << 12) | 0x6f;
+                        // jal x0, offset
+                        return (Self::c_jump_imm(halfword) << 12) | 0x6f;
                     }
                     6 => {
                         // C.BEQZ
@@ -1300,7 +1354,7 @@ impl Cpu {
                         }
                         // r == 0 is reserved instruction
                     }
-                    3 => {
+                    3 if matches!(xlen, Xlen::Bit64) => {
                         // @TODO: Support C.FLWSP in 32-bit mode
                         // C.LDSP
                         // ld rd, offset(x2)
@@ -1387,7 +1441,7 @@ impl Cpu {
                             | (imm4_0 << 7)
                             | 0x23;
                     }
-                    7 => {
+                    7 if matches!(xlen, Xlen::Bit64) => {
                         // @TODO: Support C.FSWSP in 32-bit mode
                         // C.SDSP
                         // sd rs, offset(x2)
@@ -1411,6 +1465,27 @@ impl Cpu {
         0xffffffff // Return invalid value
     }
 
+    /// The `jal` immediate (instruction bits [31:12]) of the compressed jump `halfword`,
+    /// i.e. C.J or C.JAL.
+    fn c_jump_imm(halfword: u32) -> u32 {
+        let offset = match halfword & 0x1000 {
+				0x1000 => 0xfffff000,
+				_ => 0
+			} | // offset[31:12] <= [12]
+			((halfword >> 1) & 0x800) | // offset[11] <= [12]
+			((halfword >> 7) & 0x10) | // offset[4] <= [11]
+			((halfword >> 1) & 0x300) | // offset[9:8] <= [10:9]
+			((halfword << 2) & 0x400) | // offset[10] <= [8]
+			((halfword >> 1) & 0x40) | // offset[6] <= [7]
+			((halfword << 1) & 0x80) | // offset[7] <= [6]
+			((halfword >> 2) & 0xe) | // offset[3:1] <= [5:3]
+			((halfword << 3) & 0x20); // offset[5] <= [2]
+        ((offset >> 1) & 0x80000) | // imm[19] <= offset[20]
+			((offset << 8) & 0x7fe00) | // imm[18:9] <= offset[10:1]
+			((offset >> 3) & 0x100) | // imm[8] <= offset[11]
+			((offset >> 12) & 0xff) // imm[7:0] <= offset[19:12]
+    }
+
     /// Disassembles an instruction pointed by Program Counter.
     pub fn disassemble_next_instruction(&mut self) -> String {
         // @TODO: Fetching can make a side effect,
@@ -1428,7 +1503,7 @@ impl Cpu {
             true => original_word,
             false => {
                 original_word &= 0xffff;
-                self.uncompress(original_word)
+                Self::uncompress(original_word, &self.xlen)
             }
         };
 
@@ -1456,6 +1531,87 @@ impl Cpu {
         &mut self.mmu
     }
 
+    /// Sets the private inputs, which the guest reads as the first message of advice.
+    pub fn set_private_inputs(&mut self, inputs: &[u8]) {
+        // Guests without private inputs never read them, so a (possibly empty)
+        // message would be mistaken for the response to their first hint request
+        if !inputs.is_empty() {
+            self.push_advice(inputs);
+        }
+    }
+
+    /// Sets the function answering the guest's hint requests.
+    pub fn set_hint_provider(&mut self, hint_provider: HintProvider) {
+        self.hint_provider = Some(hint_provider);
+    }
+
+    /// Sets the function supplying the advice tape on demand; see
+    /// `Mmu::set_mmio_handler`.
+    pub fn set_mmio_handler(&mut self, mmio_handler: MmioHandler) {
+        self.mmu.set_mmio_handler(mmio_handler);
+    }
+
+    /// Allows LW and SW to access misaligned addresses; see `Mmu::set_misaligned_accesses`.
+    pub fn set_misaligned_accesses(&mut self, enabled: bool) {
+        self.misaligned_accesses = enabled;
+        self.mmu.set_misaligned_accesses(enabled);
+    }
+
+    /// Sets the input stream, whose bytes the guest reads in chunks.
+    pub fn set_input_stream(&mut self, input_stream: &[u8]) {
+        self.input_stream = input_stream.to_vec();
+    }
+
+    /// Queues the next (up to) `a0` bytes of the input stream as advice, and records
+    /// them in the program I/O.
+    fn read_input_stream(&mut self) {
+        let len = self.unsigned_data(self.x[10]) as usize;
+        let start = self.input_stream_offset;
+        let end = (start + len).min(self.input_stream.len());
+        let chunk = self.input_stream[start..end].to_vec();
+        self.input_stream_offset = end;
+        self.push_advice(&chunk);
+        self.mmu.jolt_device.input_stream.extend(chunk);
+    }
+
+    /// Appends the `a1`-byte entry at address `a0` to the journal.
+    fn append_journal(&mut self) {
+        let address = self.unsigned_data(self.x[10]);
+        let len = self.unsigned_data(self.x[11]) as usize;
+        let entry = self.mmu.read_bytes_raw(address, len);
+        self.mmu.jolt_device.append_journal(&entry);
+    }
+
+    /// Queues a message of advice: its length in bytes, then its bytes.
+    fn push_advice(&mut self, bytes: &[u8]) {
+        self.advice.push(bytes.len() as u32);
+        self.advice.extend(bytes.chunks(4).map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        }));
+    }
+
+    /// Returns the next word of advice, or 0 once it is exhausted.
+    fn next_advice_word(&mut self) -> u32 {
+        let word = self.advice.get(self.advice_offset).copied().unwrap_or(0);
+        self.advice_offset += 1;
+        word
+    }
+
+    /// Answers a hint request, whose request is the `a1`-byte string at address `a0`.
+    fn request_hint(&mut self) {
+        let address = self.unsigned_data(self.x[10]);
+        let len = self.unsigned_data(self.x[11]) as usize;
+        let request = self.mmu.read_bytes_raw(address, len);
+        let hint_provider = self
+            .hint_provider
+            .as_ref()
+            .expect("guest requested a hint, but no hint provider is set");
+        let response = hint_provider(&request);
+        self.push_advice(&response);
+    }
+
     /// Returns mutable `Terminal`
     pub fn get_mut_terminal(&mut self) -> &mut Box<dyn Terminal> {
         self.mmu.get_mut_uart().get_mut_terminal()
@@ -1829,6 +1985,7 @@ fn trace_r(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: Some(normalize_register(f.rs2)),
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        compressed: false,
     }
 }
 
@@ -1842,6 +1999,7 @@ fn trace_i(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: None,
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        compressed: false,
     }
 }
 
@@ -1855,6 +2013,7 @@ fn trace_s(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: Some(normalize_register(f.rs2)),
         rd: None,
         virtual_sequence_remaining: None,
+        compressed: false,
     }
 }
 
@@ -1868,6 +2027,7 @@ fn trace_b(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: Some(normalize_register(f.rs2)),
         rd: None,
         virtual_sequence_remaining: None,
+        compressed: false,
     }
 }
 
@@ -1881,6 +2041,83 @@ fn trace_u(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: None,
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        compressed: false,
+    }
+}
+
+// LR.W is traced as a plain load, since execution is single-threaded
+fn trace_lr(_inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    ELFInstruction {
+        opcode: RV32IM::LW,
+        address: normalize_u64(address, xlen),
+        imm: Some(0),
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+        compressed: false,
+    }
+}
+
+// Reads the next word of advice
+fn trace_advice(_inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_i(word);
+    ELFInstruction {
+        opcode: RV32IM::VIRTUAL_ADVICE,
+        address: normalize_u64(address, xlen),
+        imm: None,
+        rs1: None,
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+        compressed: false,
+    }
+}
+
+// Reads the `cycle` or `instret` counter, which Jolt constrains to the step's index
+fn trace_rdcycle(_inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_csr(word);
+    ELFInstruction {
+        opcode: RV32IM::RDCYCLE,
+        address: normalize_u64(address, xlen),
+        imm: None,
+        rs1: None,
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+        compressed: false,
+    }
+}
+
+// Reads the upper half of the `cycle` or `instret` counter, which is always 0 as traces
+// are shorter than 2^32 steps; traced as `addi rd, x0, 0`
+fn trace_rdcycleh(_inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_csr(word);
+    ELFInstruction {
+        opcode: RV32IM::ADDI,
+        address: normalize_u64(address, xlen),
+        imm: Some(0),
+        rs1: Some(0),
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+        compressed: false,
+    }
+}
+
+// `RELU_INSTRUCTION`, `EXP_INSTRUCTION` and `SIGMOID_INSTRUCTION` only read rs1
+fn trace_r_unary(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    ELFInstruction {
+        opcode: RV32IM::from_str(inst.name).unwrap(),
+        address: normalize_u64(address, xlen),
+        imm: None,
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+        compressed: false,
     }
 }
 
@@ -1895,10 +2132,11 @@ fn trace_j(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
         rs2: None,
         rd: Some(normalize_register(f.rd)),
         virtual_sequence_remaining: None,
+        compressed: false,
     }
 }
 
-const INSTRUCTION_NUM: usize = 116;
+const INSTRUCTION_NUM: usize = 130;
 
 // @TODO: Reorder in often used order as
 pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
@@ -1950,6 +2188,146 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_r,
         trace: None,
     },
+    Instruction {
+        mask: 0xfffff07f,
+        data: ADVICE_INSTRUCTION,
+        name: "ADVICE",
+        operation: |cpu, word, _address| {
+            let f = parse_format_i(word);
+            let value = cpu.next_advice_word();
+            cpu.tracer.set_advice_value(value as u64);
+            cpu.x[f.rd] = cpu.sign_extend(value as i32 as i64);
+            Ok(())
+        },
+        disassemble: dump_format_i,
+        trace: Some(trace_advice),
+    },
+    // `csrrs rd, cycle, x0` and `csrrs rd, instret, x0`, i.e. `rdcycle` and `rdinstret`
+    Instruction {
+        mask: 0xffdff07f,
+        data: 0xc0002073,
+        name: "RDCYCLE",
+        operation: |cpu, word, _address| {
+            let f = parse_format_csr(word);
+            let value = cpu.trace_length;
+            cpu.tracer.set_advice_value(value);
+            cpu.x[f.rd] = cpu.sign_extend(value as i64);
+            Ok(())
+        },
+        disassemble: dump_format_csr,
+        trace: Some(trace_rdcycle),
+    },
+    // `csrrs rd, cycleh, x0` and `csrrs rd, instreth, x0`, i.e. `rdcycleh` and `rdinstreth`
+    Instruction {
+        mask: 0xffdff07f,
+        data: 0xc8002073,
+        name: "RDCYCLEH",
+        operation: |cpu, word, _address| {
+            let f = parse_format_csr(word);
+            cpu.x[f.rd] = 0;
+            Ok(())
+        },
+        disassemble: dump_format_csr,
+        trace: Some(trace_rdcycleh),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: RELU_INSTRUCTION,
+        name: "RELU",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1].max(0);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r_unary),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: ARGMAX_INSTRUCTION,
+        name: "ARGMAX",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = (cpu.x[f.rs2] > cpu.x[f.rs1]) as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: TREE_STEP_INSTRUCTION,
+        name: "TREE_STEP",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let node = cpu.x[f.rs1];
+            let split = cpu.mmu.load_word(node as u64)?;
+            let offset = cpu.mmu.load_word(node.wrapping_add(4) as u64)?;
+            let feature_address = cpu.x[f.rs2].wrapping_add(4 * (split & 0xffff) as i64);
+            let feature = cpu.mmu.load_word(feature_address as u64)?;
+            // The virtual sequence loads these again
+            let mut words_read = [0; 16];
+            words_read[..3].copy_from_slice(&[split, offset, feature]);
+            cpu.tracer.set_precompile_input(words_read);
+
+            let threshold = (split >> 16) as i16 as i32;
+            let right_child = (feature as i32 > threshold) as i64;
+            cpu.x[f.rd] =
+                cpu.sign_extend(node.wrapping_add(offset as i32 as i64 + 8 * right_child));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: FIXED_MUL_INSTRUCTION,
+        name: "FIXED_MUL",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let product = (cpu.x[f.rs1] as i32 as i64) * (cpu.x[f.rs2] as i32 as i64);
+            cpu.x[f.rd] = ((product + (1 << 15)) >> 16) as i32 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: SATURATING_ADD_INSTRUCTION,
+        name: "SATURATING_ADD",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = (cpu.x[f.rs1] as i32).saturating_add(cpu.x[f.rs2] as i32) as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: EXP_INSTRUCTION,
+        name: "EXP",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = fixed_point::exp(cpu.x[f.rs1] as i16) as i32 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: SIGMOID_INSTRUCTION,
+        name: "SIGMOID",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = fixed_point::sigmoid(cpu.x[f.rs1] as i16) as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r_unary),
+    },
     Instruction {
         mask: 0xf800707f,
         data: 0x0000302f,
@@ -1994,7 +2372,7 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2040,7 +2418,31 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0xa000202f,
+        name: "AMOMAX.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data,
+                Err(e) => return Err(e),
+            };
+            let result = match cpu.x[f.rs2] as i32 >= tmp as i32 {
+                true => cpu.x[f.rs2] as u32,
+                false => tmp,
+            };
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, result) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i32 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2088,7 +2490,55 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0x8000202f,
+        name: "AMOMIN.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data,
+                Err(e) => return Err(e),
+            };
+            let result = match (cpu.x[f.rs2] as i32) < tmp as i32 {
+                true => cpu.x[f.rs2] as u32,
+                false => tmp,
+            };
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, result) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i32 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0xc000202f,
+        name: "AMOMINU.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data,
+                Err(e) => return Err(e),
+            };
+            let result = match (cpu.x[f.rs2] as u32) < tmp {
+                true => cpu.x[f.rs2] as u32,
+                false => tmp,
+            };
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, result) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i32 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2134,7 +2584,7 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xf800707f,
@@ -2177,7 +2627,28 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xf800707f,
+        data: 0x2000202f,
+        name: "AMOXOR.W",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let tmp = match cpu.mmu.load_word(cpu.x[f.rs1] as u64) {
+                Ok(data) => data,
+                Err(e) => return Err(e),
+            };
+            let result = cpu.x[f.rs2] as u32 ^ tmp;
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, result) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
+            };
+            cpu.x[f.rd] = tmp as i32 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0xfe00707f,
@@ -3057,7 +3528,7 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_lr),
     },
     Instruction {
         mask: 0x0000007f,
@@ -3353,21 +3824,18 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         name: "SC.W",
         operation: |cpu, word, _address| {
             let f = parse_format_r(word);
-            // @TODO: Implement properly
-            cpu.x[f.rd] = match cpu.is_reservation_set && cpu.reservation == (cpu.x[f.rs1] as u64) {
-                true => match cpu.mmu.store_word(cpu.x[f.rs1] as u64, cpu.x[f.rs2] as u32) {
-                    Ok(()) => {
-                        cpu.is_reservation_set = false;
-                        0
-                    }
-                    Err(e) => return Err(e),
-                },
-                false => 1,
+            // Execution is single-threaded, so no other hart can invalidate the
+            // reservation; SC.W always succeeds, and is traced as a plain store.
+            match cpu.mmu.store_word(cpu.x[f.rs1] as u64, cpu.x[f.rs2] as u32) {
+                Ok(()) => {}
+                Err(e) => return Err(e),
             };
+            cpu.is_reservation_set = false;
+            cpu.x[f.rd] = 0;
             Ok(())
         },
         disassemble: dump_format_r,
-        trace: None,
+        trace: Some(trace_r),
     },
     Instruction {
         mask: 0x0000707f,
@@ -4061,6 +4529,338 @@ mod test_cpu {
         // @TODO: Test compressed instruction operation
     }
 
+    #[test]
+    fn tick_operate_private_input() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        // Read two words of private inputs into a0
+        let read_a0 = ADVICE_INSTRUCTION | (10 << 7);
+        cpu.get_mut_mmu().store_word(DRAM_BASE, read_a0).unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 4, read_a0)
+            .unwrap();
+        cpu.set_private_inputs(&[1, 2, 3, 4, 5]);
+
+        cpu.tick_operate().unwrap();
+        assert_eq!(5, cpu.read_register(10));
+        cpu.tick_operate().unwrap();
+        assert_eq!(0x04030201, cpu.read_register(10));
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::VIRTUAL_ADVICE, rows[1].instruction.opcode);
+        assert_eq!(Some(10), rows[1].instruction.rd);
+        assert_eq!(Some(0x04030201), rows[1].advice_value);
+        assert_eq!(Some(0x04030201), rows[1].register_state.rd_post_val);
+    }
+
+    #[test]
+    fn tick_operate_rdcycle() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(12);
+        cpu.update_pc(DRAM_BASE);
+        // div a0, a0, a1; rdcycle a2; rdcycleh a3
+        for (i, word) in [0x02b54533, 0xc0002673, 0xc80026f3].into_iter().enumerate() {
+            cpu.get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, word)
+                .unwrap();
+        }
+        cpu.x[13] = -1;
+
+        for _ in 0..3 {
+            cpu.tick_operate().unwrap();
+        }
+        // The DIV is proven in as many steps as its virtual sequence is long
+        let steps = RV32IM::DIV.virtual_sequence_length() as i64;
+        assert_eq!(steps, cpu.read_register(12));
+        assert_eq!(0, cpu.read_register(13));
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::RDCYCLE, rows[1].instruction.opcode);
+        assert_eq!(Some(12), rows[1].instruction.rd);
+        assert_eq!(Some(steps as u64), rows[1].advice_value);
+        assert_eq!(RV32IM::ADDI, rows[2].instruction.opcode);
+        assert_eq!(Some(0), rows[2].register_state.rd_post_val);
+    }
+
+    #[test]
+    fn tick_operate_relu_argmax() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        cpu.update_pc(DRAM_BASE);
+        // relu a2, a0; argmax a3, a0, a1; relu a4, a1; argmax a5, a1, a1
+        for (i, word) in [0x0005160b, 0x00b5268b, 0x0005970b, 0x00b5a78b]
+            .into_iter()
+            .enumerate()
+        {
+            cpu.get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, word)
+                .unwrap();
+        }
+        cpu.x[10] = -5;
+        cpu.x[11] = 3;
+
+        for _ in 0..4 {
+            cpu.tick_operate().unwrap();
+        }
+        assert_eq!(0, cpu.read_register(12));
+        assert_eq!(1, cpu.read_register(13));
+        assert_eq!(3, cpu.read_register(14));
+        // Ties select the first operand
+        assert_eq!(0, cpu.read_register(15));
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::RELU, rows[0].instruction.opcode);
+        assert_eq!(None, rows[0].instruction.rs2);
+        assert_eq!(RV32IM::ARGMAX, rows[1].instruction.opcode);
+        assert_eq!(Some(11), rows[1].instruction.rs2);
+    }
+
+    #[test]
+    fn tick_operate_tree_step() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(128);
+        cpu.update_pc(DRAM_BASE);
+        // tree_step a2, a0, a1; tree_step a3, a0, a4; tree_step a5, a0, a6
+        let program = [0x00b5360b, 0x00e5368b, 0x0105378b];
+        // A root node testing feature 1 against -2, and its two children
+        let split = 0xfffe_0001;
+        let nodes = [split, 8, 0, 0, 0, 0];
+        let features = [5, -3i32 as u32, 0, -2i32 as u32, 0, 7];
+        let (nodes_address, features_address) = (DRAM_BASE + 0x10, DRAM_BASE + 0x30);
+        for (address, words) in [
+            (DRAM_BASE, &program[..]),
+            (nodes_address, &nodes[..]),
+            (features_address, &features[..]),
+        ] {
+            for (i, word) in words.iter().enumerate() {
+                cpu.get_mut_mmu()
+                    .store_word(address + 4 * i as u64, *word)
+                    .unwrap();
+            }
+        }
+        cpu.x[10] = nodes_address as i64;
+        cpu.x[11] = features_address as i64;
+        cpu.x[14] = features_address as i64 + 8;
+        cpu.x[16] = features_address as i64 + 16;
+
+        for _ in 0..3 {
+            cpu.tick_operate().unwrap();
+        }
+        let (left, right) = (nodes_address + 8, nodes_address + 16);
+        assert_eq!(left, cpu.read_register(12) as u64);
+        // Ties go left
+        assert_eq!(left, cpu.read_register(13) as u64);
+        assert_eq!(right, cpu.read_register(15) as u64);
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::TREE_STEP, rows[2].instruction.opcode);
+        assert_eq!(
+            [split, 8, 7],
+            rows[2].precompile_input.unwrap()[..3],
+            "the split, child offset and feature read"
+        );
+    }
+
+    #[test]
+    fn tick_operate_fixed_point() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        cpu.update_pc(DRAM_BASE);
+        // fixed_mul a2, a0, a1; saturating_add a3, a0, a1; saturating_add a4, a5, a5
+        for (i, word) in [0x00b5460b, 0x00b5568b, 0x00f7d70b].into_iter().enumerate() {
+            cpu.get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, word)
+                .unwrap();
+        }
+        // 1.5 and -2.25
+        cpu.x[10] = 3 << 15;
+        cpu.x[11] = -9 << 14;
+        cpu.x[15] = i32::MIN as i64 + 5;
+
+        for _ in 0..3 {
+            cpu.tick_operate().unwrap();
+        }
+        // -3.375
+        assert_eq!(-27 << 13, cpu.read_register(12));
+        assert_eq!(-3 << 14, cpu.read_register(13));
+        assert_eq!(i32::MIN as i64, cpu.read_register(14));
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::FIXED_MUL, rows[0].instruction.opcode);
+        assert_eq!(RV32IM::SATURATING_ADD, rows[1].instruction.opcode);
+    }
+
+    #[test]
+    fn tick_operate_exp() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        // exp a2, a0; exp a3, a1
+        for (i, word) in [0x0005660b, 0x0005e68b].into_iter().enumerate() {
+            cpu.get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, word)
+                .unwrap();
+        }
+        // 1.0, and -1.0 in the lower half (the upper half is ignored)
+        cpu.x[10] = 1 << 8;
+        cpu.x[11] = 0x7fff_ff00;
+
+        cpu.tick_operate().unwrap();
+        cpu.tick_operate().unwrap();
+        // e and 1/e, in Q16.16
+        assert_eq!(178145, cpu.read_register(12));
+        assert_eq!(24109, cpu.read_register(13));
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::EXP, rows[0].instruction.opcode);
+        assert_eq!(None, rows[0].instruction.rs2);
+    }
+
+    #[test]
+    fn tick_operate_sigmoid() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        // sigmoid a2, a0; sigmoid a3, a1
+        for (i, word) in [0x0005760b, 0x0005f68b].into_iter().enumerate() {
+            cpu.get_mut_mmu()
+                .store_word(DRAM_BASE + 4 * i as u64, word)
+                .unwrap();
+        }
+        // 0 and -2.0
+        cpu.x[10] = 0;
+        cpu.x[11] = -2 << 8;
+
+        cpu.tick_operate().unwrap();
+        cpu.tick_operate().unwrap();
+        // 0.5 and 0.1192..., in Q16.16
+        assert_eq!(1 << 15, cpu.read_register(12));
+        assert_eq!(7812, cpu.read_register(13));
+
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(RV32IM::SIGMOID, rows[1].instruction.opcode);
+    }
+
+    #[test]
+    fn tick_operate_cycle_tracker() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        cpu.update_pc(DRAM_BASE);
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE, CYCLE_TRACKER_START)
+            .unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 4, CYCLE_TRACKER_END)
+            .unwrap();
+        cpu.get_mut_mmu().store(DRAM_BASE + 8, b'a').unwrap();
+        cpu.get_mut_mmu().store(DRAM_BASE + 9, b'b').unwrap();
+        cpu.x[10] = (DRAM_BASE + 8) as i64;
+        cpu.x[11] = 2;
+
+        cpu.tick_operate().unwrap();
+        cpu.tick_operate().unwrap();
+
+        let marker = |start, cycle| CycleMarker {
+            label: "ab".to_string(),
+            start,
+            cycle,
+        };
+        assert_eq!(
+            *cpu.tracer.cycle_markers.borrow(),
+            vec![marker(true, 0), marker(false, 1)]
+        );
+        // The markers have no effect on the registers
+        assert_eq!((DRAM_BASE + 8) as i64, cpu.x[10]);
+        assert_eq!(2, cpu.x[11]);
+    }
+
+    #[test]
+    fn tick_operate_hint_request() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        cpu.update_pc(DRAM_BASE);
+        let read_a2 = ADVICE_INSTRUCTION | (12 << 7);
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE, HINT_REQUEST)
+            .unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 4, read_a2)
+            .unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 8, read_a2)
+            .unwrap();
+        cpu.get_mut_mmu().store(DRAM_BASE + 12, 3).unwrap();
+        cpu.x[10] = (DRAM_BASE + 12) as i64;
+        cpu.x[11] = 1;
+        // Answers with the request, doubled
+        cpu.set_hint_provider(Arc::new(|request: &[u8]| {
+            request.iter().map(|byte| byte * 2).collect()
+        }));
+
+        cpu.tick_operate().unwrap();
+        cpu.tick_operate().unwrap();
+        assert_eq!(1, cpu.read_register(12));
+        cpu.tick_operate().unwrap();
+        assert_eq!(6, cpu.read_register(12));
+    }
+
+    #[test]
+    fn tick_operate_input_stream_request() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        cpu.update_pc(DRAM_BASE);
+        let read_a2 = ADVICE_INSTRUCTION | (12 << 7);
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE, INPUT_STREAM_REQUEST)
+            .unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 4, read_a2)
+            .unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 8, read_a2)
+            .unwrap();
+        cpu.x[10] = 2;
+        cpu.set_input_stream(&[1, 2, 3]);
+
+        cpu.tick_operate().unwrap();
+        cpu.tick_operate().unwrap();
+        assert_eq!(2, cpu.read_register(12));
+        cpu.tick_operate().unwrap();
+        assert_eq!(0x0201, cpu.read_register(12));
+        // Only the bytes read are part of the program I/O
+        assert_eq!(vec![1, 2], cpu.get_mut_mmu().jolt_device.input_stream);
+    }
+
+    #[test]
+    fn tick_operate_journal_append() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(12);
+        cpu.update_pc(DRAM_BASE);
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE, JOURNAL_APPEND)
+            .unwrap();
+        cpu.get_mut_mmu()
+            .store_word(DRAM_BASE + 4, JOURNAL_APPEND)
+            .unwrap();
+        cpu.get_mut_mmu().store(DRAM_BASE + 8, b'a').unwrap();
+        cpu.get_mut_mmu().store(DRAM_BASE + 9, b'b').unwrap();
+        cpu.x[10] = (DRAM_BASE + 8) as i64;
+        cpu.x[11] = 2;
+
+        cpu.tick_operate().unwrap();
+        cpu.x[11] = 1;
+        cpu.tick_operate().unwrap();
+
+        let device = &cpu.get_mut_mmu().jolt_device;
+        assert_eq!(
+            Some(vec![b"ab".to_vec(), b"a".to_vec()]),
+            device.journal.clone()
+        );
+        assert!(device.journal_matches_digest());
+        assert_ne!([0; 32], device.journal_digest);
+    }
+
     #[test]
     fn fetch() {
         // .fetch() reads four bytes from the memory
@@ -4110,13 +4910,23 @@ mod test_cpu {
         let mut cpu = create_cpu();
         // .uncompress() doesn't directly return an instruction but
         // it returns uncompressed word. Then you need to call .decode().
-        match cpu.decode(cpu.uncompress(0x20)) {
+        match cpu.decode(Cpu::uncompress(0x20, &cpu.xlen)) {
             Ok(inst) => assert_eq!(inst.name, "ADDI"),
             Err(_e) => panic!("Failed to decode"),
         };
         // @TODO: Should I test all compressed instructions?
     }
 
+    #[test]
+    fn uncompress_rv32() {
+        // c.jal -2 is C.ADDIW on RV64, but a jump and link on RV32
+        assert_eq!(Cpu::uncompress(0x3ffd, &Xlen::Bit32), 0xfffff0ef); // jal ra, -2
+        assert_eq!(Cpu::uncompress(0xbffd, &Xlen::Bit32), 0xfffff06f); // jal x0, -2
+                                                                       // c.flw and c.fsw (C.LD and C.SD on RV64) need the F extension
+        assert_eq!(Cpu::uncompress(0x6000, &Xlen::Bit32), 0xffffffff);
+        assert_eq!(Cpu::uncompress(0xe000, &Xlen::Bit32), 0xffffffff);
+    }
+
     #[test]
     fn wfi() {
         let wfi_instruction = 0x10500073;
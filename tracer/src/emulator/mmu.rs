@@ -8,12 +8,14 @@ extern crate fnv;
 
 use std::rc::Rc;
 
+use crate::fault::{FaultCause, MemoryAccessKind};
 use crate::trace::Tracer;
+use common::constants::{GUARD_REGION_SIZE, INPUTS_DIGEST_SIZE};
 use common::rv_trace::{JoltDevice, MemoryState};
 
-use self::fnv::FnvHashMap;
+use self::fnv::{FnvHashMap, FnvHashSet};
 
-use super::cpu::{get_privilege_mode, PrivilegeMode, Trap, TrapType, Xlen};
+use super::cpu::{get_privilege_mode, MmioHandler, PrivilegeMode, Trap, TrapType, Xlen};
 use super::device::clint::Clint;
 use super::device::plic::Plic;
 use super::device::uart::Uart;
@@ -40,8 +42,28 @@ pub struct Mmu {
     uart: Uart,
 
     pub jolt_device: JoltDevice,
+    /// Digest of `jolt_device.inputs`, computed once by `set_jolt_device` rather than
+    /// on every load from the inputs digest region.
+    inputs_digest: [u8; INPUTS_DIGEST_SIZE as usize],
+    /// Bytes the guest has written to the advice tape. `jolt_device` keeps the tape's
+    /// initial contents, which the prover commits to.
+    advice_writes: FnvHashMap<u64, u8>,
+    /// Supplies the initial contents of the advice tape past `mmio_start` on demand;
+    /// see `set_mmio_handler`.
+    mmio_handler: Option<MmioHandler>,
+    mmio_start: u64,
+    /// Words of the advice tape whose initial contents the MMIO handler has supplied.
+    mmio_words: FnvHashSet<u64>,
     tracer: Rc<Tracer>,
 
+    /// Start addresses of the guard regions below the stack and above the heap, if the
+    /// guest's linker script defines them; see `set_guard_regions`.
+    stack_guard: Option<u64>,
+    heap_guard: Option<u64>,
+
+    /// Whether word loads and stores may be misaligned; see `set_misaligned_accesses`.
+    misaligned_accesses: bool,
+
     /// Address translation can be affected `mstatus` (MPRV, MPP in machine mode)
     /// then `Mmu` has copy of it.
     mstatus: u64,
@@ -113,7 +135,15 @@ impl Mmu {
             clint: Clint::new(),
             uart: Uart::new(terminal),
             jolt_device: JoltDevice::new(0, 0),
+            inputs_digest: JoltDevice::new(0, 0).inputs_digest(),
+            advice_writes: FnvHashMap::default(),
+            mmio_handler: None,
+            mmio_start: 0,
+            mmio_words: FnvHashSet::default(),
             tracer,
+            stack_guard: None,
+            heap_guard: None,
+            misaligned_accesses: false,
             mstatus: 0,
             page_cache_enabled: false,
             fetch_page_cache: FnvHashMap::default(),
@@ -131,6 +161,22 @@ impl Mmu {
         self.clear_page_cache();
     }
 
+    /// Makes accesses to the `GUARD_REGION_SIZE` bytes starting at `stack_guard` (below
+    /// the stack) and `heap_guard` (above the heap) fault as a stack overflow and heap
+    /// exhaustion respectively.
+    pub fn set_guard_regions(&mut self, stack_guard: u64, heap_guard: u64) {
+        self.stack_guard = Some(stack_guard);
+        self.heap_guard = Some(heap_guard);
+    }
+
+    /// Allows word loads and stores at addresses that are not a multiple of 4, which
+    /// otherwise panic. A misaligned access is recorded as an access to the two words
+    /// it spans, packed into a doubleword (low word first) at the address of the lower
+    /// word; the virtual sequences of `LW_MISALIGNED` and `SW_MISALIGNED` split it up.
+    pub fn set_misaligned_accesses(&mut self, enabled: bool) {
+        self.misaligned_accesses = enabled;
+    }
+
     /// Initializes Main memory. This method is expected to be called only once.
     ///
     /// # Arguments
@@ -237,31 +283,101 @@ impl Mmu {
     /// * `effective_address` Effective memory address to validate
     #[inline]
     fn assert_effective_address(&self, effective_address: u64) {
+        self.check_guard_regions(effective_address, MemoryAccessKind::Store);
         if effective_address < DRAM_BASE {
+            // The advice tape lies between the program I/O and DRAM_BASE
+            if self.jolt_device.is_advice(effective_address) {
+                return;
+            }
             // less then DRAM_BASE and greater then panic => zero_padding region
-            assert!(
-                effective_address <= self.jolt_device.memory_layout.termination,
-                "Stack overflow: Attempted to write to 0x{:X}",
-                effective_address
-            );
+            if effective_address > self.jolt_device.memory_layout.termination {
+                self.fault(
+                    effective_address,
+                    MemoryAccessKind::Store,
+                    FaultCause::StackOverflow,
+                    format!(
+                        "Stack overflow: Attempted to write to 0x{:X}",
+                        effective_address
+                    ),
+                );
+            }
             // less then panic => jolt_device region (i.e. input/output)
-            assert!(
-                self.jolt_device.is_output(effective_address)
-                    || self.jolt_device.is_panic(effective_address)
-                    || self.jolt_device.is_termination(effective_address),
-                "Unknown memory mapping: 0x{:X}",
-                effective_address
-            );
-        } else {
+            if !(self.jolt_device.is_output(effective_address)
+                || self.jolt_device.is_log(effective_address)
+                || self.jolt_device.is_stream_digest(effective_address)
+                || self.jolt_device.is_journal_digest(effective_address)
+                || self.jolt_device.is_nondeterminism_digest(effective_address)
+                || self.jolt_device.is_panic(effective_address)
+                || self.jolt_device.is_termination(effective_address))
+            {
+                self.fault(
+                    effective_address,
+                    MemoryAccessKind::Store,
+                    FaultCause::UnmappedAddress,
+                    format!("Unknown memory mapping: 0x{:X}", effective_address),
+                );
+            }
+        } else if !self.memory.validate_address(effective_address) {
             // greater then memory capacity
-            assert!(
-                self.memory.validate_address(effective_address),
-                "Heap overflow: Attempted to write to 0x{:X}",
-                effective_address
+            self.fault(
+                effective_address,
+                MemoryAccessKind::Store,
+                FaultCause::OutOfMemory,
+                format!(
+                    "Heap overflow: Attempted to write to 0x{:X}",
+                    effective_address
+                ),
             );
         }
     }
 
+    /// Faults if `effective_address` lies in one of the guard regions set by
+    /// `set_guard_regions`.
+    #[inline]
+    fn check_guard_regions(&self, effective_address: u64, kind: MemoryAccessKind) {
+        let in_guard = |start: Option<u64>| {
+            start.is_some_and(|start| {
+                (start..start + GUARD_REGION_SIZE).contains(&effective_address)
+            })
+        };
+        if in_guard(self.stack_guard) {
+            self.fault(
+                effective_address,
+                kind,
+                FaultCause::StackOverflow,
+                format!(
+                    "Stack overflow: Attempted to access 0x{:X} in the stack's guard region",
+                    effective_address
+                ),
+            );
+        }
+        if in_guard(self.heap_guard) {
+            self.fault(
+                effective_address,
+                kind,
+                FaultCause::OutOfMemory,
+                format!(
+                    "Out of memory: Attempted to access 0x{:X} in the heap's guard region",
+                    effective_address
+                ),
+            );
+        }
+    }
+
+    /// Records an access outside of the guest's memory layout in the tracer (see
+    /// `GuestFault`), then panics with `message`.
+    fn fault(
+        &self,
+        effective_address: u64,
+        kind: MemoryAccessKind,
+        cause: FaultCause,
+        message: String,
+    ) -> ! {
+        self.tracer
+            .record_fault(effective_address, kind, cause, message.clone());
+        panic!("{}", message);
+    }
+
     /// Fetches an instruction byte. This method takes virtual address
     /// and translates into physical address inside.
     ///
@@ -392,6 +508,10 @@ impl Mmu {
     /// * `v_address` Virtual address
     pub fn load_word(&mut self, v_address: u64) -> Result<u32, Trap> {
         let effective_address = self.get_effective_address(v_address);
+        if effective_address % 4 != 0 && self.misaligned_accesses {
+            let value = self.trace_load_misaligned(effective_address);
+            return Ok((value >> ((effective_address % 4) * 8)) as u32);
+        }
         assert!(effective_address % 4 == 0, "Unaligned load_word");
         self.trace_load(effective_address);
         match self.load_bytes(v_address, 4) {
@@ -501,6 +621,13 @@ impl Mmu {
     /// * `value` data written
     pub fn store_word(&mut self, v_address: u64, value: u32) -> Result<(), Trap> {
         let effective_address = self.get_effective_address(v_address);
+        if effective_address % 4 != 0 && self.misaligned_accesses {
+            let word_address = (effective_address >> 2) << 2;
+            let post_value = self.trace_store_misaligned(effective_address, value as u64);
+            // Both words are aligned, so these take the fast path, which is not traced
+            self.store_bytes(word_address, post_value & 0xffffffff, 4)?;
+            return self.store_bytes(word_address + 4, post_value >> 32, 4);
+        }
         assert!(effective_address % 4 == 0, "Unaligned store_word");
         self.trace_store(effective_address, value as u64);
         self.store_bytes(v_address, value as u64, 4)
@@ -539,10 +666,18 @@ impl Mmu {
                 0x10000000..=0x100000ff => self.uart.load(effective_address),
                 0x10001000..=0x10001FFF => self.disk.load(effective_address),
                 _ => {
-                    if self.jolt_device.is_input(effective_address) {
-                        self.jolt_device.load(effective_address)
+                    if self.jolt_device.is_input(effective_address)
+                        || self.jolt_device.is_inputs_digest(effective_address)
+                        || self.jolt_device.is_advice(effective_address)
+                    {
+                        self.load_device(effective_address)
                     } else {
-                        panic!("Unknown memory mapping {:X}.", effective_address);
+                        self.fault(
+                            effective_address,
+                            MemoryAccessKind::Load,
+                            FaultCause::UnmappedAddress,
+                            format!("Unknown memory mapping {:X}.", effective_address),
+                        );
                     }
                 }
             },
@@ -552,35 +687,65 @@ impl Mmu {
     /// Records the memory word being accessed by a load instruction. The memory
     /// state is used in Jolt to construct the witnesses in `read_write_memory.rs`.
     fn trace_load(&mut self, effective_address: u64) {
+        let (address, value) = self.load_traced_word(effective_address);
+        self.tracer
+            .push_memory(MemoryState::Read { address, value });
+    }
+
+    /// Records the two memory words spanned by a misaligned word load, packed into a
+    /// doubleword, and returns the doubleword.
+    fn trace_load_misaligned(&mut self, effective_address: u64) -> u64 {
+        let (address, lo) = self.load_traced_word(effective_address);
+        let (_, hi) = self.load_traced_word(address + 4);
+        let value = (lo & 0xffffffff) | (hi << 32);
+        self.tracer
+            .push_memory(MemoryState::Read { address, value });
+        value
+    }
+
+    /// Returns the address and value of the memory word containing `effective_address`,
+    /// faulting if it cannot be loaded from.
+    fn load_traced_word(&mut self, effective_address: u64) -> (u64, u64) {
+        self.check_guard_regions(effective_address, MemoryAccessKind::Load);
         let word_address = (effective_address >> 2) << 2;
         let bytes = match self.xlen {
             Xlen::Bit32 => 4,
             Xlen::Bit64 => 8,
         };
         if word_address < DRAM_BASE {
-            if self.jolt_device.is_input(word_address) {
-                let mut value_bytes = [0u8; 8];
-                for i in 0..bytes {
-                    value_bytes[i as usize] = self.jolt_device.load(word_address + i);
-                }
-                let value = u64::from_le_bytes(value_bytes);
-                self.tracer.push_memory(MemoryState::Read {
-                    address: word_address,
-                    value,
-                });
-            } else {
-                panic!("Unknown memory mapping {:X}.", word_address);
+            if !(self.jolt_device.is_input(word_address)
+                || self.jolt_device.is_inputs_digest(word_address)
+                || self.jolt_device.is_advice(word_address))
+            {
+                self.fault(
+                    effective_address,
+                    MemoryAccessKind::Load,
+                    FaultCause::UnmappedAddress,
+                    format!("Unknown memory mapping {:X}.", word_address),
+                );
+            }
+            let mut value_bytes = [0u8; 8];
+            for i in 0..bytes {
+                value_bytes[i as usize] = self.load_device(word_address + i);
             }
+            (word_address, u64::from_le_bytes(value_bytes))
         } else {
+            if !self.memory.validate_address(word_address) {
+                self.fault(
+                    effective_address,
+                    MemoryAccessKind::Load,
+                    FaultCause::OutOfMemory,
+                    format!(
+                        "Heap overflow: Attempted to read from 0x{:X}",
+                        effective_address
+                    ),
+                );
+            }
             let mut value_bytes = [0u8; 8];
             for i in 0..bytes {
                 value_bytes[i as usize] = self.memory.read_byte(word_address + i);
             }
-            let value = u64::from_le_bytes(value_bytes);
-            self.tracer.push_memory(MemoryState::Read {
-                address: word_address,
-                value,
-            });
+            (word_address, u64::from_le_bytes(value_bytes))
         }
     }
 
@@ -598,7 +763,7 @@ impl Mmu {
         let pre_value = if effective_address < DRAM_BASE {
             let mut pre_value_bytes = [0u8; 8];
             for i in 0..bytes {
-                pre_value_bytes[i as usize] = self.jolt_device.load(word_address + i);
+                pre_value_bytes[i as usize] = self.load_device(word_address + i);
             }
             u64::from_le_bytes(pre_value_bytes)
         } else {
@@ -639,7 +804,7 @@ impl Mmu {
         let pre_value = if effective_address < DRAM_BASE {
             let mut pre_value_bytes = [0u8; 8];
             for i in 0..bytes {
-                pre_value_bytes[i as usize] = self.jolt_device.load(word_address + i);
+                pre_value_bytes[i as usize] = self.load_device(word_address + i);
             }
             u64::from_le_bytes(pre_value_bytes)
         } else {
@@ -679,7 +844,7 @@ impl Mmu {
         if effective_address < DRAM_BASE {
             let mut pre_value_bytes = [0u8; 8];
             for i in 0..bytes {
-                pre_value_bytes[i as usize] = self.jolt_device.load(effective_address + i);
+                pre_value_bytes[i as usize] = self.load_device(effective_address + i);
             }
             let pre_value = u64::from_le_bytes(pre_value_bytes);
 
@@ -702,6 +867,106 @@ impl Mmu {
         }
     }
 
+    /// Records the state of the two memory words spanned by a misaligned word store
+    /// before and after it, packed into doublewords, and returns the doubleword after
+    /// the store.
+    fn trace_store_misaligned(&mut self, effective_address: u64, value: u64) -> u64 {
+        let word_address = (effective_address >> 2) << 2;
+        self.assert_effective_address(effective_address);
+        self.assert_effective_address(word_address + 4);
+
+        let pre_value = self.peek_word(word_address) | (self.peek_word(word_address + 4) << 32);
+        let shift = (effective_address % 4) * 8;
+        let post_value = (pre_value & !(0xffffffff << shift)) | (value << shift);
+
+        self.tracer.push_memory(MemoryState::Write {
+            address: word_address,
+            pre_value,
+            post_value,
+        });
+        post_value
+    }
+
+    /// Sets the program I/O device, whose inputs must not change afterwards.
+    pub fn set_jolt_device(&mut self, jolt_device: JoltDevice) {
+        self.inputs_digest = jolt_device.inputs_digest();
+        self.jolt_device = jolt_device;
+    }
+
+    /// Sets the function supplying the initial contents of the advice tape past those
+    /// already in `jolt_device`: the first time the guest accesses each of the
+    /// remaining words, `mmio_handler` is called with the word's offset in the tape,
+    /// and the word it returns becomes part of the tape's initial contents, which the
+    /// prover commits to. The guest thus reads data the host produces on demand with
+    /// ordinary loads. The word is fixed by its first access even if that is a store,
+    /// so that every access to it is consistent with the committed contents.
+    pub fn set_mmio_handler(&mut self, mmio_handler: MmioHandler) {
+        let upfront_len = self.jolt_device.advice_tape.len() as u64;
+        self.mmio_start =
+            self.jolt_device.memory_layout.advice_start + upfront_len.next_multiple_of(4);
+        self.mmio_handler = Some(mmio_handler);
+    }
+
+    /// Calls the MMIO handler for the word of the advice tape containing `address`,
+    /// if it has not supplied it yet, faulting if it has no word at that offset.
+    fn service_mmio(&mut self, address: u64) {
+        let Some(mmio_handler) = self.mmio_handler.clone() else {
+            return;
+        };
+        let word_address = (address >> 2) << 2;
+        if word_address < self.mmio_start
+            || !self.jolt_device.is_advice(word_address)
+            || !self.mmio_words.insert(word_address)
+        {
+            return;
+        }
+        let offset = word_address - self.jolt_device.memory_layout.advice_start;
+        let Some(word) = mmio_handler(offset) else {
+            self.fault(
+                address,
+                MemoryAccessKind::Load,
+                FaultCause::UnmappedAddress,
+                format!(
+                    "MMIO handler has no word at offset 0x{:X} of the advice tape",
+                    offset
+                ),
+            );
+        };
+        let offset = offset as usize;
+        let tape = &mut self.jolt_device.advice_tape;
+        if tape.len() < offset + 4 {
+            tape.resize(offset + 4, 0);
+        }
+        tape[offset..offset + 4].copy_from_slice(&word.to_le_bytes());
+    }
+
+    /// Reads a byte below `DRAM_BASE`, i.e. of the program I/O or the advice tape.
+    fn load_device(&mut self, address: u64) -> u8 {
+        if self.jolt_device.is_inputs_digest(address) {
+            let index = address - self.jolt_device.memory_layout.inputs_digest;
+            return self.inputs_digest[index as usize];
+        }
+        self.service_mmio(address);
+        match self.advice_writes.get(&address) {
+            Some(value) => *value,
+            None => self.jolt_device.load(address),
+        }
+    }
+
+    /// Reads the (32-bit) word at `word_address` without recording the access.
+    fn peek_word(&mut self, word_address: u64) -> u64 {
+        let mut value_bytes = [0u8; 4];
+        for (i, byte) in value_bytes.iter_mut().enumerate() {
+            let address = word_address + i as u64;
+            *byte = if address < DRAM_BASE {
+                self.load_device(address)
+            } else {
+                self.memory.read_byte(address)
+            };
+        }
+        u32::from_le_bytes(value_bytes) as u64
+    }
+
     /// Loads two bytes from main memory or peripheral devices depending on
     /// physical address.
     ///
@@ -768,6 +1033,14 @@ impl Mmu {
         }
     }
 
+    /// Reads `len` bytes starting at physical address `p_address`, without
+    /// tracing the access.
+    pub fn read_bytes_raw(&mut self, p_address: u64, len: usize) -> Vec<u8> {
+        (0..len as u64)
+            .map(|i| self.load_raw(p_address.wrapping_add(i)))
+            .collect()
+    }
+
     /// Stores a byte to main memory or peripheral devices depending on
     /// physical address.
     ///
@@ -786,7 +1059,11 @@ impl Mmu {
                 0x10001000..=0x10001FFF => self.disk.store(effective_address, value),
                 _ => {
                     self.assert_effective_address(effective_address);
-                    self.jolt_device.store(effective_address, value);
+                    if self.jolt_device.is_advice(effective_address) {
+                        self.advice_writes.insert(effective_address, value);
+                    } else {
+                        self.jolt_device.store(effective_address, value);
+                    }
                 }
             },
         };
@@ -1260,7 +1537,10 @@ impl MemoryWrapper {
 mod test_mmu {
     use super::*;
     use crate::emulator::terminal::DummyTerminal;
+    use common::rv_trace::{ELFInstruction, MemoryLayout, RV32IM};
     use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     const MEM_CAPACITY: u64 = 1024 * 1024;
 
@@ -1294,6 +1574,97 @@ mod test_mmu {
         mmu.trace_store(invalid_address, 0xc50513);
     }
 
+    #[test]
+    fn test_guard_regions() {
+        let terminal = Box::new(DummyTerminal::new());
+        let tracer = Rc::new(Tracer::new());
+        let mut mmu = Mmu::new(Xlen::Bit64, terminal, tracer.clone());
+        mmu.init_memory(MEM_CAPACITY);
+        let stack_guard = DRAM_BASE + 0x1000;
+        let heap_guard = DRAM_BASE + MEM_CAPACITY - GUARD_REGION_SIZE;
+        mmu.set_guard_regions(stack_guard, heap_guard);
+
+        // Accesses just outside of the guard regions are fine
+        mmu.trace_store(stack_guard + GUARD_REGION_SIZE, 1);
+        mmu.trace_load(heap_guard - 4);
+
+        for (address, cause) in [
+            (
+                stack_guard + GUARD_REGION_SIZE - 4,
+                FaultCause::StackOverflow,
+            ),
+            (heap_guard, FaultCause::OutOfMemory),
+        ] {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                mmu.trace_store(address, 1)
+            }));
+            assert!(result.is_err());
+            let fault = tracer.fault.take().unwrap();
+            assert_eq!(fault.address, address);
+            assert_eq!(fault.cause, cause);
+        }
+    }
+
+    #[test]
+    fn test_misaligned_accesses() {
+        let terminal = Box::new(DummyTerminal::new());
+        let tracer = Rc::new(Tracer::new());
+        let mut mmu = Mmu::new(Xlen::Bit32, terminal, tracer.clone());
+        mmu.init_memory(MEM_CAPACITY);
+        mmu.set_misaligned_accesses(true);
+
+        let word_address = DRAM_BASE + 0x100;
+        mmu.store_word(word_address, 0x44332211).unwrap();
+        mmu.store_word(word_address + 4, 0x88776655).unwrap();
+
+        let last_memory_state = || {
+            let rows = tracer.rows.borrow();
+            rows.last().unwrap().memory_state.clone().unwrap()
+        };
+        let start_instruction = |opcode| {
+            tracer.start_instruction(ELFInstruction {
+                address: 0,
+                opcode,
+                rs1: None,
+                rs2: None,
+                rd: None,
+                imm: None,
+                virtual_sequence_remaining: None,
+                compressed: false,
+            })
+        };
+
+        start_instruction(RV32IM::LW_MISALIGNED);
+        assert_eq!(mmu.load_word(word_address + 3).unwrap(), 0x77665544);
+        assert_eq!(
+            last_memory_state(),
+            MemoryState::Read {
+                address: word_address,
+                value: 0x8877665544332211,
+            }
+        );
+
+        start_instruction(RV32IM::SW_MISALIGNED);
+        mmu.store_word(word_address + 1, 0xddccbbaa).unwrap();
+        assert_eq!(
+            last_memory_state(),
+            MemoryState::Write {
+                address: word_address,
+                pre_value: 0x8877665544332211,
+                post_value: 0x887766ddccbbaa11,
+            }
+        );
+        assert_eq!(mmu.load_word(word_address).unwrap(), 0xccbbaa11);
+        assert_eq!(mmu.load_word(word_address + 4).unwrap(), 0x887766dd);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unaligned load_word")]
+    fn test_misaligned_accesses_disabled() {
+        let mut mmu = setup_mmu(MEM_CAPACITY);
+        let _ = mmu.load_word(DRAM_BASE + 2);
+    }
+
     #[test]
     #[should_panic(expected = "Unknown memory mapping")]
     fn test_unknown_memory_mapping() {
@@ -1302,4 +1673,152 @@ mod test_mmu {
         let invalid_address = 1234;
         mmu.trace_store(invalid_address, 0xc50513);
     }
+
+    #[test]
+    fn test_inputs_digest_load() {
+        let mut mmu = setup_mmu(MEM_CAPACITY);
+        let mut jolt_device = JoltDevice::new(64, 64);
+        jolt_device.inputs = vec![1, 2, 3, 4, 5];
+        mmu.set_jolt_device(jolt_device);
+
+        let expected = mmu.jolt_device.inputs_digest();
+        let digest_start = mmu.jolt_device.memory_layout.inputs_digest;
+        let digest: Vec<u8> = (0..expected.len() as u64)
+            .map(|i| mmu.load_raw(digest_start + i))
+            .collect();
+        assert_eq!(digest, expected);
+    }
+
+    #[test]
+    fn test_advice_tape() {
+        let mut mmu = setup_mmu(MEM_CAPACITY);
+        mmu.jolt_device.memory_layout = MemoryLayout::new(64, 64).with_max_advice_size(16);
+        mmu.jolt_device.advice_tape = vec![1, 2, 3, 4, 5];
+
+        let advice_start = mmu.jolt_device.memory_layout.advice_start;
+        assert_eq!(mmu.load_raw(advice_start + 4), 5);
+        assert_eq!(mmu.load_raw(advice_start + 12), 0);
+
+        mmu.store_raw(advice_start + 1, 9);
+        assert_eq!(mmu.load_raw(advice_start + 1), 9);
+        // The initial contents are left as they were, for the prover to commit to
+        assert_eq!(mmu.jolt_device.advice_tape, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_mmio_handler() {
+        let mut mmu = setup_mmu(MEM_CAPACITY);
+        mmu.jolt_device.memory_layout = MemoryLayout::new(64, 64).with_max_advice_size(16);
+        mmu.jolt_device.advice_tape = vec![1, 2, 3, 4, 5];
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        // Answers with the offset of the word, in every byte
+        mmu.set_mmio_handler(Arc::new(move |offset| {
+            handler_calls.fetch_add(1, Ordering::SeqCst);
+            Some(u32::from_le_bytes([offset as u8; 4]))
+        }));
+
+        let advice_start = mmu.jolt_device.memory_layout.advice_start;
+        // The words holding the upfront contents are not serviced
+        assert_eq!(mmu.load_raw(advice_start + 4), 5);
+        assert_eq!(mmu.load_raw(advice_start + 5), 0);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert_eq!(mmu.load_raw(advice_start + 9), 8);
+        assert_eq!(mmu.load_raw(advice_start + 8), 8);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        // A store fixes the word's initial contents before overwriting them
+        mmu.trace_store_byte(advice_start + 12, 9);
+        mmu.store_raw(advice_start + 12, 9);
+        assert_eq!(mmu.load_raw(advice_start + 12), 9);
+        assert_eq!(mmu.load_raw(advice_start + 13), 12);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            mmu.jolt_device.advice_tape,
+            vec![1, 2, 3, 4, 5, 0, 0, 0, 8, 8, 8, 8, 12, 12, 12, 12]
+        );
+    }
+
+    #[test]
+    fn test_mmio_handler_fault() {
+        let terminal = Box::new(DummyTerminal::new());
+        let tracer = Rc::new(Tracer::new());
+        let mut mmu = Mmu::new(Xlen::Bit32, terminal, tracer.clone());
+        mmu.init_memory(MEM_CAPACITY);
+        mmu.jolt_device.memory_layout = MemoryLayout::new(64, 64).with_max_advice_size(16);
+        mmu.set_mmio_handler(Arc::new(|offset| (offset < 8).then_some(0)));
+
+        let advice_start = mmu.jolt_device.memory_layout.advice_start;
+        assert_eq!(mmu.load_word(advice_start + 4).unwrap(), 0);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            mmu.load_word(advice_start + 8)
+        }));
+        assert!(result.is_err());
+
+        let fault = tracer.fault.take().unwrap();
+        assert_eq!(fault.address, advice_start + 8);
+        assert_eq!(fault.cause, FaultCause::UnmappedAddress);
+        assert!(fault.message.starts_with("MMIO handler has no word"));
+    }
+
+    #[test]
+    fn test_log_store() {
+        let mut mmu = setup_mmu(MEM_CAPACITY);
+        mmu.jolt_device = JoltDevice::new(64, 64);
+
+        let log_start = mmu.jolt_device.memory_layout.log_start;
+        for (i, byte) in b"hello\nworld\n".iter().enumerate() {
+            mmu.store_raw(log_start + i as u64, *byte);
+        }
+        assert_eq!(mmu.jolt_device.log_lines(), vec!["hello", "world"]);
+        assert!(mmu.jolt_device.outputs.is_empty());
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let mut mmu = setup_mmu(MEM_CAPACITY);
+        mmu.jolt_device = JoltDevice::new(64, 64);
+
+        let log_start = mmu.jolt_device.memory_layout.log_start;
+        let logs = b"starting\npanicked at src/lib.rs:4:5:\nout of range\n";
+        for (i, byte) in logs.iter().enumerate() {
+            mmu.store_raw(log_start + i as u64, *byte);
+        }
+        assert_eq!(mmu.jolt_device.panic_message(), None);
+
+        mmu.store_raw(mmu.jolt_device.memory_layout.panic, 1);
+        assert_eq!(
+            mmu.jolt_device.panic_message().unwrap(),
+            "panicked at src/lib.rs:4:5:\nout of range"
+        );
+    }
+
+    #[test]
+    fn test_fault_recorded() {
+        let terminal = Box::new(DummyTerminal::new());
+        let tracer = Rc::new(Tracer::new());
+        let mut mmu = Mmu::new(Xlen::Bit64, terminal, tracer.clone());
+        mmu.init_memory(MEM_CAPACITY);
+
+        mmu.trace_store(DRAM_BASE, 0xc50513);
+        let overflow_address = DRAM_BASE + MEM_CAPACITY + 8;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            mmu.trace_load(overflow_address)
+        }));
+        assert!(result.is_err());
+
+        let fault = tracer.fault.take().unwrap();
+        assert_eq!(fault.address, overflow_address);
+        assert_eq!(fault.kind, MemoryAccessKind::Load);
+        assert_eq!(fault.cause, FaultCause::OutOfMemory);
+        assert!(fault.message.starts_with("Heap overflow"));
+        assert_eq!(
+            fault.last_accesses(1)[0].state,
+            MemoryState::Write {
+                address: DRAM_BASE,
+                pre_value: 0,
+                post_value: 0xc50513,
+            }
+        );
+    }
 }
@@ -0,0 +1,119 @@
+use std::fmt;
+
+use common::rv_trace::MemoryState;
+
+/// Number of memory accesses kept by the tracer for `GuestFault::recent_accesses`.
+pub const MEMORY_ACCESS_HISTORY: usize = 64;
+
+/// A load or store executed by the guest.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryAccess {
+    /// Address of the instruction that made the access.
+    pub pc: u64,
+    pub state: MemoryState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryAccessKind {
+    Load,
+    Store,
+}
+
+/// Why an access faulted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultCause {
+    /// The stack grew into the guard region below it (see `GUARD_REGION_SIZE`).
+    StackOverflow,
+    /// The heap was exhausted: the guest accessed the guard region above it, or memory
+    /// beyond the end of the guest's memory.
+    OutOfMemory,
+    /// The guest accessed an address that is not mapped.
+    UnmappedAddress,
+}
+
+/// The guest accessed memory outside of its memory layout, e.g. by overflowing its
+/// stack or heap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GuestFault {
+    /// Address of the faulting instruction.
+    pub pc: u64,
+    /// The address the instruction attempted to access.
+    pub address: u64,
+    pub kind: MemoryAccessKind,
+    pub cause: FaultCause,
+    pub message: String,
+    /// The last (up to `MEMORY_ACCESS_HISTORY`) accesses made before the fault, oldest
+    /// first.
+    pub recent_accesses: Vec<MemoryAccess>,
+}
+
+impl GuestFault {
+    /// The last `n` accesses made before the fault, oldest first.
+    pub fn last_accesses(&self, n: usize) -> &[MemoryAccess] {
+        &self.recent_accesses[self.recent_accesses.len().saturating_sub(n)..]
+    }
+}
+
+impl fmt::Display for MemoryAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.state {
+            MemoryState::Read { address, value } => write!(
+                f,
+                "pc 0x{:08x}: load  0x{:08x} -> 0x{:x}",
+                self.pc, address, value
+            ),
+            MemoryState::Write {
+                address,
+                pre_value,
+                post_value,
+            } => write!(
+                f,
+                "pc 0x{:08x}: store 0x{:08x} <- 0x{:x} (was 0x{:x})",
+                self.pc, address, post_value, pre_value
+            ),
+        }
+    }
+}
+
+impl fmt::Display for GuestFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let access = match self.kind {
+            MemoryAccessKind::Load => "load from",
+            MemoryAccessKind::Store => "store to",
+        };
+        write!(
+            f,
+            "Guest fault: {} (invalid {} 0x{:x} at pc 0x{:x})",
+            self.message, access, self.address, self.pc
+        )?;
+        if !self.recent_accesses.is_empty() {
+            write!(f, "\nRecent memory accesses (oldest first):")?;
+            for access in &self.recent_accesses {
+                write!(f, "\n  {}", access)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GuestFault {}
+
+/// The guest's ELF file could not be decoded; see `try_decode`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodeError {
+    pub message: String,
+}
+
+impl DecodeError {
+    pub(crate) fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to decode guest ELF: {}", self.message)
+    }
+}
+
+impl std::error::Error for DecodeError {}
@@ -1,7 +1,13 @@
 #![allow(dead_code)]
 #![allow(clippy::legacy_numeric_constants)]
 
-use std::{fs::File, io::Read, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+};
 
 use common::{self, constants::RAM_START_ADDRESS};
 use emulator::{
@@ -10,88 +16,400 @@ use emulator::{
     Emulator,
 };
 
-use object::{Object, ObjectSection, SectionKind};
+use object::{Object, ObjectSection, ObjectSymbol, SectionKind, SymbolKind};
 
 mod decode;
 mod emulator;
+mod fault;
 mod trace;
 
 pub use common::rv_trace::{
-    ELFInstruction, JoltDevice, MemoryState, RVTraceRow, RegisterState, RV32IM,
+    CycleMarker, ELFInstruction, FunctionSymbol, JoltDevice, MemoryLayout, MemoryState, RVTraceRow,
+    RegisterState, RV32IM,
+};
+pub use emulator::cpu::{HintProvider, MmioHandler};
+pub use fault::{
+    DecodeError, FaultCause, GuestFault, MemoryAccess, MemoryAccessKind, MEMORY_ACCESS_HISTORY,
 };
 
 use crate::decode::decode_raw;
 
-#[tracing::instrument(skip_all)]
+/// What a guest is run with besides its ELF: its inputs, the advice the prover supplies
+/// and its memory layout.
+#[derive(Clone)]
+pub struct TraceOptions<'a> {
+    pub inputs: &'a [u8],
+    pub private_inputs: &'a [u8],
+    pub input_stream: &'a [u8],
+    /// Initial contents of the advice tape (see `MemoryLayout::advice_start`).
+    pub advice_tape: &'a [u8],
+    pub hint_provider: Option<HintProvider>,
+    /// Serves the advice tape's words past `advice_tape` (see `Cpu::set_mmio_handler`).
+    pub mmio_handler: Option<MmioHandler>,
+    pub memory_layout: MemoryLayout,
+}
+
+/// Runs the guest and returns its execution trace. Fails if the guest accesses memory
+/// outside of its memory layout.
 pub fn trace(
     elf: &PathBuf,
-    inputs: &[u8],
-    input_size: u64,
-    output_size: u64,
-) -> (Vec<RVTraceRow>, JoltDevice) {
+    options: TraceOptions,
+) -> Result<(Vec<RVTraceRow>, JoltDevice), GuestFault> {
+    let (rows, device, _) = trace_with_cycle_markers(elf, options)?;
+    Ok((rows, device))
+}
+
+/// Like `trace`, but also returns the cycle-tracker markers executed by the guest,
+/// in execution order.
+pub fn trace_with_cycle_markers(
+    elf: &PathBuf,
+    options: TraceOptions,
+) -> Result<(Vec<RVTraceRow>, JoltDevice, Vec<CycleMarker>), GuestFault> {
+    let mut rows = Vec::new();
+    let (device, cycle_markers) = trace_chunked(elf, options, usize::MAX, |mut chunk| {
+        rows.append(&mut chunk)
+    })?;
+    Ok((rows, device, cycle_markers))
+}
+
+/// Like `trace_with_cycle_markers`, but passes the trace to `sink` in chunks of (at
+/// most) `chunk_size` rows as it is generated, rather than holding all of it in memory.
+#[tracing::instrument(skip_all)]
+pub fn trace_chunked(
+    elf: &PathBuf,
+    options: TraceOptions,
+    chunk_size: usize,
+    mut sink: impl FnMut(Vec<RVTraceRow>),
+) -> Result<(JoltDevice, Vec<CycleMarker>), GuestFault> {
+    let mut emulator = setup_emulator(elf, options);
+
+    run(&mut emulator, |emulator| {
+        let tracer = &emulator.get_cpu().tracer;
+        if tracer.rows.try_borrow().unwrap().len() >= chunk_size {
+            sink(tracer.drain_rows());
+        }
+    })?;
+
+    let rows = emulator.get_cpu().tracer.drain_rows();
+    if !rows.is_empty() {
+        sink(rows);
+    }
+
+    let cycle_markers = emulator.get_mut_cpu().tracer.cycle_markers.take();
+    let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+
+    Ok((device, cycle_markers))
+}
+
+/// Like `trace`, but returns an iterator over the trace, which runs the guest as it is
+/// iterated over. Rows can thus be processed (and dropped) as they are generated.
+pub fn trace_iter(elf: &PathBuf, options: TraceOptions) -> TraceIter {
+    let emulator = setup_emulator(elf, options);
+    TraceIter {
+        emulator,
+        rows: Vec::new().into_iter(),
+        prev_pc: 0,
+        terminated: false,
+        fault: None,
+    }
+}
+
+/// Number of instructions `TraceIter` executes at a time.
+const TRACE_ITER_BATCH_SIZE: usize = 1 << 12;
+
+/// The execution trace of a guest, generated as it is iterated over (see `trace_iter`).
+/// Iteration ends when the guest terminates, or early if it faults; `finish` then
+/// returns the guest's I/O.
+pub struct TraceIter {
+    emulator: Emulator,
+    rows: std::vec::IntoIter<RVTraceRow>,
+    prev_pc: u64,
+    terminated: bool,
+    fault: Option<GuestFault>,
+}
+
+impl TraceIter {
+    /// Runs the guest to completion, discarding the rows not yet iterated over, and
+    /// returns its I/O device and the cycle-tracker markers it executed.
+    pub fn finish(mut self) -> Result<(JoltDevice, Vec<CycleMarker>), GuestFault> {
+        while !self.terminated {
+            self.refill();
+        }
+        if let Some(fault) = self.fault {
+            return Err(fault);
+        }
+
+        let cycle_markers = self.emulator.get_mut_cpu().tracer.cycle_markers.take();
+        let device = self
+            .emulator
+            .get_mut_cpu()
+            .get_mut_mmu()
+            .jolt_device
+            .clone();
+        Ok((device, cycle_markers))
+    }
+
+    fn refill(&mut self) {
+        let result = run_until(&mut self.emulator, &mut self.prev_pc, |emulator| {
+            emulator.get_cpu().tracer.rows.try_borrow().unwrap().len() < TRACE_ITER_BATCH_SIZE
+        });
+        match result {
+            Ok(terminated) => self.terminated = terminated,
+            Err(fault) => {
+                self.terminated = true;
+                self.fault = Some(fault);
+            }
+        }
+        self.rows = self.emulator.get_cpu().tracer.drain_rows().into_iter();
+    }
+}
+
+impl Iterator for TraceIter {
+    type Item = RVTraceRow;
+
+    fn next(&mut self) -> Option<RVTraceRow> {
+        loop {
+            if let Some(row) = self.rows.next() {
+                return Some(row);
+            }
+            if self.terminated {
+                return None;
+            }
+            self.refill();
+        }
+    }
+}
+
+/// Runs the guest without recording a trace, returning the resulting I/O device and
+/// the number of times each instruction was executed. Much faster than `trace`.
+#[tracing::instrument(skip_all)]
+pub fn execute(
+    elf: &PathBuf,
+    options: TraceOptions,
+) -> Result<(JoltDevice, HashMap<RV32IM, usize>), GuestFault> {
+    let mut emulator = setup_emulator(elf, options);
+    emulator.get_cpu().tracer.count_only();
+
+    run(&mut emulator, |_| {})?;
+
+    let opcode_counts = emulator.get_mut_cpu().tracer.opcode_counts.take();
+    let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+
+    Ok((device, opcode_counts))
+}
+
+fn setup_emulator(elf: &PathBuf, options: TraceOptions) -> Emulator {
     let term = DefaultTerminal::new();
     let mut emulator = Emulator::new(Box::new(term));
     emulator.update_xlen(get_xlen());
 
-    let mut jolt_device = JoltDevice::new(input_size, output_size);
-    jolt_device.inputs = inputs.to_vec();
-    emulator.get_mut_cpu().get_mut_mmu().jolt_device = jolt_device;
+    let memory_layout = options.memory_layout;
+    let mut jolt_device =
+        JoltDevice::new(memory_layout.max_input_size, memory_layout.max_output_size);
+    jolt_device.memory_layout = memory_layout;
+    jolt_device.inputs = options.inputs.to_vec();
+    jolt_device.advice_tape = options.advice_tape.to_vec();
+    emulator
+        .get_mut_cpu()
+        .get_mut_mmu()
+        .set_jolt_device(jolt_device);
+    emulator
+        .get_mut_cpu()
+        .set_private_inputs(options.private_inputs);
+    emulator
+        .get_mut_cpu()
+        .set_input_stream(options.input_stream);
+    if let Some(hint_provider) = options.hint_provider {
+        emulator.get_mut_cpu().set_hint_provider(hint_provider);
+    }
+    if let Some(mmio_handler) = options.mmio_handler {
+        emulator.get_mut_cpu().set_mmio_handler(mmio_handler);
+    }
 
     let mut elf_file = File::open(elf).unwrap();
 
     let mut elf_contents = Vec::new();
     elf_file.read_to_end(&mut elf_contents).unwrap();
 
+    if let Some((stack_guard, heap_guard)) = guard_regions(&elf_contents) {
+        emulator
+            .get_mut_cpu()
+            .get_mut_mmu()
+            .set_guard_regions(stack_guard, heap_guard);
+    }
+    if misaligned_accesses(&elf_contents) {
+        emulator.get_mut_cpu().set_misaligned_accesses(true);
+    }
     emulator.setup_program(elf_contents);
+    emulator
+}
+
+/// Start addresses of the guard regions below the stack (`_STACK_GUARD`) and above the
+/// heap (`_HEAP_END`), if `elf` was linked with Jolt's linker script.
+fn guard_regions(elf: &[u8]) -> Option<(u64, u64)> {
+    let obj = object::File::parse(elf).ok()?;
+    let symbol_address = |name: &str| {
+        obj.symbols()
+            .find(|symbol| symbol.name().is_ok_and(|symbol_name| symbol_name == name))
+            .map(|symbol| symbol.address())
+    };
+    Some((
+        symbol_address("_STACK_GUARD")?,
+        symbol_address("_HEAP_END")?,
+    ))
+}
+
+/// Whether `elf` opts into misaligned word loads and stores by defining the
+/// `_JOLT_MISALIGNED_ACCESSES` symbol (see `Program::set_misaligned_accesses`).
+fn misaligned_accesses(elf: &[u8]) -> bool {
+    object::File::parse(elf).is_ok_and(|obj| {
+        obj.symbols().any(|symbol| {
+            symbol
+                .name()
+                .is_ok_and(|name| name == "_JOLT_MISALIGNED_ACCESSES")
+        })
+    })
+}
+
+/// Whether `obj` may contain compressed (RVC) instructions, i.e. was built with the C
+/// extension. Text sections of other ELFs are decoded a word at a time, as before.
+fn compressed_instructions(obj: &object::File) -> bool {
+    matches!(
+        obj.flags(),
+        object::FileFlags::Elf { e_flags, .. } if e_flags & object::elf::EF_RISCV_RVC != 0
+    )
+}
+
+/// The next instruction of `code` and its length in bytes. With `compressed_instructions`,
+/// a 16-bit instruction is expanded to the 32-bit instruction it stands for (see
+/// `Cpu::uncompress`), so that the rest of Jolt only deals with the latter.
+fn next_instruction(code: &[u8], compressed_instructions: bool) -> (u32, usize) {
+    let halfword = u16::from_le_bytes([code[0], code[1]]) as u32;
+    if compressed_instructions && halfword & 0x3 != 0x3 {
+        return (cpu::Cpu::uncompress(halfword, &get_xlen()), 2);
+    }
+    match code.get(..4) {
+        Some(word) => (u32::from_le_bytes(word.try_into().unwrap()), 4),
+        // A 32-bit instruction cut off by the end of the section
+        None => (u32::MAX, code.len()),
+    }
+}
 
-    let mut prev_pc = 0;
-    loop {
+/// Ticks the emulator until the guest terminates, calling `after_tick` after every
+/// instruction. Fails if the guest faults (see `Mmu::fault`); other panics are
+/// propagated.
+fn run(emulator: &mut Emulator, mut after_tick: impl FnMut(&Emulator)) -> Result<(), GuestFault> {
+    run_until(emulator, &mut 0, |emulator| {
+        after_tick(emulator);
+        true
+    })?;
+    Ok(())
+}
+
+/// Like `run`, but also stops (without the guest terminating) once `after_tick` returns
+/// false. `prev_pc` holds the PC of the last instruction executed, to detect
+/// termination across calls. Returns whether the guest terminated.
+fn run_until(
+    emulator: &mut Emulator,
+    prev_pc: &mut u64,
+    mut after_tick: impl FnMut(&Emulator) -> bool,
+) -> Result<bool, GuestFault> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| loop {
         let pc = emulator.get_cpu().read_pc();
         emulator.tick();
 
         // This is a trick to see if the program has terminated by throwing itself
         // into an infinite loop. It seems to be a good heuristic for now but we
         // should eventually migrate to an explicit shutdown signal.
-        if prev_pc == pc {
-            break;
+        if *prev_pc == pc {
+            return true;
         }
 
-        prev_pc = pc;
-    }
+        *prev_pc = pc;
 
-    let mut rows = emulator.get_mut_cpu().tracer.rows.try_borrow_mut().unwrap();
-    let mut output = Vec::new();
-    output.append(&mut rows);
-    drop(rows);
+        if !after_tick(emulator) {
+            return false;
+        }
+    }));
 
-    let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+    result.or_else(|payload| match emulator.get_cpu().tracer.fault.take() {
+        Some(fault) => Err(fault),
+        None => panic::resume_unwind(payload),
+    })
+}
 
-    (output, device)
+/// The functions in the ELF's symbol table, sorted by address. Empty if the ELF was
+/// stripped of its symbols.
+pub fn function_symbols(elf: &[u8]) -> Vec<FunctionSymbol> {
+    let obj = object::File::parse(elf).unwrap();
+    let mut symbols: Vec<_> = obj
+        .symbols()
+        .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.address() != 0)
+        .filter_map(|symbol| {
+            let name = symbol.name().ok()?;
+            Some(FunctionSymbol {
+                name: format!("{:#}", rustc_demangle::demangle(name)),
+                address: symbol.address(),
+                size: symbol.size(),
+            })
+        })
+        .collect();
+    symbols.sort_by_key(|symbol| symbol.address);
+    symbols.dedup_by_key(|symbol| symbol.address);
+    symbols
 }
 
-#[tracing::instrument(skip_all)]
+/// Decodes the instructions and initial memory of `elf`. Panics if `elf` is malformed;
+/// see `try_decode`.
 pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
-    let obj = object::File::parse(elf).unwrap();
+    try_decode(elf).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Like `decode`, but fails if `elf` is not a well-formed ELF file.
+#[tracing::instrument(skip_all)]
+pub fn try_decode(elf: &[u8]) -> Result<(Vec<ELFInstruction>, Vec<(u64, u8)>), DecodeError> {
+    let obj = object::File::parse(elf)
+        .map_err(|err| DecodeError::new(format!("invalid ELF file: {err}")))?;
 
     let sections = obj
         .sections()
         .filter(|s| s.address() >= RAM_START_ADDRESS)
         .collect::<Vec<_>>();
 
+    let misaligned_accesses = misaligned_accesses(elf);
+    let compressed_instructions = compressed_instructions(&obj);
+    let instruction_size = if compressed_instructions { 2 } else { 4 };
     let mut instructions = Vec::new();
     let mut data = Vec::new();
 
     for section in sections {
-        let raw_data = section.data().unwrap();
+        let raw_data = section.data().map_err(|err| {
+            DecodeError::new(format!(
+                "could not read section {}: {err}",
+                section.name().unwrap_or("<unnamed>")
+            ))
+        })?;
 
         if let SectionKind::Text = section.kind() {
-            for (chunk, word) in raw_data.chunks(4).enumerate() {
-                let word = u32::from_le_bytes(word.try_into().unwrap());
-                let address = chunk as u64 * 4 + section.address();
+            if raw_data.len() % instruction_size != 0 {
+                return Err(DecodeError::new(format!(
+                    "text section {} is not a whole number of instructions",
+                    section.name().unwrap_or("<unnamed>")
+                )));
+            }
+            let mut offset = 0;
+            while offset < raw_data.len() {
+                let (word, len) = next_instruction(&raw_data[offset..], compressed_instructions);
+                let address = offset as u64 + section.address();
+                offset += len;
 
                 if let Ok(inst) = decode_raw(word) {
                     if let Some(trace) = inst.trace {
-                        let inst = trace(&inst, &get_xlen(), word, address);
+                        let mut inst = trace(&inst, &get_xlen(), word, address);
+                        if misaligned_accesses {
+                            inst.opcode = inst.opcode.misaligned();
+                        }
+                        inst.compressed = len == 2;
                         instructions.push(inst);
                         continue;
                     }
@@ -105,6 +423,7 @@ pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
                     rd: None,
                     imm: None,
                     virtual_sequence_remaining: None,
+                    compressed: len == 2,
                 });
             }
         }
@@ -114,7 +433,7 @@ pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
         }
     }
 
-    (instructions, data)
+    Ok((instructions, data))
 }
 
 fn get_xlen() -> Xlen {
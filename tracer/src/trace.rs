@@ -1,23 +1,63 @@
 use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 
-use common::rv_trace::{ELFInstruction, MemoryState, RVTraceRow, RegisterState};
+use common::rv_trace::{
+    CycleMarker, ELFInstruction, MemoryState, RVTraceRow, RegisterState, RV32IM,
+};
 
 use crate::emulator::cpu::Xlen;
+use crate::fault::{FaultCause, GuestFault, MemoryAccess, MemoryAccessKind, MEMORY_ACCESS_HISTORY};
 
 pub struct Tracer {
     pub rows: RefCell<Vec<RVTraceRow>>,
+    pub cycle_markers: RefCell<Vec<CycleMarker>>,
     open: RefCell<bool>,
+    /// Number of rows already taken by `drain_rows`, or not recorded.
+    num_drained: RefCell<usize>,
+    /// Whether instructions are recorded as rows, or only counted in `opcode_counts`.
+    record_rows: RefCell<bool>,
+    pub opcode_counts: RefCell<HashMap<RV32IM, usize>>,
+    /// Address of the instruction being executed.
+    pc: RefCell<u64>,
+    /// The last `MEMORY_ACCESS_HISTORY` memory accesses, recorded even when rows are not.
+    recent_accesses: RefCell<VecDeque<MemoryAccess>>,
+    pub fault: RefCell<Option<GuestFault>>,
 }
 
 impl Tracer {
     pub fn new() -> Self {
         Self {
             rows: RefCell::new(Vec::new()),
+            cycle_markers: RefCell::new(Vec::new()),
             open: RefCell::new(false),
+            num_drained: RefCell::new(0),
+            record_rows: RefCell::new(true),
+            opcode_counts: RefCell::new(HashMap::new()),
+            pc: RefCell::new(0),
+            recent_accesses: RefCell::new(VecDeque::with_capacity(MEMORY_ACCESS_HISTORY)),
+            fault: RefCell::new(None),
         }
     }
 
+    /// Stops recording rows: instructions are only counted, by opcode, which makes
+    /// executing the guest much cheaper.
+    pub fn count_only(&self) {
+        *self.record_rows.try_borrow_mut().unwrap() = false;
+    }
+
     pub fn start_instruction(&self, inst: ELFInstruction) {
+        *self.pc.try_borrow_mut().unwrap() = inst.address as u32 as u64;
+        if !*self.record_rows.try_borrow().unwrap() {
+            *self.num_drained.try_borrow_mut().unwrap() += 1;
+            *self
+                .opcode_counts
+                .try_borrow_mut()
+                .unwrap()
+                .entry(inst.opcode)
+                .or_default() += 1;
+            return;
+        }
+
         let mut inst = inst;
         inst.address = inst.address as u32 as u64;
         *self.open.try_borrow_mut().unwrap() = true;
@@ -62,6 +102,16 @@ impl Tracer {
     }
 
     pub fn push_memory(&self, memory_state: MemoryState) {
+        let mut recent_accesses = self.recent_accesses.try_borrow_mut().unwrap();
+        if recent_accesses.len() == MEMORY_ACCESS_HISTORY {
+            recent_accesses.pop_front();
+        }
+        recent_accesses.push_back(MemoryAccess {
+            pc: *self.pc.try_borrow().unwrap(),
+            state: memory_state.clone(),
+        });
+        drop(recent_accesses);
+
         if !*self.open.try_borrow().unwrap() {
             return;
         }
@@ -71,9 +121,77 @@ impl Tracer {
         }
     }
 
+    /// Sets the advice value of the instruction being traced.
+    pub fn set_advice_value(&self, value: u64) {
+        if !*self.open.try_borrow().unwrap() {
+            return;
+        }
+
+        if let Some(row) = self.rows.try_borrow_mut().unwrap().last_mut() {
+            row.advice_value = Some(value);
+        }
+    }
+
+    /// Sets the words read by the instruction being traced beyond its `memory_state`.
+    pub fn set_precompile_input(&self, input: [u32; 16]) {
+        if !*self.open.try_borrow().unwrap() {
+            return;
+        }
+
+        if let Some(row) = self.rows.try_borrow_mut().unwrap().last_mut() {
+            row.precompile_input = Some(input);
+        }
+    }
+
+    /// Records a cycle-tracker marker for the instruction about to be traced.
+    pub fn push_cycle_marker(&self, label: String, start: bool) {
+        let cycle = *self.num_drained.try_borrow().unwrap() + self.rows.try_borrow().unwrap().len();
+        self.cycle_markers
+            .try_borrow_mut()
+            .unwrap()
+            .push(CycleMarker {
+                label,
+                start,
+                cycle,
+            });
+    }
+
+    /// Records that the instruction being executed accessed `address`, which is outside
+    /// of the guest's memory layout.
+    pub fn record_fault(
+        &self,
+        address: u64,
+        kind: MemoryAccessKind,
+        cause: FaultCause,
+        message: String,
+    ) {
+        *self.fault.try_borrow_mut().unwrap() = Some(GuestFault {
+            pc: *self.pc.try_borrow().unwrap(),
+            address,
+            kind,
+            cause,
+            message,
+            recent_accesses: self
+                .recent_accesses
+                .try_borrow()
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect(),
+        });
+    }
+
     pub fn end_instruction(&self) {
         *self.open.try_borrow_mut().unwrap() = false;
     }
+
+    /// Takes the rows traced so far, e.g. to write them to disk. Must be called between
+    /// instructions.
+    pub fn drain_rows(&self) -> Vec<RVTraceRow> {
+        let rows = self.rows.take();
+        *self.num_drained.try_borrow_mut().unwrap() += rows.len();
+        rows
+    }
 }
 
 fn normalize_register_value(value: i64, xlen: &Xlen) -> u64 {